@@ -30,6 +30,31 @@ pub enum AuthMode {
     ChatGPT,
 }
 
+/// Groups of `codex/event/*` notifications that a conversation listener can
+/// subscribe to. A listener that names no categories receives every event.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+pub enum EventCategory {
+    /// Session configuration, task start and completion, aborts, errors and
+    /// warnings.
+    Lifecycle,
+    /// Approval requests, elicitations and questions for the user.
+    Approvals,
+    /// Complete agent and user messages and reasoning.
+    Messages,
+    /// Streaming deltas of messages, reasoning and command output.
+    Deltas,
+    /// Begin and end of commands, tool calls, patches and web searches, plus
+    /// plan updates and turn diffs.
+    Tools,
+    /// `item_started` and `item_completed`.
+    Items,
+    /// Token usage and rate limits.
+    Tokens,
+    /// Everything not covered by another category.
+    Other,
+}
+
 /// Generates an `enum ClientRequest` where each variant is a request that the
 /// client can send to the server. Each variant has associated `params` and
 /// `response` types. Also generates a `export_client_responses()` function to
@@ -121,6 +146,18 @@ client_request_definitions! {
         params: v2::SkillsListParams,
         response: v2::SkillsListResponse,
     },
+    ArtifactList => "artifact/list" {
+        params: v2::ArtifactListParams,
+        response: v2::ArtifactListResponse,
+    },
+    ArtifactSave => "artifact/save" {
+        params: v2::ArtifactSaveParams,
+        response: v2::ArtifactSaveResponse,
+    },
+    FileRead => "file/read" {
+        params: v2::FileReadParams,
+        response: v2::FileReadResponse,
+    },
     TurnStart => "turn/start" {
         params: v2::TurnStartParams,
         response: v2::TurnStartResponse,
@@ -133,6 +170,10 @@ client_request_definitions! {
         params: v2::ReviewStartParams,
         response: v2::ReviewStartResponse,
     },
+    WorkflowRun => "workflow/run" {
+        params: v2::WorkflowRunParams,
+        response: v2::WorkflowRunResponse,
+    },
 
     ModelList => "model/list" {
         params: v2::ModelListParams,
@@ -474,6 +515,21 @@ server_request_definitions! {
         response: v2::FileChangeRequestApprovalResponse,
     },
 
+    /// Sent when the agent asks the user a question via `ask_user`.
+    /// This request is used for Turns started via turn/start.
+    QuestionRequestAnswer => "item/question/requestAnswer" {
+        params: v2::QuestionRequestAnswerParams,
+        response: v2::QuestionRequestAnswerResponse,
+    },
+
+    /// Sent when memories extracted from a finished turn wait for the user
+    /// to pick the ones to save.
+    /// This request is used for Turns started via turn/start.
+    MemoryCandidatesRequestReview => "thread/memories/requestReview" {
+        params: v2::MemoryCandidatesRequestReviewParams,
+        response: v2::MemoryCandidatesRequestReviewResponse,
+    },
+
     /// DEPRECATED APIs below
     /// Request to approve a patch.
     /// This request is used for Turns started via the legacy APIs (i.e. SendUserTurn, SendUserMessage).
@@ -523,6 +579,7 @@ server_notification_definitions! {
     TurnCompleted => "turn/completed" (v2::TurnCompletedNotification),
     TurnDiffUpdated => "turn/diff/updated" (v2::TurnDiffUpdatedNotification),
     TurnPlanUpdated => "turn/plan/updated" (v2::TurnPlanUpdatedNotification),
+    ArtifactRegistered => "artifact/registered" (v2::ArtifactRegisteredNotification),
     ItemStarted => "item/started" (v2::ItemStartedNotification),
     ItemCompleted => "item/completed" (v2::ItemCompletedNotification),
     /// This event is internal-only. Used by Codex Cloud.
@@ -540,6 +597,7 @@ server_notification_definitions! {
     ReasoningTextDelta => "item/reasoning/textDelta" (v2::ReasoningTextDeltaNotification),
     ContextCompacted => "thread/compacted" (v2::ContextCompactedNotification),
     DeprecationNotice => "deprecationNotice" (v2::DeprecationNoticeNotification),
+    Heartbeat => "heartbeat" (v2::HeartbeatNotification),
 
     /// Notifies the user of world-writable directories on Windows, which cannot be protected by the sandbox.
     WindowsWorldWritableWarning => "windows/worldWritableWarning" (v2::WindowsWorldWritableWarningNotification),
@@ -559,6 +617,8 @@ server_notification_definitions! {
 
 client_notification_definitions! {
     Initialized,
+    /// Keeps the connection alive; the server ignores it.
+    Heartbeat,
 }
 
 #[cfg(test)]
@@ -831,4 +891,17 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn serialize_heartbeats() -> Result<()> {
+        assert_eq!(
+            json!({ "method": "heartbeat" }),
+            serde_json::to_value(ClientNotification::Heartbeat)?,
+        );
+        assert_eq!(
+            json!({ "method": "heartbeat", "params": {} }),
+            serde_json::to_value(ServerNotification::Heartbeat(v2::HeartbeatNotification {}))?,
+        );
+        Ok(())
+    }
 }
@@ -58,6 +58,7 @@ impl ThreadHistoryBuilder {
             EventMsg::EnteredReviewMode(_) => {}
             EventMsg::ExitedReviewMode(_) => {}
             EventMsg::UndoCompleted(_) => {}
+            EventMsg::ArtifactRegistered(_) => {}
             EventMsg::TurnAborted(payload) => self.handle_turn_aborted(payload),
             _ => {}
         }
@@ -25,12 +25,18 @@ use uuid::Uuid;
 
 // Reuse shared types defined in `common.rs`.
 use crate::protocol::common::AuthMode;
+use crate::protocol::common::EventCategory;
 use crate::protocol::common::GitSha;
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 pub struct InitializeParams {
     pub client_info: ClientInfo,
+    /// Ask the server to send a `heartbeat` notification this often, so
+    /// clients and proxies can tell a quiet connection from a dead one.
+    #[ts(optional, type = "number")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heartbeat_interval_secs: Option<u64>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default, JsonSchema, TS)]
@@ -364,6 +370,8 @@ pub struct SandboxSettings {
     pub network_access: Option<bool>,
     pub exclude_tmpdir_env_var: Option<bool>,
     pub exclude_slash_tmp: Option<bool>,
+    #[serde(default)]
+    pub allowed_symlink_targets: Vec<AbsolutePathBuf>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -412,6 +420,10 @@ pub struct AddConversationListenerParams {
     pub conversation_id: ConversationId,
     #[serde(default)]
     pub experimental_raw_events: bool,
+    /// Only send events in these categories. Omit to receive every event.
+    /// Approval requests are sent regardless.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_categories: Option<Vec<EventCategory>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 
 use crate::protocol::common::AuthMode;
+use crate::protocol::common::EventCategory;
 use codex_protocol::account::PlanType;
 use codex_protocol::approvals::ExecPolicyAmendment as CoreExecPolicyAmendment;
 use codex_protocol::config_types::ForcedLoginMethod;
@@ -15,6 +16,7 @@ use codex_protocol::openai_models::ReasoningEffort;
 use codex_protocol::parse_command::ParsedCommand as CoreParsedCommand;
 use codex_protocol::plan_tool::PlanItemArg as CorePlanItemArg;
 use codex_protocol::plan_tool::StepStatus as CorePlanStepStatus;
+use codex_protocol::protocol::Artifact as CoreArtifact;
 use codex_protocol::protocol::AskForApproval as CoreAskForApproval;
 use codex_protocol::protocol::CodexErrorInfo as CoreCodexErrorInfo;
 use codex_protocol::protocol::CreditsSnapshot as CoreCreditsSnapshot;
@@ -27,6 +29,8 @@ use codex_protocol::protocol::SkillMetadata as CoreSkillMetadata;
 use codex_protocol::protocol::SkillScope as CoreSkillScope;
 use codex_protocol::protocol::TokenUsage as CoreTokenUsage;
 use codex_protocol::protocol::TokenUsageInfo as CoreTokenUsageInfo;
+use codex_protocol::protocol::TurnDiffFile as CoreTurnDiffFile;
+use codex_protocol::protocol::TurnDiffFileChange as CoreTurnDiffFileChange;
 use codex_protocol::user_input::UserInput as CoreUserInput;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use mcp_types::ContentBlock as McpContentBlock;
@@ -955,6 +959,10 @@ pub struct ThreadStartParams {
     /// (TODO): Figure out a better way to categorize internal / experimental events & protocols.
     #[serde(default)]
     pub experimental_raw_events: bool,
+    /// Only send `codex/event/*` notifications in these categories. Omit to
+    /// receive every event. Approval requests are sent regardless.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_categories: Option<Vec<EventCategory>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -1003,6 +1011,10 @@ pub struct ThreadResumeParams {
     pub config: Option<HashMap<String, serde_json::Value>>,
     pub base_instructions: Option<String>,
     pub developer_instructions: Option<String>,
+    /// Only send `codex/event/*` notifications in these categories. Omit to
+    /// receive every event. Approval requests are sent regardless.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event_categories: Option<Vec<EventCategory>>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -1146,6 +1158,113 @@ impl From<CoreSkillErrorInfo> for SkillErrorInfo {
     }
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ArtifactListParams {
+    pub thread_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ArtifactListResponse {
+    /// Artifacts in the order they were registered.
+    pub data: Vec<Artifact>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ArtifactSaveParams {
+    pub thread_id: String,
+    pub artifact_id: String,
+    /// Absolute path to copy the artifact to. When it names an existing
+    /// directory, the artifact keeps its file name inside it.
+    pub destination: PathBuf,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ArtifactSaveResponse {
+    /// Path the artifact was written to.
+    pub path: PathBuf,
+}
+
+/// Read a file on the machine running the app server, e.g. to show it in a
+/// client on another machine.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileReadParams {
+    /// Loaded thread whose workspace the file must be in: its working
+    /// directory, workspace roots and sandbox writable roots.
+    pub thread_id: String,
+    /// Absolute path on the server.
+    pub path: PathBuf,
+    /// Byte offset to start reading at; defaults to 0.
+    #[ts(type = "number | null")]
+    pub offset: Option<u64>,
+    /// Maximum number of bytes to return; defaults to, and is capped at,
+    /// 1 MiB.
+    #[ts(type = "number | null")]
+    pub limit: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct FileReadResponse {
+    pub path: PathBuf,
+    pub content: String,
+    pub encoding: FileContentEncoding,
+    /// Size of the whole file, so clients can page through it.
+    #[ts(type = "number")]
+    pub size_bytes: u64,
+    /// True when the file continues past the returned range.
+    pub truncated: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum FileContentEncoding {
+    /// `content` is the text of the range.
+    Utf8,
+    /// The range is not valid UTF-8; `content` is its base64 encoding.
+    Base64,
+}
+
+/// A file the model registered as an output of the thread.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct Artifact {
+    pub id: String,
+    pub turn_id: String,
+    pub path: PathBuf,
+    pub title: String,
+    #[ts(optional)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[ts(type = "number")]
+    pub size_bytes: u64,
+}
+
+impl Artifact {
+    pub fn from_core(turn_id: String, artifact: CoreArtifact) -> Self {
+        Self {
+            id: artifact.id,
+            turn_id,
+            path: artifact.path,
+            title: artifact.title,
+            description: artifact.description,
+            size_bytes: artifact.size_bytes,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1363,6 +1482,25 @@ pub struct TurnStartResponse {
     pub turn: Turn,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct WorkflowRunParams {
+    pub thread_id: String,
+    /// Name of a workflow from the `[workflows]` config table.
+    pub name: String,
+    /// Replaces `{input}` in the workflow's prompts.
+    #[serde(default)]
+    pub input: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct WorkflowRunResponse {
+    pub turn: Turn,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1622,6 +1760,58 @@ pub struct TurnDiffUpdatedNotification {
     pub thread_id: String,
     pub turn_id: String,
     pub diff: String,
+    /// Per-file summary of `diff`, for rendering concise change lists.
+    pub files: Vec<TurnDiffFile>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct TurnDiffFile {
+    pub path: PathBuf,
+    pub kind: TurnDiffFileKind,
+    /// Path before the turn, set for renames.
+    pub previous_path: Option<PathBuf>,
+    pub added_lines: u64,
+    pub removed_lines: u64,
+    pub binary: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub enum TurnDiffFileKind {
+    Added,
+    Deleted,
+    Modified,
+    Renamed,
+}
+
+impl From<CoreTurnDiffFile> for TurnDiffFile {
+    fn from(value: CoreTurnDiffFile) -> Self {
+        let (kind, previous_path) = match value.change {
+            CoreTurnDiffFileChange::Added => (TurnDiffFileKind::Added, None),
+            CoreTurnDiffFileChange::Deleted => (TurnDiffFileKind::Deleted, None),
+            CoreTurnDiffFileChange::Modified => (TurnDiffFileKind::Modified, None),
+            CoreTurnDiffFileChange::Renamed { from } => (TurnDiffFileKind::Renamed, Some(from)),
+        };
+        Self {
+            path: value.path,
+            kind,
+            previous_path,
+            added_lines: value.added_lines,
+            removed_lines: value.removed_lines,
+            binary: value.binary,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct ArtifactRegisteredNotification {
+    pub thread_id: String,
+    pub artifact: Artifact,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
@@ -1852,6 +2042,45 @@ pub struct FileChangeRequestApprovalResponse {
     pub decision: ApprovalDecision,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct QuestionRequestAnswerParams {
+    pub thread_id: String,
+    pub turn_id: String,
+    pub item_id: String,
+    pub question: String,
+    /// Suggested answers. When empty the question expects free text.
+    pub options: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[ts(export_to = "v2/")]
+pub struct QuestionRequestAnswerResponse {
+    /// `None` when the user declined to answer.
+    pub answer: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct MemoryCandidatesRequestReviewParams {
+    pub thread_id: String,
+    pub turn_id: String,
+    pub candidates: Vec<String>,
+    /// The store the accepted memories are written to.
+    pub location: PathBuf,
+    /// Seconds after which all candidates are saved if no response arrived.
+    pub auto_accept_secs: u64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[ts(export_to = "v2/")]
+pub struct MemoryCandidatesRequestReviewResponse {
+    /// Zero-based indexes of the candidates to save; empty saves none.
+    pub accepted: Vec<usize>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -1931,6 +2160,13 @@ pub struct AccountLoginCompletedNotification {
     pub error: Option<String>,
 }
 
+/// Sent every `heartbeatIntervalSecs` when the client asked for it in
+/// `initialize`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export_to = "v2/")]
+pub struct HeartbeatNotification {}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 #[serde(rename_all = "camelCase")]
 #[ts(export_to = "v2/")]
@@ -345,6 +345,7 @@ impl CodexClient {
                     title: Some("Codex Toy App Server".to_string()),
                     version: env!("CARGO_PKG_VERSION").to_string(),
                 },
+                heartbeat_interval_secs: None,
             },
         };
 
@@ -371,6 +372,7 @@ impl CodexClient {
             params: AddConversationListenerParams {
                 conversation_id: *conversation_id,
                 experimental_raw_events: false,
+                event_categories: None,
             },
         };
 
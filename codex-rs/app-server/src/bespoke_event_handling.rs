@@ -8,6 +8,8 @@ use codex_app_server_protocol::AgentMessageDeltaNotification;
 use codex_app_server_protocol::ApplyPatchApprovalParams;
 use codex_app_server_protocol::ApplyPatchApprovalResponse;
 use codex_app_server_protocol::ApprovalDecision;
+use codex_app_server_protocol::Artifact;
+use codex_app_server_protocol::ArtifactRegisteredNotification;
 use codex_app_server_protocol::CodexErrorInfo as V2CodexErrorInfo;
 use codex_app_server_protocol::CommandAction as V2ParsedCommand;
 use codex_app_server_protocol::CommandExecutionOutputDeltaNotification;
@@ -30,8 +32,12 @@ use codex_app_server_protocol::ItemStartedNotification;
 use codex_app_server_protocol::McpToolCallError;
 use codex_app_server_protocol::McpToolCallResult;
 use codex_app_server_protocol::McpToolCallStatus;
+use codex_app_server_protocol::MemoryCandidatesRequestReviewParams;
+use codex_app_server_protocol::MemoryCandidatesRequestReviewResponse;
 use codex_app_server_protocol::PatchApplyStatus;
 use codex_app_server_protocol::PatchChangeKind as V2PatchChangeKind;
+use codex_app_server_protocol::QuestionRequestAnswerParams;
+use codex_app_server_protocol::QuestionRequestAnswerResponse;
 use codex_app_server_protocol::RawResponseItemCompletedNotification;
 use codex_app_server_protocol::ReasoningSummaryPartAddedNotification;
 use codex_app_server_protocol::ReasoningSummaryTextDeltaNotification;
@@ -44,6 +50,7 @@ use codex_app_server_protocol::ThreadTokenUsage;
 use codex_app_server_protocol::ThreadTokenUsageUpdatedNotification;
 use codex_app_server_protocol::Turn;
 use codex_app_server_protocol::TurnCompletedNotification;
+use codex_app_server_protocol::TurnDiffFile;
 use codex_app_server_protocol::TurnDiffUpdatedNotification;
 use codex_app_server_protocol::TurnError;
 use codex_app_server_protocol::TurnInterruptResponse;
@@ -60,7 +67,10 @@ use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::FileChange as CoreFileChange;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::MemoryCandidatesProposedEvent;
 use codex_core::protocol::Op;
+use codex_core::protocol::PatchConflictResolution;
+use codex_core::protocol::QuestionEvent;
 use codex_core::protocol::ReviewDecision;
 use codex_core::protocol::TokenCountEvent;
 use codex_core::protocol::TurnDiffEvent;
@@ -101,6 +111,104 @@ pub(crate) async fn apply_bespoke_event_handling(
             )
             .await;
         }
+        EventMsg::PatchConflict(_) => {
+            // The app-server API has no request for this yet, so keep the
+            // concurrent edits and let the model re-read the files.
+            if let Err(err) = conversation
+                .submit(Op::ResolvePatchConflict {
+                    id: event_turn_id,
+                    resolution: PatchConflictResolution::Reread,
+                })
+                .await
+            {
+                error!("failed to submit ResolvePatchConflict: {err}");
+            }
+        }
+        EventMsg::Question(QuestionEvent {
+            call_id,
+            turn_id,
+            question,
+            options,
+        }) => match api_version {
+            ApiVersion::V1 => {
+                // The v1 API has no request for this, so decline and let the
+                // model proceed on its own judgment.
+                if let Err(err) = conversation
+                    .submit(Op::AnswerQuestion {
+                        id: event_turn_id,
+                        answer: None,
+                    })
+                    .await
+                {
+                    error!("failed to submit AnswerQuestion: {err}");
+                }
+            }
+            ApiVersion::V2 => {
+                let params = QuestionRequestAnswerParams {
+                    thread_id: conversation_id.to_string(),
+                    turn_id,
+                    item_id: call_id,
+                    question,
+                    options,
+                };
+                let rx = outgoing
+                    .send_request(ServerRequestPayload::QuestionRequestAnswer(params))
+                    .await;
+                tokio::spawn(async move {
+                    on_question_request_answer_response(event_turn_id, rx, conversation).await;
+                });
+            }
+        },
+        EventMsg::MemoryCandidatesProposed(MemoryCandidatesProposedEvent {
+            turn_id,
+            candidates,
+            location,
+            auto_accept_secs,
+        }) => match api_version {
+            ApiVersion::V1 => {
+                // The v1 API has no request for this, so save them all, as
+                // happens when nobody answers.
+                if let Err(err) = conversation
+                    .submit(Op::ReviewMemories {
+                        id: event_turn_id,
+                        accepted: (0..candidates.len()).collect(),
+                    })
+                    .await
+                {
+                    error!("failed to submit ReviewMemories: {err}");
+                }
+            }
+            ApiVersion::V2 => {
+                let count = candidates.len();
+                let params = MemoryCandidatesRequestReviewParams {
+                    thread_id: conversation_id.to_string(),
+                    turn_id,
+                    candidates,
+                    location,
+                    auto_accept_secs,
+                };
+                let rx = outgoing
+                    .send_request(ServerRequestPayload::MemoryCandidatesRequestReview(params))
+                    .await;
+                tokio::spawn(async move {
+                    on_memory_candidates_review_response(event_turn_id, count, rx, conversation)
+                        .await;
+                });
+            }
+        },
+        EventMsg::CandidatesProposed(_) => {
+            // The app-server API has no request for this yet, so leave the
+            // choice to the judge prompt.
+            if let Err(err) = conversation
+                .submit(Op::SelectCandidate {
+                    id: event_turn_id,
+                    index: None,
+                })
+                .await
+            {
+                error!("failed to submit SelectCandidate: {err}");
+            }
+        }
         EventMsg::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
             call_id,
             turn_id,
@@ -180,6 +288,7 @@ pub(crate) async fn apply_bespoke_event_handling(
             turn_id,
             command,
             cwd,
+            env: _,
             reason,
             proposed_execpolicy_amendment,
             parsed_cmd,
@@ -708,6 +817,17 @@ pub(crate) async fn apply_bespoke_event_handling(
             )
             .await;
         }
+        EventMsg::ArtifactRegistered(event) => {
+            if let ApiVersion::V2 = api_version {
+                let notification = ArtifactRegisteredNotification {
+                    thread_id: conversation_id.to_string(),
+                    artifact: Artifact::from_core(event.turn_id, event.artifact),
+                };
+                outgoing
+                    .send_server_notification(ServerNotification::ArtifactRegistered(notification))
+                    .await;
+            }
+        }
 
         _ => {}
     }
@@ -725,6 +845,11 @@ async fn handle_turn_diff(
             thread_id: conversation_id.to_string(),
             turn_id: event_turn_id.to_string(),
             diff: turn_diff_event.unified_diff,
+            files: turn_diff_event
+                .files
+                .into_iter()
+                .map(TurnDiffFile::from)
+                .collect(),
         };
         outgoing
             .send_server_notification(ServerNotification::TurnDiffUpdated(notification))
@@ -1081,6 +1206,66 @@ fn format_file_change_diff(change: &CoreFileChange) -> String {
     }
 }
 
+async fn on_question_request_answer_response(
+    event_turn_id: String,
+    receiver: oneshot::Receiver<JsonValue>,
+    codex: Arc<CodexConversation>,
+) {
+    let answer = match receiver.await {
+        Ok(value) => serde_json::from_value::<QuestionRequestAnswerResponse>(value)
+            .map(|response| response.answer)
+            .unwrap_or_else(|err| {
+                error!("failed to deserialize QuestionRequestAnswerResponse: {err}");
+                None
+            }),
+        Err(err) => {
+            error!("request failed: {err:?}");
+            None
+        }
+    };
+
+    if let Err(err) = codex
+        .submit(Op::AnswerQuestion {
+            id: event_turn_id,
+            answer,
+        })
+        .await
+    {
+        error!("failed to submit AnswerQuestion: {err}");
+    }
+}
+
+async fn on_memory_candidates_review_response(
+    event_turn_id: String,
+    count: usize,
+    receiver: oneshot::Receiver<JsonValue>,
+    codex: Arc<CodexConversation>,
+) {
+    // A failed request saves them all, as happens when nobody answers.
+    let accepted = match receiver.await {
+        Ok(value) => serde_json::from_value::<MemoryCandidatesRequestReviewResponse>(value)
+            .map(|response| response.accepted)
+            .unwrap_or_else(|err| {
+                error!("failed to deserialize MemoryCandidatesRequestReviewResponse: {err}");
+                (0..count).collect()
+            }),
+        Err(err) => {
+            error!("request failed: {err:?}");
+            (0..count).collect()
+        }
+    };
+
+    if let Err(err) = codex
+        .submit(Op::ReviewMemories {
+            id: event_turn_id,
+            accepted,
+        })
+        .await
+    {
+        error!("failed to submit ReviewMemories: {err}");
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn on_file_change_request_approval_response(
     event_turn_id: String,
@@ -1307,6 +1492,7 @@ mod tests {
     use anyhow::Result;
     use anyhow::anyhow;
     use anyhow::bail;
+    use codex_app_server_protocol::TurnDiffFileKind;
     use codex_app_server_protocol::TurnPlanStepStatus;
     use codex_core::protocol::CreditsSnapshot;
     use codex_core::protocol::McpInvocation;
@@ -1314,6 +1500,8 @@ mod tests {
     use codex_core::protocol::RateLimitWindow;
     use codex_core::protocol::TokenUsage;
     use codex_core::protocol::TokenUsageInfo;
+    use codex_core::protocol::TurnDiffFile as CoreTurnDiffFile;
+    use codex_core::protocol::TurnDiffFileChange;
     use codex_protocol::plan_tool::PlanItemArg;
     use codex_protocol::plan_tool::StepStatus;
     use mcp_types::CallToolResult;
@@ -1936,6 +2124,15 @@ mod tests {
             "turn-1",
             TurnDiffEvent {
                 unified_diff: unified_diff.clone(),
+                files: vec![CoreTurnDiffFile {
+                    path: PathBuf::from("b"),
+                    change: TurnDiffFileChange::Renamed {
+                        from: PathBuf::from("a"),
+                    },
+                    added_lines: 0,
+                    removed_lines: 0,
+                    binary: false,
+                }],
             },
             ApiVersion::V2,
             &outgoing,
@@ -1953,6 +2150,17 @@ mod tests {
                 assert_eq!(notification.thread_id, conversation_id.to_string());
                 assert_eq!(notification.turn_id, "turn-1");
                 assert_eq!(notification.diff, unified_diff);
+                assert_eq!(
+                    notification.files,
+                    vec![TurnDiffFile {
+                        path: PathBuf::from("b"),
+                        kind: TurnDiffFileKind::Renamed,
+                        previous_path: Some(PathBuf::from("a")),
+                        added_lines: 0,
+                        removed_lines: 0,
+                        binary: false,
+                    }]
+                );
             }
             other => bail!("unexpected message: {other:?}"),
         }
@@ -1971,6 +2179,7 @@ mod tests {
             "turn-1",
             TurnDiffEvent {
                 unified_diff: "diff".to_string(),
+                files: Vec::new(),
             },
             ApiVersion::V1,
             &outgoing,
@@ -1,10 +1,13 @@
 use crate::bespoke_event_handling::apply_bespoke_event_handling;
 use crate::error_code::INTERNAL_ERROR_CODE;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
+use crate::event_filter::EventFilter;
 use crate::fuzzy_file_search::run_fuzzy_file_search;
 use crate::models::supported_models;
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::outgoing_message::OutgoingNotification;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use chrono::DateTime;
 use chrono::Utc;
 use codex_app_server_protocol::Account;
@@ -14,6 +17,11 @@ use codex_app_server_protocol::AddConversationListenerParams;
 use codex_app_server_protocol::AddConversationSubscriptionResponse;
 use codex_app_server_protocol::ArchiveConversationParams;
 use codex_app_server_protocol::ArchiveConversationResponse;
+use codex_app_server_protocol::Artifact;
+use codex_app_server_protocol::ArtifactListParams;
+use codex_app_server_protocol::ArtifactListResponse;
+use codex_app_server_protocol::ArtifactSaveParams;
+use codex_app_server_protocol::ArtifactSaveResponse;
 use codex_app_server_protocol::AskForApproval;
 use codex_app_server_protocol::AuthMode;
 use codex_app_server_protocol::AuthStatusChangeNotification;
@@ -28,6 +36,9 @@ use codex_app_server_protocol::ConversationSummary;
 use codex_app_server_protocol::ExecOneOffCommandResponse;
 use codex_app_server_protocol::FeedbackUploadParams;
 use codex_app_server_protocol::FeedbackUploadResponse;
+use codex_app_server_protocol::FileContentEncoding;
+use codex_app_server_protocol::FileReadParams;
+use codex_app_server_protocol::FileReadResponse;
 use codex_app_server_protocol::FuzzyFileSearchParams;
 use codex_app_server_protocol::FuzzyFileSearchResponse;
 use codex_app_server_protocol::GetAccountParams;
@@ -104,6 +115,8 @@ use codex_app_server_protocol::TurnStatus;
 use codex_app_server_protocol::UserInfoResponse;
 use codex_app_server_protocol::UserInput as V2UserInput;
 use codex_app_server_protocol::UserSavedConfig;
+use codex_app_server_protocol::WorkflowRunParams;
+use codex_app_server_protocol::WorkflowRunResponse;
 use codex_app_server_protocol::build_turns_from_event_msgs;
 use codex_backend_client::Client as BackendClient;
 use codex_core::AuthManager;
@@ -136,6 +149,7 @@ use codex_core::protocol::Op;
 use codex_core::protocol::ReviewDelivery as CoreReviewDelivery;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget as CoreReviewTarget;
+use codex_core::protocol::SandboxPolicy as CoreSandboxPolicy;
 use codex_core::protocol::SessionConfiguredEvent;
 use codex_core::read_head_for_summary;
 use codex_core::sandboxing::SandboxPermissions;
@@ -166,6 +180,8 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncSeekExt;
 use tokio::select;
 use tokio::sync::Mutex;
 use tokio::sync::oneshot;
@@ -187,8 +203,13 @@ pub(crate) struct TurnSummary {
 
 pub(crate) type TurnSummaryStore = Arc<Mutex<HashMap<ConversationId, TurnSummary>>>;
 
+/// Per-conversation directories `file/read` may read from, resolved.
+pub(crate) type ReadableRoots = Arc<Mutex<HashMap<ConversationId, Vec<PathBuf>>>>;
+
 const THREAD_LIST_DEFAULT_LIMIT: usize = 25;
 const THREAD_LIST_MAX_LIMIT: usize = 100;
+// Largest range `file/read` returns at once.
+const MAX_FILE_READ_BYTES: u64 = 1024 * 1024;
 
 // Duration before a ChatGPT login attempt is abandoned.
 const LOGIN_CHATGPT_TIMEOUT: Duration = Duration::from_secs(10 * 60);
@@ -221,6 +242,7 @@ pub(crate) struct CodexMessageProcessor {
     // Queue of pending interrupt requests per conversation. We reply when TurnAborted arrives.
     pending_interrupts: PendingInterrupts,
     turn_summary_store: TurnSummaryStore,
+    readable_roots: ReadableRoots,
     pending_fuzzy_searches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
     feedback: CodexFeedback,
 }
@@ -276,6 +298,7 @@ impl CodexMessageProcessor {
             active_login: Arc::new(Mutex::new(None)),
             pending_interrupts: Arc::new(Mutex::new(HashMap::new())),
             turn_summary_store: Arc::new(Mutex::new(HashMap::new())),
+            readable_roots: Arc::new(Mutex::new(HashMap::new())),
             pending_fuzzy_searches: Arc::new(Mutex::new(HashMap::new())),
             feedback,
         }
@@ -371,6 +394,15 @@ impl CodexMessageProcessor {
             ClientRequest::SkillsList { request_id, params } => {
                 self.skills_list(request_id, params).await;
             }
+            ClientRequest::ArtifactList { request_id, params } => {
+                self.artifact_list(request_id, params).await;
+            }
+            ClientRequest::ArtifactSave { request_id, params } => {
+                self.artifact_save(request_id, params).await;
+            }
+            ClientRequest::FileRead { request_id, params } => {
+                self.file_read(request_id, params).await;
+            }
             ClientRequest::TurnStart { request_id, params } => {
                 self.turn_start(request_id, params).await;
             }
@@ -380,6 +412,9 @@ impl CodexMessageProcessor {
             ClientRequest::ReviewStart { request_id, params } => {
                 self.review_start(request_id, params).await;
             }
+            ClientRequest::WorkflowRun { request_id, params } => {
+                self.workflow_run(request_id, params).await;
+            }
             ClientRequest::NewConversation { request_id, params } => {
                 // Do not tokio::spawn() to process new_conversation()
                 // asynchronously because we need to ensure the conversation is
@@ -1385,6 +1420,7 @@ impl CodexMessageProcessor {
                     .attach_conversation_listener(
                         conversation_id,
                         params.experimental_raw_events,
+                        EventFilter::new(params.event_categories),
                         ApiVersion::V2,
                     )
                     .await
@@ -1534,6 +1570,7 @@ impl CodexMessageProcessor {
             config: cli_overrides,
             base_instructions,
             developer_instructions,
+            event_categories,
         } = params;
 
         let overrides_requested = model.is_some()
@@ -1670,7 +1707,12 @@ impl CodexMessageProcessor {
                 } = session_configured;
                 // Auto-attach a conversation listener when resuming a thread.
                 if let Err(err) = self
-                    .attach_conversation_listener(conversation_id, false, ApiVersion::V2)
+                    .attach_conversation_listener(
+                        conversation_id,
+                        false,
+                        EventFilter::new(event_categories),
+                        ApiVersion::V2,
+                    )
                     .await
                 {
                     tracing::warn!(
@@ -2470,6 +2512,8 @@ impl CodexMessageProcessor {
             });
         }
 
+        self.readable_roots.lock().await.remove(&conversation_id);
+
         // If the conversation is active, request shutdown and wait briefly.
         if let Some(conversation) = self
             .conversation_manager
@@ -2666,6 +2710,257 @@ impl CodexMessageProcessor {
             .await;
     }
 
+    async fn artifact_list(&self, request_id: RequestId, params: ArtifactListParams) {
+        match self.thread_artifacts(&params.thread_id).await {
+            Ok(data) => {
+                self.outgoing
+                    .send_response(request_id, ArtifactListResponse { data })
+                    .await;
+            }
+            Err(error) => self.outgoing.send_error(request_id, error).await,
+        }
+    }
+
+    async fn artifact_save(&self, request_id: RequestId, params: ArtifactSaveParams) {
+        let ArtifactSaveParams {
+            thread_id,
+            artifact_id,
+            destination,
+        } = params;
+        if !destination.is_absolute() {
+            self.send_invalid_request_error(
+                request_id,
+                format!("destination must be absolute: {}", destination.display()),
+            )
+            .await;
+            return;
+        }
+        let artifacts = match self.thread_artifacts(&thread_id).await {
+            Ok(artifacts) => artifacts,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        let Some(artifact) = artifacts
+            .into_iter()
+            .find(|artifact| artifact.id == artifact_id)
+        else {
+            self.send_invalid_request_error(
+                request_id,
+                format!("artifact not found: {artifact_id}"),
+            )
+            .await;
+            return;
+        };
+
+        let is_dir = tokio::fs::metadata(&destination)
+            .await
+            .is_ok_and(|metadata| metadata.is_dir());
+        let path = match artifact.path.file_name() {
+            Some(file_name) if is_dir => destination.join(file_name),
+            _ => destination,
+        };
+        match tokio::fs::copy(&artifact.path, &path).await {
+            Ok(_) => {
+                self.outgoing
+                    .send_response(request_id, ArtifactSaveResponse { path })
+                    .await;
+            }
+            Err(err) => {
+                self.send_internal_error(
+                    request_id,
+                    format!(
+                        "failed to save `{}` to `{}`: {err}",
+                        artifact.path.display(),
+                        path.display()
+                    ),
+                )
+                .await;
+            }
+        }
+    }
+
+    async fn file_read(&self, request_id: RequestId, params: FileReadParams) {
+        let FileReadParams {
+            thread_id,
+            path,
+            offset,
+            limit,
+        } = params;
+        if !path.is_absolute() {
+            self.send_invalid_request_error(
+                request_id,
+                format!("path must be absolute: {}", path.display()),
+            )
+            .await;
+            return;
+        }
+        let roots = match self.thread_readable_roots(&thread_id).await {
+            Ok(roots) => roots,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        // Resolve `..` and symlinks so neither can lead out of the roots.
+        let resolved = match tokio::fs::canonicalize(&path).await {
+            Ok(resolved) => resolved,
+            Err(err) => {
+                self.send_invalid_request_error(
+                    request_id,
+                    format!("failed to read `{}`: {err}", path.display()),
+                )
+                .await;
+                return;
+            }
+        };
+        if !roots.iter().any(|root| resolved.starts_with(root)) {
+            self.send_invalid_request_error(
+                request_id,
+                format!(
+                    "`{}` is outside the workspace of thread {thread_id}",
+                    path.display()
+                ),
+            )
+            .await;
+            return;
+        }
+        let offset = offset.unwrap_or(0);
+        let limit = limit
+            .unwrap_or(MAX_FILE_READ_BYTES)
+            .min(MAX_FILE_READ_BYTES);
+        match read_file_range(&resolved, offset, limit).await {
+            Ok((bytes, size_bytes)) => {
+                let truncated = offset.saturating_add(bytes.len() as u64) < size_bytes;
+                let (content, encoding) = match String::from_utf8(bytes) {
+                    Ok(text) => (text, FileContentEncoding::Utf8),
+                    Err(err) => (
+                        BASE64_STANDARD.encode(err.into_bytes()),
+                        FileContentEncoding::Base64,
+                    ),
+                };
+                self.outgoing
+                    .send_response(
+                        request_id,
+                        FileReadResponse {
+                            path,
+                            content,
+                            encoding,
+                            size_bytes,
+                            truncated,
+                        },
+                    )
+                    .await;
+            }
+            Err(err) => {
+                self.send_invalid_request_error(
+                    request_id,
+                    format!("failed to read `{}`: {err}", path.display()),
+                )
+                .await;
+            }
+        }
+    }
+
+    /// Artifacts registered in a loaded thread, read back from its rollout.
+    async fn thread_artifacts(&self, thread_id: &str) -> Result<Vec<Artifact>, JSONRPCErrorError> {
+        Ok(self
+            .thread_rollout_items(thread_id)
+            .await?
+            .into_iter()
+            .filter_map(|item| match item {
+                RolloutItem::EventMsg(EventMsg::ArtifactRegistered(event)) => {
+                    Some(Artifact::from_core(event.turn_id, event.artifact))
+                }
+                _ => None,
+            })
+            .collect())
+    }
+
+    /// Directories `file/read` may read from for a loaded thread, resolved:
+    /// the session's working directory and workspace roots, and the working
+    /// directory and explicit writable roots of its latest turn. They are
+    /// read from the rollout once and then kept up to date by `turn/start`.
+    async fn thread_readable_roots(
+        &self,
+        thread_id: &str,
+    ) -> Result<Vec<PathBuf>, JSONRPCErrorError> {
+        let (conversation_id, _) = self.conversation_from_thread_id(thread_id).await?;
+        if let Some(roots) = self.readable_roots.lock().await.get(&conversation_id) {
+            return Ok(roots.clone());
+        }
+
+        let mut roots = Vec::new();
+        let mut latest_turn = None;
+        for item in self.thread_rollout_items(thread_id).await? {
+            match item {
+                RolloutItem::SessionMeta(line) => {
+                    roots.push(line.meta.cwd);
+                    roots.extend(line.workspace_roots.into_iter().map(|root| root.path));
+                }
+                RolloutItem::TurnContext(turn) => latest_turn = Some(turn),
+                _ => {}
+            }
+        }
+        if let Some(turn) = latest_turn {
+            roots.extend(explicit_readable_roots(
+                Some(&turn.cwd),
+                Some(&turn.sandbox_policy),
+            ));
+        }
+        let resolved = resolve_roots(roots).await;
+        Ok(self
+            .readable_roots
+            .lock()
+            .await
+            .entry(conversation_id)
+            .or_insert(resolved)
+            .clone())
+    }
+
+    /// Let `file/read` into the working directory and writable roots a
+    /// `turn/start` switches the thread to.
+    async fn add_readable_roots(
+        &self,
+        thread_id: &str,
+        cwd: Option<&Path>,
+        sandbox_policy: Option<&CoreSandboxPolicy>,
+    ) {
+        let Ok((conversation_id, _)) = self.conversation_from_thread_id(thread_id).await else {
+            return;
+        };
+        // Load the roots recorded so far first, so they are not lost.
+        if self.thread_readable_roots(thread_id).await.is_err() {
+            return;
+        }
+        let added = resolve_roots(explicit_readable_roots(cwd, sandbox_policy)).await;
+        if let Some(roots) = self.readable_roots.lock().await.get_mut(&conversation_id) {
+            for root in added {
+                if !roots.contains(&root) {
+                    roots.push(root);
+                }
+            }
+        }
+    }
+
+    /// The rollout items recorded so far for a loaded thread.
+    async fn thread_rollout_items(
+        &self,
+        thread_id: &str,
+    ) -> Result<Vec<RolloutItem>, JSONRPCErrorError> {
+        let (_, conversation) = self.conversation_from_thread_id(thread_id).await?;
+        let path = conversation.rollout_path();
+        let history = RolloutRecorder::get_rollout_history(&path)
+            .await
+            .map_err(|err| JSONRPCErrorError {
+                code: INTERNAL_ERROR_CODE,
+                message: format!("failed to load rollout `{}`: {err}", path.display()),
+                data: None,
+            })?;
+        Ok(history.get_rollout_items())
+    }
+
     async fn interrupt_conversation(
         &mut self,
         request_id: RequestId,
@@ -2723,14 +3018,24 @@ impl CodexMessageProcessor {
 
         // If any overrides are provided, update the session turn context first.
         if has_any_overrides {
+            let sandbox_policy = params.sandbox_policy.map(|p| p.to_core());
+            if params.cwd.is_some() || sandbox_policy.is_some() {
+                self.add_readable_roots(
+                    &params.thread_id,
+                    params.cwd.as_deref(),
+                    sandbox_policy.as_ref(),
+                )
+                .await;
+            }
             let _ = conversation
                 .submit(Op::OverrideTurnContext {
                     cwd: params.cwd,
                     approval_policy: params.approval_policy.map(AskForApproval::to_core),
-                    sandbox_policy: params.sandbox_policy.map(|p| p.to_core()),
+                    sandbox_policy,
                     model: params.model,
                     effort: params.effort.map(Some),
                     summary: params.summary,
+                    verbosity: None,
                 })
                 .await;
         }
@@ -2774,6 +3079,57 @@ impl CodexMessageProcessor {
         }
     }
 
+    async fn workflow_run(&self, request_id: RequestId, params: WorkflowRunParams) {
+        let WorkflowRunParams {
+            thread_id,
+            name,
+            input,
+        } = params;
+        let (_, conversation) = match self.conversation_from_thread_id(&thread_id).await {
+            Ok(v) => v,
+            Err(error) => {
+                self.outgoing.send_error(request_id, error).await;
+                return;
+            }
+        };
+        if !self.config.workflows.contains_key(&name) {
+            let error = JSONRPCErrorError {
+                code: INVALID_REQUEST_ERROR_CODE,
+                message: format!("unknown workflow: {name}"),
+                data: None,
+            };
+            self.outgoing.send_error(request_id, error).await;
+            return;
+        }
+
+        match conversation.submit(Op::RunWorkflow { name, input }).await {
+            Ok(turn_id) => {
+                let turn = Turn {
+                    id: turn_id,
+                    items: vec![],
+                    error: None,
+                    status: TurnStatus::InProgress,
+                };
+                self.outgoing
+                    .send_response(request_id, WorkflowRunResponse { turn: turn.clone() })
+                    .await;
+                self.outgoing
+                    .send_server_notification(ServerNotification::TurnStarted(
+                        TurnStartedNotification { thread_id, turn },
+                    ))
+                    .await;
+            }
+            Err(err) => {
+                let error = JSONRPCErrorError {
+                    code: INTERNAL_ERROR_CODE,
+                    message: format!("failed to run workflow: {err}"),
+                    data: None,
+                };
+                self.outgoing.send_error(request_id, error).await;
+            }
+        }
+    }
+
     fn build_review_turn(turn_id: String, display_text: &str) -> Turn {
         let items = if display_text.is_empty() {
             Vec::new()
@@ -2892,7 +3248,12 @@ impl CodexMessageProcessor {
             })?;
 
         if let Err(err) = self
-            .attach_conversation_listener(conversation_id, false, ApiVersion::V2)
+            .attach_conversation_listener(
+                conversation_id,
+                false,
+                EventFilter::default(),
+                ApiVersion::V2,
+            )
             .await
         {
             tracing::warn!(
@@ -3025,9 +3386,15 @@ impl CodexMessageProcessor {
         let AddConversationListenerParams {
             conversation_id,
             experimental_raw_events,
+            event_categories,
         } = params;
         match self
-            .attach_conversation_listener(conversation_id, experimental_raw_events, ApiVersion::V1)
+            .attach_conversation_listener(
+                conversation_id,
+                experimental_raw_events,
+                EventFilter::new(event_categories),
+                ApiVersion::V1,
+            )
             .await
         {
             Ok(subscription_id) => {
@@ -3068,6 +3435,7 @@ impl CodexMessageProcessor {
         &mut self,
         conversation_id: ConversationId,
         experimental_raw_events: bool,
+        event_filter: EventFilter,
         api_version: ApiVersion,
     ) -> Result<Uuid, JSONRPCErrorError> {
         let conversation = match self
@@ -3114,6 +3482,9 @@ impl CodexMessageProcessor {
                             && !experimental_raw_events {
                                 continue;
                             }
+                        if event_filter.skips_bespoke_handling(&event.msg) {
+                            continue;
+                        }
 
                         // For now, we send a notification for every event,
                         // JSON-serializing the `Event` as-is, but these should
@@ -3136,12 +3507,14 @@ impl CodexMessageProcessor {
                             conversation_id.to_string().into(),
                         );
 
-                        outgoing_for_task
-                            .send_notification(OutgoingNotification {
-                                method,
-                                params: Some(params.into()),
-                            })
-                            .await;
+                        if event_filter.allows(&event.msg) {
+                            outgoing_for_task
+                                .send_notification(OutgoingNotification {
+                                    method,
+                                    params: Some(params.into()),
+                                })
+                                .await;
+                        }
 
                         apply_bespoke_event_handling(
                             event.clone(),
@@ -3311,6 +3684,41 @@ impl CodexMessageProcessor {
     }
 }
 
+/// The working directory and the writable roots a sandbox policy names.
+/// The temporary directories a workspace-write policy also makes writable
+/// are left out: they hold other programs' files, not the workspace.
+fn explicit_readable_roots(
+    cwd: Option<&Path>,
+    sandbox_policy: Option<&CoreSandboxPolicy>,
+) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = cwd.map(Path::to_path_buf).into_iter().collect();
+    if let Some(CoreSandboxPolicy::WorkspaceWrite { writable_roots, .. }) = sandbox_policy {
+        roots.extend(writable_roots.iter().map(|root| root.to_path_buf()));
+    }
+    roots
+}
+
+/// Resolve symlinks in `roots`, dropping the ones that do not exist.
+async fn resolve_roots(roots: Vec<PathBuf>) -> Vec<PathBuf> {
+    let mut resolved = Vec::with_capacity(roots.len());
+    for root in roots {
+        if let Ok(root) = tokio::fs::canonicalize(&root).await {
+            resolved.push(root);
+        }
+    }
+    resolved
+}
+
+/// Up to `limit` bytes of `path` from `offset`, and the size of the file.
+async fn read_file_range(path: &Path, offset: u64, limit: u64) -> std::io::Result<(Vec<u8>, u64)> {
+    let mut file = tokio::fs::File::open(path).await?;
+    let size_bytes = file.metadata().await?.len();
+    file.seek(std::io::SeekFrom::Start(offset)).await?;
+    let mut bytes = Vec::new();
+    file.take(limit).read_to_end(&mut bytes).await?;
+    Ok((bytes, size_bytes))
+}
+
 fn skills_to_info(
     skills: &[codex_core::skills::SkillMetadata],
 ) -> Vec<codex_app_server_protocol::SkillMetadata> {
@@ -3374,6 +3782,7 @@ async fn read_summary_from_rollout(
     let SessionMetaLine {
         meta: session_meta,
         git,
+        ..
     } = session_meta_line;
 
     if let Some(summary) = extract_conversation_summary(
@@ -3595,6 +4004,7 @@ mod tests {
             item: RolloutItem::SessionMeta(SessionMetaLine {
                 meta: session_meta.clone(),
                 git: None,
+                workspace_roots: Vec::new(),
             }),
         };
 
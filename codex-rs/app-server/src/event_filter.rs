@@ -0,0 +1,139 @@
+//! Per-listener filtering of the `codex/event/*` notification stream.
+
+use std::collections::HashSet;
+
+use codex_app_server_protocol::EventCategory;
+use codex_core::protocol::EventMsg;
+
+/// The event categories one conversation listener asked for.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EventFilter {
+    /// `None` lets every event through.
+    categories: Option<HashSet<EventCategory>>,
+}
+
+impl EventFilter {
+    pub(crate) fn new(categories: Option<Vec<EventCategory>>) -> Self {
+        Self {
+            categories: categories.map(|categories| categories.into_iter().collect()),
+        }
+    }
+
+    /// Whether `msg` goes out as a `codex/event/*` notification.
+    pub(crate) fn allows(&self, msg: &EventMsg) -> bool {
+        match &self.categories {
+            Some(categories) => categories.contains(&event_category(msg)),
+            None => true,
+        }
+    }
+
+    /// Whether the bespoke handling of `msg` can be skipped along with its
+    /// notification. Only deltas and token counts qualify: the handlers of
+    /// other events keep turn state or send approval requests, which must
+    /// happen whatever the listener subscribed to.
+    pub(crate) fn skips_bespoke_handling(&self, msg: &EventMsg) -> bool {
+        !self.allows(msg)
+            && matches!(
+                event_category(msg),
+                EventCategory::Deltas | EventCategory::Tokens
+            )
+    }
+}
+
+fn event_category(msg: &EventMsg) -> EventCategory {
+    match msg {
+        EventMsg::SessionConfigured(_)
+        | EventMsg::TaskStarted(_)
+        | EventMsg::TaskComplete(_)
+        | EventMsg::TurnAborted(_)
+        | EventMsg::ShutdownComplete
+        | EventMsg::Error(_)
+        | EventMsg::Warning(_)
+        | EventMsg::StreamError(_)
+        | EventMsg::ContextCompacted(_)
+        | EventMsg::ContextTrimmed(_)
+        | EventMsg::Stalled(_)
+        | EventMsg::TurnCompletedMetrics(_) => EventCategory::Lifecycle,
+        EventMsg::ExecApprovalRequest(_)
+        | EventMsg::ApplyPatchApprovalRequest(_)
+        | EventMsg::ElicitationRequest(_)
+        | EventMsg::Question(_)
+        | EventMsg::PatchConflict(_)
+        | EventMsg::CandidatesProposed(_)
+        | EventMsg::MemoryCandidatesProposed(_) => EventCategory::Approvals,
+        EventMsg::AgentMessage(_)
+        | EventMsg::UserMessage(_)
+        | EventMsg::AgentReasoning(_)
+        | EventMsg::AgentReasoningRawContent(_) => EventCategory::Messages,
+        EventMsg::AgentMessageDelta(_)
+        | EventMsg::AgentReasoningDelta(_)
+        | EventMsg::AgentReasoningRawContentDelta(_)
+        | EventMsg::AgentReasoningSectionBreak(_)
+        | EventMsg::AgentMessageContentDelta(_)
+        | EventMsg::ReasoningContentDelta(_)
+        | EventMsg::ReasoningRawContentDelta(_)
+        | EventMsg::ExecCommandOutputDelta(_)
+        | EventMsg::StructuredOutputDelta(_)
+        | EventMsg::TerminalInteraction(_) => EventCategory::Deltas,
+        EventMsg::ExecCommandBegin(_)
+        | EventMsg::ExecCommandEnd(_)
+        | EventMsg::McpToolCallBegin(_)
+        | EventMsg::McpToolCallEnd(_)
+        | EventMsg::PatchApplyBegin(_)
+        | EventMsg::PatchApplyEnd(_)
+        | EventMsg::WebSearchBegin(_)
+        | EventMsg::WebSearchEnd(_)
+        | EventMsg::ViewImageToolCall(_)
+        | EventMsg::TurnDiff(_)
+        | EventMsg::PlanUpdate(_) => EventCategory::Tools,
+        EventMsg::ItemStarted(_) | EventMsg::ItemCompleted(_) => EventCategory::Items,
+        EventMsg::TokenCount(_) => EventCategory::Tokens,
+        _ => EventCategory::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::AgentMessageDeltaEvent;
+    use codex_core::protocol::AgentMessageEvent;
+    use codex_core::protocol::TokenCountEvent;
+    use codex_core::protocol::WarningEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn filter_keeps_only_subscribed_categories() {
+        let delta = EventMsg::AgentMessageDelta(AgentMessageDeltaEvent {
+            delta: "he".to_string(),
+        });
+        let message = EventMsg::AgentMessage(AgentMessageEvent {
+            message: "hello".to_string(),
+        });
+        let warning = EventMsg::Warning(WarningEvent {
+            message: "careful".to_string(),
+        });
+        let tokens = EventMsg::TokenCount(TokenCountEvent {
+            info: None,
+            rate_limits: None,
+        });
+
+        let everything = EventFilter::new(None);
+        assert!(
+            [&delta, &message, &warning, &tokens]
+                .iter()
+                .all(|msg| everything.allows(msg))
+        );
+
+        let filter = EventFilter::new(Some(vec![
+            EventCategory::Lifecycle,
+            EventCategory::Messages,
+        ]));
+        assert_eq!(
+            [&delta, &message, &warning, &tokens].map(|msg| filter.allows(msg)),
+            [false, true, true, false]
+        );
+        assert!(filter.skips_bespoke_handling(&delta));
+        assert!(filter.skips_bespoke_handling(&tokens));
+        assert!(!filter.skips_bespoke_handling(&message));
+    }
+}
@@ -0,0 +1,82 @@
+//! Newline-delimited JSON framing for stdio that may not be a clean pipe.
+//!
+//! Over an SSH exec channel or a remote shell, stdio can carry CRLF line
+//! endings, blank keep-alive lines, invalid UTF-8 from a misbehaving login
+//! script, or banner text printed before the server starts. Frames are split
+//! on `\n` only and cleaned up here, so one bad line is skipped instead of
+//! ending the session.
+
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncRead;
+use tokio::io::BufReader;
+use tracing::warn;
+
+pub(crate) struct FrameReader<R> {
+    reader: BufReader<R>,
+    buf: Vec<u8>,
+}
+
+impl<R: AsyncRead + Unpin> FrameReader<R> {
+    pub(crate) fn new(reader: R) -> Self {
+        Self {
+            reader: BufReader::new(reader),
+            buf: Vec::new(),
+        }
+    }
+
+    /// The next non-empty line with its line ending removed, or `None` at
+    /// EOF. Lines that are not UTF-8 are logged and skipped.
+    pub(crate) async fn next_frame(&mut self) -> std::io::Result<Option<String>> {
+        loop {
+            self.buf.clear();
+            if self.reader.read_until(b'\n', &mut self.buf).await? == 0 {
+                return Ok(None);
+            }
+            let line = match std::str::from_utf8(&self.buf) {
+                Ok(line) => line,
+                Err(err) => {
+                    warn!("skipping frame that is not UTF-8: {err}");
+                    continue;
+                }
+            };
+            let line = line
+                .trim_start_matches('\u{feff}')
+                .trim_end_matches(['\n', '\r']);
+            if line.trim().is_empty() {
+                continue;
+            }
+            return Ok(Some(line.to_string()));
+        }
+    }
+}
+
+/// Whether `line` looks like a JSON-RPC message rather than stray output.
+pub(crate) fn is_json_object(line: &str) -> bool {
+    line.trim_start().starts_with('{')
+        && serde_json::from_str::<serde_json::Value>(line).is_ok_and(|value| value.is_object())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn frames_survive_crlf_blank_lines_and_bad_bytes() {
+        let input: &[u8] = b"\xef\xbb\xbf{\"id\":1}\r\n\r\n\xff\xfe\n{\"id\":2}";
+        let mut reader = FrameReader::new(input);
+
+        let mut frames = Vec::new();
+        while let Some(frame) = reader.next_frame().await.expect("read") {
+            frames.push(frame);
+        }
+        assert_eq!(frames, vec!["{\"id\":1}", "{\"id\":2}"]);
+    }
+
+    #[test]
+    fn banner_lines_are_not_json_objects() {
+        assert!(is_json_object("{\"method\":\"initialized\"}"));
+        assert!(!is_json_object("Welcome to devbox-17"));
+        assert!(!is_json_object("[1, 2]"));
+    }
+}
@@ -6,14 +6,13 @@ use std::io::ErrorKind;
 use std::io::Result as IoResult;
 use std::path::PathBuf;
 
+use crate::framing::FrameReader;
 use crate::message_processor::MessageProcessor;
 use crate::outgoing_message::OutgoingMessage;
 use crate::outgoing_message::OutgoingMessageSender;
 use codex_app_server_protocol::JSONRPCMessage;
 use codex_feedback::CodexFeedback;
-use tokio::io::AsyncBufReadExt;
 use tokio::io::AsyncWriteExt;
-use tokio::io::BufReader;
 use tokio::io::{self};
 use tokio::sync::mpsc;
 use toml::Value as TomlValue;
@@ -31,10 +30,17 @@ mod bespoke_event_handling;
 mod codex_message_processor;
 mod config_api;
 mod error_code;
+mod event_filter;
+mod framing;
 mod fuzzy_file_search;
 mod message_processor;
 mod models;
 mod outgoing_message;
+mod ssh_tunnel;
+
+pub use ssh_tunnel::DEFAULT_REMOTE_COMMAND;
+pub use ssh_tunnel::SshTunnelOptions;
+pub use ssh_tunnel::run_ssh_tunnel;
 
 /// Size of the bounded channels used to communicate between tasks. The value
 /// is a balance between throughput and memory usage – 128 messages should be
@@ -52,11 +58,17 @@ pub async fn run_main(
     // Task: read from stdin, push to `incoming_tx`.
     let stdin_reader_handle = tokio::spawn({
         async move {
-            let stdin = io::stdin();
-            let reader = BufReader::new(stdin);
-            let mut lines = reader.lines();
-
-            while let Some(line) = lines.next_line().await.unwrap_or_default() {
+            let mut frames = FrameReader::new(io::stdin());
+
+            loop {
+                let line = match frames.next_frame().await {
+                    Ok(Some(line)) => line,
+                    Ok(None) => break,
+                    Err(e) => {
+                        error!("Failed to read from stdin: {e}");
+                        break;
+                    }
+                };
                 match serde_json::from_str::<JSONRPCMessage>(&line) {
                     Ok(msg) => {
                         if incoming_tx.send(msg).await.is_err() {
@@ -156,10 +168,16 @@ pub async fn run_main(
             match serde_json::to_string(&value) {
                 Ok(mut json) => {
                     json.push('\n');
+                    // Flush per message: over a pipe or SSH channel nothing
+                    // else pushes a partial buffer out to the client.
                     if let Err(e) = stdout.write_all(json.as_bytes()).await {
                         error!("Failed to write to stdout: {e}");
                         break;
                     }
+                    if let Err(e) = stdout.flush().await {
+                        error!("Failed to flush stdout: {e}");
+                        break;
+                    }
                 }
                 Err(e) => error!("Failed to serialize JSONRPCMessage: {e}"),
             }
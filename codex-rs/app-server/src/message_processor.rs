@@ -1,15 +1,18 @@
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::codex_message_processor::CodexMessageProcessor;
 use crate::config_api::ConfigApi;
 use crate::error_code::INVALID_REQUEST_ERROR_CODE;
 use crate::outgoing_message::OutgoingMessageSender;
 use codex_app_server_protocol::ClientInfo;
+use codex_app_server_protocol::ClientNotification;
 use codex_app_server_protocol::ClientRequest;
 use codex_app_server_protocol::ConfigBatchWriteParams;
 use codex_app_server_protocol::ConfigReadParams;
 use codex_app_server_protocol::ConfigValueWriteParams;
+use codex_app_server_protocol::HeartbeatNotification;
 use codex_app_server_protocol::InitializeResponse;
 use codex_app_server_protocol::JSONRPCError;
 use codex_app_server_protocol::JSONRPCErrorError;
@@ -17,6 +20,7 @@ use codex_app_server_protocol::JSONRPCNotification;
 use codex_app_server_protocol::JSONRPCRequest;
 use codex_app_server_protocol::JSONRPCResponse;
 use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::ServerNotification;
 use codex_core::AuthManager;
 use codex_core::ConversationManager;
 use codex_core::config::Config;
@@ -26,6 +30,9 @@ use codex_feedback::CodexFeedback;
 use codex_protocol::protocol::SessionSource;
 use toml::Value as TomlValue;
 
+/// Shortest heartbeat interval a client may ask for.
+const MIN_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(1);
+
 pub(crate) struct MessageProcessor {
     outgoing: Arc<OutgoingMessageSender>,
     codex_message_processor: CodexMessageProcessor,
@@ -118,6 +125,9 @@ impl MessageProcessor {
                         title: _title,
                         version,
                     } = params.client_info;
+                    if let Some(secs) = params.heartbeat_interval_secs {
+                        self.spawn_heartbeat(Duration::from_secs(secs));
+                    }
                     let user_agent_suffix = format!("{name}; {version}");
                     if let Ok(mut suffix) = USER_AGENT_SUFFIX.lock() {
                         *suffix = Some(user_agent_suffix);
@@ -162,11 +172,37 @@ impl MessageProcessor {
     }
 
     pub(crate) async fn process_notification(&self, notification: JSONRPCNotification) {
-        // Currently, we do not expect to receive any notifications from the
-        // client, so we just log them.
+        if notification.method == ClientNotification::Heartbeat.to_string() {
+            tracing::trace!("<- heartbeat");
+            return;
+        }
+        // Other client notifications need no handling, so we just log them.
         tracing::info!("<- notification: {:?}", notification);
     }
 
+    /// Send `heartbeat` notifications until the connection shuts down. The
+    /// task holds the sender weakly so it does not keep stdout open.
+    fn spawn_heartbeat(&self, interval: Duration) {
+        let outgoing = Arc::downgrade(&self.outgoing);
+        let interval = interval.max(MIN_HEARTBEAT_INTERVAL);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            // The first tick completes immediately.
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(outgoing) = outgoing.upgrade() else {
+                    break;
+                };
+                outgoing
+                    .send_server_notification(ServerNotification::Heartbeat(
+                        HeartbeatNotification {},
+                    ))
+                    .await;
+            }
+        });
+    }
+
     /// Handle a standalone JSON-RPC response originating from the peer.
     pub(crate) async fn process_response(&mut self, response: JSONRPCResponse) {
         tracing::info!("<- response: {:?}", response);
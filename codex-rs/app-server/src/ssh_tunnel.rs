@@ -0,0 +1,114 @@
+//! `codex app-server ssh`: run the app server on another machine over an SSH
+//! exec channel and relay its stdio, so a client that spawns
+//! `codex app-server` locally can drive a remote Codex unchanged.
+//!
+//! Requests go to the remote server byte for byte. Responses are relayed one
+//! frame at a time, and anything on the remote stdout that is not a JSON-RPC
+//! message (login banners, output from shell startup files) is sent to stderr
+//! instead of corrupting the protocol stream.
+
+use std::io::Result as IoResult;
+use std::process::Stdio;
+
+use tokio::io::AsyncWriteExt;
+use tokio::io::{self};
+use tokio::process::Command;
+use tracing::debug;
+
+use crate::framing::FrameReader;
+use crate::framing::is_json_object;
+
+pub const DEFAULT_REMOTE_COMMAND: &str = "codex app-server";
+
+#[derive(Debug, Clone)]
+pub struct SshTunnelOptions {
+    /// Destination as accepted by `ssh`, e.g. `devbox` or `me@devbox:2222`.
+    pub destination: String,
+    /// Command the remote login shell runs to start the app server.
+    pub remote_command: String,
+    /// Extra arguments passed to `ssh` before the destination.
+    pub ssh_args: Vec<String>,
+}
+
+/// Relay stdio to the remote app server until either side closes. Returns
+/// the exit code of `ssh`.
+pub async fn run_ssh_tunnel(options: SshTunnelOptions) -> IoResult<i32> {
+    let mut child = Command::new("ssh")
+        .args(ssh_command_args(&options))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| io::Error::new(err.kind(), format!("failed to run ssh: {err}")))?;
+    let (Some(mut remote_stdin), Some(remote_stdout)) = (child.stdin.take(), child.stdout.take())
+    else {
+        return Err(io::Error::other("ssh did not expose stdio pipes"));
+    };
+
+    let requests = tokio::spawn(async move {
+        let result = io::copy(&mut io::stdin(), &mut remote_stdin).await;
+        // Closing the channel's stdin is how the remote server learns the
+        // client is gone.
+        let _ = remote_stdin.shutdown().await;
+        debug!("stdin relay finished: {result:?}");
+    });
+
+    let mut frames = FrameReader::new(remote_stdout);
+    let mut stdout = io::stdout();
+    let mut stderr = io::stderr();
+    while let Some(frame) = frames.next_frame().await? {
+        if is_json_object(&frame) {
+            stdout.write_all(frame.as_bytes()).await?;
+            stdout.write_all(b"\n").await?;
+            stdout.flush().await?;
+        } else {
+            stderr
+                .write_all(format!("remote: {frame}\n").as_bytes())
+                .await?;
+        }
+    }
+
+    requests.abort();
+    let status = child.wait().await?;
+    Ok(status.code().unwrap_or(1))
+}
+
+fn ssh_command_args(options: &SshTunnelOptions) -> Vec<String> {
+    // -T: no pseudo-terminal, so nothing rewrites line endings or echoes
+    // input. -e none: never treat `~` in the stream as an escape sequence.
+    let mut args = vec!["-T".to_string(), "-e".to_string(), "none".to_string()];
+    args.extend(options.ssh_args.iter().cloned());
+    args.push("--".to_string());
+    args.push(options.destination.clone());
+    args.push(options.remote_command.clone());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn ssh_runs_remote_command_without_a_tty() {
+        let options = SshTunnelOptions {
+            destination: "devbox".to_string(),
+            remote_command: DEFAULT_REMOTE_COMMAND.to_string(),
+            ssh_args: vec!["-p".to_string(), "2222".to_string()],
+        };
+        assert_eq!(
+            ssh_command_args(&options),
+            vec![
+                "-T",
+                "-e",
+                "none",
+                "-p",
+                "2222",
+                "--",
+                "devbox",
+                "codex app-server"
+            ]
+        );
+    }
+}
@@ -22,6 +22,7 @@ use codex_app_server_protocol::ConfigBatchWriteParams;
 use codex_app_server_protocol::ConfigReadParams;
 use codex_app_server_protocol::ConfigValueWriteParams;
 use codex_app_server_protocol::FeedbackUploadParams;
+use codex_app_server_protocol::FileReadParams;
 use codex_app_server_protocol::GetAccountParams;
 use codex_app_server_protocol::GetAuthStatusParams;
 use codex_app_server_protocol::InitializeParams;
@@ -144,6 +145,7 @@ impl McpProcess {
                 title: None,
                 version: "0.1.0".to_string(),
             },
+            heartbeat_interval_secs: None,
         })?);
         let req_id = self.send_request("initialize", params).await?;
         let initialized = self.read_jsonrpc_message().await?;
@@ -331,6 +333,12 @@ impl McpProcess {
         self.send_request("thread/list", params).await
     }
 
+    /// Send a `file/read` JSON-RPC request.
+    pub async fn send_file_read_request(&mut self, params: FileReadParams) -> anyhow::Result<i64> {
+        let params = Some(serde_json::to_value(params)?);
+        self.send_request("file/read", params).await
+    }
+
     /// Send a `model/list` JSON-RPC request.
     pub async fn send_list_models_request(
         &mut self,
@@ -49,10 +49,12 @@ pub fn create_fake_rollout(
         instructions: None,
         source: SessionSource::Cli,
         model_provider: model_provider.map(str::to_string),
+        environment: None,
     };
     let payload = serde_json::to_value(SessionMetaLine {
         meta,
         git: git_info,
+        workspace_roots: Vec::new(),
     })?;
 
     let lines = [
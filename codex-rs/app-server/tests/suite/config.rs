@@ -45,6 +45,7 @@ writable_roots = [{}]
 network_access = true
 exclude_tmpdir_env_var = true
 exclude_slash_tmp = true
+allowed_symlink_targets = [{}]
 
 [tools]
 web_search = false
@@ -59,7 +60,8 @@ model_verbosity = "medium"
 model_provider = "openai"
 chatgpt_base_url = "https://api.chatgpt.com"
 "#,
-            serde_json::json!(writable_root)
+            serde_json::json!(writable_root),
+            serde_json::json!(writable_root.join("store")?)
         ),
     )
 }
@@ -90,6 +92,7 @@ async fn get_config_toml_parses_all_fields() -> Result<()> {
                 network_access: Some(true),
                 exclude_tmpdir_env_var: Some(true),
                 exclude_slash_tmp: Some(true),
+                allowed_symlink_targets: vec![writable_root.join("store")?],
             }),
             forced_chatgpt_workspace_id: Some("12345678-0000-0000-0000-000000000000".into()),
             forced_login_method: Some(ForcedLoginMethod::Chatgpt),
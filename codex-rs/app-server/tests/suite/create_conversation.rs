@@ -59,6 +59,7 @@ async fn test_conversation_create_and_send_message_ok() -> Result<()> {
         .send_add_conversation_listener_request(AddConversationListenerParams {
             conversation_id,
             experimental_raw_events: false,
+            event_categories: None,
         })
         .await?;
     let _sub: AddConversationSubscriptionResponse =
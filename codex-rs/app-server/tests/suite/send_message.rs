@@ -62,6 +62,7 @@ async fn test_send_message_success() -> Result<()> {
         .send_add_conversation_listener_request(AddConversationListenerParams {
             conversation_id,
             experimental_raw_events: false,
+            event_categories: None,
         })
         .await?;
     let add_listener_resp: JSONRPCResponse = timeout(
@@ -163,6 +164,7 @@ async fn test_send_message_raw_notifications_opt_in() -> Result<()> {
         .send_add_conversation_listener_request(AddConversationListenerParams {
             conversation_id,
             experimental_raw_events: true,
+            event_categories: None,
         })
         .await?;
     let add_listener_resp: JSONRPCResponse = timeout(
@@ -0,0 +1,221 @@
+use anyhow::Result;
+use app_test_support::McpProcess;
+use app_test_support::to_response;
+use codex_app_server_protocol::FileContentEncoding;
+use codex_app_server_protocol::FileReadParams;
+use codex_app_server_protocol::FileReadResponse;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::SandboxMode;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use pretty_assertions::assert_eq;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::time::timeout;
+
+const DEFAULT_READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+#[tokio::test]
+async fn file_read_pages_through_server_side_files() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path())?;
+    let workspace = TempDir::new()?;
+    let text_path = workspace.path().join("notes.txt");
+    std::fs::write(&text_path, "hello remote world")?;
+    let binary_path = workspace.path().join("blob.bin");
+    std::fs::write(&binary_path, [0xff, 0x00, 0xfe])?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+    let thread_id = start_thread(&mut mcp, workspace.path(), None).await?;
+
+    let request_id = mcp
+        .send_file_read_request(FileReadParams {
+            thread_id: thread_id.clone(),
+            path: text_path.clone(),
+            offset: Some(6),
+            limit: Some(6),
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert_eq!(
+        to_response::<FileReadResponse>(response)?,
+        FileReadResponse {
+            path: text_path,
+            content: "remote".to_string(),
+            encoding: FileContentEncoding::Utf8,
+            size_bytes: 18,
+            truncated: true,
+        }
+    );
+
+    let request_id = mcp
+        .send_file_read_request(FileReadParams {
+            thread_id: thread_id.clone(),
+            path: binary_path,
+            offset: None,
+            limit: None,
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let binary = to_response::<FileReadResponse>(response)?;
+    assert_eq!(binary.encoding, FileContentEncoding::Base64);
+    assert_eq!(binary.content, "/wD+");
+    assert!(!binary.truncated);
+
+    let request_id = mcp
+        .send_file_read_request(FileReadParams {
+            thread_id: thread_id.clone(),
+            path: "relative.txt".into(),
+            offset: None,
+            limit: None,
+        })
+        .await?;
+    let error: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert!(error.error.message.contains("must be absolute"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn file_read_refuses_files_outside_the_thread_workspace() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path())?;
+    let workspace = TempDir::new()?;
+    let outside = TempDir::new()?;
+    let secret = outside.path().join("secret.txt");
+    std::fs::write(&secret, "keep out")?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+    let thread_id = start_thread(&mut mcp, workspace.path(), None).await?;
+
+    let mut attempts = vec![secret.clone(), workspace.path().join("..").join("x")];
+    #[cfg(unix)]
+    {
+        let link = workspace.path().join("link.txt");
+        std::os::unix::fs::symlink(&secret, &link)?;
+        attempts.push(link);
+    }
+    for path in attempts {
+        let request_id = mcp
+            .send_file_read_request(FileReadParams {
+                thread_id: thread_id.clone(),
+                path: path.clone(),
+                offset: None,
+                limit: None,
+            })
+            .await?;
+        let error: JSONRPCError = timeout(
+            DEFAULT_READ_TIMEOUT,
+            mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+        )
+        .await??;
+        assert!(
+            !error.error.message.contains("keep out"),
+            "{} was read",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn file_read_leaves_the_temp_dir_of_a_workspace_write_thread_closed() -> Result<()> {
+    let codex_home = TempDir::new()?;
+    create_config_toml(codex_home.path())?;
+    let workspace = TempDir::new()?;
+    let notes = workspace.path().join("notes.txt");
+    std::fs::write(&notes, "in the workspace")?;
+    // Another program's file in the temp dir, which the sandbox makes
+    // writable for commands but which is not part of the workspace.
+    let outside = TempDir::new()?;
+    let secret = outside.path().join("secret.txt");
+    std::fs::write(&secret, "keep out")?;
+
+    let mut mcp = McpProcess::new(codex_home.path()).await?;
+    timeout(DEFAULT_READ_TIMEOUT, mcp.initialize()).await??;
+    let thread_id = start_thread(
+        &mut mcp,
+        workspace.path(),
+        Some(SandboxMode::WorkspaceWrite),
+    )
+    .await?;
+
+    let request_id = mcp
+        .send_file_read_request(FileReadParams {
+            thread_id: thread_id.clone(),
+            path: notes,
+            offset: None,
+            limit: None,
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let FileReadResponse { content, .. } = to_response::<FileReadResponse>(response)?;
+    assert_eq!(content, "in the workspace");
+
+    let request_id = mcp
+        .send_file_read_request(FileReadParams {
+            thread_id,
+            path: secret,
+            offset: None,
+            limit: None,
+        })
+        .await?;
+    let error: JSONRPCError = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_error_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    assert!(error.error.message.contains("outside the workspace"));
+    Ok(())
+}
+
+async fn start_thread(
+    mcp: &mut McpProcess,
+    cwd: &Path,
+    sandbox: Option<SandboxMode>,
+) -> Result<String> {
+    let request_id = mcp
+        .send_thread_start_request(ThreadStartParams {
+            model: Some("mock-model".to_string()),
+            cwd: Some(cwd.display().to_string()),
+            sandbox,
+            ..Default::default()
+        })
+        .await?;
+    let response: JSONRPCResponse = timeout(
+        DEFAULT_READ_TIMEOUT,
+        mcp.read_stream_until_response_message(RequestId::Integer(request_id)),
+    )
+    .await??;
+    let ThreadStartResponse { thread, .. } = to_response::<ThreadStartResponse>(response)?;
+    Ok(thread.id)
+}
+
+fn create_config_toml(codex_home: &Path) -> std::io::Result<()> {
+    std::fs::write(
+        codex_home.join("config.toml"),
+        r#"model = "mock-model"
+approval_policy = "never"
+sandbox_mode = "read-only"
+"#,
+    )
+}
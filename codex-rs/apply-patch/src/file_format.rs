@@ -0,0 +1,163 @@
+//! Detect and reproduce the on-disk format of a text file (encoding, byte
+//! order mark, line endings) so that updating a few lines does not rewrite the
+//! rest of the file.
+
+use std::path::Path;
+
+const UTF8_BOM: &[u8] = &[0xEF, 0xBB, 0xBF];
+const UTF16_LE_BOM: &[u8] = &[0xFF, 0xFE];
+const UTF16_BE_BOM: &[u8] = &[0xFE, 0xFF];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TextEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    /// Any file that is not valid UTF-8. Each byte maps to the code point of
+    /// the same value, so bytes outside the edited lines are written back
+    /// unchanged whatever the real single-byte encoding is.
+    Latin1,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FileFormat {
+    pub(crate) encoding: TextEncoding,
+    pub(crate) bom: bool,
+    /// True when most lines end in `\r\n`; new lines are written the same way.
+    pub(crate) crlf: bool,
+}
+
+impl FileFormat {
+    /// Decode `bytes` into text without the byte order mark. Line endings are
+    /// left as they are.
+    pub(crate) fn decode(bytes: &[u8]) -> (Self, String) {
+        let (encoding, bom, text) = if let Some(rest) = bytes.strip_prefix(UTF8_BOM) {
+            let (encoding, text) = decode_utf8_or_latin1(rest);
+            (encoding, true, text)
+        } else if let Some(rest) = bytes.strip_prefix(UTF16_LE_BOM)
+            && let Some(text) = decode_utf16(rest, u16::from_le_bytes)
+        {
+            (TextEncoding::Utf16Le, true, text)
+        } else if let Some(rest) = bytes.strip_prefix(UTF16_BE_BOM)
+            && let Some(text) = decode_utf16(rest, u16::from_be_bytes)
+        {
+            (TextEncoding::Utf16Be, true, text)
+        } else {
+            let (encoding, text) = decode_utf8_or_latin1(bytes);
+            (encoding, false, text)
+        };
+
+        let line_feeds = text.matches('\n').count();
+        let crlfs = text.matches("\r\n").count();
+        let format = Self {
+            encoding,
+            bom,
+            crlf: crlfs > 0 && crlfs * 2 >= line_feeds,
+        };
+        (format, text)
+    }
+
+    /// Encode `text` back into this format, adding the byte order mark if
+    /// the original had one.
+    pub(crate) fn encode(&self, text: &str) -> Result<Vec<u8>, String> {
+        let mut bytes = Vec::with_capacity(text.len() + 3);
+        match self.encoding {
+            TextEncoding::Utf8 => {
+                if self.bom {
+                    bytes.extend_from_slice(UTF8_BOM);
+                }
+                bytes.extend_from_slice(text.as_bytes());
+            }
+            TextEncoding::Utf16Le => {
+                bytes.extend_from_slice(UTF16_LE_BOM);
+                bytes.extend(text.encode_utf16().flat_map(u16::to_le_bytes));
+            }
+            TextEncoding::Utf16Be => {
+                bytes.extend_from_slice(UTF16_BE_BOM);
+                bytes.extend(text.encode_utf16().flat_map(u16::to_be_bytes));
+            }
+            TextEncoding::Latin1 => {
+                if self.bom {
+                    bytes.extend_from_slice(UTF8_BOM);
+                }
+                for c in text.chars() {
+                    let byte = u8::try_from(u32::from(c)).map_err(|_| {
+                        format!("character {c:?} cannot be written to a file that is not UTF-8")
+                    })?;
+                    bytes.push(byte);
+                }
+            }
+        }
+        Ok(bytes)
+    }
+}
+
+/// Read `path` as text in whatever encoding it uses.
+pub(crate) fn read_text(path: &Path) -> std::io::Result<(FileFormat, String)> {
+    std::fs::read(path).map(|bytes| FileFormat::decode(&bytes))
+}
+
+fn decode_utf8_or_latin1(bytes: &[u8]) -> (TextEncoding, String) {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => (TextEncoding::Utf8, text.to_string()),
+        Err(_) => (
+            TextEncoding::Latin1,
+            bytes.iter().map(|byte| char::from(*byte)).collect(),
+        ),
+    }
+}
+
+fn decode_utf16(bytes: &[u8], to_u16: fn([u8; 2]) -> u16) -> Option<String> {
+    if bytes.len() % 2 != 0 {
+        return None;
+    }
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| to_u16([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16(&units).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn round_trips_each_format() {
+        let utf16: Vec<u8> = UTF16_LE_BOM
+            .iter()
+            .copied()
+            .chain("a\r\nb\r\n".encode_utf16().flat_map(u16::to_le_bytes))
+            .collect();
+        let cases: [&[u8]; 4] = [
+            b"one\ntwo\n",
+            b"\xEF\xBB\xBFone\r\ntwo\r\n",
+            b"caf\xE9\r\n",
+            &utf16,
+        ];
+        for bytes in cases {
+            let (format, text) = FileFormat::decode(bytes);
+            assert_eq!(format.encode(&text).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn detects_dominant_line_ending() {
+        let (format, text) = FileFormat::decode(b"\xEF\xBB\xBFa\r\nb\r\nc\n");
+        assert_eq!(
+            format,
+            FileFormat {
+                encoding: TextEncoding::Utf8,
+                bom: true,
+                crlf: true,
+            }
+        );
+        assert_eq!(text, "a\r\nb\r\nc\n");
+
+        let (format, _) = FileFormat::decode(b"caf\xE9\n");
+        assert_eq!(format.encoding, TextEncoding::Latin1);
+        assert!(!format.crlf);
+        assert!(format.encode("\u{2014}").is_err());
+    }
+}
@@ -1,3 +1,4 @@
+mod file_format;
 mod invocation;
 mod parser;
 mod seek_sequence;
@@ -299,8 +300,7 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
                 move_path,
                 chunks,
             } => {
-                let AppliedPatch { new_contents, .. } =
-                    derive_new_contents_from_chunks(path, chunks)?;
+                let AppliedPatch { new_bytes, .. } = derive_new_contents_from_chunks(path, chunks)?;
                 if let Some(dest) = move_path {
                     if let Some(parent) = dest.parent()
                         && !parent.as_os_str().is_empty()
@@ -309,13 +309,13 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
                             format!("Failed to create parent directories for {}", dest.display())
                         })?;
                     }
-                    std::fs::write(dest, new_contents)
+                    std::fs::write(dest, new_bytes)
                         .with_context(|| format!("Failed to write file {}", dest.display()))?;
                     std::fs::remove_file(path)
                         .with_context(|| format!("Failed to remove original {}", path.display()))?;
                     modified.push(dest.clone());
                 } else {
-                    std::fs::write(path, new_contents)
+                    std::fs::write(path, new_bytes)
                         .with_context(|| format!("Failed to write file {}", path.display()))?;
                     modified.push(path.clone());
                 }
@@ -332,16 +332,20 @@ fn apply_hunks_to_files(hunks: &[Hunk]) -> anyhow::Result<AffectedPaths> {
 struct AppliedPatch {
     original_contents: String,
     new_contents: String,
+    /// `new_contents` encoded like the original file.
+    new_bytes: Vec<u8>,
 }
 
 /// Return *only* the new file contents (joined into a single `String`) after
-/// applying the chunks to the file at `path`.
+/// applying the chunks to the file at `path`. The file's encoding, byte order
+/// mark, and line endings are preserved; lines the patch does not touch keep
+/// their exact bytes.
 fn derive_new_contents_from_chunks(
     path: &Path,
     chunks: &[UpdateFileChunk],
 ) -> std::result::Result<AppliedPatch, ApplyPatchError> {
-    let original_contents = match std::fs::read_to_string(path) {
-        Ok(contents) => contents,
+    let (format, original_contents) = match file_format::read_text(path) {
+        Ok(read) => read,
         Err(err) => {
             return Err(ApplyPatchError::IoError(IoError {
                 context: format!("Failed to read file to update {}", path.display()),
@@ -358,16 +362,41 @@ fn derive_new_contents_from_chunks(
         original_lines.pop();
     }
 
-    let replacements = compute_replacements(&original_lines, path, chunks)?;
+    // Match against lines without their `\r` so patches written with LF
+    // endings apply to CRLF files, and give new lines the file's ending.
+    let match_lines: Vec<String> = original_lines
+        .iter()
+        .map(|line| line.strip_suffix('\r').unwrap_or(line).to_string())
+        .collect();
+    let mut replacements = compute_replacements(&match_lines, path, chunks)?;
+    if format.crlf {
+        for (_, _, new_segment) in &mut replacements {
+            for line in new_segment.iter_mut() {
+                if !line.ends_with('\r') {
+                    line.push('\r');
+                }
+            }
+        }
+    }
     let new_lines = apply_replacements(original_lines, &replacements);
     let mut new_lines = new_lines;
     if !new_lines.last().is_some_and(String::is_empty) {
+        if format.crlf
+            && let Some(last) = new_lines.last_mut()
+            && !last.ends_with('\r')
+        {
+            last.push('\r');
+        }
         new_lines.push(String::new());
     }
     let new_contents = new_lines.join("\n");
+    let new_bytes = format.encode(&new_contents).map_err(|err| {
+        ApplyPatchError::ComputeReplacements(format!("Failed to update {}: {err}", path.display()))
+    })?;
     Ok(AppliedPatch {
         original_contents,
         new_contents,
+        new_bytes,
     })
 }
 
@@ -514,6 +543,7 @@ pub fn unified_diff_from_chunks_with_context(
     let AppliedPatch {
         original_contents,
         new_contents,
+        ..
     } = derive_new_contents_from_chunks(path, chunks)?;
     let text_diff = TextDiff::from_lines(&original_contents, &new_contents);
     let unified_diff = text_diff.unified_diff().context_radius(context).to_string();
@@ -779,6 +809,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_update_preserves_line_endings_and_encoding() {
+        let dir = tempdir().unwrap();
+        let crlf_path = dir.path().join("crlf.txt");
+        let latin1_path = dir.path().join("latin1.txt");
+        fs::write(&crlf_path, b"\xEF\xBB\xBFfoo\r\nbar\nbaz\r\n").unwrap();
+        fs::write(&latin1_path, b"caf\xE9\nold\n").unwrap();
+
+        let patch = wrap_patch(&format!(
+            r#"*** Update File: {}
+@@
+ foo
+-bar
++BAR
++qux
+*** Update File: {}
+@@
+-old
++new"#,
+            crlf_path.display(),
+            latin1_path.display()
+        ));
+        let mut stdout = Vec::new();
+        let mut stderr = Vec::new();
+        apply_patch(&patch, &mut stdout, &mut stderr).unwrap();
+
+        assert_eq!(
+            fs::read(&crlf_path).unwrap(),
+            b"\xEF\xBB\xBFfoo\r\nBAR\r\nqux\r\nbaz\r\n"
+        );
+        assert_eq!(fs::read(&latin1_path).unwrap(), b"caf\xE9\nnew\n");
+    }
+
     /// Ensure that patches authored with ASCII characters can update lines that
     /// contain typographic Unicode punctuation (e.g. EN DASH, NON-BREAKING
     /// HYPHEN). Historically `git apply` succeeds in such scenarios but our
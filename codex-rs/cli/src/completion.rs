@@ -0,0 +1,244 @@
+//! Shell completion scripts with dynamic candidates.
+//!
+//! `clap_complete` generates the static part (subcommands and flags). Values
+//! that depend on local state (session ids, profile names, workflow names,
+//! model names, and feature keys) are completed by a small shell-specific wrapper that calls
+//! the hidden `codex __complete <KIND>` command and otherwise defers to the
+//! generated completer.
+
+use std::io::Write;
+
+use clap::Command;
+use clap::Parser;
+use clap::ValueEnum;
+use clap_complete::Shell;
+use clap_complete::generate;
+use codex_common::CliConfigOverrides;
+use codex_core::AuthManager;
+use codex_core::INTERACTIVE_SESSION_SOURCES;
+use codex_core::RolloutRecorder;
+use codex_core::config::Config;
+use codex_core::config::find_codex_home;
+use codex_core::config::load_config_as_toml_with_cli_overrides;
+use codex_core::features::FEATURES;
+use codex_core::openai_models::models_manager::ModelsManager;
+use codex_protocol::protocol::SessionMetaLine;
+
+const BIN_NAME: &str = "codex";
+/// How many recent sessions `codex resume <TAB>` offers.
+const MAX_SESSION_CANDIDATES: usize = 50;
+
+#[derive(Debug, Parser)]
+pub(crate) struct CompletionCommand {
+    /// Shell to generate completions for
+    #[clap(value_enum, default_value_t = Shell::Bash)]
+    pub(crate) shell: Shell,
+}
+
+#[derive(Debug, Parser)]
+pub(crate) struct CompleteCommand {
+    /// Kind of value to list candidates for.
+    #[arg(value_enum)]
+    kind: CompletionKind,
+
+    #[clap(skip)]
+    pub(crate) config_overrides: CliConfigOverrides,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CompletionKind {
+    Sessions,
+    Profiles,
+    Workflows,
+    Models,
+    Features,
+}
+
+/// Write the completion script for `shell`: the generated static completer
+/// followed by the dynamic wrapper, when the shell has one.
+pub(crate) fn write_completion(
+    shell: Shell,
+    cmd: &mut Command,
+    out: &mut impl Write,
+) -> std::io::Result<()> {
+    let mut script = Vec::new();
+    generate(shell, cmd, BIN_NAME, &mut script);
+    let script = String::from_utf8_lossy(&script);
+    match shell {
+        Shell::Bash => write!(out, "{script}{BASH_DYNAMIC}"),
+        Shell::Zsh => write!(out, "{script}{ZSH_DYNAMIC}"),
+        Shell::Fish => write!(out, "{script}{FISH_DYNAMIC}"),
+        // PowerShell allows one native completer per command, so keep the
+        // generated one as a script block and register a wrapper around it.
+        Shell::PowerShell => {
+            let script = script.replacen(POWERSHELL_REGISTRATION, "$codexStaticCompleter =", 1);
+            write!(out, "{script}{POWERSHELL_DYNAMIC}")
+        }
+        _ => write!(out, "{script}"),
+    }
+}
+
+/// Print candidates for `kind`, one per line. Used by the completion scripts,
+/// which discard stderr, so failures simply produce no candidates.
+pub(crate) async fn run_complete(cmd: CompleteCommand) -> anyhow::Result<()> {
+    let overrides = cmd
+        .config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let candidates = match cmd.kind {
+        CompletionKind::Features => FEATURES.iter().map(|spec| spec.key.to_string()).collect(),
+        CompletionKind::Profiles => {
+            let codex_home = find_codex_home()?;
+            let config_toml =
+                load_config_as_toml_with_cli_overrides(&codex_home, overrides).await?;
+            let mut profiles: Vec<String> = config_toml.profiles.into_keys().collect();
+            profiles.sort();
+            profiles
+        }
+        CompletionKind::Workflows => {
+            let codex_home = find_codex_home()?;
+            let config_toml =
+                load_config_as_toml_with_cli_overrides(&codex_home, overrides).await?;
+            config_toml.workflows.into_keys().collect()
+        }
+        CompletionKind::Models => {
+            let config = Config::load_with_cli_overrides(overrides).await?;
+            model_candidates(&config)
+        }
+        CompletionKind::Sessions => {
+            let config = Config::load_with_cli_overrides(overrides).await?;
+            session_candidates(&config).await?
+        }
+    };
+    let mut stdout = std::io::stdout().lock();
+    for candidate in candidates {
+        writeln!(stdout, "{candidate}")?;
+    }
+    Ok(())
+}
+
+/// Models from the local presets (and any cached remote list), plus the
+/// configured model in case it is not among them.
+fn model_candidates(config: &Config) -> Vec<String> {
+    let auth_manager = AuthManager::shared(
+        config.codex_home.clone(),
+        false,
+        config.cli_auth_credentials_store_mode,
+    );
+    let mut models: Vec<String> = ModelsManager::new(auth_manager)
+        .try_list_models(config)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|preset| preset.model)
+        .collect();
+    if let Some(model) = &config.model
+        && !models.contains(model)
+    {
+        models.push(model.clone());
+    }
+    models
+}
+
+/// Ids of the most recent interactive sessions, newest first.
+async fn session_candidates(config: &Config) -> anyhow::Result<Vec<String>> {
+    let page = RolloutRecorder::list_conversations(
+        &config.codex_home,
+        MAX_SESSION_CANDIDATES,
+        None,
+        INTERACTIVE_SESSION_SOURCES,
+        None,
+        &config.model_provider_id,
+    )
+    .await?;
+    Ok(page
+        .items
+        .iter()
+        .filter_map(|item| {
+            item.head.iter().find_map(|value| {
+                serde_json::from_value::<SessionMetaLine>(value.clone())
+                    .ok()
+                    .map(|line| line.meta.id.to_string())
+            })
+        })
+        .collect())
+}
+
+const POWERSHELL_REGISTRATION: &str =
+    "Register-ArgumentCompleter -Native -CommandName 'codex' -ScriptBlock";
+
+const BASH_DYNAMIC: &str = r#"
+# Dynamic completion for values that depend on local state.
+_codex_dynamic() {
+    local cur="${COMP_WORDS[COMP_CWORD]}"
+    local prev="${COMP_WORDS[COMP_CWORD-1]}"
+    local kind=""
+    case "${prev}" in
+        -p|--profile) kind=profiles ;;
+        -m|--model) kind=models ;;
+        --enable|--disable) kind=features ;;
+        resume) kind=sessions ;;
+        run) kind=workflows ;;
+    esac
+    if [[ -n "${kind}" ]]; then
+        COMPREPLY=( $(compgen -W "$(codex __complete "${kind}" 2>/dev/null)" -- "${cur}") )
+        return 0
+    fi
+    _codex "$@"
+}
+complete -F _codex_dynamic -o bashdefault -o default codex
+"#;
+
+const ZSH_DYNAMIC: &str = r#"
+# Dynamic completion for values that depend on local state.
+_codex_dynamic() {
+    local kind
+    case "${words[CURRENT-1]}" in
+        -p|--profile) kind=profiles ;;
+        -m|--model) kind=models ;;
+        --enable|--disable) kind=features ;;
+        resume) kind=sessions ;;
+        run) kind=workflows ;;
+    esac
+    if [[ -n "$kind" ]]; then
+        local -a candidates
+        candidates=(${(f)"$(codex __complete "$kind" 2>/dev/null)"})
+        compadd -a candidates
+        return
+    fi
+    _codex "$@"
+}
+compdef _codex_dynamic codex
+"#;
+
+const FISH_DYNAMIC: &str = r#"
+# Dynamic completion for values that depend on local state.
+complete -c codex -n '__fish_seen_subcommand_from resume' -f -a '(codex __complete sessions 2>/dev/null)'
+complete -c codex -n '__fish_seen_subcommand_from run' -f -a '(codex __complete workflows 2>/dev/null)'
+complete -c codex -s p -l profile -x -a '(codex __complete profiles 2>/dev/null)'
+complete -c codex -s m -l model -x -a '(codex __complete models 2>/dev/null)'
+complete -c codex -l enable -x -a '(codex __complete features 2>/dev/null)'
+complete -c codex -l disable -x -a '(codex __complete features 2>/dev/null)'
+"#;
+
+const POWERSHELL_DYNAMIC: &str = r#"
+# Dynamic completion for values that depend on local state.
+Register-ArgumentCompleter -Native -CommandName 'codex' -ScriptBlock {
+    param($wordToComplete, $commandAst, $cursorPosition)
+    $elements = @($commandAst.CommandElements | ForEach-Object { $_.ToString() })
+    $previous = if ($wordToComplete -and $elements.Count -ge 2) { $elements[-2] } else { $elements[-1] }
+    $kind = switch ($previous) {
+        { $_ -in '-p', '--profile' } { 'profiles' }
+        { $_ -in '-m', '--model' } { 'models' }
+        { $_ -in '--enable', '--disable' } { 'features' }
+        'resume' { 'sessions' }
+        'run' { 'workflows' }
+    }
+    if ($kind) {
+        codex __complete $kind 2>$null |
+            Where-Object { $_ -like "$wordToComplete*" } |
+            ForEach-Object { [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_) }
+        return
+    }
+    & $codexStaticCompleter $wordToComplete $commandAst $cursorPosition
+}
+"#;
@@ -0,0 +1,40 @@
+//! `codex debug-prompt`: print the model instructions a new session would
+//! use, section by section, so `[prompt_sections]` changes can be checked
+//! without starting a conversation.
+
+use std::io::Write;
+
+use clap::Parser;
+use codex_common::CliConfigOverrides;
+use codex_core::AuthManager;
+use codex_core::config::Config;
+use codex_core::describe_prompt_composition;
+use codex_core::openai_models::models_manager::ModelsManager;
+
+#[derive(Debug, Parser)]
+pub(crate) struct DebugPromptCommand {
+    #[clap(skip)]
+    pub(crate) config_overrides: CliConfigOverrides,
+}
+
+pub(crate) async fn run_debug_prompt(cmd: DebugPromptCommand) -> anyhow::Result<()> {
+    let overrides = cmd
+        .config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides).await?;
+
+    let auth_manager = AuthManager::shared(
+        config.codex_home.clone(),
+        false,
+        config.cli_auth_credentials_store_mode,
+    );
+    let models_manager = ModelsManager::new(auth_manager);
+    let model = models_manager.get_model(&config.model, &config).await;
+    let model_family = models_manager.construct_model_family(&model, &config).await;
+
+    let composition = describe_prompt_composition(&config, &model_family).await;
+    let mut stdout = std::io::stdout().lock();
+    writeln!(stdout, "Model: {model}\n\n{composition}")?;
+    Ok(())
+}
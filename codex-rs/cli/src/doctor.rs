@@ -0,0 +1,537 @@
+//! `codex doctor`: check the local setup and suggest fixes for common
+//! problems (missing login, unreachable provider, missing sandbox tooling,
+//! invalid config, unwritable `CODEX_HOME`). With `--offline` it instead
+//! checks that Codex can run without internet access against a local model
+//! endpoint.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Parser;
+use codex_app_server_protocol::AuthMode;
+use codex_common::CliConfigOverrides;
+use codex_core::CodexAuth;
+use codex_core::bundled_assets::verify_bundled_assets;
+use codex_core::config::Config;
+use codex_core::config::find_codex_home;
+use codex_core::default_client::create_client;
+use codex_core::features::Feature;
+use owo_colors::OwoColorize;
+use supports_color::Stream;
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+const CHATGPT_BASE_URL: &str = "https://chatgpt.com/backend-api/codex";
+
+#[derive(Debug, Parser)]
+pub(crate) struct DoctorCommand {
+    /// Check readiness for an air-gapped machine: bundled assets, a local
+    /// model endpoint, and no features that need the internet.
+    #[arg(long)]
+    offline: bool,
+
+    #[clap(skip)]
+    pub(crate) config_overrides: CliConfigOverrides,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Ok,
+    Warning,
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Check {
+    name: &'static str,
+    status: Status,
+    detail: String,
+    fix: Option<String>,
+}
+
+impl Check {
+    fn ok(name: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Ok,
+            detail: detail.into(),
+            fix: None,
+        }
+    }
+
+    fn warning(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Warning,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+
+    fn failed(name: &'static str, detail: impl Into<String>, fix: impl Into<String>) -> Self {
+        Self {
+            name,
+            status: Status::Failed,
+            detail: detail.into(),
+            fix: Some(fix.into()),
+        }
+    }
+}
+
+/// Run every check, print the report, and exit non-zero if any check failed.
+pub(crate) async fn run_doctor(
+    cmd: DoctorCommand,
+    codex_linux_sandbox_exe: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let overrides = cmd
+        .config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+
+    let mut checks = Vec::new();
+    let config = match Config::load_with_cli_overrides(overrides).await {
+        Ok(config) => {
+            checks.push(Check::ok(
+                "config",
+                format!("loaded {}", config.codex_home.join("config.toml").display()),
+            ));
+            Some(config)
+        }
+        Err(err) => {
+            checks.push(Check::failed(
+                "config",
+                err.to_string(),
+                "Fix the reported error in config.toml or the -c overrides and rerun `codex doctor`.",
+            ));
+            None
+        }
+    };
+
+    let codex_home = match &config {
+        Some(config) => Some(config.codex_home.clone()),
+        None => find_codex_home().ok(),
+    };
+    checks.push(match &codex_home {
+        Some(codex_home) => check_codex_home(codex_home),
+        None => Check::failed(
+            "codex home",
+            "could not determine the Codex home directory",
+            "Set CODEX_HOME to an existing, writable directory.",
+        ),
+    });
+
+    if let Some(config) = &config {
+        let (auth_check, auth_mode) = check_auth(config).await;
+        checks.push(auth_check);
+        if cmd.offline {
+            checks.push(check_bundled_assets(config));
+            checks.push(check_offline_provider(config).await);
+            checks.push(check_network_features(config));
+        } else {
+            checks.push(check_provider(config, auth_mode).await);
+        }
+    }
+
+    let linux_sandbox_exe = config
+        .as_ref()
+        .and_then(|config| config.codex_linux_sandbox_exe.clone())
+        .or(codex_linux_sandbox_exe);
+    checks.push(check_sandbox(linux_sandbox_exe.as_deref()));
+    checks.push(check_git().await);
+
+    let color_enabled = supports_color::on(Stream::Stdout).is_some();
+    print!("{}", render_report(&checks, color_enabled));
+    if checks.iter().any(|check| check.status == Status::Failed) {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn check_codex_home(codex_home: &Path) -> Check {
+    const NAME: &str = "codex home";
+    let probe = codex_home.join(".doctor-write-probe");
+    let result = std::fs::create_dir_all(codex_home)
+        .and_then(|()| std::fs::write(&probe, b""))
+        .and_then(|()| std::fs::remove_file(&probe));
+    match result {
+        Ok(()) => Check::ok(NAME, format!("{} is writable", codex_home.display())),
+        Err(err) => Check::failed(
+            NAME,
+            format!("{} is not writable: {err}", codex_home.display()),
+            format!(
+                "Fix the permissions on {} or point CODEX_HOME at a writable directory.",
+                codex_home.display()
+            ),
+        ),
+    }
+}
+
+async fn check_auth(config: &Config) -> (Check, Option<AuthMode>) {
+    const NAME: &str = "auth";
+    let provider = &config.model_provider;
+    if !provider.requires_openai_auth {
+        let check = match provider.api_key() {
+            Ok(Some(_)) => Check::ok(
+                NAME,
+                format!(
+                    "{} is set for provider {}",
+                    provider.env_key.as_deref().unwrap_or_default(),
+                    config.model_provider_id
+                ),
+            ),
+            Ok(None) => Check::ok(
+                NAME,
+                format!(
+                    "provider {} does not require an API key",
+                    config.model_provider_id
+                ),
+            ),
+            Err(err) => Check::failed(
+                NAME,
+                err.to_string(),
+                format!(
+                    "Export {} before starting Codex.",
+                    provider
+                        .env_key
+                        .as_deref()
+                        .unwrap_or("the provider's API key")
+                ),
+            ),
+        };
+        return (check, None);
+    }
+
+    match CodexAuth::from_auth_storage(&config.codex_home, config.cli_auth_credentials_store_mode) {
+        Ok(Some(auth)) => {
+            let mode = auth.mode;
+            let check = match auth.get_token().await {
+                Ok(token) if !token.is_empty() => Check::ok(
+                    NAME,
+                    match mode {
+                        AuthMode::ApiKey => "logged in using an API key",
+                        AuthMode::ChatGPT => "logged in using ChatGPT",
+                    },
+                ),
+                Ok(_) => Check::failed(
+                    NAME,
+                    "stored credentials are empty",
+                    "Run `codex login` to sign in again.",
+                ),
+                Err(err) => Check::failed(
+                    NAME,
+                    format!("stored credentials are not usable: {err}"),
+                    "Run `codex logout` and then `codex login` to sign in again.",
+                ),
+            };
+            (check, Some(mode))
+        }
+        Ok(None) => (
+            Check::failed(
+                NAME,
+                "not logged in",
+                "Run `codex login`, or pipe a key to `codex login --with-api-key`.",
+            ),
+            None,
+        ),
+        Err(err) => (
+            Check::failed(
+                NAME,
+                format!("could not read stored credentials: {err}"),
+                "Run `codex logout` and then `codex login` to recreate them.",
+            ),
+            None,
+        ),
+    }
+}
+
+async fn check_provider(config: &Config, auth_mode: Option<AuthMode>) -> Check {
+    const NAME: &str = "provider";
+    let base_url = provider_base_url(config.model_provider.base_url.as_deref(), auth_mode);
+    // Any HTTP response (even 401/404) proves the endpoint is reachable.
+    match create_client()
+        .get(base_url.as_str())
+        .timeout(PROBE_TIMEOUT)
+        .send()
+        .await
+    {
+        Ok(response) => Check::ok(
+            NAME,
+            format!(
+                "{} reachable at {base_url} (HTTP {})",
+                config.model_provider_id,
+                response.status().as_u16()
+            ),
+        ),
+        Err(err) => Check::failed(
+            NAME,
+            format!("{base_url} is unreachable: {err}"),
+            "Check your network connection, proxy settings (HTTPS_PROXY), and the provider's base_url.",
+        ),
+    }
+}
+
+fn check_bundled_assets(config: &Config) -> Check {
+    const NAME: &str = "assets";
+    match verify_bundled_assets(config) {
+        Ok(summary) => Check::ok(
+            NAME,
+            format!(
+                "{} bundled files ({} KiB), {} model entries, {} tool schemas",
+                summary.files,
+                summary.bytes / 1024,
+                summary.models,
+                summary.tools
+            ),
+        ),
+        Err(err) => Check::failed(
+            NAME,
+            err,
+            "Reinstall Codex from an official release or rebuild it from a clean checkout.",
+        ),
+    }
+}
+
+async fn check_offline_provider(config: &Config) -> Check {
+    const NAME: &str = "provider";
+    let Some(base_url) = config.model_provider.base_url.as_deref() else {
+        return Check::failed(
+            NAME,
+            format!(
+                "provider {} has no base_url and uses the public OpenAI API",
+                config.model_provider_id
+            ),
+            "Set `model_provider` to a provider whose base_url points at your local model server (e.g. `ollama`).",
+        );
+    };
+    if is_public_openai_url(base_url) {
+        return Check::failed(
+            NAME,
+            format!("{base_url} requires internet access"),
+            "Point the provider's base_url at a model server inside your network.",
+        );
+    }
+    match create_client()
+        .get(base_url)
+        .timeout(PROBE_TIMEOUT)
+        .send()
+        .await
+    {
+        Ok(response) => Check::ok(
+            NAME,
+            format!(
+                "{} reachable at {base_url} (HTTP {})",
+                config.model_provider_id,
+                response.status().as_u16()
+            ),
+        ),
+        Err(err) => Check::failed(
+            NAME,
+            format!("{base_url} is unreachable: {err}"),
+            "Start the local model server or fix the provider's base_url.",
+        ),
+    }
+}
+
+fn is_public_openai_url(base_url: &str) -> bool {
+    [OPENAI_BASE_URL, CHATGPT_BASE_URL]
+        .iter()
+        .any(|public| base_url.trim_end_matches('/').starts_with(public))
+}
+
+/// Features that reach the internet on their own; they fail or stall when
+/// there is none.
+fn check_network_features(config: &Config) -> Check {
+    const NAME: &str = "network";
+    let mut online = Vec::new();
+    if config.check_for_update_on_startup {
+        online.push("check_for_update_on_startup");
+    }
+    if config.features.enabled(Feature::RemoteModels) {
+        online.push("features.remote_models");
+    }
+    if config.features.enabled(Feature::WebSearchRequest) {
+        online.push("features.web_search_request");
+    }
+    if online.is_empty() {
+        return Check::ok(NAME, "no enabled feature needs internet access");
+    }
+    Check::warning(
+        NAME,
+        format!("{} need internet access", online.join(", ")),
+        "Turn them off in config.toml for air-gapped use.",
+    )
+}
+
+fn provider_base_url(configured: Option<&str>, auth_mode: Option<AuthMode>) -> String {
+    match (configured, auth_mode) {
+        (Some(base_url), _) => base_url.to_string(),
+        (None, Some(AuthMode::ChatGPT)) => CHATGPT_BASE_URL.to_string(),
+        (None, _) => OPENAI_BASE_URL.to_string(),
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn check_sandbox(_linux_sandbox_exe: Option<&Path>) -> Check {
+    const NAME: &str = "sandbox";
+    const SEATBELT: &str = "/usr/bin/sandbox-exec";
+    if Path::new(SEATBELT).exists() {
+        Check::ok(NAME, format!("seatbelt available at {SEATBELT}"))
+    } else {
+        Check::failed(
+            NAME,
+            format!("{SEATBELT} not found"),
+            "Seatbelt ships with macOS; reinstall the command line tools or run with --sandbox danger-full-access at your own risk.",
+        )
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn check_sandbox(linux_sandbox_exe: Option<&Path>) -> Check {
+    const NAME: &str = "sandbox";
+    let Some(exe) = linux_sandbox_exe.filter(|exe| exe.exists()) else {
+        return Check::failed(
+            NAME,
+            "codex-linux-sandbox helper not found",
+            "Reinstall Codex so the sandbox helper ships alongside the binary.",
+        );
+    };
+    match std::fs::read_to_string("/sys/kernel/security/lsm") {
+        Ok(lsm) if landlock_enabled(&lsm) => Check::ok(
+            NAME,
+            format!("landlock enabled, helper at {}", exe.display()),
+        ),
+        Ok(_) => Check::failed(
+            NAME,
+            "landlock is not enabled in this kernel",
+            "Use a kernel with CONFIG_SECURITY_LANDLOCK and add `landlock` to the lsm= boot parameter.",
+        ),
+        Err(err) => Check::warning(
+            NAME,
+            format!("could not read /sys/kernel/security/lsm: {err}"),
+            "Run `codex sandbox linux -- true` to confirm the sandbox works.",
+        ),
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
+fn check_sandbox(_linux_sandbox_exe: Option<&Path>) -> Check {
+    Check::warning(
+        "sandbox",
+        "sandbox tooling is not checked on this platform",
+        "Run `codex sandbox windows -- whoami` to confirm the sandbox works.",
+    )
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn landlock_enabled(lsm: &str) -> bool {
+    lsm.trim().split(',').any(|module| module == "landlock")
+}
+
+async fn check_git() -> Check {
+    const NAME: &str = "git";
+    let output = tokio::process::Command::new("git")
+        .arg("--version")
+        .output()
+        .await;
+    match output {
+        Ok(output) if output.status.success() => Check::ok(
+            NAME,
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ),
+        Ok(output) => Check::warning(
+            NAME,
+            format!("`git --version` exited with {}", output.status),
+            "Reinstall git; Codex uses it for diffs, undo, and review.",
+        ),
+        Err(_) => Check::warning(
+            NAME,
+            "git not found on PATH",
+            "Install git; Codex uses it for diffs, undo, and review.",
+        ),
+    }
+}
+
+fn render_report(checks: &[Check], color_enabled: bool) -> String {
+    let mut report = String::new();
+    for check in checks {
+        let marker = match (check.status, color_enabled) {
+            (Status::Ok, true) => "✓".green().to_string(),
+            (Status::Warning, true) => "!".yellow().to_string(),
+            (Status::Failed, true) => "✗".red().to_string(),
+            (Status::Ok, false) => "✓".to_string(),
+            (Status::Warning, false) => "!".to_string(),
+            (Status::Failed, false) => "✗".to_string(),
+        };
+        report.push_str(&format!("{marker} {}: {}\n", check.name, check.detail));
+        if let Some(fix) = &check.fix {
+            report.push_str(&format!("    fix: {fix}\n"));
+        }
+    }
+    let failed = checks
+        .iter()
+        .filter(|check| check.status == Status::Failed)
+        .count();
+    if failed == 0 {
+        report.push_str("\nNo problems found.\n");
+    } else {
+        let noun = if failed == 1 { "problem" } else { "problems" };
+        report.push_str(&format!("\n{failed} {noun} found.\n"));
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn report_lists_fixes_and_counts_failures() {
+        let checks = vec![
+            Check::ok("git", "git version 2.43.0"),
+            Check::failed("auth", "not logged in", "Run `codex login`."),
+        ];
+        let report = render_report(&checks, false);
+        assert!(report.starts_with("✓ git: git version 2.43.0\n"));
+        assert!(report.contains("✗ auth: not logged in\n    fix: Run `codex login`.\n"));
+        assert!(report.ends_with("\n1 problem found.\n"));
+    }
+
+    #[test]
+    fn provider_base_url_follows_auth_mode() {
+        assert_eq!(provider_base_url(None, None), OPENAI_BASE_URL);
+        assert_eq!(
+            provider_base_url(None, Some(AuthMode::ChatGPT)),
+            CHATGPT_BASE_URL
+        );
+        assert_eq!(
+            provider_base_url(Some("http://localhost:11434/v1"), Some(AuthMode::ChatGPT)),
+            "http://localhost:11434/v1"
+        );
+    }
+
+    #[test]
+    fn offline_check_rejects_public_openai_endpoints() {
+        assert!(is_public_openai_url("https://api.openai.com/v1/"));
+        assert!(is_public_openai_url(CHATGPT_BASE_URL));
+        assert!(!is_public_openai_url("http://localhost:11434/v1"));
+    }
+
+    #[test]
+    fn detects_landlock_in_lsm_list() {
+        assert!(landlock_enabled(
+            "lockdown,capability,landlock,yama,apparmor\n"
+        ));
+        assert!(!landlock_enabled("capability,selinux"));
+    }
+
+    #[test]
+    fn codex_home_probe_leaves_no_file() {
+        let dir = tempdir().expect("tempdir");
+        let check = check_codex_home(dir.path());
+        assert_eq!(check.status, Status::Ok);
+        assert_eq!(std::fs::read_dir(dir.path()).expect("read_dir").count(), 0);
+    }
+}
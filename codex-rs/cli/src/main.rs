@@ -1,8 +1,6 @@
 use clap::Args;
 use clap::CommandFactory;
 use clap::Parser;
-use clap_complete::Shell;
-use clap_complete::generate;
 use codex_arg0::arg0_dispatch_or_else;
 use codex_chatgpt::apply_command::ApplyCommand;
 use codex_chatgpt::apply_command::run_apply_command;
@@ -20,6 +18,7 @@ use codex_common::CliConfigOverrides;
 use codex_exec::Cli as ExecCli;
 use codex_exec::Command as ExecCommand;
 use codex_exec::ReviewArgs;
+use codex_exec::RunArgs;
 use codex_execpolicy::ExecPolicyCheckCommand;
 use codex_responses_api_proxy::Args as ResponsesApiProxyArgs;
 use codex_tui::AppExitInfo;
@@ -30,11 +29,31 @@ use owo_colors::OwoColorize;
 use std::path::PathBuf;
 use supports_color::Stream;
 
+mod completion;
+mod debug_prompt;
+mod doctor;
 mod mcp_cmd;
+mod memories;
+mod self_update;
+mod stats;
 #[cfg(not(windows))]
 mod wsl_paths;
 
+use crate::completion::CompleteCommand;
+use crate::completion::CompletionCommand;
+use crate::completion::run_complete;
+use crate::completion::write_completion;
+use crate::debug_prompt::DebugPromptCommand;
+use crate::debug_prompt::run_debug_prompt;
+use crate::doctor::DoctorCommand;
+use crate::doctor::run_doctor;
 use crate::mcp_cmd::McpCli;
+use crate::memories::MemoriesCommand;
+use crate::memories::run_memories;
+use crate::self_update::SelfUpdateCommand;
+use crate::self_update::run_self_update;
+use crate::stats::StatsCommand;
+use crate::stats::run_stats;
 
 use codex_core::config::Config;
 use codex_core::config::ConfigOverrides;
@@ -83,12 +102,30 @@ enum Subcommand {
     /// Run a code review non-interactively.
     Review(ReviewArgs),
 
+    /// Run a workflow from config.toml non-interactively.
+    Run(RunArgs),
+
     /// Manage login.
     Login(LoginCommand),
 
     /// Remove stored authentication credentials.
     Logout(LogoutCommand),
 
+    /// Check auth, connectivity, sandbox tooling, and config, and suggest fixes.
+    Doctor(DoctorCommand),
+
+    /// Print the model instructions a new session would use, section by section.
+    DebugPrompt(DebugPromptCommand),
+
+    /// Show the usage stats kept on this machine.
+    Stats(StatsCommand),
+
+    /// List, add, or remove the memories added to every session's instructions.
+    Memories(MemoriesCommand),
+
+    /// Update a standalone Codex binary to the latest release.
+    SelfUpdate(SelfUpdateCommand),
+
     /// [experimental] Run Codex as an MCP server and manage MCP servers.
     Mcp(McpCli),
 
@@ -101,6 +138,10 @@ enum Subcommand {
     /// Generate shell completion scripts.
     Completion(CompletionCommand),
 
+    /// Internal: list dynamic completion candidates for the completion scripts.
+    #[clap(hide = true, name = "__complete")]
+    Complete(CompleteCommand),
+
     /// Run commands within a Codex-provided sandbox.
     #[clap(visible_alias = "debug")]
     Sandbox(SandboxArgs),
@@ -132,13 +173,6 @@ enum Subcommand {
     Features(FeaturesCli),
 }
 
-#[derive(Debug, Parser)]
-struct CompletionCommand {
-    /// Shell to generate completions for
-    #[clap(value_enum, default_value_t = Shell::Bash)]
-    shell: Shell,
-}
-
 #[derive(Debug, Parser)]
 struct ResumeCommand {
     /// Conversation/session id (UUID). When provided, resumes this session.
@@ -252,6 +286,23 @@ enum AppServerSubcommand {
 
     /// [experimental] Generate JSON Schema for the app server protocol.
     GenerateJsonSchema(GenerateJsonSchemaCommand),
+
+    /// Run the app server on a remote machine over SSH and relay its stdio.
+    Ssh(AppServerSshCommand),
+}
+
+#[derive(Debug, Args)]
+struct AppServerSshCommand {
+    /// Destination passed to `ssh`, e.g. `devbox` or `me@devbox`.
+    destination: String,
+
+    /// Command that starts the app server on the remote machine.
+    #[arg(long, value_name = "COMMAND", default_value = codex_app_server::DEFAULT_REMOTE_COMMAND)]
+    remote_command: String,
+
+    /// Extra arguments for `ssh`, e.g. `-- -p 2222 -J bastion`.
+    #[arg(last = true, value_name = "SSH_ARGS")]
+    ssh_args: Vec<String>,
 }
 
 #[derive(Debug, Args)]
@@ -469,6 +520,15 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             );
             codex_exec::run_main(exec_cli, codex_linux_sandbox_exe).await?;
         }
+        Some(Subcommand::Run(run_args)) => {
+            let mut exec_cli = ExecCli::try_parse_from(["codex", "exec"])?;
+            exec_cli.command = Some(ExecCommand::Run(run_args));
+            prepend_config_flags(
+                &mut exec_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            codex_exec::run_main(exec_cli, codex_linux_sandbox_exe).await?;
+        }
         Some(Subcommand::McpServer) => {
             codex_mcp_server::run_main(codex_linux_sandbox_exe, root_config_overrides).await?;
         }
@@ -490,6 +550,17 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             Some(AppServerSubcommand::GenerateJsonSchema(gen_cli)) => {
                 codex_app_server_protocol::generate_json(&gen_cli.out_dir)?;
             }
+            Some(AppServerSubcommand::Ssh(ssh_cli)) => {
+                let code = codex_app_server::run_ssh_tunnel(codex_app_server::SshTunnelOptions {
+                    destination: ssh_cli.destination,
+                    remote_command: ssh_cli.remote_command,
+                    ssh_args: ssh_cli.ssh_args,
+                })
+                .await?;
+                if code != 0 {
+                    std::process::exit(code);
+                }
+            }
         },
         Some(Subcommand::Resume(ResumeCommand {
             session_id,
@@ -546,8 +617,50 @@ async fn cli_main(codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()
             );
             run_logout(logout_cli.config_overrides).await;
         }
+        Some(Subcommand::Doctor(mut doctor_cli)) => {
+            prepend_config_flags(
+                &mut doctor_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            run_doctor(doctor_cli, codex_linux_sandbox_exe).await?;
+        }
+        Some(Subcommand::DebugPrompt(mut debug_prompt_cli)) => {
+            prepend_config_flags(
+                &mut debug_prompt_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            run_debug_prompt(debug_prompt_cli).await?;
+        }
+        Some(Subcommand::Stats(mut stats_cli)) => {
+            prepend_config_flags(
+                &mut stats_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            run_stats(stats_cli).await?;
+        }
+        Some(Subcommand::Memories(mut memories_cli)) => {
+            prepend_config_flags(
+                &mut memories_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            run_memories(memories_cli).await?;
+        }
+        Some(Subcommand::SelfUpdate(mut self_update_cli)) => {
+            prepend_config_flags(
+                &mut self_update_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            run_self_update(self_update_cli).await?;
+        }
         Some(Subcommand::Completion(completion_cli)) => {
-            print_completion(completion_cli);
+            print_completion(completion_cli)?;
+        }
+        Some(Subcommand::Complete(mut complete_cli)) => {
+            prepend_config_flags(
+                &mut complete_cli.config_overrides,
+                root_config_overrides.clone(),
+            );
+            run_complete(complete_cli).await?;
         }
         Some(Subcommand::Cloud(mut cloud_cli)) => {
             prepend_config_flags(
@@ -767,10 +880,9 @@ fn merge_resume_cli_flags(interactive: &mut TuiCli, resume_cli: TuiCli) {
         .extend(resume_cli.config_overrides.raw_overrides);
 }
 
-fn print_completion(cmd: CompletionCommand) {
+fn print_completion(cmd: CompletionCommand) -> std::io::Result<()> {
     let mut app = MultitoolCli::command();
-    let name = "codex";
-    generate(cmd.shell, &mut app, name, &mut std::io::stdout());
+    write_completion(cmd.shell, &mut app, &mut std::io::stdout().lock())
 }
 
 #[cfg(test)]
@@ -1002,4 +1114,39 @@ mod tests {
             .expect_err("feature should be rejected");
         assert_eq!(err.to_string(), "Unknown feature flag: does_not_exist");
     }
+
+    fn completion_script(shell: clap_complete::Shell) -> String {
+        let mut out = Vec::new();
+        write_completion(shell, &mut MultitoolCli::command(), &mut out).expect("generate");
+        String::from_utf8(out).expect("utf8")
+    }
+
+    #[test]
+    fn completion_scripts_wrap_generated_completer() {
+        for shell in [clap_complete::Shell::Bash, clap_complete::Shell::Zsh] {
+            let script = completion_script(shell);
+            assert!(script.contains("_codex() {"), "{shell} completer missing");
+            assert!(
+                script.contains("codex __complete"),
+                "{shell} wrapper missing"
+            );
+        }
+
+        let powershell = completion_script(clap_complete::Shell::PowerShell);
+        assert!(powershell.contains("$codexStaticCompleter = {"));
+        assert_eq!(
+            powershell
+                .matches("Register-ArgumentCompleter -Native -CommandName 'codex'")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn complete_command_is_hidden_from_completions() {
+        let script = completion_script(clap_complete::Shell::Fish);
+        assert!(!script.contains("__complete\""));
+        let cli = MultitoolCli::try_parse_from(["codex", "__complete", "profiles"]).expect("parse");
+        assert_matches!(cli.subcommand, Some(Subcommand::Complete(_)));
+    }
 }
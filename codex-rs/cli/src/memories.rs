@@ -0,0 +1,278 @@
+//! `codex memories`: list and edit the memory stores that are added to the
+//! instructions of every session.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::bail;
+use clap::Parser;
+use clap::ValueEnum;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::git_info::current_branch_name;
+use codex_core::memories::MemoryEntry;
+use codex_core::memories::add_memory;
+use codex_core::memories::clear_memories;
+use codex_core::memories::editable_memory_store;
+use codex_core::memories::import_memories;
+use codex_core::memories::list_memories;
+use codex_core::memories::memories_markdown;
+use codex_core::memories::parse_memories_export;
+use codex_core::memories::remove_memory;
+use codex_core::memories::undo_session_memories;
+use codex_core::memory_log::memory_log_path;
+
+#[derive(Debug, Parser)]
+pub(crate) struct MemoriesCommand {
+    #[command(subcommand)]
+    subcommand: MemoriesSubcommand,
+
+    #[clap(skip)]
+    pub(crate) config_overrides: CliConfigOverrides,
+}
+
+#[derive(Debug, clap::Subcommand)]
+enum MemoriesSubcommand {
+    /// List the memories of every store that is read, numbered per store.
+    List {
+        /// Print the files and their entries as JSON.
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Save a memory, unless the store already holds it.
+    Add {
+        /// Text of the memory.
+        #[arg(required = true, num_args = 1.., value_name = "TEXT")]
+        text: Vec<String>,
+
+        /// Tag the memory with the checked-out branch, so it is only used on
+        /// that branch when `memories.scope_by_branch` is set.
+        #[arg(long)]
+        branch: bool,
+
+        #[command(flatten)]
+        target: TargetArgs,
+    },
+
+    /// Remove a memory by its number in `list` or by its text.
+    Remove {
+        /// Number or text of the memory.
+        #[arg(required = true, num_args = 1.., value_name = "NUMBER|TEXT")]
+        selector: Vec<String>,
+
+        #[command(flatten)]
+        target: TargetArgs,
+    },
+
+    /// Delete every memory in the store.
+    Clear {
+        #[command(flatten)]
+        target: TargetArgs,
+    },
+
+    /// Remove the memories a session saved, per the store's `memories.log`.
+    Undo {
+        /// Id of the session, as shown by `list` and in `memories.log`.
+        #[arg(value_name = "SESSION_ID")]
+        session_id: String,
+
+        #[command(flatten)]
+        target: TargetArgs,
+    },
+
+    /// Print the memories of the store with their metadata, to share them.
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Md)]
+        format: ExportFormat,
+
+        #[command(flatten)]
+        target: TargetArgs,
+    },
+
+    /// Merge the memories of an exported file into the store. Memories it
+    /// already holds are skipped, and so are memories that contradict a
+    /// saved one unless `--keep-conflicts` is set; both are reported.
+    Import {
+        /// File written by `export`, in either format.
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Save memories that contradict a saved one as well.
+        #[arg(long)]
+        keep_conflicts: bool,
+
+        #[command(flatten)]
+        target: TargetArgs,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ExportFormat {
+    Json,
+    Md,
+}
+
+#[derive(Debug, Parser)]
+struct TargetArgs {
+    /// Edit the memories in `$CODEX_HOME` instead of the repository's
+    /// `.codex` directory.
+    #[arg(long)]
+    global: bool,
+}
+
+pub(crate) async fn run_memories(cmd: MemoriesCommand) -> anyhow::Result<()> {
+    let overrides = cmd
+        .config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides).await?;
+    let mut stdout = std::io::stdout().lock();
+
+    match cmd.subcommand {
+        MemoriesSubcommand::List { json } => {
+            let files = list_memories(&config).await?;
+            if json {
+                writeln!(stdout, "{}", serde_json::to_string_pretty(&files)?)?;
+                return Ok(());
+            }
+            if files.iter().all(|file| file.entries.is_empty()) {
+                writeln!(stdout, "No memories saved yet.")?;
+                return Ok(());
+            }
+            for file in files.iter().filter(|file| !file.entries.is_empty()) {
+                writeln!(stdout, "{}:", file.path.display())?;
+                for (index, entry) in file.entries.iter().enumerate() {
+                    writeln!(stdout, "  {:>3}. {}", index + 1, describe(entry))?;
+                }
+            }
+        }
+        MemoriesSubcommand::Add {
+            text,
+            branch,
+            target,
+        } => {
+            let branch = if branch {
+                match current_branch_name(&config.cwd).await {
+                    Some(name) => Some(name),
+                    None => bail!("no branch is checked out in {}", config.cwd.display()),
+                }
+            } else {
+                None
+            };
+            let (store, scope) = editable_memory_store(&config, target.global);
+            let path = store.location();
+            if add_memory(store.as_ref(), &text.join(" "), scope, branch).await? {
+                writeln!(stdout, "Saved to {}.", path.display())?;
+            } else {
+                writeln!(stdout, "{} already holds this memory.", path.display())?;
+            }
+        }
+        MemoriesSubcommand::Remove { selector, target } => {
+            let (store, _) = editable_memory_store(&config, target.global);
+            let path = store.location();
+            let selector = selector.join(" ");
+            match remove_memory(store.as_ref(), &selector).await? {
+                Some(removed) => {
+                    writeln!(stdout, "Removed \"{removed}\" from {}.", path.display())?
+                }
+                None => bail!("no memory matching \"{selector}\" in {}", path.display()),
+            }
+        }
+        MemoriesSubcommand::Clear { target } => {
+            let (store, _) = editable_memory_store(&config, target.global);
+            let path = store.location();
+            if clear_memories(store.as_ref()).await? {
+                writeln!(stdout, "Cleared {}.", path.display())?;
+            } else {
+                writeln!(stdout, "No memories in {}.", path.display())?;
+            }
+        }
+        MemoriesSubcommand::Undo { session_id, target } => {
+            let (store, _) = editable_memory_store(&config, target.global);
+            let path = store.location();
+            let removed = undo_session_memories(store.as_ref(), &session_id).await?;
+            if removed.is_empty() {
+                writeln!(
+                    stdout,
+                    "{} records no memories of session {session_id} left in {}.",
+                    memory_log_path(path).display(),
+                    path.display()
+                )?;
+                return Ok(());
+            }
+            for text in &removed {
+                writeln!(stdout, "Removed \"{text}\".")?;
+            }
+            writeln!(
+                stdout,
+                "Removed {} memories of session {session_id} from {}.",
+                removed.len(),
+                path.display()
+            )?;
+        }
+        MemoriesSubcommand::Export { format, target } => {
+            let (store, _) = editable_memory_store(&config, target.global);
+            let entries = store.entries(usize::MAX).await?;
+            match format {
+                ExportFormat::Json => {
+                    writeln!(stdout, "{}", serde_json::to_string_pretty(&entries)?)?
+                }
+                ExportFormat::Md => write!(stdout, "{}", memories_markdown(&entries))?,
+            }
+        }
+        MemoriesSubcommand::Import {
+            file,
+            keep_conflicts,
+            target,
+        } => {
+            let text = std::fs::read_to_string(&file)
+                .with_context(|| format!("failed to read {}", file.display()))?;
+            let entries = parse_memories_export(&text)?;
+            let (store, scope) = editable_memory_store(&config, target.global);
+            let path = store.location();
+            let report = import_memories(store.as_ref(), entries, scope, keep_conflicts).await?;
+            for conflict in &report.conflicts {
+                let outcome = if keep_conflicts { "saved" } else { "skipped" };
+                writeln!(
+                    stdout,
+                    "Conflict ({outcome}): \"{}\" contradicts \"{}\".",
+                    conflict.imported, conflict.existing
+                )?;
+            }
+            writeln!(
+                stdout,
+                "Imported {} memories into {} ({} already saved, {} conflicting).",
+                report.imported,
+                path.display(),
+                report.duplicates,
+                report.conflicts.len()
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// The entry's text followed by its category and when, in which session and
+/// for which branch it was recorded, when the file says.
+fn describe(entry: &MemoryEntry) -> String {
+    let mut details = Vec::new();
+    if let Some(category) = &entry.category {
+        details.push(category.clone());
+    }
+    if let Some(created_at) = entry.created_at {
+        details.push(created_at.format("%Y-%m-%d").to_string());
+    }
+    if let Some(session_id) = &entry.session_id {
+        details.push(format!("session {session_id}"));
+    }
+    if let Some(branch) = &entry.branch {
+        details.push(format!("branch {branch}"));
+    }
+    if details.is_empty() {
+        entry.text.clone()
+    } else {
+        format!("{} ({})", entry.text, details.join(", "))
+    }
+}
@@ -0,0 +1,424 @@
+//! `codex self-update`: replace a standalone Codex binary with the newest
+//! release on the configured channel.
+//!
+//! Release archives are signed with minisign; the signature is checked
+//! against [`RELEASE_PUBLIC_KEY`] before anything on disk is touched.
+
+use std::ffi::OsStr;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Parser;
+use clap::ValueEnum;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::config::types::UpdateChannel;
+use codex_core::default_client::create_client;
+use codex_tui::releases;
+use codex_tui::releases::Release;
+use codex_tui::update_action::get_update_action;
+use minisign_verify::PublicKey;
+use minisign_verify::Signature;
+
+const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Public half of the minisign key that signs release archives in CI. It was
+/// made with `minisign -G`; the secret half is the `CODEX_MINISIGN_SECRET_KEY`
+/// secret of `.github/workflows/rust-release.yml`, and rotating the key means
+/// replacing both together.
+const RELEASE_PUBLIC_KEY: &str = "RWSVlgzyICd3+rqkdC6qGZ9CCnR3PQ5oBLPg4vfZDxYzhgp+yCtgJwNe";
+
+#[derive(Debug, Parser)]
+pub(crate) struct SelfUpdateCommand {
+    /// Release channel to update from. Defaults to `update_channel` in config.toml.
+    #[arg(long, value_enum)]
+    channel: Option<ChannelArg>,
+
+    /// Only report whether an update is available.
+    #[arg(long, default_value_t = false)]
+    check: bool,
+
+    #[clap(skip)]
+    pub(crate) config_overrides: CliConfigOverrides,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ChannelArg {
+    Stable,
+    Beta,
+}
+
+impl From<ChannelArg> for UpdateChannel {
+    fn from(value: ChannelArg) -> Self {
+        match value {
+            ChannelArg::Stable => UpdateChannel::Stable,
+            ChannelArg::Beta => UpdateChannel::Beta,
+        }
+    }
+}
+
+pub(crate) async fn run_self_update(cmd: SelfUpdateCommand) -> anyhow::Result<()> {
+    let overrides = cmd
+        .config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides).await?;
+    let channel = cmd
+        .channel
+        .map(UpdateChannel::from)
+        .unwrap_or(config.update_channel);
+
+    let current_exe = std::env::current_exe()?;
+    if let Some(action) = get_update_action() {
+        anyhow::bail!(
+            "Codex is managed by a package manager. Run `{}` to update.",
+            action.command_str()
+        );
+    }
+    if cfg!(debug_assertions) || is_cargo_install(&current_exe) {
+        anyhow::bail!(
+            "This Codex binary was built locally; self-update only replaces release binaries."
+        );
+    }
+
+    let release = releases::fetch_latest_release(channel).await?;
+    let latest_version = release.version()?;
+    if !releases::is_newer(&latest_version, CURRENT_VERSION) {
+        println!("Codex {CURRENT_VERSION} is up to date on the {channel} channel.");
+        return Ok(());
+    }
+    if cmd.check {
+        println!(
+            "Codex {latest_version} is available on the {channel} channel (current: {CURRENT_VERSION}). Run `codex self-update` to install it."
+        );
+        return Ok(());
+    }
+
+    let target = release_target().context("No release binaries are published for this platform")?;
+    println!("Downloading Codex {latest_version} ({target})...");
+    let binary = download_verified_binary(&release, target).await?;
+
+    install_binary(&binary, &current_exe)
+        .with_context(|| format!("Failed to replace {}", current_exe.display()))?;
+    println!(
+        "Updated Codex {CURRENT_VERSION} -> {latest_version}. Restart Codex to use the new version."
+    );
+    Ok(())
+}
+
+async fn download_verified_binary(release: &Release, target: &str) -> anyhow::Result<Vec<u8>> {
+    let asset_name = asset_name(target);
+    let signature_name = format!("{asset_name}.minisig");
+    let asset = release
+        .asset(&asset_name)
+        .with_context(|| format!("Release {} has no {asset_name}", release.tag_name))?;
+    let signature_asset = release
+        .asset(&signature_name)
+        .with_context(|| format!("Release {} has no {signature_name}", release.tag_name))?;
+
+    let client = create_client();
+    let archive = client
+        .get(asset.browser_download_url.as_str())
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    let signature = client
+        .get(signature_asset.browser_download_url.as_str())
+        .send()
+        .await?
+        .error_for_status()?
+        .text()
+        .await?;
+
+    verify_signature(
+        RELEASE_PUBLIC_KEY,
+        &archive,
+        &signature,
+        &asset_name,
+        &release.tag_name,
+    )?;
+    extract_binary(&archive, target)
+}
+
+/// Check `signature` over `data` and that its trusted comment names
+/// `asset_name` and the release `tag`, so a validly signed archive for
+/// another platform or an older release cannot be swapped in under this name.
+fn verify_signature(
+    public_key: &str,
+    data: &[u8],
+    signature: &str,
+    asset_name: &str,
+    tag: &str,
+) -> anyhow::Result<()> {
+    let public_key = PublicKey::from_base64(public_key)
+        .map_err(|err| anyhow::anyhow!("Invalid release public key: {err}"))?;
+    let signature = Signature::decode(signature)
+        .map_err(|err| anyhow::anyhow!("Invalid release signature: {err}"))?;
+    public_key
+        .verify(data, &signature, false)
+        .map_err(|err| anyhow::anyhow!("Release signature verification failed: {err}"))?;
+    let signed_field = |name: &str| {
+        signature
+            .trusted_comment()
+            .split('\t')
+            .find_map(|field| field.strip_prefix(name)?.strip_prefix(':'))
+    };
+    let signed_file = signed_field("file");
+    if signed_file != Some(asset_name) {
+        anyhow::bail!(
+            "Release signature is for {}, not {asset_name}",
+            signed_file.unwrap_or("an unnamed file")
+        );
+    }
+    let signed_version = signed_field("version");
+    if signed_version != Some(tag) {
+        anyhow::bail!(
+            "Release signature is for {}, not {tag}",
+            signed_version.unwrap_or("an unversioned release")
+        );
+    }
+    Ok(())
+}
+
+/// `cargo install` puts binaries under `~/.cargo`; those are updated by
+/// reinstalling from source.
+fn is_cargo_install(current_exe: &Path) -> bool {
+    current_exe
+        .components()
+        .any(|component| component.as_os_str() == ".cargo")
+}
+
+/// Target triple of the release binaries that run on this platform.
+fn release_target() -> Option<&'static str> {
+    if cfg!(all(target_os = "linux", target_arch = "x86_64")) {
+        Some("x86_64-unknown-linux-musl")
+    } else if cfg!(all(target_os = "linux", target_arch = "aarch64")) {
+        Some("aarch64-unknown-linux-musl")
+    } else if cfg!(all(target_os = "macos", target_arch = "x86_64")) {
+        Some("x86_64-apple-darwin")
+    } else if cfg!(all(target_os = "macos", target_arch = "aarch64")) {
+        Some("aarch64-apple-darwin")
+    } else if cfg!(all(target_os = "windows", target_arch = "x86_64")) {
+        Some("x86_64-pc-windows-msvc")
+    } else if cfg!(all(target_os = "windows", target_arch = "aarch64")) {
+        Some("aarch64-pc-windows-msvc")
+    } else {
+        None
+    }
+}
+
+/// Windows releases ship the raw executable; everything else is a `.tar.gz`
+/// holding a single `codex-<target>` binary.
+fn asset_name(target: &str) -> String {
+    if target.contains("windows") {
+        format!("codex-{target}.exe")
+    } else {
+        format!("codex-{target}.tar.gz")
+    }
+}
+
+fn extract_binary(archive: &[u8], target: &str) -> anyhow::Result<Vec<u8>> {
+    if target.contains("windows") {
+        return Ok(archive.to_vec());
+    }
+    let binary_name = format!("codex-{target}");
+    let mut tar = tar::Archive::new(flate2::read::GzDecoder::new(archive));
+    for entry in tar.entries()? {
+        let mut entry = entry?;
+        if entry.path()?.file_name() == Some(OsStr::new(&binary_name)) {
+            let mut binary = Vec::new();
+            entry.read_to_end(&mut binary)?;
+            return Ok(binary);
+        }
+    }
+    anyhow::bail!("{binary_name} not found in the release archive")
+}
+
+/// Write `binary` next to `current_exe` and move it into place. The rename
+/// keeps the running process intact on Unix; Windows cannot overwrite a
+/// running executable, so the old one is moved aside first.
+fn install_binary(binary: &[u8], current_exe: &Path) -> anyhow::Result<()> {
+    let staged = sibling_path(current_exe, "update");
+    std::fs::write(&staged, binary)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    #[cfg(windows)]
+    let previous = {
+        let previous = sibling_path(current_exe, "old");
+        let _ = std::fs::remove_file(&previous);
+        if let Err(err) = std::fs::rename(current_exe, &previous) {
+            let _ = std::fs::remove_file(&staged);
+            return Err(err.into());
+        }
+        previous
+    };
+
+    if let Err(err) = std::fs::rename(&staged, current_exe) {
+        let _ = std::fs::remove_file(&staged);
+        // Put the old binary back so a failed swap leaves Codex installed.
+        #[cfg(windows)]
+        let _ = std::fs::rename(&previous, current_exe);
+        return Err(err.into());
+    }
+    Ok(())
+}
+
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{suffix}"));
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    const TEST_PUBLIC_KEY: &str = "RWRTFTrTo6j+jFpAVFduBkczN5fwqdCej7tmVvQOULE6soNR7fxIBfz0";
+    const TEST_DATA: &[u8] = b"codex release archive";
+    const TEST_ASSET: &str = "codex-x86_64-unknown-linux-musl.tar.gz";
+    const TEST_TAG: &str = "rust-v0.60.0";
+    const TEST_SIGNATURE: &str = concat!(
+        "untrusted comment: signature from minisign secret key\n",
+        "RURTFTrTo6j+jJmbXSeM1aP8PLGBE0FHFeFj0wqrRCKGVLTqLMg94jFbao8fyGwoFQHNOOqCHLokbYHwdf0QKpZHSpwWkJ7gfAY=\n",
+        "trusted comment: timestamp:1760000000\tfile:codex-x86_64-unknown-linux-musl.tar.gz\tversion:rust-v0.60.0\n",
+        "cDOhM8gBNb1DP0FNy6FovpnW2TYNxcIVegGAe7W7yADR8qFJV15IgLgtj/7xPxsbZadav/7hnPqWogHxj7zgAw==\n",
+    );
+
+    #[test]
+    fn accepts_valid_signature() {
+        verify_signature(
+            TEST_PUBLIC_KEY,
+            TEST_DATA,
+            TEST_SIGNATURE,
+            TEST_ASSET,
+            TEST_TAG,
+        )
+        .expect("valid signature");
+    }
+
+    #[test]
+    fn rejects_tampered_archive() {
+        let err = verify_signature(
+            TEST_PUBLIC_KEY,
+            b"tampered archive",
+            TEST_SIGNATURE,
+            TEST_ASSET,
+            TEST_TAG,
+        )
+        .expect_err("tampered data must not verify");
+        assert!(
+            err.to_string()
+                .starts_with("Release signature verification failed")
+        );
+    }
+
+    #[test]
+    fn rejects_signature_from_another_key() {
+        assert!(
+            verify_signature(
+                RELEASE_PUBLIC_KEY,
+                TEST_DATA,
+                TEST_SIGNATURE,
+                TEST_ASSET,
+                TEST_TAG,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_signature_for_another_asset() {
+        let err = verify_signature(
+            TEST_PUBLIC_KEY,
+            TEST_DATA,
+            TEST_SIGNATURE,
+            "codex-aarch64-apple-darwin.tar.gz",
+            TEST_TAG,
+        )
+        .expect_err("the signature names another asset");
+        assert_eq!(
+            err.to_string(),
+            "Release signature is for codex-x86_64-unknown-linux-musl.tar.gz, not codex-aarch64-apple-darwin.tar.gz"
+        );
+    }
+
+    #[test]
+    fn rejects_signature_for_another_release() {
+        let err = verify_signature(
+            TEST_PUBLIC_KEY,
+            TEST_DATA,
+            TEST_SIGNATURE,
+            TEST_ASSET,
+            "rust-v0.61.0",
+        )
+        .expect_err("an older release must not verify under a newer tag");
+        assert_eq!(
+            err.to_string(),
+            "Release signature is for rust-v0.60.0, not rust-v0.61.0"
+        );
+    }
+
+    #[test]
+    fn detects_cargo_installs() {
+        assert!(is_cargo_install(Path::new("/home/me/.cargo/bin/codex")));
+        assert!(!is_cargo_install(Path::new("/usr/local/bin/codex")));
+    }
+
+    #[test]
+    fn asset_names_match_release_artifacts() {
+        assert_eq!(
+            asset_name("aarch64-apple-darwin"),
+            "codex-aarch64-apple-darwin.tar.gz"
+        );
+        assert_eq!(
+            asset_name("x86_64-pc-windows-msvc"),
+            "codex-x86_64-pc-windows-msvc.exe"
+        );
+    }
+
+    #[test]
+    fn extracts_binary_from_tarball() {
+        let target = "x86_64-unknown-linux-musl";
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        let contents = b"#!/bin/sh\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(contents.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, format!("codex-{target}"), &contents[..])
+            .expect("append");
+        let archive = builder.into_inner().expect("tar").finish().expect("gzip");
+
+        assert_eq!(
+            extract_binary(&archive, target).expect("extract"),
+            contents.to_vec()
+        );
+    }
+
+    #[test]
+    fn install_replaces_binary_in_place() {
+        let dir = tempdir().expect("tempdir");
+        let exe = dir.path().join("codex");
+        std::fs::write(&exe, b"old").expect("write");
+
+        install_binary(b"new", &exe).expect("install");
+
+        assert_eq!(std::fs::read(&exe).expect("read"), b"new");
+        assert!(!sibling_path(&exe, "update").exists());
+    }
+}
@@ -0,0 +1,84 @@
+//! `codex stats`: show the usage counts kept in `$CODEX_HOME/stats.json`.
+//! They are only ever read here; see `telemetry` in config.toml.
+
+use std::io::Write;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use clap::Parser;
+use codex_common::CliConfigOverrides;
+use codex_core::config::Config;
+use codex_core::usage_stats::load_usage_stats;
+use codex_core::usage_stats::reset_usage_stats;
+
+#[derive(Debug, Parser)]
+pub(crate) struct StatsCommand {
+    /// Print the stats as JSON.
+    #[arg(long, conflicts_with = "reset")]
+    json: bool,
+
+    /// Delete the stats collected so far.
+    #[arg(long)]
+    reset: bool,
+
+    #[clap(skip)]
+    pub(crate) config_overrides: CliConfigOverrides,
+}
+
+pub(crate) async fn run_stats(cmd: StatsCommand) -> anyhow::Result<()> {
+    let overrides = cmd
+        .config_overrides
+        .parse_overrides()
+        .map_err(anyhow::Error::msg)?;
+    let config = Config::load_with_cli_overrides(overrides).await?;
+    let mut stdout = std::io::stdout().lock();
+
+    if cmd.reset {
+        if reset_usage_stats(&config.codex_home).await? {
+            writeln!(stdout, "Usage stats cleared.")?;
+        } else {
+            writeln!(stdout, "No usage stats to clear.")?;
+        }
+        return Ok(());
+    }
+
+    let stats = load_usage_stats(&config.codex_home).await?;
+    if cmd.json {
+        writeln!(stdout, "{}", serde_json::to_string_pretty(&stats)?)?;
+        return Ok(());
+    }
+    if stats.sessions == 0 {
+        writeln!(stdout, "No usage recorded yet.")?;
+        if !config.telemetry.local_stats {
+            writeln!(
+                stdout,
+                "Local stats are off; set `telemetry.local_stats = true` to collect them."
+            )?;
+        }
+        return Ok(());
+    }
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() as i64)
+        .unwrap_or_default();
+    let days = (now - stats.since).max(0) / 86_400;
+    writeln!(stdout, "Usage over the last {days} day(s):")?;
+    writeln!(stdout, "  sessions           {}", stats.sessions)?;
+    writeln!(stdout, "  turns              {}", stats.turns)?;
+    writeln!(stdout, "  tool calls         {}", stats.tool_calls)?;
+    writeln!(stdout, "  failed tool calls  {}", stats.failed_tool_calls)?;
+    if !stats.failures.is_empty() {
+        writeln!(stdout, "Errors by category:")?;
+        for (category, count) in &stats.failures {
+            writeln!(stdout, "  {category:<18} {count}")?;
+        }
+    }
+    if !config.telemetry.local_stats {
+        writeln!(
+            stdout,
+            "\nLocal stats are off, so these counts are no longer updated."
+        )?;
+    }
+    Ok(())
+}
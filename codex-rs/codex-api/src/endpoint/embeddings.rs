@@ -0,0 +1,143 @@
+use crate::auth::AuthProvider;
+use crate::auth::add_auth_headers;
+use crate::error::ApiError;
+use crate::provider::Provider;
+use crate::telemetry::run_with_request_telemetry;
+use codex_client::HttpTransport;
+use codex_client::RequestTelemetry;
+use http::HeaderMap;
+use http::Method;
+use serde::Deserialize;
+use serde::Serialize;
+use std::sync::Arc;
+
+pub struct EmbeddingsClient<T: HttpTransport, A: AuthProvider> {
+    transport: T,
+    provider: Provider,
+    auth: A,
+    request_telemetry: Option<Arc<dyn RequestTelemetry>>,
+}
+
+impl<T: HttpTransport, A: AuthProvider> EmbeddingsClient<T, A> {
+    pub fn new(transport: T, provider: Provider, auth: A) -> Self {
+        Self {
+            transport,
+            provider,
+            auth,
+            request_telemetry: None,
+        }
+    }
+
+    pub fn with_telemetry(mut self, request: Option<Arc<dyn RequestTelemetry>>) -> Self {
+        self.request_telemetry = request;
+        self
+    }
+
+    /// Embeds `input` with `model`, returning one vector per input in the
+    /// same order.
+    pub async fn embed(
+        &self,
+        model: &str,
+        input: &[String],
+        extra_headers: HeaderMap,
+    ) -> Result<Vec<Vec<f32>>, ApiError> {
+        if input.is_empty() {
+            return Ok(Vec::new());
+        }
+        let body = serde_json::to_value(EmbeddingsRequest { model, input })
+            .map_err(|e| ApiError::Stream(format!("failed to encode embeddings request: {e}")))?;
+        let builder = || {
+            let mut req = self.provider.build_request(Method::POST, "embeddings");
+            req.headers.extend(extra_headers.clone());
+            req.body = Some(body.clone());
+            add_auth_headers(&self.auth, req)
+        };
+
+        let resp = run_with_request_telemetry(
+            self.provider.retry.to_policy(),
+            self.request_telemetry.clone(),
+            builder,
+            |req| self.transport.execute(req),
+        )
+        .await?;
+        let parsed: EmbeddingsResponse =
+            serde_json::from_slice(&resp.body).map_err(|e| ApiError::Stream(e.to_string()))?;
+        order_embeddings(parsed.data, input.len())
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct EmbeddingsRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingsResponse {
+    data: Vec<EmbeddingData>,
+}
+
+#[derive(Debug, Deserialize)]
+struct EmbeddingData {
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+/// The API does not guarantee response order, so place each vector by its
+/// `index` and reject responses that do not cover every input.
+fn order_embeddings(data: Vec<EmbeddingData>, expected: usize) -> Result<Vec<Vec<f32>>, ApiError> {
+    let mut ordered: Vec<Option<Vec<f32>>> = vec![None; expected];
+    for item in data {
+        let Some(slot) = ordered.get_mut(item.index) else {
+            return Err(ApiError::Stream(format!(
+                "embedding index {} out of range for {expected} inputs",
+                item.index
+            )));
+        };
+        *slot = Some(item.embedding);
+    }
+    ordered
+        .into_iter()
+        .enumerate()
+        .map(|(index, embedding)| {
+            embedding
+                .ok_or_else(|| ApiError::Stream(format!("missing embedding for input {index}")))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn orders_embeddings_by_index() {
+        let data = vec![
+            EmbeddingData {
+                index: 1,
+                embedding: vec![2.0],
+            },
+            EmbeddingData {
+                index: 0,
+                embedding: vec![1.0],
+            },
+        ];
+        assert_eq!(
+            order_embeddings(data, 2).expect("ordered"),
+            vec![vec![1.0], vec![2.0]]
+        );
+    }
+
+    #[test]
+    fn rejects_missing_embeddings() {
+        let data = vec![EmbeddingData {
+            index: 0,
+            embedding: vec![1.0],
+        }];
+        match order_embeddings(data, 2) {
+            Err(ApiError::Stream(msg)) => assert_eq!(msg, "missing embedding for input 1"),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+}
@@ -1,5 +1,6 @@
 pub mod chat;
 pub mod compact;
+pub mod embeddings;
 pub mod models;
 pub mod responses;
 mod streaming;
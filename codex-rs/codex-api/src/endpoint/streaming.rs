@@ -64,6 +64,9 @@ impl<T: HttpTransport, A: AuthProvider> StreamingClient<T, A> {
             req.body = Some(body.clone());
             add_auth_headers(&self.auth, req)
         };
+        if let Some(telemetry) = self.sse_telemetry.as_ref() {
+            telemetry.on_stream_request(path, &body);
+        }
 
         let stream_response = run_with_request_telemetry(
             self.provider.retry.to_policy(),
@@ -6,6 +6,7 @@ use codex_client::StreamResponse;
 use codex_client::TransportError;
 use codex_client::run_with_retry;
 use http::StatusCode;
+use serde_json::Value;
 use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
@@ -26,6 +27,10 @@ pub trait SseTelemetry: Send + Sync {
         >,
         duration: Duration,
     );
+
+    /// Called once with the JSON body of each streaming request, before the
+    /// first attempt is sent.
+    fn on_stream_request(&self, _path: &str, _body: &Value) {}
 }
 
 pub(crate) trait WithStatus {
@@ -1,3 +1,4 @@
+use codex_core::protocol::ApprovalPresetId;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::SandboxPolicy;
 
@@ -5,7 +6,7 @@ use codex_core::protocol::SandboxPolicy;
 #[derive(Debug, Clone)]
 pub struct ApprovalPreset {
     /// Stable identifier for the preset.
-    pub id: &'static str,
+    pub id: ApprovalPresetId,
     /// Display label shown in UIs.
     pub label: &'static str,
     /// Short human description shown next to the label in UIs.
@@ -20,27 +21,31 @@ pub struct ApprovalPreset {
 ///
 /// Keep this UI-agnostic so it can be reused by both TUI and MCP server.
 pub fn builtin_approval_presets() -> Vec<ApprovalPreset> {
-    vec![
-        ApprovalPreset {
-            id: "read-only",
-            label: "Read Only",
-            description: "Requires approval to edit files and run commands.",
-            approval: AskForApproval::OnRequest,
-            sandbox: SandboxPolicy::ReadOnly,
-        },
-        ApprovalPreset {
-            id: "auto",
-            label: "Agent",
-            description: "Read and edit files, and run commands.",
-            approval: AskForApproval::OnRequest,
-            sandbox: SandboxPolicy::new_workspace_write_policy(),
-        },
-        ApprovalPreset {
-            id: "full-access",
-            label: "Agent (full access)",
-            description: "Codex can edit files outside this workspace and run commands with network access. Exercise caution when using.",
-            approval: AskForApproval::Never,
-            sandbox: SandboxPolicy::DangerFullAccess,
-        },
-    ]
+    ApprovalPresetId::ALL
+        .into_iter()
+        .map(|id| {
+            let (label, description) = match id {
+                ApprovalPresetId::ReadOnly => (
+                    "Read Only",
+                    "Requires approval to edit files and run commands.",
+                ),
+                ApprovalPresetId::AutoEdit => ("Auto Edit", "Asks before edits and unrecognized commands."),
+                ApprovalPresetId::FullAuto => (
+                    "Full Auto",
+                    "Read and edit files, and run commands.",
+                ),
+                ApprovalPresetId::Yolo => (
+                    "YOLO",
+                    "Codex can edit files outside this workspace and run commands with network access. Exercise caution when using.",
+                ),
+            };
+            ApprovalPreset {
+                id,
+                label,
+                description,
+                approval: id.approval_policy(),
+                sandbox: id.sandbox_policy(),
+            }
+        })
+        .collect()
 }
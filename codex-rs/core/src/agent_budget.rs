@@ -0,0 +1,146 @@
+//! Resource ceilings shared by a session and the sub-agents it spawns.
+//!
+//! Every session owns an [`AgentBudget`]. A sub-agent gets a child of its
+//! parent's budget: one level deeper, sharing the tree's token counter, and
+//! with a deadline no later than its parent's. Sub-agents therefore cannot
+//! outlive, outspend, or out-nest the limits of the session that started the
+//! tree, however many of them there are.
+
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use crate::config::types::SubAgentLimits;
+use crate::error::CodexErr;
+
+#[derive(Debug)]
+pub(crate) struct AgentBudget {
+    /// 0 for the session the user started.
+    depth: u32,
+    limits: SubAgentLimits,
+    /// Tokens used by all sub-agents of the tree.
+    tree_tokens: Arc<AtomicU64>,
+    deadline: Option<Instant>,
+}
+
+impl AgentBudget {
+    pub(crate) fn root(limits: SubAgentLimits) -> Arc<Self> {
+        Arc::new(Self {
+            depth: 0,
+            limits,
+            tree_tokens: Arc::new(AtomicU64::new(0)),
+            deadline: None,
+        })
+    }
+
+    /// The budget for a sub-agent of this session, or an error when starting
+    /// one would break a limit.
+    pub(crate) fn child(&self) -> Result<Arc<Self>, CodexErr> {
+        let depth = self.depth + 1;
+        if depth > self.limits.max_depth {
+            return Err(CodexErr::SubAgentBudgetExceeded(format!(
+                "sub-agents may nest at most {} level(s)",
+                self.limits.max_depth
+            )));
+        }
+        self.check()?;
+        let own_deadline = self
+            .limits
+            .max_wall_clock_secs
+            .map(|secs| Instant::now() + Duration::from_secs(secs));
+        let deadline = match (self.deadline, own_deadline) {
+            (Some(parent), Some(own)) => Some(parent.min(own)),
+            (parent, own) => parent.or(own),
+        };
+        Ok(Arc::new(Self {
+            depth,
+            limits: self.limits.clone(),
+            tree_tokens: Arc::clone(&self.tree_tokens),
+            deadline,
+        }))
+    }
+
+    pub(crate) fn is_sub_agent(&self) -> bool {
+        self.depth > 0
+    }
+
+    /// When this sub-agent has to stop, if it has a wall-clock limit.
+    pub(crate) fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Count tokens used by this session. Only sub-agents draw on the budget.
+    pub(crate) fn record_tokens(&self, tokens: i64) {
+        if self.is_sub_agent() {
+            self.tree_tokens
+                .fetch_add(u64::try_from(tokens).unwrap_or(0), Ordering::Relaxed);
+        }
+    }
+
+    /// An error once the tree has used up its tokens or this sub-agent has
+    /// run past its deadline.
+    pub(crate) fn check(&self) -> Result<(), CodexErr> {
+        if let Some(max_tokens) = self.limits.max_tokens {
+            let used = self.tree_tokens.load(Ordering::Relaxed);
+            if used >= max_tokens {
+                return Err(CodexErr::SubAgentBudgetExceeded(format!(
+                    "sub-agents used {used} of {max_tokens} tokens"
+                )));
+            }
+        }
+        if let (Some(deadline), Some(secs)) = (self.deadline, self.limits.max_wall_clock_secs)
+            && Instant::now() >= deadline
+        {
+            return Err(CodexErr::SubAgentBudgetExceeded(format!(
+                "sub-agent ran longer than {secs}s"
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(max_depth: u32, max_tokens: Option<u64>) -> SubAgentLimits {
+        SubAgentLimits {
+            max_depth,
+            max_tokens,
+            max_wall_clock_secs: None,
+        }
+    }
+
+    #[test]
+    fn children_share_tokens_and_stop_at_max_depth() {
+        let root = AgentBudget::root(limits(2, Some(100)));
+        root.record_tokens(1_000);
+        let child = root.child().expect("depth 1");
+        let grandchild = child.child().expect("depth 2");
+        assert!(grandchild.child().is_err());
+
+        child.record_tokens(60);
+        assert!(grandchild.check().is_ok());
+        grandchild.record_tokens(40);
+        assert!(child.check().is_err());
+        assert!(root.child().is_err());
+    }
+
+    #[test]
+    fn nested_deadline_never_extends_the_parent() {
+        let mut with_clock = limits(3, None);
+        with_clock.max_wall_clock_secs = Some(10);
+        let child = AgentBudget::root(with_clock.clone())
+            .child()
+            .expect("child");
+        let grandchild = child.child().expect("grandchild");
+        assert_eq!(grandchild.deadline(), child.deadline());
+
+        with_clock.max_wall_clock_secs = Some(0);
+        let expired = AgentBudget::root(with_clock).child().expect("child");
+        assert!(expired.check().is_err());
+    }
+}
@@ -0,0 +1,118 @@
+//! Client annotations (bookmarks, comments, "this was wrong") on the messages
+//! of a conversation.
+//!
+//! A message is identified by its position among the conversation's
+//! `UserMessage` and `AgentMessage` events, which clients see both live and
+//! when a session is resumed. Annotations are recorded as events in the
+//! rollout, so replaying the rollout restores them.
+
+use codex_protocol::protocol::AnnotationKind;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::HistoryAnnotation;
+use codex_protocol::protocol::RolloutItem;
+use uuid::Uuid;
+
+#[derive(Debug, Default)]
+pub(crate) struct Annotations {
+    message_count: usize,
+    annotations: Vec<HistoryAnnotation>,
+}
+
+impl Annotations {
+    pub(crate) fn from_rollout(items: &[RolloutItem]) -> Self {
+        let mut annotations = Self::default();
+        for item in items {
+            if let RolloutItem::EventMsg(msg) = item {
+                annotations.observe(msg);
+            }
+        }
+        annotations
+    }
+
+    /// Track an event recorded in the rollout.
+    pub(crate) fn observe(&mut self, msg: &EventMsg) {
+        match msg {
+            EventMsg::UserMessage(_) | EventMsg::AgentMessage(_) => self.message_count += 1,
+            EventMsg::ItemAnnotated(event) => self.annotations.push(event.annotation.clone()),
+            EventMsg::AnnotationRemoved(event) => self
+                .annotations
+                .retain(|annotation| annotation.id != event.annotation_id),
+            _ => {}
+        }
+    }
+
+    /// Build an annotation for the message at `message_index`. It takes
+    /// effect once its `ItemAnnotated` event is observed.
+    pub(crate) fn annotate(
+        &self,
+        message_index: usize,
+        kind: AnnotationKind,
+        note: Option<String>,
+    ) -> Result<HistoryAnnotation, String> {
+        if message_index >= self.message_count {
+            return Err(format!(
+                "cannot annotate message {message_index}: the conversation has {} messages",
+                self.message_count
+            ));
+        }
+        Ok(HistoryAnnotation {
+            id: Uuid::new_v4().to_string(),
+            message_index,
+            kind,
+            note: note.filter(|note| !note.trim().is_empty()),
+        })
+    }
+
+    pub(crate) fn contains(&self, annotation_id: &str) -> bool {
+        self.annotations
+            .iter()
+            .any(|annotation| annotation.id == annotation_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::AgentMessageEvent;
+    use codex_protocol::protocol::AnnotationRemovedEvent;
+    use codex_protocol::protocol::ItemAnnotatedEvent;
+    use codex_protocol::protocol::UserMessageEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn replaying_the_rollout_restores_messages_and_annotations() {
+        let user = EventMsg::UserMessage(UserMessageEvent {
+            message: "fix the build".to_string(),
+            images: None,
+        });
+        let agent = EventMsg::AgentMessage(AgentMessageEvent {
+            message: "done".to_string(),
+        });
+        let mut annotations =
+            Annotations::from_rollout(&[RolloutItem::EventMsg(user), RolloutItem::EventMsg(agent)]);
+
+        assert!(
+            annotations
+                .annotate(2, AnnotationKind::Bookmark, None)
+                .is_err()
+        );
+        let wrong = annotations
+            .annotate(
+                1,
+                AnnotationKind::Incorrect,
+                Some("tests still fail".into()),
+            )
+            .expect("message 1 exists");
+        assert_eq!(wrong.message_index, 1);
+        assert_eq!(wrong.note.as_deref(), Some("tests still fail"));
+
+        annotations.observe(&EventMsg::ItemAnnotated(ItemAnnotatedEvent {
+            annotation: wrong.clone(),
+        }));
+        assert!(annotations.contains(&wrong.id));
+        annotations.observe(&EventMsg::AnnotationRemoved(AnnotationRemovedEvent {
+            annotation_id: wrong.id.clone(),
+        }));
+        assert!(!annotations.contains(&wrong.id));
+    }
+}
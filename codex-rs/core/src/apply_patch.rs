@@ -1,13 +1,26 @@
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::config::types::PatchCriticMode;
 use crate::function_tool::FunctionCallError;
+use crate::patch_critic;
+use crate::patch_preview;
+use crate::patch_preview::PreviewReport;
+use crate::protocol::AskForApproval;
+use crate::protocol::EventMsg;
 use crate::protocol::FileChange;
+use crate::protocol::PatchConflict;
+use crate::protocol::PatchConflictResolution;
+use crate::protocol::ProtectedPathRule;
+use crate::protocol::ProtectedPathViolation;
+use crate::protocol::ProtectedPathViolationEvent;
 use crate::protocol::ReviewDecision;
+use crate::protocol::WarningEvent;
 use crate::safety::SafetyCheck;
 use crate::safety::assess_patch_safety;
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
 use std::collections::HashMap;
+use std::path::Path;
 use std::path::PathBuf;
 
 pub const CODEX_APPLY_PATCH_ARG1: &str = "--codex-run-as-apply-patch";
@@ -39,6 +52,83 @@ pub(crate) async fn apply_patch(
     call_id: &str,
     action: ApplyPatchAction,
 ) -> InternalApplyPatchInvocation {
+    let paths = affected_paths(&action);
+    let protected = turn_context.protected_paths.violations(&paths);
+    // Without approvals nobody can approve edits to protected paths either.
+    let refused: Vec<ProtectedPathViolation> =
+        if turn_context.approval_policy == AskForApproval::Never {
+            protected.clone()
+        } else {
+            protected
+                .iter()
+                .filter(|violation| violation.rule == ProtectedPathRule::Block)
+                .cloned()
+                .collect()
+        };
+    if !refused.is_empty() {
+        let message = format_protected_path_violations(&refused, &turn_context.cwd);
+        sess.send_event(
+            turn_context,
+            EventMsg::ProtectedPathViolation(ProtectedPathViolationEvent {
+                call_id: call_id.to_owned(),
+                turn_id: turn_context.sub_id.clone(),
+                violations: refused,
+            }),
+        )
+        .await;
+        return InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+            message,
+        )));
+    }
+
+    let conflicts = sess.patch_conflicts(&paths).await;
+    if !conflicts.is_empty() {
+        // Without approvals there is nobody to ask, so never clobber the edits.
+        let resolution = if turn_context.approval_policy == AskForApproval::Never {
+            PatchConflictResolution::Reread
+        } else {
+            sess.request_patch_conflict_resolution(
+                turn_context,
+                call_id.to_owned(),
+                conflicts.clone(),
+            )
+            .await
+        };
+        match resolution {
+            PatchConflictResolution::Force => {}
+            PatchConflictResolution::Reread | PatchConflictResolution::Abort => {
+                // The model learns about the changes below, so stop reporting them.
+                sess.watch_files(conflicts.iter().map(|conflict| conflict.path.clone()))
+                    .await;
+                return InternalApplyPatchInvocation::Output(Err(
+                    FunctionCallError::RespondToModel(format_patch_conflicts(
+                        &conflicts,
+                        &turn_context.cwd,
+                    )),
+                ));
+            }
+        }
+    }
+
+    if !protected.is_empty() {
+        let reason = format!(
+            "Edits protected paths: {}",
+            protected
+                .iter()
+                .map(|violation| describe_violation(violation, &turn_context.cwd))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let concerns = critique_before_asking(sess, turn_context, &action).await;
+        let preview = preview_patch(sess, turn_context, &action).await;
+        let reason = join_reasons(
+            [Some(reason), concerns, preview.map(|report| report.summary)]
+                .into_iter()
+                .flatten(),
+        );
+        return ask_user(sess, turn_context, call_id, action, reason).await;
+    }
+
     match assess_patch_safety(
         &action,
         turn_context.approval_policy,
@@ -48,42 +138,50 @@ pub(crate) async fn apply_patch(
         SafetyCheck::AutoApprove {
             user_explicitly_approved,
             ..
-        } => InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
-            action,
-            user_explicitly_approved_this_action: user_explicitly_approved,
-        }),
-        SafetyCheck::AskUser => {
-            // Compute a readable summary of path changes to include in the
-            // approval request so the user can make an informed decision.
-            //
-            // Note that it might be worth expanding this approval request to
-            // give the user the option to expand the set of writable roots so
-            // that similar patches can be auto-approved in the future during
-            // this session.
-            let rx_approve = sess
-                .request_patch_approval(
-                    turn_context,
-                    call_id.to_owned(),
-                    convert_apply_patch_to_protocol(&action),
-                    None,
-                    None,
-                )
-                .await;
-            match rx_approve.await.unwrap_or_default() {
-                ReviewDecision::Approved
-                | ReviewDecision::ApprovedExecpolicyAmendment { .. }
-                | ReviewDecision::ApprovedForSession => {
-                    InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
-                        action,
-                        user_explicitly_approved_this_action: true,
-                    })
-                }
-                ReviewDecision::Denied | ReviewDecision::Abort => {
-                    InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
-                        "patch rejected by user".to_string(),
-                    )))
+        } => {
+            if !user_explicitly_approved {
+                let concerns = match turn_context.patch_critic.as_ref() {
+                    Some(critic) => {
+                        let concerns =
+                            patch_critic::review_patch(sess, turn_context, critic, &action).await;
+                        (!concerns.is_empty()).then(|| patch_critic::format_concerns(&concerns))
+                    }
+                    None => None,
+                };
+                let preview = preview_patch(sess, turn_context, &action).await;
+                let preview_failed = preview.as_ref().is_some_and(|report| !report.passed);
+                if concerns.is_some() || preview_failed {
+                    let reason = join_reasons(
+                        concerns
+                            .into_iter()
+                            .chain(preview.map(|report| report.summary)),
+                    )
+                    .unwrap_or_default();
+                    // Ask instead of applying, unless nobody can be asked.
+                    if turn_context.approval_policy != AskForApproval::Never {
+                        return ask_user(sess, turn_context, call_id, action, Some(reason)).await;
+                    }
+                    sess.send_event(
+                        turn_context,
+                        EventMsg::Warning(WarningEvent { message: reason }),
+                    )
+                    .await;
                 }
             }
+            InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+                action,
+                user_explicitly_approved_this_action: user_explicitly_approved,
+            })
+        }
+        SafetyCheck::AskUser => {
+            let concerns = critique_before_asking(sess, turn_context, &action).await;
+            let preview = preview_patch(sess, turn_context, &action).await;
+            let reason = join_reasons(
+                concerns
+                    .into_iter()
+                    .chain(preview.map(|report| report.summary)),
+            );
+            ask_user(sess, turn_context, call_id, action, reason).await
         }
         SafetyCheck::Reject { reason } => InternalApplyPatchInvocation::Output(Err(
             FunctionCallError::RespondToModel(format!("patch rejected: {reason}")),
@@ -91,6 +189,127 @@ pub(crate) async fn apply_patch(
     }
 }
 
+/// Concerns for a patch that asks for approval anyway, when the critic
+/// reviews every patch.
+async fn critique_before_asking(
+    sess: &Session,
+    turn_context: &TurnContext,
+    action: &ApplyPatchAction,
+) -> Option<String> {
+    let critic = turn_context
+        .patch_critic
+        .as_ref()
+        .filter(|critic| critic.when == PatchCriticMode::Always)?;
+    let concerns = patch_critic::review_patch(sess, turn_context, critic, action).await;
+    (!concerns.is_empty()).then(|| patch_critic::format_concerns(&concerns))
+}
+
+/// Results of the configured checks run against a copy with the patch.
+async fn preview_patch(
+    sess: &Session,
+    turn_context: &TurnContext,
+    action: &ApplyPatchAction,
+) -> Option<PreviewReport> {
+    let preview = turn_context.patch_preview.as_ref()?;
+    Some(patch_preview::preview_patch(sess, turn_context, preview, action).await)
+}
+
+/// The parts of an approval reason, one paragraph each.
+fn join_reasons(parts: impl IntoIterator<Item = String>) -> Option<String> {
+    let parts: Vec<String> = parts.into_iter().collect();
+    (!parts.is_empty()).then(|| parts.join("\n\n"))
+}
+
+async fn ask_user(
+    sess: &Session,
+    turn_context: &TurnContext,
+    call_id: &str,
+    action: ApplyPatchAction,
+    reason: Option<String>,
+) -> InternalApplyPatchInvocation {
+    // Compute a readable summary of path changes to include in the
+    // approval request so the user can make an informed decision.
+    //
+    // Note that it might be worth expanding this approval request to
+    // give the user the option to expand the set of writable roots so
+    // that similar patches can be auto-approved in the future during
+    // this session.
+    let rx_approve = sess
+        .request_patch_approval(
+            turn_context,
+            call_id.to_owned(),
+            convert_apply_patch_to_protocol(&action),
+            reason,
+            None,
+        )
+        .await;
+    match rx_approve.await.unwrap_or_default() {
+        ReviewDecision::Approved
+        | ReviewDecision::ApprovedExecpolicyAmendment { .. }
+        | ReviewDecision::ApprovedForSession => {
+            InternalApplyPatchInvocation::DelegateToExec(ApplyPatchExec {
+                action,
+                user_explicitly_approved_this_action: true,
+            })
+        }
+        ReviewDecision::Denied | ReviewDecision::Abort => {
+            InternalApplyPatchInvocation::Output(Err(FunctionCallError::RespondToModel(
+                "patch rejected by user".to_string(),
+            )))
+        }
+    }
+}
+
+/// `path (pattern)`, with the path relative to `cwd` where possible.
+fn describe_violation(violation: &ProtectedPathViolation, cwd: &Path) -> String {
+    let path = violation.path.strip_prefix(cwd).unwrap_or(&violation.path);
+    format!("{} (`{}`)", path.display(), violation.pattern)
+}
+
+/// Tell the model which protected paths stopped the patch.
+fn format_protected_path_violations(violations: &[ProtectedPathViolation], cwd: &Path) -> String {
+    let mut message =
+        "patch rejected: these paths are protected by the user's configuration\n".to_string();
+    for violation in violations {
+        message.push_str(&format!("\n{}", describe_violation(violation, cwd)));
+    }
+    message.push_str(
+        "\n\nDo not change these paths. If the task needs them changed, tell the user what to change instead.",
+    );
+    message
+}
+
+/// Tell the model which files changed under it, so it rebuilds the patch.
+fn format_patch_conflicts(conflicts: &[PatchConflict], cwd: &Path) -> String {
+    let mut message =
+        "patch not applied: these files changed since you last read them\n".to_string();
+    for conflict in conflicts {
+        let path = conflict.path.strip_prefix(cwd).unwrap_or(&conflict.path);
+        match &conflict.diff {
+            Some(diff) => message.push_str(&format!("\n{}:\n{diff}", path.display())),
+            None => message.push_str(&format!("\n{}: deleted\n", path.display())),
+        }
+    }
+    message.push_str("\nRe-read them and rebuild the patch against their current contents.");
+    message
+}
+
+/// Every path `action` touches, including move destinations.
+pub(crate) fn affected_paths(action: &ApplyPatchAction) -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+    for (path, change) in action.changes() {
+        paths.push(path.clone());
+        if let ApplyPatchFileChange::Update {
+            move_path: Some(move_path),
+            ..
+        } = change
+        {
+            paths.push(move_path.clone());
+        }
+    }
+    paths
+}
+
 pub(crate) fn convert_apply_patch_to_protocol(
     action: &ApplyPatchAction,
 ) -> HashMap<PathBuf, FileChange> {
@@ -141,4 +360,24 @@ mod tests {
             })
         );
     }
+
+    #[test]
+    fn format_patch_conflicts_lists_changed_and_deleted_files() {
+        let cwd = PathBuf::from("/repo");
+        let conflicts = vec![
+            PatchConflict {
+                path: cwd.join("src/lib.rs"),
+                diff: Some("@@ -1 +1 @@\n-old\n+new\n".to_string()),
+            },
+            PatchConflict {
+                path: cwd.join("README.md"),
+                diff: None,
+            },
+        ];
+
+        assert_eq!(
+            format_patch_conflicts(&conflicts, &cwd),
+            "patch not applied: these files changed since you last read them\n\nsrc/lib.rs:\n@@ -1 +1 @@\n-old\n+new\n\nREADME.md: deleted\n\nRe-read them and rebuild the patch against their current contents."
+        );
+    }
 }
@@ -6,16 +6,14 @@ use sha2::Digest;
 use sha2::Sha256;
 use std::fmt::Debug;
 use std::fs::File;
-use std::fs::OpenOptions;
 use std::io::Read;
-use std::io::Write;
-#[cfg(unix)]
-use std::os::unix::fs::OpenOptionsExt;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use tracing::warn;
 
+use crate::file_lock::lock_file;
+use crate::file_lock::write_atomic;
 use crate::token_data::TokenData;
 use codex_keyring_store::DefaultKeyringStore;
 use codex_keyring_store::KeyringStore;
@@ -98,23 +96,13 @@ impl AuthStorageBackend for FileAuthStorage {
         Ok(Some(auth_dot_json))
     }
 
+    /// Replaces the file in one step, so a process logging in or refreshing
+    /// tokens never leaves another one reading half of it.
     fn save(&self, auth_dot_json: &AuthDotJson) -> std::io::Result<()> {
         let auth_file = get_auth_file(&self.codex_home);
-
-        if let Some(parent) = auth_file.parent() {
-            std::fs::create_dir_all(parent)?;
-        }
         let json_data = serde_json::to_string_pretty(auth_dot_json)?;
-        let mut options = OpenOptions::new();
-        options.truncate(true).write(true).create(true);
-        #[cfg(unix)]
-        {
-            options.mode(0o600);
-        }
-        let mut file = options.open(auth_file)?;
-        file.write_all(json_data.as_bytes())?;
-        file.flush()?;
-        Ok(())
+        let _lock = lock_file(&auth_file)?;
+        write_atomic(&auth_file, json_data.as_bytes(), Some(0o600))
     }
 
     fn delete(&self) -> std::io::Result<bool> {
@@ -349,6 +337,41 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn file_storage_readers_never_see_a_partial_save() -> anyhow::Result<()> {
+        let dir = tempdir()?;
+        let storage = FileAuthStorage::new(dir.path().to_path_buf());
+        let auth_for = |n: usize| AuthDotJson {
+            openai_api_key: Some(format!("sk-test-{}", "k".repeat(n * 97))),
+            tokens: None,
+            last_refresh: None,
+        };
+        storage.save(&auth_for(0))?;
+
+        std::thread::scope(|scope| {
+            for writer in 0..4 {
+                let storage = storage.clone();
+                scope.spawn(move || {
+                    for round in 0..20 {
+                        storage
+                            .save(&auth_for(writer * 20 + round))
+                            .expect("save auth file");
+                    }
+                });
+            }
+            for _ in 0..4 {
+                let storage = storage.clone();
+                scope.spawn(move || {
+                    for _ in 0..50 {
+                        let loaded = storage.load().expect("load auth file");
+                        assert!(loaded.is_some());
+                    }
+                });
+            }
+        });
+        Ok(())
+    }
+
     fn seed_keyring_and_fallback_auth_file_for_delete<F>(
         mock_keyring: &MockKeyringStore,
         codex_home: &Path,
@@ -0,0 +1,347 @@
+//! Best-of-N sampling for the first request of a task.
+//!
+//! The request is sent several times in parallel and each response is
+//! buffered without running its tool calls. Once the user or a judge prompt
+//! picks one, the chosen response is replayed through the normal stream
+//! handling, so its tool calls run and its items are recorded as if it had
+//! been the only response.
+
+use codex_protocol::items::TurnItem;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::LocalShellAction;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::CandidateSelectedEvent;
+use codex_protocol::protocol::CandidateSelector;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::ResponseCandidate;
+use codex_protocol::protocol::TokenUsage;
+use futures::StreamExt;
+use futures::future::try_join_all;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::client_common::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::client_common::ResponseStream;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::compact::content_items_to_text;
+use crate::config::types::BestOf;
+use crate::config::types::BestOfSelection;
+use crate::error::CodexErr;
+use crate::error::Result as CodexResult;
+use crate::event_mapping::parse_turn_item;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+pub(crate) const JUDGE_PROMPT: &str = include_str!("../templates/best_of/judge.md");
+const JUDGE_REQUEST_MAX_BYTES: usize = 4 * 1024;
+const JUDGE_CANDIDATE_MAX_BYTES: usize = 6 * 1024;
+
+/// A fully buffered response to the turn's prompt.
+struct Candidate {
+    events: Vec<ResponseEvent>,
+}
+
+impl Candidate {
+    fn items(&self) -> impl Iterator<Item = &ResponseItem> {
+        self.events.iter().filter_map(|event| match event {
+            ResponseEvent::OutputItemDone(item) => Some(item),
+            _ => None,
+        })
+    }
+
+    fn token_usage(&self) -> Option<&TokenUsage> {
+        self.events.iter().find_map(|event| match event {
+            ResponseEvent::Completed { token_usage, .. } => token_usage.as_ref(),
+            _ => None,
+        })
+    }
+
+    fn summary(&self) -> ResponseCandidate {
+        let mut message: Vec<String> = Vec::new();
+        let mut tool_calls: Vec<String> = Vec::new();
+        for item in self.items() {
+            match item {
+                ResponseItem::Message { role, content, .. } if role == "assistant" => {
+                    if let Some(text) = content_items_to_text(content) {
+                        message.push(text);
+                    }
+                }
+                ResponseItem::FunctionCall {
+                    name, arguments, ..
+                } => tool_calls.push(describe_function_call(name, arguments)),
+                ResponseItem::CustomToolCall { name, input, .. } => {
+                    tool_calls.push(format!("{name}: {input}"));
+                }
+                ResponseItem::LocalShellCall {
+                    action: LocalShellAction::Exec(exec),
+                    ..
+                } => tool_calls.push(format!("shell: {}", exec.command.join(" "))),
+                _ => {}
+            }
+        }
+        ResponseCandidate {
+            message: message.join("\n\n"),
+            tool_calls,
+        }
+    }
+
+    /// Replay the buffered events as if they were streamed by the provider.
+    fn into_stream(self) -> ResponseStream {
+        let (tx_event, rx_event) = mpsc::channel(self.events.len().max(1));
+        for event in self.events {
+            // The channel has room for every event, so this cannot fail.
+            let _ = tx_event.try_send(Ok(event));
+        }
+        ResponseStream { rx_event }
+    }
+}
+
+/// Sample `best_of.candidates` responses to `prompt` and return the chosen
+/// one as a stream.
+pub(crate) async fn sample_best_of(
+    sess: &Session,
+    turn_context: &TurnContext,
+    prompt: &Prompt,
+    best_of: BestOf,
+) -> CodexResult<ResponseStream> {
+    let candidates =
+        try_join_all((0..best_of.candidates).map(|_| sample_candidate(turn_context, prompt)))
+            .await?;
+    let summaries: Vec<ResponseCandidate> = candidates.iter().map(Candidate::summary).collect();
+
+    // Without approvals nobody is watching the session, so do not wait.
+    let user_choice = if best_of.selection == BestOfSelection::User
+        && turn_context.approval_policy != AskForApproval::Never
+    {
+        sess.request_candidate_selection(turn_context, summaries.clone())
+            .await
+            .filter(|index| *index < candidates.len())
+    } else {
+        None
+    };
+    let (index, selected_by) = match user_choice {
+        Some(index) => (index, CandidateSelector::User),
+        None => (
+            judge(sess, turn_context, prompt, &summaries).await,
+            CandidateSelector::Judge,
+        ),
+    };
+    sess.send_event(
+        turn_context,
+        EventMsg::CandidateSelected(CandidateSelectedEvent {
+            index,
+            candidates: candidates.len(),
+            selected_by,
+        }),
+    )
+    .await;
+
+    let mut chosen = None;
+    for (candidate_index, candidate) in candidates.into_iter().enumerate() {
+        if candidate_index == index {
+            chosen = Some(candidate);
+        } else {
+            // The chosen candidate's usage is recorded when it is replayed.
+            sess.update_token_usage_info(turn_context, candidate.token_usage())
+                .await;
+        }
+    }
+    chosen
+        .map(Candidate::into_stream)
+        .ok_or_else(|| CodexErr::Fatal(format!("candidate {index} was not sampled")))
+}
+
+async fn sample_candidate(turn_context: &TurnContext, prompt: &Prompt) -> CodexResult<Candidate> {
+    let mut stream = turn_context.client.clone().stream(prompt).await?;
+    let mut events = Vec::new();
+    while let Some(event) = stream.next().await {
+        let event = event?;
+        let completed = matches!(event, ResponseEvent::Completed { .. });
+        events.push(event);
+        if completed {
+            return Ok(Candidate { events });
+        }
+    }
+    Err(CodexErr::Stream(
+        "stream closed before response.completed".into(),
+        None,
+    ))
+}
+
+/// Ask the model which candidate is best. Falls back to the first candidate
+/// when the judge fails or gives an unusable answer.
+async fn judge(
+    sess: &Session,
+    turn_context: &TurnContext,
+    prompt: &Prompt,
+    candidates: &[ResponseCandidate],
+) -> usize {
+    let request = prompt
+        .input
+        .iter()
+        .rev()
+        .find_map(|item| match parse_turn_item(item) {
+            Some(TurnItem::UserMessage(message)) => Some(message.message()),
+            _ => None,
+        })
+        .unwrap_or_default();
+    let mut text = format!(
+        "<request>\n{}\n</request>\n",
+        truncate_text(&request, TruncationPolicy::Bytes(JUDGE_REQUEST_MAX_BYTES))
+    );
+    for (index, candidate) in candidates.iter().enumerate() {
+        text.push_str(&format!(
+            "\n<candidate number=\"{}\">\n{}\n</candidate>\n",
+            index + 1,
+            truncate_text(
+                &render_candidate(candidate),
+                TruncationPolicy::Bytes(JUDGE_CANDIDATE_MAX_BYTES)
+            )
+        ));
+    }
+
+    let judge_prompt = Prompt {
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText { text }],
+        }],
+        tools: Vec::new(),
+        parallel_tool_calls: false,
+        base_instructions_override: Some(JUDGE_PROMPT.to_string()),
+        locale_instructions: None,
+        model_instructions: None,
+        composed_instructions: None,
+        output_schema: None,
+    };
+    match run_judge(sess, turn_context, &judge_prompt).await {
+        Ok(reply) => parse_choice(&reply, candidates.len()).unwrap_or_else(|| {
+            warn!("best-of judge did not name a candidate: {reply}");
+            0
+        }),
+        Err(err) => {
+            warn!("best-of judge failed: {err:#}");
+            0
+        }
+    }
+}
+
+async fn run_judge(
+    sess: &Session,
+    turn_context: &TurnContext,
+    prompt: &Prompt,
+) -> CodexResult<String> {
+    let mut stream = turn_context.client.clone().stream(prompt).await?;
+    let mut reply = String::new();
+    while let Some(event) = stream.next().await {
+        match event? {
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. }) => {
+                if let Some(text) = content_items_to_text(&content) {
+                    reply.push_str(&text);
+                }
+            }
+            ResponseEvent::RateLimits(snapshot) => {
+                sess.update_rate_limits(turn_context, snapshot).await;
+            }
+            ResponseEvent::Completed { token_usage, .. } => {
+                sess.update_token_usage_info(turn_context, token_usage.as_ref())
+                    .await;
+                return Ok(reply);
+            }
+            _ => {}
+        }
+    }
+    Err(CodexErr::Stream(
+        "stream closed before response.completed".into(),
+        None,
+    ))
+}
+
+fn render_candidate(candidate: &ResponseCandidate) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    if !candidate.message.is_empty() {
+        lines.push(candidate.message.clone());
+    }
+    lines.extend(candidate.tool_calls.iter().map(|call| format!("→ {call}")));
+    lines.join("\n")
+}
+
+fn describe_function_call(name: &str, arguments: &str) -> String {
+    let args = serde_json::from_str::<serde_json::Value>(arguments).ok();
+    let command = args
+        .as_ref()
+        .and_then(|args| args.get("command").or_else(|| args.get("cmd")));
+    let detail = match command {
+        Some(serde_json::Value::String(command)) => command.clone(),
+        Some(serde_json::Value::Array(words)) => words
+            .iter()
+            .filter_map(serde_json::Value::as_str)
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => arguments.to_string(),
+    };
+    format!("{name}: {detail}")
+}
+
+/// The zero-based index of the first candidate number in the judge's reply.
+fn parse_choice(reply: &str, candidates: usize) -> Option<usize> {
+    let digits: String = reply
+        .chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(char::is_ascii_digit)
+        .collect();
+    let number: usize = digits.parse().ok()?;
+    (1..=candidates).contains(&number).then(|| number - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn summary_lists_message_and_pending_tool_calls() {
+        let candidate = Candidate {
+            events: vec![
+                ResponseEvent::Created,
+                ResponseEvent::OutputItemDone(ResponseItem::Message {
+                    id: None,
+                    role: "assistant".to_string(),
+                    content: vec![ContentItem::OutputText {
+                        text: "Running the tests first.".to_string(),
+                    }],
+                }),
+                ResponseEvent::OutputItemDone(ResponseItem::FunctionCall {
+                    id: None,
+                    name: "shell".to_string(),
+                    arguments: r#"{"command":["cargo","test"]}"#.to_string(),
+                    call_id: "call-1".to_string(),
+                }),
+                ResponseEvent::Completed {
+                    response_id: "resp-1".to_string(),
+                    token_usage: None,
+                },
+            ],
+        };
+
+        assert_eq!(
+            candidate.summary(),
+            ResponseCandidate {
+                message: "Running the tests first.".to_string(),
+                tool_calls: vec!["shell: cargo test".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn parse_choice_reads_the_first_candidate_number() {
+        assert_eq!(parse_choice("2", 3), Some(1));
+        assert_eq!(parse_choice("Candidate 3 is best.", 3), Some(2));
+        assert_eq!(parse_choice("4", 3), None);
+        assert_eq!(parse_choice("0", 3), None);
+        assert_eq!(parse_choice("none of them", 3), None);
+    }
+}
@@ -0,0 +1,235 @@
+//! Runtime assets compiled into the binary.
+//!
+//! Prompts, templates, model metadata and tool schemas are all built into
+//! `codex` at compile time, so the binary on its own is everything Codex needs
+//! to run without internet access. [`verify_bundled_assets`] backs
+//! `codex doctor --offline`, which checks that claim for the current config.
+
+use crate::best_of::JUDGE_PROMPT;
+use crate::client_common::REVIEW_EXIT_INTERRUPTED_TMPL;
+use crate::client_common::REVIEW_EXIT_SUCCESS_TMPL;
+use crate::client_common::REVIEW_PROMPT;
+use crate::compact::SESSION_SUMMARY_PROMPT;
+use crate::compact::SUMMARIZATION_PROMPT;
+use crate::compact::SUMMARY_PREFIX;
+use crate::config::Config;
+use crate::memories::MEMORIES_PROMPT;
+use crate::openai_models::model_family::BASE_INSTRUCTIONS;
+use crate::openai_models::model_family::GPT_5_1_CODEX_MAX_INSTRUCTIONS;
+use crate::openai_models::model_family::GPT_5_1_INSTRUCTIONS;
+use crate::openai_models::model_family::GPT_5_2_CODEX_INSTRUCTIONS;
+use crate::openai_models::model_family::GPT_5_2_INSTRUCTIONS;
+use crate::openai_models::model_family::GPT_5_CODEX_INSTRUCTIONS;
+use crate::openai_models::models_manager::BUNDLED_MODELS_JSON;
+use crate::openai_models::models_manager::ModelsManager;
+use crate::patch_critic::PATCH_CRITIC_PROMPT;
+use crate::tools::argument_repair::TOOL_CALL_REPAIR_PROMPT;
+use crate::tools::handlers::HANDOFF_INSTRUCTIONS;
+use crate::tools::handlers::SUMMARIZE_FILE_PROMPT;
+use crate::tools::handlers::apply_patch::APPLY_PATCH_LARK_GRAMMAR;
+use crate::tools::simulated_exec::SIMULATED_EXEC_PROMPT;
+use crate::tools::spec::ToolsConfig;
+use crate::tools::spec::ToolsConfigParams;
+use crate::tools::spec::build_specs;
+use crate::tools::spec::create_tools_json_for_responses_api;
+
+/// One file compiled into the binary, named by its path relative to the
+/// `codex-rs` workspace.
+#[derive(Debug, Clone, Copy)]
+pub struct BundledAsset {
+    pub name: &'static str,
+    pub contents: &'static str,
+}
+
+const BUNDLED_ASSETS: &[BundledAsset] = &[
+    asset("core/prompt.md", BASE_INSTRUCTIONS),
+    asset("core/gpt_5_codex_prompt.md", GPT_5_CODEX_INSTRUCTIONS),
+    asset("core/gpt_5_1_prompt.md", GPT_5_1_INSTRUCTIONS),
+    asset("core/gpt_5_2_prompt.md", GPT_5_2_INSTRUCTIONS),
+    asset(
+        "core/gpt-5.1-codex-max_prompt.md",
+        GPT_5_1_CODEX_MAX_INSTRUCTIONS,
+    ),
+    asset("core/gpt-5.2-codex_prompt.md", GPT_5_2_CODEX_INSTRUCTIONS),
+    asset("core/review_prompt.md", REVIEW_PROMPT),
+    asset("core/models.json", BUNDLED_MODELS_JSON),
+    asset(
+        "core/templates/review/exit_success.xml",
+        REVIEW_EXIT_SUCCESS_TMPL,
+    ),
+    asset(
+        "core/templates/review/exit_interrupted.xml",
+        REVIEW_EXIT_INTERRUPTED_TMPL,
+    ),
+    asset("core/templates/compact/prompt.md", SUMMARIZATION_PROMPT),
+    asset("core/templates/compact/summary_prefix.md", SUMMARY_PREFIX),
+    asset(
+        "core/templates/compact/session_summary.md",
+        SESSION_SUMMARY_PROMPT,
+    ),
+    asset("core/templates/best_of/judge.md", JUDGE_PROMPT),
+    asset("core/templates/patch_critic/prompt.md", PATCH_CRITIC_PROMPT),
+    asset("core/templates/memories/prompt.md", MEMORIES_PROMPT),
+    asset(
+        "core/templates/summarize_file/prompt.md",
+        SUMMARIZE_FILE_PROMPT,
+    ),
+    asset("core/templates/delegate/handoff.md", HANDOFF_INSTRUCTIONS),
+    asset(
+        "core/templates/tool_call_repair/prompt.md",
+        TOOL_CALL_REPAIR_PROMPT,
+    ),
+    asset(
+        "core/templates/simulated_exec/prompt.md",
+        SIMULATED_EXEC_PROMPT,
+    ),
+    asset(
+        "core/src/tools/handlers/tool_apply_patch.lark",
+        APPLY_PATCH_LARK_GRAMMAR,
+    ),
+];
+
+const fn asset(name: &'static str, contents: &'static str) -> BundledAsset {
+    BundledAsset { name, contents }
+}
+
+pub fn bundled_assets() -> &'static [BundledAsset] {
+    BUNDLED_ASSETS
+}
+
+/// What [`verify_bundled_assets`] checked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BundledAssetsSummary {
+    pub files: usize,
+    pub bytes: usize,
+    pub models: usize,
+    pub tools: usize,
+}
+
+/// Check that the compiled-in assets are usable without network access: no
+/// template is empty, the bundled model list parses, every bundled model (and
+/// the configured one) resolves to non-empty instructions the way a session
+/// looks them up, and the tool schemas for `config` serialize.
+pub fn verify_bundled_assets(config: &Config) -> Result<BundledAssetsSummary, String> {
+    if let Some(empty) = BUNDLED_ASSETS
+        .iter()
+        .find(|asset| asset.contents.trim().is_empty())
+    {
+        return Err(format!("{} is empty in this build", empty.name));
+    }
+    let models = ModelsManager::load_remote_models_from_file()
+        .map_err(|err| format!("core/models.json does not parse: {err}"))?;
+
+    let mut slugs: Vec<&str> = config.model.as_deref().into_iter().collect();
+    slugs.extend(models.iter().map(|model| model.slug.as_str()));
+    let Some(model) = slugs.first().copied() else {
+        return Err("core/models.json lists no models".to_string());
+    };
+    for slug in &slugs {
+        let family = ModelsManager::construct_model_family_bundled(slug, &models, config);
+        if family.base_instructions.trim().is_empty() {
+            return Err(format!("no instructions are bundled for model {slug}"));
+        }
+    }
+
+    let model_family = ModelsManager::construct_model_family_bundled(model, &models, config);
+    let tools_config = ToolsConfig::new(&ToolsConfigParams {
+        model_family: &model_family,
+        features: &config.features,
+    });
+    let (specs, _registry) = build_specs(&tools_config, None).build();
+    let specs: Vec<_> = specs
+        .into_iter()
+        .map(|configured| configured.spec)
+        .collect();
+    let tools = create_tools_json_for_responses_api(&specs)
+        .map_err(|err| format!("tool schemas do not serialize: {err}"))?;
+
+    Ok(BundledAssetsSummary {
+        files: BUNDLED_ASSETS.len(),
+        bytes: BUNDLED_ASSETS
+            .iter()
+            .map(|asset| asset.contents.len())
+            .sum(),
+        models: models.len(),
+        tools: tools.len(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_config;
+    use std::collections::HashSet;
+    use std::path::Path;
+    use std::path::PathBuf;
+
+    #[test]
+    fn bundled_assets_verify_for_default_config() {
+        let summary = verify_bundled_assets(&test_config()).expect("assets verify");
+        assert_eq!(summary.files, bundled_assets().len());
+        assert!(summary.models > 0);
+        assert!(summary.tools > 0);
+    }
+
+    /// Every prompt, template and metadata file that core compiles in with
+    /// `include_str!` is listed, under its real path, with that file's
+    /// contents.
+    #[test]
+    fn bundled_assets_list_every_included_file() {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .expect("workspace root");
+        for asset in bundled_assets() {
+            let on_disk = std::fs::read_to_string(workspace.join(asset.name))
+                .unwrap_or_else(|err| panic!("{} is not a file: {err}", asset.name));
+            assert_eq!(asset.contents, on_disk, "{} has other contents", asset.name);
+        }
+
+        let listed: HashSet<PathBuf> = bundled_assets()
+            .iter()
+            .map(|asset| {
+                workspace
+                    .join(asset.name)
+                    .canonicalize()
+                    .expect("listed file exists")
+            })
+            .collect();
+        let mut included = Vec::new();
+        collect_included_files(&workspace.join("core/src"), &mut included);
+        assert!(!included.is_empty());
+        for path in included {
+            let is_asset = matches!(
+                path.extension().and_then(|ext| ext.to_str()),
+                Some("md" | "xml" | "json" | "lark")
+            );
+            let path = path.canonicalize().expect("included file exists");
+            assert!(
+                !is_asset || listed.contains(&path),
+                "{} is compiled in but missing from BUNDLED_ASSETS",
+                path.display()
+            );
+        }
+    }
+
+    /// Paths of the files named by `include_str!` in the sources under `dir`.
+    fn collect_included_files(dir: &Path, out: &mut Vec<PathBuf>) {
+        for entry in std::fs::read_dir(dir).expect("read source dir") {
+            let path = entry.expect("dir entry").path();
+            if path.is_dir() {
+                collect_included_files(&path, out);
+                continue;
+            }
+            if path.extension().and_then(|ext| ext.to_str()) != Some("rs") {
+                continue;
+            }
+            let source = std::fs::read_to_string(&path).expect("read source");
+            for (start, pattern) in source.match_indices("include_str!(\"") {
+                let rest = &source[start + pattern.len()..];
+                if let Some(end) = rest.find('"') {
+                    out.push(path.parent().expect("source dir").join(&rest[..end]));
+                }
+            }
+        }
+    }
+}
@@ -6,6 +6,7 @@ use codex_api::AggregateStreamExt;
 use codex_api::ChatClient as ApiChatClient;
 use codex_api::CompactClient as ApiCompactClient;
 use codex_api::CompactionInput as ApiCompactionInput;
+use codex_api::EmbeddingsClient as ApiEmbeddingsClient;
 use codex_api::Prompt as ApiPrompt;
 use codex_api::RequestTelemetry;
 use codex_api::ReqwestTransport;
@@ -50,6 +51,8 @@ use crate::flags::CODEX_RS_SSE_FIXTURE;
 use crate::model_provider_info::ModelProviderInfo;
 use crate::model_provider_info::WireApi;
 use crate::openai_models::model_family::ModelFamily;
+use crate::request_trace::RequestTrace;
+use crate::request_trace::TraceCall;
 use crate::tools::spec::create_tools_json_for_chat_completions_api;
 use crate::tools::spec::create_tools_json_for_responses_api;
 
@@ -64,6 +67,7 @@ pub struct ModelClient {
     effort: Option<ReasoningEffortConfig>,
     summary: ReasoningSummaryConfig,
     session_source: SessionSource,
+    request_trace: Option<Arc<RequestTrace>>,
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -89,9 +93,17 @@ impl ModelClient {
             effort,
             summary,
             session_source,
+            request_trace: None,
         }
     }
 
+    /// Dump the request body and SSE stream of every model call made through
+    /// this client (and its copies) to `trace`.
+    pub(crate) fn with_request_trace(mut self, trace: RequestTrace) -> Self {
+        self.request_trace = Some(Arc::new(trace));
+        self
+    }
+
     pub fn get_model_context_window(&self) -> Option<i64> {
         let model_family = self.get_model_family();
         let effective_context_window_percent = model_family.effective_context_window_percent;
@@ -152,6 +164,7 @@ impl ModelClient {
         let api_prompt = build_api_prompt(prompt, instructions, tools_json);
         let conversation_id = self.conversation_id.to_string();
         let session_source = self.session_source.clone();
+        let trace_call = self.start_trace_call();
 
         let mut refreshed = false;
         loop {
@@ -161,7 +174,8 @@ impl ModelClient {
                 .to_api_provider(auth.as_ref().map(|a| a.mode))?;
             let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
             let transport = ReqwestTransport::new(build_reqwest_client());
-            let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
+            let (request_telemetry, sse_telemetry) =
+                self.build_streaming_telemetry(trace_call.clone());
             let client = ApiChatClient::new(transport, api_provider, api_auth)
                 .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
 
@@ -241,6 +255,7 @@ impl ModelClient {
         let api_prompt = build_api_prompt(prompt, instructions.clone(), tools_json);
         let conversation_id = self.conversation_id.to_string();
         let session_source = self.session_source.clone();
+        let trace_call = self.start_trace_call();
 
         let mut refreshed = false;
         loop {
@@ -250,7 +265,8 @@ impl ModelClient {
                 .to_api_provider(auth.as_ref().map(|a| a.mode))?;
             let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
             let transport = ReqwestTransport::new(build_reqwest_client());
-            let (request_telemetry, sse_telemetry) = self.build_streaming_telemetry();
+            let (request_telemetry, sse_telemetry) =
+                self.build_streaming_telemetry(trace_call.clone());
             let client = ApiResponsesClient::new(transport, api_provider, api_auth)
                 .with_telemetry(Some(request_telemetry), Some(sse_telemetry));
 
@@ -320,6 +336,27 @@ impl ModelClient {
         self.auth_manager.clone()
     }
 
+    /// Returns a copy of this client that talks to `model` instead of the
+    /// configured model.
+    pub fn with_model_family(&self, model: &str, model_family: ModelFamily) -> Self {
+        Self {
+            otel_manager: self
+                .otel_manager
+                .with_model(model, model_family.get_model_slug()),
+            model_family,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy of this client that requests `effort` instead of the
+    /// configured reasoning effort.
+    pub fn with_reasoning_effort(&self, effort: Option<ReasoningEffortConfig>) -> Self {
+        Self {
+            effort,
+            ..self.clone()
+        }
+    }
+
     /// Compacts the current conversation history using the Compact endpoint.
     ///
     /// This is a unary call (no streaming) that returns a new list of
@@ -368,12 +405,45 @@ impl ModelClient {
             .await
             .map_err(map_api_error)
     }
+
+    /// Embeds `input` with the provider's embeddings endpoint, returning one
+    /// vector per input in order.
+    pub async fn embed(&self, model: &str, input: &[String]) -> Result<Vec<Vec<f32>>> {
+        if input.is_empty() {
+            return Ok(Vec::new());
+        }
+        let auth_manager = self.auth_manager.clone();
+        let auth = auth_manager.as_ref().and_then(|m| m.auth());
+        let api_provider = self
+            .provider
+            .to_api_provider(auth.as_ref().map(|a| a.mode))?;
+        let api_auth = auth_provider_from_auth(auth.clone(), &self.provider).await?;
+        let transport = ReqwestTransport::new(build_reqwest_client());
+        let request_telemetry = self.build_request_telemetry();
+        let client = ApiEmbeddingsClient::new(transport, api_provider, api_auth)
+            .with_telemetry(Some(request_telemetry));
+
+        client
+            .embed(model, input, ApiHeaderMap::new())
+            .await
+            .map_err(map_api_error)
+    }
 }
 
 impl ModelClient {
+    fn start_trace_call(&self) -> Option<Arc<TraceCall>> {
+        self.request_trace
+            .as_ref()
+            .map(|trace| Arc::new(trace.start_call()))
+    }
+
     /// Builds request and SSE telemetry for streaming API calls (Chat/Responses).
-    fn build_streaming_telemetry(&self) -> (Arc<dyn RequestTelemetry>, Arc<dyn SseTelemetry>) {
-        let telemetry = Arc::new(ApiTelemetry::new(self.otel_manager.clone()));
+    fn build_streaming_telemetry(
+        &self,
+        trace_call: Option<Arc<TraceCall>>,
+    ) -> (Arc<dyn RequestTelemetry>, Arc<dyn SseTelemetry>) {
+        let telemetry =
+            Arc::new(ApiTelemetry::new(self.otel_manager.clone()).with_trace(trace_call));
         let request_telemetry: Arc<dyn RequestTelemetry> = telemetry.clone();
         let sse_telemetry: Arc<dyn SseTelemetry> = telemetry;
         (request_telemetry, sse_telemetry)
@@ -520,11 +590,20 @@ fn map_unauthorized_status(status: StatusCode) -> CodexErr {
 
 struct ApiTelemetry {
     otel_manager: OtelManager,
+    trace_call: Option<Arc<TraceCall>>,
 }
 
 impl ApiTelemetry {
     fn new(otel_manager: OtelManager) -> Self {
-        Self { otel_manager }
+        Self {
+            otel_manager,
+            trace_call: None,
+        }
+    }
+
+    fn with_trace(mut self, trace_call: Option<Arc<TraceCall>>) -> Self {
+        self.trace_call = trace_call;
+        self
     }
 }
 
@@ -556,5 +635,14 @@ impl SseTelemetry for ApiTelemetry {
         duration: Duration,
     ) {
         self.otel_manager.log_sse_event(result, duration);
+        if let (Some(trace_call), Ok(Some(Ok(event)))) = (self.trace_call.as_ref(), result) {
+            trace_call.record_event(event);
+        }
+    }
+
+    fn on_stream_request(&self, _path: &str, body: &Value) {
+        if let Some(trace_call) = self.trace_call.as_ref() {
+            trace_call.record_request(body);
+        }
     }
 }
@@ -1,6 +1,7 @@
 use crate::client_common::tools::ToolSpec;
 use crate::error::Result;
 use crate::openai_models::model_family::ModelFamily;
+use crate::prompt_sections::ComposedInstructions;
 pub use codex_api::common::ResponseEvent;
 use codex_apply_patch::APPLY_PATCH_TOOL_INSTRUCTIONS;
 use codex_protocol::models::ResponseItem;
@@ -39,6 +40,18 @@ pub struct Prompt {
     /// Optional override for the built-in BASE_INSTRUCTIONS.
     pub base_instructions_override: Option<String>,
 
+    /// Response language and timezone preferences appended to the base
+    /// instructions.
+    pub(crate) locale_instructions: Option<String>,
+
+    /// Configured instructions for the active model, appended to the base
+    /// instructions.
+    pub(crate) model_instructions: Option<String>,
+
+    /// Sections from `[prompt_sections]` that the base instructions are
+    /// placed among, when configured.
+    pub(crate) composed_instructions: Option<ComposedInstructions>,
+
     /// Optional the output schema for the model's response.
     pub output_schema: Option<Value>,
 }
@@ -58,13 +71,25 @@ impl Prompt {
             ToolSpec::Freeform(f) => f.name == "apply_patch",
             _ => false,
         });
-        if self.base_instructions_override.is_none()
+        let instructions = if self.base_instructions_override.is_none()
             && model.needs_special_apply_patch_instructions
             && !is_apply_patch_tool_present
         {
             Cow::Owned(format!("{base}\n{APPLY_PATCH_TOOL_INSTRUCTIONS}"))
         } else {
             Cow::Borrowed(base)
+        };
+        let instructions = match &self.model_instructions {
+            Some(overlay) => Cow::Owned(format!("{instructions}\n\n{overlay}")),
+            None => instructions,
+        };
+        let instructions = match &self.composed_instructions {
+            Some(composed) => Cow::Owned(composed.render(&instructions)),
+            None => instructions,
+        };
+        match &self.locale_instructions {
+            Some(locale) => Cow::Owned(format!("{instructions}\n\n{locale}")),
+            None => instructions,
         }
     }
 
@@ -329,6 +354,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn appends_locale_instructions() {
+        let prompt = Prompt {
+            base_instructions_override: Some("Base.".to_string()),
+            locale_instructions: Some("## Locale\n\n- Respond in Japanese.".to_string()),
+            ..Default::default()
+        };
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5.1", &config);
+
+        assert_eq!(
+            prompt.get_full_instructions(&model_family),
+            "Base.\n\n## Locale\n\n- Respond in Japanese."
+        );
+    }
+
+    #[test]
+    fn appends_model_instructions_before_locale() {
+        let prompt = Prompt {
+            base_instructions_override: Some("Base.".to_string()),
+            model_instructions: Some("Keep tool calls small.".to_string()),
+            locale_instructions: Some("## Locale\n\n- Respond in Japanese.".to_string()),
+            ..Default::default()
+        };
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5.1", &config);
+
+        assert_eq!(
+            prompt.get_full_instructions(&model_family),
+            "Base.\n\nKeep tool calls small.\n\n## Locale\n\n- Respond in Japanese."
+        );
+    }
+
     #[test]
     fn serializes_text_verbosity_when_set() {
         let input: Vec<ResponseItem> = vec![];
@@ -1,18 +1,22 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
+use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicU64;
 use std::sync::atomic::Ordering;
+use std::time::Duration;
 
 use crate::AuthManager;
 use crate::SandboxState;
+use crate::best_of;
 use crate::client_common::REVIEW_PROMPT;
 use crate::compact;
 use crate::compact::run_inline_auto_compact_task;
 use crate::compact::should_use_remote_compact_task;
 use crate::compact_remote::run_inline_remote_auto_compact_task;
+use crate::dependency_report;
 use crate::exec_policy::load_exec_policy_for_features;
 use crate::features::Feature;
 use crate::features::Features;
@@ -23,8 +27,10 @@ use crate::parse_turn_item;
 use crate::stream_events_utils::HandleOutputCtx;
 use crate::stream_events_utils::handle_non_tool_response_item;
 use crate::stream_events_utils::handle_output_item_done;
+use crate::structured_output::StructuredOutputParser;
 use crate::terminal;
 use crate::truncate::TruncationPolicy;
+use crate::truncate::approx_tokens_from_byte_count;
 use crate::user_notification::UserNotifier;
 use crate::util::error_or_panic;
 use async_channel::Receiver;
@@ -32,6 +38,7 @@ use async_channel::Sender;
 use codex_protocol::ConversationId;
 use codex_protocol::approvals::ExecPolicyAmendment;
 use codex_protocol::items::TurnItem;
+use codex_protocol::plan_tool::PlanItemArg;
 use codex_protocol::protocol::FileChange;
 use codex_protocol::protocol::HasLegacyEvent;
 use codex_protocol::protocol::ItemCompletedEvent;
@@ -39,9 +46,13 @@ use codex_protocol::protocol::ItemStartedEvent;
 use codex_protocol::protocol::RawResponseItemEvent;
 use codex_protocol::protocol::ReviewRequest;
 use codex_protocol::protocol::RolloutItem;
+use codex_protocol::protocol::SessionEnvironment;
 use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::SettingChange;
 use codex_protocol::protocol::TaskStartedEvent;
 use codex_protocol::protocol::TurnAbortReason;
+use codex_protocol::protocol::TurnCompletedMetricsEvent;
+use codex_protocol::protocol::TurnContextChangedEvent;
 use codex_protocol::protocol::TurnContextItem;
 use codex_rmcp_client::ElicitationResponse;
 use futures::future::BoxFuture;
@@ -72,6 +83,8 @@ use tracing::warn;
 
 use crate::ModelProviderInfo;
 use crate::WireApi;
+use crate::agent_budget::AgentBudget;
+use crate::annotations::Annotations;
 use crate::client::ModelClient;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
@@ -81,48 +94,96 @@ use crate::config::Constrained;
 use crate::config::ConstraintError;
 use crate::config::ConstraintResult;
 use crate::config::GhostSnapshotConfig;
+use crate::config::types::BestOf;
+use crate::config::types::PatchCritic;
+use crate::config::types::PatchPreview;
 use crate::config::types::ShellEnvironmentPolicy;
+use crate::config::types::StallDetection;
+use crate::config::types::WorkflowStep;
+use crate::context_ignore::ContextIgnore;
 use crate::context_manager::ContextManager;
+use crate::context_manager::HistoryFilterReport;
+use crate::context_manager::apply_history_filter;
+use crate::context_manager::repair_rejected_item;
+use crate::context_manifest::build_context_manifest;
 use crate::environment_context::EnvironmentContext;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 #[cfg(test)]
 use crate::exec::StreamOutput;
 use crate::exec_policy::ExecPolicyUpdateError;
+use crate::external_edits;
+use crate::external_edits::ExternalEdit;
+use crate::external_edits::ExternalEditKind;
+use crate::file_mentions::AttachedFiles;
+use crate::file_mentions::FileMentionInjections;
+use crate::file_mentions::build_file_mention_injections;
+use crate::index_watcher::IndexWatcher;
+use crate::locale::locale_instructions;
+use crate::login_env::start_login_env_capture;
 use crate::mcp::auth::compute_auth_statuses;
 use crate::mcp_connection_manager::McpConnectionManager;
 use crate::memories;
+use crate::memories::MemoryRanker;
+use crate::model_probe::ProbeOutcome;
+use crate::model_probe::missing_model_message;
+use crate::model_probe::probe_model;
 use crate::model_provider_info::CHAT_WIRE_API_DEPRECATION_SUMMARY;
+use crate::output_spill::OutputSpill;
 use crate::project_doc::get_user_instructions;
+use crate::prompt_sections::PromptSections;
+use crate::protected_paths::ProtectedPaths;
 use crate::protocol::AgentMessageContentDeltaEvent;
 use crate::protocol::AgentReasoningSectionBreakEvent;
 use crate::protocol::ApplyPatchApprovalRequestEvent;
+use crate::protocol::ApprovalPresetId;
 use crate::protocol::AskForApproval;
 use crate::protocol::BackgroundEventEvent;
+use crate::protocol::CandidatesProposedEvent;
+use crate::protocol::CompactedItem;
+use crate::protocol::ContextRepairedEvent;
+use crate::protocol::ContextTrimReason;
+use crate::protocol::ContextTrimmedEvent;
 use crate::protocol::DeprecationNoticeEvent;
 use crate::protocol::ErrorEvent;
 use crate::protocol::Event;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecApprovalRequestEvent;
+use crate::protocol::MemoryCandidatesProposedEvent;
 use crate::protocol::Op;
+use crate::protocol::PatchConflict;
+use crate::protocol::PatchConflictEvent;
+use crate::protocol::PatchConflictResolution;
+use crate::protocol::QuestionEvent;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::ReasoningContentDeltaEvent;
 use crate::protocol::ReasoningRawContentDeltaEvent;
+use crate::protocol::ResponseCandidate;
 use crate::protocol::ReviewDecision;
 use crate::protocol::SandboxPolicy;
 use crate::protocol::SessionConfiguredEvent;
 use crate::protocol::SkillErrorInfo;
 use crate::protocol::SkillMetadata as ProtocolSkillMetadata;
+use crate::protocol::StalledEvent;
 use crate::protocol::StreamErrorEvent;
+use crate::protocol::StructuredOutputDeltaEvent;
 use crate::protocol::Submission;
 use crate::protocol::TokenCountEvent;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
-use crate::protocol::TurnDiffEvent;
 use crate::protocol::WarningEvent;
+use crate::repo_map::RepoMap;
+use crate::repo_map::RepoMapCache;
+use crate::repo_map::SESSION_REPO_MAP_MAX_TOKENS;
+use crate::repo_map::generate_repo_map;
+use crate::request_trace::RequestTrace;
 use crate::rollout::RolloutRecorder;
 use crate::rollout::RolloutRecorderParams;
 use crate::rollout::map_session_init_error;
+use crate::scratch_dir::ScratchDir;
+use crate::semantic_index::index_root;
+use crate::session_environment::SystemInfo;
+use crate::session_environment::collect_session_environment;
 use crate::shell;
 use crate::shell_snapshot::ShellSnapshot;
 use crate::skills::SkillError;
@@ -130,6 +191,8 @@ use crate::skills::SkillInjections;
 use crate::skills::SkillMetadata;
 use crate::skills::SkillsManager;
 use crate::skills::build_skill_injections;
+use crate::startup_timings::StartupTimings;
+use crate::startup_timings::timed;
 use crate::state::ActiveTurn;
 use crate::state::SessionServices;
 use crate::state::SessionState;
@@ -137,6 +200,8 @@ use crate::tasks::GhostSnapshotTask;
 use crate::tasks::ReviewTask;
 use crate::tasks::SessionTask;
 use crate::tasks::SessionTaskContext;
+use crate::tool_hints::ToolHints;
+use crate::tool_hints::ToolUsageTracker;
 use crate::tools::ToolRouter;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::parallel::ToolCallRuntime;
@@ -145,19 +210,25 @@ use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::ToolsConfigParams;
 use crate::turn_diff_tracker::TurnDiffTracker;
 use crate::unified_exec::UnifiedExecSessionManager;
+use crate::usage_stats::UsageStatsRecorder;
 use crate::user_instructions::DeveloperInstructions;
 use crate::user_instructions::UserInstructions;
 use crate::user_notification::UserNotification;
 use crate::util::backoff;
+use crate::wsl_paths::WslPaths;
 use codex_async_utils::OrCancelExt;
 use codex_execpolicy::Policy as ExecPolicy;
 use codex_otel::otel_manager::OtelManager;
+use codex_protocol::config_types::ReasoningDisplay;
 use codex_protocol::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use codex_protocol::config_types::Verbosity;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::ErrorCategory;
+use codex_protocol::protocol::ErrorDetails;
 use codex_protocol::protocol::InitialHistory;
 use codex_protocol::user_input::UserInput;
 use codex_utils_readiness::Readiness;
@@ -181,6 +252,8 @@ pub struct CodexSpawnOk {
 
 pub(crate) const INITIAL_SUBMIT_ID: &str = "";
 pub(crate) const SUBMISSION_CHANNEL_CAPACITY: usize = 64;
+/// Tool outputs longer than this are truncated when repairing a rejected turn.
+const CONTEXT_REPAIR_OUTPUT_BYTES: usize = 4 * 1024;
 static CHAT_WIRE_API_DEPRECATION_EMITTED: AtomicBool = AtomicBool::new(false);
 
 fn maybe_push_chat_wire_api_deprecation(
@@ -207,6 +280,21 @@ fn maybe_push_chat_wire_api_deprecation(
     });
 }
 
+/// Events that carry reasoning content and are withheld from clients when
+/// reasoning display is hidden.
+fn is_reasoning_stream_event(msg: &EventMsg) -> bool {
+    matches!(
+        msg,
+        EventMsg::AgentReasoning(_)
+            | EventMsg::AgentReasoningDelta(_)
+            | EventMsg::AgentReasoningRawContent(_)
+            | EventMsg::AgentReasoningRawContentDelta(_)
+            | EventMsg::AgentReasoningSectionBreak(_)
+            | EventMsg::ReasoningContentDelta(_)
+            | EventMsg::ReasoningRawContentDelta(_)
+    )
+}
+
 impl Codex {
     /// Spawn a new [`Codex`] and initialize the session.
     pub async fn spawn(
@@ -216,9 +304,34 @@ impl Codex {
         skills_manager: Arc<SkillsManager>,
         conversation_history: InitialHistory,
         session_source: SessionSource,
+    ) -> CodexResult<CodexSpawnOk> {
+        let agent_budget = AgentBudget::root(config.sub_agents.clone());
+        Self::spawn_with_budget(
+            config,
+            auth_manager,
+            models_manager,
+            skills_manager,
+            conversation_history,
+            session_source,
+            agent_budget,
+        )
+        .await
+    }
+
+    /// Spawn a session that draws on `agent_budget`, e.g. a sub-agent's
+    /// share of its parent's.
+    pub(crate) async fn spawn_with_budget(
+        config: Config,
+        auth_manager: Arc<AuthManager>,
+        models_manager: Arc<ModelsManager>,
+        skills_manager: Arc<SkillsManager>,
+        conversation_history: InitialHistory,
+        session_source: SessionSource,
+        agent_budget: Arc<AgentBudget>,
     ) -> CodexResult<CodexSpawnOk> {
         let (tx_sub, rx_sub) = async_channel::bounded(SUBMISSION_CHANNEL_CAPACITY);
         let (tx_event, rx_event) = async_channel::unbounded();
+        let mut startup_timings = StartupTimings::new();
 
         let loaded_skills = config
             .features
@@ -235,33 +348,107 @@ impl Codex {
             }
         }
 
-        let user_instructions = get_user_instructions(
-            &config,
-            loaded_skills
-                .as_ref()
-                .map(|outcome| outcome.skills.as_slice()),
-        )
-        .await;
-
-        let exec_policy = load_exec_policy_for_features(&config.features, &config.codex_home)
+        let skills = loaded_skills
+            .as_ref()
+            .map(|outcome| outcome.skills.as_slice());
+        // Project docs, the repo map, the exec policy and the login
+        // environment do not depend on each other, so they load concurrently.
+        let instructions_fut = timed(async {
+            // With `[prompt_sections]`, project docs and memories are sent only
+            // as configured sections of the instructions.
+            match &config.prompt_sections {
+                Some(sections) => (
+                    None,
+                    Some(Arc::new(
+                        PromptSections::load(&config, sections, skills).await,
+                    )),
+                ),
+                None => (get_user_instructions(&config, skills).await, None),
+            }
+        });
+        let repo_map_fut = timed(async {
+            if !config.features.enabled(Feature::RepoMap) {
+                return None;
+            }
+            let cwd = config.cwd.clone();
+            let context_ignore = ContextIgnore::new(&cwd, &config.context_ignore);
+            tokio::task::spawn_blocking(move || {
+                generate_repo_map(&cwd, &context_ignore, SESSION_REPO_MAP_MAX_TOKENS)
+            })
             .await
+            .unwrap_or_else(|err| {
+                error!("failed to generate repo map: {err}");
+                None
+            })
+        });
+        let exec_policy_fut = timed(load_exec_policy_for_features(
+            &config.features,
+            &config.codex_home,
+        ));
+        if config.shell_environment_policy.capture_login_env {
+            start_login_env_capture(shell::default_user_shell());
+        }
+        let (
+            ((user_instructions, prompt_sections), instructions_elapsed),
+            (repo_map, repo_map_elapsed),
+            (exec_policy, exec_policy_elapsed),
+        ) = tokio::join!(instructions_fut, repo_map_fut, exec_policy_fut);
+        startup_timings.record("project_docs", instructions_elapsed);
+        startup_timings.record("repo_map", repo_map_elapsed);
+        startup_timings.record("exec_policy", exec_policy_elapsed);
+
+        let exec_policy = exec_policy
             .map_err(|err| CodexErr::Fatal(format!("failed to load execpolicy: {err}")))?;
         let exec_policy = Arc::new(RwLock::new(exec_policy));
 
-        let config = Arc::new(config);
         if config.features.enabled(Feature::RemoteModels)
-            && let Err(err) = models_manager.refresh_available_models(&config).await
+            && let Err(err) = startup_timings
+                .time(
+                    "remote_models",
+                    models_manager.refresh_available_models(&config),
+                )
+                .await
         {
             error!("failed to refresh available models: {err:?}");
         }
         let model = models_manager.get_model(&config.model, &config).await;
+        let mut probe_warnings = Vec::new();
+        if config.features.enabled(Feature::ModelProbe) {
+            let outcome = startup_timings
+                .time("model_probe", probe_model(&config, &auth_manager, &model))
+                .await;
+            match outcome {
+                ProbeOutcome::Found(probed) => {
+                    if config.model_context_window.is_none() {
+                        config.model_context_window = probed.context_window;
+                    }
+                    if probed.supports_tools == Some(false) {
+                        probe_warnings.push(format!(
+                            "Provider `{}` reports that model `{model}` does not support tools, so shell commands and file edits will fail. Choose a model with tool support.",
+                            config.model_provider_id
+                        ));
+                    }
+                }
+                ProbeOutcome::Missing { available } => {
+                    return Err(CodexErr::Fatal(missing_model_message(
+                        &config, &model, &available,
+                    )));
+                }
+                ProbeOutcome::Skipped(reason) => debug!("model probe skipped: {reason}"),
+            }
+        }
+
+        let config = Arc::new(config);
         let session_configuration = SessionConfiguration {
             provider: config.model_provider.clone(),
             model: model.clone(),
             model_reasoning_effort: config.model_reasoning_effort,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions,
+            prompt_sections,
+            repo_map,
             base_instructions: config.base_instructions.clone(),
             compact_prompt: config.compact_prompt.clone(),
             approval_policy: config.approval_policy.clone(),
@@ -284,6 +471,8 @@ impl Codex {
             conversation_history,
             session_source_clone,
             skills_manager,
+            agent_budget,
+            startup_timings,
         )
         .await
         .map_err(|e| {
@@ -291,6 +480,14 @@ impl Codex {
             map_session_init_error(&e, &config.codex_home)
         })?;
         let conversation_id = session.conversation_id;
+        for message in probe_warnings {
+            session
+                .send_event_raw(Event {
+                    id: INITIAL_SUBMIT_ID.to_owned(),
+                    msg: EventMsg::Warning(WarningEvent { message }),
+                })
+                .await;
+        }
 
         // This task will run until Op::Shutdown is received.
         tokio::spawn(submission_loop(session, config, rx_sub));
@@ -365,25 +562,59 @@ pub(crate) struct TurnContext {
     pub(crate) base_instructions: Option<String>,
     pub(crate) compact_prompt: Option<String>,
     pub(crate) user_instructions: Option<String>,
+    pub(crate) prompt_sections: Option<Arc<PromptSections>>,
+    pub(crate) repo_map: Option<String>,
+    pub(crate) locale_instructions: Option<String>,
+    /// Overlays from `[[model_instructions]]` that match this turn's model.
+    pub(crate) model_instructions: Option<String>,
     pub(crate) approval_policy: AskForApproval,
     pub(crate) sandbox_policy: SandboxPolicy,
     pub(crate) shell_environment_policy: ShellEnvironmentPolicy,
     pub(crate) tools_config: ToolsConfig,
     pub(crate) ghost_snapshot: GhostSnapshotConfig,
     pub(crate) final_output_json_schema: Option<Value>,
+    /// Sample several responses for the first request of each task.
+    pub(crate) best_of: Option<BestOf>,
+    /// Review pending patches with a separate model request.
+    pub(crate) patch_critic: Option<PatchCritic>,
+    /// Checks run against a copy of the repository with a pending patch.
+    pub(crate) patch_preview: Option<PatchPreview>,
     pub(crate) codex_linux_sandbox_exe: Option<PathBuf>,
     pub(crate) tool_call_gate: Arc<ReadinessFlag>,
     pub(crate) exec_policy: Arc<RwLock<ExecPolicy>>,
     pub(crate) truncation_policy: TruncationPolicy,
+    /// Paths that must never be read into model context.
+    pub(crate) context_ignore: Arc<ContextIgnore>,
+    /// Paths that patches may not change without approval, or at all.
+    pub(crate) protected_paths: Arc<ProtectedPaths>,
+    /// Windows/WSL path translation, when Codex runs in or against WSL.
+    pub(crate) wsl_paths: Option<WslPaths>,
 }
 
 impl TurnContext {
     pub(crate) fn resolve_path(&self, path: Option<String>) -> PathBuf {
-        path.as_ref()
-            .map(PathBuf::from)
+        path.as_deref()
+            .map(|p| self.host_path(p))
             .map_or_else(|| self.cwd.clone(), |p| self.cwd.join(p))
     }
 
+    /// Convert a path the model supplied into a form this host can open,
+    /// translating between Windows and WSL styles when needed.
+    pub(crate) fn host_path(&self, path: &str) -> PathBuf {
+        match &self.wsl_paths {
+            Some(wsl_paths) => PathBuf::from(wsl_paths.to_host_path(path)),
+            None => PathBuf::from(path),
+        }
+    }
+
+    /// Adapt a command for the side of a Windows/WSL setup that runs it.
+    pub(crate) fn exec_command(&self, command: Vec<String>, cwd: &Path) -> Vec<String> {
+        match &self.wsl_paths {
+            Some(wsl_paths) => wsl_paths.to_exec_command(command, cwd),
+            None => command,
+        }
+    }
+
     pub(crate) fn compact_prompt(&self) -> &str {
         self.compact_prompt
             .as_deref()
@@ -402,12 +633,21 @@ pub(crate) struct SessionConfiguration {
     model_reasoning_effort: Option<ReasoningEffortConfig>,
     model_reasoning_summary: ReasoningSummaryConfig,
 
+    /// Output verbosity override for models that support `text.verbosity`.
+    model_verbosity: Option<Verbosity>,
+
     /// Developer instructions that supplement the base instructions.
     developer_instructions: Option<String>,
 
     /// Model instructions that are appended to the base instructions.
     user_instructions: Option<String>,
 
+    /// Ordered instruction sections from `[prompt_sections]`, when set.
+    prompt_sections: Option<Arc<PromptSections>>,
+
+    /// Repository map injected as orientation context at session start.
+    repo_map: Option<String>,
+
     /// Base instructions override.
     base_instructions: Option<String>,
 
@@ -449,10 +689,16 @@ impl SessionConfiguration {
         if let Some(summary) = updates.reasoning_summary {
             next_configuration.model_reasoning_summary = summary;
         }
+        if let Some(verbosity) = updates.verbosity {
+            next_configuration.model_verbosity = verbosity;
+        }
         if let Some(approval_policy) = updates.approval_policy {
             next_configuration.approval_policy.set(approval_policy)?;
         }
         if let Some(sandbox_policy) = updates.sandbox_policy.clone() {
+            self.original_config_do_not_use
+                .sandbox_requirements
+                .check(&sandbox_policy)?;
             next_configuration.sandbox_policy = sandbox_policy;
         }
         if let Some(cwd) = updates.cwd.clone() {
@@ -460,6 +706,22 @@ impl SessionConfiguration {
         }
         Ok(next_configuration)
     }
+
+    /// The turn settings that differ in `next`.
+    fn changes_to(&self, next: &SessionConfiguration) -> TurnContextChangedEvent {
+        fn change<T: Clone + PartialEq>(old: &T, new: &T) -> Option<SettingChange<T>> {
+            (old != new).then(|| SettingChange {
+                old: old.clone(),
+                new: new.clone(),
+            })
+        }
+        TurnContextChangedEvent {
+            model: change(&self.model, &next.model),
+            approval_policy: change(&self.approval_policy.value(), &next.approval_policy.value()),
+            sandbox_policy: change(&self.sandbox_policy, &next.sandbox_policy),
+            cwd: change(&self.cwd, &next.cwd),
+        }
+    }
 }
 
 #[derive(Default, Clone)]
@@ -470,6 +732,7 @@ pub(crate) struct SessionSettingsUpdate {
     pub(crate) model: Option<String>,
     pub(crate) reasoning_effort: Option<Option<ReasoningEffortConfig>>,
     pub(crate) reasoning_summary: Option<ReasoningSummaryConfig>,
+    pub(crate) verbosity: Option<Option<Verbosity>>,
     pub(crate) final_output_json_schema: Option<Option<Value>>,
 }
 
@@ -481,6 +744,7 @@ impl Session {
         let mut per_turn_config = (*config).clone();
         per_turn_config.model_reasoning_effort = session_configuration.model_reasoning_effort;
         per_turn_config.model_reasoning_summary = session_configuration.model_reasoning_summary;
+        per_turn_config.model_verbosity = session_configuration.model_verbosity;
         per_turn_config.features = config.features.clone();
         per_turn_config
     }
@@ -502,7 +766,7 @@ impl Session {
         );
 
         let per_turn_config = Arc::new(per_turn_config);
-        let client = ModelClient::new(
+        let mut client = ModelClient::new(
             per_turn_config.clone(),
             auth_manager,
             model_family.clone(),
@@ -513,11 +777,25 @@ impl Session {
             conversation_id,
             session_configuration.session_source.clone(),
         );
+        if per_turn_config.features.enabled(Feature::RequestTraces) {
+            client = client.with_request_trace(RequestTrace::new(
+                &per_turn_config.codex_home,
+                conversation_id,
+                &sub_id,
+            ));
+        }
 
-        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+        let mut tools_config = ToolsConfig::new(&ToolsConfigParams {
             model_family: &model_family,
             features: &per_turn_config.features,
         });
+        tools_config.description_token_budget = per_turn_config.tool_description_budget;
+        if per_turn_config.features.enabled(Feature::ToolPruning) {
+            tools_config.prune_for_policies(
+                session_configuration.approval_policy.value(),
+                &session_configuration.sandbox_policy,
+            );
+        }
 
         TurnContext {
             sub_id,
@@ -527,12 +805,20 @@ impl Session {
             base_instructions: session_configuration.base_instructions.clone(),
             compact_prompt: session_configuration.compact_prompt.clone(),
             user_instructions: session_configuration.user_instructions.clone(),
+            prompt_sections: session_configuration.prompt_sections.clone(),
+            repo_map: session_configuration.repo_map.clone(),
+            locale_instructions: locale_instructions(&per_turn_config),
+            model_instructions: per_turn_config
+                .instructions_for_model(model_family.get_model_slug()),
             approval_policy: session_configuration.approval_policy.value(),
             sandbox_policy: session_configuration.sandbox_policy.clone(),
             shell_environment_policy: per_turn_config.shell_environment_policy.clone(),
             tools_config,
             ghost_snapshot: per_turn_config.ghost_snapshot.clone(),
             final_output_json_schema: None,
+            best_of: per_turn_config.best_of,
+            patch_critic: per_turn_config.patch_critic.clone(),
+            patch_preview: per_turn_config.patch_preview.clone(),
             codex_linux_sandbox_exe: per_turn_config.codex_linux_sandbox_exe.clone(),
             tool_call_gate: Arc::new(ReadinessFlag::new()),
             exec_policy: session_configuration.exec_policy.clone(),
@@ -540,6 +826,15 @@ impl Session {
                 per_turn_config.as_ref(),
                 model_family.truncation_policy,
             ),
+            context_ignore: Arc::new(ContextIgnore::new(
+                &session_configuration.cwd,
+                &per_turn_config.context_ignore,
+            )),
+            protected_paths: Arc::new(ProtectedPaths::new(
+                &session_configuration.cwd,
+                &per_turn_config.protected_paths,
+            )),
+            wsl_paths: per_turn_config.wsl.clone().map(WslPaths::from),
         }
     }
 
@@ -553,6 +848,8 @@ impl Session {
         initial_history: InitialHistory,
         session_source: SessionSource,
         skills_manager: Arc<SkillsManager>,
+        agent_budget: Arc<AgentBudget>,
+        mut startup_timings: StartupTimings,
     ) -> anyhow::Result<Arc<Self>> {
         debug!(
             "Configuring session: model={}; provider={:?}",
@@ -588,17 +885,47 @@ impl Session {
         // - initialize RolloutRecorder with new or resumed session info
         // - perform default shell discovery
         // - load history metadata
-        let rollout_fut = RolloutRecorder::new(&config, rollout_params);
+        // - snapshot the OS and tool versions for the rollout metadata
+        // - read MCP OAuth credentials, unless `fast_startup` leaves that to
+        //   the MCP warm-up
+        let fast_startup = config.features.enabled(Feature::FastStartup);
+        let sandbox_policy = session_configuration.sandbox_policy.clone();
+        let rollout_fut = timed(async {
+            let environment = collect_session_environment(&sandbox_policy).await;
+            let recorder = RolloutRecorder::new(
+                &config,
+                rollout_params.with_environment(environment.clone()),
+            )
+            .await;
+            (recorder, environment)
+        });
 
-        let history_meta_fut = crate::message_history::history_metadata(&config);
-        let auth_statuses_fut = compute_auth_statuses(
-            config.mcp_servers.iter(),
-            config.mcp_oauth_credentials_store_mode,
-        );
+        let history_meta_fut = timed(crate::message_history::history_metadata(&config));
+        let auth_statuses_fut = timed(async {
+            if fast_startup {
+                None
+            } else {
+                Some(
+                    compute_auth_statuses(
+                        config.mcp_servers.iter(),
+                        config.mcp_oauth_credentials_store_mode,
+                    )
+                    .await,
+                )
+            }
+        });
 
         // Join all independent futures.
-        let (rollout_recorder, (history_log_id, history_entry_count), auth_statuses) =
-            tokio::join!(rollout_fut, history_meta_fut, auth_statuses_fut);
+        let (
+            ((rollout_recorder, session_environment), rollout_elapsed),
+            ((history_log_id, history_entry_count), history_elapsed),
+            (auth_statuses, auth_statuses_elapsed),
+        ) = tokio::join!(rollout_fut, history_meta_fut, auth_statuses_fut);
+        startup_timings.record("rollout", rollout_elapsed);
+        startup_timings.record("history_metadata", history_elapsed);
+        if auth_statuses.is_some() {
+            startup_timings.record("mcp_auth", auth_statuses_elapsed);
+        }
 
         let rollout_recorder = rollout_recorder.map_err(|e| {
             error!("failed to initialize rollout recorder: {e:#}");
@@ -653,26 +980,82 @@ impl Session {
         let mut default_shell = shell::default_user_shell();
         // Create the mutable state for the Session.
         if config.features.enabled(Feature::ShellSnapshot) {
-            default_shell.shell_snapshot =
-                ShellSnapshot::try_new(&config.codex_home, &default_shell)
-                    .await
-                    .map(Arc::new);
+            default_shell.shell_snapshot = startup_timings
+                .time(
+                    "shell_snapshot",
+                    ShellSnapshot::try_new(&config.codex_home, &default_shell),
+                )
+                .await
+                .map(Arc::new);
         }
-        let state = SessionState::new(session_configuration.clone());
+        let mut state = SessionState::new(session_configuration.clone());
+        state.reasoning_display = ReasoningDisplay::from_flags(
+            config.hide_agent_reasoning,
+            config.show_raw_agent_reasoning,
+        );
+
+        let index_watcher = if config.features.enabled(Feature::SemanticSearch)
+            || config.features.enabled(Feature::RepoMap)
+        {
+            match index_root(&session_configuration.cwd) {
+                Some(root) => startup_timings
+                    .time(
+                        "index_watcher",
+                        tokio::task::spawn_blocking(move || IndexWatcher::start(&root)),
+                    )
+                    .await
+                    .ok()
+                    .flatten()
+                    .map(Arc::new),
+                None => None,
+            }
+        } else {
+            None
+        };
+
+        let scratch_dir = if config.features.enabled(Feature::ScratchDir) {
+            ScratchDir::create(conversation_id, config.scratch_dir.keep_on_error)
+                .inspect_err(|err| warn!("failed to create scratch directory: {err}"))
+                .ok()
+        } else {
+            None
+        };
+        let output_spill = config
+            .features
+            .enabled(Feature::OutputSpill)
+            .then(|| OutputSpill::new(scratch_dir.as_ref().map(ScratchDir::path), conversation_id));
+        let tool_usage = if config.features.enabled(Feature::ToolHints) {
+            Some(ToolUsageTracker::load(&config.codex_home, &session_configuration.cwd).await)
+        } else {
+            None
+        };
+        let memory_ranker = MemoryRanker::load(&config).await;
+        let usage_stats = UsageStatsRecorder::new(&config.codex_home, &config.telemetry);
 
         let services = SessionServices {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: CancellationToken::new(),
             unified_exec_manager: UnifiedExecSessionManager::default(),
-            notifier: UserNotifier::new(config.notify.clone()),
+            notifier: UserNotifier::new(config.notify.clone(), config.notify_sinks.clone()),
             rollout: Mutex::new(Some(rollout_recorder)),
             user_shell: Arc::new(default_shell),
-            show_raw_agent_reasoning: config.show_raw_agent_reasoning,
             auth_manager: Arc::clone(&auth_manager),
             otel_manager,
             models_manager: Arc::clone(&models_manager),
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
+            index_watcher,
+            repo_map_cache: RepoMapCache::default(),
+            session_environment,
+            scratch_dir,
+            output_spill,
+            tool_usage,
+            memory_ranker,
+            usage_stats,
+            agent_budget,
+            annotations: std::sync::Mutex::new(Annotations::from_rollout(
+                &initial_history.get_rollout_items(),
+            )),
         };
 
         let sess = Arc::new(Session {
@@ -716,22 +1099,54 @@ impl Session {
             codex_linux_sandbox_exe: config.codex_linux_sandbox_exe.clone(),
             sandbox_cwd: session_configuration.cwd.clone(),
         };
-        sess.services
-            .mcp_connection_manager
-            .write()
-            .await
-            .initialize(
-                config.mcp_servers.clone(),
-                config.mcp_oauth_credentials_store_mode,
-                auth_statuses.clone(),
-                tx_event.clone(),
-                sess.services.mcp_startup_cancellation_token.clone(),
-                sandbox_state,
-            )
+        // Take the write lock before anything else can read the manager, so
+        // with `fast_startup` the first turn waits for the warm-up instead of
+        // seeing no MCP servers at all.
+        let mut mcp_connection_manager = Arc::clone(&sess.services.mcp_connection_manager)
+            .write_owned()
             .await;
+        let cancel_token = sess.services.mcp_startup_cancellation_token.clone();
+        let mcp_config = Arc::clone(&config);
+        let mcp_tx_event = tx_event.clone();
+        let start_mcp = async move {
+            let auth_statuses = match auth_statuses {
+                Some(auth_statuses) => auth_statuses,
+                None => {
+                    compute_auth_statuses(
+                        mcp_config.mcp_servers.iter(),
+                        mcp_config.mcp_oauth_credentials_store_mode,
+                    )
+                    .await
+                }
+            };
+            mcp_connection_manager
+                .initialize(
+                    mcp_config.mcp_servers.clone(),
+                    mcp_config.mcp_oauth_credentials_store_mode,
+                    auth_statuses,
+                    mcp_tx_event,
+                    cancel_token,
+                    sandbox_state,
+                )
+                .await;
+        };
+        if fast_startup {
+            tokio::spawn(async move {
+                let ((), elapsed) = timed(start_mcp).await;
+                info!("MCP warm-up took {}ms", elapsed.as_millis());
+            });
+        } else {
+            startup_timings.time("mcp_start", start_mcp).await;
+        }
 
         // record_initial_history can emit events. We record only after the SessionConfiguredEvent is emitted.
-        sess.record_initial_history(initial_history).await;
+        startup_timings
+            .time(
+                "initial_history",
+                sess.record_initial_history(initial_history),
+            )
+            .await;
+        info!("{}", startup_timings.report());
 
         Ok(sess)
     }
@@ -814,6 +1229,13 @@ impl Session {
                     self.record_into_history(&reconstructed_history, &turn_context)
                         .await;
                 }
+                // Encrypted reasoning left in the history came from the last
+                // provider the session used.
+                let reasoning_provider = rollout_items.iter().rev().find_map(|item| match item {
+                    RolloutItem::TurnContext(ctx) => ctx.model_provider.clone(),
+                    _ => None,
+                });
+                self.state.lock().await.reasoning_provider = reasoning_provider;
 
                 // If persisting, persist all rollout items as-is (recorder filters)
                 if persist && !rollout_items.is_empty() {
@@ -825,15 +1247,79 @@ impl Session {
         }
     }
 
+    /// Validate `path` and make it the working directory for later turns.
+    pub(crate) async fn change_directory(
+        &self,
+        sub_id: &str,
+        path: &Path,
+    ) -> Result<PathBuf, ErrorEvent> {
+        let (cwd, changes) = {
+            let mut state = self.state.lock().await;
+            let configuration = &state.session_configuration;
+            let cwd = resolve_working_directory(
+                &configuration.cwd,
+                &configuration.original_config_do_not_use.cwd,
+                &configuration.sandbox_policy,
+                path,
+            )?;
+            let changes = TurnContextChangedEvent {
+                cwd: (configuration.cwd != cwd).then(|| SettingChange {
+                    old: configuration.cwd.clone(),
+                    new: cwd.clone(),
+                }),
+                ..Default::default()
+            };
+            state.session_configuration.cwd = cwd.clone();
+            (cwd, changes)
+        };
+        self.announce_turn_context_changes(sub_id, changes).await;
+        Ok(cwd)
+    }
+
+    /// Record the settings that changed so the rollout shows what each later
+    /// turn ran under.
+    async fn announce_turn_context_changes(&self, sub_id: &str, changes: TurnContextChangedEvent) {
+        if changes.is_empty() {
+            return;
+        }
+        self.send_event_raw(Event {
+            id: sub_id.to_string(),
+            msg: EventMsg::TurnContextChanged(changes),
+        })
+        .await;
+    }
+
+    /// Settings that switch to `preset`. A workspace-write sandbox already in
+    /// effect keeps its writable roots and network setting.
+    async fn approval_preset_settings(&self, preset: ApprovalPresetId) -> SessionSettingsUpdate {
+        let state = self.state.lock().await;
+        let current = &state.session_configuration.sandbox_policy;
+        let sandbox_policy = match (preset.sandbox_policy(), current) {
+            (SandboxPolicy::WorkspaceWrite { .. }, SandboxPolicy::WorkspaceWrite { .. }) => {
+                current.clone()
+            }
+            (policy, _) => policy,
+        };
+        SessionSettingsUpdate {
+            approval_policy: Some(preset.approval_policy()),
+            sandbox_policy: Some(sandbox_policy),
+            ..Default::default()
+        }
+    }
+
     pub(crate) async fn update_settings(
         &self,
+        sub_id: &str,
         updates: SessionSettingsUpdate,
     ) -> ConstraintResult<()> {
         let mut state = self.state.lock().await;
 
         match state.session_configuration.apply(&updates) {
             Ok(updated) => {
+                let changes = state.session_configuration.changes_to(&updated);
                 state.session_configuration = updated;
+                drop(state);
+                self.announce_turn_context_changes(sub_id, changes).await;
                 Ok(())
             }
             Err(err) => {
@@ -851,14 +1337,15 @@ impl Session {
         sub_id: String,
         updates: SessionSettingsUpdate,
     ) -> ConstraintResult<Arc<TurnContext>> {
-        let (session_configuration, sandbox_policy_changed) = {
+        let (session_configuration, sandbox_policy_changed, changes) = {
             let mut state = self.state.lock().await;
             match state.session_configuration.clone().apply(&updates) {
                 Ok(next) => {
                     let sandbox_policy_changed =
                         state.session_configuration.sandbox_policy != next.sandbox_policy;
+                    let changes = state.session_configuration.changes_to(&next);
                     state.session_configuration = next.clone();
-                    (next, sandbox_policy_changed)
+                    (next, sandbox_policy_changed, changes)
                 }
                 Err(err) => {
                     drop(state);
@@ -870,6 +1357,10 @@ impl Session {
                         msg: EventMsg::Error(ErrorEvent {
                             message: wrapped.to_string(),
                             codex_error_info: Some(CodexErrorInfo::BadRequest),
+                            details: Some(ErrorDetails::new(
+                                ErrorCategory::Protocol,
+                                "invalid_settings",
+                            )),
                         }),
                     })
                     .await;
@@ -877,6 +1368,7 @@ impl Session {
                 }
             }
         };
+        self.announce_turn_context_changes(&sub_id, changes).await;
 
         Ok(self
             .new_turn_from_configuration(
@@ -895,6 +1387,24 @@ impl Session {
         final_output_json_schema: Option<Option<Value>>,
         sandbox_policy_changed: bool,
     ) -> Arc<TurnContext> {
+        Arc::new(
+            self.build_turn_from_configuration(
+                sub_id,
+                session_configuration,
+                final_output_json_schema,
+                sandbox_policy_changed,
+            )
+            .await,
+        )
+    }
+
+    async fn build_turn_from_configuration(
+        &self,
+        sub_id: String,
+        session_configuration: SessionConfiguration,
+        final_output_json_schema: Option<Option<Value>>,
+        sandbox_policy_changed: bool,
+    ) -> TurnContext {
         let per_turn_config = Self::build_per_turn_config(&session_configuration);
 
         if sandbox_policy_changed {
@@ -933,7 +1443,34 @@ impl Session {
         if let Some(final_schema) = final_output_json_schema {
             turn_context.final_output_json_schema = final_schema;
         }
-        Arc::new(turn_context)
+        turn_context
+    }
+
+    /// Turn context for one step of a workflow. The step's approval policy,
+    /// tools and output schema apply to this turn only; the session's
+    /// settings are left unchanged.
+    pub(crate) async fn new_workflow_step_turn(
+        &self,
+        sub_id: String,
+        step: &WorkflowStep,
+    ) -> ConstraintResult<Arc<TurnContext>> {
+        let mut session_configuration = {
+            let state = self.state.lock().await;
+            state.session_configuration.clone()
+        };
+        if let Some(approval_policy) = step.approval_policy {
+            session_configuration.approval_policy.set(approval_policy)?;
+        }
+        let mut turn_context = self
+            .build_turn_from_configuration(
+                sub_id,
+                session_configuration,
+                Some(step.output_schema.clone()),
+                false,
+            )
+            .await;
+        turn_context.tools_config.allowed_tools = step.tools.clone();
+        Ok(Arc::new(turn_context))
     }
 
     pub(crate) async fn new_default_turn(&self) -> Arc<TurnContext> {
@@ -972,24 +1509,50 @@ impl Session {
 
     /// Persist the event to rollout and send it to clients.
     pub(crate) async fn send_event(&self, turn_context: &TurnContext, msg: EventMsg) {
+        let reasoning_display = self.reasoning_display().await;
         let legacy_source = msg.clone();
         let event = Event {
             id: turn_context.sub_id.clone(),
             msg,
         };
-        self.send_event_raw(event).await;
+        self.send_event_with_reasoning_display(event, reasoning_display)
+            .await;
 
-        let show_raw_agent_reasoning = self.show_raw_agent_reasoning();
-        for legacy in legacy_source.as_legacy_events(show_raw_agent_reasoning) {
+        for legacy in legacy_source.as_legacy_events(reasoning_display.shows_raw_reasoning()) {
             let legacy_event = Event {
                 id: turn_context.sub_id.clone(),
                 msg: legacy,
             };
-            self.send_event_raw(legacy_event).await;
+            self.send_event_with_reasoning_display(legacy_event, reasoning_display)
+                .await;
+        }
+    }
+
+    /// Like `send_event_raw`, but reasoning stream events are only persisted
+    /// (not delivered) while reasoning display is hidden.
+    async fn send_event_with_reasoning_display(
+        &self,
+        event: Event,
+        reasoning_display: ReasoningDisplay,
+    ) {
+        if reasoning_display.hides_reasoning() && is_reasoning_stream_event(&event.msg) {
+            let rollout_items = vec![RolloutItem::EventMsg(event.msg)];
+            self.persist_rollout_items(&rollout_items).await;
+            return;
         }
+        self.send_event_raw(event).await;
     }
 
     pub(crate) async fn send_event_raw(&self, event: Event) {
+        if let (EventMsg::Error(_), Some(scratch_dir)) = (&event.msg, &self.services.scratch_dir) {
+            scratch_dir.mark_error();
+        }
+        if let Ok(mut annotations) = self.services.annotations.lock() {
+            annotations.observe(&event.msg);
+        }
+        if let Some(usage_stats) = self.services.usage_stats.as_ref() {
+            usage_stats.observe(&event.msg);
+        }
         // Persist the event into rollout (recorder filters as needed)
         let rollout_items = vec![RolloutItem::EventMsg(event.msg.clone())];
         self.persist_rollout_items(&rollout_items).await;
@@ -1072,6 +1635,7 @@ impl Session {
         call_id: String,
         command: Vec<String>,
         cwd: PathBuf,
+        env: HashMap<String, String>,
         reason: Option<String>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     ) -> ReviewDecision {
@@ -1093,12 +1657,26 @@ impl Session {
             warn!("Overwriting existing pending approval for sub_id: {event_id}");
         }
 
+        self.notifier()
+            .notify(&UserNotification::ApprovalRequested {
+                thread_id: self.conversation_id.to_string(),
+                turn_id: turn_context.sub_id.clone(),
+                cwd: cwd.display().to_string(),
+                call_id: call_id.clone(),
+                command: Some(
+                    shlex::try_join(command.iter().map(String::as_str))
+                        .unwrap_or_else(|_| command.join(" ")),
+                ),
+                changes: Vec::new(),
+                reason: reason.clone(),
+            });
         let parsed_cmd = parse_command(&command);
         let event = EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
             call_id,
             turn_id: turn_context.sub_id.clone(),
             command,
             cwd,
+            env,
             reason,
             proposed_execpolicy_amendment,
             parsed_cmd,
@@ -1133,6 +1711,21 @@ impl Session {
             warn!("Overwriting existing pending approval for sub_id: {event_id}");
         }
 
+        let mut changed_paths: Vec<String> = changes
+            .keys()
+            .map(|path| path.display().to_string())
+            .collect();
+        changed_paths.sort();
+        self.notifier()
+            .notify(&UserNotification::ApprovalRequested {
+                thread_id: self.conversation_id.to_string(),
+                turn_id: turn_context.sub_id.clone(),
+                cwd: turn_context.cwd.display().to_string(),
+                call_id: call_id.clone(),
+                command: None,
+                changes: changed_paths,
+                reason: reason.clone(),
+            });
         let event = EventMsg::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
             call_id,
             turn_id: turn_context.sub_id.clone(),
@@ -1144,59 +1737,297 @@ impl Session {
         rx_approve
     }
 
-    pub async fn notify_approval(&self, sub_id: &str, decision: ReviewDecision) {
+    /// Ask the client how to handle a patch that would overwrite edits made
+    /// since the model last read the files, and wait for the answer.
+    pub(crate) async fn request_patch_conflict_resolution(
+        &self,
+        turn_context: &TurnContext,
+        call_id: String,
+        conflicts: Vec<PatchConflict>,
+    ) -> PatchConflictResolution {
+        let sub_id = turn_context.sub_id.clone();
+        let (tx_resolve, rx_resolve) = oneshot::channel();
+        let prev_entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.insert_pending_conflict(sub_id.clone(), tx_resolve)
+                }
+                None => None,
+            }
+        };
+        if prev_entry.is_some() {
+            warn!("Overwriting existing pending patch conflict for sub_id: {sub_id}");
+        }
+
+        let event = EventMsg::PatchConflict(PatchConflictEvent {
+            call_id,
+            turn_id: sub_id,
+            conflicts,
+        });
+        self.send_event(turn_context, event).await;
+        rx_resolve.await.unwrap_or_default()
+    }
+
+    pub async fn resolve_patch_conflict(&self, sub_id: &str, resolution: PatchConflictResolution) {
         let entry = {
             let mut active = self.active_turn.lock().await;
             match active.as_mut() {
                 Some(at) => {
                     let mut ts = at.turn_state.lock().await;
-                    ts.remove_pending_approval(sub_id)
+                    ts.remove_pending_conflict(sub_id)
                 }
                 None => None,
             }
         };
         match entry {
-            Some(tx_approve) => {
-                tx_approve.send(decision).ok();
+            Some(tx_resolve) => {
+                tx_resolve.send(resolution).ok();
             }
             None => {
-                warn!("No pending approval found for sub_id: {sub_id}");
+                warn!("No pending patch conflict found for sub_id: {sub_id}");
             }
         }
     }
 
-    pub async fn resolve_elicitation(
-        &self,
-        server_name: String,
-        id: RequestId,
-        response: ElicitationResponse,
-    ) -> anyhow::Result<()> {
-        self.services
-            .mcp_connection_manager
-            .read()
-            .await
-            .resolve_elicitation(server_name, id, response)
-            .await
-    }
-
-    /// Records input items: always append to conversation history and
-    /// persist these response items to rollout.
-    pub(crate) async fn record_conversation_items(
+    /// Ask the user a question on behalf of the model and wait for the answer.
+    /// Returns `None` when the user declines to answer.
+    pub(crate) async fn request_user_answer(
         &self,
         turn_context: &TurnContext,
-        items: &[ResponseItem],
-    ) {
-        self.record_into_history(items, turn_context).await;
-        self.persist_rollout_response_items(items).await;
-        self.send_raw_response_items(turn_context, items).await;
+        call_id: String,
+        question: String,
+        options: Vec<String>,
+    ) -> Option<String> {
+        let sub_id = turn_context.sub_id.clone();
+        let (tx_answer, rx_answer) = oneshot::channel();
+        let prev_entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.insert_pending_question(sub_id.clone(), tx_answer)
+                }
+                None => None,
+            }
+        };
+        if prev_entry.is_some() {
+            warn!("Overwriting existing pending question for sub_id: {sub_id}");
+        }
+
+        let event = EventMsg::Question(QuestionEvent {
+            call_id,
+            turn_id: sub_id,
+            question,
+            options,
+        });
+        self.send_event(turn_context, event).await;
+        rx_answer.await.unwrap_or_default()
     }
 
-    fn reconstruct_history_from_rollout(
-        &self,
-        turn_context: &TurnContext,
-        rollout_items: &[RolloutItem],
+    pub async fn answer_question(&self, sub_id: &str, answer: Option<String>) {
+        let entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.remove_pending_question(sub_id)
+                }
+                None => None,
+            }
+        };
+        match entry {
+            Some(tx_answer) => {
+                tx_answer.send(answer).ok();
+            }
+            None => {
+                warn!("No pending question found for sub_id: {sub_id}");
+            }
+        }
+    }
+
+    /// Show the user the responses sampled for this turn and wait for their
+    /// choice. Returns `None` when they leave it to the judge prompt.
+    pub(crate) async fn request_candidate_selection(
+        &self,
+        turn_context: &TurnContext,
+        candidates: Vec<ResponseCandidate>,
+    ) -> Option<usize> {
+        let sub_id = turn_context.sub_id.clone();
+        let (tx_select, rx_select) = oneshot::channel();
+        let prev_entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.insert_pending_candidate_selection(sub_id.clone(), tx_select)
+                }
+                None => None,
+            }
+        };
+        if prev_entry.is_some() {
+            warn!("Overwriting existing pending candidate selection for sub_id: {sub_id}");
+        }
+
+        let event = EventMsg::CandidatesProposed(CandidatesProposedEvent {
+            turn_id: sub_id,
+            candidates,
+        });
+        self.send_event(turn_context, event).await;
+        rx_select.await.unwrap_or_default()
+    }
+
+    /// Show the user the memories extracted from a finished turn and wait for
+    /// the indexes of those to save. Returns `None` when they do not answer
+    /// within `auto_accept_secs`.
+    pub(crate) async fn request_memory_review(
+        &self,
+        turn_context: &TurnContext,
+        candidates: Vec<String>,
+        location: PathBuf,
+        auto_accept_secs: u64,
+    ) -> Option<Vec<usize>> {
+        let sub_id = turn_context.sub_id.clone();
+        let (tx_review, rx_review) = oneshot::channel();
+        let prev_entry = self
+            .state
+            .lock()
+            .await
+            .pending_memory_reviews
+            .insert(sub_id.clone(), tx_review);
+        if prev_entry.is_some() {
+            warn!("Overwriting existing pending memory review for sub_id: {sub_id}");
+        }
+
+        let event = EventMsg::MemoryCandidatesProposed(MemoryCandidatesProposedEvent {
+            turn_id: sub_id.clone(),
+            candidates,
+            location,
+            auto_accept_secs,
+        });
+        self.send_event(turn_context, event).await;
+        match tokio::time::timeout(Duration::from_secs(auto_accept_secs), rx_review).await {
+            Ok(Ok(accepted)) => Some(accepted),
+            Ok(Err(_)) | Err(_) => {
+                self.state
+                    .lock()
+                    .await
+                    .pending_memory_reviews
+                    .remove(&sub_id);
+                None
+            }
+        }
+    }
+
+    pub async fn review_memories(&self, sub_id: &str, accepted: Vec<usize>) {
+        let entry = self
+            .state
+            .lock()
+            .await
+            .pending_memory_reviews
+            .remove(sub_id);
+        match entry {
+            Some(tx_review) => {
+                tx_review.send(accepted).ok();
+            }
+            None => {
+                warn!("No pending memory review found for sub_id: {sub_id}");
+            }
+        }
+    }
+
+    pub async fn select_candidate(&self, sub_id: &str, index: Option<usize>) {
+        let entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.remove_pending_candidate_selection(sub_id)
+                }
+                None => None,
+            }
+        };
+        match entry {
+            Some(tx_select) => {
+                tx_select.send(index).ok();
+            }
+            None => {
+                warn!("No pending candidate selection found for sub_id: {sub_id}");
+            }
+        }
+    }
+
+    pub async fn notify_approval(&self, sub_id: &str, decision: ReviewDecision) {
+        let entry = {
+            let mut active = self.active_turn.lock().await;
+            match active.as_mut() {
+                Some(at) => {
+                    let mut ts = at.turn_state.lock().await;
+                    ts.remove_pending_approval(sub_id)
+                }
+                None => None,
+            }
+        };
+        match entry {
+            Some(tx_approve) => {
+                tx_approve.send(decision).ok();
+            }
+            None => {
+                warn!("No pending approval found for sub_id: {sub_id}");
+            }
+        }
+    }
+
+    pub async fn resolve_elicitation(
+        &self,
+        server_name: String,
+        id: RequestId,
+        response: ElicitationResponse,
+    ) -> anyhow::Result<()> {
+        self.services
+            .mcp_connection_manager
+            .read()
+            .await
+            .resolve_elicitation(server_name, id, response)
+            .await
+    }
+
+    /// Records input items: always append to conversation history and
+    /// persist these response items to rollout.
+    pub(crate) async fn record_conversation_items(
+        &self,
+        turn_context: &TurnContext,
+        items: &[ResponseItem],
+    ) {
+        let truncated_bytes = self.record_into_history(items, turn_context).await;
+        if truncated_bytes > 0 {
+            let limit = match turn_context.truncation_policy {
+                TruncationPolicy::Bytes(bytes) => format!("{bytes} bytes"),
+                TruncationPolicy::Tokens(tokens) => format!("{tokens} tokens"),
+            };
+            let approx_tokens = approx_tokens_from_byte_count(truncated_bytes);
+            self.notify_context_trimmed(
+                turn_context,
+                ContextTrimReason::Truncation,
+                format!(
+                    "Truncated tool output to the {limit} limit; {truncated_bytes} bytes (~{approx_tokens} tokens) were left out."
+                ),
+                approx_tokens,
+            )
+            .await;
+        }
+        self.persist_rollout_response_items(items).await;
+        self.send_raw_response_items(turn_context, items).await;
+    }
+
+    fn reconstruct_history_from_rollout(
+        &self,
+        turn_context: &TurnContext,
+        rollout_items: &[RolloutItem],
     ) -> Vec<ResponseItem> {
         let mut history = ContextManager::new();
+        let mut reasoning_provider: Option<&String> = None;
         for item in rollout_items {
             match item {
                 RolloutItem::ResponseItem(response_item) => {
@@ -1205,6 +2036,19 @@ impl Session {
                         turn_context.truncation_policy,
                     );
                 }
+                RolloutItem::TurnContext(ctx) => {
+                    // Reasoning recorded before a provider switch was already
+                    // dropped from the live session; drop it here as well.
+                    if let Some(provider) = &ctx.model_provider {
+                        if reasoning_provider
+                            .as_ref()
+                            .is_some_and(|previous| previous != provider)
+                        {
+                            history.drop_encrypted_reasoning();
+                        }
+                        reasoning_provider = Some(provider.clone());
+                    }
+                }
                 RolloutItem::Compacted(compacted) => {
                     let snapshot = history.get_history();
                     // TODO(jif) clean
@@ -1226,14 +2070,54 @@ impl Session {
         history.get_history()
     }
 
+    /// Encrypted reasoning can only be read by the provider that produced it.
+    /// Drop it from history when `turn_context` talks to a different one.
+    pub(crate) async fn drop_foreign_reasoning(&self, turn_context: &TurnContext) {
+        let provider = turn_context.client.config().model_provider_id.clone();
+        let mut state = self.state.lock().await;
+        let previous = state.reasoning_provider.replace(provider.clone());
+        if let Some(previous) = previous
+            && previous != provider
+        {
+            let dropped = state.history.drop_encrypted_reasoning();
+            if dropped > 0 {
+                info!(
+                    "dropped {dropped} encrypted reasoning items from provider {previous} before switching to {provider}"
+                );
+            }
+        }
+    }
+
+    /// Record that the function call `call_id` ran with repaired arguments.
+    pub(crate) async fn replace_call_arguments(&self, call_id: &str, arguments: String) {
+        let mut state = self.state.lock().await;
+        if !state.history.replace_call_arguments(call_id, arguments) {
+            warn!("no recorded function call {call_id} to update with repaired arguments");
+        }
+    }
+
     /// Append ResponseItems to the in-memory conversation history only.
+    /// Returns how many bytes of tool output truncation dropped.
     pub(crate) async fn record_into_history(
         &self,
         items: &[ResponseItem],
         turn_context: &TurnContext,
-    ) {
-        let mut state = self.state.lock().await;
-        state.record_items(items.iter(), turn_context.truncation_policy);
+    ) -> usize {
+        let truncated_bytes = {
+            let mut state = self.state.lock().await;
+            state.record_items(items.iter(), turn_context.truncation_policy)
+        };
+        if truncated_bytes > 0 {
+            self.record_truncated_output(truncated_bytes).await;
+        }
+        truncated_bytes
+    }
+
+    /// Count tool output bytes the model will not see.
+    pub(crate) async fn record_truncated_output(&self, bytes: usize) {
+        let bytes = u64::try_from(bytes).unwrap_or(u64::MAX);
+        self.update_turn_metrics(|metrics| metrics.truncated_output_bytes += bytes)
+            .await;
     }
 
     pub(crate) async fn record_model_warning(&self, message: impl Into<String>, ctx: &TurnContext) {
@@ -1255,6 +2139,56 @@ impl Session {
     pub(crate) async fn replace_history(&self, items: Vec<ResponseItem>) {
         let mut state = self.state.lock().await;
         state.replace_history(items);
+        // Memories sent earlier may no longer be in history either.
+        if let Some(ranker) = &self.services.memory_ranker {
+            ranker.forget_sent();
+        }
+    }
+
+    /// Add the finished task of submission `sub_id` to the ledger behind
+    /// `Op::GetFileAtTurn`.
+    async fn record_turn_in_ledger(&self, sub_id: &str, turn_diff_tracker: &SharedTurnDiffTracker) {
+        let contents_before = turn_diff_tracker.lock().await.contents_before_turn();
+        let mut state = self.state.lock().await;
+        let checkpoint = state
+            .history
+            .get_history()
+            .into_iter()
+            .rev()
+            .find_map(|item| match item {
+                ResponseItem::GhostSnapshot { ghost_commit } => Some(ghost_commit),
+                _ => None,
+            })
+            .filter(|checkpoint| state.turn_ledger.latest_checkpoint() != Some(checkpoint));
+        state
+            .turn_ledger
+            .record(sub_id, checkpoint, contents_before);
+    }
+
+    /// Drop the last user message and everything after it from the history,
+    /// returning that message's input and the number of items dropped. The
+    /// rollout records the shortened history so a resumed session matches.
+    async fn discard_last_response(&self) -> Option<(Vec<UserInput>, usize)> {
+        let (regenerated, kept) = {
+            let mut state = self.state.lock().await;
+            let mut items = state.clone_history().get_history();
+            let (index, input) = items.iter().enumerate().rev().find_map(|(index, item)| {
+                match parse_turn_item(item) {
+                    Some(TurnItem::UserMessage(message)) => Some((index, message.content)),
+                    _ => None,
+                }
+            })?;
+            let discarded = items.len() - index;
+            items.truncate(index);
+            state.replace_history(items.clone());
+            ((input, discarded), items)
+        };
+        self.persist_rollout_items(&[RolloutItem::Compacted(CompactedItem {
+            message: String::new(),
+            replacement_history: Some(kept),
+        })])
+        .await;
+        Some(regenerated)
     }
 
     async fn persist_rollout_response_items(&self, items: &[ResponseItem]) {
@@ -1286,7 +2220,6 @@ impl Session {
 
     pub(crate) fn build_initial_context(&self, turn_context: &TurnContext) -> Vec<ResponseItem> {
         let mut items = Vec::<ResponseItem>::with_capacity(3);
-        let shell = self.user_shell();
         if let Some(developer_instructions) = turn_context.developer_instructions.as_deref() {
             items.push(DeveloperInstructions::new(developer_instructions.to_string()).into());
         }
@@ -1299,13 +2232,51 @@ impl Session {
                 .into(),
             );
         }
-        items.push(ResponseItem::from(EnvironmentContext::new(
+        if let Some(map) = turn_context.repo_map.as_deref() {
+            items.push(
+                RepoMap {
+                    root: turn_context.cwd.to_string_lossy().into_owned(),
+                    map: map.to_string(),
+                }
+                .into(),
+            );
+        }
+        if let Some(hints) = self
+            .services
+            .tool_usage
+            .as_ref()
+            .and_then(ToolUsageTracker::hints)
+        {
+            items.push(
+                ToolHints {
+                    hints: hints.to_string(),
+                }
+                .into(),
+            );
+        }
+        // With `[prompt_sections]`, the environment is part of the instructions.
+        if turn_context.prompt_sections.is_none() {
+            items.push(ResponseItem::from(self.environment_context(turn_context)));
+        }
+        if self.enabled(Feature::EnvironmentSnapshot) {
+            items.push(SystemInfo(&self.services.session_environment).into());
+        }
+        items
+    }
+
+    fn environment_context(&self, turn_context: &TurnContext) -> EnvironmentContext {
+        EnvironmentContext::new(
             Some(turn_context.cwd.clone()),
             Some(turn_context.approval_policy),
             Some(turn_context.sandbox_policy.clone()),
-            shell.as_ref().clone(),
-        )));
-        items
+            self.user_shell().as_ref().clone(),
+        )
+        .with_scratch_dir(
+            self.services
+                .scratch_dir
+                .as_ref()
+                .map(|scratch_dir| scratch_dir.path().to_path_buf()),
+        )
     }
 
     pub(crate) async fn persist_rollout_items(&self, items: &[RolloutItem]) {
@@ -1330,18 +2301,49 @@ impl Session {
         turn_context: &TurnContext,
         token_usage: Option<&TokenUsage>,
     ) {
+        if let Some(token_usage) = token_usage {
+            self.record_token_metrics(token_usage).await;
+            let mut state = self.state.lock().await;
+            state.update_token_info_from_usage(
+                token_usage,
+                turn_context.client.get_model_context_window(),
+            );
+        }
+        self.send_token_count_event(turn_context).await;
+    }
+
+    /// Count the usage of a request made beside the conversation, such as
+    /// argument repair, in the totals. Unlike [`Self::update_token_usage_info`]
+    /// this keeps the last usage, which the auto-compaction check reads as
+    /// the size of the conversation's context.
+    pub(crate) async fn update_side_token_usage(
+        &self,
+        turn_context: &TurnContext,
+        token_usage: Option<&TokenUsage>,
+    ) {
+        let Some(token_usage) = token_usage else {
+            return;
+        };
+        self.record_token_metrics(token_usage).await;
         {
             let mut state = self.state.lock().await;
-            if let Some(token_usage) = token_usage {
-                state.update_token_info_from_usage(
-                    token_usage,
-                    turn_context.client.get_model_context_window(),
-                );
-            }
+            state.add_side_token_usage(token_usage, turn_context.client.get_model_context_window());
         }
         self.send_token_count_event(turn_context).await;
     }
 
+    async fn record_token_metrics(&self, token_usage: &TokenUsage) {
+        self.update_turn_metrics(|metrics| {
+            metrics.input_tokens += token_usage.input_tokens;
+            metrics.cached_input_tokens += token_usage.cached_input_tokens;
+            metrics.output_tokens += token_usage.output_tokens;
+        })
+        .await;
+        self.services
+            .agent_budget
+            .record_tokens(token_usage.total_tokens);
+    }
+
     pub(crate) async fn recompute_token_usage(&self, turn_context: &TurnContext) {
         let Some(estimated_total_tokens) = self
             .clone_history()
@@ -1434,6 +2436,43 @@ impl Session {
         self.send_event(turn_context, event).await;
     }
 
+    /// Tell the client that content was removed from what the model sees.
+    pub(crate) async fn notify_context_trimmed(
+        &self,
+        turn_context: &TurnContext,
+        reason: ContextTrimReason,
+        summary: String,
+        approx_tokens: u64,
+    ) {
+        let event = EventMsg::ContextTrimmed(ContextTrimmedEvent {
+            reason,
+            summary,
+            approx_tokens,
+        });
+        self.send_event(turn_context, event).await;
+    }
+
+    /// Report what `[history_filter]` left out of a request, unless the last
+    /// request it filtered had the same report.
+    async fn notify_history_filtered(
+        &self,
+        turn_context: &TurnContext,
+        report: HistoryFilterReport,
+    ) {
+        let previous =
+            std::mem::replace(&mut self.state.lock().await.history_filter_report, report);
+        if report.is_empty() || report == previous {
+            return;
+        }
+        self.notify_context_trimmed(
+            turn_context,
+            ContextTrimReason::HistoryFilter,
+            report.summary(),
+            report.approx_tokens(),
+        )
+        .await;
+    }
+
     pub(crate) async fn notify_stream_error(
         &self,
         turn_context: &TurnContext,
@@ -1484,6 +2523,9 @@ impl Session {
         match active.as_mut() {
             Some(at) => {
                 let mut ts = at.turn_state.lock().await;
+                if !ts.accepts_input() {
+                    return Err(input);
+                }
                 ts.push_pending_input(input.into());
                 Ok(())
             }
@@ -1491,6 +2533,48 @@ impl Session {
         }
     }
 
+    /// Called when the model has nothing left to do. Returns false when input
+    /// was injected in the meantime, so the turn makes another request to
+    /// answer it; otherwise later input starts a turn of its own.
+    async fn close_input_if_idle(&self) -> bool {
+        let mut active = self.active_turn.lock().await;
+        match active.as_mut() {
+            Some(at) => at.turn_state.lock().await.close_input_if_idle(),
+            None => true,
+        }
+    }
+
+    /// Remember the model's latest plan for `EventMsg::TurnProgress`.
+    pub(crate) async fn set_turn_plan(&self, plan: Vec<PlanItemArg>) {
+        if let Some(at) = self.active_turn.lock().await.as_ref() {
+            at.turn_state.lock().await.set_plan(plan);
+        }
+    }
+
+    /// Count a finished tool call and tell clients how far the turn has come.
+    pub(crate) async fn record_tool_call_progress(
+        &self,
+        turn_context: &TurnContext,
+        tool_name: &str,
+    ) {
+        let progress = match self.active_turn.lock().await.as_ref() {
+            Some(at) => at.turn_state.lock().await.record_tool_call(tool_name),
+            None => return,
+        };
+        self.send_event(turn_context, EventMsg::TurnProgress(progress))
+            .await;
+    }
+
+    /// Add to the totals reported when the running turn completes.
+    pub(crate) async fn update_turn_metrics(
+        &self,
+        update: impl FnOnce(&mut TurnCompletedMetricsEvent),
+    ) {
+        if let Some(at) = self.active_turn.lock().await.as_ref() {
+            update(at.turn_state.lock().await.metrics_mut());
+        }
+    }
+
     pub async fn get_pending_input(&self) -> Vec<ResponseInputItem> {
         let mut active = self.active_turn.lock().await;
         match active.as_mut() {
@@ -1578,12 +2662,98 @@ impl Session {
         &self.services.notifier
     }
 
+    pub(crate) fn conversation_id(&self) -> ConversationId {
+        self.conversation_id
+    }
+
     pub(crate) fn user_shell(&self) -> Arc<shell::Shell> {
         Arc::clone(&self.services.user_shell)
     }
 
-    fn show_raw_agent_reasoning(&self) -> bool {
-        self.services.show_raw_agent_reasoning
+    async fn attached_files(&self) -> AttachedFiles {
+        self.state.lock().await.attached_files.clone()
+    }
+
+    async fn record_attached_files(&self, attached: AttachedFiles) {
+        self.state.lock().await.attached_files.extend(attached);
+    }
+
+    /// Snapshot `paths` so later edits made outside Codex can be reported to
+    /// the model. Paths that can no longer be snapshotted stop being watched.
+    pub(crate) async fn watch_files(&self, paths: impl IntoIterator<Item = PathBuf>) {
+        if !self.enabled(Feature::ExternalEditNotices) && !self.enabled(Feature::PatchConflicts) {
+            return;
+        }
+        let mut snapshots = Vec::new();
+        for path in paths {
+            let contents = external_edits::snapshot_file(&path).await;
+            snapshots.push((path, contents));
+        }
+        let mut state = self.state.lock().await;
+        for (path, contents) in snapshots {
+            match contents {
+                Some(contents) => {
+                    external_edits::insert_snapshot(&mut state.watched_files, path, contents)
+                }
+                None => {
+                    state.watched_files.remove(&path);
+                }
+            }
+        }
+    }
+
+    /// Files among `paths` that changed on disk since the model last read or
+    /// edited them.
+    pub(crate) async fn patch_conflicts(&self, paths: &[PathBuf]) -> Vec<PatchConflict> {
+        if !self.enabled(Feature::PatchConflicts) {
+            return Vec::new();
+        }
+        let watched = self.state.lock().await.watched_files.clone();
+        external_edits::changed_files(&watched, paths)
+            .await
+            .into_iter()
+            .map(|(path, kind)| PatchConflict {
+                path,
+                diff: match kind {
+                    ExternalEditKind::Modified { diff, .. } => Some(diff),
+                    ExternalEditKind::Deleted => None,
+                },
+            })
+            .collect()
+    }
+
+    /// Compare watched files with their snapshots and adopt the current
+    /// contents. Returns the edits found since the last check.
+    async fn take_external_edits(&self, cwd: &Path) -> Vec<ExternalEdit> {
+        let watched = self.state.lock().await.watched_files.clone();
+        if watched.is_empty() {
+            return Vec::new();
+        }
+        let (edits, current) = external_edits::detect_external_edits(&watched, cwd).await;
+        self.state.lock().await.watched_files = current;
+        edits
+    }
+
+    /// Adopt the current contents of every watched file, so changes Codex made
+    /// itself during a task are not reported as external edits.
+    pub(crate) async fn refresh_watched_files(&self) {
+        let paths: Vec<PathBuf> = self
+            .state
+            .lock()
+            .await
+            .watched_files
+            .keys()
+            .cloned()
+            .collect();
+        self.watch_files(paths).await;
+    }
+
+    async fn reasoning_display(&self) -> ReasoningDisplay {
+        self.state.lock().await.reasoning_display
+    }
+
+    pub(crate) async fn set_reasoning_display(&self, display: ReasoningDisplay) {
+        self.state.lock().await.reasoning_display = display;
     }
 
     async fn cancel_mcp_startup(&self) {
@@ -1591,6 +2761,69 @@ impl Session {
     }
 }
 
+/// Resolve `requested` against `current_cwd` and make sure moving there does
+/// not widen the sandbox: under `workspace-write` the directory must be
+/// writable given the roots derived from `session_cwd`, the directory the
+/// session started in.
+fn resolve_working_directory(
+    current_cwd: &Path,
+    session_cwd: &Path,
+    sandbox_policy: &SandboxPolicy,
+    requested: &Path,
+) -> Result<PathBuf, ErrorEvent> {
+    let candidate = current_cwd.join(requested);
+    let cwd = match dunce::canonicalize(&candidate) {
+        Ok(cwd) if cwd.is_dir() => cwd,
+        Ok(cwd) => {
+            return Err(ErrorEvent {
+                message: format!("{} is not a directory", cwd.display()),
+                codex_error_info: Some(CodexErrorInfo::BadRequest),
+                details: Some(ErrorDetails::new(ErrorCategory::Io, "cwd_not_a_directory")),
+            });
+        }
+        Err(err) => {
+            return Err(ErrorEvent {
+                message: format!("Cannot change directory to {}: {err}", candidate.display()),
+                codex_error_info: Some(CodexErrorInfo::BadRequest),
+                details: Some(ErrorDetails::new(ErrorCategory::Io, "cwd_not_found")),
+            });
+        }
+    };
+
+    let within_sandbox = match sandbox_policy {
+        SandboxPolicy::WorkspaceWrite { .. } => {
+            let session_cwd =
+                dunce::canonicalize(session_cwd).unwrap_or_else(|_| session_cwd.to_path_buf());
+            sandbox_policy
+                .get_writable_roots_with_cwd(&session_cwd)
+                .iter()
+                .any(|root| root.is_path_writable(&cwd))
+        }
+        SandboxPolicy::ReadOnly
+        | SandboxPolicy::DangerFullAccess
+        | SandboxPolicy::ExternalSandbox { .. } => true,
+    };
+    if within_sandbox {
+        Ok(cwd)
+    } else {
+        Err(ErrorEvent {
+            message: format!(
+                "{} is outside the directories this session may write to",
+                cwd.display()
+            ),
+            codex_error_info: Some(CodexErrorInfo::SandboxError),
+            details: Some(ErrorDetails {
+                category: ErrorCategory::Sandbox,
+                code: "cwd_outside_sandbox".to_string(),
+                remediation: Some(
+                    "Start a new session in that directory, or allow full access with /approvals."
+                        .to_string(),
+                ),
+            }),
+        })
+    }
+}
+
 async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiver<Submission>) {
     // Seed with context in case there is an OverrideTurnContext first.
     let mut previous_context: Option<Arc<TurnContext>> = Some(sess.new_default_turn().await);
@@ -1609,6 +2842,7 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 model,
                 effort,
                 summary,
+                verbosity,
             } => {
                 handlers::override_turn_context(
                     &sess,
@@ -1620,6 +2854,7 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                         model,
                         reasoning_effort: effort,
                         reasoning_summary: summary,
+                        verbosity,
                         ..Default::default()
                     },
                 )
@@ -1629,12 +2864,39 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 handlers::user_input_or_turn(&sess, sub.id.clone(), sub.op, &mut previous_context)
                     .await;
             }
+            Op::SetReasoningDisplay { display } => {
+                handlers::set_reasoning_display(&sess, display).await;
+            }
+            Op::SetLogFilter { filter } => {
+                handlers::set_log_filter(&sess, sub.id.clone(), filter).await;
+            }
+            Op::Steer { text } => {
+                handlers::steer(&sess, sub.id.clone(), text, &mut previous_context).await;
+            }
+            Op::ChangeDirectory { path } => {
+                handlers::change_directory(&sess, sub.id.clone(), path).await;
+            }
+            Op::SetApprovalPreset { preset } => {
+                handlers::set_approval_preset(&sess, sub.id.clone(), preset).await;
+            }
             Op::ExecApproval { id, decision } => {
                 handlers::exec_approval(&sess, id, decision).await;
             }
             Op::PatchApproval { id, decision } => {
                 handlers::patch_approval(&sess, id, decision).await;
             }
+            Op::ResolvePatchConflict { id, resolution } => {
+                handlers::resolve_patch_conflict(&sess, id, resolution).await;
+            }
+            Op::AnswerQuestion { id, answer } => {
+                handlers::answer_question(&sess, id, answer).await;
+            }
+            Op::SelectCandidate { id, index } => {
+                handlers::select_candidate(&sess, id, index).await;
+            }
+            Op::ReviewMemories { id, accepted } => {
+                handlers::review_memories(&sess, id, accepted).await;
+            }
             Op::AddToHistory { text } => {
                 handlers::add_to_history(&sess, &config, text).await;
             }
@@ -1642,6 +2904,19 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
                 handlers::get_history_entry_request(&sess, &config, sub.id.clone(), offset, log_id)
                     .await;
             }
+            Op::GetFileAtTurn { path, turn } => {
+                handlers::get_file_at_turn(&sess, sub.id.clone(), path, turn).await;
+            }
+            Op::AnnotateItem {
+                message_index,
+                kind,
+                note,
+            } => {
+                handlers::annotate_item(&sess, sub.id.clone(), message_index, kind, note).await;
+            }
+            Op::RemoveAnnotation { annotation_id } => {
+                handlers::remove_annotation(&sess, sub.id.clone(), annotation_id).await;
+            }
             Op::ListMcpTools => {
                 handlers::list_mcp_tools(&sess, &config, sub.id.clone()).await;
             }
@@ -1654,9 +2929,22 @@ async fn submission_loop(sess: Arc<Session>, config: Arc<Config>, rx_sub: Receiv
             Op::Undo => {
                 handlers::undo(&sess, sub.id.clone()).await;
             }
+            Op::Regenerate { model, effort } => {
+                handlers::regenerate(&sess, sub.id.clone(), model, effort, &mut previous_context)
+                    .await;
+            }
             Op::Compact => {
                 handlers::compact(&sess, sub.id.clone()).await;
             }
+            Op::SummarizeSession => {
+                handlers::summarize_session(&sess, sub.id.clone()).await;
+            }
+            Op::RunWorkflow { name, input } => {
+                handlers::run_workflow(&sess, &config, sub.id.clone(), name, input).await;
+            }
+            Op::UpdateSemanticIndex { rebuild } => {
+                handlers::update_semantic_index(&sess, sub.id.clone(), rebuild).await;
+            }
             Op::RunUserShellCommand { command } => {
                 handlers::run_user_shell_command(
                     &sess,
@@ -1701,21 +2989,39 @@ mod handlers {
     use crate::review_prompts::resolve_review_request;
     use crate::tasks::CompactTask;
     use crate::tasks::RegularTask;
+    use crate::tasks::SemanticIndexTask;
+    use crate::tasks::SessionSummaryTask;
     use crate::tasks::UndoTask;
     use crate::tasks::UserShellCommandTask;
+    use crate::tasks::WorkflowTask;
+    use crate::turn_ledger::FileSource;
+    use crate::turn_ledger::read_from_checkpoint;
+    use codex_protocol::config_types::ReasoningDisplay;
     use codex_protocol::custom_prompts::CustomPrompt;
+    use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
+    use codex_protocol::protocol::AnnotationKind;
+    use codex_protocol::protocol::AnnotationRemovedEvent;
+    use codex_protocol::protocol::ApprovalPresetId;
+    use codex_protocol::protocol::BackgroundEventEvent;
     use codex_protocol::protocol::CodexErrorInfo;
+    use codex_protocol::protocol::ErrorCategory;
+    use codex_protocol::protocol::ErrorDetails;
     use codex_protocol::protocol::ErrorEvent;
     use codex_protocol::protocol::Event;
     use codex_protocol::protocol::EventMsg;
+    use codex_protocol::protocol::FileAtTurnResponseEvent;
+    use codex_protocol::protocol::ItemAnnotatedEvent;
     use codex_protocol::protocol::ListCustomPromptsResponseEvent;
     use codex_protocol::protocol::ListSkillsResponseEvent;
     use codex_protocol::protocol::Op;
+    use codex_protocol::protocol::PatchConflictResolution;
+    use codex_protocol::protocol::ResponseRegeneratedEvent;
     use codex_protocol::protocol::ReviewDecision;
     use codex_protocol::protocol::ReviewRequest;
     use codex_protocol::protocol::SkillsListEntry;
     use codex_protocol::protocol::TurnAbortReason;
     use codex_protocol::protocol::WarningEvent;
+    use codex_protocol::protocol::WorkingDirectoryChangedEvent;
 
     use codex_protocol::user_input::UserInput;
     use codex_rmcp_client::ElicitationAction;
@@ -1735,18 +3041,97 @@ mod handlers {
         sub_id: String,
         updates: SessionSettingsUpdate,
     ) {
-        if let Err(err) = sess.update_settings(updates).await {
+        if let Err(err) = sess.update_settings(&sub_id, updates).await {
             sess.send_event_raw(Event {
                 id: sub_id,
                 msg: EventMsg::Error(ErrorEvent {
                     message: err.to_string(),
                     codex_error_info: Some(CodexErrorInfo::BadRequest),
+                    details: Some(ErrorDetails::new(
+                        ErrorCategory::Protocol,
+                        "invalid_settings",
+                    )),
                 }),
             })
             .await;
         }
     }
 
+    pub async fn set_reasoning_display(sess: &Session, display: ReasoningDisplay) {
+        sess.set_reasoning_display(display).await;
+    }
+
+    pub async fn set_log_filter(sess: &Session, sub_id: String, filter: String) {
+        let msg = match crate::log_filter::set_log_filter(&filter) {
+            Ok(()) => EventMsg::BackgroundEvent(BackgroundEventEvent {
+                message: format!("Log filter set to `{}`", filter.trim()),
+            }),
+            Err(err) => EventMsg::Warning(WarningEvent {
+                message: format!("Failed to set log filter: {err}"),
+            }),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
+    pub async fn annotate_item(
+        sess: &Session,
+        sub_id: String,
+        message_index: usize,
+        kind: AnnotationKind,
+        note: Option<String>,
+    ) {
+        let annotation = match sess.services.annotations.lock() {
+            Ok(annotations) => annotations.annotate(message_index, kind, note),
+            Err(_) => Err("annotations are unavailable".to_string()),
+        };
+        let msg = match annotation {
+            Ok(annotation) => EventMsg::ItemAnnotated(ItemAnnotatedEvent { annotation }),
+            Err(message) => EventMsg::Error(ErrorEvent {
+                message,
+                codex_error_info: Some(CodexErrorInfo::BadRequest),
+                details: Some(ErrorDetails::new(
+                    ErrorCategory::Protocol,
+                    "invalid_annotation_target",
+                )),
+            }),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
+    pub async fn remove_annotation(sess: &Session, sub_id: String, annotation_id: String) {
+        let exists = sess
+            .services
+            .annotations
+            .lock()
+            .is_ok_and(|annotations| annotations.contains(&annotation_id));
+        let msg = if exists {
+            EventMsg::AnnotationRemoved(AnnotationRemovedEvent { annotation_id })
+        } else {
+            EventMsg::Error(ErrorEvent {
+                message: format!("no annotation with id `{annotation_id}`"),
+                codex_error_info: Some(CodexErrorInfo::BadRequest),
+                details: Some(ErrorDetails::new(
+                    ErrorCategory::Protocol,
+                    "annotation_not_found",
+                )),
+            })
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
+    pub async fn set_approval_preset(sess: &Session, sub_id: String, preset: ApprovalPresetId) {
+        let updates = sess.approval_preset_settings(preset).await;
+        override_turn_context(sess, sub_id, updates).await;
+    }
+
+    pub async fn change_directory(sess: &Session, sub_id: String, path: PathBuf) {
+        let msg = match sess.change_directory(&sub_id, &path).await {
+            Ok(cwd) => EventMsg::WorkingDirectoryChanged(WorkingDirectoryChangedEvent { cwd }),
+            Err(err) => EventMsg::Error(err),
+        };
+        sess.send_event_raw(Event { id: sub_id, msg }).await;
+    }
+
     pub async fn user_input_or_turn(
         sess: &Arc<Session>,
         sub_id: String,
@@ -1773,6 +3158,7 @@ mod handlers {
                     reasoning_effort: Some(effort),
                     reasoning_summary: Some(summary),
                     final_output_json_schema: Some(final_output_json_schema),
+                    ..Default::default()
                 },
             ),
             Op::UserInput { items } => (items, SessionSettingsUpdate::default()),
@@ -1803,6 +3189,25 @@ mod handlers {
         }
     }
 
+    /// A steer is a text-only user input: it joins the running turn's pending
+    /// input, or starts a new turn once the running one has made its last
+    /// request.
+    pub async fn steer(
+        sess: &Arc<Session>,
+        sub_id: String,
+        text: String,
+        previous_context: &mut Option<Arc<TurnContext>>,
+    ) {
+        let text = text.trim().to_string();
+        if text.is_empty() {
+            return;
+        }
+        let op = Op::UserInput {
+            items: vec![UserInput::Text { text }],
+        };
+        user_input_or_turn(sess, sub_id, op, previous_context).await;
+    }
+
     pub async fn run_user_shell_command(
         sess: &Arc<Session>,
         sub_id: String,
@@ -1872,15 +3277,40 @@ mod handlers {
         }
     }
 
-    pub async fn patch_approval(sess: &Arc<Session>, id: String, decision: ReviewDecision) {
-        match decision {
-            ReviewDecision::Abort => {
+    pub async fn patch_approval(sess: &Arc<Session>, id: String, decision: ReviewDecision) {
+        match decision {
+            ReviewDecision::Abort => {
+                sess.interrupt_task().await;
+            }
+            other => sess.notify_approval(&id, other).await,
+        }
+    }
+
+    pub async fn resolve_patch_conflict(
+        sess: &Arc<Session>,
+        id: String,
+        resolution: PatchConflictResolution,
+    ) {
+        match resolution {
+            PatchConflictResolution::Abort => {
                 sess.interrupt_task().await;
             }
-            other => sess.notify_approval(&id, other).await,
+            other => sess.resolve_patch_conflict(&id, other).await,
         }
     }
 
+    pub async fn answer_question(sess: &Arc<Session>, id: String, answer: Option<String>) {
+        sess.answer_question(&id, answer).await;
+    }
+
+    pub async fn select_candidate(sess: &Arc<Session>, id: String, index: Option<usize>) {
+        sess.select_candidate(&id, index).await;
+    }
+
+    pub async fn review_memories(sess: &Arc<Session>, id: String, accepted: Vec<usize>) {
+        sess.review_memories(&id, accepted).await;
+    }
+
     pub async fn add_to_history(sess: &Arc<Session>, config: &Arc<Config>, text: String) {
         let id = sess.conversation_id;
         let config = Arc::clone(config);
@@ -1928,6 +3358,49 @@ mod handlers {
         });
     }
 
+    pub async fn get_file_at_turn(sess: &Arc<Session>, sub_id: String, path: PathBuf, turn: usize) {
+        let (path, turns, source) = {
+            let state = sess.state.lock().await;
+            let path = state.session_configuration.cwd.join(path);
+            let source = state.turn_ledger.source(&path, turn);
+            (path, state.turn_ledger.len(), source)
+        };
+        let from_checkpoint = matches!(source, Ok(FileSource::Checkpoint(_)));
+        let content =
+            match source {
+                Ok(FileSource::Checkpoint(checkpoint)) => {
+                    read_from_checkpoint(&checkpoint, &path).await
+                }
+                Ok(FileSource::Recorded(content)) => Ok(content),
+                Ok(FileSource::Workspace) => match tokio::fs::read(&path).await {
+                    Ok(content) => Ok(Some(content)),
+                    Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                    Err(err) => Err(anyhow::Error::new(err)
+                        .context(format!("failed to read {}", path.display()))),
+                },
+                Err(err) => Err(err),
+            };
+        let (content, error) = match content {
+            Ok(content) => (
+                content.map(|bytes| String::from_utf8_lossy(&bytes).into_owned()),
+                None,
+            ),
+            Err(err) => (None, Some(format!("{err:#}"))),
+        };
+        sess.send_event_raw(Event {
+            id: sub_id,
+            msg: EventMsg::FileAtTurnResponse(FileAtTurnResponseEvent {
+                path,
+                turn,
+                turns,
+                content,
+                from_checkpoint,
+                error,
+            }),
+        })
+        .await;
+    }
+
     pub async fn list_mcp_tools(sess: &Session, config: &Arc<Config>, sub_id: String) {
         let mcp_connection_manager = sess.services.mcp_connection_manager.read().await;
         let snapshot = collect_mcp_snapshot_from_manager(
@@ -2011,6 +3484,100 @@ mod handlers {
             .await;
     }
 
+    pub async fn regenerate(
+        sess: &Arc<Session>,
+        sub_id: String,
+        model: Option<String>,
+        effort: Option<Option<ReasoningEffortConfig>>,
+        previous_context: &mut Option<Arc<TurnContext>>,
+    ) {
+        if sess.active_turn.lock().await.is_some() {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::Error(ErrorEvent {
+                    message: "Cannot regenerate while a turn is running.".to_string(),
+                    codex_error_info: Some(CodexErrorInfo::BadRequest),
+                    details: Some(ErrorDetails::new(
+                        ErrorCategory::Protocol,
+                        "turn_in_progress",
+                    )),
+                }),
+            })
+            .await;
+            return;
+        }
+
+        let updates = SessionSettingsUpdate {
+            model,
+            reasoning_effort: effort,
+            ..Default::default()
+        };
+        let Ok(turn_context) = sess.new_turn_with_sub_id(sub_id.clone(), updates).await else {
+            // new_turn_with_sub_id already emits the error event.
+            return;
+        };
+        let Some((input, discarded_items)) = sess.discard_last_response().await else {
+            sess.send_event_raw(Event {
+                id: sub_id,
+                msg: EventMsg::Error(ErrorEvent {
+                    message: "There is no user message to regenerate a response for.".to_string(),
+                    codex_error_info: Some(CodexErrorInfo::BadRequest),
+                    details: Some(ErrorDetails::new(
+                        ErrorCategory::Protocol,
+                        "nothing_to_regenerate",
+                    )),
+                }),
+            })
+            .await;
+            return;
+        };
+        sess.recompute_token_usage(&turn_context).await;
+        sess.send_event(
+            &turn_context,
+            EventMsg::ResponseRegenerated(ResponseRegeneratedEvent {
+                model: turn_context.client.get_model(),
+                discarded_items,
+            }),
+        )
+        .await;
+
+        sess.spawn_task(Arc::clone(&turn_context), input, RegularTask)
+            .await;
+        *previous_context = Some(turn_context);
+    }
+
+    pub async fn run_workflow(
+        sess: &Arc<Session>,
+        config: &Arc<Config>,
+        sub_id: String,
+        name: String,
+        input: Option<String>,
+    ) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        let Some(workflow) = config.workflows.get(&name) else {
+            sess.send_event(
+                &turn_context,
+                EventMsg::Error(ErrorEvent {
+                    message: format!("Unknown workflow `{name}`."),
+                    codex_error_info: Some(CodexErrorInfo::BadRequest),
+                    details: Some(ErrorDetails::new(
+                        ErrorCategory::Protocol,
+                        "unknown_workflow",
+                    )),
+                }),
+            )
+            .await;
+            return;
+        };
+
+        sess.spawn_task(
+            turn_context,
+            Vec::new(),
+            WorkflowTask::new(name, workflow.clone(), input),
+        )
+        .await;
+    }
+
     pub async fn compact(sess: &Arc<Session>, sub_id: String) {
         let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
 
@@ -2024,6 +3591,18 @@ mod handlers {
         .await;
     }
 
+    pub async fn summarize_session(sess: &Arc<Session>, sub_id: String) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        sess.spawn_task(turn_context, Vec::new(), SessionSummaryTask)
+            .await;
+    }
+
+    pub async fn update_semantic_index(sess: &Arc<Session>, sub_id: String, rebuild: bool) {
+        let turn_context = sess.new_default_turn_with_sub_id(sub_id).await;
+        sess.spawn_task(turn_context, Vec::new(), SemanticIndexTask::new(rebuild))
+            .await;
+    }
+
     pub async fn shutdown(sess: &Arc<Session>, sub_id: String) -> bool {
         sess.abort_all_tasks(TurnAbortReason::Interrupted).await;
         sess.services
@@ -2047,11 +3626,28 @@ mod handlers {
                 msg: EventMsg::Error(ErrorEvent {
                     message: "Failed to shutdown rollout recorder".to_string(),
                     codex_error_info: Some(CodexErrorInfo::Other),
+                    details: Some(ErrorDetails::new(
+                        ErrorCategory::Io,
+                        "rollout_shutdown_failed",
+                    )),
                 }),
             };
             sess.send_event_raw(event).await;
         }
 
+        if let Some(tool_usage) = sess.services.tool_usage.as_ref() {
+            tool_usage.flush().await;
+        }
+        if let Some(usage_stats) = sess.services.usage_stats.as_ref() {
+            usage_stats.flush(&sess.services.otel_manager).await;
+        }
+        if let Some(output_spill) = sess.services.output_spill.as_ref() {
+            output_spill.cleanup().await;
+        }
+        if let Some(scratch_dir) = sess.services.scratch_dir.as_ref() {
+            scratch_dir.cleanup();
+        }
+
         let event = Event {
             id: sub_id,
             msg: EventMsg::ShutdownComplete,
@@ -2084,6 +3680,10 @@ mod handlers {
                     msg: EventMsg::Error(ErrorEvent {
                         message: err.to_string(),
                         codex_error_info: Some(CodexErrorInfo::Other),
+                        details: Some(ErrorDetails::new(
+                            ErrorCategory::Protocol,
+                            "invalid_review_request",
+                        )),
                     }),
                 };
                 sess.send_event(&turn_context, event.msg).await;
@@ -2111,10 +3711,11 @@ async fn spawn_review_thread(
     review_features
         .disable(crate::features::Feature::WebSearchRequest)
         .disable(crate::features::Feature::ViewImageTool);
-    let tools_config = ToolsConfig::new(&ToolsConfigParams {
+    let mut tools_config = ToolsConfig::new(&ToolsConfigParams {
         model_family: &review_model_family,
         features: &review_features,
     });
+    tools_config.description_token_budget = config.tool_description_budget;
 
     let base_instructions = REVIEW_PROMPT.to_string();
     let review_prompt = resolved.prompt.clone();
@@ -2153,6 +3754,10 @@ async fn spawn_review_thread(
         ghost_snapshot: parent_turn_context.ghost_snapshot.clone(),
         developer_instructions: None,
         user_instructions: None,
+        prompt_sections: None,
+        repo_map: None,
+        locale_instructions: parent_turn_context.locale_instructions.clone(),
+        model_instructions: config.instructions_for_model(review_model_family.slug.as_str()),
         base_instructions: Some(base_instructions.clone()),
         compact_prompt: parent_turn_context.compact_prompt.clone(),
         approval_policy: parent_turn_context.approval_policy,
@@ -2160,10 +3765,16 @@ async fn spawn_review_thread(
         shell_environment_policy: parent_turn_context.shell_environment_policy.clone(),
         cwd: parent_turn_context.cwd.clone(),
         final_output_json_schema: None,
+        best_of: None,
+        patch_critic: None,
+        patch_preview: None,
         codex_linux_sandbox_exe: parent_turn_context.codex_linux_sandbox_exe.clone(),
         tool_call_gate: Arc::new(ReadinessFlag::new()),
         exec_policy: parent_turn_context.exec_policy.clone(),
         truncation_policy: TruncationPolicy::new(&per_turn_config, model_family.truncation_policy),
+        context_ignore: parent_turn_context.context_ignore.clone(),
+        protected_paths: parent_turn_context.protected_paths.clone(),
+        wsl_paths: parent_turn_context.wsl_paths.clone(),
     };
 
     // Seed the child task with the review prompt as the initial user message.
@@ -2271,30 +3882,97 @@ pub(crate) async fn run_task(
             .await;
     }
 
+    if let Some(memories) = sess
+        .services
+        .memory_ranker
+        .as_ref()
+        .and_then(|ranker| ranker.relevant_memories(&input_for_memories))
+    {
+        sess.record_conversation_items(&turn_context, &[memories])
+            .await;
+    }
+
+    if sess.enabled(Feature::FileMentions) {
+        let previously_attached = sess.attached_files().await;
+        let FileMentionInjections {
+            items: attachment_items,
+            attached,
+            warnings: attachment_warnings,
+        } = build_file_mention_injections(
+            &input,
+            &turn_context.cwd,
+            &previously_attached,
+            &turn_context.context_ignore,
+            turn_context.truncation_policy,
+        )
+        .await;
+
+        for message in attachment_warnings {
+            sess.send_event(&turn_context, EventMsg::Warning(WarningEvent { message }))
+                .await;
+        }
+
+        if !attachment_items.is_empty() {
+            sess.record_conversation_items(&turn_context, &attachment_items)
+                .await;
+            sess.watch_files(attached.keys().cloned()).await;
+            sess.record_attached_files(attached).await;
+        }
+    }
+
+    if sess.enabled(Feature::ExternalEditNotices) {
+        let edit_items: Vec<ResponseItem> = sess
+            .take_external_edits(&turn_context.cwd)
+            .await
+            .into_iter()
+            .map(ResponseItem::from)
+            .collect();
+        if !edit_items.is_empty() {
+            sess.record_conversation_items(&turn_context, &edit_items)
+                .await;
+        }
+    }
+
     sess.maybe_start_ghost_snapshot(Arc::clone(&turn_context), cancellation_token.child_token())
         .await;
     let mut last_agent_message: Option<String> = None;
     // Although from the perspective of codex.rs, TurnDiffTracker has the lifecycle of a Task which contains
     // many turns, from the perspective of the user, it is a single turn.
     let turn_diff_tracker = Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new()));
+    // Each rejected request gets one repair attempt before the error is reported.
+    let mut context_repair_attempted = false;
+    // A repaired copy of the rejected request's input, sent again in its place.
+    let mut repaired_input: Option<Vec<ResponseItem>> = None;
+    // Only the first request of the task samples several candidates; the
+    // follow-ups build on the chosen one.
+    let mut best_of = turn_context.best_of;
 
     loop {
-        // Note that pending_input would be something like a message the user
-        // submitted through the UI while the model was running. Though the UI
-        // may support this, the model might not.
-        let pending_input = sess
-            .get_pending_input()
-            .await
-            .into_iter()
-            .map(ResponseItem::from)
-            .collect::<Vec<ResponseItem>>();
-
         // Construct the input that we will send to the model.
-        let turn_input: Vec<ResponseItem> = {
-            sess.record_conversation_items(&turn_context, &pending_input)
-                .await;
-            sess.clone_history().await.get_history_for_prompt()
+        let turn_input: Vec<ResponseItem> = match repaired_input.take() {
+            Some(input) => input,
+            None => {
+                // Note that pending_input would be something like a message the user
+                // submitted through the UI while the model was running. Though the UI
+                // may support this, the model might not.
+                let pending_input = sess
+                    .get_pending_input()
+                    .await
+                    .into_iter()
+                    .map(ResponseItem::from)
+                    .collect::<Vec<ResponseItem>>();
+                sess.record_conversation_items(&turn_context, &pending_input)
+                    .await;
+                sess.drop_foreign_reasoning(&turn_context).await;
+                let mut input = sess.clone_history().await.get_history_for_prompt();
+                let report =
+                    apply_history_filter(&mut input, &turn_context.client.config().history_filter);
+                sess.notify_history_filtered(&turn_context, report).await;
+                input
+            }
         };
+        let sent_input = (!context_repair_attempted && sess.enabled(Feature::ContextRepair))
+            .then(|| turn_input.clone());
 
         let turn_input_messages = turn_input
             .iter()
@@ -2309,15 +3987,24 @@ pub(crate) async fn run_task(
             Arc::clone(&turn_context),
             Arc::clone(&turn_diff_tracker),
             turn_input,
+            best_of,
             cancellation_token.child_token(),
         )
         .await
         {
             Ok(turn_output) => {
+                context_repair_attempted = false;
+                best_of = None;
                 let TurnRunResult {
                     needs_follow_up,
                     last_agent_message: turn_last_agent_message,
                 } = turn_output;
+                if needs_follow_up && let Err(err) = sess.services.agent_budget.check() {
+                    info!("Stopping sub-agent: {err}");
+                    let event = EventMsg::Error(err.to_error_event(None));
+                    sess.send_event(&turn_context, event).await;
+                    break;
+                }
                 let total_usage_tokens = sess.get_total_token_usage().await;
                 let token_limit_reached = total_usage_tokens >= auto_compact_limit;
 
@@ -2327,7 +4014,8 @@ pub(crate) async fn run_task(
                     continue;
                 }
 
-                if !needs_follow_up {
+                // Input that arrived as the model finished still gets an answer.
+                if !needs_follow_up && sess.close_input_if_idle().await {
                     last_agent_message = turn_last_agent_message;
                     sess.notifier()
                         .notify(&UserNotification::AgentTurnComplete {
@@ -2340,9 +4028,22 @@ pub(crate) async fn run_task(
                     let mem_sess = Arc::clone(&sess);
                     let mem_turn = Arc::clone(&turn_context);
                     let mem_inputs = input_for_memories.clone();
+                    let mem_agent_message = last_agent_message.clone();
                     tokio::spawn(async move {
-                        memories::maybe_record_memories(&mem_sess, &mem_turn, &mem_inputs).await;
+                        memories::maybe_record_memories(
+                            &mem_sess,
+                            &mem_turn,
+                            &mem_inputs,
+                            mem_agent_message.as_deref(),
+                        )
+                        .await;
                     });
+                    if let Some(tool_usage) = sess.services.tool_usage.as_ref() {
+                        tool_usage.flush().await;
+                    }
+                    if let Some(usage_stats) = sess.services.usage_stats.as_ref() {
+                        usage_stats.flush(&sess.services.otel_manager).await;
+                    }
                     break;
                 }
                 continue;
@@ -2359,6 +4060,19 @@ pub(crate) async fn run_task(
                 state.history.replace_last_turn_images("Invalid image");
             }
             Err(e) => {
+                if let Some(sent_input) = sent_input
+                    && matches!(
+                        e,
+                        CodexErr::ContextWindowExceeded | CodexErr::InvalidRequest(_)
+                    )
+                {
+                    context_repair_attempted = true;
+                    if repair_context(&sess, &turn_context, &e, sent_input, &mut repaired_input)
+                        .await
+                    {
+                        continue;
+                    }
+                }
                 info!("Turn error: {e:#}");
                 let event = EventMsg::Error(e.to_error_event(None));
                 sess.send_event(&turn_context, event).await;
@@ -2368,9 +4082,74 @@ pub(crate) async fn run_task(
         }
     }
 
+    if sess.enabled(Feature::DependencyReport) {
+        let manifests: Vec<(PathBuf, String)> = turn_diff_tracker
+            .lock()
+            .await
+            .baselines()
+            .into_iter()
+            .filter(|(path, _)| dependency_report::manifest_ecosystem(path).is_some())
+            .map(|(path, before)| (path, String::from_utf8_lossy(before).into_owned()))
+            .collect();
+        if !manifests.is_empty() {
+            // Registry lookups can be slow, so do not hold up the turn.
+            let report_sess = Arc::clone(&sess);
+            let report_turn = Arc::clone(&turn_context);
+            tokio::spawn(async move {
+                dependency_report::report_added_dependencies(&report_sess, &report_turn, manifests)
+                    .await;
+            });
+        }
+    }
+
+    sess.record_turn_in_ledger(&turn_context.sub_id, &turn_diff_tracker)
+        .await;
+    sess.refresh_watched_files().await;
     last_agent_message
 }
 
+/// Prepare a retry of a request the provider rejected. The newest oversized
+/// tool output (or, when the request was invalid, the newest item with images)
+/// is repaired in `sent_input`, the copy that was sent, and the repaired copy
+/// is stored in `retry_input`; the recorded history keeps the original. When
+/// the context window was exceeded and nothing could be repaired, the history
+/// is compacted and the retry rebuilds its input. Returns whether to retry.
+async fn repair_context(
+    sess: &Arc<Session>,
+    turn_context: &Arc<TurnContext>,
+    err: &CodexErr,
+    mut sent_input: Vec<ResponseItem>,
+    retry_input: &mut Option<Vec<ResponseItem>>,
+) -> bool {
+    let context_exceeded = matches!(err, CodexErr::ContextWindowExceeded);
+    let repaired = repair_rejected_item(
+        &mut sent_input,
+        CONTEXT_REPAIR_OUTPUT_BYTES,
+        !context_exceeded,
+    );
+    let compacted = repaired.is_none();
+    if compacted && !context_exceeded {
+        return false;
+    }
+
+    warn!("retrying turn after repairing context for: {err}");
+    if compacted {
+        run_auto_compact(sess, turn_context).await;
+    } else {
+        *retry_input = Some(sent_input);
+    }
+    sess.send_event(
+        turn_context,
+        EventMsg::ContextRepaired(ContextRepairedEvent {
+            error: err.to_string(),
+            dropped: repaired.into_iter().collect(),
+            compacted,
+        }),
+    )
+    .await;
+    true
+}
+
 async fn run_auto_compact(sess: &Arc<Session>, turn_context: &Arc<TurnContext>) {
     if should_use_remote_compact_task(sess.as_ref(), &turn_context.client.get_provider()) {
         run_inline_remote_auto_compact_task(Arc::clone(sess), Arc::clone(turn_context)).await;
@@ -2392,6 +4171,7 @@ async fn run_turn(
     turn_context: Arc<TurnContext>,
     turn_diff_tracker: SharedTurnDiffTracker,
     input: Vec<ResponseItem>,
+    best_of: Option<BestOf>,
     cancellation_token: CancellationToken,
 ) -> CodexResult<TurnRunResult> {
     let mcp_tools = sess
@@ -2422,9 +4202,26 @@ async fn run_turn(
         tools: router.specs(),
         parallel_tool_calls: model_supports_parallel && sess.enabled(Feature::ParallelToolCalls),
         base_instructions_override: turn_context.base_instructions.clone(),
+        locale_instructions: turn_context.locale_instructions.clone(),
+        model_instructions: turn_context.model_instructions.clone(),
+        composed_instructions: turn_context.prompt_sections.as_ref().map(|sections| {
+            sections.compose(
+                turn_context.approval_policy,
+                &turn_context.sandbox_policy,
+                &sess.environment_context(&turn_context).serialize_to_xml(),
+            )
+        }),
         output_schema: turn_context.final_output_json_schema.clone(),
     };
 
+    let manifest = build_context_manifest(
+        &prompt,
+        &turn_context.client.get_model_family(),
+        turn_context.client.config().project_instructions.as_ref(),
+    );
+    sess.send_event(&turn_context, EventMsg::ContextManifest(manifest))
+        .await;
+
     let mut retries = 0;
     loop {
         match try_run_turn(
@@ -2433,6 +4230,7 @@ async fn run_turn(
             Arc::clone(&turn_context),
             Arc::clone(&turn_diff_tracker),
             &prompt,
+            best_of,
             cancellation_token.child_token(),
         )
         .await
@@ -2466,6 +4264,8 @@ async fn run_turn(
                 let max_retries = turn_context.client.get_provider().stream_max_retries();
                 if retries < max_retries {
                     retries += 1;
+                    sess.update_turn_metrics(|metrics| metrics.retries += 1)
+                        .await;
                     let delay = match e {
                         CodexErr::Stream(_, Some(delay)) => delay,
                         _ => backoff(retries),
@@ -2499,6 +4299,34 @@ struct TurnRunResult {
     last_agent_message: Option<String>,
 }
 
+/// Send `EventMsg::Stalled` for a model request that has sent nothing for
+/// `stall_detection.timeout_secs`. Returns whether to retry the request,
+/// which `auto_retry` allows once per turn.
+async fn report_stalled_request(
+    sess: &Session,
+    turn_context: &TurnContext,
+    stall_detection: StallDetection,
+) -> bool {
+    let retrying = stall_detection.auto_retry
+        && match sess.active_turn.lock().await.as_ref() {
+            Some(at) => at.turn_state.lock().await.take_stall_retry(),
+            None => false,
+        };
+    warn!(
+        "turn {} received nothing from the model for {}s",
+        turn_context.sub_id, stall_detection.timeout_secs
+    );
+    sess.send_event(
+        turn_context,
+        EventMsg::Stalled(StalledEvent {
+            idle_ms: stall_detection.timeout_secs.saturating_mul(1000),
+            retrying,
+        }),
+    )
+    .await;
+    retrying
+}
+
 async fn drain_in_flight(
     in_flight: &mut FuturesOrdered<BoxFuture<'static, CodexResult<ResponseInputItem>>>,
     sess: Arc<Session>,
@@ -2532,6 +4360,7 @@ async fn try_run_turn(
     turn_context: Arc<TurnContext>,
     turn_diff_tracker: SharedTurnDiffTracker,
     prompt: &Prompt,
+    best_of: Option<BestOf>,
     cancellation_token: CancellationToken,
 ) -> CodexResult<TurnRunResult> {
     let rollout_item = RolloutItem::TurnContext(TurnContextItem {
@@ -2539,18 +4368,35 @@ async fn try_run_turn(
         approval_policy: turn_context.approval_policy,
         sandbox_policy: turn_context.sandbox_policy.clone(),
         model: turn_context.client.get_model(),
+        model_provider: Some(turn_context.client.config().model_provider_id.clone()),
+        approval_preset: ApprovalPresetId::matching(
+            turn_context.approval_policy,
+            &turn_context.sandbox_policy,
+        ),
         effort: turn_context.client.get_reasoning_effort(),
         summary: turn_context.client.get_reasoning_summary(),
     });
 
     sess.persist_rollout_items(&[rollout_item]).await;
-    let mut stream = turn_context
-        .client
-        .clone()
-        .stream(prompt)
-        .instrument(trace_span!("stream_request"))
-        .or_cancel(&cancellation_token)
-        .await??;
+    sess.update_turn_metrics(|metrics| metrics.model_calls += 1)
+        .await;
+    let mut stream = match best_of {
+        Some(best_of) => {
+            best_of::sample_best_of(&sess, &turn_context, prompt, best_of)
+                .instrument(trace_span!("sample_best_of"))
+                .or_cancel(&cancellation_token)
+                .await??
+        }
+        None => {
+            turn_context
+                .client
+                .clone()
+                .stream(prompt)
+                .instrument(trace_span!("stream_request"))
+                .or_cancel(&cancellation_token)
+                .await??
+        }
+    };
 
     let tool_runtime = ToolCallRuntime::new(
         Arc::clone(&router),
@@ -2563,7 +4409,14 @@ async fn try_run_turn(
     let mut needs_follow_up = false;
     let mut last_agent_message: Option<String> = None;
     let mut active_item: Option<TurnItem> = None;
+    // With an output schema, values of the JSON response are sent as soon as
+    // they are complete.
+    let mut structured_output = prompt
+        .output_schema
+        .as_ref()
+        .map(|_| StructuredOutputParser::default());
     let mut should_emit_turn_diff = false;
+    let stall_detection = turn_context.client.config().stall_detection;
     let receiving_span = trace_span!("receiving_stream");
     let outcome: CodexResult<TurnRunResult> = loop {
         let handle_responses = trace_span!(
@@ -2574,13 +4427,37 @@ async fn try_run_turn(
             from = field::Empty,
         );
 
-        let event = match stream
+        // Only the wait for the model counts towards `stall_detection`; tool
+        // calls run alongside the stream and never hold it up.
+        let next_event = stream
             .next()
-            .instrument(trace_span!(parent: &handle_responses, "receiving"))
+            .instrument(trace_span!(parent: &handle_responses, "receiving"));
+        let received = match stall_detection {
+            Some(stall_detection) => tokio::time::timeout(
+                Duration::from_secs(stall_detection.timeout_secs),
+                next_event,
+            )
             .or_cancel(&cancellation_token)
             .await
-        {
-            Ok(event) => event,
+            .map(Result::ok),
+            None => next_event.or_cancel(&cancellation_token).await.map(Some),
+        };
+        let event = match received {
+            Ok(Some(event)) => event,
+            Ok(None) => {
+                if let Some(stall_detection) = stall_detection
+                    && report_stalled_request(&sess, &turn_context, stall_detection).await
+                {
+                    break Err(CodexErr::Stream(
+                        format!(
+                            "no response from the model for {}s",
+                            stall_detection.timeout_secs
+                        ),
+                        Some(Duration::ZERO),
+                    ));
+                }
+                continue;
+            }
             Err(codex_async_utils::CancelErr::Cancelled) => break Err(CodexErr::TurnAborted),
         };
 
@@ -2626,6 +4503,9 @@ async fn try_run_turn(
                     sess.emit_turn_item_started(&turn_context, &turn_item).await;
 
                     active_item = Some(tracked_item);
+                    if let Some(parser) = structured_output.as_mut() {
+                        *parser = StructuredOutputParser::default();
+                    }
                 }
             }
             ResponseEvent::RateLimits(snapshot) => {
@@ -2658,6 +4538,19 @@ async fn try_run_turn(
                     };
                     sess.send_event(&turn_context, EventMsg::AgentMessageContentDelta(event))
                         .await;
+                    if let Some(parser) = structured_output.as_mut() {
+                        for partial in parser.push(&delta) {
+                            let event = StructuredOutputDeltaEvent {
+                                thread_id: sess.conversation_id.to_string(),
+                                turn_id: turn_context.sub_id.clone(),
+                                item_id: active.id(),
+                                pointer: partial.pointer,
+                                value: partial.value,
+                            };
+                            sess.send_event(&turn_context, EventMsg::StructuredOutputDelta(event))
+                                .await;
+                        }
+                    }
                 } else {
                     error_or_panic("OutputTextDelta without active item".to_string());
                 }
@@ -2716,12 +4609,12 @@ async fn try_run_turn(
     drain_in_flight(&mut in_flight, sess.clone(), turn_context.clone()).await?;
 
     if should_emit_turn_diff {
-        let unified_diff = {
+        let turn_diff = {
             let mut tracker = turn_diff_tracker.lock().await;
-            tracker.get_unified_diff()
+            tracker.get_turn_diff()
         };
-        if let Ok(Some(unified_diff)) = unified_diff {
-            let msg = EventMsg::TurnDiff(TurnDiffEvent { unified_diff });
+        if let Ok(Some(turn_diff)) = turn_diff {
+            let msg = EventMsg::TurnDiff(turn_diff);
             sess.clone().send_event(&turn_context, msg).await;
         }
     }
@@ -2826,6 +4719,82 @@ mod tests {
         assert_eq!(expected, actual);
     }
 
+    #[tokio::test]
+    async fn reconstruct_history_drops_reasoning_from_a_previous_provider() {
+        let (session, turn_context) = make_session_and_context().await;
+        let turn = |provider: &str| {
+            RolloutItem::TurnContext(TurnContextItem {
+                cwd: turn_context.cwd.clone(),
+                approval_policy: turn_context.approval_policy,
+                sandbox_policy: turn_context.sandbox_policy.clone(),
+                model: turn_context.client.get_model(),
+                model_provider: Some(provider.to_string()),
+                approval_preset: None,
+                effort: None,
+                summary: turn_context.client.get_reasoning_summary(),
+            })
+        };
+        let reasoning = |encrypted: &str| ResponseItem::Reasoning {
+            id: String::new(),
+            summary: Vec::new(),
+            content: None,
+            encrypted_content: Some(encrypted.to_string()),
+        };
+        let user = ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: "hi".to_string(),
+            }],
+        };
+        let rollout_items = vec![
+            turn("openai"),
+            RolloutItem::ResponseItem(user.clone()),
+            RolloutItem::ResponseItem(reasoning("from openai")),
+            turn("azure"),
+            RolloutItem::ResponseItem(reasoning("from azure")),
+        ];
+
+        let reconstructed = session.reconstruct_history_from_rollout(&turn_context, &rollout_items);
+
+        assert_eq!(reconstructed, vec![user, reasoning("from azure")]);
+    }
+
+    #[tokio::test]
+    async fn discard_last_response_returns_the_last_user_message() {
+        let (session, turn_context) = make_session_and_context().await;
+        let message = |role: &str, text: &str| ResponseItem::Message {
+            id: None,
+            role: role.to_string(),
+            content: vec![if role == "user" {
+                ContentItem::InputText {
+                    text: text.to_string(),
+                }
+            } else {
+                ContentItem::OutputText {
+                    text: text.to_string(),
+                }
+            }],
+        };
+        let kept = vec![message("user", "first"), message("assistant", "one")];
+        let mut history = kept.clone();
+        history.extend([message("user", "second"), message("assistant", "two")]);
+        session.record_into_history(&history, &turn_context).await;
+
+        let discarded = session.discard_last_response().await;
+
+        assert_eq!(
+            discarded,
+            Some((
+                vec![UserInput::Text {
+                    text: "second".to_string()
+                }],
+                2
+            ))
+        );
+        assert_eq!(session.clone_history().await.get_history(), kept);
+    }
+
     #[tokio::test]
     async fn record_initial_history_reconstructs_forked_transcript() {
         let (session, turn_context) = make_session_and_context().await;
@@ -2850,8 +4819,11 @@ mod tests {
             model,
             model_reasoning_effort: config.model_reasoning_effort,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
+            prompt_sections: None,
+            repo_map: None,
             base_instructions: config.base_instructions.clone(),
             compact_prompt: config.compact_prompt.clone(),
             approval_policy: config.approval_policy.clone(),
@@ -2917,8 +4889,11 @@ mod tests {
             model,
             model_reasoning_effort: config.model_reasoning_effort,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
+            prompt_sections: None,
+            repo_map: None,
             base_instructions: config.base_instructions.clone(),
             compact_prompt: config.compact_prompt.clone(),
             approval_policy: config.approval_policy.clone(),
@@ -2973,6 +4948,101 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn changes_to_lists_only_changed_settings() {
+        let codex_home = tempfile::tempdir().expect("create temp dir");
+        let config = Arc::new(build_test_config(codex_home.path()).await);
+        let model = ModelsManager::get_model_offline(config.model.as_deref());
+        let session_configuration = SessionConfiguration {
+            provider: config.model_provider.clone(),
+            model,
+            model_reasoning_effort: config.model_reasoning_effort,
+            model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
+            developer_instructions: config.developer_instructions.clone(),
+            user_instructions: config.user_instructions.clone(),
+            prompt_sections: None,
+            repo_map: None,
+            base_instructions: config.base_instructions.clone(),
+            compact_prompt: config.compact_prompt.clone(),
+            approval_policy: config.approval_policy.clone(),
+            sandbox_policy: config.sandbox_policy.clone(),
+            cwd: config.cwd.clone(),
+            original_config_do_not_use: Arc::clone(&config),
+            exec_policy: Arc::new(RwLock::new(ExecPolicy::empty())),
+            session_source: SessionSource::Exec,
+        };
+
+        let mut next = session_configuration.clone();
+        next.model = "other-model".to_string();
+        next.cwd = config.cwd.join("sub");
+
+        assert_eq!(
+            session_configuration.changes_to(&next),
+            TurnContextChangedEvent {
+                model: Some(SettingChange {
+                    old: session_configuration.model.clone(),
+                    new: "other-model".to_string(),
+                }),
+                cwd: Some(SettingChange {
+                    old: config.cwd.clone(),
+                    new: config.cwd.join("sub"),
+                }),
+                ..Default::default()
+            }
+        );
+        assert!(
+            session_configuration
+                .changes_to(&session_configuration)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn change_directory_stays_within_writable_roots() {
+        let workspace = tempfile::tempdir().expect("create temp dir");
+        let root = dunce::canonicalize(workspace.path()).expect("canonicalize");
+        let subproject = root.join("crates").join("app");
+        std::fs::create_dir_all(&subproject).expect("create subproject");
+        let outside = tempfile::tempdir().expect("create temp dir");
+        let policy = SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+
+        let cwd = resolve_working_directory(&root, &root, &policy, Path::new("crates/app"))
+            .expect("subproject is writable");
+        assert_eq!(cwd, subproject);
+        // Going back up is allowed because roots come from the session's
+        // starting directory, not the current one.
+        let cwd = resolve_working_directory(&subproject, &root, &policy, Path::new("../.."))
+            .expect("workspace root is writable");
+        assert_eq!(cwd, root);
+
+        let err = resolve_working_directory(&root, &root, &policy, outside.path())
+            .expect_err("outside the workspace");
+        assert_eq!(
+            err.details.map(|details| details.code),
+            Some("cwd_outside_sandbox".to_string())
+        );
+        resolve_working_directory(
+            &root,
+            &root,
+            &SandboxPolicy::DangerFullAccess,
+            outside.path(),
+        )
+        .expect("full access allows any directory");
+
+        let err = resolve_working_directory(&root, &root, &policy, Path::new("missing"))
+            .expect_err("missing directory");
+        assert_eq!(
+            err.details.map(|details| details.code),
+            Some("cwd_not_found".to_string())
+        );
+    }
+
     #[test]
     fn prefers_structured_content_when_present() {
         let ctr = CallToolResult {
@@ -3124,8 +5194,11 @@ mod tests {
             model,
             model_reasoning_effort: config.model_reasoning_effort,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
+            prompt_sections: None,
+            repo_map: None,
             base_instructions: config.base_instructions.clone(),
             compact_prompt: config.compact_prompt.clone(),
             approval_policy: config.approval_policy.clone(),
@@ -3154,15 +5227,24 @@ mod tests {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: CancellationToken::new(),
             unified_exec_manager: UnifiedExecSessionManager::default(),
-            notifier: UserNotifier::new(None),
+            notifier: UserNotifier::new(None, Vec::new()),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
-            show_raw_agent_reasoning: config.show_raw_agent_reasoning,
             auth_manager: auth_manager.clone(),
             otel_manager: otel_manager.clone(),
             models_manager,
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
+            index_watcher: None,
+            repo_map_cache: RepoMapCache::default(),
+            session_environment: SessionEnvironment::default(),
+            scratch_dir: None,
+            output_spill: None,
+            tool_usage: None,
+            memory_ranker: None,
+            usage_stats: None,
+            agent_budget: AgentBudget::root(config.sub_agents.clone()),
+            annotations: std::sync::Mutex::new(Annotations::default()),
         };
 
         let turn_context = Session::make_turn_context(
@@ -3210,8 +5292,11 @@ mod tests {
             model,
             model_reasoning_effort: config.model_reasoning_effort,
             model_reasoning_summary: config.model_reasoning_summary,
+            model_verbosity: config.model_verbosity,
             developer_instructions: config.developer_instructions.clone(),
             user_instructions: config.user_instructions.clone(),
+            prompt_sections: None,
+            repo_map: None,
             base_instructions: config.base_instructions.clone(),
             compact_prompt: config.compact_prompt.clone(),
             approval_policy: config.approval_policy.clone(),
@@ -3240,15 +5325,24 @@ mod tests {
             mcp_connection_manager: Arc::new(RwLock::new(McpConnectionManager::default())),
             mcp_startup_cancellation_token: CancellationToken::new(),
             unified_exec_manager: UnifiedExecSessionManager::default(),
-            notifier: UserNotifier::new(None),
+            notifier: UserNotifier::new(None, Vec::new()),
             rollout: Mutex::new(None),
             user_shell: Arc::new(default_user_shell()),
-            show_raw_agent_reasoning: config.show_raw_agent_reasoning,
             auth_manager: Arc::clone(&auth_manager),
             otel_manager: otel_manager.clone(),
             models_manager,
             tool_approvals: Mutex::new(ApprovalStore::default()),
             skills_manager,
+            index_watcher: None,
+            repo_map_cache: RepoMapCache::default(),
+            session_environment: SessionEnvironment::default(),
+            scratch_dir: None,
+            output_spill: None,
+            tool_usage: None,
+            memory_ranker: None,
+            usage_stats: None,
+            agent_budget: AgentBudget::root(config.sub_agents.clone()),
+            annotations: std::sync::Mutex::new(Annotations::default()),
         };
 
         let turn_context = Arc::new(Session::make_turn_context(
@@ -9,15 +9,19 @@ use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::ExecApprovalRequestEvent;
 use codex_protocol::protocol::Op;
+use codex_protocol::protocol::PatchConflictResolution;
+use codex_protocol::protocol::QuestionEvent;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::protocol::SubAgentSource;
 use codex_protocol::protocol::Submission;
 use codex_protocol::user_input::UserInput;
 use std::time::Duration;
+use tokio::time::sleep_until;
 use tokio::time::timeout;
 use tokio_util::sync::CancellationToken;
 
 use crate::AuthManager;
+use crate::agent_budget::AgentBudget;
 use crate::codex::Codex;
 use crate::codex::CodexSpawnOk;
 use crate::codex::SUBMISSION_CHANNEL_CAPACITY;
@@ -33,6 +37,9 @@ use codex_protocol::protocol::InitialHistory;
 /// The returned `events_rx` yields non-approval events emitted by the sub-agent.
 /// Approval requests are handled via `parent_session` and are not surfaced.
 /// The returned `ops_tx` allows the caller to submit additional `Op`s to the sub-agent.
+/// The sub-agent draws on the parent's `[sub_agents]` budget and is refused
+/// when that budget is already spent; it is stopped when its time runs out.
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn run_codex_conversation_interactive(
     config: Config,
     auth_manager: Arc<AuthManager>,
@@ -41,17 +48,20 @@ pub(crate) async fn run_codex_conversation_interactive(
     parent_ctx: Arc<TurnContext>,
     cancel_token: CancellationToken,
     initial_history: Option<InitialHistory>,
+    source: SubAgentSource,
 ) -> Result<Codex, CodexErr> {
     let (tx_sub, rx_sub) = async_channel::bounded(SUBMISSION_CHANNEL_CAPACITY);
     let (tx_ops, rx_ops) = async_channel::bounded(SUBMISSION_CHANNEL_CAPACITY);
 
-    let CodexSpawnOk { codex, .. } = Codex::spawn(
+    let agent_budget = parent_session.services.agent_budget.child()?;
+    let CodexSpawnOk { codex, .. } = Codex::spawn_with_budget(
         config,
         auth_manager,
         models_manager,
         Arc::clone(&parent_session.services.skills_manager),
         initial_history.unwrap_or(InitialHistory::New),
-        SessionSource::SubAgent(SubAgentSource::Review),
+        SessionSource::SubAgent(source),
+        Arc::clone(&agent_budget),
     )
     .await?;
     let codex = Arc::new(codex);
@@ -71,6 +81,7 @@ pub(crate) async fn run_codex_conversation_interactive(
             tx_sub,
             parent_session_clone,
             parent_ctx_clone,
+            agent_budget,
             cancel_token_events,
         )
         .await;
@@ -102,6 +113,7 @@ pub(crate) async fn run_codex_conversation_one_shot(
     parent_ctx: Arc<TurnContext>,
     cancel_token: CancellationToken,
     initial_history: Option<InitialHistory>,
+    source: SubAgentSource,
 ) -> Result<Codex, CodexErr> {
     // Use a child token so we can stop the delegate after completion without
     // requiring the caller to cancel the parent token.
@@ -114,6 +126,7 @@ pub(crate) async fn run_codex_conversation_one_shot(
         parent_ctx,
         child_cancel.clone(),
         initial_history,
+        source,
     )
     .await?;
 
@@ -162,10 +175,19 @@ async fn forward_events(
     tx_sub: Sender<Event>,
     parent_session: Arc<Session>,
     parent_ctx: Arc<TurnContext>,
+    agent_budget: Arc<AgentBudget>,
     cancel_token: CancellationToken,
 ) {
     let cancelled = cancel_token.cancelled();
     tokio::pin!(cancelled);
+    let deadline = agent_budget.deadline();
+    let out_of_time = async move {
+        match deadline {
+            Some(deadline) => sleep_until(deadline).await,
+            None => std::future::pending().await,
+        }
+    };
+    tokio::pin!(out_of_time);
 
     loop {
         tokio::select! {
@@ -173,6 +195,17 @@ async fn forward_events(
                 shutdown_delegate(&codex).await;
                 break;
             }
+            _ = &mut out_of_time => {
+                if let Err(err) = agent_budget.check() {
+                    let event = Event {
+                        id: String::new(),
+                        msg: EventMsg::Error(err.to_error_event(None)),
+                    };
+                    let _ = tx_sub.send(event).await;
+                }
+                shutdown_delegate(&codex).await;
+                break;
+            }
             event = codex.next_event() => {
                 let event = match event {
                     Ok(event) => event,
@@ -217,6 +250,41 @@ async fn forward_events(
                         )
                         .await;
                     }
+                    Event {
+                        id,
+                        msg: EventMsg::PatchConflict(_),
+                    } => {
+                        // Sub-agents keep concurrent edits and re-read the files.
+                        let _ = codex
+                            .submit(Op::ResolvePatchConflict {
+                                id,
+                                resolution: PatchConflictResolution::Reread,
+                            })
+                            .await;
+                    }
+                    Event {
+                        id,
+                        msg: EventMsg::Question(event),
+                    } => {
+                        handle_question(
+                            &codex,
+                            id,
+                            &parent_session,
+                            &parent_ctx,
+                            event,
+                            &cancel_token,
+                        )
+                        .await;
+                    }
+                    Event {
+                        id,
+                        msg: EventMsg::CandidatesProposed(_),
+                    } => {
+                        // Sub-agents leave the choice to the judge prompt.
+                        let _ = codex
+                            .submit(Op::SelectCandidate { id, index: None })
+                            .await;
+                    }
                     other => {
                         match tx_sub.send(other).or_cancel(&cancel_token).await {
                             Ok(Ok(())) => {}
@@ -280,6 +348,7 @@ async fn handle_exec_approval(
         parent_ctx.sub_id.clone(),
         event.command,
         event.cwd,
+        event.env,
         event.reason,
         event.proposed_execpolicy_amendment,
     );
@@ -322,6 +391,31 @@ async fn handle_patch_approval(
     let _ = codex.submit(Op::PatchApproval { id, decision }).await;
 }
 
+/// Route a sub-agent question to the parent session's user; decline on cancellation.
+async fn handle_question(
+    codex: &Codex,
+    id: String,
+    parent_session: &Session,
+    parent_ctx: &TurnContext,
+    event: QuestionEvent,
+    cancel_token: &CancellationToken,
+) {
+    let answer = tokio::select! {
+        biased;
+        _ = cancel_token.cancelled() => {
+            parent_session.answer_question(&parent_ctx.sub_id, None).await;
+            None
+        }
+        answer = parent_session.request_user_answer(
+            parent_ctx,
+            event.call_id,
+            event.question,
+            event.options,
+        ) => answer,
+    };
+    let _ = codex.submit(Op::AnswerQuestion { id, answer }).await;
+}
+
 /// Await an approval decision, aborting on cancellation.
 async fn await_approval_with_cancel<F>(
     fut: F,
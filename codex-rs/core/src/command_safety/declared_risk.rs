@@ -0,0 +1,216 @@
+//! Intent and risk level the model declares for each shell command.
+//!
+//! Declarations can only make approvals stricter. A command declared
+//! destructive always asks first, and so does a command the safety classifier
+//! considers more dangerous than the model claimed. A low declared risk never
+//! skips a prompt the command would otherwise get.
+
+use codex_protocol::models::CommandRisk;
+use codex_protocol::protocol::AskForApproval;
+
+use crate::command_safety::is_dangerous_command::command_might_be_dangerous;
+use crate::command_safety::is_safe_command::is_known_safe_command;
+use crate::tools::sandboxing::ExecApprovalRequirement;
+
+pub(crate) const MISSING_DECLARATION_MESSAGE: &str = "Every command must declare `intent` (one sentence on what it is for) and `risk` (\"read_only\", \"write\", or \"destructive\"). Resend the call with both.";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct CommandDeclaration {
+    pub intent: String,
+    pub risk: CommandRisk,
+}
+
+impl CommandDeclaration {
+    /// Build a declaration from tool call arguments. Returns `None` when
+    /// either field is missing or the intent is blank.
+    pub(crate) fn from_args(intent: Option<String>, risk: Option<CommandRisk>) -> Option<Self> {
+        let intent = intent?.trim().to_string();
+        if intent.is_empty() {
+            return None;
+        }
+        Some(Self {
+            intent,
+            risk: risk?,
+        })
+    }
+
+    /// Tighten `requirement` based on this declaration and the safety
+    /// classifier, and put the stated intent in any approval prompt.
+    pub(crate) fn apply(
+        &self,
+        command: &[String],
+        approval_policy: AskForApproval,
+        requirement: ExecApprovalRequirement,
+    ) -> ExecApprovalRequirement {
+        let classified = classify(command);
+        let note = (classified > self.risk).then(|| {
+            let looks = match classified {
+                CommandRisk::Destructive => "looks destructive",
+                CommandRisk::ReadOnly | CommandRisk::Write => "may change files",
+            };
+            format!("Declared {}, but this command {looks}.", self.risk)
+        });
+        let must_ask = self.risk == CommandRisk::Destructive
+            || (note.is_some() && classified == CommandRisk::Destructive);
+
+        match requirement {
+            ExecApprovalRequirement::NeedsApproval {
+                reason,
+                proposed_execpolicy_amendment,
+            } => ExecApprovalRequirement::NeedsApproval {
+                reason: Some(self.reason(note.or(reason))),
+                proposed_execpolicy_amendment,
+            },
+            // With approvals off there is nobody to ask; the sandbox still applies.
+            ExecApprovalRequirement::Skip { .. }
+                if must_ask && approval_policy != AskForApproval::Never =>
+            {
+                ExecApprovalRequirement::NeedsApproval {
+                    reason: Some(self.reason(note)),
+                    proposed_execpolicy_amendment: None,
+                }
+            }
+            requirement => requirement,
+        }
+    }
+
+    /// Reason shown when the command is sent for approval after a sandbox
+    /// denial, where the requirement's own reason does not apply.
+    pub(crate) fn intent_reason(&self) -> String {
+        self.reason(None)
+    }
+
+    fn reason(&self, note: Option<String>) -> String {
+        let intent = format!("Intent ({}): {}", self.risk, self.intent);
+        match note {
+            Some(note) => format!("{note} {intent}"),
+            None => intent,
+        }
+    }
+}
+
+/// Risk level the safety classifier assigns to `command`.
+fn classify(command: &[String]) -> CommandRisk {
+    if command_might_be_dangerous(command) {
+        CommandRisk::Destructive
+    } else if is_known_safe_command(command) {
+        CommandRisk::ReadOnly
+    } else {
+        CommandRisk::Write
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn cmd(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    fn declaration(risk: CommandRisk) -> CommandDeclaration {
+        CommandDeclaration {
+            intent: "clean up the build".to_string(),
+            risk,
+        }
+    }
+
+    fn skip() -> ExecApprovalRequirement {
+        ExecApprovalRequirement::Skip {
+            bypass_sandbox: false,
+            proposed_execpolicy_amendment: None,
+        }
+    }
+
+    #[test]
+    fn requires_both_fields() {
+        assert_eq!(
+            CommandDeclaration::from_args(Some("list files".to_string()), None),
+            None
+        );
+        assert_eq!(
+            CommandDeclaration::from_args(Some("  ".to_string()), Some(CommandRisk::ReadOnly)),
+            None
+        );
+        assert_eq!(
+            CommandDeclaration::from_args(
+                Some(" list files ".to_string()),
+                Some(CommandRisk::ReadOnly)
+            ),
+            Some(CommandDeclaration {
+                intent: "list files".to_string(),
+                risk: CommandRisk::ReadOnly,
+            })
+        );
+    }
+
+    #[test]
+    fn understated_dangerous_command_needs_approval() {
+        let requirement = declaration(CommandRisk::Write).apply(
+            &cmd(&["rm", "-rf", "target"]),
+            AskForApproval::OnRequest,
+            skip(),
+        );
+        assert_eq!(
+            requirement,
+            ExecApprovalRequirement::NeedsApproval {
+                reason: Some(
+                    "Declared write, but this command looks destructive. Intent (write): clean up the build"
+                        .to_string()
+                ),
+                proposed_execpolicy_amendment: None,
+            }
+        );
+    }
+
+    #[test]
+    fn declared_destructive_needs_approval_unless_approvals_are_off() {
+        let command = cmd(&["cargo", "clean"]);
+        let requirement = declaration(CommandRisk::Destructive).apply(
+            &command,
+            AskForApproval::OnRequest,
+            skip(),
+        );
+        assert_eq!(
+            requirement,
+            ExecApprovalRequirement::NeedsApproval {
+                reason: Some("Intent (destructive): clean up the build".to_string()),
+                proposed_execpolicy_amendment: None,
+            }
+        );
+
+        let requirement =
+            declaration(CommandRisk::Destructive).apply(&command, AskForApproval::Never, skip());
+        assert_eq!(requirement, skip());
+    }
+
+    #[test]
+    fn low_declared_risk_never_skips_approval() {
+        let requirement = declaration(CommandRisk::ReadOnly).apply(
+            &cmd(&["cargo", "clean"]),
+            AskForApproval::UnlessTrusted,
+            ExecApprovalRequirement::NeedsApproval {
+                reason: None,
+                proposed_execpolicy_amendment: None,
+            },
+        );
+        assert_eq!(
+            requirement,
+            ExecApprovalRequirement::NeedsApproval {
+                reason: Some(
+                    "Declared read_only, but this command may change files. Intent (read_only): clean up the build"
+                        .to_string()
+                ),
+                proposed_execpolicy_amendment: None,
+            }
+        );
+
+        let requirement = declaration(CommandRisk::ReadOnly).apply(
+            &cmd(&["ls"]),
+            AskForApproval::OnRequest,
+            skip(),
+        );
+        assert_eq!(requirement, skip());
+    }
+}
@@ -14,8 +14,12 @@ pub fn requires_initial_appoval(
     sandbox_policy: &SandboxPolicy,
     command: &[String],
     sandbox_permissions: SandboxPermissions,
+    env_overridden: bool,
 ) -> bool {
-    if is_known_safe_command(command) {
+    // A known-safe command is only safe with the environment it normally
+    // gets; variables set by the call can make it load or run anything.
+    let known_safe = !env_overridden && is_known_safe_command(command);
+    if known_safe {
         return false;
     }
     match policy {
@@ -37,7 +41,7 @@ pub fn requires_initial_appoval(
             }
             command_might_be_dangerous(command)
         }
-        AskForApproval::UnlessTrusted => !is_known_safe_command(command),
+        AskForApproval::UnlessTrusted => !known_safe,
     }
 }
 
@@ -165,12 +169,33 @@ mod tests {
             &external_policy,
             &vec_str(&["ls"]),
             SandboxPermissions::UseDefault,
+            false,
         ));
         assert!(requires_initial_appoval(
             AskForApproval::OnRequest,
             &external_policy,
             &vec_str(&["rm", "-rf", "/"]),
             SandboxPermissions::UseDefault,
+            false,
+        ));
+    }
+
+    #[test]
+    fn env_overrides_disable_known_safe_auto_approval() {
+        let ls = vec_str(&["ls"]);
+        assert!(!requires_initial_appoval(
+            AskForApproval::UnlessTrusted,
+            &SandboxPolicy::ReadOnly,
+            &ls,
+            SandboxPermissions::UseDefault,
+            false,
+        ));
+        assert!(requires_initial_appoval(
+            AskForApproval::UnlessTrusted,
+            &SandboxPolicy::ReadOnly,
+            &ls,
+            SandboxPermissions::UseDefault,
+            true,
         ));
     }
 }
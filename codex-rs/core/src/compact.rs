@@ -9,8 +9,10 @@ use crate::codex::get_last_assistant_message_from_turn;
 use crate::error::CodexErr;
 use crate::error::Result as CodexResult;
 use crate::features::Feature;
+use crate::protocol::ApprovalPresetId;
 use crate::protocol::CompactedItem;
 use crate::protocol::ContextCompactedEvent;
+use crate::protocol::ContextTrimReason;
 use crate::protocol::EventMsg;
 use crate::protocol::TaskStartedEvent;
 use crate::protocol::TurnContextItem;
@@ -30,6 +32,7 @@ use tracing::error;
 
 pub const SUMMARIZATION_PROMPT: &str = include_str!("../templates/compact/prompt.md");
 pub const SUMMARY_PREFIX: &str = include_str!("../templates/compact/summary_prefix.md");
+pub const SESSION_SUMMARY_PROMPT: &str = include_str!("../templates/compact/session_summary.md");
 const COMPACT_USER_MESSAGE_MAX_TOKENS: usize = 20_000;
 
 pub(crate) fn should_use_remote_compact_task(
@@ -68,7 +71,10 @@ async fn run_compact_task_inner(
 ) {
     let initial_input_for_turn: ResponseInputItem = ResponseInputItem::from(input);
 
+    sess.drop_foreign_reasoning(&turn_context).await;
     let mut history = sess.clone_history().await;
+    let items_before = history.get_history_for_prompt().len();
+    let tokens_before = history.estimate_token_count(&turn_context);
     history.record_items(
         &[initial_input_for_turn.into()],
         turn_context.truncation_policy,
@@ -84,6 +90,11 @@ async fn run_compact_task_inner(
         approval_policy: turn_context.approval_policy,
         sandbox_policy: turn_context.sandbox_policy.clone(),
         model: turn_context.client.get_model(),
+        model_provider: Some(turn_context.client.config().model_provider_id.clone()),
+        approval_preset: ApprovalPresetId::matching(
+            turn_context.approval_policy,
+            &turn_context.sandbox_policy,
+        ),
         effort: turn_context.client.get_reasoning_effort(),
         summary: turn_context.client.get_reasoning_summary(),
     });
@@ -175,6 +186,14 @@ async fn run_compact_task_inner(
 
     let event = EventMsg::ContextCompacted(ContextCompactedEvent {});
     sess.send_event(&turn_context, event).await;
+    notify_compacted(
+        &sess,
+        &turn_context,
+        items_before,
+        tokens_before,
+        truncated_count,
+    )
+    .await;
 
     let warning = EventMsg::Warning(WarningEvent {
         message: "Heads up: Long conversations and multiple compactions can cause the model to be less accurate. Start a new conversation when possible to keep conversations small and targeted.".to_string(),
@@ -182,6 +201,90 @@ async fn run_compact_task_inner(
     sess.send_event(&turn_context, warning).await;
 }
 
+/// Tell the client how much of the conversation compaction replaced with a
+/// summary. `oldest_dropped` items did not make it into the summary.
+pub(crate) async fn notify_compacted(
+    sess: &Session,
+    turn_context: &TurnContext,
+    items_before: usize,
+    tokens_before: Option<i64>,
+    oldest_dropped: usize,
+) {
+    let tokens_before = tokens_before.unwrap_or_default();
+    let tokens_after = sess
+        .clone_history()
+        .await
+        .estimate_token_count(turn_context)
+        .unwrap_or_default();
+    sess.notify_context_trimmed(
+        turn_context,
+        ContextTrimReason::Compaction,
+        compaction_summary(items_before, tokens_before, tokens_after, oldest_dropped),
+        u64::try_from(tokens_before.saturating_sub(tokens_after)).unwrap_or_default(),
+    )
+    .await;
+}
+
+fn compaction_summary(
+    items_before: usize,
+    tokens_before: i64,
+    tokens_after: i64,
+    oldest_dropped: usize,
+) -> String {
+    let mut summary = format!(
+        "Compacted {items_before} history items (~{tokens_before} tokens) into a summary; the history is now ~{tokens_after} tokens."
+    );
+    if oldest_dropped > 0 {
+        summary.push_str(&format!(
+            " The {oldest_dropped} oldest item(s) were dropped before summarizing so the request fit the context window."
+        ));
+    }
+    summary
+}
+
+/// Summarize the session as a pull request description. The history is
+/// left as it is; like compaction, the oldest items are dropped from the
+/// request until it fits the context window.
+pub(crate) async fn summarize_session(
+    sess: &Session,
+    turn_context: &TurnContext,
+) -> CodexResult<String> {
+    let input = ResponseInputItem::from(vec![UserInput::Text {
+        text: SESSION_SUMMARY_PROMPT.to_string(),
+    }]);
+    let mut history = sess.clone_history().await;
+    history.record_items(&[input.into()], turn_context.truncation_policy);
+
+    let max_retries = turn_context.client.get_provider().stream_max_retries();
+    let mut retries = 0;
+    loop {
+        let turn_input = history.get_history_for_prompt();
+        let prompt = Prompt {
+            input: turn_input.clone(),
+            ..Default::default()
+        };
+        match drain_to_text(sess, turn_context, &prompt).await {
+            Ok(text) => return Ok(text),
+            Err(CodexErr::ContextWindowExceeded) if turn_input.len() > 1 => {
+                history.remove_first_item();
+                retries = 0;
+            }
+            Err(e @ (CodexErr::Interrupted | CodexErr::ContextWindowExceeded)) => return Err(e),
+            Err(e) if retries < max_retries => {
+                retries += 1;
+                sess.notify_stream_error(
+                    turn_context,
+                    format!("Reconnecting... {retries}/{max_retries}"),
+                    e,
+                )
+                .await;
+                tokio::time::sleep(backoff(retries)).await;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 pub fn content_items_to_text(content: &[ContentItem]) -> Option<String> {
     let mut pieces = Vec::new();
     for item in content {
@@ -318,12 +421,61 @@ async fn drain_to_completed(
     }
 }
 
+/// Like [`drain_to_completed`], but the response is returned as text
+/// instead of being recorded into the history.
+async fn drain_to_text(
+    sess: &Session,
+    turn_context: &TurnContext,
+    prompt: &Prompt,
+) -> CodexResult<String> {
+    let mut stream = turn_context.client.clone().stream(prompt).await?;
+    let mut messages = Vec::new();
+    loop {
+        let Some(event) = stream.next().await else {
+            return Err(CodexErr::Stream(
+                "stream closed before response.completed".into(),
+                None,
+            ));
+        };
+        match event {
+            Ok(ResponseEvent::OutputItemDone(ResponseItem::Message { role, content, .. }))
+                if role == "assistant" =>
+            {
+                messages.extend(content_items_to_text(&content));
+            }
+            Ok(ResponseEvent::RateLimits(snapshot)) => {
+                sess.update_rate_limits(turn_context, snapshot).await;
+            }
+            Ok(ResponseEvent::Completed { token_usage, .. }) => {
+                sess.update_token_usage_info(turn_context, token_usage.as_ref())
+                    .await;
+                return Ok(messages.join("\n\n"));
+            }
+            Ok(_) => continue,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn compaction_summary_mentions_dropped_items_only_when_there_are_some() {
+        assert_eq!(
+            compaction_summary(42, 90_000, 3_000, 0),
+            "Compacted 42 history items (~90000 tokens) into a summary; the history is now ~3000 tokens."
+        );
+        assert!(
+            compaction_summary(42, 90_000, 3_000, 2).ends_with(
+                " The 2 oldest item(s) were dropped before summarizing so the request fit the context window."
+            )
+        );
+    }
+
     #[test]
     fn content_items_to_text_joins_non_empty_segments() {
         let items = vec![
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use crate::Prompt;
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::compact::notify_compacted;
 use crate::error::Result as CodexResult;
 use crate::protocol::CompactedItem;
 use crate::protocol::ContextCompactedEvent;
@@ -40,12 +41,19 @@ async fn run_remote_compact_task_inner_impl(
     sess: &Arc<Session>,
     turn_context: &Arc<TurnContext>,
 ) -> CodexResult<()> {
+    sess.drop_foreign_reasoning(turn_context).await;
     let mut history = sess.clone_history().await;
+    let input = history.get_history_for_prompt();
+    let items_before = input.len();
+    let tokens_before = history.estimate_token_count(turn_context);
     let prompt = Prompt {
-        input: history.get_history_for_prompt(),
+        input,
         tools: vec![],
         parallel_tool_calls: false,
         base_instructions_override: turn_context.base_instructions.clone(),
+        locale_instructions: turn_context.locale_instructions.clone(),
+        model_instructions: turn_context.model_instructions.clone(),
+        composed_instructions: None,
         output_schema: None,
     };
 
@@ -76,6 +84,7 @@ async fn run_remote_compact_task_inner_impl(
 
     let event = EventMsg::ContextCompacted(ContextCompactedEvent {});
     sess.send_event(turn_context, event).await;
+    notify_compacted(sess, turn_context, items_before, tokens_before, 0).await;
 
     Ok(())
 }
@@ -20,6 +20,33 @@ impl ConstraintError {
         }
     }
 
+    /// `field` is fixed by an administrator's requirements and `candidate`
+    /// is not one of the values they allow.
+    pub fn locked(
+        field_name: impl Into<String>,
+        candidate: impl Into<String>,
+        allowed: impl Into<String>,
+    ) -> Self {
+        Self {
+            message: format!(
+                "`{}` is locked by your administrator: `{}` is not allowed (allowed: {})",
+                field_name.into(),
+                candidate.into(),
+                allowed.into()
+            ),
+        }
+    }
+
+    /// `field` may only be set by an administrator's requirements.
+    pub fn redefined(field_name: impl Into<String>) -> Self {
+        Self {
+            message: format!(
+                "`{}` is locked by your administrator and cannot be set in config.toml",
+                field_name.into()
+            ),
+        }
+    }
+
     pub fn empty_field(field_name: impl Into<String>) -> Self {
         Self {
             message: format!("field `{}` cannot be empty", field_name.into()),
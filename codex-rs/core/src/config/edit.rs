@@ -1,7 +1,9 @@
 use crate::config::CONFIG_TOML_FILE;
 use crate::config::types::McpServerConfig;
 use crate::config::types::Notice;
+use crate::file_lock::lock_file;
 use anyhow::Context;
+use codex_protocol::config_types::ReasoningDisplay;
 use codex_protocol::config_types::TrustLevel;
 use codex_protocol::openai_models::ReasoningEffort;
 use std::collections::BTreeMap;
@@ -22,6 +24,8 @@ pub enum ConfigEdit {
         model: Option<String>,
         effort: Option<ReasoningEffort>,
     },
+    /// Update the active (or default) reasoning display preference.
+    SetReasoningDisplay(ReasoningDisplay),
     /// Toggle the acknowledgement flag under `[notice]`.
     SetNoticeHideFullAccessWarning(bool),
     /// Toggle the Windows world-writable directories warning acknowledgement flag.
@@ -265,6 +269,18 @@ impl ConfigDocument {
                 );
                 mutated
             }),
+            ConfigEdit::SetReasoningDisplay(display) => Ok({
+                let mut mutated = false;
+                mutated |= self.write_profile_value(
+                    &["hide_agent_reasoning"],
+                    Some(value(display.hides_reasoning())),
+                );
+                mutated |= self.write_profile_value(
+                    &["show_raw_agent_reasoning"],
+                    Some(value(display.shows_raw_reasoning())),
+                );
+                mutated
+            }),
             ConfigEdit::SetNoticeHideFullAccessWarning(acknowledged) => Ok(self.write_value(
                 Scope::Global,
                 &[Notice::TABLE_KEY, "hide_full_access_warning"],
@@ -505,6 +521,10 @@ pub fn apply_blocking(
     }
 
     let config_path = codex_home.join(CONFIG_TOML_FILE);
+    // Held until the new file is in place so edits made by other processes
+    // in the meantime are not overwritten.
+    let _lock = lock_file(&config_path)
+        .with_context(|| format!("failed to lock config file at {}", config_path.display()))?;
     let serialized = match std::fs::read_to_string(&config_path) {
         Ok(contents) => contents,
         Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
@@ -596,6 +616,11 @@ impl ConfigEditsBuilder {
         self
     }
 
+    pub fn set_reasoning_display(mut self, display: ReasoningDisplay) -> Self {
+        self.edits.push(ConfigEdit::SetReasoningDisplay(display));
+        self
+    }
+
     pub fn set_hide_full_access_warning(mut self, acknowledged: bool) -> Self {
         self.edits
             .push(ConfigEdit::SetNoticeHideFullAccessWarning(acknowledged));
@@ -946,6 +971,35 @@ model = "o4-mini"
         assert_eq!(contents, expected);
     }
 
+    #[test]
+    fn blocking_set_reasoning_display_with_explicit_profile() {
+        let tmp = tempdir().expect("tmpdir");
+        let codex_home = tmp.path();
+        std::fs::write(
+            codex_home.join(CONFIG_TOML_FILE),
+            r#"[profiles.team]
+model = "gpt-5.1-codex"
+"#,
+        )
+        .expect("seed");
+
+        apply_blocking(
+            codex_home,
+            Some("team"),
+            &[ConfigEdit::SetReasoningDisplay(ReasoningDisplay::Raw)],
+        )
+        .expect("persist");
+
+        let contents =
+            std::fs::read_to_string(codex_home.join(CONFIG_TOML_FILE)).expect("read config");
+        let expected = r#"[profiles.team]
+model = "gpt-5.1-codex"
+hide_agent_reasoning = false
+show_raw_agent_reasoning = true
+"#;
+        assert_eq!(contents, expected);
+    }
+
     #[test]
     fn blocking_set_hide_full_access_warning_preserves_table() {
         let tmp = tempdir().expect("tmpdir");
@@ -1,19 +1,38 @@
 use crate::auth::AuthCredentialsStoreMode;
+use crate::config::types::AccessibilityMode;
+use crate::config::types::BestOf;
 use crate::config::types::DEFAULT_OTEL_ENVIRONMENT;
 use crate::config::types::History;
+use crate::config::types::HistoryFilter;
+use crate::config::types::ImageProtocolPreference;
+use crate::config::types::IssueTrackerConfig;
+use crate::config::types::Logging;
 use crate::config::types::McpServerConfig;
+use crate::config::types::ModelProviderOverrides;
 use crate::config::types::Notice;
 use crate::config::types::Notifications;
+use crate::config::types::NotifySink;
 use crate::config::types::OtelConfig;
 use crate::config::types::OtelConfigToml;
 use crate::config::types::OtelExporterKind;
+use crate::config::types::PatchCritic;
+use crate::config::types::PatchCriticMode;
+use crate::config::types::PatchPreview;
 use crate::config::types::SandboxWorkspaceWrite;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyToml;
+use crate::config::types::StallDetection;
+use crate::config::types::SubAgentLimits;
+use crate::config::types::Telemetry;
 use crate::config::types::Tui;
+use crate::config::types::UpdateChannel;
 use crate::config::types::UriBasedFileOpener;
+use crate::config::types::Workflow;
+use crate::config::types::WorkflowStep;
+use crate::config::types::WorkflowToml;
 use crate::config_loader::ConfigRequirements;
 use crate::config_loader::LoaderOverrides;
+use crate::config_loader::SandboxRequirements;
 use crate::config_loader::load_config_layers_state;
 use crate::features::Feature;
 use crate::features::FeatureOverrides;
@@ -26,6 +45,10 @@ use crate::model_provider_info::OLLAMA_OSS_PROVIDER_ID;
 use crate::model_provider_info::built_in_model_providers;
 use crate::project_doc::DEFAULT_PROJECT_DOC_FILENAME;
 use crate::project_doc::LOCAL_PROJECT_DOC_FILENAME;
+use crate::project_instructions::PROJECT_INSTRUCTIONS_MAX_BYTES;
+use crate::project_instructions::ProjectInstructions;
+use crate::project_instructions::ProjectInstructionsMode;
+use crate::project_instructions::resolve_project_instructions;
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
 use codex_app_server_protocol::Tools;
@@ -54,6 +77,7 @@ use tempfile::tempdir;
 use crate::config::profile::ConfigProfile;
 use toml::Value as TomlValue;
 use toml_edit::DocumentMut;
+use wildmatch::WildMatchPattern;
 
 mod constraint;
 pub mod edit;
@@ -76,6 +100,9 @@ pub use codex_git::GhostSnapshotConfig;
 /// the context window.
 pub(crate) const PROJECT_DOC_MAX_BYTES: usize = 32 * 1024; // 32 KiB
 
+/// Upper bound on `best_of.candidates`; each candidate is a full request.
+const MAX_BEST_OF_CANDIDATES: usize = 8;
+
 pub const CONFIG_TOML_FILE: &str = "config.toml";
 
 #[cfg(test)]
@@ -98,6 +125,10 @@ pub struct Config {
     /// Model used specifically for review sessions. Defaults to "gpt-5.1-codex-max".
     pub review_model: String,
 
+    /// Model for the chunk passes of the `summarize_file` tool. Defaults to
+    /// the session model.
+    pub summarize_file_model: Option<String>,
+
     /// Size of the context window for the model, in tokens.
     pub model_context_window: Option<i64>,
 
@@ -115,6 +146,10 @@ pub struct Config {
 
     pub sandbox_policy: SandboxPolicy,
 
+    /// Sandbox settings locked by an administrator; `sandbox_policy` must
+    /// satisfy them whenever it changes.
+    pub sandbox_requirements: SandboxRequirements,
+
     /// True if the user passed in an override or set a value in config.toml
     /// for either of approval_policy or sandbox_mode.
     pub did_user_set_custom_approval_policy_or_sandbox_mode: bool,
@@ -146,6 +181,14 @@ pub struct Config {
     /// Compact prompt override.
     pub compact_prompt: Option<String>,
 
+    /// Language the model should respond in, as a language tag such as `ja`
+    /// or `pt-BR`. Also selects the TUI's interface language when available.
+    pub language: Option<String>,
+
+    /// IANA timezone (e.g. `Asia/Tokyo`) used when the model reasons about
+    /// dates and times.
+    pub timezone: Option<String>,
+
     /// Optional external notifier command. When set, Codex will spawn this
     /// program after each completed *turn* (i.e. when the agent finishes
     /// processing a user submission). The value must be the full command
@@ -168,6 +211,10 @@ pub struct Config {
     /// If unset the feature is disabled.
     pub notify: Option<Vec<String>>,
 
+    /// Sockets, named pipes, and commands that receive every notification
+    /// (turn completions and approval requests) as a line of JSON.
+    pub notify_sinks: Vec<NotifySink>,
+
     /// TUI notifications preference. When set, the TUI will send OSC 9 notifications on approvals
     /// and turn completions when not focused.
     pub tui_notifications: Notifications,
@@ -178,11 +225,25 @@ pub struct Config {
     /// Show startup tooltips in the TUI welcome screen.
     pub show_tooltips: bool,
 
+    /// Accessibility adaptations for the TUI.
+    pub tui_accessibility: AccessibilityMode,
+
+    /// File the TUI mirrors a plain-text transcript into, if any.
+    pub tui_transcript_file: Option<PathBuf>,
+
+    /// Terminal graphics protocol preference for inline images in the TUI.
+    pub tui_image_protocol: ImageProtocolPreference,
+
     /// The directory that should be treated as the current working directory
     /// for the session. All relative paths inside the business-logic layer are
     /// resolved against this path.
     pub cwd: PathBuf,
 
+    /// Directories that belong to the session's workspace besides `cwd`, such
+    /// as sibling repositories. Absolute, deduplicated, and never equal to
+    /// `cwd`. Use [`Config::workspace_roots`] to iterate over every root.
+    pub additional_workspace_roots: Vec<PathBuf>,
+
     /// Preferred store for CLI auth credentials.
     /// file (default): Use a file in the Codex home directory.
     /// keyring: Use an OS-specific keyring service.
@@ -214,6 +275,38 @@ pub struct Config {
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
 
+    /// Approximate token budget for the tool descriptions sent with each
+    /// request. `None` sends them in full.
+    pub tool_description_budget: Option<usize>,
+
+    /// Sample several responses for each user turn and continue with one.
+    /// `None` samples a single response.
+    pub best_of: Option<BestOf>,
+
+    /// Review pending patches with a separate model request. `None` applies
+    /// patches without a review.
+    pub patch_critic: Option<PatchCritic>,
+
+    /// Checks run against a copy of the repository with a pending patch
+    /// applied. `None` applies patches without trying them first.
+    pub patch_preview: Option<PatchPreview>,
+
+    /// Report turns that produce no events for a while. `None` never
+    /// reports them.
+    pub stall_detection: Option<StallDetection>,
+
+    /// Which history items are replayed to the model.
+    pub history_filter: HistoryFilter,
+
+    /// Local usage stats and which categories may be shared.
+    pub telemetry: Telemetry,
+
+    /// Limits on the sub-agents a session spawns.
+    pub sub_agents: SubAgentLimits,
+
+    /// Named multi-step workflows, run with `codex run <name>`.
+    pub workflows: BTreeMap<String, Workflow>,
+
     /// Directory containing all Codex state (defaults to `~/.codex` but can be
     /// overridden by the `CODEX_HOME` environment variable).
     pub codex_home: PathBuf,
@@ -221,6 +314,9 @@ pub struct Config {
     /// Settings that govern if and what will be written to `~/.codex/history.jsonl`.
     pub history: History,
 
+    /// Filter and rotation settings for the TUI log file.
+    pub log: Logging,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: UriBasedFileOpener,
@@ -276,6 +372,39 @@ pub struct Config {
     /// Settings for ghost snapshots (used for undo).
     pub ghost_snapshot: GhostSnapshotConfig,
 
+    /// Gitignore-style patterns for paths that must never be read into model
+    /// context. Combined with any `.codexignore` files.
+    pub context_ignore: Vec<String>,
+
+    /// Paths that patches may not change without approval, or at all.
+    pub protected_paths: ProtectedPathsToml,
+
+    /// Provider, API URL and token source for the `issue_tracker` tool.
+    pub issue_tracker: IssueTrackerConfig,
+
+    /// Settings for the per-session scratch directory.
+    pub scratch_dir: ScratchDirToml,
+
+    /// Whether memories are saved and sent, and how many.
+    pub memories: MemoriesToml,
+
+    /// Path translation between Windows and WSL, when Codex runs in or
+    /// against WSL. `execution_side` is always set once resolved.
+    pub wsl: Option<WslToml>,
+
+    /// Ordered sections of the model instructions, when `[prompt_sections]`
+    /// is set. `None` keeps the default layout.
+    pub prompt_sections: Option<Vec<PromptSection>>,
+
+    /// Extra instructions for particular models or providers, in the order
+    /// they were configured.
+    pub model_instructions: Vec<ModelInstructions>,
+
+    /// The repository's `.codex/instructions.md`, when it applies. Appended
+    /// instructions are also the last entry of `model_instructions`, and
+    /// replacing ones are `base_instructions`.
+    pub project_instructions: Option<ProjectInstructions>,
+
     /// Centralized feature flags; source of truth for feature gating.
     pub features: Features,
 
@@ -297,6 +426,9 @@ pub struct Config {
     /// Defaults to `true`.
     pub check_for_update_on_startup: bool,
 
+    /// Release channel followed by `codex self-update` and the update notice.
+    pub update_channel: UpdateChannel,
+
     /// When true, disables burst-paste detection for typed input entirely.
     /// All characters are inserted as they are received, and no buffering
     /// or placeholder replacement will occur for fast keypress bursts.
@@ -606,6 +738,8 @@ pub struct ConfigToml {
     pub model: Option<String>,
     /// Review model override used by the `/review` feature.
     pub review_model: Option<String>,
+    /// Model for the chunk passes of the `summarize_file` tool.
+    pub summarize_file_model: Option<String>,
 
     /// Provider to use from the model_providers map.
     pub model_provider: Option<String>,
@@ -628,10 +762,18 @@ pub struct ConfigToml {
     /// Sandbox configuration to apply if `sandbox` is `WorkspaceWrite`.
     pub sandbox_workspace_write: Option<SandboxWorkspaceWrite>,
 
+    /// Additional workspace roots, resolved against the session's cwd. Each
+    /// root is writable under `workspace-write` and contributes its own
+    /// project docs, memories, and git metadata.
+    pub workspace_roots: Option<Vec<PathBuf>>,
+
     /// Optional external command to spawn for end-user notifications.
     #[serde(default)]
     pub notify: Option<Vec<String>>,
 
+    /// Other destinations for notifications.
+    pub notify_sinks: Option<Vec<NotifySink>>,
+
     /// System instructions.
     pub instructions: Option<String>,
 
@@ -642,6 +784,12 @@ pub struct ConfigToml {
     /// Compact prompt used for history compaction.
     pub compact_prompt: Option<String>,
 
+    /// Preferred response language, e.g. `ja` or `pt-BR`.
+    pub language: Option<String>,
+
+    /// IANA timezone name, e.g. `Asia/Tokyo`.
+    pub timezone: Option<String>,
+
     /// When set, restricts ChatGPT login to a specific workspace identifier.
     #[serde(default)]
     pub forced_chatgpt_workspace_id: Option<String>,
@@ -673,15 +821,50 @@ pub struct ConfigToml {
     #[serde(default)]
     pub model_providers: HashMap<String, ModelProviderInfo>,
 
+    /// Network tuning for providers by id, built-in ones included.
+    #[serde(default)]
+    pub model_provider_overrides: HashMap<String, ModelProviderOverrides>,
+
     /// Maximum number of bytes to include from an AGENTS.md project doc file.
     pub project_doc_max_bytes: Option<usize>,
 
+    /// Largest `.codex/instructions.md` accepted; 0 ignores the file.
+    pub project_instructions_max_bytes: Option<usize>,
+
     /// Ordered list of fallback filenames to look for when AGENTS.md is missing.
     pub project_doc_fallback_filenames: Option<Vec<String>>,
 
     /// Token budget applied when storing tool/function outputs in the context manager.
     pub tool_output_token_limit: Option<usize>,
 
+    /// Approximate token budget for tool descriptions sent with each request.
+    pub tool_description_budget: Option<usize>,
+
+    /// Sample several responses for each user turn and continue with one.
+    pub best_of: Option<BestOf>,
+
+    /// Review pending patches with a separate model request.
+    pub patch_critic: Option<PatchCritic>,
+
+    /// Try pending patches in a copy of the repository first.
+    pub patch_preview: Option<PatchPreview>,
+
+    /// Report turns that produce no events for a while.
+    pub stall_detection: Option<StallDetection>,
+
+    /// Which history items are replayed to the model.
+    pub history_filter: Option<HistoryFilter>,
+
+    /// Local usage stats and opt-in sharing.
+    pub telemetry: Option<Telemetry>,
+
+    /// Limits on the sub-agents a session spawns.
+    pub sub_agents: Option<SubAgentLimits>,
+
+    /// Named multi-step workflows.
+    #[serde(default)]
+    pub workflows: BTreeMap<String, WorkflowToml>,
+
     /// Profile to use from the `profiles` map.
     pub profile: Option<String>,
 
@@ -693,6 +876,10 @@ pub struct ConfigToml {
     #[serde(default)]
     pub history: Option<History>,
 
+    /// Filter and rotation settings for the TUI log file.
+    #[serde(default)]
+    pub log: Option<Logging>,
+
     /// Optional URI-based file opener. If set, citations to files in the model
     /// output will be hyperlinked using the specified URI scheme.
     pub file_opener: Option<UriBasedFileOpener>,
@@ -735,11 +922,47 @@ pub struct ConfigToml {
     #[serde(default)]
     pub ghost_snapshot: Option<GhostSnapshotToml>,
 
+    /// Rules for excluding paths from model context.
+    #[serde(default)]
+    pub context: Option<ContextToml>,
+
+    /// Paths the model may not edit without approval, or at all.
+    #[serde(default)]
+    pub protected_paths: Option<ProtectedPathsToml>,
+
+    /// Settings for the `issue_tracker` tool.
+    #[serde(default)]
+    pub issue_tracker: Option<IssueTrackerConfig>,
+
+    /// Settings for the per-session scratch directory.
+    #[serde(default)]
+    pub scratch_dir: Option<ScratchDirToml>,
+
+    /// Switch and limits for saved memories.
+    #[serde(default)]
+    pub memories: Option<MemoriesToml>,
+
+    /// Windows/WSL path translation settings.
+    #[serde(default)]
+    pub wsl: Option<WslToml>,
+
+    /// Order, selection, and extra files for the model instructions.
+    #[serde(default)]
+    pub prompt_sections: Option<PromptSectionsToml>,
+
+    /// Extra instructions for particular models or providers.
+    #[serde(default)]
+    pub model_instructions: Option<Vec<ModelInstructionsToml>>,
+
     /// When `true`, checks for Codex updates on startup and surfaces update prompts.
     /// Set to `false` only if your Codex updates are centrally managed.
     /// Defaults to `true`.
     pub check_for_update_on_startup: Option<bool>,
 
+    /// Release channel followed by `codex self-update` and the update notice.
+    /// Defaults to `stable`.
+    pub update_channel: Option<UpdateChannel>,
+
     /// When true, disables burst-paste detection for typed input entirely.
     /// All characters are inserted as they are received, and no buffering
     /// or placeholder replacement will occur for fast keypress bursts.
@@ -824,6 +1047,342 @@ impl From<ToolsToml> for Tools {
     }
 }
 
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContextToml {
+    /// Gitignore-style patterns for paths that are never read into context.
+    pub ignore: Option<Vec<String>>,
+}
+
+/// Gitignore-style patterns for paths that patches may not change freely.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProtectedPathsToml {
+    /// Patches touching these paths always need explicit approval.
+    #[serde(default)]
+    pub require_approval: Vec<String>,
+    /// Patches touching these paths are always refused.
+    #[serde(default)]
+    pub block: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScratchDirToml {
+    /// Keep the scratch directory after a session that hit an error, for
+    /// debugging. It is always removed after a clean session.
+    #[serde(default)]
+    pub keep_on_error: bool,
+}
+
+/// When all saved memories fit `token_budget` and `max_entries` they go
+/// into the instructions. Otherwise each turn gets the memories most
+/// relevant to its input, within the same limits.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct MemoriesToml {
+    /// When false, no memories are extracted from conversations and saved
+    /// ones are not sent to the model.
+    #[serde(default = "default_memories_enabled")]
+    pub enabled: bool,
+    /// Approximate tokens the memories may take.
+    #[serde(default = "default_memories_token_budget")]
+    pub token_budget: usize,
+    /// Most memories sent at once.
+    #[serde(default = "default_memories_max_entries")]
+    pub max_entries: usize,
+    /// Where memories are kept.
+    #[serde(default)]
+    pub backend: MemoryBackend,
+    /// Bytes read from the end of each memories file. The SQLite backend
+    /// reads every entry.
+    #[serde(default = "default_memories_max_file_bytes")]
+    pub max_file_bytes: usize,
+    /// Most memories extracted from one turn.
+    #[serde(default = "default_memories_max_new_per_turn")]
+    pub max_new_per_turn: usize,
+    /// Bytes of the user's input given to the extraction request, and with
+    /// `include_turn_output` of the assistant's message and of the tool
+    /// results each.
+    #[serde(default = "default_memories_extraction_input_bytes")]
+    pub extraction_input_bytes: usize,
+    /// Also give the extraction request the assistant's last message and
+    /// the tool results of the turn, so facts the agent found out, not only
+    /// what the user said, can be remembered.
+    #[serde(default)]
+    pub include_turn_output: bool,
+    /// Seconds the user has to review the memories extracted from a turn
+    /// before all of them are saved. 0 saves them without asking.
+    #[serde(default = "default_memories_auto_accept_secs")]
+    pub auto_accept_secs: u64,
+    /// When set, extracted memories whose embedding has at least this cosine
+    /// similarity to a saved memory, or to another memory from the same
+    /// turn, are not saved.
+    #[serde(default)]
+    pub similarity_threshold: Option<f32>,
+    /// Tag memories extracted from a session with the checked-out branch and
+    /// only send tagged memories on their branch. Untagged memories are
+    /// sent on every branch.
+    #[serde(default)]
+    pub scope_by_branch: bool,
+    /// Categories of the memories that are sent, e.g. `["build", "infra"]`.
+    /// Memories without a category are always sent; empty sends all.
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// What happens to extracted memories that look like they hold a
+    /// credential.
+    #[serde(default)]
+    pub redaction: MemoryRedaction,
+    /// Regexes for secrets in formats the built-in checks miss, e.g. an
+    /// internal token prefix.
+    #[serde(default)]
+    pub redaction_patterns: Vec<String>,
+}
+
+impl Default for MemoriesToml {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            token_budget: default_memories_token_budget(),
+            max_entries: default_memories_max_entries(),
+            backend: MemoryBackend::default(),
+            max_file_bytes: default_memories_max_file_bytes(),
+            max_new_per_turn: default_memories_max_new_per_turn(),
+            extraction_input_bytes: default_memories_extraction_input_bytes(),
+            include_turn_output: false,
+            auto_accept_secs: default_memories_auto_accept_secs(),
+            similarity_threshold: None,
+            scope_by_branch: false,
+            categories: Vec::new(),
+            redaction: MemoryRedaction::default(),
+            redaction_patterns: Vec::new(),
+        }
+    }
+}
+
+/// Storage for saved memories, in `$CODEX_HOME` and each repository's
+/// `.codex` directory.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryBackend {
+    /// `memories.md`, a Markdown list that can be edited by hand.
+    #[default]
+    File,
+    /// `memories.sqlite`, which keeps every entry with its metadata.
+    Sqlite,
+}
+
+/// Handling of extracted memories in which a credential was found, by known
+/// key formats or by how random a token looks.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryRedaction {
+    /// Save the memory with `[REDACTED]` in place of the credential.
+    #[default]
+    Scrub,
+    /// Drop the memory.
+    Reject,
+    /// Save the memory as extracted.
+    Off,
+}
+
+const fn default_memories_enabled() -> bool {
+    true
+}
+
+const fn default_memories_token_budget() -> usize {
+    1_000
+}
+
+const fn default_memories_max_entries() -> usize {
+    20
+}
+
+const fn default_memories_max_file_bytes() -> usize {
+    8 * 1024
+}
+
+const fn default_memories_max_new_per_turn() -> usize {
+    6
+}
+
+const fn default_memories_extraction_input_bytes() -> usize {
+    2_000
+}
+
+const fn default_memories_auto_accept_secs() -> u64 {
+    60
+}
+
+/// Which side of a Windows/WSL setup runs commands.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum WslExecutionSide {
+    Windows,
+    Wsl,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct WslToml {
+    /// Where commands run. Defaults to the side Codex itself runs on.
+    pub execution_side: Option<WslExecutionSide>,
+    /// WSL distribution for `\\wsl.localhost\<distro>` paths and `wsl.exe -d`.
+    /// Detected from `WSL_DISTRO_NAME` or the working directory when unset.
+    pub distro: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct PromptSectionsToml {
+    /// Section names in the order they are sent. Built-in sections that are
+    /// left out are not sent at all.
+    pub order: Vec<String>,
+    /// Extra sections, by name, read from these files. Names must not clash
+    /// with the built-in sections.
+    #[serde(default)]
+    pub files: HashMap<String, AbsolutePathBuf>,
+}
+
+/// One section of the model instructions, resolved from `[prompt_sections]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PromptSection {
+    /// The model's base instructions, or `experimental_instructions_file`.
+    Base,
+    /// A summary of the sandbox and approval policy.
+    Permissions,
+    /// `instructions` from config, AGENTS.md files, and skills.
+    ProjectDocs,
+    /// Saved memories.
+    Memories,
+    /// Working directory, policies, and shell, as in the environment context.
+    Environment,
+    /// A user-provided file.
+    File { name: String, path: PathBuf },
+}
+
+impl PromptSection {
+    pub fn name(&self) -> &str {
+        match self {
+            PromptSection::Base => "base",
+            PromptSection::Permissions => "permissions",
+            PromptSection::ProjectDocs => "project_docs",
+            PromptSection::Memories => "memories",
+            PromptSection::Environment => "environment",
+            PromptSection::File { name, .. } => name,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelInstructionsToml {
+    /// Glob patterns (`*`, `?`) for the model slugs this entry applies to.
+    /// Matches every model when empty.
+    #[serde(default)]
+    pub models: Vec<String>,
+    /// Provider ids this entry applies to. Matches every provider when empty.
+    #[serde(default)]
+    pub providers: Vec<String>,
+    /// The instructions to add.
+    pub instructions: Option<String>,
+    /// A file to read the instructions from, instead of `instructions`.
+    pub file: Option<AbsolutePathBuf>,
+}
+
+/// Instructions added after the base instructions when the active model and
+/// provider match.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelInstructions {
+    pub models: Vec<String>,
+    pub providers: Vec<String>,
+    pub instructions: String,
+}
+
+impl ModelInstructions {
+    pub fn applies_to(&self, model: &str, provider_id: &str) -> bool {
+        let model_matches = self.models.is_empty()
+            || self.models.iter().any(|pattern| {
+                WildMatchPattern::<'*', '?'>::new_case_insensitive(pattern).matches(model)
+            });
+        let provider_matches =
+            self.providers.is_empty() || self.providers.iter().any(|id| id == provider_id);
+        model_matches && provider_matches
+    }
+}
+
+fn resolve_model_instructions(
+    entries: Vec<ModelInstructionsToml>,
+) -> std::io::Result<Vec<ModelInstructions>> {
+    entries
+        .into_iter()
+        .map(|entry| {
+            let instructions = match (entry.instructions, entry.file) {
+                (Some(instructions), None) => instructions,
+                (None, Some(file)) => {
+                    Config::try_read_non_empty_file(Some(&file), "model instructions file")?
+                        .unwrap_or_default()
+                }
+                _ => {
+                    return Err(std::io::Error::new(
+                        ErrorKind::InvalidData,
+                        "each model_instructions entry needs exactly one of `instructions` or `file`",
+                    ));
+                }
+            };
+            Ok(ModelInstructions {
+                models: entry.models,
+                providers: entry.providers,
+                instructions,
+            })
+        })
+        .collect()
+}
+
+fn resolve_prompt_sections(
+    toml: Option<PromptSectionsToml>,
+) -> std::io::Result<Option<Vec<PromptSection>>> {
+    let Some(PromptSectionsToml { order, mut files }) = toml else {
+        return Ok(None);
+    };
+    let invalid = |message: String| std::io::Error::new(ErrorKind::InvalidData, message);
+
+    let builtin = [
+        PromptSection::Base,
+        PromptSection::Permissions,
+        PromptSection::ProjectDocs,
+        PromptSection::Memories,
+        PromptSection::Environment,
+    ];
+    if let Some(section) = builtin
+        .iter()
+        .find(|section| files.contains_key(section.name()))
+    {
+        return Err(invalid(format!(
+            "prompt_sections.files: `{}` is a built-in section",
+            section.name()
+        )));
+    }
+
+    let mut sections: Vec<PromptSection> = Vec::with_capacity(order.len());
+    for name in order {
+        if sections.iter().any(|section| section.name() == name) {
+            return Err(invalid(format!(
+                "prompt_sections.order lists `{name}` more than once"
+            )));
+        }
+        let section = match builtin.iter().find(|section| section.name() == name) {
+            Some(section) => section.clone(),
+            None => match files.remove(&name) {
+                Some(path) => PromptSection::File {
+                    name,
+                    path: path.into_path_buf(),
+                },
+                None => {
+                    return Err(invalid(format!(
+                        "prompt_sections.order: unknown section `{name}`; add it under prompt_sections.files"
+                    )));
+                }
+            },
+        };
+        sections.push(section);
+    }
+    Ok(Some(sections))
+}
+
 #[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
 pub struct GhostSnapshotToml {
     /// Exclude untracked files larger than this many bytes from ghost snapshots.
@@ -873,8 +1432,13 @@ impl ConfigToml {
                     network_access,
                     exclude_tmpdir_env_var,
                     exclude_slash_tmp,
+                    allowed_symlink_targets,
                 }) => SandboxPolicy::WorkspaceWrite {
-                    writable_roots: writable_roots.clone(),
+                    writable_roots: writable_roots
+                        .iter()
+                        .chain(allowed_symlink_targets)
+                        .cloned()
+                        .collect(),
                     network_access: *network_access,
                     exclude_tmpdir_env_var: *exclude_tmpdir_env_var,
                     exclude_slash_tmp: *exclude_slash_tmp,
@@ -1082,18 +1646,51 @@ impl Config {
                 }
             }
         };
-        let additional_writable_roots: Vec<AbsolutePathBuf> = additional_writable_roots
-            .into_iter()
+        let additional_writable_roots: Vec<AbsolutePathBuf> = cfg
+            .workspace_roots
+            .iter()
+            .flatten()
+            .cloned()
+            .chain(additional_writable_roots)
             .map(|path| AbsolutePathBuf::resolve_path_against_base(path, &resolved_cwd))
             .collect::<Result<Vec<_>, _>>()?;
+        let mut additional_workspace_roots: Vec<PathBuf> = Vec::new();
+        for root in &additional_writable_roots {
+            let root = root.to_path_buf();
+            if root != resolved_cwd && !additional_workspace_roots.contains(&root) {
+                additional_workspace_roots.push(root);
+            }
+        }
         let active_project = cfg
             .get_active_project(&resolved_cwd)
             .unwrap_or(ProjectConfig { trust_level: None });
 
+        let constraint_error = |e: ConstraintError| {
+            std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}"))
+        };
+        let mut resolution =
+            cfg.derive_sandbox_policy(sandbox_mode, config_profile.sandbox_mode, &resolved_cwd);
+        if let Err(err) = requirements.sandbox.check(&resolution.policy) {
+            let user_sandbox_mode = sandbox_mode
+                .or(config_profile.sandbox_mode)
+                .or(cfg.sandbox_mode);
+            // Only the default gives way to the administrator's requirements;
+            // a mode the user asked for is reported instead.
+            match (user_sandbox_mode, requirements.sandbox.fallback_mode()) {
+                (None, Some(mode)) => {
+                    resolution = cfg.derive_sandbox_policy(Some(mode), None, &resolved_cwd);
+                    requirements
+                        .sandbox
+                        .check(&resolution.policy)
+                        .map_err(constraint_error)?;
+                }
+                _ => return Err(constraint_error(err)),
+            }
+        }
         let SandboxPolicyResolution {
             policy: mut sandbox_policy,
             forced_auto_mode_downgraded_on_windows,
-        } = cfg.derive_sandbox_policy(sandbox_mode, config_profile.sandbox_mode, &resolved_cwd);
+        } = resolution;
         if let SandboxPolicy::WorkspaceWrite { writable_roots, .. } = &mut sandbox_policy {
             for path in additional_writable_roots {
                 if !writable_roots.iter().any(|existing| existing == &path) {
@@ -1123,16 +1720,38 @@ impl Config {
             || config_profile.sandbox_mode.is_some()
             || cfg.sandbox_mode.is_some();
 
+        requirements
+            .check_model_provider_definitions(cfg.model_providers.keys())
+            .map_err(constraint_error)?;
         let mut model_providers = built_in_model_providers();
         // Merge user-defined providers into the built-in list.
         for (key, provider) in cfg.model_providers.into_iter() {
             model_providers.entry(key).or_insert(provider);
         }
+        // Providers from the requirements replace any of the same ID.
+        model_providers.extend(requirements.model_providers.clone());
+        for (key, overrides) in &cfg.model_provider_overrides {
+            let Some(provider) = model_providers.get_mut(key) else {
+                continue;
+            };
+            let ModelProviderOverrides {
+                request_max_retries,
+                stream_max_retries,
+                stream_idle_timeout_ms,
+            } = *overrides;
+            provider.request_max_retries = request_max_retries.or(provider.request_max_retries);
+            provider.stream_max_retries = stream_max_retries.or(provider.stream_max_retries);
+            provider.stream_idle_timeout_ms =
+                stream_idle_timeout_ms.or(provider.stream_idle_timeout_ms);
+        }
 
         let model_provider_id = model_provider
             .or(config_profile.model_provider)
             .or(cfg.model_provider)
             .unwrap_or_else(|| "openai".to_string());
+        requirements
+            .check_model_provider(&model_provider_id)
+            .map_err(constraint_error)?;
         let model_provider = model_providers
             .get(&model_provider_id)
             .ok_or_else(|| {
@@ -1223,6 +1842,7 @@ impl Config {
             "experimental compact prompt file",
         )?;
         let compact_prompt = compact_prompt.or(file_compact_prompt);
+        let workflows = Self::resolve_workflows(&cfg.workflows)?;
 
         // Default review model when not set in config; allow CLI override to take precedence.
         let review_model = override_review_model
@@ -1235,30 +1855,63 @@ impl Config {
         // Config.
         let ConfigRequirements {
             approval_policy: mut constrained_approval_policy,
+            sandbox: sandbox_requirements,
+            allowed_model_providers: _,
+            model_providers: _,
         } = requirements;
 
         constrained_approval_policy
             .set(approval_policy)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("{e}")))?;
+            .map_err(constraint_error)?;
+
+        let mut model_instructions =
+            resolve_model_instructions(cfg.model_instructions.unwrap_or_default())?;
+        let project_instructions = resolve_project_instructions(
+            &resolved_cwd,
+            cfg.project_instructions_max_bytes
+                .unwrap_or(PROJECT_INSTRUCTIONS_MAX_BYTES),
+            active_project.is_trusted(),
+            base_instructions.is_some(),
+        );
+        let base_instructions = match &project_instructions {
+            Some(project) if project.mode == ProjectInstructionsMode::Replace => {
+                Some(project.text.clone())
+            }
+            Some(project) => {
+                model_instructions.push(ModelInstructions {
+                    models: Vec::new(),
+                    providers: Vec::new(),
+                    instructions: project.text.clone(),
+                });
+                base_instructions
+            }
+            None => base_instructions,
+        };
 
         let config = Self {
             model,
             review_model,
+            summarize_file_model: cfg.summarize_file_model,
             model_context_window: cfg.model_context_window,
             model_auto_compact_token_limit: cfg.model_auto_compact_token_limit,
             model_provider_id,
             model_provider,
             cwd: resolved_cwd,
+            additional_workspace_roots,
             approval_policy: constrained_approval_policy,
             sandbox_policy,
+            sandbox_requirements,
             did_user_set_custom_approval_policy_or_sandbox_mode,
             forced_auto_mode_downgraded_on_windows,
             shell_environment_policy,
             notify: cfg.notify,
+            notify_sinks: cfg.notify_sinks.unwrap_or_default(),
             user_instructions,
             base_instructions,
             developer_instructions,
             compact_prompt,
+            language: non_empty(cfg.language),
+            timezone: non_empty(cfg.timezone),
             // The config.toml omits "_mode" because it's a config file. However, "_mode"
             // is important in code to differentiate the mode from the store implementation.
             cli_auth_credentials_store_mode: cfg.cli_auth_credentials_store.unwrap_or_default(),
@@ -1282,14 +1935,42 @@ impl Config {
                 })
                 .collect(),
             tool_output_token_limit: cfg.tool_output_token_limit,
+            tool_description_budget: cfg.tool_description_budget,
+            best_of: cfg
+                .best_of
+                .filter(|best_of| best_of.candidates > 1)
+                .map(|best_of| BestOf {
+                    candidates: best_of.candidates.min(MAX_BEST_OF_CANDIDATES),
+                    ..best_of
+                }),
+            patch_critic: config_profile
+                .patch_critic
+                .clone()
+                .or_else(|| cfg.patch_critic.clone())
+                .filter(|critic| critic.when != PatchCriticMode::Never),
+            patch_preview: cfg
+                .patch_preview
+                .filter(|preview| !preview.commands.is_empty()),
+            stall_detection: cfg
+                .stall_detection
+                .filter(|stall_detection| stall_detection.timeout_secs > 0),
+            history_filter: cfg.history_filter.unwrap_or_default(),
+            telemetry: cfg.telemetry.unwrap_or_default(),
+            sub_agents: cfg.sub_agents.unwrap_or_default(),
+            workflows,
             codex_home,
             history,
+            log: cfg.log.unwrap_or_default(),
             file_opener: cfg.file_opener.unwrap_or(UriBasedFileOpener::VsCode),
             codex_linux_sandbox_exe,
 
-            hide_agent_reasoning: cfg.hide_agent_reasoning.unwrap_or(false),
-            show_raw_agent_reasoning: cfg
+            hide_agent_reasoning: config_profile
+                .hide_agent_reasoning
+                .or(cfg.hide_agent_reasoning)
+                .unwrap_or(false),
+            show_raw_agent_reasoning: config_profile
                 .show_raw_agent_reasoning
+                .or(cfg.show_raw_agent_reasoning)
                 .or(show_raw_agent_reasoning)
                 .unwrap_or(false),
             model_reasoning_effort: config_profile
@@ -1313,20 +1994,54 @@ impl Config {
             use_experimental_unified_exec_tool,
             use_experimental_use_rmcp_client,
             ghost_snapshot,
+            context_ignore: cfg
+                .context
+                .and_then(|context| context.ignore)
+                .unwrap_or_default(),
+            protected_paths: cfg.protected_paths.unwrap_or_default(),
+            issue_tracker: cfg.issue_tracker.unwrap_or_default(),
+            scratch_dir: cfg.scratch_dir.unwrap_or_default(),
+            memories: cfg.memories.unwrap_or_default(),
+            wsl: crate::wsl_paths::resolve_config(cfg.wsl, &resolved_cwd),
+            prompt_sections: resolve_prompt_sections(cfg.prompt_sections)?,
+            model_instructions,
+            project_instructions,
             features,
             active_profile: active_profile_name,
             active_project,
             windows_wsl_setup_acknowledged: cfg.windows_wsl_setup_acknowledged.unwrap_or(false),
             notices: cfg.notice.unwrap_or_default(),
             check_for_update_on_startup,
+            update_channel: cfg.update_channel.unwrap_or_default(),
             disable_paste_burst: cfg.disable_paste_burst.unwrap_or(false),
             tui_notifications: cfg
                 .tui
                 .as_ref()
                 .map(|t| t.notifications.clone())
                 .unwrap_or_default(),
-            animations: cfg.tui.as_ref().map(|t| t.animations).unwrap_or(true),
+            // Screen readers re-announce every animation frame, so
+            // screen-reader mode always disables animations.
+            animations: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.animations && t.accessibility != AccessibilityMode::ScreenReader)
+                .unwrap_or(true),
             show_tooltips: cfg.tui.as_ref().map(|t| t.show_tooltips).unwrap_or(true),
+            tui_accessibility: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.accessibility)
+                .unwrap_or_default(),
+            tui_transcript_file: cfg
+                .tui
+                .as_ref()
+                .and_then(|t| t.transcript_file.as_ref())
+                .map(AbsolutePathBuf::to_path_buf),
+            tui_image_protocol: cfg
+                .tui
+                .as_ref()
+                .map(|t| t.image_protocol)
+                .unwrap_or_default(),
             otel: {
                 let t: OtelConfigToml = cfg.otel.unwrap_or_default();
                 let log_user_prompt = t.log_user_prompt.unwrap_or(false);
@@ -1390,6 +2105,70 @@ impl Config {
         }
     }
 
+    /// Resolve `[workflows]`, inheriting each workflow's approval policy into
+    /// its steps and loading their output schemas.
+    fn resolve_workflows(
+        workflows: &BTreeMap<String, WorkflowToml>,
+    ) -> std::io::Result<BTreeMap<String, Workflow>> {
+        let mut resolved = BTreeMap::new();
+        for (name, workflow) in workflows {
+            if workflow.steps.is_empty() {
+                return Err(std::io::Error::new(
+                    ErrorKind::InvalidData,
+                    format!("workflow `{name}` has no steps"),
+                ));
+            }
+            let mut steps = Vec::with_capacity(workflow.steps.len());
+            for step in &workflow.steps {
+                let output_schema = match Self::try_read_non_empty_file(
+                    step.output_schema.as_ref(),
+                    &format!("output schema of workflow `{name}`"),
+                )? {
+                    Some(schema) => Some(serde_json::from_str(&schema).map_err(|e| {
+                        std::io::Error::new(
+                            ErrorKind::InvalidData,
+                            format!("invalid output schema in workflow `{name}`: {e}"),
+                        )
+                    })?),
+                    None => None,
+                };
+                steps.push(WorkflowStep {
+                    prompt: step.prompt.clone(),
+                    tools: step.tools.clone(),
+                    approval_policy: step.approval_policy.or(workflow.approval_policy),
+                    output_schema,
+                });
+            }
+            resolved.insert(
+                name.clone(),
+                Workflow {
+                    description: workflow.description.clone(),
+                    steps,
+                },
+            );
+        }
+        Ok(resolved)
+    }
+
+    /// Configured extra instructions that apply to `model` with the active
+    /// provider, joined in order.
+    pub fn instructions_for_model(&self, model: &str) -> Option<String> {
+        let matching: Vec<&str> = self
+            .model_instructions
+            .iter()
+            .filter(|entry| entry.applies_to(model, &self.model_provider_id))
+            .map(|entry| entry.instructions.as_str())
+            .collect();
+        (!matching.is_empty()).then(|| matching.join("\n\n"))
+    }
+
+    /// Every workspace root of the session: `cwd` first, followed by
+    /// [`Config::additional_workspace_roots`] in declaration order.
+    pub fn workspace_roots(&self) -> impl Iterator<Item = &Path> {
+        std::iter::once(self.cwd.as_path())
+            .chain(self.additional_workspace_roots.iter().map(PathBuf::as_path))
+    }
+
     pub fn set_windows_sandbox_globally(&mut self, value: bool) {
         crate::safety::set_windows_sandbox_enabled(value);
         if value {
@@ -1405,6 +2184,12 @@ fn default_review_model() -> String {
     OPENAI_DEFAULT_REVIEW_MODEL.to_string()
 }
 
+fn non_empty(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
 /// Returns the path to the Codex configuration directory, which can be
 /// specified by the `CODEX_HOME` environment variable. If not set, defaults to
 /// `~/.codex`.
@@ -1501,6 +2286,64 @@ persistence = "none"
 
         assert_eq!(tui.notifications, Notifications::Enabled(true));
         assert!(tui.show_tooltips);
+        assert_eq!(tui.accessibility, AccessibilityMode::Standard);
+    }
+
+    #[test]
+    fn memories_config_keeps_defaults_for_unset_limits() {
+        let cfg = r#"
+[memories]
+enabled = false
+backend = "sqlite"
+max_new_per_turn = 2
+auto_accept_secs = 0
+include_turn_output = true
+similarity_threshold = 0.9
+scope_by_branch = true
+categories = ["build"]
+redaction = "reject"
+"#;
+
+        let parsed = toml::from_str::<ConfigToml>(cfg).expect("memories config should parse");
+
+        assert_eq!(
+            parsed.memories,
+            Some(MemoriesToml {
+                enabled: false,
+                backend: MemoryBackend::Sqlite,
+                max_new_per_turn: 2,
+                auto_accept_secs: 0,
+                include_turn_output: true,
+                similarity_threshold: Some(0.9),
+                scope_by_branch: true,
+                categories: vec!["build".to_string()],
+                redaction: MemoryRedaction::Reject,
+                ..MemoriesToml::default()
+            })
+        );
+    }
+
+    #[test]
+    fn screen_reader_mode_disables_animations() -> std::io::Result<()> {
+        let cfg = toml::from_str::<ConfigToml>(
+            r#"
+[tui]
+accessibility = "screen-reader"
+"#,
+        )
+        .expect("TUI accessibility should parse");
+        let codex_home = TempDir::new()?;
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(config.tui_accessibility, AccessibilityMode::ScreenReader);
+        assert!(!config.animations);
+
+        Ok(())
     }
 
     #[test]
@@ -1696,6 +2539,42 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn workspace_roots_merge_config_and_add_dir() -> std::io::Result<()> {
+        let temp_dir = TempDir::new()?;
+        let frontend = temp_dir.path().join("frontend");
+        let backend = temp_dir.path().join("backend");
+        let shared = temp_dir.path().join("shared");
+        for dir in [&frontend, &backend, &shared] {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        let cfg = ConfigToml {
+            workspace_roots: Some(vec![PathBuf::from("../backend"), PathBuf::from(".")]),
+            ..Default::default()
+        };
+        let overrides = ConfigOverrides {
+            cwd: Some(frontend.clone()),
+            additional_writable_roots: vec![backend.clone(), shared.clone()],
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            overrides,
+            temp_dir.path().to_path_buf(),
+        )?;
+
+        assert_eq!(config.additional_workspace_roots, vec![backend, shared]);
+        assert_eq!(
+            config.workspace_roots().next(),
+            Some(frontend.as_path()),
+            "cwd is always the first workspace root"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn config_defaults_to_file_cli_auth_store_mode() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -1715,6 +2594,27 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn config_trims_language_and_timezone() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg = ConfigToml {
+            language: Some(" ja ".to_string()),
+            timezone: Some(String::new()),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(config.language.as_deref(), Some("ja"));
+        assert_eq!(config.timezone, None);
+
+        Ok(())
+    }
+
     #[test]
     fn config_honors_explicit_keyring_auth_store_mode() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -1784,6 +2684,173 @@ trust_level = "trusted"
         Ok(())
     }
 
+    #[test]
+    fn profile_patch_critic_replaces_the_top_level_one() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+[patch_critic]
+model = "gpt-5.1-codex-mini"
+
+[profiles.auto.patch_critic]
+when = "auto-approved"
+
+[profiles.fast.patch_critic]
+when = "never"
+"#,
+        )
+        .expect("patch critic config should parse");
+        let load = |profile: Option<&str>| {
+            Config::load_from_base_config_with_overrides(
+                cfg.clone(),
+                ConfigOverrides {
+                    config_profile: profile.map(str::to_string),
+                    ..Default::default()
+                },
+                codex_home.path().to_path_buf(),
+            )
+        };
+
+        assert_eq!(
+            load(None)?.patch_critic,
+            Some(PatchCritic {
+                model: Some("gpt-5.1-codex-mini".to_string()),
+                when: PatchCriticMode::Always,
+            })
+        );
+        assert_eq!(
+            load(Some("auto"))?.patch_critic,
+            Some(PatchCritic {
+                model: None,
+                when: PatchCriticMode::AutoApproved,
+            })
+        );
+        assert_eq!(load(Some("fast"))?.patch_critic, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn workflow_steps_inherit_the_approval_policy_and_load_schemas() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        std::fs::write(
+            codex_home.path().join("summary.json"),
+            r#"{"type":"object","properties":{"fixed":{"type":"boolean"}}}"#,
+        )?;
+        let cfg: ConfigToml = {
+            let _guard = AbsolutePathBufGuard::new(codex_home.path());
+            toml::from_str(
+                r#"
+[workflows.triage]
+description = "Reproduce and fix an issue"
+approval_policy = "on-request"
+
+[[workflows.triage.steps]]
+prompt = "Reproduce {input}"
+tools = ["shell", "read_file"]
+approval_policy = "never"
+
+[[workflows.triage.steps]]
+prompt = "Fix it and run the tests"
+output_schema = "summary.json"
+"#,
+            )
+            .expect("workflow config should parse")
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.workflows.get("triage"),
+            Some(&Workflow {
+                description: Some("Reproduce and fix an issue".to_string()),
+                steps: vec![
+                    WorkflowStep {
+                        prompt: "Reproduce {input}".to_string(),
+                        tools: Some(vec!["shell".to_string(), "read_file".to_string()]),
+                        approval_policy: Some(AskForApproval::Never),
+                        output_schema: None,
+                    },
+                    WorkflowStep {
+                        prompt: "Fix it and run the tests".to_string(),
+                        tools: None,
+                        approval_policy: Some(AskForApproval::OnRequest),
+                        output_schema: Some(serde_json::json!({
+                            "type": "object",
+                            "properties": {"fixed": {"type": "boolean"}},
+                        })),
+                    },
+                ],
+            })
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn profile_reasoning_display_overrides_base() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let mut profiles = HashMap::new();
+        profiles.insert(
+            "work".to_string(),
+            ConfigProfile {
+                hide_agent_reasoning: Some(false),
+                show_raw_agent_reasoning: Some(true),
+                ..Default::default()
+            },
+        );
+        let cfg = ConfigToml {
+            profiles,
+            profile: Some("work".to_string()),
+            hide_agent_reasoning: Some(true),
+            ..Default::default()
+        };
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            (config.hide_agent_reasoning, config.show_raw_agent_reasoning),
+            (false, true)
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn model_provider_overrides_tune_built_in_providers() -> std::io::Result<()> {
+        let codex_home = TempDir::new()?;
+        let cfg: ConfigToml = toml::from_str(
+            r#"
+[model_provider_overrides.openai]
+stream_idle_timeout_ms = 900000
+stream_max_retries = 2
+"#,
+        )
+        .expect("TOML deserialization should succeed");
+
+        let config = Config::load_from_base_config_with_overrides(
+            cfg,
+            ConfigOverrides::default(),
+            codex_home.path().to_path_buf(),
+        )?;
+
+        assert_eq!(
+            config.model_provider.stream_idle_timeout(),
+            Duration::from_secs(900)
+        );
+        assert_eq!(config.model_provider.stream_max_retries(), 2);
+        assert_eq!(config.model_provider.request_max_retries, None);
+        Ok(())
+    }
+
     #[test]
     fn profile_sandbox_mode_overrides_base() -> std::io::Result<()> {
         let codex_home = TempDir::new()?;
@@ -2988,6 +4055,7 @@ model_verbosity = "high"
             stream_max_retries: Some(10),
             stream_idle_timeout_ms: Some(300_000),
             requires_openai_auth: false,
+            embedding_model: None,
         };
         let model_provider_map = {
             let mut model_provider_map = built_in_model_providers();
@@ -3043,18 +4111,22 @@ model_verbosity = "high"
             Config {
                 model: Some("o3".to_string()),
                 review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
+                summarize_file_model: None,
                 model_context_window: None,
                 model_auto_compact_token_limit: None,
                 model_provider_id: "openai".to_string(),
                 model_provider: fixture.openai_provider.clone(),
                 approval_policy: Constrained::allow_any(AskForApproval::Never),
                 sandbox_policy: SandboxPolicy::new_read_only_policy(),
+                sandbox_requirements: SandboxRequirements::default(),
                 did_user_set_custom_approval_policy_or_sandbox_mode: true,
                 forced_auto_mode_downgraded_on_windows: false,
                 shell_environment_policy: ShellEnvironmentPolicy::default(),
                 user_instructions: None,
                 notify: None,
+                notify_sinks: Vec::new(),
                 cwd: fixture.cwd(),
+                additional_workspace_roots: Vec::new(),
                 cli_auth_credentials_store_mode: Default::default(),
                 mcp_servers: HashMap::new(),
                 mcp_oauth_credentials_store_mode: Default::default(),
@@ -3062,8 +4134,18 @@ model_verbosity = "high"
                 project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
                 project_doc_fallback_filenames: Vec::new(),
                 tool_output_token_limit: None,
+                tool_description_budget: None,
+                best_of: None,
+                patch_critic: None,
+                patch_preview: None,
+                stall_detection: None,
+                history_filter: HistoryFilter::default(),
+                telemetry: Telemetry::default(),
+                sub_agents: SubAgentLimits::default(),
+                workflows: BTreeMap::new(),
                 codex_home: fixture.codex_home(),
                 history: History::default(),
+                log: Logging::default(),
                 file_opener: UriBasedFileOpener::VsCode,
                 codex_linux_sandbox_exe: None,
                 hide_agent_reasoning: false,
@@ -3077,6 +4159,8 @@ model_verbosity = "high"
                 base_instructions: None,
                 developer_instructions: None,
                 compact_prompt: None,
+                language: None,
+                timezone: None,
                 forced_chatgpt_workspace_id: None,
                 forced_login_method: None,
                 include_apply_patch_tool: false,
@@ -3084,16 +4168,29 @@ model_verbosity = "high"
                 use_experimental_unified_exec_tool: false,
                 use_experimental_use_rmcp_client: false,
                 ghost_snapshot: GhostSnapshotConfig::default(),
+                context_ignore: Vec::new(),
+                protected_paths: ProtectedPathsToml::default(),
+                issue_tracker: IssueTrackerConfig::default(),
+                scratch_dir: ScratchDirToml::default(),
+                memories: MemoriesToml::default(),
+                wsl: None,
+                prompt_sections: None,
+                model_instructions: Vec::new(),
+                project_instructions: None,
                 features: Features::with_defaults(),
                 active_profile: Some("o3".to_string()),
                 active_project: ProjectConfig { trust_level: None },
                 windows_wsl_setup_acknowledged: false,
                 notices: Default::default(),
                 check_for_update_on_startup: true,
+                update_channel: UpdateChannel::Stable,
                 disable_paste_burst: false,
                 tui_notifications: Default::default(),
                 animations: true,
                 show_tooltips: true,
+                tui_accessibility: AccessibilityMode::Standard,
+                tui_transcript_file: None,
+                tui_image_protocol: ImageProtocolPreference::Auto,
                 otel: OtelConfig::default(),
             },
             o3_profile_config
@@ -3118,18 +4215,22 @@ model_verbosity = "high"
         let expected_gpt3_profile_config = Config {
             model: Some("gpt-3.5-turbo".to_string()),
             review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
+            summarize_file_model: None,
             model_context_window: None,
             model_auto_compact_token_limit: None,
             model_provider_id: "openai-chat-completions".to_string(),
             model_provider: fixture.openai_chat_completions_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::UnlessTrusted),
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            sandbox_requirements: SandboxRequirements::default(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            notify_sinks: Vec::new(),
             cwd: fixture.cwd(),
+            additional_workspace_roots: Vec::new(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
@@ -3137,8 +4238,18 @@ model_verbosity = "high"
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            tool_description_budget: None,
+            best_of: None,
+            patch_critic: None,
+            patch_preview: None,
+            stall_detection: None,
+            history_filter: HistoryFilter::default(),
+            telemetry: Telemetry::default(),
+            sub_agents: SubAgentLimits::default(),
+            workflows: BTreeMap::new(),
             codex_home: fixture.codex_home(),
             history: History::default(),
+            log: Logging::default(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
@@ -3152,6 +4263,8 @@ model_verbosity = "high"
             base_instructions: None,
             developer_instructions: None,
             compact_prompt: None,
+            language: None,
+            timezone: None,
             forced_chatgpt_workspace_id: None,
             forced_login_method: None,
             include_apply_patch_tool: false,
@@ -3159,16 +4272,29 @@ model_verbosity = "high"
             use_experimental_unified_exec_tool: false,
             use_experimental_use_rmcp_client: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
+            context_ignore: Vec::new(),
+            protected_paths: ProtectedPathsToml::default(),
+            issue_tracker: IssueTrackerConfig::default(),
+            scratch_dir: ScratchDirToml::default(),
+            memories: MemoriesToml::default(),
+            wsl: None,
+            prompt_sections: None,
+            model_instructions: Vec::new(),
+            project_instructions: None,
             features: Features::with_defaults(),
             active_profile: Some("gpt3".to_string()),
             active_project: ProjectConfig { trust_level: None },
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
+            update_channel: UpdateChannel::Stable,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
             animations: true,
             show_tooltips: true,
+            tui_accessibility: AccessibilityMode::Standard,
+            tui_transcript_file: None,
+            tui_image_protocol: ImageProtocolPreference::Auto,
             otel: OtelConfig::default(),
         };
 
@@ -3208,18 +4334,22 @@ model_verbosity = "high"
         let expected_zdr_profile_config = Config {
             model: Some("o3".to_string()),
             review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
+            summarize_file_model: None,
             model_context_window: None,
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            sandbox_requirements: SandboxRequirements::default(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            notify_sinks: Vec::new(),
             cwd: fixture.cwd(),
+            additional_workspace_roots: Vec::new(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
@@ -3227,8 +4357,18 @@ model_verbosity = "high"
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            tool_description_budget: None,
+            best_of: None,
+            patch_critic: None,
+            patch_preview: None,
+            stall_detection: None,
+            history_filter: HistoryFilter::default(),
+            telemetry: Telemetry::default(),
+            sub_agents: SubAgentLimits::default(),
+            workflows: BTreeMap::new(),
             codex_home: fixture.codex_home(),
             history: History::default(),
+            log: Logging::default(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
@@ -3242,6 +4382,8 @@ model_verbosity = "high"
             base_instructions: None,
             developer_instructions: None,
             compact_prompt: None,
+            language: None,
+            timezone: None,
             forced_chatgpt_workspace_id: None,
             forced_login_method: None,
             include_apply_patch_tool: false,
@@ -3249,16 +4391,29 @@ model_verbosity = "high"
             use_experimental_unified_exec_tool: false,
             use_experimental_use_rmcp_client: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
+            context_ignore: Vec::new(),
+            protected_paths: ProtectedPathsToml::default(),
+            issue_tracker: IssueTrackerConfig::default(),
+            scratch_dir: ScratchDirToml::default(),
+            memories: MemoriesToml::default(),
+            wsl: None,
+            prompt_sections: None,
+            model_instructions: Vec::new(),
+            project_instructions: None,
             features: Features::with_defaults(),
             active_profile: Some("zdr".to_string()),
             active_project: ProjectConfig { trust_level: None },
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
+            update_channel: UpdateChannel::Stable,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
             animations: true,
             show_tooltips: true,
+            tui_accessibility: AccessibilityMode::Standard,
+            tui_transcript_file: None,
+            tui_image_protocol: ImageProtocolPreference::Auto,
             otel: OtelConfig::default(),
         };
 
@@ -3284,18 +4439,22 @@ model_verbosity = "high"
         let expected_gpt5_profile_config = Config {
             model: Some("gpt-5.1".to_string()),
             review_model: OPENAI_DEFAULT_REVIEW_MODEL.to_string(),
+            summarize_file_model: None,
             model_context_window: None,
             model_auto_compact_token_limit: None,
             model_provider_id: "openai".to_string(),
             model_provider: fixture.openai_provider.clone(),
             approval_policy: Constrained::allow_any(AskForApproval::OnFailure),
             sandbox_policy: SandboxPolicy::new_read_only_policy(),
+            sandbox_requirements: SandboxRequirements::default(),
             did_user_set_custom_approval_policy_or_sandbox_mode: true,
             forced_auto_mode_downgraded_on_windows: false,
             shell_environment_policy: ShellEnvironmentPolicy::default(),
             user_instructions: None,
             notify: None,
+            notify_sinks: Vec::new(),
             cwd: fixture.cwd(),
+            additional_workspace_roots: Vec::new(),
             cli_auth_credentials_store_mode: Default::default(),
             mcp_servers: HashMap::new(),
             mcp_oauth_credentials_store_mode: Default::default(),
@@ -3303,8 +4462,18 @@ model_verbosity = "high"
             project_doc_max_bytes: PROJECT_DOC_MAX_BYTES,
             project_doc_fallback_filenames: Vec::new(),
             tool_output_token_limit: None,
+            tool_description_budget: None,
+            best_of: None,
+            patch_critic: None,
+            patch_preview: None,
+            stall_detection: None,
+            history_filter: HistoryFilter::default(),
+            telemetry: Telemetry::default(),
+            sub_agents: SubAgentLimits::default(),
+            workflows: BTreeMap::new(),
             codex_home: fixture.codex_home(),
             history: History::default(),
+            log: Logging::default(),
             file_opener: UriBasedFileOpener::VsCode,
             codex_linux_sandbox_exe: None,
             hide_agent_reasoning: false,
@@ -3318,6 +4487,8 @@ model_verbosity = "high"
             base_instructions: None,
             developer_instructions: None,
             compact_prompt: None,
+            language: None,
+            timezone: None,
             forced_chatgpt_workspace_id: None,
             forced_login_method: None,
             include_apply_patch_tool: false,
@@ -3325,16 +4496,29 @@ model_verbosity = "high"
             use_experimental_unified_exec_tool: false,
             use_experimental_use_rmcp_client: false,
             ghost_snapshot: GhostSnapshotConfig::default(),
+            context_ignore: Vec::new(),
+            protected_paths: ProtectedPathsToml::default(),
+            issue_tracker: IssueTrackerConfig::default(),
+            scratch_dir: ScratchDirToml::default(),
+            memories: MemoriesToml::default(),
+            wsl: None,
+            prompt_sections: None,
+            model_instructions: Vec::new(),
+            project_instructions: None,
             features: Features::with_defaults(),
             active_profile: Some("gpt5".to_string()),
             active_project: ProjectConfig { trust_level: None },
             windows_wsl_setup_acknowledged: false,
             notices: Default::default(),
             check_for_update_on_startup: true,
+            update_channel: UpdateChannel::Stable,
             disable_paste_burst: false,
             tui_notifications: Default::default(),
             animations: true,
             show_tooltips: true,
+            tui_accessibility: AccessibilityMode::Standard,
+            tui_transcript_file: None,
+            tui_image_protocol: ImageProtocolPreference::Auto,
             otel: OtelConfig::default(),
         };
 
@@ -3522,6 +4706,66 @@ trust_level = "untrusted"
         Ok(())
     }
 
+    #[test]
+    fn prompt_sections_resolve_in_order_and_reject_unknown_names() {
+        let team = test_absolute_path("/prompts/team.md");
+        let sections = resolve_prompt_sections(Some(PromptSectionsToml {
+            order: vec![
+                "team".to_string(),
+                "base".to_string(),
+                "environment".to_string(),
+            ],
+            files: HashMap::from([("team".to_string(), team.clone())]),
+        }))
+        .expect("valid sections");
+        assert_eq!(
+            sections,
+            Some(vec![
+                PromptSection::File {
+                    name: "team".to_string(),
+                    path: team.to_path_buf(),
+                },
+                PromptSection::Base,
+                PromptSection::Environment,
+            ])
+        );
+
+        for order in [vec!["nope"], vec!["base", "base"]] {
+            let toml = PromptSectionsToml {
+                order: order.into_iter().map(str::to_string).collect(),
+                files: HashMap::new(),
+            };
+            assert!(resolve_prompt_sections(Some(toml)).is_err());
+        }
+        assert_eq!(resolve_prompt_sections(None).expect("no sections"), None);
+    }
+
+    #[test]
+    fn model_instructions_match_model_globs_and_providers() -> std::io::Result<()> {
+        let entries = resolve_model_instructions(vec![
+            ModelInstructionsToml {
+                models: vec!["qwen*".to_string(), "llama3.?".to_string()],
+                instructions: Some("Keep tool calls small.".to_string()),
+                ..Default::default()
+            },
+            ModelInstructionsToml {
+                providers: vec!["ollama".to_string()],
+                instructions: Some("Prefer the shell tool.".to_string()),
+                ..Default::default()
+            },
+        ])?;
+
+        assert!(entries[0].applies_to("Qwen2.5-coder", "openai"));
+        assert!(entries[0].applies_to("llama3.1", "ollama"));
+        assert!(!entries[0].applies_to("gpt-5.1", "openai"));
+        assert!(entries[1].applies_to("qwen2.5-coder", "ollama"));
+        assert!(!entries[1].applies_to("qwen2.5-coder", "openai"));
+
+        let neither = ModelInstructionsToml::default();
+        assert!(resolve_model_instructions(vec![neither]).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_resolve_oss_provider_explicit_override() {
         let config_toml = ConfigToml::default();
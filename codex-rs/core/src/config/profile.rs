@@ -1,6 +1,7 @@
 use codex_utils_absolute_path::AbsolutePathBuf;
 use serde::Deserialize;
 
+use crate::config::types::PatchCritic;
 use crate::protocol::AskForApproval;
 use codex_protocol::config_types::ReasoningSummary;
 use codex_protocol::config_types::SandboxMode;
@@ -20,6 +21,8 @@ pub struct ConfigProfile {
     pub model_reasoning_effort: Option<ReasoningEffort>,
     pub model_reasoning_summary: Option<ReasoningSummary>,
     pub model_verbosity: Option<Verbosity>,
+    pub hide_agent_reasoning: Option<bool>,
+    pub show_raw_agent_reasoning: Option<bool>,
     pub chatgpt_base_url: Option<String>,
     pub experimental_instructions_file: Option<AbsolutePathBuf>,
     pub experimental_compact_prompt_file: Option<AbsolutePathBuf>,
@@ -33,6 +36,8 @@ pub struct ConfigProfile {
     #[serde(default)]
     pub features: Option<crate::features::FeaturesToml>,
     pub oss_provider: Option<String>,
+    /// Patch critic for this profile; replaces the top-level `patch_critic`.
+    pub patch_critic: Option<PatchCritic>,
 }
 
 impl From<ConfigProfile> for codex_app_server_protocol::Profile {
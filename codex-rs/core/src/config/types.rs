@@ -3,6 +3,7 @@
 // Note this file should generally be restricted to simple struct/enum
 // definitions that do not contain business logic.
 
+use codex_protocol::protocol::AskForApproval;
 use codex_utils_absolute_path::AbsolutePathBuf;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
@@ -273,6 +274,35 @@ pub enum HistoryPersistence {
     None,
 }
 
+/// Settings for the TUI log file (`~/.codex/log/codex-tui.log`).
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Logging {
+    /// `RUST_LOG`-style directives, e.g. `codex_core=info,codex_core::exec=warn`.
+    /// The `RUST_LOG` environment variable takes precedence when set.
+    pub filter: Option<String>,
+
+    /// Rotate the log file once it reaches this size. Defaults to 10 MiB;
+    /// `0` disables size-based rotation.
+    pub max_bytes: Option<u64>,
+
+    /// Also rotate when the hour or day changes. Defaults to `never`.
+    #[serde(default)]
+    pub rotation: LogRotation,
+
+    /// Number of rotated files (`codex-tui.log.1`, `.2`, ...) to keep.
+    /// Defaults to 5.
+    pub max_files: Option<usize>,
+}
+
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum LogRotation {
+    #[default]
+    Never,
+    Hourly,
+    Daily,
+}
+
 // ===== OTEL configuration =====
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
@@ -380,6 +410,317 @@ pub struct Tui {
     /// Defaults to `true`.
     #[serde(default = "default_true")]
     pub show_tooltips: bool,
+
+    /// Adapt the TUI for assistive technology. Defaults to `standard`.
+    #[serde(default)]
+    pub accessibility: AccessibilityMode,
+
+    /// Terminal graphics protocol used to show images inline. Defaults to
+    /// `auto`, which picks one from the detected terminal.
+    #[serde(default)]
+    pub image_protocol: ImageProtocolPreference,
+
+    /// Append a plain-text rendering of the transcript to this file as the
+    /// session runs. Relative paths resolve against the config file's
+    /// directory.
+    #[serde(default)]
+    pub transcript_file: Option<AbsolutePathBuf>,
+}
+
+/// How the TUI presents progress and state changes.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum AccessibilityMode {
+    #[default]
+    Standard,
+    /// No spinners or animations, state changes announced as plain text in
+    /// the transcript, and no information conveyed by color alone.
+    ScreenReader,
+}
+
+/// Which terminal graphics protocol the TUI uses for inline images.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageProtocolPreference {
+    /// Use the protocol the detected terminal supports, if any.
+    #[default]
+    Auto,
+    Kitty,
+    Iterm2,
+    Sixel,
+    /// Never draw images; show a placeholder instead.
+    None,
+}
+
+/// Sample several responses for each user turn and continue with one of them.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BestOf {
+    /// Number of responses sampled in parallel. Values below 2 turn sampling
+    /// off.
+    pub candidates: usize,
+    #[serde(default)]
+    pub selection: BestOfSelection,
+}
+
+/// Who picks the response the turn continues with.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum BestOfSelection {
+    /// A separate request asks the model which candidate is best.
+    #[default]
+    Judge,
+    /// The user picks. Falls back to the judge when approvals are off.
+    User,
+}
+
+/// Network settings layered over a model provider's own. Unlike entries in
+/// `model_providers`, these also apply to the built-in providers.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ModelProviderOverrides {
+    pub request_max_retries: Option<u64>,
+    pub stream_max_retries: Option<u64>,
+    /// Raise this for models that think for minutes without streaming.
+    pub stream_idle_timeout_ms: Option<u64>,
+}
+
+/// Notice model requests that stop producing output, e.g. a provider that
+/// never finishes its response.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StallDetection {
+    /// Seconds without output from the model before a request counts as
+    /// stalled.
+    #[serde(default = "default_stall_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Send a stalled model request again. Only done once per turn.
+    #[serde(default)]
+    pub auto_retry: bool,
+}
+
+const fn default_stall_timeout_secs() -> u64 {
+    300
+}
+
+/// Which history items are sent back to the model. Counts are in turns, each
+/// starting at a user message; `None` keeps the items from every turn.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HistoryFilter {
+    /// Send reasoning only from this many of the most recent turns.
+    #[serde(default)]
+    pub reasoning_turns: Option<usize>,
+    /// Send tool outputs in full only from this many of the most recent
+    /// turns; older ones are replaced with a short placeholder.
+    #[serde(default)]
+    pub tool_output_turns: Option<usize>,
+    /// Replace a tool output identical to an earlier one with a reference to
+    /// the call that produced it.
+    #[serde(default)]
+    pub dedupe_tool_outputs: bool,
+}
+
+/// Ceilings for the tree of sub-agents a session spawns, e.g. for reviews.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SubAgentLimits {
+    /// How deeply sub-agents may start sub-agents of their own.
+    #[serde(default = "default_sub_agent_max_depth")]
+    pub max_depth: u32,
+    /// Tokens all sub-agents of a session may use together.
+    #[serde(default)]
+    pub max_tokens: Option<u64>,
+    /// Seconds a sub-agent may run. Nested sub-agents stop no later than
+    /// their parent.
+    #[serde(default)]
+    pub max_wall_clock_secs: Option<u64>,
+}
+
+impl Default for SubAgentLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: default_sub_agent_max_depth(),
+            max_tokens: None,
+            max_wall_clock_secs: None,
+        }
+    }
+}
+
+const fn default_sub_agent_max_depth() -> u32 {
+    2
+}
+
+/// What usage data Codex keeps and what it may send.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Telemetry {
+    /// Keep aggregate counts in `$CODEX_HOME/stats.json` for `codex stats`.
+    /// They never leave the machine.
+    #[serde(default = "default_true")]
+    pub local_stats: bool,
+    /// Categories that may be exported through the `[otel]` exporter.
+    /// Empty shares nothing.
+    #[serde(default)]
+    pub share: Vec<TelemetryCategory>,
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        Self {
+            local_stats: true,
+            share: Vec::new(),
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TelemetryCategory {
+    /// Turn and tool call counts.
+    Usage,
+    /// Failed tool calls and errors by category.
+    Failures,
+}
+
+/// Review pending patches with a separate model request before they apply.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PatchCritic {
+    /// Model that reviews the patches. Defaults to the session's model.
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub when: PatchCriticMode,
+}
+
+/// Which patches the critic reviews.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum PatchCriticMode {
+    /// Every patch. Concerns are added to the approval prompt.
+    #[default]
+    Always,
+    /// Only patches that would apply without asking. A patch with concerns
+    /// asks for approval instead.
+    AutoApproved,
+    /// None; lets a profile turn off a critic configured at the top level.
+    Never,
+}
+
+/// Apply pending patches to a throwaway copy of the repository and run
+/// checks there before they apply for real.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PatchPreview {
+    /// Shell commands run in the copy, from the session's directory.
+    #[serde(default)]
+    pub commands: Vec<String>,
+    /// Seconds each command may run before it counts as failed.
+    #[serde(default = "default_patch_preview_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+const fn default_patch_preview_timeout_secs() -> u64 {
+    120
+}
+
+/// A destination for notifications, from `[[notify_sinks]]`.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct NotifySink {
+    #[serde(flatten)]
+    pub target: NotifySinkTarget,
+    /// Notifications to deliver; all of them when empty.
+    #[serde(default)]
+    pub events: Vec<NotificationKind>,
+}
+
+/// Each notification is delivered as one line of JSON.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum NotifySinkTarget {
+    /// A Unix domain socket, connected to for each notification.
+    Socket { path: PathBuf },
+    /// A named pipe. Notifications are dropped while nothing reads it.
+    Fifo { path: PathBuf },
+    /// A command run for each notification, with the JSON on stdin.
+    Command { command: Vec<String> },
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum NotificationKind {
+    AgentTurnComplete,
+    ApprovalRequested,
+}
+
+/// A named sequence of prompts from `[workflows.<name>]`, run with
+/// `codex run <name>`.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct WorkflowToml {
+    pub description: Option<String>,
+    /// Approval policy for every step that does not set its own.
+    pub approval_policy: Option<AskForApproval>,
+    #[serde(default)]
+    pub steps: Vec<WorkflowStepToml>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct WorkflowStepToml {
+    /// Sent as the user message of the step. `{input}` is replaced with the
+    /// input given to the run.
+    pub prompt: String,
+    /// Names of the tools the model may call during the step. `None` keeps
+    /// every tool.
+    pub tools: Option<Vec<String>>,
+    pub approval_policy: Option<AskForApproval>,
+    /// JSON Schema file the step's final message must conform to.
+    pub output_schema: Option<AbsolutePathBuf>,
+}
+
+/// Resolved form of [`WorkflowToml`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Workflow {
+    pub description: Option<String>,
+    pub steps: Vec<WorkflowStep>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkflowStep {
+    pub prompt: String,
+    pub tools: Option<Vec<String>>,
+    /// `None` keeps the session's approval policy.
+    pub approval_policy: Option<AskForApproval>,
+    pub output_schema: Option<serde_json::Value>,
+}
+
+/// Settings for the `issue_tracker` tool.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+pub struct IssueTrackerConfig {
+    /// Inferred from the host of the `origin` remote when unset.
+    pub provider: Option<IssueTrackerProvider>,
+    /// API base URL, for GitHub Enterprise Server or self-hosted GitLab.
+    pub api_url: Option<String>,
+    /// Environment variable that holds the access token.
+    pub token_env_var: Option<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueTrackerProvider {
+    GitHub,
+    GitLab,
+}
+
+/// Release channel that `codex self-update` and the update notice follow.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UpdateChannel {
+    #[default]
+    Stable,
+    /// Stable releases plus `-beta` pre-releases.
+    Beta,
+}
+
+impl std::fmt::Display for UpdateChannel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdateChannel::Stable => f.write_str("stable"),
+            UpdateChannel::Beta => f.write_str("beta"),
+        }
+    }
 }
 
 const fn default_true() -> bool {
@@ -422,6 +763,12 @@ pub struct SandboxWorkspaceWrite {
     pub exclude_tmpdir_env_var: bool,
     #[serde(default)]
     pub exclude_slash_tmp: bool,
+    /// Directories outside the writable roots that symlinks inside them may
+    /// point into (a pnpm store, a bazel output base). Writes are checked
+    /// against the resolved path, so such layouts need these to be listed;
+    /// they are writable under the sandbox.
+    #[serde(default)]
+    pub allowed_symlink_targets: Vec<AbsolutePathBuf>,
 }
 
 impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings {
@@ -431,6 +778,7 @@ impl From<SandboxWorkspaceWrite> for codex_app_server_protocol::SandboxSettings
             network_access: Some(sandbox_workspace_write.network_access),
             exclude_tmpdir_env_var: Some(sandbox_workspace_write.exclude_tmpdir_env_var),
             exclude_slash_tmp: Some(sandbox_workspace_write.exclude_slash_tmp),
+            allowed_symlink_targets: sandbox_workspace_write.allowed_symlink_targets,
         }
     }
 }
@@ -467,12 +815,18 @@ pub struct ShellEnvironmentPolicyToml {
     pub include_only: Option<Vec<String>>,
 
     pub experimental_use_profile: Option<bool>,
+
+    /// Source the user's login shell once at session start and use the
+    /// environment it exports as the starting point for spawned commands.
+    pub experimental_capture_login_env: Option<bool>,
 }
 
 pub type EnvironmentVariablePattern = WildMatchPattern<'*', '?'>;
 
 /// Deriving the `env` based on this policy works as follows:
-/// 1. Create an initial map based on the `inherit` policy.
+/// 1. Create an initial map based on the `inherit` policy. When a login
+///    environment was captured, its values take precedence over the parent
+///    process's.
 /// 2. If `ignore_default_excludes` is false, filter the map using the default
 ///    exclude pattern(s), which are: `"*KEY*"`, `"*SECRET*"`, and `"*TOKEN*"`.
 /// 3. If `exclude` is not empty, filter the map using the provided patterns.
@@ -498,6 +852,10 @@ pub struct ShellEnvironmentPolicy {
 
     /// If true, the shell profile will be used to run the command.
     pub use_profile: bool,
+
+    /// If true, the login shell environment is captured once per process,
+    /// starting with the first session, and layered over the inherited one.
+    pub capture_login_env: bool,
 }
 
 impl From<ShellEnvironmentPolicyToml> for ShellEnvironmentPolicy {
@@ -519,6 +877,7 @@ impl From<ShellEnvironmentPolicyToml> for ShellEnvironmentPolicy {
             .map(|s| EnvironmentVariablePattern::new_case_insensitive(&s))
             .collect();
         let use_profile = toml.experimental_use_profile.unwrap_or(false);
+        let capture_login_env = toml.experimental_capture_login_env.unwrap_or(false);
 
         Self {
             inherit,
@@ -527,6 +886,7 @@ impl From<ShellEnvironmentPolicyToml> for ShellEnvironmentPolicy {
             r#set,
             include_only,
             use_profile,
+            capture_login_env,
         }
     }
 }
@@ -540,6 +900,7 @@ impl Default for ShellEnvironmentPolicy {
             r#set: HashMap::new(),
             include_only: Vec::new(),
             use_profile: false,
+            capture_login_env: false,
         }
     }
 }
@@ -1,21 +1,121 @@
+use std::collections::HashMap;
+
+use codex_protocol::config_types::SandboxMode;
 use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::SandboxPolicy;
 use serde::Deserialize;
 
 use crate::config::Constrained;
 use crate::config::ConstraintError;
+use crate::config::ConstraintResult;
+use crate::model_provider_info::ModelProviderInfo;
 
 /// Normalized version of [`ConfigRequirementsToml`] after deserialization and
 /// normalization.
 #[derive(Debug, Clone, PartialEq)]
 pub struct ConfigRequirements {
     pub approval_policy: Constrained<AskForApproval>,
+    pub sandbox: SandboxRequirements,
+    /// `None` allows every model provider.
+    pub allowed_model_providers: Option<Vec<String>>,
+    /// Providers defined by the administrator. They take the place of
+    /// built-in and user-defined providers with the same ID.
+    pub model_providers: HashMap<String, ModelProviderInfo>,
 }
 
 impl Default for ConfigRequirements {
     fn default() -> Self {
         Self {
             approval_policy: Constrained::allow_any_from_default(),
+            sandbox: SandboxRequirements::default(),
+            allowed_model_providers: None,
+            model_providers: HashMap::new(),
+        }
+    }
+}
+
+impl ConfigRequirements {
+    pub fn check_model_provider(&self, provider_id: &str) -> ConstraintResult<()> {
+        match &self.allowed_model_providers {
+            Some(allowed) if !allowed.iter().any(|id| id == provider_id) => Err(
+                ConstraintError::locked("model_provider", provider_id, allowed.join(", ")),
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Refuse user-defined `model_providers` that reuse an allowed ID, since
+    /// they could point an allowed provider at another server. Allowed
+    /// providers come from the built-in list or from the requirements.
+    pub fn check_model_provider_definitions<'a>(
+        &self,
+        user_defined: impl IntoIterator<Item = &'a String>,
+    ) -> ConstraintResult<()> {
+        let Some(allowed) = &self.allowed_model_providers else {
+            return Ok(());
+        };
+        match user_defined.into_iter().find(|id| allowed.contains(id)) {
+            Some(id) => Err(ConstraintError::redefined(format!("model_providers.{id}"))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Sandbox settings an administrator has locked. They apply to the sandbox
+/// chosen at startup and to every later change, e.g. from `/approvals`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SandboxRequirements {
+    /// `None` allows every mode.
+    pub allowed_modes: Option<Vec<SandboxMode>>,
+    /// `Some(false)` forbids sandboxes with network access.
+    pub network_access: Option<bool>,
+}
+
+impl SandboxRequirements {
+    pub fn check(&self, policy: &SandboxPolicy) -> ConstraintResult<()> {
+        let mode = match policy {
+            SandboxPolicy::ReadOnly => SandboxMode::ReadOnly,
+            SandboxPolicy::WorkspaceWrite { .. } => SandboxMode::WorkspaceWrite,
+            // Codex does not sandbox commands itself in either case.
+            SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
+                SandboxMode::DangerFullAccess
+            }
+        };
+        if let Some(allowed) = &self.allowed_modes
+            && !allowed.contains(&mode)
+        {
+            let allowed = allowed
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(ConstraintError::locked(
+                "sandbox_mode",
+                mode.to_string(),
+                allowed,
+            ));
         }
+        if self.network_access == Some(false) && policy.has_full_network_access() {
+            return Err(ConstraintError::locked(
+                "sandbox_workspace_write.network_access",
+                "true",
+                "false",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Mode to start in when the user did not pick one and the default is
+    /// not allowed: the most restrictive allowed mode.
+    pub fn fallback_mode(&self) -> Option<SandboxMode> {
+        let allowed = self.allowed_modes.as_ref()?;
+        [
+            SandboxMode::ReadOnly,
+            SandboxMode::WorkspaceWrite,
+            SandboxMode::DangerFullAccess,
+        ]
+        .into_iter()
+        .find(|mode| allowed.contains(mode))
     }
 }
 
@@ -23,6 +123,14 @@ impl Default for ConfigRequirements {
 #[derive(Deserialize, Debug, Clone, Default, PartialEq)]
 pub struct ConfigRequirementsToml {
     pub allowed_approval_policies: Option<Vec<AskForApproval>>,
+    pub allowed_sandbox_modes: Option<Vec<SandboxMode>>,
+    /// Set to `false` to forbid sandboxes with network access.
+    pub allow_network_access: Option<bool>,
+    /// IDs of the model providers users may select.
+    pub allowed_model_providers: Option<Vec<String>>,
+    /// Provider definitions for IDs in `allowed_model_providers` that are
+    /// not built in.
+    pub model_providers: Option<HashMap<String, ModelProviderInfo>>,
 }
 
 impl ConfigRequirementsToml {
@@ -41,7 +149,13 @@ impl ConfigRequirementsToml {
             };
         }
 
-        fill_missing_take!(self, other, { allowed_approval_policies });
+        fill_missing_take!(self, other, {
+            allowed_approval_policies,
+            allowed_sandbox_modes,
+            allow_network_access,
+            allowed_model_providers,
+            model_providers,
+        });
     }
 }
 
@@ -62,7 +176,29 @@ impl TryFrom<ConfigRequirementsToml> for ConfigRequirements {
             }
             None => Constrained::allow_any_from_default(),
         };
-        Ok(ConfigRequirements { approval_policy })
+        if toml
+            .allowed_sandbox_modes
+            .as_ref()
+            .is_some_and(Vec::is_empty)
+        {
+            return Err(ConstraintError::empty_field("allowed_sandbox_modes"));
+        }
+        if toml
+            .allowed_model_providers
+            .as_ref()
+            .is_some_and(Vec::is_empty)
+        {
+            return Err(ConstraintError::empty_field("allowed_model_providers"));
+        }
+        Ok(ConfigRequirements {
+            approval_policy,
+            sandbox: SandboxRequirements {
+                allowed_modes: toml.allowed_sandbox_modes,
+                network_access: toml.allow_network_access,
+            },
+            allowed_model_providers: toml.allowed_model_providers,
+            model_providers: toml.model_providers.unwrap_or_default(),
+        })
     }
 }
 
@@ -104,4 +240,77 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn sandbox_requirements_reject_locked_modes_and_network() -> Result<()> {
+        let requirements: ConfigRequirements = from_str::<ConfigRequirementsToml>(
+            r#"
+                allowed_sandbox_modes = ["workspace-write", "read-only"]
+                allow_network_access = false
+                allowed_model_providers = ["corp-proxy"]
+            "#,
+        )?
+        .try_into()?;
+
+        assert_eq!(
+            requirements
+                .sandbox
+                .check(&SandboxPolicy::new_read_only_policy()),
+            Ok(())
+        );
+        assert_eq!(
+            requirements.sandbox.check(&SandboxPolicy::DangerFullAccess),
+            Err(ConstraintError::locked(
+                "sandbox_mode",
+                "danger-full-access",
+                "workspace-write, read-only"
+            ))
+        );
+        let networked = SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            network_access: true,
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+        };
+        assert!(requirements.sandbox.check(&networked).is_err());
+        assert_eq!(
+            requirements.sandbox.fallback_mode(),
+            Some(SandboxMode::ReadOnly)
+        );
+        assert!(requirements.check_model_provider("corp-proxy").is_ok());
+        assert!(requirements.check_model_provider("openai").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn allowed_providers_cannot_be_redefined_by_users() -> Result<()> {
+        let requirements: ConfigRequirements = from_str::<ConfigRequirementsToml>(
+            r#"
+                allowed_model_providers = ["corp-proxy"]
+
+                [model_providers.corp-proxy]
+                name = "Corp proxy"
+                base_url = "https://llm.corp.example/v1"
+            "#,
+        )?
+        .try_into()?;
+
+        assert_eq!(
+            requirements
+                .model_providers
+                .get("corp-proxy")
+                .and_then(|provider| provider.base_url.as_deref()),
+            Some("https://llm.corp.example/v1")
+        );
+        let user_defined = ["local".to_string(), "corp-proxy".to_string()];
+        assert_eq!(
+            requirements.check_model_provider_definitions(&user_defined[..1]),
+            Ok(())
+        );
+        assert_eq!(
+            requirements.check_model_provider_definitions(&user_defined),
+            Err(ConstraintError::redefined("model_providers.corp-proxy"))
+        );
+        Ok(())
+    }
 }
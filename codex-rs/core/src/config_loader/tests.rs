@@ -2,9 +2,11 @@ use super::LoaderOverrides;
 use super::load_config_layers_state;
 use crate::config::CONFIG_TOML_FILE;
 use crate::config_loader::ConfigRequirements;
+use crate::config_loader::SandboxRequirements;
 use crate::config_loader::config_requirements::ConfigRequirementsToml;
 use crate::config_loader::load_requirements_toml;
 use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::SandboxPolicy;
 use pretty_assertions::assert_eq;
 use tempfile::tempdir;
 use toml::Value as TomlValue;
@@ -189,3 +191,41 @@ allowed_approval_policies = ["never", "on-request"]
     );
     Ok(())
 }
+
+#[tokio::test]
+async fn managed_config_sandbox_and_model_provider_stay_defaults() -> anyhow::Result<()> {
+    let tmp = tempdir()?;
+    let managed_path = tmp.path().join("managed_config.toml");
+    std::fs::write(
+        &managed_path,
+        r#"sandbox_mode = "read-only"
+model_provider = "corp-gateway"
+
+[sandbox_workspace_write]
+network_access = false
+"#,
+    )?;
+
+    let overrides = LoaderOverrides {
+        managed_config_path: Some(managed_path),
+        #[cfg(target_os = "macos")]
+        managed_preferences_base64: None,
+    };
+    let state =
+        load_config_layers_state(tmp.path(), &[] as &[(String, TomlValue)], overrides).await?;
+    let requirements = state.requirements();
+
+    assert_eq!(requirements.sandbox, SandboxRequirements::default());
+    assert_eq!(
+        requirements
+            .sandbox
+            .check(&SandboxPolicy::new_workspace_write_policy()),
+        Ok(())
+    );
+    requirements.check_model_provider("openai")?;
+    assert_eq!(
+        state.effective_config().get("sandbox_mode"),
+        Some(&TomlValue::String("read-only".to_string()))
+    );
+    Ok(())
+}
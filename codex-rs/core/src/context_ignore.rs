@@ -0,0 +1,112 @@
+//! User-controlled rules for paths that must never be read into model context.
+//!
+//! Rules use gitignore syntax and come from two sources: the `context.ignore`
+//! globs in `config.toml` and `.codexignore` files at the repository root and
+//! the working directory. Patterns are anchored at the repository root (or
+//! the working directory outside a repository).
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use ignore::gitignore::Gitignore;
+use ignore::gitignore::GitignoreBuilder;
+use tracing::warn;
+
+use crate::git_info::get_git_repo_root;
+
+pub(crate) const CODEXIGNORE_FILENAME: &str = ".codexignore";
+
+#[derive(Debug, Clone)]
+pub(crate) struct ContextIgnore {
+    root: PathBuf,
+    matcher: Gitignore,
+}
+
+impl ContextIgnore {
+    /// Build the exclusion rules that apply to `cwd` from the configured
+    /// `patterns` and any `.codexignore` files.
+    pub(crate) fn new(cwd: &Path, patterns: &[String]) -> Self {
+        let root = get_git_repo_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+        let mut builder = GitignoreBuilder::new(&root);
+
+        let mut ignore_files = vec![root.join(CODEXIGNORE_FILENAME)];
+        if cwd != root {
+            ignore_files.push(cwd.join(CODEXIGNORE_FILENAME));
+        }
+        for path in ignore_files {
+            if !path.is_file() {
+                continue;
+            }
+            if let Some(err) = builder.add(&path) {
+                warn!("Failed to parse {}: {err}", path.display());
+            }
+        }
+
+        for pattern in patterns {
+            if let Err(err) = builder.add_line(None, pattern) {
+                warn!("Invalid context.ignore pattern `{pattern}`: {err}");
+            }
+        }
+
+        let matcher = builder.build().unwrap_or_else(|err| {
+            warn!("Failed to build context ignore rules: {err}");
+            Gitignore::empty()
+        });
+        Self { root, matcher }
+    }
+
+    /// Whether `path` (or any of its parent directories) is excluded from
+    /// context.
+    pub(crate) fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        if self.matcher.is_empty() {
+            return false;
+        }
+        if path.starts_with(&self.root) {
+            self.matcher
+                .matched_path_or_any_parents(path, is_dir)
+                .is_ignore()
+        } else {
+            // Paths outside the root can only match unanchored patterns.
+            self.matcher.matched(path, is_dir).is_ignore()
+        }
+    }
+}
+
+impl Default for ContextIgnore {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::new(),
+            matcher: Gitignore::empty(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn combines_config_patterns_and_codexignore() {
+        let dir = tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::write(root.join(CODEXIGNORE_FILENAME), "secrets/\n*.pem\n").expect("write");
+
+        let rules = ContextIgnore::new(root, &["vendor/**".to_string()]);
+
+        assert!(rules.is_ignored(&root.join("secrets/token.txt"), false));
+        assert!(rules.is_ignored(&root.join("secrets"), true));
+        assert!(rules.is_ignored(&root.join("certs/server.pem"), false));
+        assert!(rules.is_ignored(&root.join("vendor/lib/mod.rs"), false));
+        assert!(!rules.is_ignored(&root.join("src/main.rs"), false));
+    }
+
+    #[test]
+    fn unanchored_patterns_apply_outside_root() {
+        let dir = tempdir().expect("tempdir");
+        let rules = ContextIgnore::new(dir.path(), &["*.key".to_string()]);
+
+        assert!(rules.is_ignored(Path::new("/elsewhere/id.key"), false));
+        assert!(!rules.is_ignored(Path::new("/elsewhere/id.pub"), false));
+    }
+}
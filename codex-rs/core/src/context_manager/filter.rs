@@ -0,0 +1,461 @@
+//! Rules from `[history_filter]` for which history items are replayed to the
+//! model. Filtering only changes what a request sends; the session history
+//! and the rollout keep every item.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+use codex_protocol::items::TurnItem;
+use codex_protocol::models::FunctionCallOutputPayload;
+use codex_protocol::models::ResponseItem;
+
+use crate::config::types::HistoryFilter;
+use crate::event_mapping::parse_turn_item;
+use crate::truncate::approx_tokens_from_byte_count;
+
+pub(crate) const OMITTED_TOOL_OUTPUT: &str = "[output omitted: from an earlier turn]";
+
+/// What [`apply_history_filter`] left out of one request.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct HistoryFilterReport {
+    pub(crate) reasoning_removed: usize,
+    pub(crate) outputs_omitted: usize,
+    pub(crate) outputs_deduped: usize,
+    /// Bytes of the removed reasoning, as serialized, and of the replaced
+    /// outputs beyond their markers.
+    pub(crate) bytes: usize,
+}
+
+impl HistoryFilterReport {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.reasoning_removed == 0 && self.outputs_omitted == 0 && self.outputs_deduped == 0
+    }
+
+    pub(crate) fn approx_tokens(&self) -> u64 {
+        approx_tokens_from_byte_count(self.bytes)
+    }
+
+    /// A sentence for the user, e.g. "[history_filter] left out 2 reasoning
+    /// items from earlier turns (~300 tokens)."
+    pub(crate) fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.reasoning_removed > 0 {
+            parts.push(format!(
+                "{} reasoning item(s) from earlier turns",
+                self.reasoning_removed
+            ));
+        }
+        if self.outputs_omitted > 0 {
+            parts.push(format!(
+                "{} tool output(s) from earlier turns",
+                self.outputs_omitted
+            ));
+        }
+        if self.outputs_deduped > 0 {
+            parts.push(format!(
+                "{} tool output(s) repeating an earlier one",
+                self.outputs_deduped
+            ));
+        }
+        format!(
+            "[history_filter] left out {} (~{} tokens).",
+            parts.join(", "),
+            self.approx_tokens()
+        )
+    }
+}
+
+/// Apply `filter` to `items`, the history about to be sent. A turn starts at
+/// each user message; the turn in progress is the most recent one. User
+/// and assistant messages are never changed.
+pub(crate) fn apply_history_filter(
+    items: &mut Vec<ResponseItem>,
+    filter: &HistoryFilter,
+) -> HistoryFilterReport {
+    let mut report = HistoryFilterReport::default();
+    filter_old_turns(items, filter, &mut report);
+    if filter.dedupe_tool_outputs {
+        dedupe_tool_outputs(items, &mut report);
+    }
+    report
+}
+
+fn filter_old_turns(
+    items: &mut Vec<ResponseItem>,
+    filter: &HistoryFilter,
+    report: &mut HistoryFilterReport,
+) {
+    if filter.reasoning_turns.is_none() && filter.tool_output_turns.is_none() {
+        return;
+    }
+    // The current turn is always kept whole, or the model would lose track
+    // of the work it is doing.
+    let reasoning_turns = filter.reasoning_turns.map(|turns| turns.max(1));
+    let tool_output_turns = filter.tool_output_turns.map(|turns| turns.max(1));
+
+    let ages = turn_ages(items);
+    let mut index = 0;
+    items.retain_mut(|item| {
+        let age = ages[index];
+        index += 1;
+        match item {
+            ResponseItem::Reasoning { .. } => {
+                let keep = reasoning_turns.is_none_or(|turns| age < turns);
+                if !keep {
+                    report.reasoning_removed += 1;
+                    report.bytes += serde_json::to_string(&*item).map_or(0, |json| json.len());
+                }
+                keep
+            }
+            ResponseItem::FunctionCallOutput { output, .. } => {
+                if tool_output_turns.is_some_and(|turns| age >= turns)
+                    && output.content.len() > OMITTED_TOOL_OUTPUT.len()
+                {
+                    report.outputs_omitted += 1;
+                    report.bytes += output.content.len() - OMITTED_TOOL_OUTPUT.len();
+                    *output = FunctionCallOutputPayload {
+                        content: OMITTED_TOOL_OUTPUT.to_string(),
+                        content_items: None,
+                        success: output.success,
+                    };
+                }
+                true
+            }
+            ResponseItem::CustomToolCallOutput { output, .. } => {
+                if tool_output_turns.is_some_and(|turns| age >= turns)
+                    && output.len() > OMITTED_TOOL_OUTPUT.len()
+                {
+                    report.outputs_omitted += 1;
+                    report.bytes += output.len() - OMITTED_TOOL_OUTPUT.len();
+                    *output = OMITTED_TOOL_OUTPUT.to_string();
+                }
+                true
+            }
+            _ => true,
+        }
+    });
+}
+
+/// Replace each tool output that repeats an earlier one, as sent, with a
+/// reference to the earlier call. Runs after the old turns are filtered, so
+/// the output referred to is always still there in full.
+fn dedupe_tool_outputs(items: &mut [ResponseItem], report: &mut HistoryFilterReport) {
+    // Output body -> the call that first produced it.
+    let mut first_calls: HashMap<Cow<'_, str>, &str> = HashMap::new();
+    let mut repeats = Vec::new();
+    for (index, item) in items.iter().enumerate() {
+        let Some((call_id, text)) = dedupable_output(item) else {
+            continue;
+        };
+        let body = output_body(text);
+        match first_calls.get(&body) {
+            Some(first_call_id) => {
+                let marker = repeated_output_marker(first_call_id);
+                report.outputs_deduped += 1;
+                report.bytes += text.len().saturating_sub(marker.len());
+                repeats.push((index, marker));
+            }
+            None => {
+                first_calls.insert(body, call_id);
+            }
+        }
+    }
+    for (index, marker) in repeats {
+        match &mut items[index] {
+            ResponseItem::FunctionCallOutput { output, .. } => output.content = marker,
+            ResponseItem::CustomToolCallOutput { output, .. } => *output = marker,
+            _ => {}
+        }
+    }
+}
+
+/// The call id and text of a tool output worth replacing with a marker.
+fn dedupable_output(item: &ResponseItem) -> Option<(&str, &str)> {
+    let (call_id, text) = match item {
+        // Outputs with images are sent as content items, not as text.
+        ResponseItem::FunctionCallOutput { call_id, output } if output.content_items.is_none() => {
+            (call_id, output.content.as_str())
+        }
+        ResponseItem::CustomToolCallOutput { call_id, output } => (call_id, output.as_str()),
+        _ => return None,
+    };
+    (text.len() > repeated_output_marker(call_id).len()).then_some((call_id.as_str(), text))
+}
+
+/// `text` without the parts that differ between runs of the same command:
+/// shell outputs report how long the call took, and unified exec outputs also
+/// carry a chunk id.
+fn output_body(text: &str) -> Cow<'_, str> {
+    if text.starts_with('{')
+        && let Ok(mut value) = serde_json::from_str::<serde_json::Value>(text)
+        && let Some(metadata) = value
+            .get_mut("metadata")
+            .and_then(serde_json::Value::as_object_mut)
+        && metadata.remove("duration_seconds").is_some()
+    {
+        return Cow::Owned(value.to_string());
+    }
+    let Some((header, body)) = text.split_once("Output:\n") else {
+        return Cow::Borrowed(text);
+    };
+    let header: Vec<&str> = header
+        .lines()
+        .filter(|line| !line.starts_with("Wall time: ") && !line.starts_with("Chunk ID: "))
+        .collect();
+    Cow::Owned(format!("{}\nOutput:\n{body}", header.join("\n")))
+}
+
+fn repeated_output_marker(call_id: &str) -> String {
+    format!("[output identical to that of call {call_id}]")
+}
+
+/// How many user turns started after each item: 0 for the turn in progress,
+/// 1 for the one before it, and so on.
+fn turn_ages(items: &[ResponseItem]) -> Vec<usize> {
+    let mut ages = vec![0; items.len()];
+    let mut age = 0;
+    for (index, item) in items.iter().enumerate().rev() {
+        ages[index] = age;
+        if matches!(parse_turn_item(item), Some(TurnItem::UserMessage(_))) {
+            age += 1;
+        }
+    }
+    ages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::models::ContentItem;
+    use pretty_assertions::assert_eq;
+
+    fn user(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    fn reasoning(encrypted: &str) -> ResponseItem {
+        ResponseItem::Reasoning {
+            id: String::new(),
+            summary: Vec::new(),
+            content: None,
+            encrypted_content: Some(encrypted.to_string()),
+        }
+    }
+
+    fn call(call_id: &str) -> ResponseItem {
+        ResponseItem::FunctionCall {
+            id: None,
+            name: "shell".to_string(),
+            arguments: "{}".to_string(),
+            call_id: call_id.to_string(),
+        }
+    }
+
+    fn output(call_id: &str, content: &str) -> ResponseItem {
+        ResponseItem::FunctionCallOutput {
+            call_id: call_id.to_string(),
+            output: FunctionCallOutputPayload {
+                content: content.to_string(),
+                success: Some(true),
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn old_reasoning_and_tool_outputs_are_filtered() {
+        let long_output = "x".repeat(200);
+        let mut items = vec![
+            user("first"),
+            reasoning("r1"),
+            call("c1"),
+            output("c1", &long_output),
+            user("second"),
+            reasoning("r2"),
+            call("c2"),
+            output("c2", &long_output),
+            user("third"),
+            reasoning("r3"),
+            call("c3"),
+            output("c3", &long_output),
+        ];
+
+        let report = apply_history_filter(
+            &mut items,
+            &HistoryFilter {
+                reasoning_turns: Some(1),
+                tool_output_turns: Some(2),
+                dedupe_tool_outputs: false,
+            },
+        );
+
+        assert_eq!(
+            items,
+            vec![
+                user("first"),
+                call("c1"),
+                output("c1", OMITTED_TOOL_OUTPUT),
+                user("second"),
+                call("c2"),
+                output("c2", &long_output),
+                user("third"),
+                reasoning("r3"),
+                call("c3"),
+                output("c3", &long_output),
+            ]
+        );
+        assert_eq!(report.reasoning_removed, 2);
+        assert_eq!(report.outputs_omitted, 1);
+        assert_eq!(report.outputs_deduped, 0);
+        assert!(
+            report.summary().starts_with(
+                "[history_filter] left out 2 reasoning item(s) from earlier turns, 1 tool output(s) from earlier turns (~"
+            ),
+            "{}",
+            report.summary()
+        );
+    }
+
+    #[test]
+    fn the_current_turn_is_never_filtered() {
+        let mut items = vec![user("only"), reasoning("r1")];
+
+        let report = apply_history_filter(
+            &mut items,
+            &HistoryFilter {
+                reasoning_turns: Some(0),
+                tool_output_turns: None,
+                dedupe_tool_outputs: false,
+            },
+        );
+
+        assert_eq!(items, vec![user("only"), reasoning("r1")]);
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    fn repeated_tool_outputs_refer_to_the_first_call() {
+        let status = "On branch main\nnothing to commit, working tree clean".to_string();
+        let mut items = vec![
+            user("first"),
+            call("c1"),
+            output("c1", &status),
+            call("c2"),
+            output("c2", "ok"),
+            user("second"),
+            call("c3"),
+            output("c3", &status),
+            call("c4"),
+            output("c4", "ok"),
+        ];
+
+        apply_history_filter(
+            &mut items,
+            &HistoryFilter {
+                dedupe_tool_outputs: true,
+                ..HistoryFilter::default()
+            },
+        );
+
+        assert_eq!(
+            items,
+            vec![
+                user("first"),
+                call("c1"),
+                output("c1", &status),
+                call("c2"),
+                output("c2", "ok"),
+                user("second"),
+                call("c3"),
+                output("c3", "[output identical to that of call c1]"),
+                call("c4"),
+                output("c4", "ok"),
+            ]
+        );
+    }
+
+    #[test]
+    fn outputs_omitted_from_old_turns_are_not_referred_to() {
+        let status = "x".repeat(200);
+        let mut items = vec![
+            user("first"),
+            call("c1"),
+            output("c1", &status),
+            user("second"),
+            call("c2"),
+            output("c2", &status),
+            call("c3"),
+            output("c3", &status),
+        ];
+
+        apply_history_filter(
+            &mut items,
+            &HistoryFilter {
+                tool_output_turns: Some(1),
+                dedupe_tool_outputs: true,
+                ..HistoryFilter::default()
+            },
+        );
+
+        assert_eq!(
+            items,
+            vec![
+                user("first"),
+                call("c1"),
+                output("c1", OMITTED_TOOL_OUTPUT),
+                user("second"),
+                call("c2"),
+                output("c2", &status),
+                call("c3"),
+                output("c3", "[output identical to that of call c2]"),
+            ]
+        );
+    }
+
+    #[test]
+    fn timing_does_not_keep_outputs_apart() {
+        let freeform = |seconds: &str| {
+            format!("Exit code: 0\nWall time: {seconds} seconds\nOutput:\nnothing to commit")
+        };
+        let structured = |seconds: &str| {
+            format!(
+                r#"{{"output":"nothing to commit","metadata":{{"exit_code":0,"duration_seconds":{seconds}}}}}"#
+            )
+        };
+        let mut items = vec![
+            user("first"),
+            call("c1"),
+            output("c1", &freeform("0.1")),
+            call("c2"),
+            output("c2", &freeform("0.3")),
+            call("c3"),
+            output("c3", &structured("0.1")),
+            call("c4"),
+            output("c4", &structured("0.2")),
+        ];
+
+        let report = apply_history_filter(
+            &mut items,
+            &HistoryFilter {
+                dedupe_tool_outputs: true,
+                ..HistoryFilter::default()
+            },
+        );
+
+        assert_eq!(report.outputs_deduped, 2);
+        assert_eq!(
+            items[4],
+            output("c2", "[output identical to that of call c1]")
+        );
+        assert_eq!(
+            items[8],
+            output("c4", "[output identical to that of call c3]")
+        );
+    }
+}
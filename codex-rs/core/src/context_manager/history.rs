@@ -13,6 +13,8 @@ use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::TokenUsageInfo;
 use std::ops::Deref;
 
+const REMOVED_IMAGE_PLACEHOLDER: &str = "[image removed after the provider rejected the request]";
+
 /// Transcript of conversation history
 #[derive(Debug, Clone, Default)]
 pub(crate) struct ContextManager {
@@ -47,11 +49,13 @@ impl ContextManager {
     }
 
     /// `items` is ordered from oldest to newest.
-    pub(crate) fn record_items<I>(&mut self, items: I, policy: TruncationPolicy)
+    /// Returns how many bytes of tool output truncation dropped.
+    pub(crate) fn record_items<I>(&mut self, items: I, policy: TruncationPolicy) -> usize
     where
         I: IntoIterator,
         I::Item: std::ops::Deref<Target = ResponseItem>,
     {
+        let mut truncated_bytes = 0;
         for item in items {
             let item_ref = item.deref();
             let is_ghost_snapshot = matches!(item_ref, ResponseItem::GhostSnapshot { .. });
@@ -60,8 +64,11 @@ impl ContextManager {
             }
 
             let processed = self.process_item(item_ref, policy);
+            truncated_bytes +=
+                tool_output_len(item_ref).saturating_sub(tool_output_len(&processed));
             self.items.push(processed);
         }
+        truncated_bytes
     }
 
     pub(crate) fn get_history(&mut self) -> Vec<ResponseItem> {
@@ -117,6 +124,42 @@ impl ContextManager {
         }
     }
 
+    /// Remove reasoning items that carry encrypted content, which only the
+    /// provider that produced them can read. Returns how many were removed.
+    pub(crate) fn drop_encrypted_reasoning(&mut self) -> usize {
+        let before = self.items.len();
+        self.items.retain(|item| {
+            !matches!(
+                item,
+                ResponseItem::Reasoning {
+                    encrypted_content: Some(_),
+                    ..
+                }
+            )
+        });
+        before - self.items.len()
+    }
+
+    /// Replace the arguments of the recorded function call `call_id`, so the
+    /// history shows the call that actually ran. Returns whether it was found.
+    pub(crate) fn replace_call_arguments(&mut self, call_id: &str, new_arguments: String) -> bool {
+        let call = self.items.iter_mut().rev().find_map(|item| match item {
+            ResponseItem::FunctionCall {
+                call_id: id,
+                arguments,
+                ..
+            } if id == call_id => Some(arguments),
+            _ => None,
+        });
+        match call {
+            Some(arguments) => {
+                *arguments = new_arguments;
+                true
+            }
+            None => false,
+        }
+    }
+
     pub(crate) fn replace(&mut self, items: Vec<ResponseItem>) {
         self.items = items;
     }
@@ -164,6 +207,23 @@ impl ContextManager {
         );
     }
 
+    /// Count `usage` of a request made beside the conversation in the totals.
+    /// The last usage keeps measuring the conversation's context.
+    pub(crate) fn add_side_token_usage(
+        &mut self,
+        usage: &TokenUsage,
+        model_context_window: Option<i64>,
+    ) {
+        self.token_info
+            .get_or_insert_with(|| TokenUsageInfo {
+                total_token_usage: TokenUsage::default(),
+                last_token_usage: TokenUsage::default(),
+                model_context_window,
+            })
+            .total_token_usage
+            .add_assign(usage);
+    }
+
     fn get_non_last_reasoning_items_tokens(&self) -> usize {
         // get reasoning items excluding all the ones after the last user message
         let Some(last_user_index) = self
@@ -265,6 +325,15 @@ impl ContextManager {
     }
 }
 
+/// Length of the text a tool output item sends to the model.
+fn tool_output_len(item: &ResponseItem) -> usize {
+    match item {
+        ResponseItem::FunctionCallOutput { output, .. } => output.content.len(),
+        ResponseItem::CustomToolCallOutput { output, .. } => output.len(),
+        _ => 0,
+    }
+}
+
 /// API messages include every non-system item (user/assistant messages, reasoning,
 /// tool calls, tool outputs, shell calls, and web-search calls).
 fn is_api_message(message: &ResponseItem) -> bool {
@@ -291,6 +360,82 @@ fn estimate_reasoning_length(encoded_len: usize) -> usize {
         .saturating_sub(650)
 }
 
+/// Repair the input of a request the provider rejected, so it can be sent
+/// again. Earlier requests were accepted, so only the newest offending item is
+/// touched: a tool output longer than `max_output_bytes` is cut down to that
+/// budget and, with `strip_images`, images are replaced by a placeholder.
+/// Returns a description of the change, for reporting to the user.
+pub(crate) fn repair_rejected_item(
+    input: &mut [ResponseItem],
+    max_output_bytes: usize,
+    strip_images: bool,
+) -> Option<String> {
+    input
+        .iter_mut()
+        .rev()
+        .find_map(|item| repair_item(item, max_output_bytes, strip_images))
+}
+
+fn repair_item(
+    item: &mut ResponseItem,
+    max_output_bytes: usize,
+    strip_images: bool,
+) -> Option<String> {
+    let policy = TruncationPolicy::Bytes(max_output_bytes);
+    match item {
+        ResponseItem::FunctionCallOutput { call_id, output } => {
+            let mut changes = Vec::new();
+            if output.content.len() > max_output_bytes {
+                changes.push(format!(
+                    "truncated output of {call_id} ({} bytes)",
+                    output.content.len()
+                ));
+                output.content = truncate_text(&output.content, policy);
+            }
+            if strip_images && let Some(content_items) = output.content_items.as_mut() {
+                let mut images = 0;
+                for content_item in content_items.iter_mut() {
+                    if matches!(
+                        content_item,
+                        FunctionCallOutputContentItem::InputImage { .. }
+                    ) {
+                        *content_item = FunctionCallOutputContentItem::InputText {
+                            text: REMOVED_IMAGE_PLACEHOLDER.to_string(),
+                        };
+                        images += 1;
+                    }
+                }
+                if images > 0 {
+                    changes.push(format!(
+                        "removed {images} image(s) from output of {call_id}"
+                    ));
+                }
+            }
+            (!changes.is_empty()).then(|| changes.join("; "))
+        }
+        ResponseItem::CustomToolCallOutput { call_id, output }
+            if output.len() > max_output_bytes =>
+        {
+            let change = format!("truncated output of {call_id} ({} bytes)", output.len());
+            *output = truncate_text(output, policy);
+            Some(change)
+        }
+        ResponseItem::Message { role, content, .. } if strip_images => {
+            let mut images = 0;
+            for content_item in content.iter_mut() {
+                if matches!(content_item, ContentItem::InputImage { .. }) {
+                    *content_item = ContentItem::InputText {
+                        text: REMOVED_IMAGE_PLACEHOLDER.to_string(),
+                    };
+                    images += 1;
+                }
+            }
+            (images > 0).then(|| format!("removed {images} image(s) from a {role} message"))
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 #[path = "history_tests.rs"]
 mod tests;
@@ -123,6 +123,31 @@ fn filters_non_api_messages() {
     );
 }
 
+#[test]
+fn side_token_usage_counts_in_totals_but_not_context() {
+    let mut history = ContextManager::new();
+    let turn = TokenUsage {
+        input_tokens: 90_000,
+        output_tokens: 1_000,
+        total_tokens: 91_000,
+        ..TokenUsage::default()
+    };
+    let repair = TokenUsage {
+        input_tokens: 400,
+        output_tokens: 100,
+        total_tokens: 500,
+        ..TokenUsage::default()
+    };
+
+    history.update_token_info(&turn, Some(200_000));
+    history.add_side_token_usage(&repair, Some(200_000));
+
+    let info = history.token_info().expect("token info");
+    assert_eq!(info.last_token_usage.total_tokens, 91_000);
+    assert_eq!(info.total_token_usage.total_tokens, 91_500);
+    assert_eq!(history.get_total_token_usage(), 91_000);
+}
+
 #[test]
 fn non_last_reasoning_tokens_return_zero_when_no_user_messages() {
     let history = create_history_with_items(vec![reasoning_with_encrypted_content(800)]);
@@ -145,6 +170,26 @@ fn non_last_reasoning_tokens_ignore_entries_after_last_user() {
     assert_eq!(history.get_non_last_reasoning_items_tokens(), 32);
 }
 
+#[test]
+fn drop_encrypted_reasoning_keeps_plain_reasoning() {
+    let mut history = create_history_with_items(vec![
+        user_msg("first"),
+        reasoning_with_encrypted_content(100),
+        reasoning_msg("thinking"),
+        assistant_msg("done"),
+    ]);
+
+    assert_eq!(history.drop_encrypted_reasoning(), 1);
+    assert_eq!(
+        history.get_history(),
+        vec![
+            user_msg("first"),
+            reasoning_msg("thinking"),
+            assistant_msg("done"),
+        ]
+    );
+}
+
 #[test]
 fn get_history_for_prompt_drops_ghost_commits() {
     let items = vec![ResponseItem::GhostSnapshot {
@@ -314,6 +359,83 @@ fn record_items_truncates_function_call_output_content() {
     }
 }
 
+#[test]
+fn replace_call_arguments_updates_the_recorded_call() {
+    let call = |arguments: &str| ResponseItem::FunctionCall {
+        id: None,
+        name: "shell".to_string(),
+        arguments: arguments.to_string(),
+        call_id: "call-1".to_string(),
+    };
+    let output = ResponseItem::FunctionCallOutput {
+        call_id: "call-1".to_string(),
+        output: FunctionCallOutputPayload {
+            content: "ok".to_string(),
+            ..Default::default()
+        },
+    };
+    let mut history = create_history_with_items(vec![call(r#"{"command":"ls"}"#), output.clone()]);
+
+    assert!(history.replace_call_arguments("call-1", r#"{"command":["ls"]}"#.to_string()));
+    assert!(!history.replace_call_arguments("call-2", "{}".to_string()));
+    assert_eq!(
+        history.get_history(),
+        vec![call(r#"{"command":["ls"]}"#), output]
+    );
+}
+
+#[test]
+fn repair_rejected_item_repairs_only_the_newest_offending_item() {
+    let mut input = vec![
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![
+                ContentItem::InputText {
+                    text: "look".to_string(),
+                },
+                ContentItem::InputImage {
+                    image_url: "data:image/png;base64,AAAA".to_string(),
+                },
+            ],
+        },
+        ResponseItem::CustomToolCallOutput {
+            call_id: "call-1".to_string(),
+            output: "x".repeat(500),
+        },
+        ResponseItem::CustomToolCallOutput {
+            call_id: "call-2".to_string(),
+            output: "y".repeat(500),
+        },
+    ];
+    let original = input.clone();
+
+    assert_eq!(
+        repair_rejected_item(&mut input, 100, false),
+        Some("truncated output of call-2 (500 bytes)".to_string())
+    );
+    assert_eq!(input[..2], original[..2]);
+    let ResponseItem::CustomToolCallOutput { output, .. } = &input[2] else {
+        panic!("expected tool output, got {:?}", input[2]);
+    };
+    assert!(output.len() < 500, "output was not truncated: {output}");
+
+    let mut input = original[..1].to_vec();
+    assert_eq!(repair_rejected_item(&mut input, 100, false), None);
+    assert_eq!(
+        repair_rejected_item(&mut input, 100, true),
+        Some("removed 1 image(s) from a user message".to_string())
+    );
+    let ResponseItem::Message { content, .. } = &input[0] else {
+        panic!("expected user message, got {:?}", input[0]);
+    };
+    assert!(
+        !content
+            .iter()
+            .any(|item| matches!(item, ContentItem::InputImage { .. }))
+    );
+}
+
 #[test]
 fn record_items_truncates_custom_tool_call_output_content() {
     let mut history = ContextManager::new();
@@ -367,6 +489,29 @@ fn record_items_respects_custom_token_limit() {
     assert!(stored.content.contains("tokens truncated"));
 }
 
+#[test]
+fn record_items_reports_truncated_output_bytes() {
+    let mut history = ContextManager::new();
+    let policy = TruncationPolicy::Tokens(10);
+    let long_output = "tokenized content repeated many times ".repeat(200);
+    let item = ResponseItem::FunctionCallOutput {
+        call_id: "call-truncated-bytes".to_string(),
+        output: FunctionCallOutputPayload {
+            content: long_output.clone(),
+            success: Some(true),
+            ..Default::default()
+        },
+    };
+
+    let truncated = history.record_items([&item, &assistant_msg("done")], policy);
+
+    let ResponseItem::FunctionCallOutput { output, .. } = &history.items[0] else {
+        panic!("unexpected history item: {:?}", history.items[0]);
+    };
+    assert_eq!(truncated, long_output.len() - output.content.len());
+    assert_eq!(history.record_items([&assistant_msg("short")], policy), 0);
+}
+
 fn assert_truncated_message_matches(message: &str, line: &str, expected_removed: usize) {
     let pattern = truncated_message_pattern(line);
     let regex = Regex::new(&pattern).unwrap_or_else(|err| {
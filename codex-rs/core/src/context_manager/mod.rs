@@ -1,4 +1,8 @@
+mod filter;
 mod history;
 mod normalize;
 
+pub(crate) use filter::HistoryFilterReport;
+pub(crate) use filter::apply_history_filter;
 pub(crate) use history::ContextManager;
+pub(crate) use history::repair_rejected_item;
@@ -0,0 +1,394 @@
+//! Breakdown of the context sent to the model for a single request.
+//!
+//! Every item of a [`Prompt`] is classified into a [`ContextComponentKind`] and
+//! sized with the same byte-based heuristic used for history token estimates.
+//! Conversation turns are aggregated into a single component so the manifest
+//! stays small for long sessions.
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::ContextComponentKind;
+use codex_protocol::protocol::ContextManifestComponent;
+use codex_protocol::protocol::ContextManifestEvent;
+use codex_protocol::protocol::ENVIRONMENT_CONTEXT_OPEN_TAG;
+
+use crate::client_common::Prompt;
+use crate::compact::is_summary_message;
+use crate::external_edits::ExternalEdit;
+use crate::file_mentions::FileAttachment;
+use crate::memories::MEMORIES_SEPARATOR;
+use crate::memories::RelevantMemories;
+use crate::openai_models::model_family::ModelFamily;
+use crate::project_instructions::ProjectInstructions;
+use crate::repo_map::RepoMap;
+use crate::session_environment::SystemInfo;
+use crate::tool_hints::ToolHints;
+use crate::truncate::approx_token_count;
+use crate::user_instructions::SkillInstructions;
+use crate::user_instructions::UserInstructions;
+
+/// `project_instructions` are counted apart from the rest of the base
+/// instructions when the prompt's instructions include them.
+pub(crate) fn build_context_manifest(
+    prompt: &Prompt,
+    model_family: &ModelFamily,
+    project_instructions: Option<&ProjectInstructions>,
+) -> ContextManifestEvent {
+    let instructions = prompt.get_full_instructions(model_family);
+    let project = project_instructions.filter(|project| instructions.contains(&project.text));
+    let project_tokens = project.map_or(0, |project| approx_tokens(&project.text));
+    let mut components = vec![component(
+        ContextComponentKind::BaseInstructions,
+        "base instructions",
+        approx_tokens(&instructions).saturating_sub(project_tokens),
+    )];
+    if let Some(project) = project {
+        components.push(component(
+            ContextComponentKind::ProjectBaseInstructions,
+            &project.path.display().to_string(),
+            project_tokens,
+        ));
+    }
+
+    let mut history_items = 0usize;
+    let mut history_tokens = 0i64;
+    for item in &prompt.input {
+        if let Some(mut classified) = classify_context_item(item) {
+            components.append(&mut classified);
+            continue;
+        }
+        if matches!(item, ResponseItem::GhostSnapshot { .. }) {
+            continue;
+        }
+        history_items += 1;
+        history_tokens = history_tokens.saturating_add(approx_item_tokens(item));
+    }
+
+    if history_items > 0 {
+        components.push(component(
+            ContextComponentKind::ConversationHistory,
+            &format!("{history_items} items"),
+            history_tokens,
+        ));
+    }
+
+    if !prompt.tools.is_empty() {
+        let serialized = serde_json::to_string(&prompt.tools).unwrap_or_default();
+        components.push(component(
+            ContextComponentKind::Tools,
+            &format!("{} tools", prompt.tools.len()),
+            approx_tokens(&serialized),
+        ));
+    }
+
+    let total_tokens = components
+        .iter()
+        .fold(0i64, |acc, component| acc.saturating_add(component.tokens));
+    ContextManifestEvent {
+        components,
+        total_tokens,
+    }
+}
+
+/// Returns the components for `item` when it is injected context rather than
+/// part of the conversation itself.
+fn classify_context_item(item: &ResponseItem) -> Option<Vec<ContextManifestComponent>> {
+    let ResponseItem::Message { role, content, .. } = item else {
+        return match item {
+            ResponseItem::Compaction { encrypted_content } => Some(vec![component(
+                ContextComponentKind::HistorySummary,
+                "compacted history",
+                approx_tokens(encrypted_content),
+            )]),
+            _ => None,
+        };
+    };
+
+    if role == "developer" {
+        return Some(vec![component(
+            ContextComponentKind::DeveloperInstructions,
+            "developer instructions",
+            approx_content_tokens(content),
+        )]);
+    }
+
+    if UserInstructions::is_user_instructions(content) {
+        return Some(user_instructions_components(content));
+    }
+
+    let [ContentItem::InputText { text }] = content.as_slice() else {
+        return None;
+    };
+    if SkillInstructions::is_skill_instructions(content) {
+        let name = tag_value(text, "<name>", "</name>").unwrap_or("skill");
+        return Some(vec![component(
+            ContextComponentKind::Skill,
+            name,
+            approx_tokens(text),
+        )]);
+    }
+    if RepoMap::is_repo_map(content) {
+        return Some(vec![component(
+            ContextComponentKind::RepoMap,
+            "repository map",
+            approx_tokens(text),
+        )]);
+    }
+    if RelevantMemories::is_relevant_memories(content) {
+        return Some(vec![component(
+            ContextComponentKind::Memories,
+            "relevant memories",
+            approx_tokens(text),
+        )]);
+    }
+    if ToolHints::is_tool_hints(content) {
+        return Some(vec![component(
+            ContextComponentKind::ToolHints,
+            "tool hints",
+            approx_tokens(text),
+        )]);
+    }
+    if FileAttachment::is_file_attachment(content) {
+        let path = tag_value(text, "path=\"", "\"").unwrap_or("file");
+        return Some(vec![component(
+            ContextComponentKind::AttachedFile,
+            path,
+            approx_tokens(text),
+        )]);
+    }
+    if ExternalEdit::is_external_edit(content) {
+        let path = tag_value(text, "path=\"", "\"").unwrap_or("file");
+        return Some(vec![component(
+            ContextComponentKind::ExternalEdit,
+            path,
+            approx_tokens(text),
+        )]);
+    }
+    if text.trim_start().starts_with(ENVIRONMENT_CONTEXT_OPEN_TAG) {
+        return Some(vec![component(
+            ContextComponentKind::EnvironmentContext,
+            "environment context",
+            approx_tokens(text),
+        )]);
+    }
+    if SystemInfo::is_system_info(content) {
+        return Some(vec![component(
+            ContextComponentKind::EnvironmentContext,
+            "system info",
+            approx_tokens(text),
+        )]);
+    }
+    if is_summary_message(text) {
+        return Some(vec![component(
+            ContextComponentKind::HistorySummary,
+            "conversation summary",
+            approx_tokens(text),
+        )]);
+    }
+
+    None
+}
+
+/// User instructions carry both the `AGENTS.md` contents and, when present,
+/// the memories section appended after [`MEMORIES_SEPARATOR`].
+fn user_instructions_components(content: &[ContentItem]) -> Vec<ContextManifestComponent> {
+    let text = content
+        .iter()
+        .filter_map(|item| match item {
+            ContentItem::InputText { text } => Some(text.as_str()),
+            _ => None,
+        })
+        .collect::<String>();
+
+    match text.split_once(MEMORIES_SEPARATOR) {
+        Some((instructions, memories)) => vec![
+            component(
+                ContextComponentKind::ProjectInstructions,
+                "AGENTS.md",
+                approx_tokens(instructions),
+            ),
+            component(
+                ContextComponentKind::Memories,
+                "memories",
+                approx_tokens(memories),
+            ),
+        ],
+        None => vec![component(
+            ContextComponentKind::ProjectInstructions,
+            "AGENTS.md",
+            approx_tokens(&text),
+        )],
+    }
+}
+
+fn tag_value<'a>(text: &'a str, open: &str, close: &str) -> Option<&'a str> {
+    let start = text.find(open)? + open.len();
+    let len = text[start..].find(close)?;
+    Some(&text[start..start + len])
+}
+
+fn component(kind: ContextComponentKind, label: &str, tokens: i64) -> ContextManifestComponent {
+    ContextManifestComponent {
+        kind,
+        label: label.to_string(),
+        tokens,
+    }
+}
+
+fn approx_tokens(text: &str) -> i64 {
+    i64::try_from(approx_token_count(text)).unwrap_or(i64::MAX)
+}
+
+fn approx_content_tokens(content: &[ContentItem]) -> i64 {
+    content.iter().fold(0i64, |acc, item| match item {
+        ContentItem::InputText { text } | ContentItem::OutputText { text } => {
+            acc.saturating_add(approx_tokens(text))
+        }
+        ContentItem::InputImage { .. } => acc,
+    })
+}
+
+fn approx_item_tokens(item: &ResponseItem) -> i64 {
+    let serialized = serde_json::to_string(item).unwrap_or_default();
+    approx_tokens(&serialized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::test_config;
+    use crate::openai_models::models_manager::ModelsManager;
+    use crate::project_instructions::ProjectInstructionsMode;
+    use pretty_assertions::assert_eq;
+
+    fn user_text(text: &str) -> ResponseItem {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: text.to_string(),
+            }],
+        }
+    }
+
+    #[test]
+    fn classifies_injected_context_and_aggregates_history() {
+        let instructions = format!(
+            "# AGENTS.md instructions for /repo\n\n<INSTRUCTIONS>\nbe nice{MEMORIES_SEPARATOR}## Memories\n- likes tests\n</INSTRUCTIONS>"
+        );
+        let attachment = ResponseItem::from(FileAttachment {
+            path: "src/lib.rs".to_string(),
+            contents: "fn main() {}".to_string(),
+        });
+        let prompt = Prompt {
+            input: vec![
+                user_text(&instructions),
+                user_text("<environment_context>\n</environment_context>"),
+                user_text("hello"),
+                attachment.clone(),
+                user_text("bye"),
+            ],
+            ..Default::default()
+        };
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5.1", &test_config());
+
+        let manifest = build_context_manifest(&prompt, &model_family, None);
+
+        let (agents, memories) = instructions
+            .split_once(MEMORIES_SEPARATOR)
+            .expect("separator");
+        let attachment_text = match &attachment {
+            ResponseItem::Message { content, .. } => match content.as_slice() {
+                [ContentItem::InputText { text }] => text.clone(),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        };
+        let history_tokens =
+            approx_item_tokens(&user_text("hello")) + approx_item_tokens(&user_text("bye"));
+        let expected_components = vec![
+            component(
+                ContextComponentKind::BaseInstructions,
+                "base instructions",
+                approx_tokens(&prompt.get_full_instructions(&model_family)),
+            ),
+            component(
+                ContextComponentKind::ProjectInstructions,
+                "AGENTS.md",
+                approx_tokens(agents),
+            ),
+            component(
+                ContextComponentKind::Memories,
+                "memories",
+                approx_tokens(memories),
+            ),
+            component(
+                ContextComponentKind::EnvironmentContext,
+                "environment context",
+                approx_tokens("<environment_context>\n</environment_context>"),
+            ),
+            component(
+                ContextComponentKind::AttachedFile,
+                "src/lib.rs",
+                approx_tokens(&attachment_text),
+            ),
+            component(
+                ContextComponentKind::ConversationHistory,
+                "2 items",
+                history_tokens,
+            ),
+        ];
+        let total_tokens = expected_components
+            .iter()
+            .map(|component| component.tokens)
+            .sum();
+
+        assert_eq!(
+            manifest,
+            ContextManifestEvent {
+                components: expected_components,
+                total_tokens,
+            }
+        );
+    }
+
+    #[test]
+    fn project_instructions_are_counted_apart_from_base_instructions() {
+        let project = ProjectInstructions {
+            path: "/repo/.codex/instructions.md".into(),
+            mode: ProjectInstructionsMode::Append,
+            text: "Run `just fmt` before finishing.".to_string(),
+        };
+        let prompt = Prompt {
+            model_instructions: Some(project.text.clone()),
+            ..Default::default()
+        };
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5.1", &test_config());
+
+        let manifest = build_context_manifest(&prompt, &model_family, Some(&project));
+
+        let full = approx_tokens(&prompt.get_full_instructions(&model_family));
+        let project_tokens = approx_tokens(&project.text);
+        assert_eq!(
+            manifest.components,
+            vec![
+                component(
+                    ContextComponentKind::BaseInstructions,
+                    "base instructions",
+                    full - project_tokens,
+                ),
+                component(
+                    ContextComponentKind::ProjectBaseInstructions,
+                    "/repo/.codex/instructions.md",
+                    project_tokens,
+                ),
+            ]
+        );
+        assert_eq!(
+            build_context_manifest(&Prompt::default(), &model_family, Some(&project))
+                .components
+                .len(),
+            1
+        );
+    }
+}
@@ -0,0 +1,468 @@
+//! License and advisory reports for dependencies the model adds.
+//!
+//! When a turn ends, every dependency manifest it patched is compared with
+//! the contents from before the turn. Each package that was not listed
+//! before is looked up in its registry for a license and in the OSV database
+//! for known advisories, and the results are sent as a `DependencyReport`
+//! event. Manifests changed by shell commands are not tracked.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_protocol::protocol::AddedDependency;
+use codex_protocol::protocol::DependencyAdvisory;
+use codex_protocol::protocol::DependencyEcosystem;
+use codex_protocol::protocol::DependencyReportEvent;
+use codex_protocol::protocol::EventMsg;
+use futures::future::join_all;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+use tokio::fs;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::default_client::build_reqwest_client;
+
+const LOOKUP_TIMEOUT: Duration = Duration::from_secs(10);
+const OSV_QUERY_URL: &str = "https://api.osv.dev/v1/query";
+
+const CARGO_SECTIONS: [&str; 3] = ["dependencies", "dev-dependencies", "build-dependencies"];
+const NPM_SECTIONS: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    "optionalDependencies",
+    "peerDependencies",
+];
+
+/// A package listed in a manifest after the turn but not before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct NewDependency {
+    pub manifest: PathBuf,
+    pub ecosystem: DependencyEcosystem,
+    pub name: String,
+    pub requirement: Option<String>,
+}
+
+/// The ecosystem whose manifest format `path` uses, if any.
+pub(crate) fn manifest_ecosystem(path: &Path) -> Option<DependencyEcosystem> {
+    let name = path.file_name()?.to_str()?;
+    match name {
+        "Cargo.toml" => Some(DependencyEcosystem::Cargo),
+        "package.json" => Some(DependencyEcosystem::Npm),
+        _ if name.starts_with("requirements") && name.ends_with(".txt") => {
+            Some(DependencyEcosystem::PyPi)
+        }
+        _ => None,
+    }
+}
+
+/// Look up the packages added to `manifests`, given as each manifest's path
+/// and its contents before the turn, and report them to the client.
+pub(crate) async fn report_added_dependencies(
+    sess: &Session,
+    turn_context: &TurnContext,
+    manifests: Vec<(PathBuf, String)>,
+) {
+    let mut added = Vec::new();
+    for (manifest, before) in manifests {
+        // Deleted manifests add nothing.
+        let Ok(after) = fs::read_to_string(&manifest).await else {
+            continue;
+        };
+        added.extend(added_dependencies(&manifest, &before, &after));
+    }
+    if added.is_empty() {
+        return;
+    }
+
+    let client = build_reqwest_client();
+    let dependencies = join_all(added.into_iter().map(|dep| lookup(&client, dep))).await;
+    sess.send_event(
+        turn_context,
+        EventMsg::DependencyReport(DependencyReportEvent {
+            turn_id: turn_context.sub_id.clone(),
+            dependencies,
+        }),
+    )
+    .await;
+}
+
+/// Packages listed in `after` but not in `before`.
+pub(crate) fn added_dependencies(manifest: &Path, before: &str, after: &str) -> Vec<NewDependency> {
+    let Some(ecosystem) = manifest_ecosystem(manifest) else {
+        return Vec::new();
+    };
+    let parse = match ecosystem {
+        DependencyEcosystem::Cargo => cargo_dependencies,
+        DependencyEcosystem::Npm => npm_dependencies,
+        DependencyEcosystem::PyPi => requirements_dependencies,
+    };
+    let previous = parse(before);
+    parse(after)
+        .into_iter()
+        .filter(|(key, _)| !previous.contains_key(key))
+        .map(|(_, (name, requirement))| NewDependency {
+            manifest: manifest.to_path_buf(),
+            ecosystem,
+            name,
+            requirement,
+        })
+        .collect()
+}
+
+/// Dependencies keyed by normalized name, with the name as written and the
+/// version requirement.
+type Dependencies = BTreeMap<String, (String, Option<String>)>;
+
+fn cargo_dependencies(contents: &str) -> Dependencies {
+    let mut deps = Dependencies::new();
+    let Ok(doc) = contents.parse::<toml::Table>() else {
+        return deps;
+    };
+    let mut tables: Vec<&toml::Table> = vec![&doc];
+    if let Some(workspace) = doc.get("workspace").and_then(toml::Value::as_table) {
+        tables.push(workspace);
+    }
+    if let Some(targets) = doc.get("target").and_then(toml::Value::as_table) {
+        tables.extend(targets.values().filter_map(toml::Value::as_table));
+    }
+    for table in tables {
+        for section in CARGO_SECTIONS {
+            let Some(entries) = table.get(section).and_then(toml::Value::as_table) else {
+                continue;
+            };
+            for (key, spec) in entries {
+                let (name, requirement) = match spec {
+                    toml::Value::String(version) => (key.clone(), Some(version.clone())),
+                    toml::Value::Table(spec) => {
+                        // Path, git, and workspace-inherited dependencies do
+                        // not come from the registry.
+                        if ["path", "git", "workspace"]
+                            .iter()
+                            .any(|source| spec.contains_key(*source))
+                        {
+                            continue;
+                        }
+                        let name = spec
+                            .get("package")
+                            .and_then(toml::Value::as_str)
+                            .unwrap_or(key);
+                        let version = spec
+                            .get("version")
+                            .and_then(toml::Value::as_str)
+                            .map(str::to_string);
+                        (name.to_string(), version)
+                    }
+                    _ => continue,
+                };
+                deps.insert(name.replace('_', "-"), (name, requirement));
+            }
+        }
+    }
+    deps
+}
+
+fn npm_dependencies(contents: &str) -> Dependencies {
+    let mut deps = Dependencies::new();
+    let Ok(doc) = serde_json::from_str::<JsonValue>(contents) else {
+        return deps;
+    };
+    for section in NPM_SECTIONS {
+        let Some(entries) = doc.get(section).and_then(JsonValue::as_object) else {
+            continue;
+        };
+        for (name, spec) in entries {
+            let Some(spec) = spec.as_str() else {
+                continue;
+            };
+            // Skip `file:`, `git+https:`, `workspace:` and other non-registry
+            // sources, and local paths.
+            if spec.contains(':') || spec.starts_with('.') || spec.starts_with('/') {
+                continue;
+            }
+            deps.insert(name.clone(), (name.clone(), Some(spec.to_string())));
+        }
+    }
+    deps
+}
+
+fn requirements_dependencies(contents: &str) -> Dependencies {
+    let mut deps = Dependencies::new();
+    for line in contents.lines() {
+        let line = line.split(" #").next().unwrap_or_default().trim();
+        // Options (`-r`, `-e`, `--index-url`), comments, and direct URLs.
+        if line.is_empty() || line.starts_with(['-', '#']) || line.contains("://") {
+            continue;
+        }
+        let line = line.split(';').next().unwrap_or_default();
+        let name_len = line
+            .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')))
+            .unwrap_or(line.len());
+        let (name, rest) = line.split_at(name_len);
+        if name.is_empty() {
+            continue;
+        }
+        // Drop extras such as `[security]`.
+        let rest = match rest.trim_start().strip_prefix('[') {
+            Some(extras) => extras.split_once(']').map_or("", |(_, rest)| rest),
+            None => rest,
+        };
+        let requirement = Some(rest.trim().to_string()).filter(|spec| !spec.is_empty());
+        let key = name.to_ascii_lowercase().replace(['_', '.'], "-");
+        deps.insert(key, (name.to_string(), requirement));
+    }
+    deps
+}
+
+/// The exact version `requirement` pins, if it pins one.
+fn pinned_version(ecosystem: DependencyEcosystem, requirement: Option<&str>) -> Option<String> {
+    let requirement = requirement?.trim();
+    let version = match ecosystem {
+        DependencyEcosystem::Cargo => requirement.strip_prefix('=')?,
+        DependencyEcosystem::Npm => requirement,
+        DependencyEcosystem::PyPi => requirement.strip_prefix("==")?,
+    }
+    .trim();
+    let exact = !version.is_empty()
+        && version.starts_with(|c: char| c.is_ascii_digit())
+        && version
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'));
+    exact.then(|| version.to_string())
+}
+
+async fn lookup(client: &reqwest::Client, dep: NewDependency) -> AddedDependency {
+    let mut report = AddedDependency {
+        manifest: dep.manifest,
+        ecosystem: dep.ecosystem,
+        name: dep.name,
+        requirement: dep.requirement,
+        resolved_version: None,
+        license: None,
+        advisories: Vec::new(),
+        lookup_error: None,
+    };
+    let pinned = pinned_version(report.ecosystem, report.requirement.as_deref());
+    let result = async {
+        let (version, license) =
+            registry_info(client, report.ecosystem, &report.name, pinned.as_deref()).await?;
+        let advisories = advisories(client, report.ecosystem, &report.name, &version).await?;
+        Ok::<_, anyhow::Error>((version, license, advisories))
+    }
+    .await;
+    match result {
+        Ok((version, license, advisories)) => {
+            report.resolved_version = Some(version);
+            report.license = license;
+            report.advisories = advisories;
+        }
+        Err(err) => report.lookup_error = Some(format!("{err:#}")),
+    }
+    report
+}
+
+async fn get_json(client: &reqwest::Client, url: &str) -> Result<JsonValue> {
+    let response = client
+        .get(url)
+        .timeout(LOOKUP_TIMEOUT)
+        .send()
+        .await
+        .with_context(|| format!("failed to reach {url}"))?;
+    if !response.status().is_success() {
+        return Err(anyhow!("{url} returned {}", response.status()));
+    }
+    response.json().await.context("invalid registry response")
+}
+
+/// The version to report on and its license, from the package registry.
+async fn registry_info(
+    client: &reqwest::Client,
+    ecosystem: DependencyEcosystem,
+    name: &str,
+    pinned: Option<&str>,
+) -> Result<(String, Option<String>)> {
+    match ecosystem {
+        DependencyEcosystem::Cargo => {
+            let version = match pinned {
+                Some(version) => version.to_string(),
+                None => {
+                    let info = get_json(client, &format!("https://crates.io/api/v1/crates/{name}"))
+                        .await?;
+                    json_string(info.pointer("/crate/max_stable_version"))
+                        .or_else(|| json_string(info.pointer("/crate/max_version")))
+                        .ok_or_else(|| anyhow!("crates.io lists no versions of {name}"))?
+                }
+            };
+            let info = get_json(
+                client,
+                &format!("https://crates.io/api/v1/crates/{name}/{version}"),
+            )
+            .await?;
+            Ok((version, json_string(info.pointer("/version/license"))))
+        }
+        DependencyEcosystem::Npm => {
+            let version = pinned.unwrap_or("latest");
+            let info = get_json(
+                client,
+                &format!("https://registry.npmjs.org/{name}/{version}"),
+            )
+            .await?;
+            let version = json_string(info.get("version"))
+                .ok_or_else(|| anyhow!("npm lists no version {version} of {name}"))?;
+            Ok((version, json_string(info.get("license"))))
+        }
+        DependencyEcosystem::PyPi => {
+            let url = match pinned {
+                Some(version) => format!("https://pypi.org/pypi/{name}/{version}/json"),
+                None => format!("https://pypi.org/pypi/{name}/json"),
+            };
+            let info = get_json(client, &url).await?;
+            let version = json_string(info.pointer("/info/version"))
+                .ok_or_else(|| anyhow!("PyPI lists no versions of {name}"))?;
+            let license = json_string(info.pointer("/info/license_expression"))
+                .or_else(|| json_string(info.pointer("/info/license")));
+            Ok((version, license))
+        }
+    }
+}
+
+fn json_string(value: Option<&JsonValue>) -> Option<String> {
+    value
+        .and_then(JsonValue::as_str)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+/// Known advisories for `name` at `version`, from the OSV database.
+async fn advisories(
+    client: &reqwest::Client,
+    ecosystem: DependencyEcosystem,
+    name: &str,
+    version: &str,
+) -> Result<Vec<DependencyAdvisory>> {
+    let osv_ecosystem = match ecosystem {
+        DependencyEcosystem::Cargo => "crates.io",
+        DependencyEcosystem::Npm => "npm",
+        DependencyEcosystem::PyPi => "PyPI",
+    };
+    let response = client
+        .post(OSV_QUERY_URL)
+        .timeout(LOOKUP_TIMEOUT)
+        .json(&json!({
+            "package": { "name": name, "ecosystem": osv_ecosystem },
+            "version": version,
+        }))
+        .send()
+        .await
+        .context("failed to reach the OSV advisory database")?;
+    if !response.status().is_success() {
+        return Err(anyhow!("OSV returned {}", response.status()));
+    }
+    let body: JsonValue = response.json().await.context("invalid OSV response")?;
+    Ok(body
+        .get("vulns")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|vuln| {
+            Some(DependencyAdvisory {
+                id: vuln.get("id")?.as_str()?.to_string(),
+                summary: vuln
+                    .get("summary")
+                    .and_then(JsonValue::as_str)
+                    .map(str::to_string),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn names(deps: &[NewDependency]) -> Vec<(&str, Option<&str>)> {
+        deps.iter()
+            .map(|dep| (dep.name.as_str(), dep.requirement.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn finds_crates_added_to_cargo_toml() {
+        let before = r#"
+[dependencies]
+serde = "1"
+"#;
+        let after = r#"
+[dependencies]
+serde = "1"
+tokio = { version = "1.40", features = ["full"] }
+yaml = { package = "serde_yaml", version = "0.9" }
+local = { path = "../local" }
+
+[target.'cfg(unix)'.dev-dependencies]
+nix = "0.29"
+"#;
+        let deps = added_dependencies(Path::new("/repo/Cargo.toml"), before, after);
+        assert_eq!(
+            names(&deps),
+            vec![
+                ("nix", Some("0.29")),
+                ("serde_yaml", Some("0.9")),
+                ("tokio", Some("1.40")),
+            ]
+        );
+        assert_eq!(deps[0].ecosystem, DependencyEcosystem::Cargo);
+    }
+
+    #[test]
+    fn finds_packages_added_to_package_json_and_requirements() {
+        let before = r#"{"dependencies": {"react": "^18.0.0"}}"#;
+        let after = r#"{
+            "dependencies": {"react": "^18.0.0", "left-pad": "1.3.0", "local": "file:../local"},
+            "devDependencies": {"vitest": "^2.0.0"}
+        }"#;
+        let deps = added_dependencies(Path::new("package.json"), before, after);
+        assert_eq!(
+            names(&deps),
+            vec![("left-pad", Some("1.3.0")), ("vitest", Some("^2.0.0"))]
+        );
+
+        let before = "requests==2.31.0\n";
+        let after = "requests==2.31.0\n# tools\n-r base.txt\nPyYAML[libyaml] >= 6.0 ; python_version > '3.8'\nrich\n";
+        let deps = added_dependencies(Path::new("requirements-dev.txt"), before, after);
+        assert_eq!(
+            names(&deps),
+            vec![("PyYAML", Some(">= 6.0")), ("rich", None)]
+        );
+    }
+
+    #[test]
+    fn pinned_versions_are_looked_up_exactly() {
+        assert_eq!(
+            pinned_version(DependencyEcosystem::PyPi, Some("==2.31.0")),
+            Some("2.31.0".to_string())
+        );
+        assert_eq!(
+            pinned_version(DependencyEcosystem::Npm, Some("1.3.0")),
+            Some("1.3.0".to_string())
+        );
+        assert_eq!(
+            pinned_version(DependencyEcosystem::Npm, Some("^1.3.0")),
+            None
+        );
+        assert_eq!(
+            pinned_version(DependencyEcosystem::Cargo, Some("1.40")),
+            None
+        );
+        assert_eq!(
+            pinned_version(DependencyEcosystem::Cargo, Some("=1.40.0")),
+            Some("1.40.0".to_string())
+        );
+    }
+}
@@ -21,9 +21,21 @@ pub(crate) struct EnvironmentContext {
     pub sandbox_mode: Option<SandboxMode>,
     pub network_access: Option<NetworkAccess>,
     pub writable_roots: Option<Vec<AbsolutePathBuf>>,
+    pub scratch_dir: Option<PathBuf>,
     pub shell: Shell,
 }
 
+/// The coarse sandbox mode reported to the model for `policy`.
+pub(crate) fn sandbox_mode_for_policy(policy: &SandboxPolicy) -> SandboxMode {
+    match policy {
+        SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
+            SandboxMode::DangerFullAccess
+        }
+        SandboxPolicy::ReadOnly => SandboxMode::ReadOnly,
+        SandboxPolicy::WorkspaceWrite { .. } => SandboxMode::WorkspaceWrite,
+    }
+}
+
 impl EnvironmentContext {
     pub fn new(
         cwd: Option<PathBuf>,
@@ -34,13 +46,7 @@ impl EnvironmentContext {
         Self {
             cwd,
             approval_policy,
-            sandbox_mode: match sandbox_policy {
-                Some(SandboxPolicy::DangerFullAccess) => Some(SandboxMode::DangerFullAccess),
-                Some(SandboxPolicy::ReadOnly) => Some(SandboxMode::ReadOnly),
-                Some(SandboxPolicy::ExternalSandbox { .. }) => Some(SandboxMode::DangerFullAccess),
-                Some(SandboxPolicy::WorkspaceWrite { .. }) => Some(SandboxMode::WorkspaceWrite),
-                None => None,
-            },
+            sandbox_mode: sandbox_policy.as_ref().map(sandbox_mode_for_policy),
             network_access: match sandbox_policy {
                 Some(SandboxPolicy::DangerFullAccess) => Some(NetworkAccess::Enabled),
                 Some(SandboxPolicy::ReadOnly) => Some(NetworkAccess::Restricted),
@@ -64,10 +70,17 @@ impl EnvironmentContext {
                 }
                 _ => None,
             },
+            scratch_dir: None,
             shell,
         }
     }
 
+    /// Report the session's scratch directory to the model.
+    pub fn with_scratch_dir(mut self, scratch_dir: Option<PathBuf>) -> Self {
+        self.scratch_dir = scratch_dir;
+        self
+    }
+
     /// Compares two environment contexts, ignoring the shell. Useful when
     /// comparing turn to turn, since the initial environment_context will
     /// include the shell, and then it is not configurable from turn to turn.
@@ -78,6 +91,7 @@ impl EnvironmentContext {
             sandbox_mode,
             network_access,
             writable_roots,
+            scratch_dir,
             // should compare all fields except shell
             shell: _,
         } = other;
@@ -87,6 +101,7 @@ impl EnvironmentContext {
             && self.sandbox_mode == *sandbox_mode
             && self.network_access == *network_access
             && self.writable_roots == *writable_roots
+            && self.scratch_dir == *scratch_dir
     }
 
     pub fn diff(before: &TurnContext, after: &TurnContext, shell: &Shell) -> Self {
@@ -130,6 +145,7 @@ impl EnvironmentContext {
     ///   <sandbox_mode>...</sandbox_mode>
     ///   <writable_roots>...</writable_roots>
     ///   <network_access>...</network_access>
+    ///   <scratch_dir>...</scratch_dir>
     ///   <shell>...</shell>
     /// </environment_context>
     /// ```
@@ -161,6 +177,12 @@ impl EnvironmentContext {
             }
             lines.push("  </writable_roots>".to_string());
         }
+        if let Some(scratch_dir) = self.scratch_dir {
+            lines.push(format!(
+                "  <scratch_dir>{}</scratch_dir>",
+                scratch_dir.to_string_lossy()
+            ));
+        }
 
         let shell_name = self.shell.name();
         lines.push(format!("  <shell>{shell_name}</shell>"));
@@ -266,6 +288,31 @@ mod tests {
         assert_eq!(context.serialize_to_xml(), expected);
     }
 
+    #[test]
+    fn serialize_scratch_dir_environment_context() {
+        let scratch_dir = test_tmp_path_buf().join("codex-scratch");
+        let context = EnvironmentContext::new(
+            None,
+            Some(AskForApproval::Never),
+            Some(SandboxPolicy::ReadOnly),
+            fake_shell(),
+        )
+        .with_scratch_dir(Some(scratch_dir.clone()));
+
+        let expected = format!(
+            r#"<environment_context>
+  <approval_policy>never</approval_policy>
+  <sandbox_mode>read-only</sandbox_mode>
+  <network_access>restricted</network_access>
+  <scratch_dir>{}</scratch_dir>
+  <shell>bash</shell>
+</environment_context>"#,
+            scratch_dir.display()
+        );
+
+        assert_eq!(context.serialize_to_xml(), expected);
+    }
+
     #[test]
     fn serialize_external_sandbox_environment_context() {
         let context = EnvironmentContext::new(
@@ -10,6 +10,8 @@ use chrono::Utc;
 use codex_async_utils::CancelErr;
 use codex_protocol::ConversationId;
 use codex_protocol::protocol::CodexErrorInfo;
+use codex_protocol::protocol::ErrorCategory;
+use codex_protocol::protocol::ErrorDetails;
 use codex_protocol::protocol::ErrorEvent;
 use codex_protocol::protocol::RateLimitSnapshot;
 use reqwest::StatusCode;
@@ -21,6 +23,11 @@ use tokio::task::JoinError;
 
 pub type Result<T> = std::result::Result<T, CodexErr>;
 
+const RETRY_HINT: &str = "Check your network connection and proxy settings, then try again.";
+const LOGIN_HINT: &str = "Run `codex login` to sign in again.";
+const SANDBOX_HINT: &str = "Run `codex doctor` to check sandbox support on this machine.";
+const SUB_AGENT_HINT: &str = "Raise the limits under `[sub_agents]` in config.toml.";
+
 /// Limit UI error messages to a reasonable size while keeping useful context.
 const ERROR_MESSAGE_UI_MAX_BYTES: usize = 2 * 1024; // 4 KiB
 
@@ -151,6 +158,10 @@ pub enum CodexErr {
     #[error("Fatal error: {0}")]
     Fatal(String),
 
+    /// A sub-agent would break a `[sub_agents]` limit.
+    #[error("sub-agent budget exceeded: {0}")]
+    SubAgentBudgetExceeded(String),
+
     // -----------------------------------------------------------------
     // Automatic conversions for common external error types
     // -----------------------------------------------------------------
@@ -472,6 +483,129 @@ impl CodexErr {
         ErrorEvent {
             message,
             codex_error_info: Some(self.to_codex_protocol_error()),
+            details: Some(self.to_error_details()),
+        }
+    }
+
+    /// Subsystem this error originated in.
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            CodexErr::UnexpectedStatus(err) if err.status == StatusCode::UNAUTHORIZED => {
+                ErrorCategory::Auth
+            }
+            CodexErr::Stream(..)
+            | CodexErr::ContextWindowExceeded
+            | CodexErr::UnexpectedStatus(_)
+            | CodexErr::InvalidRequest(_)
+            | CodexErr::InvalidImageRequest()
+            | CodexErr::UsageLimitReached(_)
+            | CodexErr::ResponseStreamFailed(_)
+            | CodexErr::ConnectionFailed(_)
+            | CodexErr::QuotaExceeded
+            | CodexErr::UsageNotIncluded
+            | CodexErr::InternalServerError
+            | CodexErr::RetryLimit(_) => ErrorCategory::Provider,
+            CodexErr::RefreshTokenFailed(_) | CodexErr::EnvVar(_) => ErrorCategory::Auth,
+            CodexErr::Sandbox(_) | CodexErr::LandlockSandboxExecutableNotProvided => {
+                ErrorCategory::Sandbox
+            }
+            #[cfg(target_os = "linux")]
+            CodexErr::LandlockRuleset(_) | CodexErr::LandlockPathFd(_) => ErrorCategory::Sandbox,
+            CodexErr::Timeout | CodexErr::Spawn | CodexErr::Io(_) => ErrorCategory::Io,
+            CodexErr::ConversationNotFound(_)
+            | CodexErr::SessionConfiguredNotFirstEvent
+            | CodexErr::UnsupportedOperation(_)
+            | CodexErr::Json(_) => ErrorCategory::Protocol,
+            CodexErr::TurnAborted
+            | CodexErr::Interrupted
+            | CodexErr::InternalAgentDied
+            | CodexErr::Fatal(_)
+            | CodexErr::SubAgentBudgetExceeded(_)
+            | CodexErr::TokioJoin(_) => ErrorCategory::Internal,
+        }
+    }
+
+    /// Stable snake_case identifier for this error. Clients may match on it,
+    /// so existing codes must not be renamed.
+    pub fn code(&self) -> &'static str {
+        match self {
+            CodexErr::TurnAborted => "turn_aborted",
+            CodexErr::Stream(..) => "stream_disconnected",
+            CodexErr::ContextWindowExceeded => "context_window_exceeded",
+            CodexErr::ConversationNotFound(_) => "conversation_not_found",
+            CodexErr::SessionConfiguredNotFirstEvent => "session_configured_not_first_event",
+            CodexErr::Timeout => "process_timeout",
+            CodexErr::Spawn => "spawn_failed",
+            CodexErr::Interrupted => "interrupted",
+            CodexErr::UnexpectedStatus(err) if err.status == StatusCode::UNAUTHORIZED => {
+                "unauthorized"
+            }
+            CodexErr::UnexpectedStatus(_) => "unexpected_status",
+            CodexErr::InvalidRequest(_) => "invalid_request",
+            CodexErr::InvalidImageRequest() => "invalid_image",
+            CodexErr::UsageLimitReached(_) => "usage_limit_reached",
+            CodexErr::ResponseStreamFailed(_) => "response_stream_failed",
+            CodexErr::ConnectionFailed(_) => "connection_failed",
+            CodexErr::QuotaExceeded => "quota_exceeded",
+            CodexErr::UsageNotIncluded => "usage_not_included",
+            CodexErr::InternalServerError => "server_overloaded",
+            CodexErr::RetryLimit(_) => "retry_limit_reached",
+            CodexErr::InternalAgentDied => "agent_died",
+            CodexErr::Sandbox(SandboxErr::Denied { .. }) => "sandbox_denied",
+            CodexErr::Sandbox(SandboxErr::Timeout { .. }) => "sandbox_timeout",
+            CodexErr::Sandbox(SandboxErr::Signal(_)) => "sandbox_signal",
+            CodexErr::Sandbox(_) => "sandbox_setup_failed",
+            CodexErr::LandlockSandboxExecutableNotProvided => "sandbox_executable_missing",
+            #[cfg(target_os = "linux")]
+            CodexErr::LandlockRuleset(_) | CodexErr::LandlockPathFd(_) => "sandbox_setup_failed",
+            CodexErr::UnsupportedOperation(_) => "unsupported_operation",
+            CodexErr::RefreshTokenFailed(err) => match err.reason {
+                RefreshTokenFailedReason::Expired => "refresh_token_expired",
+                RefreshTokenFailedReason::Exhausted => "refresh_token_reused",
+                RefreshTokenFailedReason::Revoked => "refresh_token_revoked",
+                RefreshTokenFailedReason::Other => "refresh_token_failed",
+            },
+            CodexErr::Fatal(_) => "fatal",
+            CodexErr::SubAgentBudgetExceeded(_) => "sub_agent_budget_exceeded",
+            CodexErr::Io(_) => "io",
+            CodexErr::Json(_) => "invalid_json",
+            CodexErr::TokioJoin(_) => "task_failed",
+            CodexErr::EnvVar(_) => "missing_env_var",
+        }
+    }
+
+    /// A next step for the user, for errors whose message does not already
+    /// say what to do.
+    pub fn remediation(&self) -> Option<String> {
+        let hint = match self {
+            CodexErr::Stream(..)
+            | CodexErr::ConnectionFailed(_)
+            | CodexErr::ResponseStreamFailed(_)
+            | CodexErr::RetryLimit(_) => RETRY_HINT,
+            CodexErr::UnexpectedStatus(err) if err.status == StatusCode::UNAUTHORIZED => LOGIN_HINT,
+            CodexErr::EnvVar(err) => {
+                return Some(format!(
+                    "Set `{}` in the environment Codex is started from.",
+                    err.var
+                ));
+            }
+            CodexErr::Sandbox(
+                SandboxErr::Denied { .. } | SandboxErr::Timeout { .. } | SandboxErr::Signal(_),
+            ) => return None,
+            CodexErr::Sandbox(_) | CodexErr::LandlockSandboxExecutableNotProvided => SANDBOX_HINT,
+            #[cfg(target_os = "linux")]
+            CodexErr::LandlockRuleset(_) | CodexErr::LandlockPathFd(_) => SANDBOX_HINT,
+            CodexErr::SubAgentBudgetExceeded(_) => SUB_AGENT_HINT,
+            _ => return None,
+        };
+        Some(hint.to_string())
+    }
+
+    pub fn to_error_details(&self) -> ErrorDetails {
+        ErrorDetails {
+            category: self.category(),
+            code: self.code().to_string(),
+            remediation: self.remediation(),
         }
     }
 
@@ -658,6 +792,64 @@ mod tests {
                 http_status_code: Some(429)
             })
         );
+        assert_eq!(
+            event.details,
+            Some(ErrorDetails {
+                category: ErrorCategory::Provider,
+                code: "response_stream_failed".to_string(),
+                remediation: Some(RETRY_HINT.to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn error_details_classify_errors() {
+        let unauthorized = CodexErr::UnexpectedStatus(UnexpectedResponseError {
+            status: StatusCode::UNAUTHORIZED,
+            body: String::new(),
+            request_id: None,
+        });
+        assert_eq!(
+            unauthorized.to_error_details(),
+            ErrorDetails {
+                category: ErrorCategory::Auth,
+                code: "unauthorized".to_string(),
+                remediation: Some(LOGIN_HINT.to_string()),
+            }
+        );
+
+        let missing_key = CodexErr::EnvVar(EnvVarError {
+            var: "OPENAI_API_KEY".to_string(),
+            instructions: None,
+        });
+        assert_eq!(
+            missing_key.to_error_details(),
+            ErrorDetails {
+                category: ErrorCategory::Auth,
+                code: "missing_env_var".to_string(),
+                remediation: Some(
+                    "Set `OPENAI_API_KEY` in the environment Codex is started from.".to_string()
+                ),
+            }
+        );
+
+        assert_eq!(
+            CodexErr::LandlockSandboxExecutableNotProvided.to_error_details(),
+            ErrorDetails {
+                category: ErrorCategory::Sandbox,
+                code: "sandbox_executable_missing".to_string(),
+                remediation: Some(SANDBOX_HINT.to_string()),
+            }
+        );
+
+        assert_eq!(
+            CodexErr::ContextWindowExceeded.to_error_details(),
+            ErrorDetails::new(ErrorCategory::Provider, "context_window_exceeded")
+        );
+        assert_eq!(
+            CodexErr::Io(io::Error::other("disk full")).to_error_details(),
+            ErrorDetails::new(ErrorCategory::Io, "io")
+        );
     }
 
     #[test]
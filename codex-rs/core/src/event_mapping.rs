@@ -13,6 +13,12 @@ use codex_protocol::user_input::UserInput;
 use tracing::warn;
 use uuid::Uuid;
 
+use crate::external_edits::ExternalEdit;
+use crate::file_mentions::FileAttachment;
+use crate::memories::RelevantMemories;
+use crate::repo_map::RepoMap;
+use crate::session_environment::SystemInfo;
+use crate::tool_hints::ToolHints;
 use crate::user_instructions::SkillInstructions;
 use crate::user_instructions::UserInstructions;
 use crate::user_shell_command::is_user_shell_command_text;
@@ -26,6 +32,12 @@ fn is_session_prefix(text: &str) -> bool {
 fn parse_user_message(message: &[ContentItem]) -> Option<UserMessageItem> {
     if UserInstructions::is_user_instructions(message)
         || SkillInstructions::is_skill_instructions(message)
+        || FileAttachment::is_file_attachment(message)
+        || ExternalEdit::is_external_edit(message)
+        || RepoMap::is_repo_map(message)
+        || ToolHints::is_tool_hints(message)
+        || RelevantMemories::is_relevant_memories(message)
+        || SystemInfo::is_system_info(message)
     {
         return None;
     }
@@ -10,10 +10,12 @@ use std::time::Duration;
 use std::time::Instant;
 
 use async_channel::Sender;
+use codex_utils_pty::SpawnedPty;
 use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
 use tokio::io::BufReader;
 use tokio::process::Child;
+use tokio::sync::broadcast::error::RecvError;
 use tokio_util::sync::CancellationToken;
 
 use crate::error::CodexErr;
@@ -29,6 +31,7 @@ use crate::sandboxing::CommandSpec;
 use crate::sandboxing::ExecEnv;
 use crate::sandboxing::SandboxManager;
 use crate::sandboxing::SandboxPermissions;
+use crate::spawn::CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR;
 use crate::spawn::StdioPolicy;
 use crate::spawn::spawn_child_async;
 use crate::text_encoding::bytes_to_string_smart;
@@ -46,6 +49,14 @@ const EXEC_TIMEOUT_EXIT_CODE: i32 = 124; // conventional timeout exit code
 const READ_CHUNK_SIZE: usize = 8192; // bytes per read
 const AGGREGATE_BUFFER_INITIAL_CAPACITY: usize = 8 * 1024; // 8 KiB
 
+/// Commands see a color terminal in the PTY, so pagers must not wait for
+/// keypresses nobody will send.
+const PTY_EXEC_ENV: [(&str, &str); 3] = [
+    ("TERM", "xterm-256color"),
+    ("PAGER", "cat"),
+    ("GIT_PAGER", "cat"),
+];
+
 /// Limit the number of ExecCommandOutputDelta events emitted per exec call.
 /// Aggregation still collects full output; only the live event stream is capped.
 pub(crate) const MAX_EXEC_OUTPUT_DELTAS_PER_CALL: usize = 10_000;
@@ -216,6 +227,43 @@ pub(crate) async fn execute_exec_env(
     finalize_exec_result(raw_output_result, sandbox, duration)
 }
 
+/// Runs `env` attached to a pseudo-terminal. The Windows restricted-token
+/// sandbox has its own capture pipeline, so it keeps using pipes.
+pub(crate) async fn execute_exec_env_in_pty(
+    env: ExecEnv,
+    sandbox_policy: &SandboxPolicy,
+    stdout_stream: Option<StdoutStream>,
+) -> Result<ExecToolCallOutput> {
+    if env.sandbox == SandboxType::WindowsRestrictedToken {
+        return execute_exec_env(env, sandbox_policy, stdout_stream).await;
+    }
+    let ExecEnv {
+        command,
+        cwd,
+        env,
+        expiration,
+        sandbox,
+        sandbox_permissions,
+        justification,
+        arg0,
+    } = env;
+
+    let params = ExecParams {
+        command,
+        cwd,
+        expiration,
+        env,
+        sandbox_permissions,
+        justification,
+        arg0,
+    };
+
+    let start = Instant::now();
+    let raw_output_result = exec_in_pty(params, sandbox_policy, stdout_stream).await;
+    let duration = start.elapsed();
+    finalize_exec_result(raw_output_result, sandbox, duration)
+}
+
 #[cfg(target_os = "windows")]
 async fn exec_windows_sandbox(
     params: ExecParams,
@@ -233,6 +281,7 @@ async fn exec_windows_sandbox(
         expiration,
         ..
     } = params;
+    let command = crate::powershell::encode_for_exec(command);
     // TODO(iceweasel-oai): run_windows_sandbox_capture should support all
     // variants of ExecExpiration, not just timeout.
     let timeout_ms = expiration.timeout_ms();
@@ -540,6 +589,8 @@ async fn exec(
         expiration,
         ..
     } = params;
+    #[cfg(target_os = "windows")]
+    let command = crate::powershell::encode_for_exec(command);
 
     let (program, args) = command.split_first().ok_or_else(|| {
         CodexErr::Io(io::Error::new(
@@ -561,6 +612,137 @@ async fn exec(
     consume_truncated_output(child, expiration, stdout_stream).await
 }
 
+/// The environment of a PTY command: what [`spawn_child_async`] would set,
+/// plus the terminal and pager variables.
+fn apply_pty_exec_env(
+    mut env: HashMap<String, String>,
+    sandbox_policy: &SandboxPolicy,
+) -> HashMap<String, String> {
+    if !sandbox_policy.has_full_network_access() {
+        env.insert(
+            CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR.to_string(),
+            "1".to_string(),
+        );
+    }
+    for (key, value) in PTY_EXEC_ENV {
+        env.insert(key.to_string(), value.to_string());
+    }
+    env
+}
+
+/// Mark where output the PTY reader outran this receiver went missing, so
+/// the model knows the output has a gap.
+fn insert_dropped_notice(output: &mut Vec<u8>, position: usize, dropped: u64) {
+    let notice = format!("\n[... {dropped} bytes of output dropped ...]\n");
+    let position = position.min(output.len());
+    output.splice(position..position, notice.into_bytes());
+}
+
+/// Runs `params` on a terminal. The terminal merges stderr into stdout, so
+/// all output is reported as stdout.
+async fn exec_in_pty(
+    params: ExecParams,
+    sandbox_policy: &SandboxPolicy,
+    stdout_stream: Option<StdoutStream>,
+) -> Result<RawExecToolCallOutput> {
+    let ExecParams {
+        command,
+        cwd,
+        env,
+        arg0,
+        expiration,
+        ..
+    } = params;
+    #[cfg(target_os = "windows")]
+    let command = crate::powershell::encode_for_exec(command);
+    let env = apply_pty_exec_env(env, sandbox_policy);
+
+    let (program, args) = command.split_first().ok_or_else(|| {
+        CodexErr::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "command args are empty",
+        ))
+    })?;
+    let SpawnedPty {
+        session,
+        mut output_rx,
+        mut exit_rx,
+    } = codex_utils_pty::spawn_pty_process(program, args, &cwd, &env, &arg0)
+        .await
+        .map_err(|err| CodexErr::Io(io::Error::other(format!("failed to spawn PTY: {err}"))))?;
+
+    let mut output = Vec::with_capacity(AGGREGATE_BUFFER_INITIAL_CAPACITY);
+    let mut emitted_deltas: usize = 0;
+    let mut received_bytes: u64 = 0;
+    let mut lagged_at: Option<usize> = None;
+    let expiration = expiration.wait();
+    tokio::pin!(expiration);
+
+    let (exit_status, timed_out) = loop {
+        tokio::select! {
+            chunk = output_rx.recv() => match chunk {
+                Ok(chunk) => {
+                    received_bytes += chunk.len() as u64;
+                    emit_output_delta(stdout_stream.as_ref(), &chunk, false, &mut emitted_deltas)
+                        .await;
+                    append_all(&mut output, &chunk);
+                }
+                Err(RecvError::Lagged(_)) => {
+                    lagged_at.get_or_insert(output.len());
+                }
+                Err(RecvError::Closed) => {
+                    let code = (&mut exit_rx).await.unwrap_or(-1);
+                    break (exit_status_from_code(code), false);
+                }
+            },
+            code = &mut exit_rx => {
+                break (exit_status_from_code(code.unwrap_or(-1)), false);
+            }
+            _ = &mut expiration => {
+                session.terminate();
+                break (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + TIMEOUT_CODE), true);
+            }
+            _ = tokio::signal::ctrl_c() => {
+                session.terminate();
+                break (synthetic_exit_status(EXIT_CODE_SIGNAL_BASE + SIGKILL_CODE), false);
+            }
+        }
+    };
+
+    // The reader may still hold output written just before the child exited.
+    // The session keeps the channel open, so stop once it goes quiet.
+    const PTY_DRAIN_IDLE_MS: u64 = 50;
+    while let Ok(Ok(chunk)) =
+        tokio::time::timeout(Duration::from_millis(PTY_DRAIN_IDLE_MS), output_rx.recv()).await
+    {
+        received_bytes += chunk.len() as u64;
+        emit_output_delta(stdout_stream.as_ref(), &chunk, false, &mut emitted_deltas).await;
+        append_all(&mut output, &chunk);
+    }
+    session.terminate();
+    if let Some(position) = lagged_at {
+        let dropped = session.output_bytes().saturating_sub(received_bytes);
+        insert_dropped_notice(&mut output, position, dropped);
+    }
+
+    Ok(RawExecToolCallOutput {
+        exit_status,
+        stdout: StreamOutput {
+            text: output.clone(),
+            truncated_after_lines: None,
+        },
+        stderr: StreamOutput {
+            text: Vec::new(),
+            truncated_after_lines: None,
+        },
+        aggregated_output: StreamOutput {
+            text: output,
+            truncated_after_lines: None,
+        },
+        timed_out,
+    })
+}
+
 /// Consumes the output of a child process, truncating it so it is suitable for
 /// use as the output of a `shell` tool call. Also enforces specified timeout.
 async fn consume_truncated_output(
@@ -700,27 +882,7 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
             break;
         }
 
-        if let Some(stream) = &stream
-            && emitted_deltas < MAX_EXEC_OUTPUT_DELTAS_PER_CALL
-        {
-            let chunk = tmp[..n].to_vec();
-            let msg = EventMsg::ExecCommandOutputDelta(ExecCommandOutputDeltaEvent {
-                call_id: stream.call_id.clone(),
-                stream: if is_stderr {
-                    ExecOutputStream::Stderr
-                } else {
-                    ExecOutputStream::Stdout
-                },
-                chunk,
-            });
-            let event = Event {
-                id: stream.sub_id.clone(),
-                msg,
-            };
-            #[allow(clippy::let_unit_value)]
-            let _ = stream.tx_event.send(event).await;
-            emitted_deltas += 1;
-        }
+        emit_output_delta(stream.as_ref(), &tmp[..n], is_stderr, &mut emitted_deltas).await;
 
         if let Some(tx) = &aggregate_tx {
             let _ = tx.send(tmp[..n].to_vec()).await;
@@ -736,6 +898,47 @@ async fn read_capped<R: AsyncRead + Unpin + Send + 'static>(
     })
 }
 
+async fn emit_output_delta(
+    stream: Option<&StdoutStream>,
+    chunk: &[u8],
+    is_stderr: bool,
+    emitted_deltas: &mut usize,
+) {
+    let Some(stream) = stream else {
+        return;
+    };
+    if *emitted_deltas >= MAX_EXEC_OUTPUT_DELTAS_PER_CALL {
+        return;
+    }
+    let msg = EventMsg::ExecCommandOutputDelta(ExecCommandOutputDeltaEvent {
+        call_id: stream.call_id.clone(),
+        stream: if is_stderr {
+            ExecOutputStream::Stderr
+        } else {
+            ExecOutputStream::Stdout
+        },
+        chunk: chunk.to_vec(),
+    });
+    let event = Event {
+        id: stream.sub_id.clone(),
+        msg,
+    };
+    #[allow(clippy::let_unit_value)]
+    let _ = stream.tx_event.send(event).await;
+    *emitted_deltas += 1;
+}
+
+/// Exit status for a process that exited normally with `code`.
+#[cfg(unix)]
+fn exit_status_from_code(code: i32) -> ExitStatus {
+    synthetic_exit_status((code & 0xff) << 8)
+}
+
+#[cfg(windows)]
+fn exit_status_from_code(code: i32) -> ExitStatus {
+    synthetic_exit_status(code)
+}
+
 #[cfg(unix)]
 fn synthetic_exit_status(code: i32) -> ExitStatus {
     use std::os::unix::process::ExitStatusExt;
@@ -787,6 +990,36 @@ mod tests {
     use super::*;
     use std::time::Duration;
 
+    #[test]
+    fn pty_env_disables_pagers_and_sets_term() {
+        let env = apply_pty_exec_env(
+            HashMap::from([
+                ("TERM".to_string(), "dumb".to_string()),
+                ("PAGER".to_string(), "less".to_string()),
+                ("HOME".to_string(), "/home/me".to_string()),
+            ]),
+            &SandboxPolicy::DangerFullAccess,
+        );
+
+        assert_eq!(env.get("TERM").map(String::as_str), Some("xterm-256color"));
+        assert_eq!(env.get("PAGER").map(String::as_str), Some("cat"));
+        assert_eq!(env.get("GIT_PAGER").map(String::as_str), Some("cat"));
+        assert_eq!(env.get("HOME").map(String::as_str), Some("/home/me"));
+        assert_eq!(env.get(CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR), None);
+    }
+
+    #[test]
+    fn dropped_pty_output_is_marked_where_it_went_missing() {
+        let mut output = b"first\nlast\n".to_vec();
+
+        insert_dropped_notice(&mut output, 6, 16_384);
+
+        assert_eq!(
+            String::from_utf8_lossy(&output),
+            "first\n\n[... 16384 bytes of output dropped ...]\nlast\n"
+        );
+    }
+
     fn make_exec_output(
         exit_code: i32,
         stdout: &str,
@@ -911,6 +1144,61 @@ mod tests {
         Ok(())
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn pty_exec_runs_command_on_a_terminal() -> Result<()> {
+        let cwd = std::env::current_dir()?;
+        let env = ExecEnv {
+            command: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                "test -t 1 && printf 'on a tty'; exit 3".to_string(),
+            ],
+            cwd,
+            env: std::env::vars().collect(),
+            expiration: ExecExpiration::Timeout(Duration::from_secs(10)),
+            sandbox: SandboxType::None,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            justification: None,
+            arg0: None,
+        };
+
+        let output = execute_exec_env_in_pty(env, &SandboxPolicy::DangerFullAccess, None).await?;
+
+        assert_eq!(output.exit_code, 3);
+        assert_eq!(output.aggregated_output.text, "on a tty");
+        assert!(output.stderr.text.is_empty());
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn pty_exec_marks_network_as_disabled() -> Result<()> {
+        let cwd = std::env::current_dir()?;
+        let env = ExecEnv {
+            command: vec![
+                "/bin/sh".to_string(),
+                "-c".to_string(),
+                format!("printf %s \"${CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR}\""),
+            ],
+            cwd,
+            env: std::env::vars()
+                .filter(|(key, _)| key != CODEX_SANDBOX_NETWORK_DISABLED_ENV_VAR)
+                .collect(),
+            expiration: ExecExpiration::Timeout(Duration::from_secs(10)),
+            sandbox: SandboxType::None,
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            justification: None,
+            arg0: None,
+        };
+
+        let output =
+            execute_exec_env_in_pty(env, &SandboxPolicy::new_read_only_policy(), None).await?;
+
+        assert_eq!(output.aggregated_output.text, "1");
+        Ok(())
+    }
+
     #[tokio::test]
     async fn process_exec_tool_call_respects_cancellation_token() -> Result<()> {
         let command = long_running_command();
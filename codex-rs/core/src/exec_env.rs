@@ -1,6 +1,7 @@
 use crate::config::types::EnvironmentVariablePattern;
 use crate::config::types::ShellEnvironmentPolicy;
 use crate::config::types::ShellEnvironmentPolicyInherit;
+use crate::login_env::captured_login_env;
 use std::collections::HashMap;
 use std::collections::HashSet;
 
@@ -12,7 +13,25 @@ use std::collections::HashSet;
 /// The derivation follows the algorithm documented in the struct-level comment
 /// for [`ShellEnvironmentPolicy`].
 pub fn create_env(policy: &ShellEnvironmentPolicy) -> HashMap<String, String> {
-    populate_env(std::env::vars(), policy)
+    let login_env = if policy.capture_login_env {
+        captured_login_env()
+    } else {
+        None
+    };
+    match login_env {
+        Some(login_env) => populate_env(with_login_env(std::env::vars(), login_env), policy),
+        None => populate_env(std::env::vars(), policy),
+    }
+}
+
+/// Overlay the captured login shell environment on the inherited variables.
+fn with_login_env<I>(vars: I, login_env: &HashMap<String, String>) -> HashMap<String, String>
+where
+    I: IntoIterator<Item = (String, String)>,
+{
+    let mut merged: HashMap<String, String> = vars.into_iter().collect();
+    merged.extend(login_env.clone());
+    merged
 }
 
 fn populate_env<I>(vars: I, policy: &ShellEnvironmentPolicy) -> HashMap<String, String>
@@ -217,4 +236,25 @@ mod tests {
         };
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn test_login_env_overrides_inherited_values() {
+        let vars = make_vars(&[("PATH", "/usr/bin"), ("HOME", "/home")]);
+        let login_env = hashmap! {
+            "PATH".to_string() => "/home/.nvm/bin:/usr/bin".to_string(),
+            "NVM_DIR".to_string() => "/home/.nvm".to_string(),
+        };
+
+        let policy = ShellEnvironmentPolicy {
+            inherit: ShellEnvironmentPolicyInherit::Core,
+            ..Default::default()
+        };
+        let result = populate_env(with_login_env(vars, &login_env), &policy);
+
+        let expected: HashMap<String, String> = hashmap! {
+            "PATH".to_string() => "/home/.nvm/bin:/usr/bin".to_string(),
+            "HOME".to_string() => "/home".to_string(),
+        };
+        assert_eq!(result, expected);
+    }
 }
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
@@ -224,10 +225,18 @@ pub(crate) async fn create_exec_approval_requirement_for_command(
     approval_policy: AskForApproval,
     sandbox_policy: &SandboxPolicy,
     sandbox_permissions: SandboxPermissions,
+    env_overrides: &HashMap<String, String>,
 ) -> ExecApprovalRequirement {
     let commands = parse_shell_lc_plain_commands(command).unwrap_or_else(|| vec![command.to_vec()]);
+    let env_overridden = !env_overrides.is_empty();
     let heuristics_fallback = |cmd: &[String]| {
-        if requires_initial_appoval(approval_policy, sandbox_policy, cmd, sandbox_permissions) {
+        if requires_initial_appoval(
+            approval_policy,
+            sandbox_policy,
+            cmd,
+            sandbox_permissions,
+            env_overridden,
+        ) {
             Decision::Prompt
         } else {
             Decision::Allow
@@ -257,10 +266,13 @@ pub(crate) async fn create_exec_approval_requirement_for_command(
             }
         }
         Decision::Allow => ExecApprovalRequirement::Skip {
-            // Bypass sandbox if execpolicy allows the command
-            bypass_sandbox: evaluation.matched_rules.iter().any(|rule_match| {
-                is_policy_match(rule_match) && rule_match.decision() == Decision::Allow
-            }),
+            // Bypass sandbox if execpolicy allows the command, unless the call
+            // changes its environment: a rule approves a command, not the
+            // variables it runs with.
+            bypass_sandbox: !env_overridden
+                && evaluation.matched_rules.iter().any(|rule_match| {
+                    is_policy_match(rule_match) && rule_match.decision() == Decision::Allow
+                }),
             proposed_execpolicy_amendment: if features.enabled(Feature::ExecPolicy) {
                 try_derive_execpolicy_amendment_for_allow_rules(&evaluation.matched_rules)
             } else {
@@ -440,6 +452,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             AskForApproval::OnRequest,
             &SandboxPolicy::DangerFullAccess,
             SandboxPermissions::UseDefault,
+            &HashMap::new(),
         )
         .await;
 
@@ -468,6 +481,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             AskForApproval::OnRequest,
             &SandboxPolicy::DangerFullAccess,
             SandboxPermissions::UseDefault,
+            &HashMap::new(),
         )
         .await;
 
@@ -497,6 +511,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             AskForApproval::Never,
             &SandboxPolicy::DangerFullAccess,
             SandboxPermissions::UseDefault,
+            &HashMap::new(),
         )
         .await;
 
@@ -520,6 +535,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             AskForApproval::UnlessTrusted,
             &SandboxPolicy::ReadOnly,
             SandboxPermissions::UseDefault,
+            &HashMap::new(),
         )
         .await;
 
@@ -554,6 +570,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
                 AskForApproval::UnlessTrusted,
                 &SandboxPolicy::DangerFullAccess,
                 SandboxPermissions::UseDefault,
+                &HashMap::new(),
             )
             .await,
             ExecApprovalRequirement::NeedsApproval {
@@ -625,6 +642,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             AskForApproval::UnlessTrusted,
             &SandboxPolicy::ReadOnly,
             SandboxPermissions::UseDefault,
+            &HashMap::new(),
         )
         .await;
 
@@ -651,6 +669,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             AskForApproval::UnlessTrusted,
             &SandboxPolicy::ReadOnly,
             SandboxPermissions::UseDefault,
+            &HashMap::new(),
         )
         .await;
 
@@ -680,6 +699,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             AskForApproval::OnRequest,
             &SandboxPolicy::DangerFullAccess,
             SandboxPermissions::UseDefault,
+            &HashMap::new(),
         )
         .await;
 
@@ -706,6 +726,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             AskForApproval::UnlessTrusted,
             &SandboxPolicy::ReadOnly,
             SandboxPermissions::UseDefault,
+            &HashMap::new(),
         )
         .await;
 
@@ -744,6 +765,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
                 AskForApproval::UnlessTrusted,
                 &SandboxPolicy::ReadOnly,
                 SandboxPermissions::UseDefault,
+                &HashMap::new(),
             )
             .await,
             ExecApprovalRequirement::NeedsApproval {
@@ -766,6 +788,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             AskForApproval::OnRequest,
             &SandboxPolicy::ReadOnly,
             SandboxPermissions::UseDefault,
+            &HashMap::new(),
         )
         .await;
 
@@ -795,6 +818,7 @@ prefix_rule(pattern=["rm"], decision="forbidden")
             AskForApproval::OnRequest,
             &SandboxPolicy::ReadOnly,
             SandboxPermissions::UseDefault,
+            &HashMap::new(),
         )
         .await;
 
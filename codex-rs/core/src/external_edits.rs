@@ -0,0 +1,327 @@
+//! Detection of edits made outside Codex to files the model has seen.
+//!
+//! Files the model reads (with `read_file` or a shell command such as `cat`)
+//! or patches are snapshotted. When the next task
+//! starts, each snapshot is compared with the file on disk and every file that
+//! changed in the meantime is reported to the model as a compact notice with a
+//! diff summary, so it re-reads the file instead of patching stale content.
+//! Snapshots are refreshed when a task ends or is aborted, which keeps
+//! Codex's own shell edits from being reported as external ones.
+//!
+//! The same snapshots let `apply_patch` notice that a file it is about to
+//! write changed after the model last looked at it.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use similar::ChangeTag;
+use similar::TextDiff;
+use tokio::fs;
+
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+pub(crate) const EXTERNAL_EDIT_OPEN_TAG: &str = "<file_changed_outside_codex";
+const EXTERNAL_EDIT_CLOSE_TAG: &str = "</file_changed_outside_codex>";
+
+/// Files larger than this are not snapshotted, so edits to them go unnoticed.
+const MAX_WATCHED_FILE_BYTES: u64 = 1024 * 1024;
+/// At most this many files are watched at once...
+const MAX_WATCHED_FILES: usize = 256;
+/// ...and their snapshots hold at most this many bytes together.
+const MAX_WATCHED_BYTES: usize = 16 * 1024 * 1024;
+/// Budget for the diff excerpt included in each notice.
+const DIFF_EXCERPT_MAX_BYTES: usize = 2 * 1024;
+
+/// Contents of each watched file as Codex last saw it, keyed by absolute path.
+pub(crate) type WatchedFiles = HashMap<PathBuf, String>;
+
+/// How a watched file differs from its snapshot.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ExternalEditKind {
+    Modified {
+        added_lines: usize,
+        removed_lines: usize,
+        diff: String,
+    },
+    Deleted,
+}
+
+/// A watched file that changed since Codex last read or edited it, rendered
+/// as a user-role context message.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ExternalEdit {
+    pub path: String,
+    pub kind: ExternalEditKind,
+}
+
+impl ExternalEdit {
+    pub fn is_external_edit(message: &[ContentItem]) -> bool {
+        if let [ContentItem::InputText { text }] = message {
+            text.starts_with(EXTERNAL_EDIT_OPEN_TAG)
+        } else {
+            false
+        }
+    }
+
+    fn render(&self) -> String {
+        let body = match &self.kind {
+            ExternalEditKind::Modified {
+                added_lines,
+                removed_lines,
+                diff,
+            } => format!(
+                "Modified outside Codex since you last read or edited it (+{added_lines} -{removed_lines} lines). Re-read the file before patching it.\n{diff}"
+            ),
+            ExternalEditKind::Deleted => {
+                "Deleted outside Codex since you last read or edited it.".to_string()
+            }
+        };
+        format!(
+            "{EXTERNAL_EDIT_OPEN_TAG} path=\"{}\">\n{}\n{EXTERNAL_EDIT_CLOSE_TAG}",
+            self.path,
+            body.trim_end()
+        )
+    }
+}
+
+impl From<ExternalEdit> for ResponseItem {
+    fn from(edit: ExternalEdit) -> Self {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: edit.render(),
+            }],
+        }
+    }
+}
+
+/// Read `path` for watching. Returns `None` for missing, oversized, or
+/// non-UTF-8 files.
+pub(crate) async fn snapshot_file(path: &Path) -> Option<String> {
+    let metadata = fs::metadata(path).await.ok()?;
+    if !metadata.is_file() || metadata.len() > MAX_WATCHED_FILE_BYTES {
+        return None;
+    }
+    String::from_utf8(fs::read(path).await.ok()?).ok()
+}
+
+/// Store `contents` as the snapshot of `path`. Once the snapshots reach
+/// [`MAX_WATCHED_FILES`] no new file is watched, and a snapshot that would
+/// take them past [`MAX_WATCHED_BYTES`] is dropped, so edits to that file go
+/// unnoticed.
+pub(crate) fn insert_snapshot(watched: &mut WatchedFiles, path: PathBuf, contents: String) {
+    let replaced = watched.remove(&path);
+    if replaced.is_none() && watched.len() >= MAX_WATCHED_FILES {
+        return;
+    }
+    let bytes: usize = watched.values().map(String::len).sum();
+    if bytes + contents.len() > MAX_WATCHED_BYTES {
+        return;
+    }
+    watched.insert(path, contents);
+}
+
+/// Compare every watched file with its snapshot. Returns the edits found,
+/// with paths shown relative to `cwd` where possible, and the snapshots to
+/// keep watching from now on.
+pub(crate) async fn detect_external_edits(
+    watched: &WatchedFiles,
+    cwd: &Path,
+) -> (Vec<ExternalEdit>, WatchedFiles) {
+    let mut edits = Vec::new();
+    let mut current = WatchedFiles::with_capacity(watched.len());
+
+    let mut paths: Vec<&PathBuf> = watched.keys().collect();
+    paths.sort();
+    for path in paths {
+        let (kind, contents) = check_file(path, &watched[path]).await;
+        if let Some(kind) = kind {
+            edits.push(ExternalEdit {
+                path: path.strip_prefix(cwd).unwrap_or(path).display().to_string(),
+                kind,
+            });
+        }
+        if let Some(contents) = contents {
+            insert_snapshot(&mut current, path.clone(), contents);
+        }
+    }
+
+    (edits, current)
+}
+
+/// Watched files among `paths` that changed on disk since their snapshot.
+/// Paths that are not watched are skipped, since there is nothing to compare.
+pub(crate) async fn changed_files(
+    watched: &WatchedFiles,
+    paths: &[PathBuf],
+) -> Vec<(PathBuf, ExternalEditKind)> {
+    let mut changed = Vec::new();
+    for path in paths {
+        let Some(previous) = watched.get(path) else {
+            continue;
+        };
+        if let (Some(kind), _) = check_file(path, previous).await
+            && !changed.iter().any(|(seen, _)| seen == path)
+        {
+            changed.push((path.clone(), kind));
+        }
+    }
+    changed
+}
+
+/// Compare `path` with `previous`. Returns how it changed, if it did, and the
+/// snapshot to keep watching, which is `None` once the file can no longer be
+/// snapshotted.
+async fn check_file(path: &Path, previous: &str) -> (Option<ExternalEditKind>, Option<String>) {
+    match snapshot_file(path).await {
+        Some(contents) if contents == previous => (None, Some(contents)),
+        Some(contents) => (Some(diff_summary(previous, &contents)), Some(contents)),
+        None if fs::metadata(path).await.is_err() => (Some(ExternalEditKind::Deleted), None),
+        // Grew past the size limit or stopped being text; stop watching.
+        None => (None, None),
+    }
+}
+
+fn diff_summary(previous: &str, current: &str) -> ExternalEditKind {
+    let diff = TextDiff::from_lines(previous, current);
+    let mut added_lines = 0;
+    let mut removed_lines = 0;
+    for change in diff.iter_all_changes() {
+        match change.tag() {
+            ChangeTag::Insert => added_lines += 1,
+            ChangeTag::Delete => removed_lines += 1,
+            ChangeTag::Equal => {}
+        }
+    }
+    let unified = diff.unified_diff().context_radius(1).to_string();
+    ExternalEditKind::Modified {
+        added_lines,
+        removed_lines,
+        diff: truncate_text(&unified, TruncationPolicy::Bytes(DIFF_EXCERPT_MAX_BYTES)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[tokio::test]
+    async fn reports_modified_and_deleted_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let unchanged = dir.path().join("unchanged.rs");
+        let modified = dir.path().join("modified.rs");
+        let deleted = dir.path().join("deleted.rs");
+        std::fs::write(&unchanged, "fn a() {}\n").unwrap();
+        std::fs::write(&modified, "fn b() {}\nfn c() {}\n").unwrap();
+        std::fs::write(&deleted, "fn d() {}\n").unwrap();
+
+        let mut watched = WatchedFiles::new();
+        for path in [&unchanged, &modified, &deleted] {
+            let contents = snapshot_file(path).await.expect("snapshot");
+            watched.insert(path.clone(), contents);
+        }
+
+        std::fs::write(&modified, "fn b() {}\nfn c2() {}\nfn e() {}\n").unwrap();
+        std::fs::remove_file(&deleted).unwrap();
+
+        let (edits, current) = detect_external_edits(&watched, dir.path()).await;
+        assert_eq!(
+            edits,
+            vec![
+                ExternalEdit {
+                    path: "deleted.rs".to_string(),
+                    kind: ExternalEditKind::Deleted,
+                },
+                ExternalEdit {
+                    path: "modified.rs".to_string(),
+                    kind: ExternalEditKind::Modified {
+                        added_lines: 2,
+                        removed_lines: 1,
+                        diff: "@@ -1,2 +1,3 @@\n fn b() {}\n-fn c() {}\n+fn c2() {}\n+fn e() {}\n"
+                            .to_string(),
+                    },
+                },
+            ]
+        );
+        assert_eq!(current.len(), 2);
+        assert!(!current.contains_key(&deleted));
+
+        let (edits, _) = detect_external_edits(&current, dir.path()).await;
+        assert_eq!(edits, Vec::new(), "snapshots were refreshed");
+    }
+
+    #[test]
+    fn insert_snapshot_stops_watching_new_files_at_the_cap() {
+        let mut watched: WatchedFiles = (0..MAX_WATCHED_FILES)
+            .map(|index| (PathBuf::from(format!("/{index}.rs")), String::new()))
+            .collect();
+
+        insert_snapshot(&mut watched, PathBuf::from("/new.rs"), "new".to_string());
+        assert!(!watched.contains_key(Path::new("/new.rs")));
+
+        insert_snapshot(&mut watched, PathBuf::from("/0.rs"), "updated".to_string());
+        assert_eq!(
+            watched.get(Path::new("/0.rs")).map(String::as_str),
+            Some("updated")
+        );
+
+        let too_big = "x".repeat(MAX_WATCHED_BYTES + 1);
+        insert_snapshot(&mut watched, PathBuf::from("/0.rs"), too_big);
+        assert!(!watched.contains_key(Path::new("/0.rs")));
+    }
+
+    #[tokio::test]
+    async fn changed_files_ignores_unwatched_paths() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let watched_path = dir.path().join("watched.rs");
+        let unwatched_path = dir.path().join("unwatched.rs");
+        std::fs::write(&watched_path, "old\n").unwrap();
+        std::fs::write(&unwatched_path, "old\n").unwrap();
+        let watched = WatchedFiles::from([(watched_path.clone(), "old\n".to_string())]);
+
+        std::fs::write(&watched_path, "new\n").unwrap();
+        std::fs::write(&unwatched_path, "new\n").unwrap();
+
+        let changed =
+            changed_files(&watched, &[watched_path.clone(), unwatched_path.clone()]).await;
+        assert_eq!(
+            changed,
+            vec![(
+                watched_path,
+                ExternalEditKind::Modified {
+                    added_lines: 1,
+                    removed_lines: 1,
+                    diff: "@@ -1 +1 @@\n-old\n+new\n".to_string(),
+                },
+            )]
+        );
+    }
+
+    #[test]
+    fn renders_notice_with_summary() {
+        let item = ResponseItem::from(ExternalEdit {
+            path: "src/lib.rs".to_string(),
+            kind: ExternalEditKind::Modified {
+                added_lines: 1,
+                removed_lines: 0,
+                diff: "@@ -1 +1,2 @@\n a\n+b\n".to_string(),
+            },
+        });
+        let ResponseItem::Message { content, .. } = item else {
+            panic!("expected message");
+        };
+        assert!(ExternalEdit::is_external_edit(&content));
+        assert_eq!(
+            content,
+            vec![ContentItem::InputText {
+                text: "<file_changed_outside_codex path=\"src/lib.rs\">\nModified outside Codex since you last read or edited it (+1 -0 lines). Re-read the file before patching it.\n@@ -1 +1,2 @@\n a\n+b\n</file_changed_outside_codex>".to_string(),
+            }]
+        );
+    }
+}
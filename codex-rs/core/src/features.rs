@@ -93,6 +93,56 @@ pub enum Feature {
     Tui2,
     /// Enable discovery and injection of skills.
     Skills,
+    /// Attach the contents of files mentioned as `@path` in user input.
+    FileMentions,
+    /// Include the summarize_file tool for chunked summaries of large files.
+    SummarizeFileTool,
+    /// Inject a repository map at session start and expose the repo_map tool.
+    RepoMap,
+    /// Enable the local embedding index and the semantic_search tool.
+    SemanticSearch,
+    /// Tell the model the OS and tool versions captured at session start.
+    EnvironmentSnapshot,
+    /// Run shell tool commands in a PTY so they print color and progress.
+    ShellPty,
+    /// Tell the model when files it read or edited change outside Codex.
+    ExternalEditNotices,
+    /// Pause before apply_patch overwrites files that changed since the model read them.
+    PatchConflicts,
+    /// Include the ask_user tool so the model can ask the user a question.
+    AskUserTool,
+    /// Require the model to declare intent and risk for every shell command.
+    CommandRiskDeclarations,
+    /// Report licenses and advisories for dependencies added during a turn.
+    DependencyReport,
+    /// Check free disk and memory before builds and installs.
+    ResourcePreflight,
+    /// Give each session a scratch directory that is removed when it ends.
+    ScratchDir,
+    /// Include the register_artifact tool so the model can list its outputs.
+    Artifacts,
+    /// Save the full output of over-budget commands for the read_output_range tool.
+    OutputSpill,
+    /// Retry a rejected turn once after trimming the history.
+    ContextRepair,
+    /// Omit tools that the turn's sandbox and approval policy leave no use for.
+    ToolPruning,
+    /// Hint at the tools and commands that worked well in past sessions of a project.
+    ToolHints,
+    /// Read issues, pull requests and CI status from GitHub or GitLab, and post comments.
+    IssueTracker,
+    /// Include the delegate tool so the model can hand tasks to sub-agents.
+    DelegateTool,
+    /// Write the request body and SSE stream of every model call under `$CODEX_HOME/traces`.
+    RequestTraces,
+    /// Ask the model to fix tool-call arguments that fail schema validation.
+    ToolCallRepair,
+    /// Answer shell tool calls with a predicted outcome instead of running them.
+    SimulatedExec,
+    /// Check the configured model against the provider's model list at session start.
+    ModelProbe,
+    /// Start MCP servers in the background instead of before the session is ready.
+    FastStartup,
 }
 
 impl Feature {
@@ -397,6 +447,156 @@ pub const FEATURES: &[FeatureSpec] = &[
         stage: Stage::Experimental,
         default_enabled: true,
     },
+    FeatureSpec {
+        id: Feature::FileMentions,
+        key: "file_mentions",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::SummarizeFileTool,
+        key: "summarize_file_tool",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::RepoMap,
+        key: "repo_map",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::SemanticSearch,
+        key: "semantic_search",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::EnvironmentSnapshot,
+        key: "environment_snapshot",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ShellPty,
+        key: "shell_pty",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ExternalEditNotices,
+        key: "external_edit_notices",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::PatchConflicts,
+        key: "patch_conflicts",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::AskUserTool,
+        key: "ask_user_tool",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::CommandRiskDeclarations,
+        key: "command_risk_declarations",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::DependencyReport,
+        key: "dependency_report",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ResourcePreflight,
+        key: "resource_preflight",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ScratchDir,
+        key: "scratch_dir",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::Artifacts,
+        key: "artifacts",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::OutputSpill,
+        key: "output_spill",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ContextRepair,
+        key: "context_repair",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ToolPruning,
+        key: "tool_pruning",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ToolHints,
+        key: "tool_hints",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::IssueTracker,
+        key: "issue_tracker",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::DelegateTool,
+        key: "delegate_tool",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::RequestTraces,
+        key: "request_traces",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ToolCallRepair,
+        key: "tool_call_repair",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::SimulatedExec,
+        key: "simulated_exec",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::ModelProbe,
+        key: "model_probe",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
+    FeatureSpec {
+        id: Feature::FastStartup,
+        key: "fast_startup",
+        stage: Stage::Experimental,
+        default_enabled: false,
+    },
     FeatureSpec {
         id: Feature::Tui2,
         key: "tui2",
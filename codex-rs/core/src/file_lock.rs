@@ -0,0 +1,185 @@
+//! Files in `CODEX_HOME` that several Codex processes (the TUI, `codex exec`
+//! and IDE extensions) may update at the same time. A read-modify-write holds
+//! an advisory lock on a `.lock` file next to the target, and the new contents
+//! replace the old ones through a rename so readers never see a partial file.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::ErrorKind;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use tempfile::NamedTempFile;
+
+/// Attempts to take a contended lock before giving up, so a stuck process
+/// makes the others fail instead of hang.
+const MAX_RETRIES: usize = 100;
+const RETRY_SLEEP: Duration = Duration::from_millis(50);
+
+/// Exclusive lock on `<path>.lock`, released when dropped. The lock file is
+/// left in place: removing it would let a waiting process lock a file that
+/// a third one has already replaced.
+#[derive(Debug)]
+pub(crate) struct FileLock {
+    _file: File,
+}
+
+/// Block until the lock of `path` is held by this process.
+pub(crate) fn lock_file(path: &Path) -> std::io::Result<FileLock> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let lock_path = lock_path(path);
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)?;
+    for _ in 0..MAX_RETRIES {
+        match file.try_lock() {
+            Ok(()) => return Ok(FileLock { _file: file }),
+            Err(std::fs::TryLockError::WouldBlock) => std::thread::sleep(RETRY_SLEEP),
+            Err(std::fs::TryLockError::Error(err)) => return Err(err),
+        }
+    }
+    Err(std::io::Error::new(
+        ErrorKind::WouldBlock,
+        format!(
+            "could not lock {} after multiple attempts",
+            lock_path.display()
+        ),
+    ))
+}
+
+/// [`lock_file`] on a blocking task.
+pub(crate) async fn lock_file_async(path: &Path) -> std::io::Result<FileLock> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || lock_file(&path))
+        .await
+        .map_err(std::io::Error::other)?
+}
+
+/// Replace `path` with `contents` through a temporary file in the same
+/// directory. `mode` sets the Unix permissions of the new file.
+pub(crate) fn write_atomic(path: &Path, contents: &[u8], mode: Option<u32>) -> std::io::Result<()> {
+    let dir = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    std::fs::create_dir_all(dir)?;
+    let mut tmp = NamedTempFile::new_in(dir)?;
+    #[cfg(unix)]
+    if let Some(mode) = mode {
+        use std::os::unix::fs::PermissionsExt;
+        tmp.as_file()
+            .set_permissions(std::fs::Permissions::from_mode(mode))?;
+    }
+    #[cfg(not(unix))]
+    let _ = mode;
+    tmp.write_all(contents)?;
+    tmp.as_file().sync_all()?;
+    tmp.persist(path).map_err(|err| err.error)?;
+    Ok(())
+}
+
+/// [`write_atomic`] on a blocking task.
+pub(crate) async fn write_atomic_async(
+    path: &Path,
+    contents: Vec<u8>,
+    mode: Option<u32>,
+) -> std::io::Result<()> {
+    let path = path.to_path_buf();
+    tokio::task::spawn_blocking(move || write_atomic(&path, &contents, mode))
+        .await
+        .map_err(std::io::Error::other)?
+}
+
+fn lock_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".lock");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::process::Command;
+    use std::process::Stdio;
+
+    /// Set for the child processes of `concurrent_processes_do_not_lose_writes`
+    /// to the counter they increment.
+    const CHILD_COUNTER_ENV: &str = "CODEX_FILE_LOCK_TEST_COUNTER";
+
+    fn increment(path: &Path) -> std::io::Result<()> {
+        let _lock = lock_file(path)?;
+        let count: usize = std::fs::read_to_string(path)?
+            .parse()
+            .map_err(std::io::Error::other)?;
+        write_atomic(path, (count + 1).to_string().as_bytes(), None)
+    }
+
+    #[test]
+    fn concurrent_read_modify_writes_are_not_lost() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("counter");
+        std::fs::write(&path, "0")?;
+
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    for _ in 0..25 {
+                        increment(&path).expect("increment counter");
+                    }
+                });
+            }
+        });
+
+        assert_eq!(std::fs::read_to_string(&path)?, "200");
+        Ok(())
+    }
+
+    /// Does nothing unless run as a child process of
+    /// `concurrent_processes_do_not_lose_writes`.
+    #[test]
+    fn increment_counter_in_child_process() -> std::io::Result<()> {
+        let Some(path) = std::env::var_os(CHILD_COUNTER_ENV) else {
+            return Ok(());
+        };
+        for _ in 0..25 {
+            increment(Path::new(&path))?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn concurrent_processes_do_not_lose_writes() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = dir.path().join("counter");
+        std::fs::write(&path, "0")?;
+
+        // Re-run this test binary so each writer holds the lock from its own
+        // process, as separate Codex processes do.
+        let exe = std::env::current_exe()?;
+        let children = (0..4)
+            .map(|_| {
+                Command::new(&exe)
+                    .args([
+                        "--exact",
+                        "file_lock::tests::increment_counter_in_child_process",
+                    ])
+                    .env(CHILD_COUNTER_ENV, &path)
+                    .stdout(Stdio::null())
+                    .spawn()
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+        for mut child in children {
+            assert!(child.wait()?.success(), "child process failed");
+        }
+
+        assert_eq!(std::fs::read_to_string(&path)?, "100");
+        Ok(())
+    }
+}
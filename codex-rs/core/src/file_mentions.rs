@@ -0,0 +1,316 @@
+//! Resolution of `@path` file mentions in user input.
+//!
+//! Mentioned files are read from disk, truncated to the turn's truncation
+//! budget, and recorded as dedicated context items ahead of the model request.
+//! Files that were already attached earlier in the session are skipped while
+//! their size and modification time are unchanged.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::user_input::UserInput;
+use tokio::fs;
+
+use crate::context_ignore::ContextIgnore;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+pub(crate) const ATTACHED_FILE_OPEN_TAG: &str = "<attached_file";
+const ATTACHED_FILE_CLOSE_TAG: &str = "</attached_file>";
+
+/// Files larger than this are never read into memory for attachment.
+const MAX_ATTACHMENT_FILE_BYTES: u64 = 8 * 1024 * 1024;
+
+/// Contents of a mentioned file, rendered as a user-role context message.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FileAttachment {
+    pub path: String,
+    pub contents: String,
+}
+
+impl FileAttachment {
+    pub fn is_file_attachment(message: &[ContentItem]) -> bool {
+        if let [ContentItem::InputText { text }] = message {
+            text.starts_with(ATTACHED_FILE_OPEN_TAG)
+        } else {
+            false
+        }
+    }
+}
+
+impl From<FileAttachment> for ResponseItem {
+    fn from(attachment: FileAttachment) -> Self {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format!(
+                    "{ATTACHED_FILE_OPEN_TAG} path=\"{}\">\n{}\n{ATTACHED_FILE_CLOSE_TAG}",
+                    attachment.path, attachment.contents
+                ),
+            }],
+        }
+    }
+}
+
+/// Identifies the on-disk state of a file at the time it was attached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct FileFingerprint {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl FileFingerprint {
+    fn from_metadata(metadata: &std::fs::Metadata) -> Self {
+        Self {
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        }
+    }
+}
+
+/// Files attached so far in the session, keyed by absolute path.
+pub(crate) type AttachedFiles = HashMap<PathBuf, FileFingerprint>;
+
+#[derive(Debug, Default)]
+pub(crate) struct FileMentionInjections {
+    pub(crate) items: Vec<ResponseItem>,
+    pub(crate) attached: AttachedFiles,
+    pub(crate) warnings: Vec<String>,
+}
+
+/// Resolve `@path` mentions in `inputs` against `cwd` and build context items
+/// for every file that is new or changed relative to `previously_attached`.
+pub(crate) async fn build_file_mention_injections(
+    inputs: &[UserInput],
+    cwd: &Path,
+    previously_attached: &AttachedFiles,
+    context_ignore: &ContextIgnore,
+    policy: TruncationPolicy,
+) -> FileMentionInjections {
+    let mut result = FileMentionInjections::default();
+
+    for mention in collect_file_mentions(inputs) {
+        let path = cwd.join(&mention);
+        let Ok(metadata) = fs::metadata(&path).await else {
+            // Not every `@token` names a file (e.g. `@username`).
+            continue;
+        };
+        if !metadata.is_file() {
+            continue;
+        }
+        if context_ignore.is_ignored(&path, false) {
+            result.warnings.push(format!(
+                "Skipped attaching {mention}: excluded from context by ignore rules."
+            ));
+            continue;
+        }
+        let fingerprint = FileFingerprint::from_metadata(&metadata);
+        if previously_attached.get(&path) == Some(&fingerprint)
+            || result.attached.contains_key(&path)
+        {
+            continue;
+        }
+        if metadata.len() > MAX_ATTACHMENT_FILE_BYTES {
+            result.warnings.push(format!(
+                "Skipped attaching {mention}: file is larger than {MAX_ATTACHMENT_FILE_BYTES} bytes."
+            ));
+            continue;
+        }
+
+        let bytes = match fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                result
+                    .warnings
+                    .push(format!("Failed to attach {mention}: {err:#}"));
+                continue;
+            }
+        };
+        let Ok(contents) = String::from_utf8(bytes) else {
+            result.warnings.push(format!(
+                "Skipped attaching {mention}: file is not valid UTF-8 text."
+            ));
+            continue;
+        };
+
+        result.items.push(ResponseItem::from(FileAttachment {
+            path: mention,
+            contents: truncate_text(&contents, policy),
+        }));
+        result.attached.insert(path, fingerprint);
+    }
+
+    result
+}
+
+fn collect_file_mentions(inputs: &[UserInput]) -> Vec<String> {
+    let mut mentions: Vec<String> = Vec::new();
+    for input in inputs {
+        if let UserInput::Text { text } = input {
+            for mention in extract_file_mentions(text) {
+                if !mentions.contains(&mention) {
+                    mentions.push(mention);
+                }
+            }
+        }
+    }
+    mentions
+}
+
+/// Extract the paths of `@path` tokens from `text`.
+///
+/// A mention starts at an `@` that begins a whitespace-delimited token. Paths
+/// containing whitespace can be quoted (`@"docs/My File.md"`). Trailing
+/// sentence punctuation is not considered part of an unquoted path.
+pub(crate) fn extract_file_mentions(text: &str) -> Vec<String> {
+    let mut mentions = Vec::new();
+    let mut rest = text;
+    let mut at_token_start = true;
+
+    while let Some(ch) = rest.chars().next() {
+        if ch.is_whitespace() {
+            at_token_start = true;
+            rest = &rest[ch.len_utf8()..];
+            continue;
+        }
+        if !(at_token_start && ch == '@') {
+            at_token_start = false;
+            rest = &rest[ch.len_utf8()..];
+            continue;
+        }
+
+        let after_at = &rest[1..];
+        if let Some(quoted) = after_at.strip_prefix('"')
+            && let Some(end) = quoted.find('"')
+        {
+            let path = &quoted[..end];
+            if !path.is_empty() {
+                mentions.push(path.to_string());
+            }
+            rest = &quoted[end + 1..];
+        } else {
+            let end = after_at.find(char::is_whitespace).unwrap_or(after_at.len());
+            let path = after_at[..end].trim_end_matches([',', '.', ';', ':', '!', '?', ')']);
+            if !path.is_empty() {
+                mentions.push(path.to_string());
+            }
+            rest = &after_at[end..];
+        }
+        at_token_start = false;
+    }
+
+    mentions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn extracts_plain_and_quoted_mentions() {
+        assert_eq!(
+            extract_file_mentions(
+                "look at @src/lib.rs, and @\"docs/My File.md\" but not me@example.com"
+            ),
+            vec!["src/lib.rs".to_string(), "docs/My File.md".to_string()]
+        );
+    }
+
+    #[test]
+    fn ignores_bare_at_signs() {
+        assert_eq!(
+            extract_file_mentions("@ @\"\" trailing @"),
+            Vec::<String>::new()
+        );
+    }
+
+    #[tokio::test]
+    async fn attaches_files_once_until_they_change() {
+        let dir = tempdir().expect("tempdir");
+        let file = dir.path().join("notes.txt");
+        std::fs::write(&file, "first").expect("write");
+        let inputs = vec![UserInput::Text {
+            text: "summarize @notes.txt and @missing.txt".to_string(),
+        }];
+        let policy = TruncationPolicy::Bytes(1024);
+
+        let first = build_file_mention_injections(
+            &inputs,
+            dir.path(),
+            &AttachedFiles::new(),
+            &ContextIgnore::default(),
+            policy,
+        )
+        .await;
+        assert_eq!(
+            first.items,
+            vec![ResponseItem::from(FileAttachment {
+                path: "notes.txt".to_string(),
+                contents: "first".to_string(),
+            })]
+        );
+
+        let repeat = build_file_mention_injections(
+            &inputs,
+            dir.path(),
+            &first.attached,
+            &ContextIgnore::default(),
+            policy,
+        )
+        .await;
+        assert_eq!(repeat.items, Vec::<ResponseItem>::new());
+
+        std::fs::write(&file, "second, longer").expect("rewrite");
+        let changed = build_file_mention_injections(
+            &inputs,
+            dir.path(),
+            &first.attached,
+            &ContextIgnore::default(),
+            policy,
+        )
+        .await;
+        assert_eq!(
+            changed.items,
+            vec![ResponseItem::from(FileAttachment {
+                path: "notes.txt".to_string(),
+                contents: "second, longer".to_string(),
+            })]
+        );
+    }
+
+    #[tokio::test]
+    async fn skips_ignored_files_with_warning() {
+        let dir = tempdir().expect("tempdir");
+        std::fs::create_dir(dir.path().join("secrets")).expect("mkdir");
+        std::fs::write(dir.path().join("secrets/token.txt"), "hunter2").expect("write");
+        let inputs = vec![UserInput::Text {
+            text: "use @secrets/token.txt".to_string(),
+        }];
+        let context_ignore = ContextIgnore::new(dir.path(), &["secrets/".to_string()]);
+
+        let result = build_file_mention_injections(
+            &inputs,
+            dir.path(),
+            &AttachedFiles::new(),
+            &context_ignore,
+            TruncationPolicy::Bytes(1024),
+        )
+        .await;
+
+        assert_eq!(result.items, Vec::<ResponseItem>::new());
+        assert_eq!(
+            result.warnings,
+            vec![
+                "Skipped attaching secrets/token.txt: excluded from context by ignore rules."
+                    .to_string()
+            ]
+        );
+    }
+}
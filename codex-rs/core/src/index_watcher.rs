@@ -0,0 +1,306 @@
+//! Tracks which files changed since each local index was last refreshed.
+//!
+//! A recursive file watcher on the repository root records changed paths in
+//! a dirty set per index. Indexes drain their set when they are next used and
+//! refresh only those paths, so long-lived sessions never search stale
+//! content and never pay for a full rebuild on every change.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use ignore::gitignore::Gitignore;
+use notify::Event;
+use notify::EventKind;
+use notify::RecommendedWatcher;
+use notify::RecursiveMode;
+use notify::Watcher;
+use tracing::warn;
+
+use crate::semantic_index::INDEX_DIR;
+
+/// The local indexes that consume change notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum IndexKind {
+    Semantic,
+    RepoMap,
+}
+
+/// How far an index has drifted from the working tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IndexStaleness {
+    pub changed_paths: usize,
+    /// Time since the oldest change that has not been reindexed.
+    pub since: Duration,
+}
+
+impl fmt::Display for IndexStaleness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.since.as_secs();
+        let age = if secs < 60 {
+            format!("{secs}s")
+        } else if secs < 3600 {
+            format!("{}m", secs / 60)
+        } else {
+            format!("{}h", secs / 3600)
+        };
+        write!(
+            f,
+            "{} changed path(s) over the last {age}",
+            self.changed_paths
+        )
+    }
+}
+
+pub(crate) struct IndexWatcher {
+    tracker: Arc<DirtyTracker>,
+    // Dropping the watcher stops the notifications.
+    _watcher: RecommendedWatcher,
+}
+
+impl IndexWatcher {
+    /// Watch `root` recursively. Returns `None` (after logging) when the
+    /// platform watcher cannot be started, e.g. because the inotify watch
+    /// limit is exhausted.
+    pub(crate) fn start(root: &Path) -> Option<Self> {
+        let tracker = Arc::new(DirtyTracker::new(root));
+        let callback_tracker = Arc::clone(&tracker);
+        let watcher = notify::recommended_watcher(move |res: notify::Result<Event>| match res {
+            Ok(event) => callback_tracker.record_event(&event),
+            Err(err) => warn!("index watcher error: {err}"),
+        });
+        let mut watcher = match watcher {
+            Ok(watcher) => watcher,
+            Err(err) => {
+                warn!("failed to create index watcher: {err}");
+                return None;
+            }
+        };
+        if let Err(err) = watcher.watch(root, RecursiveMode::Recursive) {
+            warn!(
+                "failed to watch {} for index changes: {err}",
+                root.display()
+            );
+            return None;
+        }
+        Some(Self {
+            tracker,
+            _watcher: watcher,
+        })
+    }
+
+    pub(crate) fn root(&self) -> &Path {
+        &self.tracker.root
+    }
+
+    /// Whether `path` is covered by this watcher.
+    pub(crate) fn watches(&self, path: &Path) -> bool {
+        path.starts_with(&self.tracker.root)
+    }
+
+    /// Drain the paths (relative to the root) that changed since `kind` was
+    /// last refreshed.
+    pub(crate) fn take_dirty(&self, kind: IndexKind) -> Vec<String> {
+        self.tracker.take_dirty(kind)
+    }
+
+    /// Put back paths whose refresh failed so the next use retries them.
+    pub(crate) fn restore_dirty(&self, kind: IndexKind, paths: Vec<String>) {
+        self.tracker.restore_dirty(kind, paths);
+    }
+
+    pub(crate) fn staleness(&self, kind: IndexKind) -> Option<IndexStaleness> {
+        self.tracker.staleness(kind)
+    }
+}
+
+#[derive(Debug, Default)]
+struct DirtyPaths {
+    paths: BTreeSet<String>,
+    oldest_change: Option<Instant>,
+}
+
+struct DirtyTracker {
+    root: PathBuf,
+    /// `notify` may report canonical paths (e.g. `/private/var` on macOS).
+    canonical_root: Option<PathBuf>,
+    gitignore: Gitignore,
+    state: Mutex<HashMap<IndexKind, DirtyPaths>>,
+}
+
+impl DirtyTracker {
+    fn new(root: &Path) -> Self {
+        let (gitignore, err) = Gitignore::new(root.join(".gitignore"));
+        if let Some(err) = err
+            && root.join(".gitignore").is_file()
+        {
+            warn!("failed to parse .gitignore for index watcher: {err}");
+        }
+        Self {
+            root: root.to_path_buf(),
+            canonical_root: root.canonicalize().ok(),
+            gitignore,
+            state: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn record_event(&self, event: &Event) {
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        let relative: Vec<String> = event
+            .paths
+            .iter()
+            .filter_map(|path| self.relative_path(path))
+            .collect();
+        self.mark_dirty(relative);
+    }
+
+    fn relative_path(&self, path: &Path) -> Option<String> {
+        let relative = path.strip_prefix(&self.root).ok().or_else(|| {
+            self.canonical_root
+                .as_ref()
+                .and_then(|root| path.strip_prefix(root).ok())
+        })?;
+        let relative = relative.to_str()?.replace('\\', "/");
+        if relative.is_empty()
+            || relative == ".git"
+            || relative.starts_with(".git/")
+            || relative.starts_with(INDEX_DIR)
+        {
+            return None;
+        }
+        if self
+            .gitignore
+            .matched_path_or_any_parents(&relative, path.is_dir())
+            .is_ignore()
+        {
+            return None;
+        }
+        Some(relative)
+    }
+
+    fn mark_dirty(&self, paths: Vec<String>) {
+        if paths.is_empty() {
+            return;
+        }
+        let now = Instant::now();
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        for kind in [IndexKind::Semantic, IndexKind::RepoMap] {
+            let dirty = state.entry(kind).or_default();
+            dirty.paths.extend(paths.iter().cloned());
+            dirty.oldest_change.get_or_insert(now);
+        }
+    }
+
+    fn take_dirty(&self, kind: IndexKind) -> Vec<String> {
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        state
+            .remove(&kind)
+            .map(|dirty| dirty.paths.into_iter().collect())
+            .unwrap_or_default()
+    }
+
+    fn restore_dirty(&self, kind: IndexKind, paths: Vec<String>) {
+        if paths.is_empty() {
+            return;
+        }
+        let mut state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let dirty = state.entry(kind).or_default();
+        dirty.paths.extend(paths);
+        dirty.oldest_change.get_or_insert_with(Instant::now);
+    }
+
+    fn staleness(&self, kind: IndexKind) -> Option<IndexStaleness> {
+        let state = self
+            .state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let dirty = state.get(&kind)?;
+        Some(IndexStaleness {
+            changed_paths: dirty.paths.len(),
+            since: dirty.oldest_change?.elapsed(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use notify::event::CreateKind;
+    use notify::event::ModifyKind;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn event(kind: EventKind, paths: &[PathBuf]) -> Event {
+        let mut event = Event::new(kind);
+        event.paths = paths.to_vec();
+        event
+    }
+
+    #[test]
+    fn records_changes_for_each_index_independently() {
+        let dir = tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::write(root.join(".gitignore"), "target/\n").expect("write");
+        let tracker = DirtyTracker::new(root);
+
+        tracker.record_event(&event(
+            EventKind::Modify(ModifyKind::Any),
+            &[
+                root.join("src/lib.rs"),
+                root.join("target/debug/build.log"),
+                root.join(".git/index"),
+                root.join(INDEX_DIR).join("semantic.json"),
+            ],
+        ));
+        tracker.record_event(&event(
+            EventKind::Create(CreateKind::File),
+            &[root.join("README.md")],
+        ));
+
+        let staleness = tracker.staleness(IndexKind::RepoMap).expect("stale");
+        assert_eq!(staleness.changed_paths, 2);
+        assert_eq!(
+            tracker.take_dirty(IndexKind::Semantic),
+            vec!["README.md".to_string(), "src/lib.rs".to_string()]
+        );
+        assert_eq!(tracker.staleness(IndexKind::Semantic), None);
+        assert_eq!(tracker.take_dirty(IndexKind::RepoMap).len(), 2);
+    }
+
+    #[test]
+    fn describes_staleness() {
+        let staleness = IndexStaleness {
+            changed_paths: 3,
+            since: Duration::from_secs(125),
+        };
+        assert_eq!(staleness.to_string(), "3 changed path(s) over the last 2m");
+    }
+
+    #[test]
+    fn restored_paths_are_retried() {
+        let dir = tempdir().expect("tempdir");
+        let tracker = DirtyTracker::new(dir.path());
+        tracker.restore_dirty(IndexKind::Semantic, vec!["a.rs".to_string()]);
+        assert_eq!(
+            tracker.take_dirty(IndexKind::Semantic),
+            vec!["a.rs".to_string()]
+        );
+    }
+}
@@ -0,0 +1,962 @@
+//! Read-only access to issues, pull requests and CI results on GitHub or
+//! GitLab, plus posting comments, for the `issue_tracker` tool.
+//!
+//! The repository comes from the `origin` remote of the working directory
+//! unless the model names one. The token is read from the environment
+//! variable named in `[issue_tracker]`. For github.com and gitlab.com it may
+//! also come from the OS keychain (service `Codex Issue Tracker`, account set
+//! to the host) or the provider's usual variables (`GITHUB_TOKEN`,
+//! `GH_TOKEN`, `GITLAB_TOKEN`). Those are never sent anywhere else: a remote
+//! on another host only works once `api_url` and `token_env_var` name its
+//! API and token explicitly.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::anyhow;
+use codex_keyring_store::DefaultKeyringStore;
+use codex_keyring_store::KeyringStore;
+use reqwest::RequestBuilder;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+use tracing::warn;
+
+use crate::config::types::IssueTrackerConfig;
+use crate::config::types::IssueTrackerProvider;
+use crate::default_client::build_reqwest_client;
+use crate::git_info::collect_git_info;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+const KEYRING_SERVICE: &str = "Codex Issue Tracker";
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(20);
+const BODY_MAX_BYTES: usize = 8 * 1024;
+const COMMENT_MAX_BYTES: usize = 2 * 1024;
+/// Only the most recent comments are shown.
+const MAX_COMMENTS: usize = 20;
+/// Lines kept before each `##[error]` line of a failed job's log.
+const ERROR_CONTEXT_LINES: usize = 40;
+/// Lines kept from the end of a failed job's log without `##[error]` lines.
+const LOG_TAIL_LINES: usize = 80;
+/// Budget for the log excerpts of all failed jobs together.
+const CI_LOGS_MAX_BYTES: usize = 24 * 1024;
+
+/// A repository on a GitHub or GitLab instance.
+pub(crate) struct IssueTracker {
+    provider: IssueTrackerProvider,
+    api_url: String,
+    /// `owner/name` on GitHub, the full project path on GitLab.
+    repo: String,
+    token: Option<String>,
+    client: reqwest::Client,
+}
+
+impl IssueTracker {
+    /// Resolve the tracker for `repo`, or for the `origin` remote of `cwd`
+    /// when `repo` is `None`.
+    pub(crate) async fn for_repo(
+        config: &IssueTrackerConfig,
+        cwd: &Path,
+        repo: Option<&str>,
+    ) -> Result<Self> {
+        let remote = collect_git_info(cwd)
+            .await
+            .and_then(|info| info.repository_url)
+            .and_then(|url| parse_remote_url(&url));
+        let provider = config
+            .provider
+            .or_else(|| remote.as_ref().map(|(host, _)| infer_provider(host)))
+            .unwrap_or(IssueTrackerProvider::GitHub);
+        let host = match &remote {
+            Some((host, _)) => host.clone(),
+            None => default_host(provider).to_string(),
+        };
+        let repo = match (repo, remote) {
+            (Some(repo), _) => repo.trim().trim_matches('/').to_string(),
+            (None, Some((_, path))) => path,
+            (None, None) => {
+                return Err(anyhow!(
+                    "no `origin` remote to take the repository from; pass `repo` explicitly"
+                ));
+            }
+        };
+        if !is_valid_repo(provider, &repo) {
+            return Err(anyhow!(match provider {
+                IssueTrackerProvider::GitHub =>
+                    format!("`{repo}` is not a repository of the form `owner/name`"),
+                IssueTrackerProvider::GitLab =>
+                    format!("`{repo}` is not a project path of the form `group/name`"),
+            }));
+        }
+        let api_url = api_url(config, provider, &host)?;
+        let token = resolve_token(config, provider, &api_url);
+        Ok(Self {
+            provider,
+            api_url,
+            repo,
+            token,
+            client: build_reqwest_client(),
+        })
+    }
+
+    /// How the item is written on the provider, e.g. `owner/repo#12`.
+    pub(crate) fn reference(&self, number: u64, pull_request: bool) -> String {
+        let sigil = match self.provider {
+            IssueTrackerProvider::GitLab if pull_request => '!',
+            _ => '#',
+        };
+        format!("{}{sigil}{number}", self.repo)
+    }
+
+    /// Title, state, description and recent comments of an issue.
+    pub(crate) async fn issue(&self, number: u64) -> Result<String> {
+        match self.provider {
+            IssueTrackerProvider::GitHub => {
+                let issue = self.get(&format!("issues/{number}")).await?;
+                let comments = self
+                    .get(&format!("issues/{number}/comments?per_page=100"))
+                    .await?;
+                Ok(format_github_item(&issue, &comments, None))
+            }
+            IssueTrackerProvider::GitLab => {
+                let issue = self.get(&format!("issues/{number}")).await?;
+                let notes = self
+                    .get(&format!("issues/{number}/notes?sort=asc&per_page=100"))
+                    .await?;
+                Ok(format_gitlab_item(&issue, &notes, '#', false))
+            }
+        }
+    }
+
+    /// Like [`IssueTracker::issue`], plus the branches and head commit.
+    pub(crate) async fn pull_request(&self, number: u64) -> Result<String> {
+        match self.provider {
+            IssueTrackerProvider::GitHub => {
+                let pull = self.get(&format!("pulls/{number}")).await?;
+                let comments = self
+                    .get(&format!("issues/{number}/comments?per_page=100"))
+                    .await?;
+                let branches = format!(
+                    "Branch: {} → {} (head {})",
+                    json_str(pull.pointer("/head/ref")),
+                    json_str(pull.pointer("/base/ref")),
+                    json_str(pull.pointer("/head/sha")),
+                );
+                Ok(format_github_item(&pull, &comments, Some(branches)))
+            }
+            IssueTrackerProvider::GitLab => {
+                let merge_request = self.get(&format!("merge_requests/{number}")).await?;
+                let notes = self
+                    .get(&format!(
+                        "merge_requests/{number}/notes?sort=asc&per_page=100"
+                    ))
+                    .await?;
+                Ok(format_gitlab_item(&merge_request, &notes, '!', true))
+            }
+        }
+    }
+
+    /// Check results for the head of a pull request, or for the local `HEAD`
+    /// commit when `number` is `None`.
+    pub(crate) async fn ci_status(&self, number: Option<u64>, cwd: &Path) -> Result<String> {
+        match self.provider {
+            IssueTrackerProvider::GitHub => {
+                let sha = match number {
+                    Some(number) => json_str(
+                        self.get(&format!("pulls/{number}"))
+                            .await?
+                            .pointer("/head/sha"),
+                    ),
+                    None => local_head(cwd).await?,
+                };
+                let check_runs = self
+                    .get(&format!("commits/{sha}/check-runs?per_page=100"))
+                    .await?;
+                let statuses = self.get(&format!("commits/{sha}/status")).await?;
+                Ok(format_github_checks(&sha, &check_runs, &statuses))
+            }
+            IssueTrackerProvider::GitLab => {
+                let pipelines = match number {
+                    Some(number) => {
+                        self.get(&format!("merge_requests/{number}/pipelines"))
+                            .await?
+                    }
+                    None => {
+                        let sha = local_head(cwd).await?;
+                        self.get(&format!("pipelines?sha={sha}&per_page=1")).await?
+                    }
+                };
+                let Some(pipeline) = pipelines.as_array().and_then(|list| list.first()) else {
+                    return Ok("No pipelines found.".to_string());
+                };
+                let id = pipeline
+                    .get("id")
+                    .and_then(JsonValue::as_u64)
+                    .ok_or_else(|| anyhow!("pipeline without an id"))?;
+                let jobs = self
+                    .get(&format!("pipelines/{id}/jobs?per_page=100"))
+                    .await?;
+                Ok(format_gitlab_jobs(pipeline, &jobs))
+            }
+        }
+    }
+
+    /// Log excerpts of the failed jobs in the latest GitHub Actions run for
+    /// `head_sha` on `branch` (its newest commit with runs when `None`), or
+    /// `None` when that run did not fail.
+    pub(crate) async fn failing_ci_logs(
+        &self,
+        branch: &str,
+        head_sha: Option<&str>,
+    ) -> Result<Option<String>> {
+        if self.provider != IssueTrackerProvider::GitHub {
+            return Err(anyhow!("CI logs are only available for GitHub Actions"));
+        }
+        let mut path = format!(
+            "actions/runs?branch={}&per_page=50",
+            url::form_urlencoded::byte_serialize(branch.as_bytes()).collect::<String>()
+        );
+        if let Some(sha) = head_sha {
+            path.push_str(&format!("&head_sha={sha}"));
+        }
+        let runs = self.get(&path).await?;
+        let Some(run) = latest_failed_run(&runs) else {
+            return Ok(None);
+        };
+        let run_id = run
+            .get("id")
+            .and_then(JsonValue::as_u64)
+            .ok_or_else(|| anyhow!("workflow run without an id"))?;
+        let jobs = self
+            .get(&format!(
+                "actions/runs/{run_id}/jobs?filter=latest&per_page=100"
+            ))
+            .await?;
+        let failed_jobs: Vec<&JsonValue> = jobs
+            .get("jobs")
+            .and_then(JsonValue::as_array)
+            .into_iter()
+            .flatten()
+            .filter(|job| job.get("conclusion").and_then(JsonValue::as_str) == Some("failure"))
+            .collect();
+
+        let sha = json_str(run.get("head_sha"));
+        let mut sections = vec![
+            format!(
+                "Workflow `{}` run #{} failed on `{branch}` (commit {}): {}",
+                json_str(run.get("name")),
+                run.get("run_number")
+                    .and_then(JsonValue::as_u64)
+                    .unwrap_or(0),
+                sha.get(..12).unwrap_or(&sha),
+                json_str(run.get("html_url"))
+            )
+            .trim_end()
+            .to_string(),
+        ];
+        if failed_jobs.is_empty() {
+            sections.push("No job of the run is marked as failed.".to_string());
+        }
+        // Share the budget so one noisy job cannot crowd out the others.
+        let job_budget = CI_LOGS_MAX_BYTES / failed_jobs.len().max(1);
+        for job in failed_jobs {
+            let failed_steps: Vec<String> = job
+                .get("steps")
+                .and_then(JsonValue::as_array)
+                .into_iter()
+                .flatten()
+                .filter(|step| {
+                    step.get("conclusion").and_then(JsonValue::as_str) == Some("failure")
+                })
+                .map(|step| format!("`{}`", json_str(step.get("name"))))
+                .collect();
+            let mut heading = format!("Job `{}` failed", json_str(job.get("name")));
+            if !failed_steps.is_empty() {
+                heading.push_str(&format!(" in step {}", failed_steps.join(", ")));
+            }
+            let excerpt = match job.get("id").and_then(JsonValue::as_u64) {
+                Some(job_id) => match self.get_text(&format!("actions/jobs/{job_id}/logs")).await {
+                    Ok(log) => truncate_text(
+                        &extract_failure_excerpt(&log),
+                        TruncationPolicy::Bytes(job_budget),
+                    ),
+                    Err(err) => format!("(log unavailable: {err:#})"),
+                },
+                None => "(log unavailable)".to_string(),
+            };
+            sections.push(format!(
+                "{heading}: {}\n{excerpt}",
+                json_str(job.get("html_url"))
+            ));
+        }
+        Ok(Some(sections.join("\n\n")))
+    }
+
+    /// Post `body` as a comment and return a link to it when the provider
+    /// gives one.
+    pub(crate) async fn post_comment(
+        &self,
+        number: u64,
+        pull_request: bool,
+        body: &str,
+    ) -> Result<String> {
+        let token = self
+            .token
+            .as_deref()
+            .ok_or_else(|| anyhow!("posting comments needs an access token"))?;
+        let (path, field) = match self.provider {
+            IssueTrackerProvider::GitHub => (format!("issues/{number}/comments"), "/html_url"),
+            IssueTrackerProvider::GitLab if pull_request => {
+                (format!("merge_requests/{number}/notes"), "/id")
+            }
+            IssueTrackerProvider::GitLab => (format!("issues/{number}/notes"), "/id"),
+        };
+        let url = self.url(&path);
+        let request = self
+            .client
+            .post(&url)
+            .bearer_auth(token)
+            .json(&json!({ "body": body }));
+        let comment = self.send(request, &url).await?;
+        let reference = self.reference(number, pull_request);
+        Ok(match comment.pointer(field) {
+            Some(JsonValue::String(link)) => format!("Posted a comment on {reference}: {link}"),
+            _ => format!("Posted a comment on {reference}."),
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        match self.provider {
+            IssueTrackerProvider::GitHub => format!("{}/repos/{}/{path}", self.api_url, self.repo),
+            IssueTrackerProvider::GitLab => format!(
+                "{}/projects/{}/{path}",
+                self.api_url,
+                self.repo.replace('/', "%2F")
+            ),
+        }
+    }
+
+    async fn get(&self, path: &str) -> Result<JsonValue> {
+        let url = self.url(path);
+        let mut request = self.client.get(&url);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        self.send(request, &url).await
+    }
+
+    async fn get_text(&self, path: &str) -> Result<String> {
+        let url = self.url(path);
+        let mut request = self.client.get(&url);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+        self.send_raw(request, &url)
+            .await?
+            .text()
+            .await
+            .context("invalid issue tracker response")
+    }
+
+    async fn send(&self, request: RequestBuilder, url: &str) -> Result<JsonValue> {
+        self.send_raw(request, url)
+            .await?
+            .json()
+            .await
+            .context("invalid issue tracker response")
+    }
+
+    async fn send_raw(&self, request: RequestBuilder, url: &str) -> Result<reqwest::Response> {
+        let request = match self.provider {
+            IssueTrackerProvider::GitHub => request
+                .header("Accept", "application/vnd.github+json")
+                .header("X-GitHub-Api-Version", "2022-11-28"),
+            IssueTrackerProvider::GitLab => request,
+        };
+        let response = request
+            .timeout(REQUEST_TIMEOUT)
+            .send()
+            .await
+            .with_context(|| format!("failed to reach {url}"))?;
+        let status = response.status();
+        if !status.is_success() {
+            let hint = if self.token.is_none() && matches!(status.as_u16(), 401 | 403 | 404) {
+                " (no access token is configured)"
+            } else {
+                ""
+            };
+            return Err(anyhow!("{url} returned {status}{hint}"));
+        }
+        Ok(response)
+    }
+}
+
+/// Log excerpts of the latest GitHub Actions run for the `HEAD` commit of
+/// `cwd`, or `None` when that run did not fail.
+pub async fn fetch_failing_ci_logs(
+    config: &IssueTrackerConfig,
+    cwd: &Path,
+) -> Result<Option<String>> {
+    let branch = current_branch(cwd).await?;
+    let head_sha = collect_git_info(cwd)
+        .await
+        .and_then(|info| info.commit_hash);
+    IssueTracker::for_repo(config, cwd, None)
+        .await?
+        .failing_ci_logs(&branch, head_sha.as_deref())
+        .await
+}
+
+pub(crate) async fn current_branch(cwd: &Path) -> Result<String> {
+    collect_git_info(cwd)
+        .await
+        .and_then(|info| info.branch)
+        .ok_or_else(|| anyhow!("the working directory is not on a branch"))
+}
+
+/// The failed run among the latest run of each workflow for the newest commit
+/// in `runs` (a GitHub `actions/runs` listing, newest first). A workflow that
+/// failed and then passed on a re-run or a later push counts as passing.
+fn latest_failed_run(runs: &JsonValue) -> Option<&JsonValue> {
+    let runs = runs.get("workflow_runs")?.as_array()?;
+    let head_sha = runs.first()?.get("head_sha")?;
+    let mut seen_workflows = HashSet::new();
+    runs.iter()
+        .filter(|run| run.get("head_sha") == Some(head_sha))
+        .filter(|run| seen_workflows.insert(run.get("workflow_id").cloned()))
+        .find(|run| run.get("conclusion").and_then(JsonValue::as_str) == Some("failure"))
+}
+
+/// Keep the part of a job log that explains the failure: the lines leading
+/// up to each `##[error]` line, or the end of the log when there are none.
+/// Timestamps and group markers are dropped.
+fn extract_failure_excerpt(log: &str) -> String {
+    let lines: Vec<&str> = log
+        .lines()
+        .map(strip_log_timestamp)
+        .filter(|line| !line.starts_with("##[endgroup]"))
+        .collect();
+    let errors: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("##[error]"))
+        .map(|(index, _)| index)
+        .collect();
+    if errors.is_empty() {
+        let start = lines.len().saturating_sub(LOG_TAIL_LINES);
+        return lines[start..].join("\n");
+    }
+    let mut excerpt: Vec<&str> = Vec::new();
+    let mut next = 0;
+    for error in errors {
+        let start = error.saturating_sub(ERROR_CONTEXT_LINES).max(next);
+        if start > next {
+            excerpt.push("...");
+        }
+        excerpt.extend(&lines[start..=error]);
+        next = error + 1;
+    }
+    excerpt.join("\n")
+}
+
+/// Drop the `2024-01-01T00:00:00.0000000Z ` prefix GitHub puts on log lines.
+fn strip_log_timestamp(line: &str) -> &str {
+    match line.split_once(' ') {
+        Some((timestamp, rest))
+            if timestamp.len() > 20
+                && timestamp.ends_with('Z')
+                && timestamp.as_bytes().get(4) == Some(&b'-') =>
+        {
+            rest
+        }
+        _ => line,
+    }
+}
+
+/// Split a remote URL into host and repository path. Handles
+/// `https://host/owner/repo.git`, `ssh://git@host:22/owner/repo.git` and
+/// `git@host:owner/repo.git`.
+fn parse_remote_url(url: &str) -> Option<(String, String)> {
+    let url = url.trim();
+    let (authority, path) = match url.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?,
+        None => url.split_once(':')?,
+    };
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix(".git").unwrap_or(path);
+    if host.is_empty() || !path.contains('/') {
+        return None;
+    }
+    Some((host.to_string(), path.to_string()))
+}
+
+fn infer_provider(host: &str) -> IssueTrackerProvider {
+    if host.contains("gitlab") {
+        IssueTrackerProvider::GitLab
+    } else {
+        IssueTrackerProvider::GitHub
+    }
+}
+
+fn default_host(provider: IssueTrackerProvider) -> &'static str {
+    match provider {
+        IssueTrackerProvider::GitHub => "github.com",
+        IssueTrackerProvider::GitLab => "gitlab.com",
+    }
+}
+
+/// The API of the provider's public instance, which the keychain entry and
+/// the usual token variables are meant for.
+fn public_api_url(provider: IssueTrackerProvider) -> &'static str {
+    match provider {
+        IssueTrackerProvider::GitHub => "https://api.github.com",
+        IssueTrackerProvider::GitLab => "https://gitlab.com/api/v4",
+    }
+}
+
+/// The API to call for a remote on `host`. Only github.com and gitlab.com
+/// have a default: for any other host, which the repository's remote alone
+/// decides, `api_url` and `token_env_var` must be configured, so a remote
+/// pointing somewhere unexpected cannot collect the user's tokens.
+fn api_url(
+    config: &IssueTrackerConfig,
+    provider: IssueTrackerProvider,
+    host: &str,
+) -> Result<String> {
+    if let Some(api_url) = &config.api_url {
+        let api_url = api_url.trim_end_matches('/');
+        if api_url != public_api_url(provider) && config.token_env_var.is_none() {
+            return Err(anyhow!(
+                "`issue_tracker.api_url` is {api_url}; set `issue_tracker.token_env_var` to the variable holding its token"
+            ));
+        }
+        return Ok(api_url.to_string());
+    }
+    if host == default_host(provider) {
+        return Ok(public_api_url(provider).to_string());
+    }
+    Err(anyhow!(
+        "the repository is hosted on {host}; set `issue_tracker.api_url` and `issue_tracker.token_env_var` in config.toml to use its API"
+    ))
+}
+
+fn resolve_token(
+    config: &IssueTrackerConfig,
+    provider: IssueTrackerProvider,
+    api_url: &str,
+) -> Option<String> {
+    let from_env = |var: &str| std::env::var(var).ok().filter(|token| !token.is_empty());
+    if let Some(var) = config.token_env_var.as_deref() {
+        return from_env(var);
+    }
+    if api_url != public_api_url(provider) {
+        return None;
+    }
+    match DefaultKeyringStore.load(KEYRING_SERVICE, default_host(provider)) {
+        Ok(Some(token)) => return Some(token),
+        Ok(None) => {}
+        Err(err) => warn!("failed to read the issue tracker token from the keychain: {err}"),
+    }
+    let defaults: &[&str] = match provider {
+        IssueTrackerProvider::GitHub => &["GITHUB_TOKEN", "GH_TOKEN"],
+        IssueTrackerProvider::GitLab => &["GITLAB_TOKEN"],
+    };
+    defaults.iter().find_map(|var| from_env(var))
+}
+
+/// Whether `repo` is `owner/name` on GitHub, or a `group/.../name` project
+/// path on GitLab, so it cannot reach other API routes.
+fn is_valid_repo(provider: IssueTrackerProvider, repo: &str) -> bool {
+    let segments: Vec<&str> = repo.split('/').collect();
+    let count_ok = match provider {
+        IssueTrackerProvider::GitHub => segments.len() == 2,
+        IssueTrackerProvider::GitLab => segments.len() >= 2,
+    };
+    count_ok
+        && segments.iter().all(|segment| {
+            !segment.is_empty()
+                && !segment.starts_with('.')
+                && segment
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        })
+}
+
+async fn local_head(cwd: &Path) -> Result<String> {
+    collect_git_info(cwd)
+        .await
+        .and_then(|info| info.commit_hash)
+        .ok_or_else(|| anyhow!("the working directory has no commits; pass `number` instead"))
+}
+
+fn format_github_item(item: &JsonValue, comments: &JsonValue, branches: Option<String>) -> String {
+    let state = if item.get("merged").and_then(JsonValue::as_bool) == Some(true) {
+        "merged".to_string()
+    } else {
+        json_str(item.get("state"))
+    };
+    let labels: Vec<String> = item
+        .get("labels")
+        .and_then(JsonValue::as_array)
+        .map(|labels| {
+            labels
+                .iter()
+                .map(|label| json_str(label.get("name")))
+                .collect()
+        })
+        .unwrap_or_default();
+    let comments: Vec<(String, String, String)> = comments
+        .as_array()
+        .map(|comments| {
+            comments
+                .iter()
+                .map(|comment| {
+                    (
+                        json_str(comment.pointer("/user/login")),
+                        json_str(comment.get("created_at")),
+                        json_str(comment.get("body")),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    format_item(
+        &format!(
+            "#{} {}",
+            item.get("number").and_then(JsonValue::as_u64).unwrap_or(0),
+            json_str(item.get("title"))
+        ),
+        &state,
+        &json_str(item.pointer("/user/login")),
+        &labels,
+        branches,
+        &json_str(item.get("html_url")),
+        &json_str(item.get("body")),
+        &comments,
+    )
+}
+
+fn format_gitlab_item(
+    item: &JsonValue,
+    notes: &JsonValue,
+    sigil: char,
+    merge_request: bool,
+) -> String {
+    let labels: Vec<String> = item
+        .get("labels")
+        .and_then(JsonValue::as_array)
+        .map(|labels| labels.iter().map(|label| json_str(Some(label))).collect())
+        .unwrap_or_default();
+    let comments: Vec<(String, String, String)> = notes
+        .as_array()
+        .map(|notes| {
+            notes
+                .iter()
+                // System notes record events such as label changes.
+                .filter(|note| note.get("system").and_then(JsonValue::as_bool) != Some(true))
+                .map(|note| {
+                    (
+                        json_str(note.pointer("/author/username")),
+                        json_str(note.get("created_at")),
+                        json_str(note.get("body")),
+                    )
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    let branches = merge_request.then(|| {
+        format!(
+            "Branch: {} → {} (head {})",
+            json_str(item.get("source_branch")),
+            json_str(item.get("target_branch")),
+            json_str(item.get("sha")),
+        )
+    });
+    format_item(
+        &format!(
+            "{sigil}{} {}",
+            item.get("iid").and_then(JsonValue::as_u64).unwrap_or(0),
+            json_str(item.get("title"))
+        ),
+        &json_str(item.get("state")),
+        &json_str(item.pointer("/author/username")),
+        &labels,
+        branches,
+        &json_str(item.get("web_url")),
+        &json_str(item.get("description")),
+        &comments,
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+fn format_item(
+    heading: &str,
+    state: &str,
+    author: &str,
+    labels: &[String],
+    branches: Option<String>,
+    url: &str,
+    body: &str,
+    comments: &[(String, String, String)],
+) -> String {
+    let mut lines = vec![format!("{heading} ({state}) by {author}")];
+    if !labels.is_empty() {
+        lines.push(format!("Labels: {}", labels.join(", ")));
+    }
+    lines.extend(branches);
+    if !url.is_empty() {
+        lines.push(url.to_string());
+    }
+    lines.push(String::new());
+    lines.push(if body.trim().is_empty() {
+        "(no description)".to_string()
+    } else {
+        truncate_text(body.trim(), TruncationPolicy::Bytes(BODY_MAX_BYTES))
+    });
+    if !comments.is_empty() {
+        lines.push(String::new());
+        let skipped = comments.len().saturating_sub(MAX_COMMENTS);
+        if skipped > 0 {
+            lines.push(format!(
+                "Comments ({}, showing the last {MAX_COMMENTS}):",
+                comments.len()
+            ));
+        } else {
+            lines.push(format!("Comments ({}):", comments.len()));
+        }
+        for (author, created_at, body) in &comments[skipped..] {
+            lines.push(format!("--- {author}, {created_at}"));
+            lines.push(truncate_text(
+                body.trim(),
+                TruncationPolicy::Bytes(COMMENT_MAX_BYTES),
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+fn format_github_checks(sha: &str, check_runs: &JsonValue, statuses: &JsonValue) -> String {
+    let mut lines = Vec::new();
+    for run in check_runs
+        .get("check_runs")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+    {
+        // `conclusion` is only set once the run has completed.
+        let result = match run.get("conclusion").and_then(JsonValue::as_str) {
+            Some(conclusion) => conclusion.to_string(),
+            None => json_str(run.get("status")),
+        };
+        lines.push(format!(
+            "- {}: {result} {}",
+            json_str(run.get("name")),
+            json_str(run.get("html_url"))
+        ));
+    }
+    for status in statuses
+        .get("statuses")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+    {
+        lines.push(format!(
+            "- {}: {} {}",
+            json_str(status.get("context")),
+            json_str(status.get("state")),
+            json_str(status.get("target_url"))
+        ));
+    }
+    let short_sha = sha.get(..12).unwrap_or(sha);
+    if lines.is_empty() {
+        return format!("No checks reported for {short_sha}.");
+    }
+    let lines: Vec<String> = lines
+        .iter()
+        .map(|line| line.trim_end().to_string())
+        .collect();
+    format!("Checks for {short_sha}:\n{}", lines.join("\n"))
+}
+
+fn format_gitlab_jobs(pipeline: &JsonValue, jobs: &JsonValue) -> String {
+    let mut lines = vec![
+        format!(
+            "Pipeline {} ({}) for {} {}",
+            pipeline.get("id").and_then(JsonValue::as_u64).unwrap_or(0),
+            json_str(pipeline.get("status")),
+            json_str(pipeline.get("ref")),
+            json_str(pipeline.get("web_url"))
+        )
+        .trim_end()
+        .to_string(),
+    ];
+    for job in jobs.as_array().into_iter().flatten() {
+        lines.push(
+            format!(
+                "- {} / {}: {} {}",
+                json_str(job.get("stage")),
+                json_str(job.get("name")),
+                json_str(job.get("status")),
+                json_str(job.get("web_url"))
+            )
+            .trim_end()
+            .to_string(),
+        );
+    }
+    lines.join("\n")
+}
+
+fn json_str(value: Option<&JsonValue>) -> String {
+    value
+        .and_then(JsonValue::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn latest_failed_run_only_looks_at_the_latest_run_per_workflow() {
+        let runs = json!({"workflow_runs": [
+            {"id": 5, "head_sha": "b", "workflow_id": 1, "conclusion": "success"},
+            {"id": 4, "head_sha": "b", "workflow_id": 1, "conclusion": "failure"},
+            {"id": 3, "head_sha": "b", "workflow_id": 2, "conclusion": "failure"},
+            {"id": 2, "head_sha": "a", "workflow_id": 3, "conclusion": "failure"},
+        ]});
+        assert_eq!(
+            latest_failed_run(&runs).and_then(|run| run.get("id")),
+            Some(&json!(3))
+        );
+
+        let passing = json!({"workflow_runs": [
+            {"id": 2, "head_sha": "b", "workflow_id": 1, "conclusion": "success"},
+            {"id": 1, "head_sha": "a", "workflow_id": 1, "conclusion": "failure"},
+        ]});
+        assert_eq!(latest_failed_run(&passing), None);
+        assert_eq!(latest_failed_run(&json!({"workflow_runs": []})), None);
+    }
+
+    #[test]
+    fn parse_remote_url_handles_https_ssh_and_scp_forms() {
+        let expected = Some(("github.com".to_string(), "openai/codex".to_string()));
+        assert_eq!(
+            parse_remote_url("https://github.com/openai/codex.git"),
+            expected
+        );
+        assert_eq!(
+            parse_remote_url("git@github.com:openai/codex.git"),
+            expected
+        );
+        assert_eq!(
+            parse_remote_url("ssh://git@gitlab.example.com:2222/group/sub/project.git"),
+            Some((
+                "gitlab.example.com".to_string(),
+                "group/sub/project".to_string()
+            ))
+        );
+        assert_eq!(parse_remote_url("/srv/git/project"), None);
+    }
+
+    #[test]
+    fn default_tokens_only_go_to_the_public_instances() {
+        let config = IssueTrackerConfig::default();
+        assert_eq!(
+            api_url(&config, IssueTrackerProvider::GitHub, "github.com").ok(),
+            Some("https://api.github.com".to_string())
+        );
+        assert_eq!(
+            api_url(&config, IssueTrackerProvider::GitLab, "gitlab.com").ok(),
+            Some("https://gitlab.com/api/v4".to_string())
+        );
+        assert!(api_url(&config, IssueTrackerProvider::GitHub, "github.evil.example").is_err());
+        assert!(api_url(&config, IssueTrackerProvider::GitLab, "gitlab.example.com").is_err());
+
+        let api_only = IssueTrackerConfig {
+            api_url: Some("https://git.example.com/api/v3/".to_string()),
+            ..IssueTrackerConfig::default()
+        };
+        assert!(api_url(&api_only, IssueTrackerProvider::GitHub, "git.example.com").is_err());
+        let explicit = IssueTrackerConfig {
+            token_env_var: Some("CODEX_TEST_UNSET_ISSUE_TOKEN".to_string()),
+            ..api_only
+        };
+        let url = api_url(&explicit, IssueTrackerProvider::GitHub, "git.example.com")
+            .expect("explicit API");
+        assert_eq!(url, "https://git.example.com/api/v3");
+        assert_eq!(
+            resolve_token(&explicit, IssueTrackerProvider::GitHub, &url),
+            None
+        );
+    }
+
+    #[test]
+    fn is_valid_repo_rejects_paths_outside_the_repository() {
+        use IssueTrackerProvider::GitHub;
+        use IssueTrackerProvider::GitLab;
+        assert!(is_valid_repo(GitHub, "openai/codex"));
+        assert!(is_valid_repo(GitHub, "my-org/my_repo.rs"));
+        assert!(is_valid_repo(GitLab, "group/sub/project"));
+        assert!(!is_valid_repo(GitHub, "group/sub/project"));
+        assert!(!is_valid_repo(GitHub, "codex"));
+        assert!(!is_valid_repo(GitHub, "openai/../../user"));
+        assert!(!is_valid_repo(GitHub, "openai/codex?per_page=1"));
+        assert!(!is_valid_repo(GitLab, "group//project"));
+    }
+
+    #[test]
+    fn extract_failure_excerpt_keeps_lines_before_errors() {
+        let mut log = String::new();
+        for index in 0..100 {
+            log.push_str(&format!("2024-05-01T10:00:00.0000000Z line {index}\n"));
+        }
+        log.push_str("2024-05-01T10:00:01.0000000Z ##[error]Process completed with exit code 1.\n");
+        log.push_str("2024-05-01T10:00:02.0000000Z ##[endgroup]\n");
+        log.push_str("2024-05-01T10:00:02.0000000Z Post job cleanup.\n");
+
+        let excerpt = extract_failure_excerpt(&log);
+        let lines: Vec<&str> = excerpt.lines().collect();
+        assert_eq!(lines.len(), ERROR_CONTEXT_LINES + 2);
+        assert_eq!(lines[0], "...");
+        assert_eq!(lines[1], "line 60");
+        assert_eq!(
+            lines.last().copied(),
+            Some("##[error]Process completed with exit code 1.")
+        );
+    }
+
+    #[test]
+    fn format_github_item_lists_labels_and_comments() {
+        let issue = json!({
+            "number": 12,
+            "title": "Crash on empty config",
+            "state": "open",
+            "user": {"login": "alice"},
+            "labels": [{"name": "bug"}],
+            "html_url": "https://github.com/openai/codex/issues/12",
+            "body": "Running `codex` with an empty config.toml panics.",
+        });
+        let comments = json!([
+            {"user": {"login": "bob"}, "created_at": "2025-01-02T03:04:05Z", "body": "Reproduced on main."},
+        ]);
+
+        assert_eq!(
+            format_github_item(&issue, &comments, None),
+            "#12 Crash on empty config (open) by alice
+Labels: bug
+https://github.com/openai/codex/issues/12
+
+Running `codex` with an empty config.toml panics.
+
+Comments (1):
+--- bob, 2025-01-02T03:04:05Z
+Reproduced on main."
+        );
+    }
+}
@@ -5,10 +5,14 @@
 // the TUI or the tracing stack).
 #![deny(clippy::print_stdout, clippy::print_stderr)]
 
+mod agent_budget;
+mod annotations;
 pub mod api_bridge;
 mod apply_patch;
 pub mod auth;
 pub mod bash;
+mod best_of;
+pub mod bundled_assets;
 mod client;
 mod client_common;
 pub mod codex;
@@ -19,33 +23,57 @@ mod codex_delegate;
 mod command_safety;
 pub mod config;
 pub mod config_loader;
+mod context_ignore;
 mod context_manager;
+mod context_manifest;
 pub mod custom_prompts;
+mod dependency_report;
 pub mod env;
 mod environment_context;
 pub mod error;
 pub mod exec;
 pub mod exec_env;
 mod exec_policy;
+mod external_edits;
 pub mod features;
+mod file_lock;
+mod file_mentions;
 mod flags;
 pub mod git_info;
+mod index_watcher;
+mod issue_tracker;
+pub use issue_tracker::fetch_failing_ci_logs;
 pub mod landlock;
+mod locale;
+pub mod log_filter;
+mod login_env;
 pub mod mcp;
 mod mcp_connection_manager;
 pub mod openai_models;
+mod output_spill;
 pub use mcp_connection_manager::MCP_SANDBOX_STATE_CAPABILITY;
 pub use mcp_connection_manager::MCP_SANDBOX_STATE_METHOD;
 pub use mcp_connection_manager::SandboxState;
 mod mcp_tool_call;
-mod memories;
+pub mod memories;
+pub mod memory_log;
 mod message_history;
+mod model_probe;
 mod model_provider_info;
 pub mod parse_command;
+mod patch_critic;
+mod patch_preview;
 pub mod path_utils;
 pub mod powershell;
+mod powershell_translate;
+mod prompt_sections;
+pub use prompt_sections::describe_prompt_composition;
+mod protected_paths;
+mod resource_preflight;
 pub mod sandboxing;
+mod scratch_dir;
 mod stream_events_utils;
+mod structured_output;
 mod text_encoding;
 pub mod token_data;
 mod truncate;
@@ -72,16 +100,26 @@ pub use auth::AuthManager;
 pub use auth::CodexAuth;
 pub mod default_client;
 pub mod project_doc;
+pub mod project_instructions;
+mod repo_map;
+mod request_trace;
 mod rollout;
 pub(crate) mod safety;
 pub mod seatbelt;
+mod secrets;
+mod semantic_index;
+mod session_environment;
 pub mod shell;
 pub mod shell_snapshot;
 pub mod skills;
 pub mod spawn;
+mod startup_timings;
 pub mod terminal;
+mod tool_hints;
 mod tools;
 pub mod turn_diff_tracker;
+mod turn_ledger;
+pub mod usage_stats;
 pub use rollout::ARCHIVED_SESSIONS_SUBDIR;
 pub use rollout::INTERACTIVE_SESSION_SOURCES;
 pub use rollout::RolloutRecorder;
@@ -99,6 +137,7 @@ mod tasks;
 mod user_notification;
 mod user_shell_command;
 pub mod util;
+mod wsl_paths;
 
 pub use apply_patch::CODEX_APPLY_PATCH_ARG1;
 pub use command_safety::is_dangerous_command;
@@ -0,0 +1,92 @@
+//! Response language and timezone preferences from `config.toml`.
+//!
+//! When either is configured, a short section is appended to the base
+//! instructions so users do not have to repeat "answer in Japanese" at the
+//! start of every session.
+
+use crate::config::Config;
+
+/// English names for common primary language subtags. Unknown tags are passed
+/// through verbatim, which models understand just as well.
+const LANGUAGE_NAMES: &[(&str, &str)] = &[
+    ("ar", "Arabic"),
+    ("de", "German"),
+    ("en", "English"),
+    ("es", "Spanish"),
+    ("fr", "French"),
+    ("hi", "Hindi"),
+    ("it", "Italian"),
+    ("ja", "Japanese"),
+    ("ko", "Korean"),
+    ("nl", "Dutch"),
+    ("pl", "Polish"),
+    ("pt", "Portuguese"),
+    ("ru", "Russian"),
+    ("tr", "Turkish"),
+    ("uk", "Ukrainian"),
+    ("zh", "Chinese"),
+];
+
+pub(crate) fn locale_instructions(config: &Config) -> Option<String> {
+    render_locale_instructions(config.language.as_deref(), config.timezone.as_deref())
+}
+
+fn render_locale_instructions(language: Option<&str>, timezone: Option<&str>) -> Option<String> {
+    let mut lines = Vec::new();
+    if let Some(language) = language {
+        let name = language_name(language);
+        lines.push(format!(
+            "- Respond to the user in {name}, including explanations, questions, and summaries. Keep code, identifiers, commands, and file paths unchanged, and write code comments in the language the surrounding code already uses."
+        ));
+    }
+    if let Some(timezone) = timezone {
+        lines.push(format!(
+            "- The user's timezone is {timezone}. Interpret and present dates and times in this timezone unless asked otherwise."
+        ));
+    }
+    if lines.is_empty() {
+        return None;
+    }
+    Some(format!("## Locale\n\n{}", lines.join("\n")))
+}
+
+/// Human-readable name for a language tag such as `ja` or `pt-BR`.
+fn language_name(tag: &str) -> String {
+    let primary = tag.split(['-', '_']).next().unwrap_or(tag);
+    match LANGUAGE_NAMES
+        .iter()
+        .find(|(code, _)| code.eq_ignore_ascii_case(primary))
+    {
+        Some((_, name)) if primary.len() == tag.len() => (*name).to_string(),
+        Some((_, name)) => format!("{name} ({tag})"),
+        None => tag.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn no_preferences_add_nothing() {
+        assert_eq!(render_locale_instructions(None, None), None);
+    }
+
+    #[test]
+    fn renders_language_and_timezone() {
+        let rendered =
+            render_locale_instructions(Some("ja"), Some("Asia/Tokyo")).expect("instructions");
+        assert!(rendered.starts_with("## Locale\n\n- Respond to the user in Japanese,"));
+        assert!(rendered.ends_with(
+            "- The user's timezone is Asia/Tokyo. Interpret and present dates and times in this timezone unless asked otherwise."
+        ));
+    }
+
+    #[test]
+    fn names_language_tags() {
+        assert_eq!(language_name("ja"), "Japanese");
+        assert_eq!(language_name("pt-BR"), "Portuguese (pt-BR)");
+        assert_eq!(language_name("eo"), "eo");
+    }
+}
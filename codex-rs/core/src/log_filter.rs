@@ -0,0 +1,68 @@
+//! Runtime control of the log filter.
+//!
+//! Front ends that own a reloadable `tracing` filter register a reloader at
+//! startup; `Op::SetLogFilter` then swaps the filter without a restart. The
+//! filter is process-wide, so it applies to every session in the process.
+
+use std::sync::OnceLock;
+
+type Reloader = Box<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+
+static RELOADER: OnceLock<Reloader> = OnceLock::new();
+
+/// Register the callback that applies new filter directives. Only the first
+/// registration takes effect.
+pub fn register_reloader(reloader: impl Fn(&str) -> Result<(), String> + Send + Sync + 'static) {
+    let _ = RELOADER.set(Box::new(reloader));
+}
+
+/// Replace the active filter with `directives` (`RUST_LOG` syntax).
+pub fn set_log_filter(directives: &str) -> Result<(), String> {
+    match RELOADER.get() {
+        Some(reload) => reload(directives.trim()),
+        None => Err("this client does not support changing the log filter".to_string()),
+    }
+}
+
+/// Directives to start with: `RUST_LOG` when set, then the configured
+/// `log.filter`, then `default`.
+pub fn initial_filter(configured: Option<&str>, default: &str) -> String {
+    resolve_filter(std::env::var("RUST_LOG").ok(), configured, default)
+}
+
+fn resolve_filter(env: Option<String>, configured: Option<&str>, default: &str) -> String {
+    env.filter(|directives| !directives.trim().is_empty())
+        .or_else(|| {
+            configured
+                .map(str::trim)
+                .filter(|directives| !directives.is_empty())
+                .map(str::to_string)
+        })
+        .unwrap_or_else(|| default.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn env_overrides_config_which_overrides_default() {
+        assert_eq!(
+            resolve_filter(
+                Some("codex_core=trace".to_string()),
+                Some("codex_core=debug"),
+                "info"
+            ),
+            "codex_core=trace"
+        );
+        assert_eq!(
+            resolve_filter(None, Some(" codex_core::exec=warn "), "info"),
+            "codex_core::exec=warn"
+        );
+        assert_eq!(
+            resolve_filter(Some(String::new()), Some(""), "info"),
+            "info"
+        );
+    }
+}
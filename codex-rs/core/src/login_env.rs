@@ -0,0 +1,158 @@
+//! Capture the environment the user's login shell sets up (PATH additions,
+//! version manager shims, exported tool settings) so spawned commands see the
+//! same environment as an interactive terminal instead of Codex's own, often
+//! minimal, inherited one.
+//!
+//! The capture runs once per process, in the background, the first time a
+//! session starts with `shell_environment_policy.experimental_capture_login_env`
+//! enabled; the result is layered over the parent environment by
+//! [`crate::exec_env::create_env`]. Commands spawned before it finishes use
+//! the inherited environment.
+
+use std::collections::HashMap;
+use std::process::Stdio;
+use std::sync::Once;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use tokio::process::Command;
+use tokio::time::timeout;
+use tracing::warn;
+
+use crate::shell::Shell;
+use crate::shell::ShellType;
+
+const CAPTURE_TIMEOUT: Duration = Duration::from_secs(10);
+const ENV_MARKER: &str = "__CODEX_LOGIN_ENV__";
+
+/// Variables that describe the capturing shell itself rather than the user's
+/// setup, and so must not leak into later commands.
+const SHELL_STATE_VARS: &[&str] = &["_", "OLDPWD", "PWD", "SHLVL"];
+
+static CAPTURE_STARTED: Once = Once::new();
+static LOGIN_ENV: OnceLock<Option<HashMap<String, String>>> = OnceLock::new();
+
+/// Start capturing the login environment of `shell` in the background unless
+/// a capture has already started in this process.
+pub(crate) fn start_login_env_capture(shell: Shell) {
+    CAPTURE_STARTED.call_once(|| {
+        tokio::spawn(async move {
+            let env = capture_login_env(&shell).await;
+            let _ = LOGIN_ENV.set(env);
+        });
+    });
+}
+
+/// The captured login environment, once the capture has succeeded.
+pub(crate) fn captured_login_env() -> Option<&'static HashMap<String, String>> {
+    LOGIN_ENV.get().and_then(Option::as_ref)
+}
+
+/// Run `shell` as an interactive login shell and return the environment it
+/// exports. Returns `None` (after logging why) when the shell is not supported
+/// or the capture fails.
+async fn capture_login_env(shell: &Shell) -> Option<HashMap<String, String>> {
+    let flags = match shell.shell_type {
+        ShellType::Zsh | ShellType::Bash => "-lic",
+        ShellType::Sh => "-lc",
+        ShellType::PowerShell | ShellType::Cmd => {
+            warn!(
+                "login environment capture is not supported for {}",
+                shell.name()
+            );
+            return None;
+        }
+    };
+
+    let mut command = Command::new(&shell.shell_path);
+    command
+        .arg(flags)
+        .arg(format!("printf '%s' '{ENV_MARKER}'; env -0"))
+        .stdin(Stdio::null())
+        .kill_on_drop(true);
+    // An interactive shell takes over the terminal it finds (job control,
+    // prompts); run it in a new session so it has none to take.
+    #[cfg(unix)]
+    unsafe {
+        command.pre_exec(|| {
+            if libc::setsid() == -1 {
+                return Err(std::io::Error::last_os_error());
+            }
+            Ok(())
+        });
+    }
+    let output = match timeout(CAPTURE_TIMEOUT, command.output()).await {
+        Ok(Ok(output)) => output,
+        Ok(Err(err)) => {
+            warn!(
+                "failed to run {} to capture login environment: {err}",
+                shell.name()
+            );
+            return None;
+        }
+        Err(_) => {
+            warn!("capturing the {} login environment timed out", shell.name());
+            return None;
+        }
+    };
+    if !output.status.success() {
+        warn!(
+            "capturing the {} login environment exited with {}: {}",
+            shell.name(),
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        return None;
+    }
+
+    let env = parse_env_output(&output.stdout);
+    if env.is_none() {
+        warn!("could not parse the {} login environment", shell.name());
+    }
+    env
+}
+
+/// Parse `env -0` output that follows [`ENV_MARKER`]. Anything the profile
+/// printed before the marker is ignored.
+fn parse_env_output(stdout: &[u8]) -> Option<HashMap<String, String>> {
+    let marker = ENV_MARKER.as_bytes();
+    let start = stdout
+        .windows(marker.len())
+        .position(|window| window == marker)?
+        + marker.len();
+
+    let env: HashMap<String, String> = stdout[start..]
+        .split(|byte| *byte == 0)
+        .filter_map(|entry| {
+            let entry = std::str::from_utf8(entry).ok()?;
+            let (key, value) = entry.split_once('=')?;
+            (!key.is_empty() && !SHELL_STATE_VARS.contains(&key))
+                .then(|| (key.to_string(), value.to_string()))
+        })
+        .collect();
+    (!env.is_empty()).then_some(env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_env_output_skips_profile_noise_and_shell_state() {
+        let stdout = format!(
+            "Welcome back!\n{ENV_MARKER}PATH=/home/u/.nvm/bin:/usr/bin\0MULTI=a\nb\0SHLVL=2\0PWD=/tmp\0"
+        );
+
+        let env = parse_env_output(stdout.as_bytes()).expect("env parsed");
+
+        assert_eq!(
+            env,
+            HashMap::from([
+                ("PATH".to_string(), "/home/u/.nvm/bin:/usr/bin".to_string()),
+                ("MULTI".to_string(), "a\nb".to_string()),
+            ])
+        );
+        assert_eq!(parse_env_output(b"no marker here"), None);
+    }
+}
@@ -1,21 +1,52 @@
+use crate::client::ModelClient;
 use crate::client_common::Prompt;
 use crate::client_common::ResponseEvent;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::compact::content_items_to_text;
 use crate::config::Config;
+use crate::config::MemoriesToml;
+use crate::config::MemoryBackend;
+use crate::config::MemoryRedaction;
+use crate::file_lock::lock_file_async;
+use crate::file_lock::write_atomic_async;
+use crate::git_info::current_branch_name;
 use crate::git_info::get_git_repo_root;
+use crate::memory_log::MemoryLogAction;
+use crate::memory_log::MemoryLogReason;
+use crate::memory_log::MemoryLogRecord;
+use crate::memory_log::memory_log_path;
+use crate::memory_log::read_memory_log;
+use crate::memory_log::record_memory_decisions;
+use crate::memory_log::session_additions;
+use crate::secrets::PatternDetector;
+use crate::secrets::REDACTED;
+use crate::secrets::SecretScanner;
+use crate::semantic_index::cosine_similarity;
 use crate::truncate::TruncationPolicy;
+use crate::truncate::approx_token_count;
 use crate::truncate::truncate_text;
+use async_trait::async_trait;
+use chrono::DateTime;
+use chrono::SecondsFormat;
+use chrono::Utc;
 use codex_protocol::models::ContentItem;
 use codex_protocol::models::ResponseItem;
 use codex_protocol::protocol::SessionSource;
 use codex_protocol::user_input::UserInput;
 use futures::StreamExt;
+use rusqlite::Connection;
+use rusqlite::OptionalExtension;
+use rusqlite::params;
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
 use tokio::fs;
 use tokio::fs::OpenOptions;
 use tokio::io::AsyncWriteExt;
@@ -23,65 +54,964 @@ use tracing::warn;
 
 pub(crate) const MEMORIES_DIRNAME: &str = ".codex";
 pub(crate) const MEMORIES_FILENAME: &str = "memories.md";
+const MEMORIES_DB_FILENAME: &str = "memories.sqlite";
 pub(crate) const MEMORIES_HEADER: &str = "## Memories";
 pub(crate) const MEMORIES_SEPARATOR: &str = "\n\n--- memories ---\n\n";
 
 const MEMORIES_FILE_HEADER: &str = "# Memories";
-const MEMORIES_MAX_BYTES: usize = 8 * 1024;
-const MEMORIES_PROMPT: &str = include_str!("../templates/memories/prompt.md");
-const MEMORIES_PROMPT_MAX_BYTES: usize = 2000;
-const MAX_NEW_MEMORIES_PER_TURN: usize = 6;
+pub(crate) const MEMORIES_PROMPT: &str = include_str!("../templates/memories/prompt.md");
 const NO_MEMORIES_RESPONSE: &str = "NO_MEMORIES";
+/// Bytes of each tool result given to the extraction request.
+const EXTRACTION_TOOL_OUTPUT_BYTES: usize = 1024;
+/// Embeddings of saved memories, next to the store they belong to.
+const MEMORY_EMBEDDINGS_FILENAME: &str = "memories.embeddings.json";
+/// Saved memories, newest first, that new ones are compared against.
+const MAX_SIMILARITY_ENTRIES: usize = 256;
+/// Texts sent in one embeddings request.
+const EMBED_BATCH_SIZE: usize = 64;
+/// Entries end with their metadata in an HTML comment, which Markdown
+/// viewers hide: `- Run tests <!-- created=... session=... scope=repo
+/// branch=main category=build -->`.
+const METADATA_OPEN: &str = "<!--";
+const METADATA_CLOSE: &str = "-->";
+pub(crate) const RELEVANT_MEMORIES_OPEN_TAG: &str = "<memories>";
+const RELEVANT_MEMORIES_CLOSE_TAG: &str = "</memories>";
+/// Texts are unique ignoring ASCII case, like entries of `memories.md`.
+const SQLITE_SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS memories (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    text TEXT NOT NULL,
+    created_at TEXT,
+    session_id TEXT,
+    scope TEXT,
+    branch TEXT,
+    category TEXT
+);
+CREATE UNIQUE INDEX IF NOT EXISTS memories_text ON memories (text COLLATE NOCASE);
+CREATE INDEX IF NOT EXISTS memories_session ON memories (session_id);
+";
+/// How long a connection waits for another process to finish writing the
+/// database before failing.
+const SQLITE_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Words too common to say whether a memory is relevant.
+const STOP_WORDS: &[&str] = &[
+    "about", "all", "also", "and", "any", "are", "but", "can", "could", "for", "from", "have",
+    "into", "its", "just", "like", "make", "not", "our", "out", "please", "should", "that", "the",
+    "then", "there", "they", "this", "use", "was", "what", "when", "with", "would", "you", "your",
+];
 
+/// The memories section of the instructions, or `None` when memories are
+/// disabled or exceed `[memories]` limits; [`MemoryRanker`] sends those per
+/// turn.
 pub(crate) async fn read_memories_for_instructions(config: &Config) -> Option<String> {
+    if !config.memories.enabled {
+        return None;
+    }
+    let entries = read_memory_texts(config).await;
+    if !fits_limits(&entries, &config.memories) {
+        return None;
+    }
+    build_memories_section(&entries)
+}
+
+/// Memories too many for the instructions. Each turn gets those most
+/// relevant to its input, within the `[memories]` limits, and each memory
+/// is sent at most once until the history is compacted. The first turn
+/// after that gets the top-ranked memories even when none of them share
+/// words with its input.
+#[derive(Debug)]
+pub(crate) struct MemoryRanker {
+    entries: Vec<String>,
+    limits: MemoriesToml,
+    sent: Mutex<HashSet<String>>,
+}
+
+impl MemoryRanker {
+    /// `None` when memories are disabled or fit in the instructions.
+    pub(crate) async fn load(config: &Config) -> Option<Self> {
+        if !config.memories.enabled {
+            return None;
+        }
+        let entries = read_memory_texts(config).await;
+        if fits_limits(&entries, &config.memories) {
+            return None;
+        }
+        Some(Self {
+            entries,
+            limits: config.memories.clone(),
+            sent: Mutex::new(HashSet::new()),
+        })
+    }
+
+    /// A message with the memories relevant to `inputs` that were not sent
+    /// yet, if there are any.
+    pub(crate) fn relevant_memories(&self, inputs: &[UserInput]) -> Option<ResponseItem> {
+        let query = keywords(&collect_user_input_texts(inputs).join("\n"));
+        let mut sent = self.sent.lock().ok()?;
+        let include_unmatched = sent.is_empty();
+        if query.is_empty() && !include_unmatched {
+            return None;
+        }
+        let unsent: Vec<&String> = self
+            .entries
+            .iter()
+            .filter(|entry| !sent.contains(&entry.to_ascii_lowercase()))
+            .collect();
+        let selected = rank_memories(&unsent, &query, &self.limits, include_unmatched);
+        if selected.is_empty() {
+            return None;
+        }
+        sent.extend(selected.iter().map(|entry| entry.to_ascii_lowercase()));
+        Some(RelevantMemories { entries: selected }.into())
+    }
+
+    /// Allow every memory to be sent again, once the history that held the
+    /// earlier ones was replaced.
+    pub(crate) fn forget_sent(&self) {
+        if let Ok(mut sent) = self.sent.lock() {
+            sent.clear();
+        }
+    }
+}
+
+/// Memories picked for a turn by [`MemoryRanker`].
+pub(crate) struct RelevantMemories {
+    entries: Vec<String>,
+}
+
+impl RelevantMemories {
+    pub(crate) fn is_relevant_memories(message: &[ContentItem]) -> bool {
+        if let [ContentItem::InputText { text }] = message {
+            text.starts_with(RELEVANT_MEMORIES_OPEN_TAG)
+        } else {
+            false
+        }
+    }
+}
+
+impl From<RelevantMemories> for ResponseItem {
+    fn from(memories: RelevantMemories) -> Self {
+        let section = build_memories_section(&memories.entries).unwrap_or_default();
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format!(
+                    "{RELEVANT_MEMORIES_OPEN_TAG}\n{section}\n{RELEVANT_MEMORIES_CLOSE_TAG}"
+                ),
+            }],
+        }
+    }
+}
+
+/// Every memory of the files that are read, without duplicates. With
+/// `scope_by_branch`, memories tagged with another branch are left out, and
+/// with `categories`, those of other categories.
+async fn read_memory_texts(config: &Config) -> Vec<String> {
     let mut entries: Vec<String> = Vec::new();
     let mut seen: HashSet<String> = HashSet::new();
+    let branch = if config.memories.scope_by_branch {
+        Some(current_branch_name(&config.cwd).await)
+    } else {
+        None
+    };
 
-    let paths = memory_paths(config);
-    for path in paths {
-        match read_memories_file(&path).await {
+    for store in memory_stores(config) {
+        match store.entries(config.memories.max_file_bytes).await {
             Ok(values) => {
                 for entry in values {
-                    let trimmed = entry.trim();
-                    if trimmed.is_empty() {
+                    if let Some(branch) = &branch
+                        && !entry.applies_to_branch(branch.as_deref())
+                    {
                         continue;
                     }
-                    let key = trimmed.to_ascii_lowercase();
+                    if !entry.in_categories(&config.memories.categories) {
+                        continue;
+                    }
+                    let key = entry.text.to_ascii_lowercase();
                     if seen.insert(key) {
-                        entries.push(trimmed.to_string());
+                        entries.push(entry.text);
                     }
                 }
             }
             Err(err) => {
-                warn!("Failed to read memories at {}: {err:#}", path.display());
+                warn!(
+                    "Failed to read memories at {}: {err:#}",
+                    store.location().display()
+                );
             }
         }
     }
+    entries
+}
 
-    build_memories_section(&entries)
+fn fits_limits(entries: &[String], limits: &MemoriesToml) -> bool {
+    entries.len() <= limits.max_entries
+        && build_memories_section(entries)
+            .is_none_or(|section| approx_token_count(&section) <= limits.token_budget)
+}
+
+/// The entries that share words with `query`, most shared words first and
+/// newer entries first among equals, within `limits`. With
+/// `include_unmatched`, the remaining budget goes to the newest entries that
+/// share none.
+fn rank_memories(
+    entries: &[&String],
+    query: &HashSet<String>,
+    limits: &MemoriesToml,
+    include_unmatched: bool,
+) -> Vec<String> {
+    let mut scored: Vec<(usize, usize, &String)> = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entry)| {
+            let score = keywords(entry).intersection(query).count();
+            (score > 0 || include_unmatched).then_some((score, index, *entry))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(b.1.cmp(&a.1)));
+
+    let mut selected = Vec::new();
+    let mut tokens = approx_token_count(MEMORIES_HEADER);
+    for (_, _, entry) in scored.into_iter().take(limits.max_entries) {
+        let cost = approx_token_count(entry) + 1;
+        if tokens + cost > limits.token_budget {
+            continue;
+        }
+        tokens += cost;
+        selected.push(entry.clone());
+    }
+    selected
+}
+
+/// Lowercase words of `text` that can tell memories apart, with a plural
+/// `s` dropped so `tests` matches `test`.
+fn keywords(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .map(str::to_lowercase)
+        .filter(|word| word.chars().count() >= 3 && !STOP_WORDS.contains(&word.as_str()))
+        .map(|word| match word.strip_suffix('s') {
+            Some(stem) if stem.chars().count() >= 3 && !stem.ends_with('s') => stem.to_string(),
+            _ => word,
+        })
+        .collect()
+}
+
+/// A memory store and the entries it holds, for `codex memories`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MemoriesFile {
+    pub path: PathBuf,
+    pub entries: Vec<MemoryEntry>,
+}
+
+/// One memory and what is known about how it was recorded. Entries written
+/// before metadata existed, or by hand, have none.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryEntry {
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    /// The session that recorded the memory; `None` for `codex memories add`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<MemoryScope>,
+    /// The branch the memory applies to; `None` for every branch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch: Option<String>,
+    /// Lowercase category such as `build` or `style`, from the entry's
+    /// metadata or the `## Category` heading above it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+}
+
+/// Which store an entry was written to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MemoryScope {
+    /// The store in `$CODEX_HOME`.
+    Global,
+    /// The store in the repository's `.codex` directory.
+    Repo,
+}
+
+impl MemoryScope {
+    fn as_str(self) -> &'static str {
+        match self {
+            MemoryScope::Global => "global",
+            MemoryScope::Repo => "repo",
+        }
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "global" => Some(MemoryScope::Global),
+            "repo" => Some(MemoryScope::Repo),
+            _ => None,
+        }
+    }
+}
+
+/// Metadata written with new entries.
+#[derive(Debug, Clone)]
+struct NewMemoryMetadata {
+    created_at: DateTime<Utc>,
+    session_id: Option<String>,
+    scope: MemoryScope,
+    branch: Option<String>,
+}
+
+impl NewMemoryMetadata {
+    fn now(session_id: Option<String>, scope: MemoryScope, branch: Option<String>) -> Self {
+        Self {
+            created_at: Utc::now(),
+            session_id,
+            scope,
+            branch,
+        }
+    }
+
+    /// A leading `[category]` of each text becomes the entry's category.
+    fn entries(&self, texts: &[String]) -> Vec<MemoryEntry> {
+        texts
+            .iter()
+            .map(|text| {
+                let (category, text) = split_category(text);
+                MemoryEntry {
+                    text: text.to_string(),
+                    created_at: Some(self.created_at),
+                    session_id: self.session_id.clone(),
+                    scope: Some(self.scope),
+                    branch: self.branch.clone(),
+                    category,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The lowercase category of a `[category] text` memory and its text.
+fn split_category(text: &str) -> (Option<String>, &str) {
+    if let Some(rest) = text.strip_prefix('[')
+        && let Some((category, text)) = rest.split_once("] ")
+        && (1..=32).contains(&category.len())
+        && category
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && !text.trim().is_empty()
+    {
+        (Some(category.to_ascii_lowercase()), text.trim())
+    } else {
+        (None, text)
+    }
+}
+
+impl MemoryEntry {
+    /// Whether the memory is used on `branch`: untagged memories are used on
+    /// every branch, tagged ones only on theirs.
+    fn applies_to_branch(&self, branch: Option<&str>) -> bool {
+        self.branch
+            .as_deref()
+            .is_none_or(|tagged| Some(tagged) == branch)
+    }
+
+    /// Whether the memory is sent when `[memories] categories` is
+    /// `categories`: uncategorized memories always are.
+    fn in_categories(&self, categories: &[String]) -> bool {
+        categories.is_empty()
+            || self.category.as_ref().is_none_or(|category| {
+                categories
+                    .iter()
+                    .any(|wanted| wanted.eq_ignore_ascii_case(category))
+            })
+    }
+
+    /// The metadata comment written after the entry in `memories.md`.
+    fn annotation(&self) -> Option<String> {
+        let mut fields = Vec::new();
+        if let Some(created_at) = self.created_at {
+            fields.push(format!(
+                "created={}",
+                created_at.to_rfc3339_opts(SecondsFormat::Secs, true)
+            ));
+        }
+        if let Some(session_id) = &self.session_id {
+            fields.push(format!("session={session_id}"));
+        }
+        if let Some(scope) = self.scope {
+            fields.push(format!("scope={}", scope.as_str()));
+        }
+        if let Some(branch) = &self.branch {
+            fields.push(format!("branch={branch}"));
+        }
+        if let Some(category) = &self.category {
+            fields.push(format!("category={category}"));
+        }
+        (!fields.is_empty())
+            .then(|| format!("{METADATA_OPEN} {} {METADATA_CLOSE}", fields.join(" ")))
+    }
+}
+
+/// Where the memories of one scope are kept; `[memories] backend` picks the
+/// implementation.
+#[async_trait]
+pub trait MemoryStore: Send + Sync {
+    /// The file that holds the entries.
+    fn location(&self) -> &Path;
+
+    /// The entries in the order they were saved. The file backend reads only
+    /// the last `max_bytes` of the file; the SQLite backend reads them all.
+    async fn entries(&self, max_bytes: usize) -> std::io::Result<Vec<MemoryEntry>>;
+
+    /// Save the entries whose text the store does not hold yet, ignoring
+    /// case. Returns how many were saved.
+    async fn append(&self, entries: Vec<MemoryEntry>) -> std::io::Result<usize>;
+
+    /// Remove the entry `selector` names, either by its 1-based position or
+    /// by its text (ignoring case). Returns the removed entry.
+    async fn remove(&self, selector: &str) -> std::io::Result<Option<String>>;
+
+    /// Remove the entry whose text is `text` (ignoring case), even when the
+    /// text is a number. Returns the removed entry.
+    async fn remove_text(&self, text: &str) -> std::io::Result<Option<String>>;
+
+    /// Delete every entry. Returns whether there were any.
+    async fn clear(&self) -> std::io::Result<bool>;
+}
+
+/// `memories.md`: a Markdown list with the metadata of each entry in a
+/// trailing HTML comment.
+struct FileMemoryStore {
+    path: PathBuf,
+}
+
+impl FileMemoryStore {
+    async fn remove_entry(
+        &self,
+        selector: &str,
+        by_position: bool,
+    ) -> std::io::Result<Option<String>> {
+        let _lock = lock_file_async(&self.path).await?;
+        let text = match fs::read_to_string(&self.path).await {
+            Ok(text) => text,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        let Some((remaining, removed)) = without_memory(&text, selector, by_position) else {
+            return Ok(None);
+        };
+        // The file is replaced through a temporary file, so a crash mid-write
+        // cannot truncate it; keep the permissions the user gave it.
+        #[cfg(unix)]
+        let mode = {
+            use std::os::unix::fs::PermissionsExt;
+            Some(fs::metadata(&self.path).await?.permissions().mode() & 0o777)
+        };
+        #[cfg(not(unix))]
+        let mode = None;
+        write_atomic_async(&self.path, remaining.into_bytes(), mode).await?;
+        Ok(Some(removed))
+    }
+}
+
+#[async_trait]
+impl MemoryStore for FileMemoryStore {
+    fn location(&self) -> &Path {
+        &self.path
+    }
+
+    async fn entries(&self, max_bytes: usize) -> std::io::Result<Vec<MemoryEntry>> {
+        read_memories_file(&self.path, max_bytes).await
+    }
+
+    async fn append(&self, entries: Vec<MemoryEntry>) -> std::io::Result<usize> {
+        append_memories(&self.path, entries).await
+    }
+
+    /// Lines other than the removed entry are kept as they are.
+    async fn remove(&self, selector: &str) -> std::io::Result<Option<String>> {
+        self.remove_entry(selector, true).await
+    }
+
+    async fn remove_text(&self, text: &str) -> std::io::Result<Option<String>> {
+        self.remove_entry(text, false).await
+    }
+
+    /// Deletes the file.
+    async fn clear(&self) -> std::io::Result<bool> {
+        let _lock = lock_file_async(&self.path).await?;
+        match fs::remove_file(&self.path).await {
+            Ok(()) => Ok(true),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// `memories.sqlite`: every entry with its metadata, looked up by text
+/// through an index. When the database is created, the entries of a
+/// `memories.md` next to it are imported.
+struct SqliteMemoryStore {
+    path: PathBuf,
+}
+
+impl SqliteMemoryStore {
+    /// Run `query` on the database in a blocking task. Returns `missing`
+    /// without creating the database when it does not exist, there is
+    /// nothing to import and `create` is false.
+    async fn with_connection<T, F>(&self, create: bool, missing: T, query: F) -> std::io::Result<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut Connection) -> rusqlite::Result<T> + Send + 'static,
+    {
+        let path = self.path.clone();
+        tokio::task::spawn_blocking(move || {
+            let markdown = path.with_file_name(MEMORIES_FILENAME);
+            let exists = path.exists();
+            if !exists && !create && !markdown.exists() {
+                return Ok(missing);
+            }
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            let mut conn = Connection::open(&path).map_err(std::io::Error::other)?;
+            conn.busy_timeout(SQLITE_BUSY_TIMEOUT)
+                .map_err(std::io::Error::other)?;
+            conn.execute_batch(SQLITE_SCHEMA)
+                .map_err(std::io::Error::other)?;
+            if !exists {
+                let imported = match std::fs::read_to_string(&markdown) {
+                    Ok(text) => parse_memories(&text),
+                    Err(err) if err.kind() == ErrorKind::NotFound => Vec::new(),
+                    Err(err) => return Err(err),
+                };
+                insert_entries(&mut conn, &imported).map_err(std::io::Error::other)?;
+            }
+            query(&mut conn).map_err(std::io::Error::other)
+        })
+        .await
+        .map_err(std::io::Error::other)?
+    }
+}
+
+#[async_trait]
+impl MemoryStore for SqliteMemoryStore {
+    fn location(&self) -> &Path {
+        &self.path
+    }
+
+    async fn entries(&self, _max_bytes: usize) -> std::io::Result<Vec<MemoryEntry>> {
+        self.with_connection(false, Vec::new(), |conn| {
+            let mut statement = conn.prepare(
+                "SELECT text, created_at, session_id, scope, branch, category FROM memories ORDER BY id",
+            )?;
+            statement
+                .query_map([], |row| {
+                    let created_at: Option<String> = row.get(1)?;
+                    let scope: Option<String> = row.get(3)?;
+                    Ok(MemoryEntry {
+                        text: row.get(0)?,
+                        created_at: created_at
+                            .and_then(|value| DateTime::parse_from_rfc3339(&value).ok())
+                            .map(|created| created.with_timezone(&Utc)),
+                        session_id: row.get(2)?,
+                        scope: scope.as_deref().and_then(MemoryScope::parse),
+                        branch: row.get(4)?,
+                        category: row.get(5)?,
+                    })
+                })?
+                .collect()
+        })
+        .await
+    }
+
+    async fn append(&self, entries: Vec<MemoryEntry>) -> std::io::Result<usize> {
+        if entries.is_empty() {
+            return Ok(0);
+        }
+        self.with_connection(true, 0, move |conn| insert_entries(conn, &entries))
+            .await
+    }
+
+    async fn remove(&self, selector: &str) -> std::io::Result<Option<String>> {
+        self.remove_entry(selector, true).await
+    }
+
+    async fn remove_text(&self, text: &str) -> std::io::Result<Option<String>> {
+        self.remove_entry(text, false).await
+    }
+
+    async fn clear(&self) -> std::io::Result<bool> {
+        self.with_connection(false, false, |conn| {
+            Ok(conn.execute("DELETE FROM memories", [])? > 0)
+        })
+        .await
+    }
+}
+
+impl SqliteMemoryStore {
+    async fn remove_entry(
+        &self,
+        selector: &str,
+        by_position: bool,
+    ) -> std::io::Result<Option<String>> {
+        let selector = selector.trim().to_string();
+        self.with_connection(false, None, move |conn| {
+            let position = selector.parse::<i64>().ok().filter(|_| by_position);
+            let target: Option<(i64, String)> = match position {
+                Some(position) if position >= 1 => conn
+                    .query_row(
+                        "SELECT id, text FROM memories ORDER BY id LIMIT 1 OFFSET ?1",
+                        params![position - 1],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )
+                    .optional()?,
+                _ => conn
+                    .query_row(
+                        "SELECT id, text FROM memories WHERE text = ?1 COLLATE NOCASE",
+                        params![selector],
+                        |row| Ok((row.get(0)?, row.get(1)?)),
+                    )
+                    .optional()?,
+            };
+            let Some((id, text)) = target else {
+                return Ok(None);
+            };
+            conn.execute("DELETE FROM memories WHERE id = ?1", params![id])?;
+            Ok(Some(text))
+        })
+        .await
+    }
+}
+
+/// Insert the entries whose text is new. Returns how many were inserted.
+fn insert_entries(conn: &mut Connection, entries: &[MemoryEntry]) -> rusqlite::Result<usize> {
+    let tx = conn.transaction()?;
+    let mut inserted = 0;
+    {
+        let mut statement = tx.prepare(
+            "INSERT OR IGNORE INTO memories (text, created_at, session_id, scope, branch, category) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        )?;
+        for entry in entries {
+            let text = entry.text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            inserted += statement.execute(params![
+                text,
+                entry
+                    .created_at
+                    .map(|created| created.to_rfc3339_opts(SecondsFormat::Secs, true)),
+                entry.session_id,
+                entry.scope.map(MemoryScope::as_str),
+                entry.branch,
+                entry.category,
+            ])?;
+        }
+    }
+    tx.commit()?;
+    Ok(inserted)
+}
+
+/// Every memory store that feeds the instructions, in the order they are
+/// read. Stores that do not exist are listed with no entries.
+pub async fn list_memories(config: &Config) -> std::io::Result<Vec<MemoriesFile>> {
+    let mut files = Vec::new();
+    for store in memory_stores(config) {
+        let entries = store.entries(usize::MAX).await?;
+        files.push(MemoriesFile {
+            path: store.location().to_path_buf(),
+            entries,
+        });
+    }
+    Ok(files)
+}
+
+/// The store `codex memories` edits and its scope: the one new memories are
+/// written to, or the one in `$CODEX_HOME` when `global` is set.
+pub fn editable_memory_store(config: &Config, global: bool) -> (Box<dyn MemoryStore>, MemoryScope) {
+    if global {
+        (
+            memory_store(config.memories.backend, &config.codex_home),
+            MemoryScope::Global,
+        )
+    } else {
+        memory_write_target(config, &config.cwd)
+    }
+}
+
+/// Add `entry` to `store`, which holds memories of `scope`, for `branch`
+/// only when set. Returns false when the store already holds it.
+pub async fn add_memory(
+    store: &dyn MemoryStore,
+    entry: &str,
+    scope: MemoryScope,
+    branch: Option<String>,
+) -> std::io::Result<bool> {
+    let entry = entry.split_whitespace().collect::<Vec<_>>().join(" ");
+    if entry.is_empty() {
+        return Err(std::io::Error::new(
+            ErrorKind::InvalidInput,
+            "memory must not be empty",
+        ));
+    }
+    let metadata = NewMemoryMetadata::now(None, scope, branch);
+    let added = store.append(metadata.entries(&[entry.clone()])).await? > 0;
+    if added {
+        record_memory_decisions(
+            store.location(),
+            &[MemoryLogRecord::new(
+                MemoryLogAction::Add,
+                Some(entry),
+                Some(MemoryLogReason::Manual),
+            )],
+        )
+        .await;
+    }
+    Ok(added)
+}
+
+/// Remove the memory `selector` picks, by number or text, from `store`.
+/// Returns its text, or `None` when no memory matches.
+pub async fn remove_memory(
+    store: &dyn MemoryStore,
+    selector: &str,
+) -> std::io::Result<Option<String>> {
+    let removed = store.remove(selector).await?;
+    if let Some(text) = &removed {
+        record_memory_decisions(
+            store.location(),
+            &[MemoryLogRecord::new(
+                MemoryLogAction::Prune,
+                Some(text.clone()),
+                Some(MemoryLogReason::Removed),
+            )],
+        )
+        .await;
+    }
+    Ok(removed)
+}
+
+/// Delete every memory in `store`. Returns whether there were any.
+pub async fn clear_memories(store: &dyn MemoryStore) -> std::io::Result<bool> {
+    let cleared = store.clear().await?;
+    let embeddings = store.location().with_file_name(MEMORY_EMBEDDINGS_FILENAME);
+    match fs::remove_file(&embeddings).await {
+        Ok(()) => {}
+        Err(err) if err.kind() == ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+    if cleared {
+        record_memory_decisions(
+            store.location(),
+            &[MemoryLogRecord::new(
+                MemoryLogAction::Prune,
+                None,
+                Some(MemoryLogReason::Cleared),
+            )],
+        )
+        .await;
+    }
+    Ok(cleared)
+}
+
+/// Remove the memories the session `session_id` saved to `store`, per its
+/// log. Returns the texts removed.
+pub async fn undo_session_memories(
+    store: &dyn MemoryStore,
+    session_id: &str,
+) -> std::io::Result<Vec<String>> {
+    let records = read_memory_log(&memory_log_path(store.location())).await?;
+    let mut removed = Vec::new();
+    let mut pruned = Vec::new();
+    for text in session_additions(&records, session_id) {
+        if let Some(text) = store.remove_text(&text).await? {
+            pruned.push(MemoryLogRecord {
+                session_id: Some(session_id.to_string()),
+                ..MemoryLogRecord::new(
+                    MemoryLogAction::Prune,
+                    Some(text.clone()),
+                    Some(MemoryLogReason::Undone),
+                )
+            });
+            removed.push(text);
+        }
+    }
+    record_memory_decisions(store.location(), &pruned).await;
+    Ok(removed)
+}
+
+/// `entries` as a `memories.md` file, metadata included.
+pub fn memories_markdown(entries: &[MemoryEntry]) -> String {
+    let mut out = format!("{MEMORIES_FILE_HEADER}\n\n");
+    for entry in entries {
+        out.push_str(&entry_line(entry));
+    }
+    out
+}
+
+/// The entries of an export: a JSON array as written by `codex memories
+/// export --format json`, or a memories file.
+pub fn parse_memories_export(text: &str) -> std::io::Result<Vec<MemoryEntry>> {
+    if text.trim_start().starts_with('[') {
+        serde_json::from_str(text).map_err(|err| {
+            std::io::Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid memories JSON: {err}"),
+            )
+        })
+    } else {
+        Ok(parse_memories(text))
+    }
+}
+
+/// An imported memory that says the opposite of a saved one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryConflict {
+    pub imported: String,
+    pub existing: String,
+}
+
+/// What [`import_memories`] did with the entries it was given.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryImport {
+    pub imported: usize,
+    /// Entries whose text the store already held, ignoring case.
+    pub duplicates: usize,
+    pub conflicts: Vec<MemoryConflict>,
+}
+
+/// Merge `entries` into `store`, which holds memories of `scope`. Entries are
+/// keyed by their text like [`MemoryStore::append`]; those that contradict a
+/// saved memory are reported and only saved when `keep_conflicts` is set.
+/// The other metadata of each entry is kept.
+pub async fn import_memories(
+    store: &dyn MemoryStore,
+    entries: Vec<MemoryEntry>,
+    scope: MemoryScope,
+    keep_conflicts: bool,
+) -> std::io::Result<MemoryImport> {
+    let existing = store.entries(usize::MAX).await?;
+    let mut seen: HashSet<String> = existing
+        .iter()
+        .map(|entry| entry.text.to_ascii_lowercase())
+        .collect();
+    let mut report = MemoryImport::default();
+    let mut additions = Vec::new();
+    let mut records = Vec::new();
+    for mut entry in entries {
+        entry.text = entry.text.split_whitespace().collect::<Vec<_>>().join(" ");
+        if entry.text.is_empty() {
+            continue;
+        }
+        if !seen.insert(entry.text.to_ascii_lowercase()) {
+            report.duplicates += 1;
+            records.push(MemoryLogRecord::new(
+                MemoryLogAction::Skip,
+                Some(entry.text),
+                Some(MemoryLogReason::Duplicate),
+            ));
+            continue;
+        }
+        if let Some(saved) = existing
+            .iter()
+            .find(|saved| memories_contradict(&saved.text, &entry.text))
+        {
+            report.conflicts.push(MemoryConflict {
+                imported: entry.text.clone(),
+                existing: saved.text.clone(),
+            });
+            if !keep_conflicts {
+                records.push(MemoryLogRecord::new(
+                    MemoryLogAction::Skip,
+                    Some(entry.text),
+                    Some(MemoryLogReason::Conflict),
+                ));
+                continue;
+            }
+        }
+        entry.scope = Some(scope);
+        records.push(MemoryLogRecord {
+            session_id: entry.session_id.clone(),
+            ..MemoryLogRecord::new(
+                MemoryLogAction::Add,
+                Some(entry.text.clone()),
+                Some(MemoryLogReason::Imported),
+            )
+        });
+        additions.push(entry);
+    }
+    report.imported = store.append(additions).await?;
+    record_memory_decisions(store.location(), &records).await;
+    Ok(report)
+}
+
+/// Words that turn a memory into its opposite, as in "Use tabs" and "Never
+/// use tabs".
+const NEGATIONS: &[&str] = &["not", "never", "no", "don't", "dont", "avoid"];
+/// Words that do not change what a memory says.
+const FILLER_WORDS: &[&str] = &["do", "always", "please", "should", "must"];
+
+/// Whether two memories say opposite things: the same words with and
+/// without a negation, or the same `label: value` label with another value.
+fn memories_contradict(a: &str, b: &str) -> bool {
+    fn words(text: &str) -> (Vec<String>, usize) {
+        let mut kept = Vec::new();
+        let mut negations = 0;
+        for word in text
+            .split(|c: char| !c.is_alphanumeric() && c != '\'')
+            .filter(|word| !word.is_empty())
+        {
+            let word = word.to_lowercase();
+            if NEGATIONS.contains(&word.as_str()) {
+                negations += 1;
+            } else if !FILLER_WORDS.contains(&word.as_str()) {
+                kept.push(word);
+            }
+        }
+        (kept, negations)
+    }
+
+    if let Some((label_a, value_a)) = a.split_once(':')
+        && let Some((label_b, value_b)) = b.split_once(':')
+        && !label_a.trim().is_empty()
+        && label_a.trim().eq_ignore_ascii_case(label_b.trim())
+        && !value_a.trim().eq_ignore_ascii_case(value_b.trim())
+    {
+        return true;
+    }
+    let (words_a, negations_a) = words(a);
+    let (words_b, negations_b) = words(b);
+    !words_a.is_empty() && words_a == words_b && negations_a % 2 != negations_b % 2
 }
 
 pub(crate) async fn maybe_record_memories(
     sess: &Session,
     turn_context: &TurnContext,
     inputs: &[UserInput],
+    last_agent_message: Option<&str>,
 ) {
     if !should_record_memories(turn_context) {
         return;
     }
+    let limits = turn_context.client.config().memories.clone();
 
     let input_texts = collect_user_input_texts(inputs);
     if input_texts.is_empty() {
         return;
     }
 
-    let mut combined = input_texts.join("\n\n");
-    if combined.len() > MEMORIES_PROMPT_MAX_BYTES {
-        combined = truncate_text(
-            &combined,
-            TruncationPolicy::Bytes(MEMORIES_PROMPT_MAX_BYTES),
-        );
-    }
+    let combined = if limits.include_turn_output {
+        let history = sess.clone_history().await.get_history();
+        extraction_input(
+            &input_texts,
+            last_agent_message,
+            &turn_tool_outputs(&history),
+            limits.extraction_input_bytes,
+        )
+    } else {
+        extraction_input(&input_texts, None, &[], limits.extraction_input_bytes)
+    };
 
     let prompt = Prompt {
         input: vec![ResponseItem::Message {
@@ -92,6 +1022,9 @@ pub(crate) async fn maybe_record_memories(
         tools: Vec::new(),
         parallel_tool_calls: false,
         base_instructions_override: Some(MEMORIES_PROMPT.to_string()),
+        locale_instructions: None,
+        model_instructions: None,
+        composed_instructions: None,
         output_schema: None,
     };
 
@@ -145,53 +1078,427 @@ pub(crate) async fn maybe_record_memories(
         output_items.join("\n")
     };
 
-    let mut candidates = parse_memory_candidates(&raw_output);
-    if candidates.is_empty() {
+    let parsed = parse_memory_candidates(&raw_output);
+    if parsed.is_empty() {
+        return;
+    }
+    let (store, scope) =
+        memory_write_target(turn_context.client.config().as_ref(), &turn_context.cwd);
+    let mut log = ExtractionLog {
+        session_id: sess.conversation_id().to_string(),
+        turn_id: turn_context.sub_id.clone(),
+        records: Vec::new(),
+    };
+    let raw_output = match memory_scanner(&limits) {
+        Some(scanner) => scanner.redact(&raw_output).unwrap_or(raw_output),
+        None => raw_output,
+    };
+    log.records.push(MemoryLogRecord {
+        session_id: Some(log.session_id.clone()),
+        turn_id: Some(log.turn_id.clone()),
+        raw_output: Some(raw_output),
+        ..MemoryLogRecord::new(MemoryLogAction::Extract, None, None)
+    });
+
+    let (mut candidates, rejected) = redact_candidates(parsed, &limits);
+    log.skip(rejected, MemoryLogReason::Credential);
+    if candidates.len() > limits.max_new_per_turn {
+        let over = candidates.split_off(limits.max_new_per_turn);
+        log.skip(over, MemoryLogReason::Limit);
+    }
+
+    if !candidates.is_empty()
+        && let Some(threshold) = limits.similarity_threshold
+    {
+        let kept = drop_similar_candidates(
+            &turn_context.client,
+            store.as_ref(),
+            candidates.clone(),
+            threshold,
+            limits.max_file_bytes,
+        )
+        .await;
+        log.skip(
+            dropped_candidates(&candidates, &kept),
+            MemoryLogReason::Similar,
+        );
+        candidates = kept;
+    }
+    if !candidates.is_empty()
+        && limits.auto_accept_secs > 0
+        && can_review_memories(&turn_context.client.get_session_source())
+        && let Some(accepted) = sess
+            .request_memory_review(
+                turn_context,
+                candidates.clone(),
+                store.location().to_path_buf(),
+                limits.auto_accept_secs,
+            )
+            .await
+    {
+        let kept = keep_accepted(candidates.clone(), &accepted);
+        log.skip(
+            dropped_candidates(&candidates, &kept),
+            MemoryLogReason::Declined,
+        );
+        candidates = kept;
+    }
+
+    if !candidates.is_empty() {
+        let branch = if limits.scope_by_branch {
+            current_branch_name(&turn_context.cwd).await
+        } else {
+            None
+        };
+        let metadata = NewMemoryMetadata::now(Some(log.session_id.clone()), scope, branch);
+        save_candidates(store.as_ref(), &metadata, candidates, &mut log).await;
+    }
+    record_memory_decisions(store.location(), &log.records).await;
+}
+
+/// Save `candidates` to `store` and log each as added or as a duplicate,
+/// by the text saved: without its `[category]` prefix.
+async fn save_candidates(
+    store: &dyn MemoryStore,
+    metadata: &NewMemoryMetadata,
+    candidates: Vec<String>,
+    log: &mut ExtractionLog,
+) {
+    let saved = saved_texts(store).await;
+    if let Err(err) = store.append(metadata.entries(&candidates)).await {
+        warn!(
+            "Failed to write memories to {}: {err:#}",
+            store.location().display()
+        );
         return;
     }
-    if candidates.len() > MAX_NEW_MEMORIES_PER_TURN {
-        candidates.truncate(MAX_NEW_MEMORIES_PER_TURN);
+    for candidate in &candidates {
+        let text = split_category(candidate).1.to_string();
+        if saved.contains(&text.to_ascii_lowercase()) {
+            log.skip(vec![text], MemoryLogReason::Duplicate);
+        } else {
+            log.push(MemoryLogAction::Add, Some(text), None);
+        }
+    }
+}
+
+/// The decisions about the memories extracted from one turn.
+struct ExtractionLog {
+    session_id: String,
+    turn_id: String,
+    records: Vec<MemoryLogRecord>,
+}
+
+impl ExtractionLog {
+    fn push(
+        &mut self,
+        action: MemoryLogAction,
+        text: Option<String>,
+        reason: Option<MemoryLogReason>,
+    ) {
+        self.records.push(MemoryLogRecord {
+            session_id: Some(self.session_id.clone()),
+            turn_id: Some(self.turn_id.clone()),
+            ..MemoryLogRecord::new(action, text, reason)
+        });
+    }
+
+    fn skip(&mut self, texts: Vec<String>, reason: MemoryLogReason) {
+        for text in texts {
+            self.push(MemoryLogAction::Skip, Some(text), Some(reason));
+        }
+    }
+}
+
+/// The texts in `store`, lowercased, or none when it cannot be read.
+async fn saved_texts(store: &dyn MemoryStore) -> HashSet<String> {
+    match store.entries(usize::MAX).await {
+        Ok(entries) => entries
+            .into_iter()
+            .map(|entry| entry.text.to_ascii_lowercase())
+            .collect(),
+        Err(_) => HashSet::new(),
+    }
+}
+
+/// The scanner `memories.redaction` calls for, or `None` when it is off.
+fn memory_scanner(limits: &MemoriesToml) -> Option<SecretScanner> {
+    if limits.redaction == MemoryRedaction::Off {
+        return None;
+    }
+    let mut scanner = SecretScanner::new();
+    if !limits.redaction_patterns.is_empty() {
+        scanner = scanner.with_detector(PatternDetector::new(
+            limits
+                .redaction_patterns
+                .iter()
+                .map(|pattern| (pattern.as_str(), REDACTED)),
+        ));
     }
+    Some(scanner)
+}
 
-    let path = memory_write_path(turn_context.client.config().as_ref(), &turn_context.cwd);
-    match append_memories(&path, &candidates).await {
-        Ok(_) => {}
+/// The candidates with any credentials in them replaced by `[REDACTED]`, or
+/// without the candidates holding credentials, per `memories.redaction`.
+/// Also returns the dropped candidates, redacted.
+fn redact_candidates(candidates: Vec<String>, limits: &MemoriesToml) -> (Vec<String>, Vec<String>) {
+    let Some(scanner) = memory_scanner(limits) else {
+        return (candidates, Vec::new());
+    };
+    let mut kept = Vec::new();
+    let mut rejected = Vec::new();
+    for candidate in candidates {
+        match scanner.redact(&candidate) {
+            None => kept.push(candidate),
+            Some(redacted) if limits.redaction == MemoryRedaction::Scrub => {
+                warn!("Redacted a credential from an extracted memory");
+                kept.push(redacted);
+            }
+            Some(redacted) => {
+                warn!("Dropped an extracted memory that holds a credential");
+                rejected.push(redacted);
+            }
+        }
+    }
+    (kept, rejected)
+}
+
+/// The candidates that are not in `kept`, in their original order.
+fn dropped_candidates(candidates: &[String], kept: &[String]) -> Vec<String> {
+    candidates
+        .iter()
+        .filter(|candidate| !kept.contains(candidate))
+        .cloned()
+        .collect()
+}
+
+/// The candidates at the `accepted` indexes, in their original order.
+fn keep_accepted(candidates: Vec<String>, accepted: &[usize]) -> Vec<String> {
+    candidates
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| accepted.contains(index))
+        .map(|(_, candidate)| candidate)
+        .collect()
+}
+
+/// The candidates that are not near-duplicates of a memory in `store` or of
+/// an earlier candidate, by embedding similarity. Only the newest
+/// [`MAX_SIMILARITY_ENTRIES`] memories are compared, and their embeddings are
+/// kept in [`MEMORY_EMBEDDINGS_FILENAME`] next to the store so each memory is
+/// embedded once. Keeps every candidate when the provider has no embedding
+/// model or the embeddings cannot be computed.
+async fn drop_similar_candidates(
+    client: &ModelClient,
+    store: &dyn MemoryStore,
+    candidates: Vec<String>,
+    threshold: f32,
+    max_bytes: usize,
+) -> Vec<String> {
+    let provider = client.get_provider();
+    let Some(model) = provider.embedding_model() else {
+        warn!(
+            "Skipping the memory similarity check: provider `{}` has no `embedding_model`",
+            provider.name
+        );
+        return candidates;
+    };
+    let saved: Vec<String> = match store.entries(max_bytes).await {
+        Ok(entries) => {
+            let skip = entries.len().saturating_sub(MAX_SIMILARITY_ENTRIES);
+            entries
+                .into_iter()
+                .skip(skip)
+                .map(|entry| entry.text)
+                .collect()
+        }
+        Err(err) => {
+            warn!(
+                "Failed to read memories from {}: {err:#}",
+                store.location().display()
+            );
+            Vec::new()
+        }
+    };
+    let cache_path = store.location().with_file_name(MEMORY_EMBEDDINGS_FILENAME);
+    let mut cache = load_memory_embeddings(&cache_path, model).await;
+    cache.embeddings.retain(|text, _| saved.contains(text));
+
+    let uncached: Vec<String> = saved
+        .iter()
+        .filter(|text| !cache.embeddings.contains_key(*text))
+        .cloned()
+        .collect();
+    let texts: Vec<String> = candidates
+        .iter()
+        .map(|candidate| split_category(candidate).1.to_string())
+        .collect();
+    let input: Vec<String> = uncached.iter().chain(&texts).cloned().collect();
+    let embeddings = match embed_in_batches(client, model, &input).await {
+        Ok(embeddings) => embeddings,
         Err(err) => {
-            warn!("Failed to write memories to {}: {err:#}", path.display());
+            warn!("Failed to embed memories for deduplication: {err:#}");
+            return candidates;
+        }
+    };
+    let (uncached_embeddings, new) = embeddings.split_at(uncached.len());
+    cache.embeddings.extend(
+        uncached
+            .into_iter()
+            .zip(uncached_embeddings.iter().cloned()),
+    );
+    let saved_embeddings: Vec<Vec<f32>> = saved
+        .iter()
+        .filter_map(|text| cache.embeddings.get(text).cloned())
+        .collect();
+    let kept = dissimilar_indexes(&saved_embeddings, new, threshold);
+    // The kept candidates are about to be saved, so their embeddings are too.
+    for &index in &kept {
+        cache
+            .embeddings
+            .insert(texts[index].clone(), new[index].clone());
+    }
+    if let Err(err) = save_memory_embeddings(&cache_path, &cache).await {
+        warn!(
+            "Failed to write memory embeddings to {}: {err:#}",
+            cache_path.display()
+        );
+    }
+    keep_accepted(candidates, &kept)
+}
+
+/// Embeddings of `input`, requested [`EMBED_BATCH_SIZE`] texts at a time.
+async fn embed_in_batches(
+    client: &ModelClient,
+    model: &str,
+    input: &[String],
+) -> anyhow::Result<Vec<Vec<f32>>> {
+    let mut embeddings = Vec::with_capacity(input.len());
+    for batch in input.chunks(EMBED_BATCH_SIZE) {
+        let batch_embeddings = client.embed(model, batch).await?;
+        if batch_embeddings.len() != batch.len() {
+            anyhow::bail!(
+                "expected {} memory embeddings, got {}",
+                batch.len(),
+                batch_embeddings.len()
+            );
         }
+        embeddings.extend(batch_embeddings);
     }
+    Ok(embeddings)
+}
+
+/// Embeddings of saved memories, keyed by their text, for one model.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct MemoryEmbeddings {
+    model: String,
+    embeddings: HashMap<String, Vec<f32>>,
+}
+
+/// The cached embeddings at `path`, or none when they were computed with a
+/// different model or cannot be read.
+async fn load_memory_embeddings(path: &Path, model: &str) -> MemoryEmbeddings {
+    let cached = match fs::read(path).await {
+        Ok(contents) => serde_json::from_slice::<MemoryEmbeddings>(&contents).ok(),
+        Err(_) => None,
+    };
+    match cached {
+        Some(cached) if cached.model == model => cached,
+        _ => MemoryEmbeddings {
+            model: model.to_string(),
+            embeddings: HashMap::new(),
+        },
+    }
+}
+
+async fn save_memory_embeddings(path: &Path, cache: &MemoryEmbeddings) -> anyhow::Result<()> {
+    write_atomic_async(path, serde_json::to_vec(cache)?, None).await?;
+    Ok(())
+}
+
+/// Indexes of the `candidates` whose similarity to every `saved` embedding
+/// and to every earlier kept candidate is below `threshold`.
+fn dissimilar_indexes(saved: &[Vec<f32>], candidates: &[Vec<f32>], threshold: f32) -> Vec<usize> {
+    let mut kept: Vec<usize> = Vec::new();
+    for (index, candidate) in candidates.iter().enumerate() {
+        let similar = saved
+            .iter()
+            .chain(kept.iter().map(|&kept_index| &candidates[kept_index]))
+            .any(|other| cosine_similarity(candidate, other) >= threshold);
+        if !similar {
+            kept.push(index);
+        }
+    }
+    kept
 }
 
 fn should_record_memories(turn_context: &TurnContext) -> bool {
-    !matches!(
-        turn_context.client.get_session_source(),
-        SessionSource::Exec | SessionSource::SubAgent(_)
-    )
-}
-
-fn memory_paths(config: &Config) -> Vec<PathBuf> {
-    let mut paths = Vec::new();
-    let global = config.codex_home.join(MEMORIES_FILENAME);
-    paths.push(global.clone());
-    if let Some(repo_path) = repo_memories_path(&config.cwd)
-        && repo_path != global
-    {
-        paths.push(repo_path);
+    let config = turn_context.client.config();
+    let limits = &config.memories;
+    limits.enabled
+        && limits.max_new_per_turn > 0
+        && !matches!(
+            turn_context.client.get_session_source(),
+            SessionSource::Exec | SessionSource::SubAgent(_)
+        )
+}
+
+/// The TUI answers `MemoryCandidatesProposed`, and the app server forwards
+/// it to its clients. Other clients would leave the review pending until
+/// `auto_accept_secs` runs out, so their memories are saved right away.
+fn can_review_memories(source: &SessionSource) -> bool {
+    matches!(source, SessionSource::Cli | SessionSource::VSCode)
+}
+
+fn memory_stores(config: &Config) -> Vec<Box<dyn MemoryStore>> {
+    let mut dirs = vec![config.codex_home.clone()];
+    for root in config.workspace_roots() {
+        if let Some(repo_dir) = repo_memories_dir(root)
+            && !dirs.contains(&repo_dir)
+        {
+            dirs.push(repo_dir);
+        }
+    }
+    dirs.iter()
+        .map(|dir| memory_store(config.memories.backend, dir))
+        .collect()
+}
+
+/// The store new memories for `cwd` go to, and its scope.
+fn memory_write_target(config: &Config, cwd: &Path) -> (Box<dyn MemoryStore>, MemoryScope) {
+    match repo_memories_dir(cwd) {
+        Some(dir) => (
+            memory_store(config.memories.backend, &dir),
+            MemoryScope::Repo,
+        ),
+        None => (
+            memory_store(config.memories.backend, &config.codex_home),
+            MemoryScope::Global,
+        ),
     }
-    paths
 }
 
-fn memory_write_path(config: &Config, cwd: &Path) -> PathBuf {
-    repo_memories_path(cwd).unwrap_or_else(|| config.codex_home.join(MEMORIES_FILENAME))
+/// The store of `backend` in `dir`.
+fn memory_store(backend: MemoryBackend, dir: &Path) -> Box<dyn MemoryStore> {
+    match backend {
+        MemoryBackend::File => Box::new(FileMemoryStore {
+            path: dir.join(MEMORIES_FILENAME),
+        }),
+        MemoryBackend::Sqlite => Box::new(SqliteMemoryStore {
+            path: dir.join(MEMORIES_DB_FILENAME),
+        }),
+    }
 }
 
-fn repo_memories_path(cwd: &Path) -> Option<PathBuf> {
+fn repo_memories_dir(cwd: &Path) -> Option<PathBuf> {
     let base = if cwd.is_dir() { cwd } else { cwd.parent()? };
     let repo_root = get_git_repo_root(base)?;
-    Some(repo_root.join(MEMORIES_DIRNAME).join(MEMORIES_FILENAME))
+    Some(repo_root.join(MEMORIES_DIRNAME))
 }
 
-async fn read_memories_file(path: &Path) -> std::io::Result<Vec<String>> {
+/// The entries of the file at `path`, of which only the last `max_bytes`
+/// are read.
+async fn read_memories_file(path: &Path, max_bytes: usize) -> std::io::Result<Vec<MemoryEntry>> {
     let data = match fs::read(path).await {
         Ok(data) => data,
         Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
@@ -201,13 +1508,13 @@ async fn read_memories_file(path: &Path) -> std::io::Result<Vec<String>> {
         return Ok(Vec::new());
     }
 
-    let data = if data.len() > MEMORIES_MAX_BYTES {
+    let data = if data.len() > max_bytes {
         warn!(
             "Memories file {} exceeds max size ({} bytes); truncating.",
             path.display(),
-            MEMORIES_MAX_BYTES,
+            max_bytes,
         );
-        data[data.len() - MEMORIES_MAX_BYTES..].to_vec()
+        data[data.len() - max_bytes..].to_vec()
     } else {
         data
     };
@@ -216,28 +1523,121 @@ async fn read_memories_file(path: &Path) -> std::io::Result<Vec<String>> {
     Ok(parse_memories(&text))
 }
 
-fn parse_memories(text: &str) -> Vec<String> {
-    let mut bullets: Vec<String> = Vec::new();
-    let mut lines: Vec<String> = Vec::new();
+/// A `## Category` heading gives its category to the entries below it that
+/// do not name one; a `#` heading ends it.
+fn parse_memories(text: &str) -> Vec<MemoryEntry> {
+    let mut bullets: Vec<MemoryEntry> = Vec::new();
+    let mut lines: Vec<MemoryEntry> = Vec::new();
+    let mut category: Option<String> = None;
 
     for line in text.lines() {
         let trimmed = line.trim();
-        if trimmed.is_empty() || trimmed.starts_with('#') {
+        if let Some(heading) = trimmed.strip_prefix("## ") {
+            category = Some(heading.trim().to_lowercase()).filter(|name| !name.is_empty());
             continue;
         }
-        if let Some(entry) = trimmed
-            .strip_prefix("- ")
-            .or_else(|| trimmed.strip_prefix("* "))
-        {
-            bullets.push(entry.trim().to_string());
-        } else {
-            lines.push(trimmed.to_string());
+        if trimmed.starts_with("# ") {
+            category = None;
+            continue;
+        }
+        if let Some((mut entry, bullet)) = line_entry(line) {
+            // Entries Codex appended carry a metadata comment and land after
+            // whatever heading ends the file, so only hand-written entries
+            // take the category of the heading above them.
+            if entry.category.is_none() && entry.annotation().is_none() {
+                entry.category = category.clone();
+            }
+            if bullet {
+                bullets.push(entry);
+            } else {
+                lines.push(entry);
+            }
         }
     }
 
     if !bullets.is_empty() { bullets } else { lines }
 }
 
+/// The entry on a line of a memories file and whether it is a bullet.
+/// Blank lines and headings hold none.
+fn line_entry(line: &str) -> Option<(MemoryEntry, bool)> {
+    let trimmed = line.trim();
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return None;
+    }
+    let (entry, bullet) = match trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+    {
+        Some(entry) => (entry.trim(), true),
+        None => (trimmed, false),
+    };
+    let mut memory = MemoryEntry {
+        text: entry.to_string(),
+        created_at: None,
+        session_id: None,
+        scope: None,
+        branch: None,
+        category: None,
+    };
+    if let Some(body) = entry.strip_suffix(METADATA_CLOSE)
+        && let Some((text, fields)) = body.rsplit_once(METADATA_OPEN)
+        && !text.trim().is_empty()
+    {
+        memory.text = text.trim().to_string();
+        for (key, value) in fields.split_whitespace().filter_map(|f| f.split_once('=')) {
+            match key {
+                "created" => {
+                    memory.created_at = DateTime::parse_from_rfc3339(value)
+                        .ok()
+                        .map(|created| created.with_timezone(&Utc));
+                }
+                "session" => memory.session_id = Some(value.to_string()),
+                "scope" => memory.scope = MemoryScope::parse(value),
+                "branch" => memory.branch = Some(value.to_string()),
+                "category" => memory.category = Some(value.to_ascii_lowercase()),
+                _ => {}
+            }
+        }
+    }
+    Some((memory, bullet))
+}
+
+/// `text` without the line holding the entry `selector` names, and that
+/// entry. A number names a position only when `by_position` is set.
+fn without_memory(text: &str, selector: &str, by_position: bool) -> Option<(String, String)> {
+    let entries = parse_memories(text);
+    let selector = selector.trim();
+    let position = selector.parse::<usize>().ok().filter(|_| by_position);
+    let target = match position {
+        Some(position) if position >= 1 => entries.get(position - 1),
+        _ => entries
+            .iter()
+            .find(|entry| entry.text.eq_ignore_ascii_case(selector)),
+    }?
+    .text
+    .clone();
+    // Entries are bullets when the file has any, so only those can match.
+    let bullets = text
+        .lines()
+        .any(|line| matches!(line_entry(line), Some((_, true))));
+
+    let mut removed = false;
+    let mut remaining = String::with_capacity(text.len());
+    for line in text.lines() {
+        if !removed
+            && line_entry(line)
+                .is_some_and(|(entry, bullet)| entry.text == target && bullet == bullets)
+        {
+            removed = true;
+            continue;
+        }
+        remaining.push_str(line);
+        remaining.push('\n');
+    }
+    Some((remaining, target))
+}
+
 fn parse_memory_candidates(text: &str) -> Vec<String> {
     let trimmed = text.trim();
     if trimmed.is_empty() || trimmed.eq_ignore_ascii_case(NO_MEMORIES_RESPONSE) {
@@ -281,6 +1681,66 @@ fn build_memories_section(entries: &[String]) -> Option<String> {
     Some(lines.join("\n"))
 }
 
+/// What the extraction request reads: the user's messages alone, or, with
+/// the turn's output, each part under a heading so the model can tell what
+/// the user asked for from what the agent found out. Each part is cut to
+/// `max_bytes`, and tool results to [`EXTRACTION_TOOL_OUTPUT_BYTES`] each.
+fn extraction_input(
+    user_texts: &[String],
+    agent_message: Option<&str>,
+    tool_outputs: &[String],
+    max_bytes: usize,
+) -> String {
+    let cut = |text: &str, max_bytes: usize| {
+        if text.len() > max_bytes {
+            truncate_text(text, TruncationPolicy::Bytes(max_bytes))
+        } else {
+            text.to_string()
+        }
+    };
+    let user = cut(&user_texts.join("\n\n"), max_bytes);
+    let agent_message = agent_message
+        .map(str::trim)
+        .filter(|message| !message.is_empty());
+    if agent_message.is_none() && tool_outputs.is_empty() {
+        return user;
+    }
+
+    let mut sections = vec![format!("User messages:\n{user}")];
+    if let Some(message) = agent_message {
+        sections.push(format!(
+            "Assistant's final message:\n{}",
+            cut(message, max_bytes)
+        ));
+    }
+    if !tool_outputs.is_empty() {
+        let results = tool_outputs
+            .iter()
+            .map(|output| format!("- {}", cut(output.trim(), EXTRACTION_TOOL_OUTPUT_BYTES)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        sections.push(format!("Tool results:\n{}", cut(&results, max_bytes)));
+    }
+    sections.join("\n\n")
+}
+
+/// Outputs of the tool calls made since the last user message.
+fn turn_tool_outputs(history: &[ResponseItem]) -> Vec<String> {
+    let turn_start = history
+        .iter()
+        .rposition(|item| matches!(item, ResponseItem::Message { role, .. } if role == "user"))
+        .map_or(0, |index| index + 1);
+    history[turn_start..]
+        .iter()
+        .filter_map(|item| match item {
+            ResponseItem::FunctionCallOutput { output, .. } => Some(output.content.clone()),
+            ResponseItem::CustomToolCallOutput { output, .. } => Some(output.clone()),
+            _ => None,
+        })
+        .filter(|output| !output.trim().is_empty())
+        .collect()
+}
+
 fn collect_user_input_texts(inputs: &[UserInput]) -> Vec<String> {
     let mut texts = Vec::new();
     for input in inputs {
@@ -293,27 +1753,29 @@ fn collect_user_input_texts(inputs: &[UserInput]) -> Vec<String> {
     texts
 }
 
-async fn append_memories(path: &Path, entries: &[String]) -> std::io::Result<usize> {
+async fn append_memories(path: &Path, entries: Vec<MemoryEntry>) -> std::io::Result<usize> {
     if entries.is_empty() {
         return Ok(0);
     }
 
-    let existing = read_memories_file(path).await?;
+    // Held from the duplicate check to the write, so sessions saving at the
+    // same time neither save the same entry twice nor interleave lines.
+    let _lock = lock_file_async(path).await?;
+    // Every entry counts as a duplicate, not only those within the read limit.
+    let existing = read_memories_file(path, usize::MAX).await?;
     let mut seen: HashSet<String> = existing
         .iter()
-        .map(String::as_str)
-        .map(str::to_ascii_lowercase)
+        .map(|entry| entry.text.to_ascii_lowercase())
         .collect();
 
     let mut additions = Vec::new();
-    for entry in entries {
-        let trimmed = entry.trim();
-        if trimmed.is_empty() {
+    for mut entry in entries {
+        entry.text = entry.text.trim().to_string();
+        if entry.text.is_empty() {
             continue;
         }
-        let key = trimmed.to_ascii_lowercase();
-        if seen.insert(key) {
-            additions.push(trimmed.to_string());
+        if seen.insert(entry.text.to_ascii_lowercase()) {
+            additions.push(entry);
         }
     }
 
@@ -343,29 +1805,615 @@ async fn append_memories(path: &Path, entries: &[String]) -> std::io::Result<usi
     }
 
     for entry in &additions {
-        file.write_all(format!("- {entry}\n").as_bytes()).await?;
+        file.write_all(entry_line(entry).as_bytes()).await?;
     }
 
     Ok(additions.len())
 }
 
+/// The bullet of `entry` in `memories.md`.
+fn entry_line(entry: &MemoryEntry) -> String {
+    match entry.annotation() {
+        Some(annotation) => format!("- {} {annotation}\n", entry.text),
+        None => format!("- {}\n", entry.text),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_protocol::models::FunctionCallOutputPayload;
     use pretty_assertions::assert_eq;
 
+    fn texts(entries: Vec<MemoryEntry>) -> Vec<String> {
+        entries.into_iter().map(|entry| entry.text).collect()
+    }
+
     #[test]
     fn parse_memories_prefers_bullets() {
         let text = "# Memories\n- Prefer short diffs\n* Run tests\nextra line";
         let parsed = parse_memories(text);
-        assert_eq!(parsed, vec!["Prefer short diffs", "Run tests"]);
+        assert_eq!(texts(parsed), vec!["Prefer short diffs", "Run tests"]);
     }
 
     #[test]
     fn parse_memories_falls_back_to_lines() {
         let text = "# Memories\nPrefer short diffs\nRun tests";
         let parsed = parse_memories(text);
-        assert_eq!(parsed, vec!["Prefer short diffs", "Run tests"]);
+        assert_eq!(texts(parsed), vec!["Prefer short diffs", "Run tests"]);
+    }
+
+    #[test]
+    fn parse_memories_reads_metadata_comments() {
+        let text = "# Memories\n- Plain entry\n- Run tests <!-- created=2026-10-16T09:30:00Z session=abc-123 scope=repo branch=feature/login -->\n- <!-- not metadata -->\n";
+        let parsed = parse_memories(text);
+        assert_eq!(
+            parsed,
+            vec![
+                MemoryEntry {
+                    text: "Plain entry".to_string(),
+                    created_at: None,
+                    session_id: None,
+                    scope: None,
+                    branch: None,
+                    category: None,
+                },
+                MemoryEntry {
+                    text: "Run tests".to_string(),
+                    created_at: Some(
+                        DateTime::parse_from_rfc3339("2026-10-16T09:30:00Z")
+                            .expect("timestamp")
+                            .with_timezone(&Utc)
+                    ),
+                    session_id: Some("abc-123".to_string()),
+                    scope: Some(MemoryScope::Repo),
+                    branch: Some("feature/login".to_string()),
+                    category: None,
+                },
+                MemoryEntry {
+                    text: "<!-- not metadata -->".to_string(),
+                    created_at: None,
+                    session_id: None,
+                    scope: None,
+                    branch: None,
+                    category: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn categories_come_from_headings_metadata_and_tags() {
+        let text = "# Memories\n- Prefer short diffs\n## Build\n- Run cargo test\n- Use terraform 1.6 <!-- category=infra -->\n- Keep PRs small <!-- scope=repo -->\n# Other\n- Be brief\n";
+        let categories: Vec<Option<String>> = parse_memories(text)
+            .into_iter()
+            .map(|entry| entry.category)
+            .collect();
+        assert_eq!(
+            categories,
+            vec![
+                None,
+                Some("build".to_string()),
+                Some("infra".to_string()),
+                None,
+                None,
+            ]
+        );
+
+        assert_eq!(
+            split_category("[Style] Prefer tabs"),
+            (Some("style".to_string()), "Prefer tabs")
+        );
+        assert_eq!(split_category("[WIP]"), (None, "[WIP]"));
+        assert_eq!(
+            split_category("[see docs] for details"),
+            (None, "[see docs] for details")
+        );
+
+        let entry = parse_memories("- Run cargo test <!-- category=build -->")
+            .pop()
+            .expect("entry");
+        assert!(entry.in_categories(&[]));
+        assert!(entry.in_categories(&["BUILD".to_string()]));
+        assert!(!entry.in_categories(&["infra".to_string()]));
+        assert!(parse_memories("- Be brief")[0].in_categories(&["infra".to_string()]));
+    }
+
+    #[test]
+    fn branch_tagged_memories_apply_only_to_their_branch() {
+        let mut entry = parse_memories("- Use the v2 API <!-- branch=feature/v2 -->")
+            .pop()
+            .expect("entry");
+        assert!(entry.applies_to_branch(Some("feature/v2")));
+        assert!(!entry.applies_to_branch(Some("main")));
+        assert!(!entry.applies_to_branch(None));
+
+        entry.branch = None;
+        assert!(entry.applies_to_branch(Some("main")));
+        assert!(entry.applies_to_branch(None));
+    }
+
+    #[test]
+    fn keep_accepted_keeps_order_and_ignores_unknown_indexes() {
+        let candidates = vec![
+            "Prefer short diffs".to_string(),
+            "Run tests".to_string(),
+            "Use pnpm".to_string(),
+        ];
+
+        assert_eq!(
+            keep_accepted(candidates.clone(), &[2, 0, 7]),
+            vec!["Prefer short diffs".to_string(), "Use pnpm".to_string()]
+        );
+        assert_eq!(keep_accepted(candidates, &[]), Vec::<String>::new());
+    }
+
+    #[test]
+    fn redact_candidates_scrubs_or_drops_credentials() {
+        let candidates = vec![
+            "Deploy with OPENAI_API_KEY=sk-proj-abcdefghijklmnopqrstuv".to_string(),
+            "Use pnpm".to_string(),
+            "Staging tokens start with acme_".to_string(),
+            "The staging token is acme_qwertyui".to_string(),
+        ];
+        let mut limits = MemoriesToml {
+            redaction_patterns: vec![r"\bacme_[a-z]{8}\b".to_string()],
+            ..MemoriesToml::default()
+        };
+
+        assert_eq!(
+            redact_candidates(candidates.clone(), &limits),
+            (
+                vec![
+                    "Deploy with OPENAI_API_KEY=[REDACTED]".to_string(),
+                    "Use pnpm".to_string(),
+                    "Staging tokens start with acme_".to_string(),
+                    "The staging token is [REDACTED]".to_string(),
+                ],
+                Vec::new()
+            )
+        );
+        limits.redaction = MemoryRedaction::Reject;
+        assert_eq!(
+            redact_candidates(candidates.clone(), &limits),
+            (
+                vec![
+                    "Use pnpm".to_string(),
+                    "Staging tokens start with acme_".to_string(),
+                ],
+                vec![
+                    "Deploy with OPENAI_API_KEY=[REDACTED]".to_string(),
+                    "The staging token is [REDACTED]".to_string(),
+                ]
+            )
+        );
+        limits.redaction = MemoryRedaction::Off;
+        assert_eq!(
+            redact_candidates(candidates.clone(), &limits),
+            (candidates, Vec::new())
+        );
+    }
+
+    #[test]
+    fn dissimilar_indexes_skips_near_duplicates_of_saved_and_earlier_candidates() {
+        let saved = vec![vec![1.0, 0.0, 0.0]];
+        let candidates = vec![
+            vec![0.99, 0.1, 0.0],
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.98, 0.2],
+            vec![0.0, 0.0, 1.0],
+        ];
+
+        assert_eq!(dissimilar_indexes(&saved, &candidates, 0.9), vec![1, 3]);
+        assert_eq!(
+            dissimilar_indexes(&saved, &candidates, 1.01),
+            vec![0, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn without_memory_removes_by_position_or_text() {
+        let text = "# Memories\n\n- Prefer short diffs\n- Run tests\nnote to self\n";
+
+        assert_eq!(
+            without_memory(text, "2", true),
+            Some((
+                "# Memories\n\n- Prefer short diffs\nnote to self\n".to_string(),
+                "Run tests".to_string()
+            ))
+        );
+        assert_eq!(
+            without_memory(text, "prefer SHORT diffs", true),
+            Some((
+                "# Memories\n\n- Run tests\nnote to self\n".to_string(),
+                "Prefer short diffs".to_string()
+            ))
+        );
+        assert_eq!(without_memory(text, "note to self", true), None);
+        assert_eq!(without_memory(text, "3", true), None);
+        assert_eq!(without_memory(text, "2", false), None);
+    }
+
+    #[tokio::test]
+    async fn add_memory_skips_duplicates() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let store = memory_store(MemoryBackend::File, &dir.path().join(MEMORIES_DIRNAME));
+        let path = store.location().to_path_buf();
+
+        assert!(
+            add_memory(
+                store.as_ref(),
+                "Run  tests\nbefore pushing",
+                MemoryScope::Repo,
+                None
+            )
+            .await?
+        );
+        assert!(
+            !add_memory(
+                store.as_ref(),
+                "run tests before pushing",
+                MemoryScope::Repo,
+                None
+            )
+            .await?
+        );
+        assert!(
+            add_memory(store.as_ref(), "   ", MemoryScope::Repo, None)
+                .await
+                .is_err()
+        );
+
+        let entries = store.entries(usize::MAX).await?;
+        assert_eq!(texts(entries.clone()), vec!["Run tests before pushing"]);
+        assert_eq!(entries[0].scope, Some(MemoryScope::Repo));
+        assert_eq!(entries[0].session_id, None);
+        assert!(entries[0].created_at.is_some());
+        assert_eq!(
+            without_memory(&fs::read_to_string(&path).await?, "1"),
+            Some((
+                "# Memories\n".to_string(),
+                "Run tests before pushing".to_string()
+            ))
+        );
+        assert!(store.clear().await?);
+        assert!(!store.clear().await?);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_sessions_neither_lose_nor_repeat_memories() -> std::io::Result<()> {
+        for backend in [MemoryBackend::File, MemoryBackend::Sqlite] {
+            let dir = tempfile::tempdir()?;
+            let mut sessions = Vec::new();
+            for session in 0..6 {
+                // A store per session, as separate processes would have.
+                let store = memory_store(backend, dir.path());
+                sessions.push(tokio::spawn(async move {
+                    for round in 0..10 {
+                        for text in [
+                            format!("Session {session} note {round}"),
+                            format!("Shared note {round}"),
+                        ] {
+                            add_memory(store.as_ref(), &text, MemoryScope::Repo, None).await?;
+                        }
+                    }
+                    std::io::Result::Ok(())
+                }));
+            }
+            for session in sessions {
+                session.await.map_err(std::io::Error::other)??;
+            }
+
+            let store = memory_store(backend, dir.path());
+            let mut saved = texts(store.entries(usize::MAX).await?);
+            saved.sort();
+            let mut expected: Vec<String> = (0..10)
+                .flat_map(|round| {
+                    (0..6)
+                        .map(move |session| format!("Session {session} note {round}"))
+                        .chain(std::iter::once(format!("Shared note {round}")))
+                })
+                .collect();
+            expected.sort();
+            assert_eq!(saved, expected);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn import_memories_merges_and_reports_conflicts() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let store = memory_store(MemoryBackend::File, &dir.path().join(MEMORIES_DIRNAME));
+        for text in ["Use tabs for indentation", "Package manager: pnpm"] {
+            add_memory(store.as_ref(), text, MemoryScope::Repo, None).await?;
+        }
+        let exported = vec![
+            MemoryEntry {
+                text: "Run tests before pushing".to_string(),
+                created_at: None,
+                session_id: Some("s1".to_string()),
+                scope: Some(MemoryScope::Global),
+                branch: None,
+                category: Some("build".to_string()),
+            },
+            exported_entry("use tabs for indentation"),
+            exported_entry("Never use tabs for indentation"),
+            exported_entry("Package manager: yarn"),
+        ];
+        let json = serde_json::to_string(&exported).map_err(std::io::Error::other)?;
+        assert_eq!(parse_memories_export(&json)?, exported);
+        assert_eq!(
+            parse_memories_export(&memories_markdown(&exported))?,
+            exported
+        );
+
+        let report =
+            import_memories(store.as_ref(), exported.clone(), MemoryScope::Repo, false).await?;
+        assert_eq!(
+            report,
+            MemoryImport {
+                imported: 1,
+                duplicates: 1,
+                conflicts: vec![
+                    MemoryConflict {
+                        imported: "Never use tabs for indentation".to_string(),
+                        existing: "Use tabs for indentation".to_string(),
+                    },
+                    MemoryConflict {
+                        imported: "Package manager: yarn".to_string(),
+                        existing: "Package manager: pnpm".to_string(),
+                    },
+                ],
+            }
+        );
+        let entries = store.entries(usize::MAX).await?;
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[2].category.as_deref(), Some("build"));
+        assert_eq!(entries[2].session_id.as_deref(), Some("s1"));
+        assert_eq!(entries[2].scope, Some(MemoryScope::Repo));
+
+        let report = import_memories(store.as_ref(), exported, MemoryScope::Repo, true).await?;
+        assert_eq!((report.imported, report.duplicates), (2, 2));
+        assert!(!memories_contradict("Use tabs", "Use spaces"));
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn remove_memory_keeps_the_file_permissions() -> std::io::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir()?;
+        let store = memory_store(MemoryBackend::File, dir.path());
+        add_memory(store.as_ref(), "Use tabs", MemoryScope::Repo, None).await?;
+        add_memory(store.as_ref(), "Run tests", MemoryScope::Repo, None).await?;
+        fs::set_permissions(store.location(), std::fs::Permissions::from_mode(0o644)).await?;
+
+        assert_eq!(
+            remove_memory(store.as_ref(), "1").await?,
+            Some("Use tabs".to_string())
+        );
+        let mode = fs::metadata(store.location()).await?.permissions().mode();
+        assert_eq!(mode & 0o777, 0o644);
+        assert_eq!(texts(store.entries(usize::MAX).await?), vec!["Run tests"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn undo_session_memories_removes_what_the_session_added() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let store = memory_store(MemoryBackend::File, &dir.path().join(MEMORIES_DIRNAME));
+        add_memory(store.as_ref(), "Use tabs", MemoryScope::Repo, None).await?;
+        let metadata = NewMemoryMetadata::now(Some("s1".to_string()), MemoryScope::Repo, None);
+        let texts = vec!["Run tests".to_string(), "Use pnpm".to_string()];
+        store.append(metadata.entries(&texts)).await?;
+        record_memory_decisions(
+            store.location(),
+            &[
+                MemoryLogRecord {
+                    session_id: Some("s1".to_string()),
+                    ..MemoryLogRecord::new(MemoryLogAction::Add, Some(texts[0].clone()), None)
+                },
+                MemoryLogRecord {
+                    session_id: Some("s1".to_string()),
+                    ..MemoryLogRecord::new(MemoryLogAction::Add, Some(texts[1].clone()), None)
+                },
+            ],
+        )
+        .await;
+        remove_memory(store.as_ref(), "Use pnpm").await?;
+
+        assert_eq!(
+            undo_session_memories(store.as_ref(), "s1").await?,
+            vec!["Run tests".to_string()]
+        );
+        let saved: Vec<String> = store
+            .entries(usize::MAX)
+            .await?
+            .into_iter()
+            .map(|entry| entry.text)
+            .collect();
+        assert_eq!(saved, vec!["Use tabs".to_string()]);
+        let log = read_memory_log(&memory_log_path(store.location())).await?;
+        let actions: Vec<(MemoryLogAction, Option<MemoryLogReason>)> = log
+            .iter()
+            .map(|record| (record.action, record.reason))
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                (MemoryLogAction::Add, Some(MemoryLogReason::Manual)),
+                (MemoryLogAction::Add, None),
+                (MemoryLogAction::Add, None),
+                (MemoryLogAction::Prune, Some(MemoryLogReason::Removed)),
+                (MemoryLogAction::Prune, Some(MemoryLogReason::Undone)),
+            ]
+        );
+        assert_eq!(
+            undo_session_memories(store.as_ref(), "s1").await?,
+            Vec::<String>::new()
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn undo_removes_categorized_and_numeric_extracted_memories() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let store = memory_store(MemoryBackend::File, &dir.path().join(MEMORIES_DIRNAME));
+        add_memory(store.as_ref(), "Use pnpm", MemoryScope::Repo, None).await?;
+        add_memory(store.as_ref(), "Keep diffs small", MemoryScope::Repo, None).await?;
+        let mut log = ExtractionLog {
+            session_id: "s1".to_string(),
+            turn_id: "t1".to_string(),
+            records: Vec::new(),
+        };
+        let metadata = NewMemoryMetadata::now(Some("s1".to_string()), MemoryScope::Repo, None);
+        save_candidates(
+            store.as_ref(),
+            &metadata,
+            vec!["[Style] Prefer tabs".to_string(), "2".to_string()],
+            &mut log,
+        )
+        .await;
+        record_memory_decisions(store.location(), &log.records).await;
+
+        assert_eq!(
+            undo_session_memories(store.as_ref(), "s1").await?,
+            vec!["Prefer tabs".to_string(), "2".to_string()]
+        );
+        let saved: Vec<String> = store
+            .entries(usize::MAX)
+            .await?
+            .into_iter()
+            .map(|entry| entry.text)
+            .collect();
+        assert_eq!(
+            saved,
+            vec!["Use pnpm".to_string(), "Keep diffs small".to_string()]
+        );
+        Ok(())
+    }
+
+    fn exported_entry(text: &str) -> MemoryEntry {
+        MemoryEntry {
+            text: text.to_string(),
+            created_at: None,
+            session_id: None,
+            scope: None,
+            branch: None,
+            category: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn sqlite_store_imports_markdown_and_keeps_metadata() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        fs::write(
+            dir.path().join(MEMORIES_FILENAME),
+            "# Memories\n- Prefer short diffs\n",
+        )
+        .await?;
+        let store = memory_store(MemoryBackend::Sqlite, dir.path());
+        let metadata = NewMemoryMetadata::now(
+            Some("abc-123".to_string()),
+            MemoryScope::Global,
+            Some("main".to_string()),
+        );
+
+        assert_eq!(
+            store
+                .append(metadata.entries(&[
+                    "Run tests".to_string(),
+                    "PREFER SHORT DIFFS".to_string(),
+                    "run TESTS".to_string(),
+                ]))
+                .await?,
+            1
+        );
+        let entries = store.entries(0).await?;
+        assert_eq!(
+            texts(entries.clone()),
+            vec!["Prefer short diffs", "Run tests"]
+        );
+        assert_eq!(entries[0].session_id, None);
+        assert_eq!(entries[1].session_id, Some("abc-123".to_string()));
+        assert_eq!(entries[1].scope, Some(MemoryScope::Global));
+        assert_eq!(entries[0].branch, None);
+        assert_eq!(entries[1].branch, Some("main".to_string()));
+        assert_eq!(
+            entries[1].created_at.map(|created| created.timestamp()),
+            Some(metadata.created_at.timestamp())
+        );
+
+        assert_eq!(
+            store.remove("run tests").await?,
+            Some("Run tests".to_string())
+        );
+        assert_eq!(store.remove("2").await?, None);
+        assert_eq!(
+            store.remove("1").await?,
+            Some("Prefer short diffs".to_string())
+        );
+        assert!(!store.clear().await?);
+        Ok(())
+    }
+
+    #[test]
+    fn rank_memories_prefers_shared_words_within_limits() {
+        let entries = [
+            "Prefer short diffs".to_string(),
+            "Run cargo tests with --all-features".to_string(),
+            "Use the staging database for migrations".to_string(),
+            "Cargo builds need the nightly toolchain".to_string(),
+        ];
+        let entries: Vec<&String> = entries.iter().collect();
+        let query = keywords("Why do the cargo tests fail?");
+        let limits = MemoriesToml {
+            token_budget: 1_000,
+            max_entries: 20,
+            ..MemoriesToml::default()
+        };
+
+        assert_eq!(
+            rank_memories(&entries, &query, &limits, false),
+            vec![
+                "Run cargo tests with --all-features".to_string(),
+                "Cargo builds need the nightly toolchain".to_string(),
+            ]
+        );
+        assert_eq!(
+            rank_memories(
+                &entries,
+                &query,
+                &MemoriesToml {
+                    max_entries: 1,
+                    ..limits.clone()
+                },
+                false
+            ),
+            vec!["Run cargo tests with --all-features".to_string()]
+        );
+        assert_eq!(
+            rank_memories(&entries, &keywords("the and with"), &limits, false),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            rank_memories(
+                &entries,
+                &query,
+                &MemoriesToml {
+                    max_entries: 3,
+                    ..limits.clone()
+                },
+                true
+            ),
+            vec![
+                "Run cargo tests with --all-features".to_string(),
+                "Cargo builds need the nightly toolchain".to_string(),
+                "Use the staging database for migrations".to_string(),
+            ]
+        );
     }
 
     #[test]
@@ -391,6 +2439,55 @@ mod tests {
         assert_eq!(texts, vec!["Hello".to_string()]);
     }
 
+    #[test]
+    fn extraction_input_adds_the_turn_output_under_headings() {
+        let user = vec!["Why do the tests fail?".to_string()];
+        let history = vec![
+            ResponseItem::Message {
+                id: None,
+                role: "user".to_string(),
+                content: vec![ContentItem::InputText {
+                    text: "Why do the tests fail?".to_string(),
+                }],
+            },
+            ResponseItem::FunctionCallOutput {
+                call_id: "call-1".to_string(),
+                output: FunctionCallOutputPayload {
+                    content: "error: DATABASE_URL must be set".to_string(),
+                    ..Default::default()
+                },
+            },
+            ResponseItem::CustomToolCallOutput {
+                call_id: "call-2".to_string(),
+                output: "  ".to_string(),
+            },
+        ];
+
+        assert_eq!(
+            extraction_input(&user, None, &[], 2_000),
+            "Why do the tests fail?"
+        );
+        assert_eq!(
+            extraction_input(
+                &user,
+                Some("The tests need `DATABASE_URL`; set it to the local Postgres."),
+                &turn_tool_outputs(&history),
+                2_000
+            ),
+            "User messages:\nWhy do the tests fail?\n\n\
+             Assistant's final message:\nThe tests need `DATABASE_URL`; set it to the local Postgres.\n\n\
+             Tool results:\n- error: DATABASE_URL must be set"
+        );
+    }
+
+    #[test]
+    fn only_interactive_clients_review_memories() {
+        assert!(can_review_memories(&SessionSource::Cli));
+        assert!(can_review_memories(&SessionSource::VSCode));
+        assert!(!can_review_memories(&SessionSource::Mcp));
+        assert!(!can_review_memories(&SessionSource::Exec));
+    }
+
     #[test]
     fn build_memories_section_renders_header_and_bullets() {
         let entries = vec!["Prefer rustfmt".to_string(), "Run tests".to_string()];
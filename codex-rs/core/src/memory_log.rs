@@ -0,0 +1,226 @@
+//! `memories.log`, next to each memory store: one JSON line per decision
+//! about a memory, so users can see where a memory came from and take back
+//! what a session saved with `codex memories undo`. The file is only ever
+//! appended to.
+
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::fs;
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt;
+use tracing::warn;
+
+use crate::file_lock::lock_file_async;
+
+pub const MEMORIES_LOG_FILENAME: &str = "memories.log";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryLogAction {
+    /// The extraction model answered; `raw_output` holds its answer.
+    Extract,
+    /// The memory was saved.
+    Add,
+    /// The memory was not saved, for `reason`.
+    Skip,
+    /// The memory, or every memory without `text`, was deleted.
+    Prune,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MemoryLogReason {
+    /// Added with `codex memories add`.
+    Manual,
+    /// Added with `codex memories import`.
+    Imported,
+    /// The store already held it.
+    Duplicate,
+    /// Over `memories.max_new_per_turn`.
+    Limit,
+    /// Held a credential and `memories.redaction` is `reject`.
+    Credential,
+    /// Too close to a saved memory per `memories.similarity_threshold`.
+    Similar,
+    /// Left out when the memories were reviewed.
+    Declined,
+    /// Contradicts a saved memory.
+    Conflict,
+    /// Removed with `codex memories remove`.
+    Removed,
+    /// Removed with `codex memories clear`.
+    Cleared,
+    /// Removed with `codex memories undo`.
+    Undone,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryLogRecord {
+    pub ts: DateTime<Utc>,
+    pub action: MemoryLogAction,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<MemoryLogReason>,
+    /// Session the memory was extracted from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub turn_id: Option<String>,
+    /// Answer of the extraction model, with credentials redacted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw_output: Option<String>,
+}
+
+impl MemoryLogRecord {
+    pub(crate) fn new(
+        action: MemoryLogAction,
+        text: Option<String>,
+        reason: Option<MemoryLogReason>,
+    ) -> Self {
+        Self {
+            ts: Utc::now(),
+            action,
+            text,
+            reason,
+            session_id: None,
+            turn_id: None,
+            raw_output: None,
+        }
+    }
+}
+
+/// The log of the memory store at `store_location`.
+pub fn memory_log_path(store_location: &Path) -> PathBuf {
+    store_location.with_file_name(MEMORIES_LOG_FILENAME)
+}
+
+/// Append `records` to the log at `path`.
+pub(crate) async fn append_memory_log(
+    path: &Path,
+    records: &[MemoryLogRecord],
+) -> std::io::Result<()> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    let mut lines = String::new();
+    for record in records {
+        lines.push_str(&serde_json::to_string(record).map_err(std::io::Error::other)?);
+        lines.push('\n');
+    }
+    let _lock = lock_file_async(path).await?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    file.write_all(lines.as_bytes()).await?;
+    file.flush().await
+}
+
+/// [`append_memory_log`], with failures only logged: the decisions stand
+/// whether or not they could be recorded.
+pub(crate) async fn record_memory_decisions(store_location: &Path, records: &[MemoryLogRecord]) {
+    let path = memory_log_path(store_location);
+    if let Err(err) = append_memory_log(&path, records).await {
+        warn!("Failed to write the memory log {}: {err:#}", path.display());
+    }
+}
+
+/// The records of the log at `path`, oldest first. Lines that do not parse
+/// are skipped.
+pub async fn read_memory_log(path: &Path) -> std::io::Result<Vec<MemoryLogRecord>> {
+    let text = match fs::read_to_string(path).await {
+        Ok(text) => text,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    Ok(text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(record) => Some(record),
+            Err(err) => {
+                warn!("Skipping a malformed line of {}: {err}", path.display());
+                None
+            }
+        })
+        .collect())
+}
+
+/// Texts the session `session_id` added that are still in the store as far
+/// as the log knows, oldest first.
+pub(crate) fn session_additions(records: &[MemoryLogRecord], session_id: &str) -> Vec<String> {
+    let mut texts: Vec<String> = Vec::new();
+    for record in records {
+        let Some(text) = &record.text else {
+            if record.action == MemoryLogAction::Prune {
+                texts.clear();
+            }
+            continue;
+        };
+        match record.action {
+            MemoryLogAction::Add if record.session_id.as_deref() == Some(session_id) => {
+                texts.push(text.clone());
+            }
+            MemoryLogAction::Prune => texts.retain(|added| !added.eq_ignore_ascii_case(text)),
+            _ => {}
+        }
+    }
+    texts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn record(action: MemoryLogAction, text: Option<&str>, session_id: &str) -> MemoryLogRecord {
+        MemoryLogRecord {
+            session_id: Some(session_id.to_string()),
+            ..MemoryLogRecord::new(action, text.map(str::to_string), None)
+        }
+    }
+
+    #[tokio::test]
+    async fn records_round_trip_and_undo_finds_what_a_session_still_has() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let path = memory_log_path(&dir.path().join("memories.md"));
+        let records = vec![
+            MemoryLogRecord {
+                raw_output: Some("- [build] Run tests\n- Use pnpm".to_string()),
+                ..record(MemoryLogAction::Extract, None, "s1")
+            },
+            record(MemoryLogAction::Add, Some("Run tests"), "s1"),
+            record(MemoryLogAction::Add, Some("Use pnpm"), "s1"),
+            record(MemoryLogAction::Add, Some("Use tabs"), "s2"),
+            MemoryLogRecord::new(
+                MemoryLogAction::Prune,
+                Some("use PNPM".to_string()),
+                Some(MemoryLogReason::Removed),
+            ),
+        ];
+        append_memory_log(&path, &records[..2]).await?;
+        append_memory_log(&path, &records[2..]).await?;
+        fs::write(
+            &path,
+            format!("{}not json\n", fs::read_to_string(&path).await?),
+        )
+        .await?;
+
+        let read = read_memory_log(&path).await?;
+        assert_eq!(read, records);
+        assert_eq!(
+            session_additions(&read, "s1"),
+            vec!["Run tests".to_string()]
+        );
+        assert_eq!(session_additions(&read, "s3"), Vec::<String>::new());
+        Ok(())
+    }
+}
@@ -0,0 +1,320 @@
+//! Check the configured model against the provider before the first turn.
+//!
+//! The provider's `GET /models` list says whether the model exists, and for
+//! some providers how large its context window is and whether it accepts
+//! tools. The list is cached per provider under `$CODEX_HOME`, so only the
+//! first session after the cache expires pays for the request. A model
+//! missing from a cached list is looked up again in a fresh one, since the
+//! provider may have added it since.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use chrono::DateTime;
+use chrono::Utc;
+use codex_api::AuthProvider as ApiAuthProvider;
+use codex_app_server_protocol::AuthMode;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::api_bridge::auth_provider_from_auth;
+use crate::auth::AuthManager;
+use crate::config::Config;
+use crate::default_client::build_reqwest_client;
+use crate::file_lock::write_atomic_async;
+
+const PROBE_CACHE_FILE: &str = "model_probe_cache.json";
+const PROBE_CACHE_TTL: Duration = Duration::from_secs(60 * 60);
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+/// Names listed in the error when the model is missing.
+const MAX_LISTED_MODELS: usize = 20;
+
+/// What the provider reports about one model.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ProbedModel {
+    pub(crate) id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) context_window: Option<i64>,
+    /// `None` when the provider does not say.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) supports_tools: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ProbeOutcome {
+    Found(ProbedModel),
+    /// The provider listed its models and the configured one is not among them.
+    Missing {
+        available: Vec<String>,
+    },
+    /// The probe could not run; the session starts without it.
+    Skipped(String),
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ProbeCache {
+    /// Keyed by provider id and base URL.
+    providers: HashMap<String, CachedModels>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedModels {
+    fetched_at: DateTime<Utc>,
+    models: Vec<ProbedModel>,
+}
+
+/// Look up `model` in the model list of the configured provider.
+pub(crate) async fn probe_model(
+    config: &Config,
+    auth_manager: &AuthManager,
+    model: &str,
+) -> ProbeOutcome {
+    // The ChatGPT backend serves its own model list, which `ModelsManager`
+    // already reads.
+    if auth_manager.get_auth_mode() == Some(AuthMode::ChatGPT)
+        && config.model_provider.requires_openai_auth
+    {
+        return ProbeOutcome::Skipped("the ChatGPT backend is not probed".to_string());
+    }
+    let outcome = match provider_models(config, auth_manager, false).await {
+        Ok((models, true)) => find_model(models, model),
+        Ok((models, false)) => return find_model(models, model),
+        Err(err) => return ProbeOutcome::Skipped(format!("{err:#}")),
+    };
+    if !matches!(outcome, ProbeOutcome::Missing { .. }) {
+        return outcome;
+    }
+    match provider_models(config, auth_manager, true).await {
+        Ok((models, _)) => find_model(models, model),
+        Err(err) => ProbeOutcome::Skipped(format!("{err:#}")),
+    }
+}
+
+/// The message for a session whose model the provider does not serve.
+pub(crate) fn missing_model_message(config: &Config, model: &str, available: &[String]) -> String {
+    let mut message = format!(
+        "Model `{model}` is not available from provider `{}`.",
+        config.model_provider_id
+    );
+    if !available.is_empty() {
+        let mut listed = available
+            .iter()
+            .take(MAX_LISTED_MODELS)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+        if available.len() > MAX_LISTED_MODELS {
+            listed.push_str(&format!(
+                " and {} more",
+                available.len() - MAX_LISTED_MODELS
+            ));
+        }
+        message.push_str(&format!(" Available models: {listed}."));
+    }
+    message.push_str(
+        " Set `model` in config.toml or pass `-m <model>`, or disable the check with `--disable model_probe`.",
+    );
+    message
+}
+
+fn find_model(models: Vec<ProbedModel>, model: &str) -> ProbeOutcome {
+    if models.is_empty() {
+        return ProbeOutcome::Skipped("the provider listed no models".to_string());
+    }
+    match models.iter().position(|probed| probed.id == model) {
+        Some(index) => ProbeOutcome::Found(models[index].clone()),
+        None => {
+            let mut available: Vec<String> = models.into_iter().map(|probed| probed.id).collect();
+            available.sort();
+            ProbeOutcome::Missing { available }
+        }
+    }
+}
+
+/// The provider's model list, and whether it came from the cache. With
+/// `refresh`, the cache is bypassed.
+async fn provider_models(
+    config: &Config,
+    auth_manager: &AuthManager,
+    refresh: bool,
+) -> Result<(Vec<ProbedModel>, bool)> {
+    let provider = config
+        .model_provider
+        .to_api_provider(auth_manager.get_auth_mode())?;
+    let cache_key = format!("{}|{}", config.model_provider_id, provider.base_url);
+    let cache_path = config.codex_home.join(PROBE_CACHE_FILE);
+    let mut cache = load_cache(&cache_path).await;
+    if !refresh
+        && let Some(cached) = cache.providers.get(&cache_key)
+        && is_fresh(cached.fetched_at)
+    {
+        return Ok((cached.models.clone(), true));
+    }
+
+    let auth = auth_provider_from_auth(auth_manager.auth(), &config.model_provider).await?;
+    let mut request = build_reqwest_client()
+        .get(provider.url_for_path("models"))
+        .headers(provider.headers.clone())
+        .timeout(PROBE_TIMEOUT);
+    if let Some(token) = auth.bearer_token() {
+        request = request.bearer_auth(token);
+    }
+    let response = request.send().await.context("failed to list models")?;
+    let status = response.status();
+    if !status.is_success() {
+        anyhow::bail!("listing models returned {status}");
+    }
+    let body = response
+        .bytes()
+        .await
+        .context("failed to read the model list")?;
+    let models = parse_models(&body)?;
+
+    cache.providers.insert(
+        cache_key,
+        CachedModels {
+            fetched_at: Utc::now(),
+            models: models.clone(),
+        },
+    );
+    if let Err(err) = save_cache(&cache_path, &cache).await {
+        tracing::warn!("failed to save the model probe cache: {err:#}");
+    }
+    Ok((models, false))
+}
+
+/// Parse an OpenAI-style `{"data": [{"id": ...}]}` model list. The context
+/// window and tool support are read from the fields that OpenRouter, vLLM,
+/// Ollama, LM Studio and similar servers add to each entry.
+fn parse_models(body: &[u8]) -> Result<Vec<ProbedModel>> {
+    let value: Value = serde_json::from_slice(body).context("the model list is not JSON")?;
+    let entries = value
+        .get("data")
+        .or_else(|| value.get("models"))
+        .and_then(Value::as_array)
+        .context("the model list has no `data` array")?;
+    Ok(entries
+        .iter()
+        .filter_map(|entry| {
+            let id = entry
+                .get("id")
+                .or_else(|| entry.get("name"))
+                .and_then(Value::as_str)?;
+            let context_window = ["context_window", "context_length", "max_model_len"]
+                .iter()
+                .find_map(|key| entry.get(*key).and_then(Value::as_i64));
+            let supports_tools = ["supported_parameters", "capabilities"]
+                .iter()
+                .find_map(|key| entry.get(*key).and_then(Value::as_array))
+                .map(|params| {
+                    params.iter().any(|param| {
+                        matches!(
+                            param.as_str(),
+                            Some("tools" | "tool_use" | "function_calling")
+                        )
+                    })
+                });
+            Some(ProbedModel {
+                id: id.to_string(),
+                context_window,
+                supports_tools,
+            })
+        })
+        .collect())
+}
+
+fn is_fresh(fetched_at: DateTime<Utc>) -> bool {
+    chrono::Duration::from_std(PROBE_CACHE_TTL)
+        .is_ok_and(|ttl| Utc::now().signed_duration_since(fetched_at) <= ttl)
+}
+
+async fn load_cache(path: &Path) -> ProbeCache {
+    match tokio::fs::read(path).await {
+        Ok(contents) => serde_json::from_slice(&contents).unwrap_or_default(),
+        Err(_) => ProbeCache::default(),
+    }
+}
+
+async fn save_cache(path: &Path, cache: &ProbeCache) -> Result<()> {
+    write_atomic_async(path, serde_json::to_vec_pretty(cache)?, None).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_models_reads_context_window_and_tool_support() {
+        let body = br#"{"data": [
+            {"id": "gpt-5.1"},
+            {"id": "qwen/qwen3-coder", "context_length": 262144, "supported_parameters": ["tools", "temperature"]},
+            {"id": "local-llama", "max_model_len": 8192, "supported_parameters": ["temperature"]},
+            {"id": "qwen2.5-coder", "capabilities": ["tool_use"]},
+            {"id": "gemma", "capabilities": ["completion"]}
+        ]}"#;
+
+        assert_eq!(
+            parse_models(body).expect("parse"),
+            vec![
+                ProbedModel {
+                    id: "gpt-5.1".to_string(),
+                    context_window: None,
+                    supports_tools: None,
+                },
+                ProbedModel {
+                    id: "qwen/qwen3-coder".to_string(),
+                    context_window: Some(262_144),
+                    supports_tools: Some(true),
+                },
+                ProbedModel {
+                    id: "local-llama".to_string(),
+                    context_window: Some(8_192),
+                    supports_tools: Some(false),
+                },
+                ProbedModel {
+                    id: "qwen2.5-coder".to_string(),
+                    context_window: None,
+                    supports_tools: Some(true),
+                },
+                ProbedModel {
+                    id: "gemma".to_string(),
+                    context_window: None,
+                    supports_tools: Some(false),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn find_model_lists_alternatives_when_missing() {
+        let models = ["b-model", "a-model"]
+            .into_iter()
+            .map(|id| ProbedModel {
+                id: id.to_string(),
+                context_window: None,
+                supports_tools: None,
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            find_model(models.clone(), "a-model"),
+            ProbeOutcome::Found(models[1].clone())
+        );
+        assert_eq!(
+            find_model(models, "gpt-typo"),
+            ProbeOutcome::Missing {
+                available: vec!["a-model".to_string(), "b-model".to_string()],
+            }
+        );
+        assert!(matches!(
+            find_model(Vec::new(), "a-model"),
+            ProbeOutcome::Skipped(_)
+        ));
+    }
+}
@@ -29,6 +29,7 @@ const MAX_REQUEST_MAX_RETRIES: u64 = 100;
 pub const CHAT_WIRE_API_DEPRECATION_SUMMARY: &str = r#"Support for the "chat" wire API is deprecated and will soon be removed. Update your model provider definition in config.toml to use wire_api = "responses"."#;
 
 const OPENAI_PROVIDER_NAME: &str = "OpenAI";
+const OPENAI_EMBEDDING_MODEL: &str = "text-embedding-3-small";
 
 /// Wire protocol that the provider speaks. Most third-party services only
 /// implement the classic OpenAI Chat Completions JSON schema, whereas OpenAI
@@ -99,6 +100,12 @@ pub struct ModelProviderInfo {
     /// and API key (if needed) comes from the "env_key" environment variable.
     #[serde(default)]
     pub requires_openai_auth: bool,
+
+    /// Model to request from the provider's `/embeddings` endpoint. Defaults
+    /// to OpenAI's `text-embedding-3-small` for providers that use OpenAI
+    /// auth; other providers have no embedding model unless this is set.
+    #[serde(default)]
+    pub embedding_model: Option<String>,
 }
 
 impl ModelProviderInfo {
@@ -210,6 +217,16 @@ impl ModelProviderInfo {
             .map(Duration::from_millis)
             .unwrap_or(Duration::from_millis(DEFAULT_STREAM_IDLE_TIMEOUT_MS))
     }
+
+    /// The model to request embeddings from, if this provider has one.
+    pub fn embedding_model(&self) -> Option<&str> {
+        match &self.embedding_model {
+            Some(model) => Some(model.as_str()),
+            None if self.requires_openai_auth => Some(OPENAI_EMBEDDING_MODEL),
+            None => None,
+        }
+    }
+
     pub fn create_openai_provider() -> ModelProviderInfo {
         ModelProviderInfo {
             name: OPENAI_PROVIDER_NAME.into(),
@@ -247,6 +264,7 @@ impl ModelProviderInfo {
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: true,
+            embedding_model: None,
         }
     }
 
@@ -320,6 +338,7 @@ pub fn create_oss_provider_with_base_url(base_url: &str, wire_api: WireApi) -> M
         stream_max_retries: None,
         stream_idle_timeout_ms: None,
         requires_openai_auth: false,
+        embedding_model: None,
     }
 }
 
@@ -348,6 +367,7 @@ base_url = "http://localhost:11434/v1"
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            embedding_model: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -378,6 +398,7 @@ query_params = { api-version = "2025-04-01-preview" }
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            embedding_model: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -411,6 +432,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            embedding_model: None,
         };
 
         let provider: ModelProviderInfo = toml::from_str(azure_provider_toml).unwrap();
@@ -442,6 +464,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 stream_max_retries: None,
                 stream_idle_timeout_ms: None,
                 requires_openai_auth: false,
+                embedding_model: None,
             };
             let api = provider.to_api_provider(None).expect("api provider");
             assert!(
@@ -464,6 +487,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             stream_max_retries: None,
             stream_idle_timeout_ms: None,
             requires_openai_auth: false,
+            embedding_model: None,
         };
         let named_api = named_provider.to_api_provider(None).expect("api provider");
         assert!(named_api.is_azure_responses_endpoint());
@@ -488,6 +512,7 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
                 stream_max_retries: None,
                 stream_idle_timeout_ms: None,
                 requires_openai_auth: false,
+                embedding_model: None,
             };
             let api = provider.to_api_provider(None).expect("api provider");
             assert!(
@@ -496,4 +521,15 @@ env_http_headers = { "X-Example-Env-Header" = "EXAMPLE_ENV_VAR" }
             );
         }
     }
+
+    #[test]
+    fn embedding_model_defaults_only_for_openai_auth() {
+        let openai = ModelProviderInfo::create_openai_provider();
+        assert_eq!(openai.embedding_model(), Some("text-embedding-3-small"));
+
+        let mut oss = create_oss_provider_with_base_url("http://localhost:11434/v1", WireApi::Chat);
+        assert_eq!(oss.embedding_model(), None);
+        oss.embedding_model = Some("nomic-embed-text".to_string());
+        assert_eq!(oss.embedding_model(), Some("nomic-embed-text"));
+    }
 }
@@ -28,6 +28,8 @@ use crate::openai_models::model_family::ModelFamily;
 use crate::openai_models::model_presets::builtin_model_presets;
 
 const MODEL_CACHE_FILE: &str = "models_cache.json";
+/// Model metadata used when remote models are off or unreachable.
+pub(crate) const BUNDLED_MODELS_JSON: &str = include_str!("../../models.json");
 const DEFAULT_MODEL_CACHE_TTL: Duration = Duration::from_secs(300);
 const OPENAI_DEFAULT_API_MODEL: &str = "gpt-5.1-codex-max";
 const OPENAI_DEFAULT_CHATGPT_MODEL: &str = "gpt-5.2-codex";
@@ -165,14 +167,25 @@ impl ModelsManager {
         Self::find_family_for_model(model).with_config_overrides(config)
     }
 
+    /// Build the family for `model` from the bundled model metadata alone, as
+    /// a session does when remote models are unavailable.
+    pub(crate) fn construct_model_family_bundled(
+        model: &str,
+        bundled_models: &[ModelInfo],
+        config: &Config,
+    ) -> ModelFamily {
+        Self::find_family_for_model(model)
+            .with_remote_overrides(bundled_models.to_vec())
+            .with_config_overrides(config)
+    }
+
     /// Replace the cached remote models and rebuild the derived presets list.
     async fn apply_remote_models(&self, models: Vec<ModelInfo>) {
         *self.remote_models.write().await = models;
     }
 
-    fn load_remote_models_from_file() -> Result<Vec<ModelInfo>, std::io::Error> {
-        let file_contents = include_str!("../../models.json");
-        let response: ModelsResponse = serde_json::from_str(file_contents)?;
+    pub(crate) fn load_remote_models_from_file() -> Result<Vec<ModelInfo>, std::io::Error> {
+        let response: ModelsResponse = serde_json::from_str(BUNDLED_MODELS_JSON)?;
         Ok(response.models)
     }
 
@@ -375,6 +388,7 @@ mod tests {
             stream_max_retries: Some(0),
             stream_idle_timeout_ms: Some(5_000),
             requires_openai_auth: false,
+            embedding_model: None,
         }
     }
 
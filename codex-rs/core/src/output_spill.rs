@@ -0,0 +1,199 @@
+//! Keep the full output of commands that overflow the model's truncation
+//! budget. The truncated view the model sees drops the middle of long logs;
+//! with `output_spill` enabled the complete text is written to a per-session
+//! file named after the tool call, and the model can page through it or
+//! search it with the `read_output_range` tool.
+//!
+//! Spilled files live under the session scratch directory when there is one,
+//! otherwise under the system temp directory, and are removed when the
+//! session shuts down.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::ConversationId;
+use tracing::warn;
+
+const OUTPUT_DIR_PREFIX: &str = "codex-output-";
+
+/// Number of lines returned by a range read when the model gives no limit.
+pub(crate) const DEFAULT_RANGE_LIMIT: usize = 200;
+
+/// Longest line returned by a range read; longer lines are cut at a char
+/// boundary.
+const MAX_LINE_LENGTH: usize = 1000;
+
+#[derive(Debug)]
+pub(crate) struct OutputSpill {
+    dir: PathBuf,
+}
+
+impl OutputSpill {
+    /// Place spilled output in `scratch_dir/output` when the session has a
+    /// scratch directory, otherwise in a directory of its own under the
+    /// system temp directory.
+    pub(crate) fn new(scratch_dir: Option<&Path>, conversation_id: ConversationId) -> Self {
+        let dir = match scratch_dir {
+            Some(scratch_dir) => scratch_dir.join("output"),
+            None => std::env::temp_dir().join(format!("{OUTPUT_DIR_PREFIX}{conversation_id}")),
+        };
+        Self { dir }
+    }
+
+    /// Write `content` under `handle`, replacing any earlier output with the
+    /// same handle.
+    pub(crate) async fn spill(&self, handle: &str, content: &str) -> io::Result<()> {
+        let path = self.path_for(handle)?;
+        tokio::fs::create_dir_all(&self.dir).await?;
+        tokio::fs::write(path, content).await
+    }
+
+    /// Save the full output of tool call `handle` and return the notice to put
+    /// ahead of the truncated output the model sees. A failed write is logged
+    /// and leaves the output as it was.
+    pub(crate) async fn save_full_output(&self, handle: &str, content: &str) -> Option<String> {
+        if let Err(err) = self.spill(handle, content).await {
+            warn!("failed to save full output of {handle}: {err}");
+            return None;
+        }
+        Some(format!(
+            "[Full output ({} lines) saved as handle `{handle}`; call read_output_range with this handle to read or search any part of it.]",
+            content.lines().count(),
+        ))
+    }
+
+    /// Read the output previously spilled under `handle`.
+    pub(crate) async fn read(&self, handle: &str) -> io::Result<String> {
+        let path = self.path_for(handle)?;
+        tokio::fs::read_to_string(path).await
+    }
+
+    /// Remove every spilled file.
+    pub(crate) async fn cleanup(&self) {
+        match tokio::fs::remove_dir_all(&self.dir).await {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => warn!(
+                "Failed to remove spilled output directory {}: {err}",
+                self.dir.display()
+            ),
+        }
+    }
+
+    /// Handles are tool call ids; anything that could escape the directory is
+    /// rejected rather than sanitized so a handle always names one file.
+    fn path_for(&self, handle: &str) -> io::Result<PathBuf> {
+        let valid = !handle.is_empty()
+            && handle
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+        if !valid {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid output handle `{handle}`"),
+            ));
+        }
+        Ok(self.dir.join(format!("{handle}.txt")))
+    }
+}
+
+/// Render up to `limit` lines of `content`, starting at the 1-based
+/// `start_line`. With a `pattern`, only lines containing it are returned, so
+/// the model can find the interesting parts before reading around them.
+pub(crate) fn render_range(
+    content: &str,
+    start_line: usize,
+    limit: usize,
+    pattern: Option<&str>,
+) -> String {
+    let total_lines = content.lines().count();
+    let start_line = start_line.max(1);
+    let mut selected = content
+        .lines()
+        .enumerate()
+        .skip(start_line - 1)
+        .filter(|(_, line)| pattern.is_none_or(|pattern| line.contains(pattern)));
+
+    let mut rendered = Vec::new();
+    for (index, line) in selected.by_ref().take(limit) {
+        rendered.push(format!("L{}: {}", index + 1, truncate_line(line)));
+    }
+    let more = selected.next().map(|(index, _)| index + 1);
+
+    let header = match (pattern, rendered.is_empty()) {
+        (Some(pattern), true) => {
+            format!(
+                "No lines from L{start_line} on contain `{pattern}` ({total_lines} lines total)."
+            )
+        }
+        (None, true) => format!("Output has {total_lines} lines; L{start_line} is past the end."),
+        (Some(pattern), false) => {
+            format!("Lines containing `{pattern}` ({total_lines} lines total):")
+        }
+        (None, false) => format!("Output has {total_lines} lines:"),
+    };
+    let mut sections = vec![header];
+    sections.extend(rendered);
+    if let Some(next) = more {
+        let what = if pattern.is_some() {
+            "matching lines"
+        } else {
+            "lines"
+        };
+        sections.push(format!(
+            "More {what} follow; continue with start_line {next}."
+        ));
+    }
+    sections.join("\n")
+}
+
+fn truncate_line(line: &str) -> &str {
+    if line.len() <= MAX_LINE_LENGTH {
+        return line;
+    }
+    let mut end = MAX_LINE_LENGTH;
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+    &line[..end]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn spill_round_trips_and_rejects_bad_handles() {
+        let scratch = tempdir().expect("tempdir");
+        let spill = OutputSpill::new(Some(scratch.path()), ConversationId::new());
+
+        spill.spill("call_1", "one\ntwo\n").await.expect("spill");
+        assert_eq!(spill.read("call_1").await.expect("read"), "one\ntwo\n");
+        assert!(spill.read("../call_1").await.is_err());
+        assert!(spill.spill("a/b", "x").await.is_err());
+
+        spill.cleanup().await;
+        assert!(!scratch.path().join("output").exists());
+    }
+
+    #[test]
+    fn render_range_pages_and_filters() {
+        let content = "ok 1\nFAIL a\nok 2\nFAIL b\nFAIL c\n";
+
+        assert_eq!(
+            render_range(content, 2, 2, None),
+            "Output has 5 lines:\nL2: FAIL a\nL3: ok 2\nMore lines follow; continue with start_line 4."
+        );
+        assert_eq!(
+            render_range(content, 3, 1, Some("FAIL")),
+            "Lines containing `FAIL` (5 lines total):\nL4: FAIL b\nMore matching lines follow; continue with start_line 5."
+        );
+        assert_eq!(
+            render_range(content, 6, 10, None),
+            "Output has 5 lines; L6 is past the end."
+        );
+    }
+}
@@ -0,0 +1,185 @@
+//! Second-opinion review of a pending patch.
+//!
+//! Before a patch applies, a separate model request compares it with the
+//! user's latest request and the project's instructions. The concerns it
+//! raises are shown on the approval prompt, and turn an auto-approved patch
+//! into one that asks first.
+
+use codex_apply_patch::ApplyPatchAction;
+use codex_protocol::items::TurnItem;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use futures::StreamExt;
+use tracing::warn;
+
+use crate::client::ModelClient;
+use crate::client_common::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::compact::content_items_to_text;
+use crate::config::types::PatchCritic;
+use crate::error::CodexErr;
+use crate::error::Result as CodexResult;
+use crate::event_mapping::parse_turn_item;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+pub(crate) const PATCH_CRITIC_PROMPT: &str = include_str!("../templates/patch_critic/prompt.md");
+const NO_CONCERNS_RESPONSE: &str = "NO_CONCERNS";
+const REQUEST_MAX_BYTES: usize = 4 * 1024;
+const CONVENTIONS_MAX_BYTES: usize = 8 * 1024;
+const PATCH_MAX_BYTES: usize = 24 * 1024;
+const MAX_CONCERNS: usize = 5;
+
+/// Ask the critic about `action`. Returns no concerns when the review itself
+/// fails, so a broken critic never blocks a patch.
+pub(crate) async fn review_patch(
+    sess: &Session,
+    turn_context: &TurnContext,
+    critic: &PatchCritic,
+    action: &ApplyPatchAction,
+) -> Vec<String> {
+    let request = sess
+        .clone_history()
+        .await
+        .get_history()
+        .iter()
+        .rev()
+        .find_map(|item| match parse_turn_item(item) {
+            Some(TurnItem::UserMessage(message)) => Some(message.message()),
+            _ => None,
+        })
+        .unwrap_or_default();
+    let mut text = format!(
+        "<request>\n{}\n</request>\n",
+        truncate_text(&request, TruncationPolicy::Bytes(REQUEST_MAX_BYTES))
+    );
+    if let Some(conventions) = turn_context.user_instructions.as_deref() {
+        text.push_str(&format!(
+            "\n<conventions>\n{}\n</conventions>\n",
+            truncate_text(conventions, TruncationPolicy::Bytes(CONVENTIONS_MAX_BYTES))
+        ));
+    }
+    text.push_str(&format!(
+        "\n<patch>\n{}\n</patch>\n",
+        truncate_text(&action.patch, TruncationPolicy::Bytes(PATCH_MAX_BYTES))
+    ));
+
+    let prompt = Prompt {
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText { text }],
+        }],
+        tools: Vec::new(),
+        parallel_tool_calls: false,
+        base_instructions_override: Some(PATCH_CRITIC_PROMPT.to_string()),
+        locale_instructions: None,
+        model_instructions: None,
+        composed_instructions: None,
+        output_schema: None,
+    };
+    let client = critic_client(sess, turn_context, critic).await;
+    match run_review(sess, turn_context, &client, &prompt).await {
+        Ok(reply) => parse_concerns(&reply),
+        Err(err) => {
+            warn!("patch critic failed: {err:#}");
+            Vec::new()
+        }
+    }
+}
+
+/// Format concerns for the approval prompt.
+pub(crate) fn format_concerns(concerns: &[String]) -> String {
+    let mut message = "The patch review raised concerns:".to_string();
+    for concern in concerns {
+        message.push_str(&format!("\n- {concern}"));
+    }
+    message
+}
+
+async fn critic_client(
+    sess: &Session,
+    turn_context: &TurnContext,
+    critic: &PatchCritic,
+) -> ModelClient {
+    let Some(model) = critic.model.as_deref() else {
+        return turn_context.client.clone();
+    };
+    let model_family = sess
+        .services
+        .models_manager
+        .construct_model_family(model, &turn_context.client.config())
+        .await;
+    turn_context.client.with_model_family(model, model_family)
+}
+
+async fn run_review(
+    sess: &Session,
+    turn_context: &TurnContext,
+    client: &ModelClient,
+    prompt: &Prompt,
+) -> CodexResult<String> {
+    let mut stream = client.clone().stream(prompt).await?;
+    let mut reply = String::new();
+    while let Some(event) = stream.next().await {
+        match event? {
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. }) => {
+                if let Some(text) = content_items_to_text(&content) {
+                    reply.push_str(&text);
+                }
+            }
+            ResponseEvent::RateLimits(snapshot) => {
+                sess.update_rate_limits(turn_context, snapshot).await;
+            }
+            ResponseEvent::Completed { token_usage, .. } => {
+                sess.update_side_token_usage(turn_context, token_usage.as_ref())
+                    .await;
+                return Ok(reply);
+            }
+            _ => {}
+        }
+    }
+    Err(CodexErr::Stream(
+        "stream closed before response.completed".into(),
+        None,
+    ))
+}
+
+fn parse_concerns(reply: &str) -> Vec<String> {
+    if reply.trim() == NO_CONCERNS_RESPONSE {
+        return Vec::new();
+    }
+    reply
+        .lines()
+        .filter_map(|line| {
+            line.trim()
+                .strip_prefix("- ")
+                .or_else(|| line.trim().strip_prefix("* "))
+        })
+        .map(|concern| concern.trim().to_string())
+        .filter(|concern| !concern.is_empty())
+        .take(MAX_CONCERNS)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_concerns_reads_bullets_and_the_no_concerns_marker() {
+        assert_eq!(parse_concerns("NO_CONCERNS\n"), Vec::<String>::new());
+        assert_eq!(
+            parse_concerns(
+                "Two problems:\n- Deletes `tests/config.rs` without a reason.\n* Renames a public function the request did not mention.\n"
+            ),
+            vec![
+                "Deletes `tests/config.rs` without a reason.".to_string(),
+                "Renames a public function the request did not mention.".to_string(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,373 @@
+//! Try a patch in a throwaway copy of the repository before it applies.
+//!
+//! The copy is a detached `git worktree` checked out at a ghost commit of
+//! the working tree, so uncommitted and untracked files are there too. The
+//! patch is written into the copy, the configured checks run in it, and the
+//! copy is removed again. The results go into the approval prompt.
+//!
+//! The checks run in the platform sandbox with the copy as the only writable
+//! root and no network access, whatever the session's own sandbox policy is.
+//! Where no sandbox is available the checks are skipped.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Context;
+use anyhow::Result;
+use codex_apply_patch::ApplyPatchAction;
+use codex_apply_patch::ApplyPatchFileChange;
+use codex_git::CreateGhostCommitOptions;
+use codex_git::create_ghost_commit;
+use codex_protocol::protocol::SandboxPolicy;
+use codex_utils_absolute_path::AbsolutePathBuf;
+use tokio::process::Command;
+
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::config::types::PatchPreview;
+use crate::error::CodexErr;
+use crate::error::SandboxErr;
+use crate::exec::ExecExpiration;
+use crate::exec::ExecParams;
+use crate::exec::SandboxType;
+use crate::exec::process_exec_tool_call;
+use crate::exec_env::create_env;
+use crate::git_info::get_git_repo_root;
+use crate::safety::get_platform_sandbox;
+use crate::sandboxing::SandboxPermissions;
+
+/// Lines of output kept from a failed check.
+const OUTPUT_TAIL_LINES: usize = 20;
+
+/// What the checks said about a patch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PreviewReport {
+    /// False when a check failed. A preview that could not run counts as
+    /// passed, so it never holds up a patch on its own.
+    pub(crate) passed: bool,
+    /// Text for the approval prompt.
+    pub(crate) summary: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CheckResult {
+    command: String,
+    outcome: CheckOutcome,
+    duration: Duration,
+    /// Last lines of the combined output, kept only for failed checks.
+    output: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CheckOutcome {
+    Passed,
+    Failed(Option<i32>),
+    TimedOut,
+}
+
+/// Apply `action` to a copy of the repository and run the checks there.
+pub(crate) async fn preview_patch(
+    sess: &Session,
+    turn: &TurnContext,
+    preview: &PatchPreview,
+    action: &ApplyPatchAction,
+) -> PreviewReport {
+    sess.notify_background_event(
+        turn,
+        format!(
+            "Checking the patch in a preview copy ({} command(s))",
+            preview.commands.len()
+        ),
+    )
+    .await;
+    match run_preview(sess, turn, preview, action).await {
+        Ok(results) => PreviewReport {
+            passed: results
+                .iter()
+                .all(|result| result.outcome == CheckOutcome::Passed),
+            summary: format_results(&results),
+        },
+        Err(err) => PreviewReport {
+            passed: true,
+            summary: format!("Patch preview skipped: {err:#}"),
+        },
+    }
+}
+
+async fn run_preview(
+    sess: &Session,
+    turn: &TurnContext,
+    preview: &PatchPreview,
+    action: &ApplyPatchAction,
+) -> Result<Vec<CheckResult>> {
+    match get_platform_sandbox() {
+        None => anyhow::bail!("no sandbox is available to run the checks in"),
+        Some(SandboxType::LinuxSeccomp) if turn.codex_linux_sandbox_exe.is_none() => {
+            anyhow::bail!("the Linux sandbox helper is not available to run the checks in")
+        }
+        Some(_) => {}
+    }
+    let root = get_git_repo_root(&turn.cwd).context("the workspace is not a git repository")?;
+    let ghost_snapshot = turn.ghost_snapshot.clone();
+    let snapshot_root = root.clone();
+    let snapshot = tokio::task::spawn_blocking(move || {
+        let options = CreateGhostCommitOptions::new(&snapshot_root).ghost_snapshot(ghost_snapshot);
+        create_ghost_commit(&options)
+    })
+    .await
+    .context("snapshot task panicked")?
+    .context("failed to snapshot the working tree")?;
+
+    let tmp = tempfile::tempdir().context("failed to create a temporary directory")?;
+    let copy = tmp.path().join("worktree");
+    run_git(
+        &root,
+        &[
+            "worktree",
+            "add",
+            "--detach",
+            &copy.to_string_lossy(),
+            snapshot.id(),
+        ],
+    )
+    .await?;
+
+    let results = check_copy(sess, turn, preview, action, &root, &copy).await;
+    if let Err(err) = run_git(
+        &root,
+        &["worktree", "remove", "--force", &copy.to_string_lossy()],
+    )
+    .await
+    {
+        tracing::warn!("failed to remove patch preview worktree: {err:#}");
+    }
+    results
+}
+
+async fn check_copy(
+    sess: &Session,
+    turn: &TurnContext,
+    preview: &PatchPreview,
+    action: &ApplyPatchAction,
+    root: &Path,
+    copy: &Path,
+) -> Result<Vec<CheckResult>> {
+    write_changes(action, root, copy)?;
+    let cwd = copy.join(turn.cwd.strip_prefix(root).unwrap_or(Path::new("")));
+    let sandbox_policy = check_sandbox_policy(copy)?;
+    let shell = sess.user_shell();
+    let timeout = Duration::from_secs(preview.timeout_secs);
+    let mut results = Vec::with_capacity(preview.commands.len());
+    for command in &preview.commands {
+        let params = ExecParams {
+            command: shell.derive_exec_args(command, true),
+            cwd: cwd.clone(),
+            expiration: ExecExpiration::Timeout(timeout),
+            env: create_env(&turn.shell_environment_policy),
+            sandbox_permissions: SandboxPermissions::UseDefault,
+            justification: None,
+            arg0: None,
+        };
+        let result = process_exec_tool_call(
+            params,
+            &sandbox_policy,
+            &cwd,
+            &turn.codex_linux_sandbox_exe,
+            None,
+        )
+        .await;
+        let (outcome, duration, output) = match result {
+            Ok(output) if output.exit_code == 0 => {
+                (CheckOutcome::Passed, output.duration, String::new())
+            }
+            Ok(output) => (
+                CheckOutcome::Failed(Some(output.exit_code)),
+                output.duration,
+                output_tail(&output.aggregated_output.text),
+            ),
+            Err(CodexErr::Sandbox(SandboxErr::Timeout { output })) => {
+                (CheckOutcome::TimedOut, output.duration, String::new())
+            }
+            Err(CodexErr::Sandbox(SandboxErr::Denied { output })) => (
+                CheckOutcome::Failed(Some(output.exit_code)),
+                output.duration,
+                output_tail(&output.aggregated_output.text),
+            ),
+            Err(err) => return Err(err).with_context(|| format!("failed to run `{command}`")),
+        };
+        results.push(CheckResult {
+            command: command.clone(),
+            outcome,
+            duration,
+            output,
+        });
+    }
+    Ok(results)
+}
+
+/// The policy checks run under: only the copy is writable, and there is no
+/// network access.
+fn check_sandbox_policy(copy: &Path) -> Result<SandboxPolicy> {
+    let copy = AbsolutePathBuf::try_from(copy).context("the preview copy has no absolute path")?;
+    Ok(SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![copy],
+        network_access: false,
+        exclude_tmpdir_env_var: true,
+        exclude_slash_tmp: true,
+    })
+}
+
+/// Write the changes of `action`, which are against `root`, into `copy`.
+fn write_changes(action: &ApplyPatchAction, root: &Path, copy: &Path) -> Result<()> {
+    let in_copy = |path: &Path| -> Result<PathBuf> {
+        let relative = path
+            .strip_prefix(root)
+            .with_context(|| format!("{} is outside the repository", path.display()))?;
+        Ok(copy.join(relative))
+    };
+    for (path, change) in action.changes() {
+        let target = in_copy(path)?;
+        match change {
+            ApplyPatchFileChange::Add { content } => write_file(&target, content)?,
+            ApplyPatchFileChange::Delete { .. } => std::fs::remove_file(&target)
+                .with_context(|| format!("failed to delete {}", target.display()))?,
+            ApplyPatchFileChange::Update {
+                move_path,
+                new_content,
+                ..
+            } => match move_path {
+                Some(move_path) => {
+                    write_file(&in_copy(move_path)?, new_content)?;
+                    std::fs::remove_file(&target)
+                        .with_context(|| format!("failed to move {}", target.display()))?;
+                }
+                None => write_file(&target, new_content)?,
+            },
+        }
+    }
+    Ok(())
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create {}", parent.display()))?;
+    }
+    std::fs::write(path, content).with_context(|| format!("failed to write {}", path.display()))
+}
+
+async fn run_git(cwd: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .await
+        .context("failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+fn output_tail(output: &str) -> String {
+    let lines: Vec<&str> = output.trim_end().lines().collect();
+    lines[lines.len().saturating_sub(OUTPUT_TAIL_LINES)..].join("\n")
+}
+
+fn format_results(results: &[CheckResult]) -> String {
+    let mut summary = "Checks in a preview copy with the patch applied:".to_string();
+    for result in results {
+        let seconds = result.duration.as_secs_f32();
+        let status = match result.outcome {
+            CheckOutcome::Passed => format!("passed in {seconds:.1}s"),
+            CheckOutcome::Failed(Some(code)) => format!("failed with exit code {code}"),
+            CheckOutcome::Failed(None) => "failed".to_string(),
+            CheckOutcome::TimedOut => format!("timed out after {seconds:.0}s"),
+        };
+        summary.push_str(&format!("\n- `{}`: {status}", result.command));
+        if !result.output.is_empty() {
+            summary.push_str(&format!("\n{}", result.output));
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn write_changes_maps_paths_into_the_copy() {
+        let root = PathBuf::from("/repo");
+        let copy = tempdir().expect("tmp");
+        let action =
+            ApplyPatchAction::new_add_for_test(&root.join("src/new.rs"), "fn f() {}\n".to_string());
+
+        write_changes(&action, &root, copy.path()).expect("write");
+
+        assert_eq!(
+            std::fs::read_to_string(copy.path().join("src/new.rs")).expect("read"),
+            "fn f() {}\n"
+        );
+        let outside =
+            ApplyPatchAction::new_add_for_test(Path::new("/elsewhere/a.txt"), String::new());
+        assert!(write_changes(&outside, &root, copy.path()).is_err());
+    }
+
+    #[test]
+    fn checks_may_only_write_to_the_copy() {
+        let copy = tempdir().expect("tmp");
+
+        let policy = check_sandbox_policy(copy.path()).expect("policy");
+
+        assert_eq!(
+            policy,
+            SandboxPolicy::WorkspaceWrite {
+                writable_roots: vec![AbsolutePathBuf::try_from(copy.path()).expect("absolute")],
+                network_access: false,
+                exclude_tmpdir_env_var: true,
+                exclude_slash_tmp: true,
+            }
+        );
+    }
+
+    #[test]
+    fn format_results_shows_output_of_failed_checks() {
+        let results = vec![
+            CheckResult {
+                command: "cargo check".to_string(),
+                outcome: CheckOutcome::Passed,
+                duration: Duration::from_millis(2_340),
+                output: String::new(),
+            },
+            CheckResult {
+                command: "cargo test".to_string(),
+                outcome: CheckOutcome::Failed(Some(101)),
+                duration: Duration::from_secs(9),
+                output: "test lib::it_works ... FAILED".to_string(),
+            },
+        ];
+
+        assert_eq!(
+            format_results(&results),
+            "Checks in a preview copy with the patch applied:\n- `cargo check`: passed in 2.3s\n- `cargo test`: failed with exit code 101\ntest lib::it_works ... FAILED"
+        );
+    }
+
+    #[test]
+    fn output_tail_keeps_the_last_lines() {
+        let output: String = (1..=30).map(|n| format!("line {n}\n")).collect();
+
+        let tail = output_tail(&output);
+
+        assert_eq!(tail.lines().count(), OUTPUT_TAIL_LINES);
+        assert_eq!(tail.lines().last(), Some("line 30"));
+    }
+}
@@ -17,6 +17,13 @@ const POWERSHELL_FLAGS: &[&str] = &["-nologo", "-noprofile", "-command", "-c"];
 /// Returns (`shell`, `script`) when the first arg is a PowerShell executable and a
 /// `-Command` (or `-c`) flag is present followed by a script string.
 pub fn extract_powershell_command(command: &[String]) -> Option<(&str, &str)> {
+    let index = script_index(command)?;
+    Some((command[0].as_str(), command[index].as_str()))
+}
+
+/// Index of the script in a PowerShell invocation accepted by
+/// [`extract_powershell_command`].
+pub(crate) fn script_index(command: &[String]) -> Option<usize> {
     if command.len() < 3 {
         return None;
     }
@@ -35,14 +42,47 @@ pub fn extract_powershell_command(command: &[String]) -> Option<(&str, &str)> {
             return None;
         }
         if flag.eq_ignore_ascii_case("-Command") || flag.eq_ignore_ascii_case("-c") {
-            let script = &command[i + 1];
-            return Some((shell, script.as_str()));
+            return Some(i + 1);
         }
         i += 1;
     }
     None
 }
 
+/// Rewrite a `-Command` invocation to pass its script with
+/// `-EncodedCommand`, so quotes in the script survive the Windows command
+/// line, and to exit with the status of the script's last command.
+///
+/// `powershell.exe -Command` only exits with 0 or 1 and parses embedded
+/// double quotes inconsistently, which made commands that succeeded look
+/// failed and the other way round. Other commands are returned unchanged.
+#[cfg(any(windows, test))]
+pub(crate) fn encode_for_exec(command: Vec<String>) -> Vec<String> {
+    use base64::Engine;
+
+    let Some(index) = script_index(&command) else {
+        return command;
+    };
+    if index + 1 != command.len() {
+        return command;
+    }
+    let script = format!(
+        "$ProgressPreference = 'SilentlyContinue'\n{}\nif (-not $?) {{ if ($LASTEXITCODE) {{ exit $LASTEXITCODE }} exit 1 }}",
+        command[index]
+    );
+    let utf16: Vec<u8> = script.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    let mut encoded = command[..index - 1].to_vec();
+    if !encoded
+        .iter()
+        .any(|arg| arg.eq_ignore_ascii_case("-NonInteractive"))
+    {
+        encoded.push("-NonInteractive".to_string());
+    }
+    encoded.push("-EncodedCommand".to_string());
+    encoded.push(base64::engine::general_purpose::STANDARD.encode(utf16));
+    encoded
+}
+
 /// This function attempts to find a valid PowerShell executable on the system.
 /// It first tries to find pwsh.exe, and if that fails, it tries to find
 /// powershell.exe.
@@ -132,7 +172,10 @@ fn is_powershellish_executable_available(powershell_or_pwsh_exe: &std::path::Pat
 
 #[cfg(test)]
 mod tests {
+    use super::encode_for_exec;
     use super::extract_powershell_command;
+    use base64::Engine;
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn extracts_basic_powershell_command() {
@@ -180,4 +223,38 @@ mod tests {
         let (_shell, script) = extract_powershell_command(&cmd).expect("extract");
         assert_eq!(script, "Get-ChildItem | Select-String foo");
     }
+
+    #[test]
+    fn encodes_script_with_exit_code_trailer() {
+        let cmd = vec![
+            "pwsh.exe".to_string(),
+            "-NoProfile".to_string(),
+            "-Command".to_string(),
+            "git commit -m \"fix: quote\"".to_string(),
+        ];
+
+        let encoded = encode_for_exec(cmd);
+
+        assert_eq!(
+            encoded[..3],
+            ["pwsh.exe", "-NoProfile", "-NonInteractive"].map(String::from)
+        );
+        assert_eq!(encoded[3], "-EncodedCommand");
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(&encoded[4])
+            .expect("base64");
+        let utf16: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
+        let script = String::from_utf16(&utf16).expect("utf-16");
+        assert!(script.contains("\ngit commit -m \"fix: quote\"\n"));
+        assert!(script.ends_with("exit $LASTEXITCODE } exit 1 }"));
+    }
+
+    #[test]
+    fn leaves_other_commands_alone() {
+        let cmd = vec!["bash".to_string(), "-lc".to_string(), "ls".to_string()];
+        assert_eq!(encode_for_exec(cmd.clone()), cmd);
+    }
 }
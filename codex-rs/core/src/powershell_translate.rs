@@ -0,0 +1,582 @@
+//! Rewrite Unix-isms in scripts the model runs under PowerShell.
+//!
+//! Models often write POSIX shell even when the user's shell is PowerShell.
+//! Commands with a direct PowerShell equivalent (`ls -la`, `rm -rf`,
+//! `mkdir -p`, `export`, `&&` chains, `/dev/null`, ...) are rewritten. Tools
+//! that are not installed and have no one-to-one replacement (`grep`, `sed`,
+//! heredocs, ...) are rejected with a hint, so the model rewrites the command
+//! instead of reading a confusing PowerShell error.
+
+use crate::powershell::script_index;
+
+/// Scan state shared by the splitters below.
+#[derive(Default)]
+struct Scanner {
+    in_single: bool,
+    in_double: bool,
+    depth: usize,
+    escaped: bool,
+}
+
+impl Scanner {
+    /// Advance over `c` and report whether it is outside any quotes, braces
+    /// or parentheses.
+    fn top_level(&mut self, c: char) -> bool {
+        if self.escaped {
+            self.escaped = false;
+            return false;
+        }
+        match c {
+            '`' if !self.in_single => {
+                self.escaped = true;
+                false
+            }
+            '\'' if !self.in_double => {
+                self.in_single = !self.in_single;
+                false
+            }
+            '"' if !self.in_single => {
+                self.in_double = !self.in_double;
+                false
+            }
+            _ if self.in_single || self.in_double => false,
+            '(' | '{' | '[' => {
+                self.depth += 1;
+                false
+            }
+            ')' | '}' | ']' => {
+                self.depth = self.depth.saturating_sub(1);
+                false
+            }
+            _ => self.depth == 0,
+        }
+    }
+}
+
+/// One problem that stops a script from running under PowerShell.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Problem {
+    what: String,
+    hint: &'static str,
+}
+
+/// Rewrite the script of a PowerShell invocation in place, treating programs
+/// on `PATH` as installed. Other commands are left alone.
+pub(crate) fn translate_powershell_command(command: &mut [String]) -> Result<(), String> {
+    let Some(index) = script_index(command) else {
+        return Ok(());
+    };
+    command[index] = translate_script(&command[index], |name| which::which(name).is_ok())?;
+    Ok(())
+}
+
+/// Rewrite `script` for PowerShell. `installed` reports whether a program is
+/// on `PATH`; installed tools run as they are. Returns the script unchanged
+/// when nothing needed rewriting, and the feedback for the model when some
+/// part of it cannot be rewritten.
+pub(crate) fn translate_script(
+    script: &str,
+    installed: impl Fn(&str) -> bool,
+) -> Result<String, String> {
+    let mut problems = Vec::new();
+    if has_heredoc(script) {
+        problems.push(Problem {
+            what: "heredoc (`<<`)".to_string(),
+            hint: "use a here-string, e.g. `@'\n...\n'@ | Set-Content <file>`",
+        });
+    }
+
+    let mut changed = false;
+    let mut output = String::new();
+    for (statement, separator) in split(script, &[";", "\n"]) {
+        let translated = translate_statement(&statement, &installed, &mut problems);
+        if let Some(translated) = translated {
+            changed = true;
+            output.push_str(&translated);
+        } else {
+            output.push_str(&statement);
+        }
+        output.push_str(separator);
+    }
+
+    if !problems.is_empty() {
+        return Err(feedback(&problems));
+    }
+    Ok(if changed { output } else { script.to_string() })
+}
+
+/// Translate a statement of `&&` / `||` chained pipelines, or `None` when
+/// it runs under PowerShell as it is.
+fn translate_statement(
+    statement: &str,
+    installed: &impl Fn(&str) -> bool,
+    problems: &mut Vec<Problem>,
+) -> Option<String> {
+    let indent = &statement[..statement.len() - statement.trim_start().len()];
+    let chain = split(statement, &["&&", "||"]);
+    let mut changed = chain.len() > 1;
+    let mut elements = Vec::with_capacity(chain.len());
+    for (element, _) in &chain {
+        let pipeline = split(element, &["|"]);
+        let mut commands = Vec::with_capacity(pipeline.len());
+        for (index, (command, _)) in pipeline.iter().enumerate() {
+            match translate_command(command.trim(), index > 0, installed) {
+                Ok(Some(translated)) => {
+                    changed = true;
+                    commands.push(translated);
+                }
+                Ok(None) => commands.push(command.trim().to_string()),
+                Err(problem) => {
+                    problems.push(problem);
+                    commands.push(command.trim().to_string());
+                }
+            }
+        }
+        elements.push(commands.join(" | "));
+    }
+    if !changed {
+        return None;
+    }
+
+    let operators: Vec<&str> = chain
+        .iter()
+        .map(|(_, separator)| *separator)
+        .filter(|separator| !separator.is_empty())
+        .collect();
+    let condition = match operators.first() {
+        None => return elements.pop().map(|element| format!("{indent}{element}")),
+        Some(first) if operators.iter().any(|operator| operator != first) => {
+            problems.push(Problem {
+                what: "a chain mixing `&&` and `||`".to_string(),
+                hint: "split it into `if ($?) { ... } else { ... }` blocks",
+            });
+            return None;
+        }
+        Some(&"&&") => "$?",
+        Some(_) => "-not $?",
+    };
+    // `&&` and `||` need PowerShell 7; nested `if` blocks also run under
+    // Windows PowerShell 5.1.
+    let mut rest = elements.split_off(1).into_iter().rev();
+    let mut translated = rest.next()?;
+    for element in rest {
+        translated = format!("{element}; if ({condition}) {{ {translated} }}");
+    }
+    let first = elements.pop()?;
+    Some(format!(
+        "{indent}{first}; if ({condition}) {{ {translated} }}"
+    ))
+}
+
+/// Translate one command of a pipeline. `piped` is true when it reads the
+/// output of the previous command.
+fn translate_command(
+    command: &str,
+    piped: bool,
+    installed: &impl Fn(&str) -> bool,
+) -> Result<Option<String>, Problem> {
+    let original = words(command);
+    let mut words = original.clone();
+    redirect_dev_null(&mut words);
+    let Some((program, args)) = words.split_first() else {
+        return Ok(None);
+    };
+    let (flags, operands): (Vec<&String>, Vec<&String>) = args
+        .iter()
+        .partition(|arg| arg.starts_with('-') && arg.len() > 1);
+    let letters: String = flags
+        .iter()
+        .filter(|flag| !flag.starts_with("--"))
+        .flat_map(|flag| flag.chars().skip(1))
+        .collect();
+    let operands: Vec<&str> = operands.into_iter().map(String::as_str).collect();
+    let missing = |name: &str| !installed(name);
+
+    let translated = match program.as_str() {
+        "ls" | "ll" if !flags.is_empty() && missing(program.as_str()) => {
+            if letters.chars().any(|c| !"laAh1R".contains(c)) {
+                return Err(Problem {
+                    what: format!("`{command}`"),
+                    hint: "use `Get-ChildItem` with `-Force`, `-Recurse` or `| Sort-Object`",
+                });
+            }
+            let mut parts = vec!["Get-ChildItem".to_string()];
+            if letters.contains(['a', 'A']) {
+                parts.push("-Force".to_string());
+            }
+            if letters.contains('R') {
+                parts.push("-Recurse".to_string());
+            }
+            parts.extend(path_list(&operands));
+            parts.join(" ")
+        }
+        "rm" if !flags.is_empty() && missing("rm") => {
+            if letters.chars().any(|c| !"rRfv".contains(c)) {
+                return Err(Problem {
+                    what: format!("`{command}`"),
+                    hint: "use `Remove-Item -Recurse -Force -Path <paths>`",
+                });
+            }
+            let mut parts = vec!["Remove-Item".to_string()];
+            if letters.contains(['r', 'R']) {
+                parts.push("-Recurse".to_string());
+            }
+            if letters.contains('f') {
+                parts.push("-Force".to_string());
+                parts.push("-ErrorAction SilentlyContinue".to_string());
+            }
+            parts.extend(path_list(&operands));
+            parts.join(" ")
+        }
+        "mkdir" if missing("mkdir") && (letters.contains('p') || operands.len() > 1) => {
+            let mut parts = vec!["New-Item -ItemType Directory -Force".to_string()];
+            parts.extend(path_list(&operands));
+            format!("{} | Out-Null", parts.join(" "))
+        }
+        "cp" if missing("cp") && letters.contains(['r', 'R']) && operands.len() == 2 => {
+            format!("Copy-Item -Recurse -Force {} {}", operands[0], operands[1])
+        }
+        "export" => {
+            let mut assignments = Vec::with_capacity(operands.len());
+            for operand in &operands {
+                let Some((name, value)) = operand.split_once('=') else {
+                    continue;
+                };
+                assignments.push(format!("$env:{name} = {}", quote(value)));
+            }
+            if assignments.is_empty() {
+                return Ok(None);
+            }
+            assignments.join("; ")
+        }
+        "which" if missing("which") && operands.len() == 1 => {
+            format!("(Get-Command {}).Source", operands[0])
+        }
+        "touch" if missing("touch") && operands.len() == 1 && flags.is_empty() => {
+            let path = operands[0];
+            format!(
+                "if (Test-Path {path}) {{ (Get-Item {path}).LastWriteTime = Get-Date }} else {{ New-Item -ItemType File -Path {path} | Out-Null }}"
+            )
+        }
+        "head" | "tail" if missing(program.as_str()) => {
+            let Some(count) = line_count(args) else {
+                return Err(Problem {
+                    what: format!("`{command}`"),
+                    hint: "use `Get-Content <file> -TotalCount <n>` or `-Tail <n>`",
+                });
+            };
+            let files: Vec<&str> = operands
+                .iter()
+                .copied()
+                .filter(|operand| operand.parse::<usize>().is_err())
+                .collect();
+            match (program.as_str(), piped, files.as_slice()) {
+                ("head", true, []) => format!("Select-Object -First {count}"),
+                ("tail", true, []) => format!("Select-Object -Last {count}"),
+                ("head", false, [file]) => format!("Get-Content {file} -TotalCount {count}"),
+                ("tail", false, [file]) => format!("Get-Content {file} -Tail {count}"),
+                _ => {
+                    return Err(Problem {
+                        what: format!("`{command}`"),
+                        hint: "use `Get-Content <file> -TotalCount <n>` or `-Tail <n>`",
+                    });
+                }
+            }
+        }
+        "find" if args.iter().any(|arg| is_unix_find_test(arg)) => {
+            return Err(Problem {
+                what: format!("`{command}`"),
+                hint: "Windows `find` searches text; use `Get-ChildItem -Recurse -Filter <pattern>`",
+            });
+        }
+        "sudo" => {
+            return Err(Problem {
+                what: "`sudo`".to_string(),
+                hint: "Windows has no `sudo`; run the command without it",
+            });
+        }
+        name if missing(name) => match missing_tool_hint(name) {
+            Some(hint) => {
+                return Err(Problem {
+                    what: format!("`{name}`, which is not installed"),
+                    hint,
+                });
+            }
+            None if words == original => return Ok(None),
+            None => words.join(" "),
+        },
+        _ if words == original => return Ok(None),
+        _ => words.join(" "),
+    };
+    Ok(Some(translated))
+}
+
+/// The PowerShell replacement for a Unix tool that is not installed.
+fn missing_tool_hint(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "grep" | "egrep" | "fgrep" => {
+            "use `Select-String -Pattern <pattern> -Path <files>`, or `rg` if it is installed"
+        }
+        "sed" => "use `(Get-Content <file>) -replace '<old>', '<new>' | Set-Content <file>`",
+        "awk" => {
+            "use `ForEach-Object` with `-split`, e.g. `ForEach-Object { ($_ -split '\\s+')[0] }`"
+        }
+        "xargs" => "pipe into `ForEach-Object { <command> $_ }`",
+        "wc" => "use `Measure-Object -Line -Word -Character`",
+        "cut" => "use `ForEach-Object { ($_ -split '<delimiter>')[<index>] }`",
+        "chmod" | "chown" => "Windows has no Unix permissions; use `icacls` if access must change",
+        "env" | "printenv" => "use `Get-ChildItem env:`",
+        _ => return None,
+    })
+}
+
+fn is_unix_find_test(arg: &str) -> bool {
+    matches!(
+        arg,
+        "-name" | "-iname" | "-type" | "-path" | "-maxdepth" | "-mindepth" | "-newer" | "-exec"
+    )
+}
+
+/// The line count of `head -n 5`, `head -5` or `head` (10).
+fn line_count(args: &[String]) -> Option<usize> {
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        if arg == "-n" {
+            return args.next()?.parse().ok();
+        }
+        if let Some(count) = arg.strip_prefix("-n") {
+            return count.parse().ok();
+        }
+        if let Some(count) = arg.strip_prefix('-') {
+            return count.parse().ok();
+        }
+    }
+    Some(10)
+}
+
+fn path_list(operands: &[&str]) -> Option<String> {
+    (!operands.is_empty()).then(|| format!("-Path {}", operands.join(", ")))
+}
+
+/// Quote a value for PowerShell unless the model already did. Values that
+/// expand variables (`$PATH`, `${HOME}`) are double-quoted with the variables
+/// read from the environment, as the POSIX shell would have done.
+fn quote(value: &str) -> String {
+    if value.starts_with(['\'', '"']) {
+        return value.to_string();
+    }
+    if !value.contains('$') {
+        return format!("'{}'", value.replace('\'', "''"));
+    }
+    let mut quoted = String::from("\"");
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '$' => {
+                let braced = chars.next_if_eq(&'{').is_some();
+                let mut name = String::new();
+                while let Some(c) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_') {
+                    name.push(c);
+                }
+                if name.is_empty() {
+                    quoted.push_str(if braced { "`${" } else { "`$" });
+                    continue;
+                }
+                if braced {
+                    chars.next_if_eq(&'}');
+                }
+                quoted.push_str(&format!("${{env:{name}}}"));
+            }
+            '"' | '`' => {
+                quoted.push('`');
+                quoted.push(c);
+            }
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Replace `/dev/null` redirections with `$null`.
+fn redirect_dev_null(words: &mut Vec<String>) {
+    let mut index = 0;
+    while index < words.len() {
+        let word = &words[index];
+        let redirect = word.strip_suffix("/dev/null").unwrap_or(word);
+        let is_redirect = matches!(redirect, ">" | ">>" | "1>" | "2>" | "&>");
+        if is_redirect && word.ends_with("/dev/null") {
+            words[index] = format!("{}$null", redirect.replace('&', "*"));
+        } else if is_redirect && words.get(index + 1).is_some_and(|next| next == "/dev/null") {
+            words[index] = format!("{}$null", redirect.replace('&', "*"));
+            words.remove(index + 1);
+        }
+        index += 1;
+    }
+}
+
+fn has_heredoc(script: &str) -> bool {
+    let mut scanner = Scanner::default();
+    let mut previous = None;
+    for c in script.chars() {
+        let top_level = scanner.top_level(c);
+        if top_level && c == '<' && previous == Some('<') {
+            return true;
+        }
+        previous = top_level.then_some(c);
+    }
+    false
+}
+
+/// Split `text` at top-level occurrences of `separators`. Each piece comes
+/// with the separator that ended it, or `""` for the last one.
+fn split(text: &str, separators: &[&'static str]) -> Vec<(String, &'static str)> {
+    let mut pieces = Vec::new();
+    let mut scanner = Scanner::default();
+    let mut start = 0;
+    let mut skip_until = 0;
+    for (index, c) in text.char_indices() {
+        if index < skip_until {
+            scanner.top_level(c);
+            continue;
+        }
+        if !scanner.top_level(c) {
+            continue;
+        }
+        let rest = &text[index..];
+        let Some(separator) = separators.iter().find(|separator| {
+            rest.starts_with(**separator)
+                // `|` must not match the first half of `||`.
+                && !(**separator == "|" && rest.starts_with("||"))
+        }) else {
+            continue;
+        };
+        pieces.push((text[start..index].to_string(), *separator));
+        start = index + separator.len();
+        skip_until = start;
+    }
+    pieces.push((text[start..].to_string(), ""));
+    pieces
+}
+
+/// Split a command into words, keeping quotes.
+fn words(command: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut scanner = Scanner::default();
+    let mut current = String::new();
+    for c in command.chars() {
+        if scanner.top_level(c) && c.is_whitespace() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        } else {
+            current.push(c);
+        }
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn feedback(problems: &[Problem]) -> String {
+    let mut message =
+        "The command was not run: the user's shell is PowerShell, which does not understand:"
+            .to_string();
+    for problem in problems {
+        message.push_str(&format!("\n- {}: {}", problem.what, problem.hint));
+    }
+    message.push_str("\nRewrite the command in PowerShell and run it again.");
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn translate(script: &str) -> Result<String, String> {
+        translate_script(script, |name| name == "git" || name == "rg")
+    }
+
+    #[test]
+    fn rewrites_commands_with_powershell_equivalents() {
+        assert_eq!(
+            translate("ls -la src"),
+            Ok("Get-ChildItem -Force -Path src".to_string())
+        );
+        assert_eq!(
+            translate("rm -rf target build"),
+            Ok(
+                "Remove-Item -Recurse -Force -ErrorAction SilentlyContinue -Path target, build"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            translate("mkdir -p a/b"),
+            Ok("New-Item -ItemType Directory -Force -Path a/b | Out-Null".to_string())
+        );
+        assert_eq!(
+            translate("export RUST_LOG=debug"),
+            Ok("$env:RUST_LOG = 'debug'".to_string())
+        );
+        assert_eq!(
+            translate("export PATH=$PATH:${HOME}/bin"),
+            Ok("$env:PATH = \"${env:PATH}:${env:HOME}/bin\"".to_string())
+        );
+        assert_eq!(
+            translate("git log --oneline | head -n 5"),
+            Ok("git log --oneline | Select-Object -First 5".to_string())
+        );
+        assert_eq!(
+            translate("git status 2>/dev/null"),
+            Ok("git status 2>$null".to_string())
+        );
+    }
+
+    #[test]
+    fn rewrites_and_chains_as_nested_ifs() {
+        assert_eq!(
+            translate("cd app && git pull && ls -a"),
+            Ok("cd app; if ($?) { git pull; if ($?) { Get-ChildItem -Force } }".to_string())
+        );
+        assert_eq!(
+            translate("git fetch || exit 1"),
+            Ok("git fetch; if (-not $?) { exit 1 }".to_string())
+        );
+    }
+
+    #[test]
+    fn leaves_powershell_alone() {
+        for script in [
+            "Get-ChildItem -Recurse | Where-Object { $_.Length -gt 1kb; $_ }",
+            "rg -n \"a && b\" src",
+            "git commit -m 'ls -la | head'",
+            "ls",
+        ] {
+            assert_eq!(translate(script), Ok(script.to_string()));
+        }
+    }
+
+    #[test]
+    fn installed_unix_tools_run_as_they_are() {
+        let installed = |name: &str| matches!(name, "ls" | "rm" | "mkdir");
+        for script in ["ls -la src", "rm -rf target", "mkdir -p a/b"] {
+            assert_eq!(translate_script(script, installed), Ok(script.to_string()));
+        }
+    }
+
+    #[test]
+    fn rejects_missing_tools_with_hints() {
+        let err = translate("cat log.txt | grep error && sed -i s/a/b/ f").expect_err("rejected");
+        assert_eq!(
+            err,
+            "The command was not run: the user's shell is PowerShell, which does not understand:\n\
+             - `grep`, which is not installed: use `Select-String -Pattern <pattern> -Path <files>`, or `rg` if it is installed\n\
+             - `sed`, which is not installed: use `(Get-Content <file>) -replace '<old>', '<new>' | Set-Content <file>`\n\
+             Rewrite the command in PowerShell and run it again."
+        );
+        assert!(translate("cat <<EOF > f.txt\nhi\nEOF").is_err());
+        assert!(translate("make && make install || echo failed").is_err());
+    }
+}
@@ -12,12 +12,17 @@
 //!     current working directory (inclusive) and concatenate their contents in
 //!     that order.
 //! 3.  We do **not** walk past the Git root.
+//!
+//! The same discovery runs for every additional workspace root, starting at
+//! that root, and the results follow those of the working directory.
 
 use crate::config::Config;
+use crate::context_ignore::ContextIgnore;
 use crate::memories;
 use crate::skills::SkillMetadata;
 use crate::skills::render_skills_section;
 use dunce::canonicalize as normalize_path;
+use std::path::Path;
 use std::path::PathBuf;
 use tokio::io::AsyncReadExt;
 use tracing::error;
@@ -31,9 +36,26 @@ pub const LOCAL_PROJECT_DOC_FILENAME: &str = "AGENTS.override.md";
 /// be concatenated with the following separator.
 const PROJECT_DOC_SEPARATOR: &str = "\n\n--- project-doc ---\n\n";
 
+/// Combines `Config::instructions`, `AGENTS.md` (if present), and saved
+/// memories into a single string of instructions.
+pub(crate) async fn get_user_instructions(
+    config: &Config,
+    skills: Option<&[SkillMetadata]>,
+) -> Option<String> {
+    let project_instructions = get_project_instructions(config, skills).await;
+    let memories = memories::read_memories_for_instructions(config).await;
+    match (project_instructions, memories) {
+        (Some(instructions), Some(memories)) => Some(format!(
+            "{instructions}{}{memories}",
+            memories::MEMORIES_SEPARATOR
+        )),
+        (instructions, memories) => instructions.or(memories),
+    }
+}
+
 /// Combines `Config::instructions` and `AGENTS.md` (if present) into a single
 /// string of instructions.
-pub(crate) async fn get_user_instructions(
+pub(crate) async fn get_project_instructions(
     config: &Config,
     skills: Option<&[SkillMetadata]>,
 ) -> Option<String> {
@@ -48,7 +70,6 @@ pub(crate) async fn get_user_instructions(
     };
 
     let combined_project_docs = merge_project_docs_with_skills(project_docs, skills_section);
-    let memories = memories::read_memories_for_instructions(config).await;
 
     let mut parts: Vec<String> = Vec::new();
 
@@ -63,13 +84,6 @@ pub(crate) async fn get_user_instructions(
         parts.push(project_doc);
     }
 
-    if let Some(memories) = memories {
-        if !parts.is_empty() {
-            parts.push(memories::MEMORIES_SEPARATOR.to_string());
-        }
-        parts.push(memories);
-    }
-
     if parts.is_empty() {
         None
     } else {
@@ -139,13 +153,30 @@ pub async fn read_project_docs(config: &Config) -> std::io::Result<Option<String
 /// Discover the list of AGENTS.md files using the same search rules as
 /// `read_project_docs`, but return the file paths instead of concatenated
 /// contents. The list is ordered from repository root to the current working
-/// directory (inclusive). Symlinks are allowed. When `project_doc_max_bytes`
-/// is zero, returns an empty list.
+/// directory (inclusive), followed by the docs of each additional workspace
+/// root. Symlinks are allowed. When `project_doc_max_bytes` is zero, returns
+/// an empty list.
 pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBuf>> {
-    let mut dir = config.cwd.clone();
+    let mut found: Vec<PathBuf> = Vec::new();
+    for root in config.workspace_roots() {
+        for path in discover_project_doc_paths_for_root(config, root)? {
+            if !found.contains(&path) {
+                found.push(path);
+            }
+        }
+    }
+    Ok(found)
+}
+
+fn discover_project_doc_paths_for_root(
+    config: &Config,
+    root: &Path,
+) -> std::io::Result<Vec<PathBuf>> {
+    let mut dir = root.to_path_buf();
     if let Ok(canon) = normalize_path(&dir) {
         dir = canon;
     }
+    let context_ignore = ContextIgnore::new(&dir, &config.context_ignore);
 
     // Build chain from cwd upwards and detect git root.
     let mut chain: Vec<PathBuf> = vec![dir.clone()];
@@ -183,7 +214,7 @@ pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBu
         }
         dirs
     } else {
-        vec![config.cwd.clone()]
+        vec![root.to_path_buf()]
     };
 
     let mut found: Vec<PathBuf> = Vec::new();
@@ -191,6 +222,9 @@ pub fn discover_project_doc_paths(config: &Config) -> std::io::Result<Vec<PathBu
     for d in search_dirs {
         for name in &candidate_filenames {
             let candidate = d.join(name);
+            if context_ignore.is_ignored(&candidate, false) {
+                continue;
+            }
             match std::fs::symlink_metadata(&candidate) {
                 Ok(md) => {
                     let ft = md.file_type();
@@ -434,6 +468,31 @@ mod tests {
         assert_eq!(res, "root doc\n\ncrate doc");
     }
 
+    /// Docs matched by `.codexignore` are never read into context.
+    #[tokio::test]
+    async fn skips_docs_excluded_by_codexignore() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        std::fs::write(
+            repo.path().join(".git"),
+            "gitdir: /path/to/actual/git/dir\n",
+        )
+        .unwrap();
+        fs::write(repo.path().join("AGENTS.md"), "root doc").unwrap();
+        fs::write(repo.path().join(".codexignore"), "vendor/\n").unwrap();
+
+        let nested = repo.path().join("vendor/dep");
+        std::fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("AGENTS.md"), "vendored doc").unwrap();
+
+        let mut cfg = make_config(&repo, 4096, None).await;
+        cfg.cwd = nested;
+
+        let res = get_user_instructions(&cfg, None)
+            .await
+            .expect("doc expected");
+        assert_eq!(res, "root doc");
+    }
+
     /// AGENTS.override.md is preferred over AGENTS.md when both are present.
     #[tokio::test]
     async fn agents_local_md_preferred() {
@@ -597,6 +656,38 @@ mod tests {
         assert_eq!(res, expected);
     }
 
+    #[tokio::test]
+    async fn reads_docs_and_memories_from_every_workspace_root() {
+        let frontend = tempfile::tempdir().expect("tempdir");
+        let backend = tempfile::tempdir().expect("tempdir");
+        for (repo, doc, memory) in [
+            (&frontend, "frontend doc", "- Use pnpm\n"),
+            (&backend, "backend doc", "- Use cargo nextest\n"),
+        ] {
+            fs::create_dir(repo.path().join(".git")).unwrap();
+            fs::write(repo.path().join("AGENTS.md"), doc).unwrap();
+            fs::create_dir(repo.path().join(memories::MEMORIES_DIRNAME)).unwrap();
+            fs::write(
+                repo.path()
+                    .join(memories::MEMORIES_DIRNAME)
+                    .join(memories::MEMORIES_FILENAME),
+                memory,
+            )
+            .unwrap();
+        }
+        let mut cfg = make_config(&frontend, 4096, None).await;
+        cfg.additional_workspace_roots = vec![backend.path().to_path_buf()];
+
+        let res = get_user_instructions(&cfg, None)
+            .await
+            .expect("instructions expected");
+        let expected = format!(
+            "frontend doc\n\nbackend doc{}{MEMORIES_HEADER}\n- Use pnpm\n- Use cargo nextest",
+            memories::MEMORIES_SEPARATOR
+        );
+        assert_eq!(res, expected);
+    }
+
     fn create_skill(codex_home: PathBuf, name: &str, description: &str) {
         let skill_dir = codex_home.join(format!("skills/{name}"));
         fs::create_dir_all(&skill_dir).unwrap();
@@ -0,0 +1,204 @@
+//! `.codex/instructions.md` at the repository root: instructions a project
+//! appends to the model's base instructions, or replaces them with when the
+//! file starts with [`REPLACE_BASE_INSTRUCTIONS_MARKER`].
+//!
+//! Only a trusted project may replace the base instructions. A file that
+//! cannot be used is skipped with a warning rather than failing the session.
+
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::git_info::get_git_repo_root;
+
+pub(crate) const PROJECT_INSTRUCTIONS_DIRNAME: &str = ".codex";
+pub(crate) const PROJECT_INSTRUCTIONS_FILENAME: &str = "instructions.md";
+/// First line of a file that replaces the base instructions.
+pub const REPLACE_BASE_INSTRUCTIONS_MARKER: &str = "<!-- codex: replace base instructions -->";
+/// Default for `project_instructions_max_bytes`.
+pub const PROJECT_INSTRUCTIONS_MAX_BYTES: usize = 32 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectInstructionsMode {
+    /// Added after the base instructions, like `[[model_instructions]]`.
+    Append,
+    /// Sent instead of the model's base instructions.
+    Replace,
+}
+
+/// The project's instructions as they apply to the session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectInstructions {
+    pub path: PathBuf,
+    pub mode: ProjectInstructionsMode,
+    /// The file's contents without the marker, trimmed.
+    pub text: String,
+}
+
+/// The project instructions that apply to a session in `cwd`. `trusted` is
+/// whether the user trusts the project, and `has_base_instructions` whether
+/// they set base instructions of their own, which win over a replacing file.
+pub fn resolve_project_instructions(
+    cwd: &Path,
+    max_bytes: usize,
+    trusted: bool,
+    has_base_instructions: bool,
+) -> Option<ProjectInstructions> {
+    let project = match load_project_instructions(cwd, max_bytes) {
+        Ok(project) => project?,
+        Err(err) => {
+            tracing::warn!("ignoring project instructions: {err}");
+            return None;
+        }
+    };
+    match project.mode {
+        ProjectInstructionsMode::Append => Some(project),
+        ProjectInstructionsMode::Replace if !trusted => {
+            tracing::warn!(
+                "ignoring {}: only a trusted project may replace the base instructions",
+                project.path.display()
+            );
+            None
+        }
+        ProjectInstructionsMode::Replace => (!has_base_instructions).then_some(project),
+    }
+}
+
+/// Read `.codex/instructions.md` of the repository holding `cwd`, or of
+/// `cwd` outside a repository. Returns `None` when there is no such file,
+/// it is empty, or `max_bytes` is 0; a file over `max_bytes` is an error.
+pub fn load_project_instructions(
+    cwd: &Path,
+    max_bytes: usize,
+) -> std::io::Result<Option<ProjectInstructions>> {
+    if max_bytes == 0 {
+        return Ok(None);
+    }
+    let root = get_git_repo_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+    let path = root
+        .join(PROJECT_INSTRUCTIONS_DIRNAME)
+        .join(PROJECT_INSTRUCTIONS_FILENAME);
+    let contents = match std::fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(std::io::Error::new(
+                err.kind(),
+                format!(
+                    "failed to read project instructions {}: {err}",
+                    path.display()
+                ),
+            ));
+        }
+    };
+    if contents.len() > max_bytes {
+        return Err(std::io::Error::new(
+            ErrorKind::InvalidData,
+            format!(
+                "project instructions {} are {} bytes, over the limit of {max_bytes}; shorten the file or raise `project_instructions_max_bytes`",
+                path.display(),
+                contents.len()
+            ),
+        ));
+    }
+    Ok(parse_project_instructions(path, &contents))
+}
+
+fn parse_project_instructions(path: PathBuf, contents: &str) -> Option<ProjectInstructions> {
+    let contents = contents.trim();
+    let (mode, text) = match contents.strip_prefix(REPLACE_BASE_INSTRUCTIONS_MARKER) {
+        Some(rest) => (ProjectInstructionsMode::Replace, rest.trim()),
+        None => (ProjectInstructionsMode::Append, contents),
+    };
+    (!text.is_empty()).then(|| ProjectInstructions {
+        path,
+        mode,
+        text: text.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn marker_on_the_first_line_replaces_base_instructions() {
+        let path = PathBuf::from("/repo/.codex/instructions.md");
+
+        assert_eq!(
+            parse_project_instructions(path.clone(), "\nUse tabs.\n"),
+            Some(ProjectInstructions {
+                path: path.clone(),
+                mode: ProjectInstructionsMode::Append,
+                text: "Use tabs.".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_project_instructions(
+                path.clone(),
+                &format!("{REPLACE_BASE_INSTRUCTIONS_MARKER}\nYou are a build bot.\n")
+            ),
+            Some(ProjectInstructions {
+                path: path.clone(),
+                mode: ProjectInstructionsMode::Replace,
+                text: "You are a build bot.".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_project_instructions(path, REPLACE_BASE_INSTRUCTIONS_MARKER),
+            None
+        );
+    }
+
+    #[test]
+    fn oversized_files_are_rejected() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let codex_dir = dir.path().join(PROJECT_INSTRUCTIONS_DIRNAME);
+        std::fs::create_dir(&codex_dir)?;
+        std::fs::write(
+            codex_dir.join(PROJECT_INSTRUCTIONS_FILENAME),
+            "x".repeat(64),
+        )?;
+
+        assert!(load_project_instructions(dir.path(), 32).is_err());
+        assert_eq!(load_project_instructions(dir.path(), 0)?, None);
+        assert_eq!(
+            load_project_instructions(dir.path(), 64)?.map(|project| project.mode),
+            Some(ProjectInstructionsMode::Append)
+        );
+        assert_eq!(
+            resolve_project_instructions(dir.path(), 32, true, false),
+            None
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn only_trusted_projects_replace_base_instructions() -> std::io::Result<()> {
+        let dir = tempfile::tempdir()?;
+        let codex_dir = dir.path().join(PROJECT_INSTRUCTIONS_DIRNAME);
+        std::fs::create_dir(&codex_dir)?;
+        std::fs::write(
+            codex_dir.join(PROJECT_INSTRUCTIONS_FILENAME),
+            format!("{REPLACE_BASE_INSTRUCTIONS_MARKER}\nYou are a build bot.\n"),
+        )?;
+        let mode = |trusted, has_base_instructions| {
+            resolve_project_instructions(
+                dir.path(),
+                PROJECT_INSTRUCTIONS_MAX_BYTES,
+                trusted,
+                has_base_instructions,
+            )
+            .map(|project| project.mode)
+        };
+
+        assert_eq!(mode(false, false), None);
+        assert_eq!(mode(true, true), None);
+        assert_eq!(mode(true, false), Some(ProjectInstructionsMode::Replace));
+
+        std::fs::write(codex_dir.join(PROJECT_INSTRUCTIONS_FILENAME), "Use tabs.\n")?;
+        assert_eq!(mode(false, false), Some(ProjectInstructionsMode::Append));
+        Ok(())
+    }
+}
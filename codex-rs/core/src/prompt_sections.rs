@@ -0,0 +1,272 @@
+//! Compose the model instructions from the ordered sections configured under
+//! `[prompt_sections]`: the base instructions, a permissions summary, project
+//! docs, memories, the environment, and user-provided files.
+//!
+//! Without that table the default layout applies: the base instructions are
+//! the system instructions, and project docs, memories, and the environment
+//! are sent as context messages at the start of the conversation.
+
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::SandboxPolicy;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::config::PromptSection;
+use crate::environment_context::EnvironmentContext;
+use crate::features::Feature;
+use crate::memories;
+use crate::openai_models::model_family::ModelFamily;
+use crate::project_doc::get_project_instructions;
+use crate::shell::default_user_shell;
+use crate::skills::SkillMetadata;
+use crate::skills::SkillsManager;
+
+/// Sections loaded at session start, ready to be composed for each request.
+#[derive(Debug)]
+pub(crate) struct PromptSections {
+    sections: Vec<LoadedSection>,
+}
+
+#[derive(Debug)]
+enum LoadedSection {
+    Base,
+    Permissions,
+    Environment,
+    /// Text read once at session start; `None` when there was nothing to read.
+    Text {
+        name: String,
+        text: Option<String>,
+    },
+}
+
+impl PromptSections {
+    pub(crate) async fn load(
+        config: &Config,
+        sections: &[PromptSection],
+        skills: Option<&[SkillMetadata]>,
+    ) -> Self {
+        let mut loaded = Vec::with_capacity(sections.len());
+        for section in sections {
+            let name = section.name().to_string();
+            loaded.push(match section {
+                PromptSection::Base => LoadedSection::Base,
+                PromptSection::Permissions => LoadedSection::Permissions,
+                PromptSection::Environment => LoadedSection::Environment,
+                PromptSection::ProjectDocs => LoadedSection::Text {
+                    name,
+                    text: get_project_instructions(config, skills).await,
+                },
+                PromptSection::Memories => LoadedSection::Text {
+                    name,
+                    text: memories::read_memories_for_instructions(config).await,
+                },
+                PromptSection::File { path, .. } => {
+                    let text = tokio::fs::read_to_string(path)
+                        .await
+                        .inspect_err(|err| {
+                            warn!(
+                                "failed to read prompt section `{name}` from {}: {err}",
+                                path.display()
+                            );
+                        })
+                        .ok();
+                    LoadedSection::Text { name, text }
+                }
+            });
+        }
+        Self { sections: loaded }
+    }
+
+    /// Lay out the sections for one request. `environment` is the rendered
+    /// environment context for the turn.
+    pub(crate) fn compose(
+        &self,
+        approval_policy: AskForApproval,
+        sandbox_policy: &SandboxPolicy,
+        environment: &str,
+    ) -> ComposedInstructions {
+        let sections = self
+            .sections
+            .iter()
+            .map(|section| match section {
+                LoadedSection::Base => ("base".to_string(), None),
+                LoadedSection::Permissions => (
+                    "permissions".to_string(),
+                    Some(permissions_text(approval_policy, sandbox_policy)),
+                ),
+                LoadedSection::Environment => {
+                    ("environment".to_string(), Some(environment.to_string()))
+                }
+                LoadedSection::Text { name, text } => {
+                    (name.clone(), Some(text.clone().unwrap_or_default()))
+                }
+            })
+            .collect();
+        ComposedInstructions { sections }
+    }
+}
+
+/// Instructions laid out by [`PromptSections::compose`], with a slot for the
+/// base instructions, which depend on the model and tools of each request.
+#[derive(Debug, Clone)]
+pub(crate) struct ComposedInstructions {
+    /// Section name and text, in order; `None` marks the base instructions.
+    sections: Vec<(String, Option<String>)>,
+}
+
+impl ComposedInstructions {
+    /// Join the non-empty sections, in order, with `base` in its slot.
+    pub(crate) fn render(&self, base: &str) -> String {
+        self.texts(base)
+            .map(|(_, text)| text)
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    /// Like [`Self::render`], with a header naming each section.
+    fn render_annotated(&self, base: &str) -> String {
+        self.texts(base)
+            .map(|(name, text)| format!("=== {name} ===\n{text}"))
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    fn texts<'a>(&'a self, base: &'a str) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.sections
+            .iter()
+            .map(move |(name, text)| (name.as_str(), text.as_deref().unwrap_or(base)))
+            .filter(|(_, text)| !text.trim().is_empty())
+    }
+}
+
+/// Render the instructions a new session with `config` would start with,
+/// section by section, for `codex debug-prompt`.
+pub async fn describe_prompt_composition(config: &Config, model_family: &ModelFamily) -> String {
+    let skills = config
+        .features
+        .enabled(Feature::Skills)
+        .then(|| SkillsManager::new(config.codex_home.clone()).skills_for_cwd(&config.cwd));
+    let skills = skills.as_ref().map(|outcome| outcome.skills.as_slice());
+
+    let (note, sections) = match &config.prompt_sections {
+        Some(sections) => (
+            "Sections from [prompt_sections], sent as the system instructions.",
+            sections.clone(),
+        ),
+        None => (
+            "Default layout: `base` is sent as the system instructions; the other sections are sent as context messages.",
+            vec![
+                PromptSection::Base,
+                PromptSection::ProjectDocs,
+                PromptSection::Memories,
+                PromptSection::Environment,
+            ],
+        ),
+    };
+
+    let approval_policy = config.approval_policy.value();
+    let environment = EnvironmentContext::new(
+        Some(config.cwd.clone()),
+        Some(approval_policy),
+        Some(config.sandbox_policy.clone()),
+        default_user_shell(),
+    )
+    .serialize_to_xml();
+    let mut base = config
+        .base_instructions
+        .clone()
+        .unwrap_or_else(|| model_family.base_instructions.clone());
+    if let Some(overlay) = config.instructions_for_model(&model_family.slug) {
+        base = format!("{base}\n\n{overlay}");
+    }
+
+    let composed = PromptSections::load(config, &sections, skills)
+        .await
+        .compose(approval_policy, &config.sandbox_policy, &environment);
+    format!("{note}\n\n{}", composed.render_annotated(&base))
+}
+
+fn permissions_text(approval_policy: AskForApproval, sandbox_policy: &SandboxPolicy) -> String {
+    let sandbox = match sandbox_policy {
+        SandboxPolicy::DangerFullAccess => {
+            "Commands run without a sandbox and can read and write anywhere.".to_string()
+        }
+        SandboxPolicy::ReadOnly => {
+            "Commands run in a read-only sandbox: they can read files but not change them."
+                .to_string()
+        }
+        SandboxPolicy::ExternalSandbox { network_access } => {
+            format!("Commands run inside an external sandbox; network access is {network_access}.")
+        }
+        SandboxPolicy::WorkspaceWrite { network_access, .. } => format!(
+            "Commands run in a sandbox that can write only inside the workspace and its writable roots; network access is {}.",
+            if *network_access {
+                "enabled"
+            } else {
+                "restricted"
+            }
+        ),
+    };
+    let approval = match approval_policy {
+        AskForApproval::UnlessTrusted => {
+            "The user must approve every command that is not known to be safe."
+        }
+        AskForApproval::OnFailure => {
+            "Commands run in the sandbox first; if one fails there, you may ask the user to rerun it without the sandbox."
+        }
+        AskForApproval::OnRequest => {
+            "You may ask the user to approve a command that needs to run outside the sandbox."
+        }
+        AskForApproval::Never => {
+            "The user is never asked for approval; work within the sandbox and report anything you could not do."
+        }
+    };
+    format!("## Permissions\n\n{sandbox}\n{approval}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn compose_follows_configured_order_and_skips_empty_sections() {
+        let sections = PromptSections {
+            sections: vec![
+                LoadedSection::Text {
+                    name: "team".to_string(),
+                    text: Some("Use tabs.".to_string()),
+                },
+                LoadedSection::Base,
+                LoadedSection::Text {
+                    name: "memories".to_string(),
+                    text: None,
+                },
+                LoadedSection::Environment,
+                LoadedSection::Permissions,
+            ],
+        };
+
+        let composed = sections.compose(
+            AskForApproval::Never,
+            &SandboxPolicy::ReadOnly,
+            "<environment_context />",
+        );
+
+        assert_eq!(
+            composed.render("Base."),
+            [
+                "Use tabs.",
+                "Base.",
+                "<environment_context />",
+                "## Permissions\n\nCommands run in a read-only sandbox: they can read files but not change them.\nThe user is never asked for approval; work within the sandbox and report anything you could not do.",
+            ]
+            .join("\n\n")
+        );
+        assert!(
+            composed
+                .render_annotated("Base.")
+                .starts_with("=== team ===\nUse tabs.\n\n=== base ===\nBase.")
+        );
+    }
+}
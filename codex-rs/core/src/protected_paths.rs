@@ -0,0 +1,204 @@
+//! Paths that patches from the model may not change freely.
+//!
+//! Patterns come from `protected_paths` in `config.toml` and use gitignore
+//! syntax, anchored at the repository root (or the working directory outside
+//! a repository). `block` patterns refuse the patch outright; patterns under
+//! `require_approval` make it wait for explicit approval whatever the
+//! approval policy says.
+//!
+//! Paths are resolved against the working directory and normalized lexically
+//! before matching, so `src/../infra/main.tf` is checked as `infra/main.tf`.
+
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use codex_protocol::protocol::ProtectedPathRule;
+use codex_protocol::protocol::ProtectedPathViolation;
+use ignore::Match;
+use ignore::gitignore::Gitignore;
+use ignore::gitignore::GitignoreBuilder;
+use tracing::warn;
+
+use crate::config::ProtectedPathsToml;
+use crate::git_info::get_git_repo_root;
+
+#[derive(Debug, Clone)]
+pub(crate) struct ProtectedPaths {
+    cwd: PathBuf,
+    root: PathBuf,
+    block: Gitignore,
+    require_approval: Gitignore,
+}
+
+impl ProtectedPaths {
+    pub(crate) fn new(cwd: &Path, config: &ProtectedPathsToml) -> Self {
+        let root = get_git_repo_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+        Self {
+            block: build_matcher(&root, &config.block),
+            require_approval: build_matcher(&root, &config.require_approval),
+            cwd: cwd.to_path_buf(),
+            root,
+        }
+    }
+
+    /// The protected paths among `paths`, each with the rule and pattern
+    /// that protects it. `block` wins when both rules match.
+    pub(crate) fn violations(&self, paths: &[PathBuf]) -> Vec<ProtectedPathViolation> {
+        let mut violations: Vec<ProtectedPathViolation> = Vec::new();
+        for path in paths {
+            if violations.iter().any(|violation| &violation.path == path) {
+                continue;
+            }
+            let normalized = normalize(&self.cwd.join(path));
+            let rules = [
+                (ProtectedPathRule::Block, &self.block),
+                (ProtectedPathRule::RequireApproval, &self.require_approval),
+            ];
+            if let Some((rule, pattern)) = rules.into_iter().find_map(|(rule, matcher)| {
+                Some((rule, self.matched_pattern(matcher, &normalized)?))
+            }) {
+                violations.push(ProtectedPathViolation {
+                    path: path.clone(),
+                    pattern,
+                    rule,
+                });
+            }
+        }
+        violations
+    }
+
+    fn matched_pattern(&self, matcher: &Gitignore, path: &Path) -> Option<String> {
+        if matcher.is_empty() {
+            return None;
+        }
+        let matched = if path.starts_with(&self.root) {
+            matcher.matched_path_or_any_parents(path, false)
+        } else {
+            // Paths outside the root can only match unanchored patterns.
+            matcher.matched(path, false)
+        };
+        match matched {
+            Match::Ignore(glob) => Some(glob.original().to_string()),
+            Match::None | Match::Whitelist(_) => None,
+        }
+    }
+}
+
+impl Default for ProtectedPaths {
+    fn default() -> Self {
+        Self {
+            cwd: PathBuf::new(),
+            root: PathBuf::new(),
+            block: Gitignore::empty(),
+            require_approval: Gitignore::empty(),
+        }
+    }
+}
+
+/// Drop `.` and resolve `..` without touching the filesystem, so paths that
+/// do not exist yet normalize too.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            Component::CurDir => {}
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+fn build_matcher(root: &Path, patterns: &[String]) -> Gitignore {
+    let mut builder = GitignoreBuilder::new(root);
+    for pattern in patterns {
+        if let Err(err) = builder.add_line(None, pattern) {
+            warn!("Invalid protected_paths pattern `{pattern}`: {err}");
+        }
+    }
+    builder.build().unwrap_or_else(|err| {
+        warn!("Failed to build protected path rules: {err}");
+        Gitignore::empty()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn block_wins_over_require_approval() {
+        let dir = tempdir().expect("tempdir");
+        let root = dir.path();
+        let rules = ProtectedPaths::new(
+            root,
+            &ProtectedPathsToml {
+                require_approval: vec!["infra/".to_string(), ".github/workflows/".to_string()],
+                block: vec!["*.lock".to_string()],
+            },
+        );
+
+        let paths = [
+            root.join("infra/main.tf"),
+            root.join("infra/Cargo.lock"),
+            root.join(".github/workflows/ci.yml"),
+            root.join("src/lib.rs"),
+        ];
+        assert_eq!(
+            rules.violations(&paths),
+            vec![
+                ProtectedPathViolation {
+                    path: root.join("infra/main.tf"),
+                    pattern: "infra/".to_string(),
+                    rule: ProtectedPathRule::RequireApproval,
+                },
+                ProtectedPathViolation {
+                    path: root.join("infra/Cargo.lock"),
+                    pattern: "*.lock".to_string(),
+                    rule: ProtectedPathRule::Block,
+                },
+                ProtectedPathViolation {
+                    path: root.join(".github/workflows/ci.yml"),
+                    pattern: ".github/workflows/".to_string(),
+                    rule: ProtectedPathRule::RequireApproval,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn paths_are_normalized_before_matching() {
+        let dir = tempdir().expect("tempdir");
+        let root = dir.path();
+        let rules = ProtectedPaths::new(
+            root,
+            &ProtectedPathsToml {
+                require_approval: Vec::new(),
+                block: vec!["infra/".to_string()],
+            },
+        );
+
+        let dotted = root.join("src/../infra/main.tf");
+        let relative = PathBuf::from("./src/../infra/variables.tf");
+        assert_eq!(
+            rules.violations(&[dotted.clone(), relative.clone(), root.join("src/infra.rs")]),
+            vec![
+                ProtectedPathViolation {
+                    path: dotted,
+                    pattern: "infra/".to_string(),
+                    rule: ProtectedPathRule::Block,
+                },
+                ProtectedPathViolation {
+                    path: relative,
+                    pattern: "infra/".to_string(),
+                    rule: ProtectedPathRule::Block,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,400 @@
+//! Token-budgeted map of a repository for orientation.
+//!
+//! The map is a directory tree where each source file lists its top-level
+//! declarations, extracted with tree-sitter. The walk honors `.gitignore` and
+//! the session's context ignore rules. Entries are emitted in path order until
+//! the token budget is exhausted.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use ignore::WalkBuilder;
+use tree_sitter::Language;
+use tree_sitter::Node;
+use tree_sitter::Parser;
+
+use crate::context_ignore::ContextIgnore;
+use crate::truncate::approx_token_count;
+
+pub(crate) const REPO_MAP_OPEN_TAG: &str = "<repo_map";
+const REPO_MAP_CLOSE_TAG: &str = "</repo_map>";
+
+/// Token budget for the map injected at session start.
+pub(crate) const SESSION_REPO_MAP_MAX_TOKENS: usize = 2048;
+/// Default token budget when the model requests a map through the tool.
+pub(crate) const TOOL_REPO_MAP_MAX_TOKENS: usize = 4096;
+
+const MAX_FILES: usize = 5_000;
+/// Files larger than this are listed without symbols.
+const MAX_PARSE_BYTES: u64 = 512 * 1024;
+const MAX_SYMBOLS_PER_FILE: usize = 24;
+
+/// A rendered repository map, converted into a user-role context message.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RepoMap {
+    pub root: String,
+    pub map: String,
+}
+
+impl RepoMap {
+    pub fn is_repo_map(message: &[ContentItem]) -> bool {
+        if let [ContentItem::InputText { text }] = message {
+            text.starts_with(REPO_MAP_OPEN_TAG)
+        } else {
+            false
+        }
+    }
+}
+
+impl From<RepoMap> for ResponseItem {
+    fn from(repo_map: RepoMap) -> Self {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format!(
+                    "{REPO_MAP_OPEN_TAG} root=\"{}\">\n{}\n{REPO_MAP_CLOSE_TAG}",
+                    repo_map.root, repo_map.map
+                ),
+            }],
+        }
+    }
+}
+
+/// The most recent map produced by the `repo_map` tool, reused until the
+/// index watcher reports a change.
+#[derive(Debug, Default)]
+pub(crate) struct RepoMapCache {
+    entry: Mutex<Option<CachedRepoMap>>,
+}
+
+#[derive(Debug)]
+struct CachedRepoMap {
+    root: PathBuf,
+    max_tokens: usize,
+    map: Option<String>,
+}
+
+impl RepoMapCache {
+    /// The cached map for `root` and `max_tokens`, if one exists. The outer
+    /// `Option` is the cache hit; the inner one mirrors `generate_repo_map`.
+    pub(crate) fn get(&self, root: &Path, max_tokens: usize) -> Option<Option<String>> {
+        let entry = self
+            .entry
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        entry
+            .as_ref()
+            .filter(|cached| cached.root == root && cached.max_tokens == max_tokens)
+            .map(|cached| cached.map.clone())
+    }
+
+    pub(crate) fn store(&self, root: PathBuf, max_tokens: usize, map: Option<String>) {
+        let mut entry = self
+            .entry
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        *entry = Some(CachedRepoMap {
+            root,
+            max_tokens,
+            map,
+        });
+    }
+
+    pub(crate) fn clear(&self) {
+        let mut entry = self
+            .entry
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        *entry = None;
+    }
+}
+
+/// Render the map for `root`, stopping once `max_tokens` would be exceeded.
+/// Returns `None` when `root` contains no visible files.
+///
+/// This walks and parses files synchronously; call it from a blocking task.
+pub(crate) fn generate_repo_map(
+    root: &Path,
+    context_ignore: &ContextIgnore,
+    max_tokens: usize,
+) -> Option<String> {
+    let walker = WalkBuilder::new(root)
+        .sort_by_file_name(std::cmp::Ord::cmp)
+        .build();
+
+    let mut lines: Vec<String> = Vec::new();
+    let mut used_tokens = 0usize;
+    let mut omitted = 0usize;
+    let mut files = 0usize;
+    let mut exhausted = false;
+
+    for entry in walker.flatten() {
+        let path = entry.path();
+        let Ok(relative) = path.strip_prefix(root) else {
+            continue;
+        };
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        let is_dir = entry
+            .file_type()
+            .is_some_and(|file_type| file_type.is_dir());
+        if context_ignore.is_ignored(path, is_dir) {
+            continue;
+        }
+        if !is_dir {
+            files += 1;
+            if files > MAX_FILES {
+                omitted += 1;
+                continue;
+            }
+        }
+
+        if exhausted {
+            if !is_dir {
+                omitted += 1;
+            }
+            continue;
+        }
+
+        let depth = relative.components().count() - 1;
+        let name = relative
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let mut line = format!("{}{name}", "  ".repeat(depth));
+        if is_dir {
+            line.push('/');
+        } else if used_tokens + approx_token_count(&line) < max_tokens {
+            // Only parse files whose name alone still fits the budget.
+            let symbols = entry
+                .metadata()
+                .ok()
+                .filter(|metadata| metadata.len() <= MAX_PARSE_BYTES)
+                .and_then(|_| file_symbols(path))
+                .unwrap_or_default();
+            if !symbols.is_empty() {
+                line.push_str(&format!(": {}", symbols.join(", ")));
+            }
+        }
+
+        let tokens = approx_token_count(&line) + 1;
+        if used_tokens + tokens > max_tokens {
+            // Stop at the first entry that does not fit so the tree never
+            // lists files without their parent directories.
+            exhausted = true;
+            if !is_dir {
+                omitted += 1;
+            }
+            continue;
+        }
+        used_tokens += tokens;
+        lines.push(line);
+    }
+
+    if files == 0 {
+        return None;
+    }
+    if omitted > 0 {
+        lines.push(format!("… ({omitted} more files omitted)"));
+    }
+    Some(lines.join("\n"))
+}
+
+#[derive(Clone, Copy)]
+enum SourceLanguage {
+    Rust,
+    Python,
+    JavaScript,
+    TypeScript,
+    Tsx,
+    Go,
+}
+
+impl SourceLanguage {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "rs" => Some(Self::Rust),
+            "py" => Some(Self::Python),
+            "js" | "jsx" | "mjs" | "cjs" => Some(Self::JavaScript),
+            "ts" | "mts" | "cts" => Some(Self::TypeScript),
+            "tsx" => Some(Self::Tsx),
+            "go" => Some(Self::Go),
+            _ => None,
+        }
+    }
+
+    fn grammar(self) -> Language {
+        match self {
+            Self::Rust => tree_sitter_rust::LANGUAGE.into(),
+            Self::Python => tree_sitter_python::LANGUAGE.into(),
+            Self::JavaScript => tree_sitter_javascript::LANGUAGE.into(),
+            Self::TypeScript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+            Self::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
+            Self::Go => tree_sitter_go::LANGUAGE.into(),
+        }
+    }
+}
+
+fn file_symbols(path: &Path) -> Option<Vec<String>> {
+    let language = SourceLanguage::from_path(path)?;
+    let source = std::fs::read_to_string(path).ok()?;
+    Some(extract_symbols(language, &source))
+}
+
+fn extract_symbols(language: SourceLanguage, source: &str) -> Vec<String> {
+    let mut parser = Parser::new();
+    if parser.set_language(&language.grammar()).is_err() {
+        return Vec::new();
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return Vec::new();
+    };
+
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    let mut symbols = Vec::new();
+    for node in root.named_children(&mut cursor) {
+        if symbols.len() >= MAX_SYMBOLS_PER_FILE {
+            symbols.push("…".to_string());
+            break;
+        }
+        symbols.extend(describe_declaration(language, node, source));
+    }
+    symbols
+}
+
+/// Describe a top-level declaration as `<keyword> <name>`, unwrapping export
+/// statements and decorators.
+fn describe_declaration(language: SourceLanguage, node: Node, source: &str) -> Vec<String> {
+    let text = |node: Node| node.utf8_text(source.as_bytes()).unwrap_or_default();
+    let named = |keyword: &str, field: &str| {
+        node.child_by_field_name(field)
+            .map(|name| vec![format!("{keyword} {}", text(name))])
+            .unwrap_or_default()
+    };
+
+    match node.kind() {
+        "export_statement" | "decorated_definition" => ["declaration", "definition"]
+            .iter()
+            .find_map(|field| node.child_by_field_name(field))
+            .map(|inner| describe_declaration(language, inner, source))
+            .unwrap_or_default(),
+        "function_item" | "function_definition" | "function_declaration" => {
+            let keyword = match language {
+                SourceLanguage::Rust => "fn",
+                SourceLanguage::Python => "def",
+                SourceLanguage::Go => "func",
+                SourceLanguage::JavaScript | SourceLanguage::TypeScript | SourceLanguage::Tsx => {
+                    "function"
+                }
+            };
+            named(keyword, "name")
+        }
+        "method_declaration" => named("func", "name"),
+        "struct_item" => named("struct", "name"),
+        "enum_item" | "enum_declaration" => named("enum", "name"),
+        "trait_item" => named("trait", "name"),
+        "mod_item" => named("mod", "name"),
+        "type_item" | "type_alias_declaration" => named("type", "name"),
+        "const_item" => named("const", "name"),
+        "static_item" => named("static", "name"),
+        "macro_definition" => named("macro", "name"),
+        "class_definition" | "class_declaration" | "abstract_class_declaration" => {
+            named("class", "name")
+        }
+        "interface_declaration" => named("interface", "name"),
+        "impl_item" => {
+            let Some(type_node) = node.child_by_field_name("type") else {
+                return Vec::new();
+            };
+            match node.child_by_field_name("trait") {
+                Some(trait_node) => {
+                    vec![format!("impl {} for {}", text(trait_node), text(type_node))]
+                }
+                None => vec![format!("impl {}", text(type_node))],
+            }
+        }
+        "type_declaration" => {
+            let mut cursor = node.walk();
+            node.named_children(&mut cursor)
+                .filter(|child| child.kind() == "type_spec")
+                .filter_map(|spec| spec.child_by_field_name("name"))
+                .map(|name| format!("type {}", text(name)))
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn extracts_rust_top_level_symbols() {
+        let source = r#"
+pub struct Widget;
+impl Widget { fn render(&self) {} }
+impl std::fmt::Display for Widget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result { Ok(()) }
+}
+pub(crate) fn build() {}
+mod tests {}
+"#;
+        assert_eq!(
+            extract_symbols(SourceLanguage::Rust, source),
+            vec![
+                "struct Widget".to_string(),
+                "impl Widget".to_string(),
+                "impl std::fmt::Display for Widget".to_string(),
+                "fn build".to_string(),
+                "mod tests".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_python_and_typescript_symbols() {
+        let python = "@dataclass\nclass Point:\n    x: int\n\ndef main():\n    pass\n";
+        assert_eq!(
+            extract_symbols(SourceLanguage::Python, python),
+            vec!["class Point".to_string(), "def main".to_string()]
+        );
+
+        let typescript =
+            "export interface Props {}\nexport function render() {}\ntype Id = string;\n";
+        assert_eq!(
+            extract_symbols(SourceLanguage::TypeScript, typescript),
+            vec![
+                "interface Props".to_string(),
+                "function render".to_string(),
+                "type Id".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_tree_and_respects_budget_and_ignores() {
+        let dir = tempdir().expect("tempdir");
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src")).expect("mkdir");
+        std::fs::create_dir_all(root.join("secrets")).expect("mkdir");
+        std::fs::write(root.join("src/lib.rs"), "pub fn run() {}\n").expect("write");
+        std::fs::write(root.join("secrets/key.txt"), "hunter2").expect("write");
+        std::fs::write(root.join("README.md"), "# readme").expect("write");
+        let context_ignore = ContextIgnore::new(root, &["secrets/".to_string()]);
+
+        let map = generate_repo_map(root, &context_ignore, 1024).expect("map");
+        assert_eq!(map, "README.md\nsrc/\n  lib.rs: fn run");
+
+        let truncated = generate_repo_map(root, &context_ignore, 4).expect("map");
+        assert_eq!(truncated, "README.md\n… (1 more files omitted)");
+    }
+}
@@ -0,0 +1,289 @@
+//! Free disk and memory checks before commands that tend to need a lot of
+//! both, such as `cargo build`, `docker build`, or `npm install`.
+//!
+//! When resources are getting low the user sees a warning; when they are
+//! nearly exhausted the command waits for approval, so a build does not fill
+//! the disk halfway through and leave the machine wedged.
+
+use std::path::Path;
+
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::WarningEvent;
+
+use crate::bash::parse_shell_lc_plain_commands;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::features::Feature;
+use crate::tools::sandboxing::ExecApprovalRequirement;
+
+const GIB: u64 = 1024 * 1024 * 1024;
+const MIB: u64 = 1024 * 1024;
+
+/// Below these, heavy commands run with a warning.
+const WARN_FREE_DISK_BYTES: u64 = 5 * GIB;
+const WARN_AVAILABLE_MEMORY_BYTES: u64 = 2 * GIB;
+/// Below these, heavy commands need approval.
+const MIN_FREE_DISK_BYTES: u64 = GIB;
+const MIN_AVAILABLE_MEMORY_BYTES: u64 = 512 * MIB;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Resources {
+    pub free_disk_bytes: Option<u64>,
+    pub available_memory_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Preflight {
+    Ok,
+    Warn(String),
+    RequireApproval(String),
+}
+
+/// Check resources before a heavy `command` runs in `cwd`. Warnings are sent
+/// to the client; when resources are nearly exhausted the command needs
+/// approval, unless approvals are off.
+pub(crate) async fn apply_resource_preflight(
+    sess: &Session,
+    turn: &TurnContext,
+    command: &[String],
+    cwd: &Path,
+    requirement: ExecApprovalRequirement,
+) -> ExecApprovalRequirement {
+    if !sess.enabled(Feature::ResourcePreflight) || !is_heavy_command(command) {
+        return requirement;
+    }
+    let cwd = cwd.to_path_buf();
+    let resources = tokio::task::spawn_blocking(move || current_resources(&cwd))
+        .await
+        .unwrap_or(Resources {
+            free_disk_bytes: None,
+            available_memory_bytes: None,
+        });
+    match (check(resources), requirement) {
+        (Preflight::Ok, requirement) => requirement,
+        (Preflight::RequireApproval(reason), ExecApprovalRequirement::Skip { .. })
+            if turn.approval_policy != AskForApproval::Never =>
+        {
+            ExecApprovalRequirement::NeedsApproval {
+                reason: Some(reason),
+                proposed_execpolicy_amendment: None,
+            }
+        }
+        (
+            Preflight::RequireApproval(reason),
+            ExecApprovalRequirement::NeedsApproval {
+                reason: existing,
+                proposed_execpolicy_amendment,
+            },
+        ) => ExecApprovalRequirement::NeedsApproval {
+            reason: Some(match existing {
+                Some(existing) => format!("{reason} {existing}"),
+                None => reason,
+            }),
+            proposed_execpolicy_amendment,
+        },
+        (Preflight::Warn(message) | Preflight::RequireApproval(message), requirement) => {
+            sess.send_event(turn, EventMsg::Warning(WarningEvent { message }))
+                .await;
+            requirement
+        }
+    }
+}
+
+/// Whether `command` is a build, install, or image build that commonly
+/// needs gigabytes of disk or memory.
+pub(crate) fn is_heavy_command(command: &[String]) -> bool {
+    let commands = parse_shell_lc_plain_commands(command).unwrap_or_else(|| vec![command.to_vec()]);
+    commands.iter().any(|cmd| is_heavy_single_command(cmd))
+}
+
+fn is_heavy_single_command(command: &[String]) -> bool {
+    let Some(program) = command.first() else {
+        return false;
+    };
+    let program = Path::new(program)
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(program);
+    let args: Vec<&str> = command[1..]
+        .iter()
+        .map(String::as_str)
+        .filter(|arg| !arg.starts_with('-'))
+        .collect();
+    let first = args.first().copied();
+    match program {
+        "cargo" => matches!(
+            first,
+            Some("build" | "b" | "test" | "t" | "bench" | "install" | "run" | "r" | "clippy")
+        ),
+        "docker" | "podman" => match first {
+            Some("build") => true,
+            Some("buildx" | "compose") => {
+                matches!(args.get(1).copied(), Some("build" | "up" | "bake"))
+            }
+            _ => false,
+        },
+        "npm" | "pnpm" | "bun" => matches!(first, Some("install" | "i" | "ci" | "add")),
+        "yarn" => matches!(first, None | Some("install" | "add")),
+        "pip" | "pip3" | "poetry" => first == Some("install"),
+        "uv" => matches!(first, Some("sync")) || args.starts_with(&["pip", "install"]),
+        "go" => matches!(first, Some("build" | "test" | "install")),
+        "gradle" | "gradlew" | "mvn" | "bazel" | "make" => true,
+        _ => false,
+    }
+}
+
+/// How `resources` compare with the thresholds.
+pub(crate) fn check(resources: Resources) -> Preflight {
+    let mut critical = Vec::new();
+    let mut low = Vec::new();
+    if let Some(free) = resources.free_disk_bytes {
+        let message = format!("{} of free disk space", format_bytes(free));
+        if free < MIN_FREE_DISK_BYTES {
+            critical.push(message);
+        } else if free < WARN_FREE_DISK_BYTES {
+            low.push(message);
+        }
+    }
+    if let Some(available) = resources.available_memory_bytes {
+        let message = format!("{} of available memory", format_bytes(available));
+        if available < MIN_AVAILABLE_MEMORY_BYTES {
+            critical.push(message);
+        } else if available < WARN_AVAILABLE_MEMORY_BYTES {
+            low.push(message);
+        }
+    }
+    if !critical.is_empty() {
+        critical.extend(low);
+        Preflight::RequireApproval(format!(
+            "Only {} left; this command may run out of resources and leave a partial build.",
+            critical.join(" and ")
+        ))
+    } else if !low.is_empty() {
+        Preflight::Warn(format!(
+            "Only {} left; large builds or installs may fail.",
+            low.join(" and ")
+        ))
+    } else {
+        Preflight::Ok
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    if bytes >= GIB {
+        format!("{:.1} GiB", bytes as f64 / GIB as f64)
+    } else {
+        format!("{} MiB", bytes / MIB)
+    }
+}
+
+fn current_resources(cwd: &Path) -> Resources {
+    Resources {
+        free_disk_bytes: free_disk_bytes(cwd),
+        available_memory_bytes: available_memory_bytes(),
+    }
+}
+
+#[cfg(unix)]
+fn free_disk_bytes(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+    // SAFETY: `statvfs` is a plain C struct of integers, for which all-zero
+    // bytes are a valid value.
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    // SAFETY: `path` is a valid C string and `stat` is a valid out pointer.
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+    #[allow(clippy::unnecessary_cast)]
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+fn free_disk_bytes(_path: &Path) -> Option<u64> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn available_memory_bytes() -> Option<u64> {
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let line = meminfo
+        .lines()
+        .find(|line| line.starts_with("MemAvailable:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn available_memory_bytes() -> Option<u64> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn cmd(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn detects_heavy_commands() {
+        assert!(is_heavy_command(&cmd(&["cargo", "build", "--release"])));
+        assert!(is_heavy_command(&cmd(&[
+            "docker", "build", "-t", "app", "."
+        ])));
+        assert!(is_heavy_command(&cmd(&[
+            "bash",
+            "-lc",
+            "cd web && npm install"
+        ])));
+        assert!(is_heavy_command(&cmd(&["/usr/bin/yarn"])));
+        assert!(!is_heavy_command(&cmd(&["cargo", "fmt"])));
+        assert!(!is_heavy_command(&cmd(&["npm", "run", "lint"])));
+        assert!(!is_heavy_command(&cmd(&["ls", "-la"])));
+    }
+
+    #[test]
+    fn thresholds_warn_then_require_approval() {
+        let plenty = Resources {
+            free_disk_bytes: Some(50 * GIB),
+            available_memory_bytes: Some(8 * GIB),
+        };
+        assert_eq!(check(plenty), Preflight::Ok);
+
+        let low_disk = Resources {
+            free_disk_bytes: Some(3 * GIB),
+            ..plenty
+        };
+        assert_eq!(
+            check(low_disk),
+            Preflight::Warn(
+                "Only 3.0 GiB of free disk space left; large builds or installs may fail."
+                    .to_string()
+            )
+        );
+
+        let exhausted = Resources {
+            free_disk_bytes: Some(200 * MIB),
+            available_memory_bytes: Some(GIB),
+        };
+        assert_eq!(
+            check(exhausted),
+            Preflight::RequireApproval(
+                "Only 200 MiB of free disk space and 1.0 GiB of available memory left; this command may run out of resources and leave a partial build."
+                    .to_string()
+            )
+        );
+
+        let unknown = Resources {
+            free_disk_bytes: None,
+            available_memory_bytes: None,
+        };
+        assert_eq!(check(unknown), Preflight::Ok);
+    }
+}
@@ -46,9 +46,25 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::EnteredReviewMode(_)
         | EventMsg::ExitedReviewMode(_)
         | EventMsg::UndoCompleted(_)
+        | EventMsg::ArtifactRegistered(_)
+        | EventMsg::ItemAnnotated(_)
+        | EventMsg::AnnotationRemoved(_)
+        | EventMsg::ResponseRegenerated(_)
+        | EventMsg::WorkflowStepStarted(_)
+        | EventMsg::CandidateSelected(_)
+        | EventMsg::TurnContextChanged(_)
         | EventMsg::TurnAborted(_) => true,
         EventMsg::Error(_)
         | EventMsg::Warning(_)
+        | EventMsg::ContextRepaired(_)
+        | EventMsg::ContextTrimmed(_)
+        | EventMsg::ContextManifest(_)
+        | EventMsg::SemanticIndexUpdated(_)
+        | EventMsg::SessionSummary(_)
+        | EventMsg::WorkingDirectoryChanged(_)
+        | EventMsg::TurnProgress(_)
+        | EventMsg::TurnCompletedMetrics(_)
+        | EventMsg::Stalled(_)
         | EventMsg::TaskStarted(_)
         | EventMsg::TaskComplete(_)
         | EventMsg::AgentMessageDelta(_)
@@ -68,12 +84,19 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::ExecApprovalRequest(_)
         | EventMsg::ElicitationRequest(_)
         | EventMsg::ApplyPatchApprovalRequest(_)
+        | EventMsg::PatchConflict(_)
+        | EventMsg::Question(_)
+        | EventMsg::CandidatesProposed(_)
+        | EventMsg::MemoryCandidatesProposed(_)
+        | EventMsg::ProtectedPathViolation(_)
+        | EventMsg::DependencyReport(_)
         | EventMsg::BackgroundEvent(_)
         | EventMsg::StreamError(_)
         | EventMsg::PatchApplyBegin(_)
         | EventMsg::PatchApplyEnd(_)
         | EventMsg::TurnDiff(_)
         | EventMsg::GetHistoryEntryResponse(_)
+        | EventMsg::FileAtTurnResponse(_)
         | EventMsg::UndoStarted(_)
         | EventMsg::McpListToolsResponse(_)
         | EventMsg::McpStartupUpdate(_)
@@ -89,6 +112,7 @@ pub(crate) fn should_persist_event_msg(ev: &EventMsg) -> bool {
         | EventMsg::AgentMessageContentDelta(_)
         | EventMsg::ReasoningContentDelta(_)
         | EventMsg::ReasoningRawContentDelta(_)
+        | EventMsg::StructuredOutputDelta(_)
         | EventMsg::SkillsUpdateAvailable => false,
     }
 }
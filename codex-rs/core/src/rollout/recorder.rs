@@ -30,9 +30,12 @@ use codex_protocol::protocol::InitialHistory;
 use codex_protocol::protocol::ResumedHistory;
 use codex_protocol::protocol::RolloutItem;
 use codex_protocol::protocol::RolloutLine;
+use codex_protocol::protocol::SessionEnvironment;
 use codex_protocol::protocol::SessionMeta;
 use codex_protocol::protocol::SessionMetaLine;
 use codex_protocol::protocol::SessionSource;
+use codex_protocol::protocol::WorkspaceRoot;
+use futures::future::join_all;
 
 /// Records all [`ResponseItem`]s for a session and flushes them to disk after
 /// every update.
@@ -55,6 +58,7 @@ pub enum RolloutRecorderParams {
         conversation_id: ConversationId,
         instructions: Option<String>,
         source: SessionSource,
+        environment: Option<SessionEnvironment>,
     },
     Resume {
         path: PathBuf,
@@ -82,6 +86,27 @@ impl RolloutRecorderParams {
             conversation_id,
             instructions,
             source,
+            environment: None,
+        }
+    }
+
+    /// Record `environment` in the session metadata of a new rollout. Has no
+    /// effect when resuming, since the metadata was written by the original
+    /// session.
+    pub fn with_environment(self, environment: SessionEnvironment) -> Self {
+        match self {
+            Self::Create {
+                conversation_id,
+                instructions,
+                source,
+                ..
+            } => Self::Create {
+                conversation_id,
+                instructions,
+                source,
+                environment: Some(environment),
+            },
+            resume @ Self::Resume { .. } => resume,
         }
     }
 
@@ -120,6 +145,7 @@ impl RolloutRecorder {
                 conversation_id,
                 instructions,
                 source,
+                environment,
             } => {
                 let LogFileInfo {
                     file,
@@ -148,6 +174,7 @@ impl RolloutRecorder {
                         instructions,
                         source,
                         model_provider: Some(config.model_provider_id.clone()),
+                        environment,
                     }),
                 )
             }
@@ -161,8 +188,10 @@ impl RolloutRecorder {
             ),
         };
 
-        // Clone the cwd for the spawned task to collect git info asynchronously
+        // Clone the workspace roots for the spawned task to collect git info
+        // asynchronously.
         let cwd = config.cwd.clone();
+        let additional_roots = config.additional_workspace_roots.clone();
 
         // A reasonably-sized bounded channel. If the buffer fills up the send
         // future will yield, which is fine – we only need to ensure we do not
@@ -172,7 +201,7 @@ impl RolloutRecorder {
         // Spawn a Tokio task that owns the file handle and performs async
         // writes. Using `tokio::fs::File` keeps everything on the async I/O
         // driver instead of blocking the runtime.
-        tokio::task::spawn(rollout_writer(file, rx, meta, cwd));
+        tokio::task::spawn(rollout_writer(file, rx, meta, cwd, additional_roots));
 
         Ok(Self { tx, rollout_path })
     }
@@ -351,15 +380,22 @@ async fn rollout_writer(
     mut rx: mpsc::Receiver<RolloutCmd>,
     mut meta: Option<SessionMeta>,
     cwd: std::path::PathBuf,
+    additional_roots: Vec<PathBuf>,
 ) -> std::io::Result<()> {
     let mut writer = JsonlWriter { file };
 
     // If we have a meta, collect git info asynchronously and write meta first
     if let Some(session_meta) = meta.take() {
         let git_info = collect_git_info(&cwd).await;
+        let workspace_roots = join_all(additional_roots.into_iter().map(|path| async move {
+            let git = collect_git_info(&path).await;
+            WorkspaceRoot { path, git }
+        }))
+        .await;
         let session_meta_line = SessionMetaLine {
             meta: session_meta,
             git: git_info,
+            workspace_roots,
         };
 
         // Write the SessionMeta as the first item in the file, wrapped in a rollout line
@@ -592,8 +592,10 @@ async fn test_updated_at_uses_file_mtime() -> Result<()> {
                 cli_version: "test_version".into(),
                 source: SessionSource::VSCode,
                 model_provider: Some("test-provider".into()),
+                environment: None,
             },
             git: None,
+            workspace_roots: Vec::new(),
         }),
     };
     writeln!(file, "{}", serde_json::to_string(&meta_line)?)?;
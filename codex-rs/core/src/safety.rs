@@ -4,12 +4,14 @@ use std::path::PathBuf;
 
 use codex_apply_patch::ApplyPatchAction;
 use codex_apply_patch::ApplyPatchFileChange;
+use codex_utils_absolute_path::AbsolutePathBuf;
 
 use crate::exec::SandboxType;
 use crate::util::resolve_path;
 
 use crate::protocol::AskForApproval;
 use crate::protocol::SandboxPolicy;
+use crate::protocol::WritableRoot;
 
 #[cfg(target_os = "windows")]
 use std::sync::atomic::AtomicBool;
@@ -172,9 +174,27 @@ fn is_write_patch_constrained_to_writable_paths(
         Some(out)
     }
 
+    // The same roots with symlinks resolved, so a link inside the workspace
+    // that points elsewhere cannot be used to write outside of it.
+    let resolved_roots: Vec<WritableRoot> = writable_roots
+        .iter()
+        .filter_map(|writable_root| {
+            let resolve =
+                |path: &Path| AbsolutePathBuf::from_absolute_path(resolve_symlinks(path)).ok();
+            Some(WritableRoot {
+                root: resolve(writable_root.root.as_path())?,
+                read_only_subpaths: writable_root
+                    .read_only_subpaths
+                    .iter()
+                    .filter_map(|subpath| resolve(subpath.as_path()))
+                    .collect(),
+            })
+        })
+        .collect();
+
     // Determine whether `path` is inside **any** writable root. Both `path`
     // and roots are converted to absolute, normalized forms before the
-    // prefix check.
+    // prefix check, and the check must also pass once symlinks are resolved.
     let is_path_writable = |p: &PathBuf| {
         let abs = resolve_path(cwd, p);
         let abs = match normalize(&abs) {
@@ -182,9 +202,13 @@ fn is_write_patch_constrained_to_writable_paths(
             None => return false,
         };
 
+        let resolved = resolve_symlinks(&abs);
         writable_roots
             .iter()
             .any(|writable_root| writable_root.is_path_writable(&abs))
+            && resolved_roots
+                .iter()
+                .any(|writable_root| writable_root.is_path_writable(&resolved))
     };
 
     for (path, change) in action.changes() {
@@ -210,10 +234,46 @@ fn is_write_patch_constrained_to_writable_paths(
     true
 }
 
+/// Resolve every symlink in `path`, including one at the final component.
+/// The path need not exist: the deepest existing ancestor is resolved and the
+/// remaining components are appended to it.
+fn resolve_symlinks(path: &Path) -> PathBuf {
+    resolve_symlinks_with_depth(path, 0)
+}
+
+fn resolve_symlinks_with_depth(path: &Path, depth: usize) -> PathBuf {
+    const MAX_SYMLINK_DEPTH: usize = 40;
+
+    let mut existing = path.to_path_buf();
+    let mut missing = Vec::new();
+    loop {
+        if let Ok(real) = existing.canonicalize() {
+            return missing.iter().rev().fold(real, |out, name| out.join(name));
+        }
+        // A dangling link still redirects writes to its target.
+        if depth < MAX_SYMLINK_DEPTH
+            && let Ok(target) = std::fs::read_link(&existing)
+        {
+            let target = match existing.parent() {
+                Some(parent) => parent.join(target),
+                None => target,
+            };
+            let real = resolve_symlinks_with_depth(&target, depth + 1);
+            return missing.iter().rev().fold(real, |out, name| out.join(name));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name.to_os_string());
+                existing = parent.to_path_buf();
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use codex_utils_absolute_path::AbsolutePathBuf;
     use tempfile::TempDir;
 
     #[test]
@@ -266,6 +326,53 @@ mod tests {
         ));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_cannot_escape_writable_roots() {
+        let tmp = TempDir::new().unwrap();
+        let cwd = tmp.path().join("workspace");
+        let outside = tmp.path().join("store");
+        std::fs::create_dir_all(&cwd).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::os::unix::fs::symlink(&outside, cwd.join("node_modules")).unwrap();
+        std::os::unix::fs::symlink(outside.join("missing.txt"), cwd.join("dangling.txt")).unwrap();
+
+        let through_dir =
+            ApplyPatchAction::new_add_for_test(&cwd.join("node_modules/pkg/index.js"), "".into());
+        let through_dangling =
+            ApplyPatchAction::new_add_for_test(&cwd.join("dangling.txt"), "".into());
+        let workspace_only = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+        assert!(!is_write_patch_constrained_to_writable_paths(
+            &through_dir,
+            &workspace_only,
+            &cwd,
+        ));
+        assert!(!is_write_patch_constrained_to_writable_paths(
+            &through_dangling,
+            &workspace_only,
+            &cwd,
+        ));
+
+        // Listing the link target (as `allowed_symlink_targets` does) makes
+        // the symlinked layout writable again.
+        let with_store = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![AbsolutePathBuf::try_from(outside).unwrap()],
+            network_access: false,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: true,
+        };
+        assert!(is_write_patch_constrained_to_writable_paths(
+            &through_dir,
+            &with_store,
+            &cwd,
+        ));
+    }
+
     #[test]
     fn external_sandbox_auto_approves_in_on_request() {
         let tmp = TempDir::new().unwrap();
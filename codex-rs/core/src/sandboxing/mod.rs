@@ -11,6 +11,7 @@ use crate::exec::ExecToolCallOutput;
 use crate::exec::SandboxType;
 use crate::exec::StdoutStream;
 use crate::exec::execute_exec_env;
+use crate::exec::execute_exec_env_in_pty;
 use crate::landlock::create_linux_sandbox_command_args;
 use crate::protocol::SandboxPolicy;
 #[cfg(target_os = "macos")]
@@ -178,3 +179,13 @@ pub async fn execute_env(
 ) -> crate::error::Result<ExecToolCallOutput> {
     execute_exec_env(env, policy, stdout_stream).await
 }
+
+/// Like [`execute_env`], but attaches the command to a pseudo-terminal so it
+/// behaves as it would interactively. stdout and stderr arrive merged.
+pub async fn execute_env_in_pty(
+    env: ExecEnv,
+    policy: &SandboxPolicy,
+    stdout_stream: Option<StdoutStream>,
+) -> crate::error::Result<ExecToolCallOutput> {
+    execute_exec_env_in_pty(env, policy, stdout_stream).await
+}
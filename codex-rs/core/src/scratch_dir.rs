@@ -0,0 +1,131 @@
+//! A temporary directory owned by each session, for files the model needs
+//! while working (downloads, intermediate output, throwaway scripts) that do
+//! not belong in the user's repository.
+//!
+//! The path is reported to the model in the environment context. The
+//! directory is removed when the session shuts down, or when it is dropped
+//! without a shutdown, unless `scratch_dir.keep_on_error` is set and the
+//! session reported an error.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use codex_protocol::ConversationId;
+use tracing::info;
+use tracing::warn;
+
+const SCRATCH_DIR_PREFIX: &str = "codex-scratch-";
+
+#[derive(Debug)]
+pub(crate) struct ScratchDir {
+    path: PathBuf,
+    keep_on_error: bool,
+    had_error: AtomicBool,
+    cleaned_up: AtomicBool,
+}
+
+impl ScratchDir {
+    /// Create the scratch directory for `conversation_id` under the system
+    /// temp directory.
+    pub(crate) fn create(conversation_id: ConversationId, keep_on_error: bool) -> io::Result<Self> {
+        Self::create_in(&std::env::temp_dir(), conversation_id, keep_on_error)
+    }
+
+    fn create_in(
+        parent: &Path,
+        conversation_id: ConversationId,
+        keep_on_error: bool,
+    ) -> io::Result<Self> {
+        let path = parent.join(format!("{SCRATCH_DIR_PREFIX}{conversation_id}"));
+        std::fs::create_dir_all(&path)?;
+        Ok(Self {
+            path,
+            keep_on_error,
+            had_error: AtomicBool::new(false),
+            cleaned_up: AtomicBool::new(false),
+        })
+    }
+
+    pub(crate) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Note that the session reported an error, so `cleanup` keeps the
+    /// directory when `keep_on_error` is set.
+    pub(crate) fn mark_error(&self) {
+        self.had_error.store(true, Ordering::Relaxed);
+    }
+
+    /// Remove the directory and everything in it, unless it should be kept
+    /// for debugging. Only the first call does anything.
+    pub(crate) fn cleanup(&self) {
+        if self.cleaned_up.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        if self.keep_on_error && self.had_error.load(Ordering::Relaxed) {
+            info!(
+                "Keeping scratch directory {} after session error",
+                self.path.display()
+            );
+            return;
+        }
+        match std::fs::remove_dir_all(&self.path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => warn!(
+                "Failed to remove scratch directory {}: {err}",
+                self.path.display()
+            ),
+        }
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        self.cleanup();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn cleanup_keeps_directory_only_after_error_when_requested() {
+        let parent = tempdir().expect("tempdir");
+
+        let scratch = ScratchDir::create_in(parent.path(), ConversationId::new(), false)
+            .expect("create scratch dir");
+        std::fs::write(scratch.path().join("out.txt"), "data").expect("write file");
+        scratch.mark_error();
+        scratch.cleanup();
+        assert!(!scratch.path().exists());
+
+        let scratch = ScratchDir::create_in(parent.path(), ConversationId::new(), true)
+            .expect("create scratch dir");
+        scratch.cleanup();
+        assert!(!scratch.path().exists());
+
+        let scratch = ScratchDir::create_in(parent.path(), ConversationId::new(), true)
+            .expect("create scratch dir");
+        scratch.mark_error();
+        scratch.cleanup();
+        assert!(scratch.path().exists());
+    }
+
+    #[test]
+    fn drop_removes_directory_without_cleanup() {
+        let parent = tempdir().expect("tempdir");
+        let scratch = ScratchDir::create_in(parent.path(), ConversationId::new(), false)
+            .expect("create scratch dir");
+        let path = scratch.path().to_path_buf();
+        std::fs::write(path.join("out.txt"), "data").expect("write file");
+
+        drop(scratch);
+        assert!(!path.exists());
+    }
+}
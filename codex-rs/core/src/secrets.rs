@@ -0,0 +1,260 @@
+//! Credentials in text that is written to disk, such as request traces and
+//! saved memories. Each [`SecretDetector`] replaces what it finds with
+//! [`REDACTED`]; [`SecretScanner`] runs several of them.
+
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use regex_lite::Captures;
+use regex_lite::Regex;
+use tracing::warn;
+
+pub(crate) const REDACTED: &str = "[REDACTED]";
+
+/// Shortest run of token characters the entropy check looks at.
+const ENTROPY_MIN_LEN: usize = 24;
+/// Bits per character above which a token is taken for a random secret.
+/// English words and identifiers stay well below it.
+const ENTROPY_MIN_BITS_PER_CHAR: f64 = 4.0;
+
+static KNOWN_FORMATS: LazyLock<PatternDetector> = LazyLock::new(|| {
+    PatternDetector::new([
+        // Private keys: drop the whole block.
+        (
+            r"-----BEGIN [A-Z ]*PRIVATE KEY-----[\s\S]*?-----END [A-Z ]*PRIVATE KEY-----",
+            REDACTED,
+        ),
+        // `Authorization: Bearer <token>` and similar.
+        (
+            r"(?i)\b(bearer|basic)\s+[A-Za-z0-9._~+/=-]{8,}",
+            "$1 [REDACTED]",
+        ),
+        // `API_KEY=...`, `"password": "..."`, `client_secret: ...`.
+        (
+            r#"(?i)\b([A-Za-z0-9_.-]*(?:api[_-]?key|secret|password|passwd|access[_-]?token|auth[_-]?token)["']?\s*[:=]\s*["']?)[^\s"',;]{6,}"#,
+            "${1}[REDACTED]",
+        ),
+        // Well-known token formats.
+        (r"\bsk-[A-Za-z0-9_-]{16,}", REDACTED),
+        (r"\b(?:ghp|gho|ghu|ghs|ghr)_[A-Za-z0-9]{20,}", REDACTED),
+        (r"\bgithub_pat_[A-Za-z0-9_]{20,}", REDACTED),
+        (r"\bglpat-[A-Za-z0-9_-]{20,}", REDACTED),
+        (r"\bxox[abprs]-[A-Za-z0-9-]{10,}", REDACTED),
+        (r"\b(?:AKIA|ASIA)[0-9A-Z]{16}\b", REDACTED),
+        (r"\bAIza[0-9A-Za-z_-]{35}", REDACTED),
+        // JSON Web Tokens.
+        (
+            r"\beyJ[A-Za-z0-9_-]{8,}\.eyJ[A-Za-z0-9_-]{8,}\.[A-Za-z0-9_-]{8,}",
+            REDACTED,
+        ),
+    ])
+});
+
+static ENTROPY_TOKEN: LazyLock<Option<Regex>> =
+    LazyLock::new(|| match Regex::new(r"[A-Za-z0-9+/=_-]+") {
+        Ok(regex) => Some(regex),
+        Err(err) => {
+            warn!("invalid entropy token pattern: {err}");
+            None
+        }
+    });
+
+/// Finds one kind of secret in text.
+pub(crate) trait SecretDetector: Send + Sync {
+    /// `text` with every secret this detector finds replaced.
+    fn redact<'a>(&self, text: &'a str) -> Cow<'a, str>;
+}
+
+/// Regexes, each with the replacement of its matches.
+#[derive(Debug, Clone)]
+pub(crate) struct PatternDetector {
+    patterns: Vec<(Regex, String)>,
+}
+
+impl PatternDetector {
+    /// Invalid patterns are logged and skipped.
+    pub(crate) fn new<'a>(patterns: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let patterns = patterns
+            .into_iter()
+            .filter_map(|(pattern, replacement)| match Regex::new(pattern) {
+                Ok(regex) => Some((regex, replacement.to_string())),
+                Err(err) => {
+                    warn!("invalid secret pattern {pattern}: {err}");
+                    None
+                }
+            })
+            .collect();
+        Self { patterns }
+    }
+}
+
+impl SecretDetector for PatternDetector {
+    fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut text = Cow::Borrowed(text);
+        for (regex, replacement) in &self.patterns {
+            let replaced = match regex.replace_all(&text, replacement.as_str()) {
+                Cow::Owned(replaced) => replaced,
+                Cow::Borrowed(_) => continue,
+            };
+            text = Cow::Owned(replaced);
+        }
+        text
+    }
+}
+
+/// Long runs of letters, digits and base64 punctuation whose characters are
+/// spread evenly enough to look random, like keys in formats no pattern
+/// knows. Hex strings are left alone: they are mostly commit hashes and
+/// checksums.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct EntropyDetector {
+    pub(crate) min_len: usize,
+    pub(crate) min_bits_per_char: f64,
+}
+
+impl Default for EntropyDetector {
+    fn default() -> Self {
+        Self {
+            min_len: ENTROPY_MIN_LEN,
+            min_bits_per_char: ENTROPY_MIN_BITS_PER_CHAR,
+        }
+    }
+}
+
+impl EntropyDetector {
+    fn is_secret(&self, token: &str) -> bool {
+        token.len() >= self.min_len
+            && token.bytes().any(|byte| byte.is_ascii_digit())
+            && token.bytes().any(|byte| byte.is_ascii_alphabetic())
+            && !token.bytes().all(|byte| byte.is_ascii_hexdigit())
+            && shannon_entropy(token) >= self.min_bits_per_char
+    }
+}
+
+impl SecretDetector for EntropyDetector {
+    fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let Some(token) = ENTROPY_TOKEN.as_ref() else {
+            return Cow::Borrowed(text);
+        };
+        token.replace_all(text, |caps: &Captures| {
+            let token = &caps[0];
+            if self.is_secret(token) {
+                REDACTED.to_string()
+            } else {
+                token.to_string()
+            }
+        })
+    }
+}
+
+/// Detectors run in turn, each on the output of the previous one.
+pub(crate) struct SecretScanner {
+    detectors: Vec<Box<dyn SecretDetector>>,
+}
+
+impl SecretScanner {
+    /// The known credential formats and the entropy check.
+    pub(crate) fn new() -> Self {
+        Self {
+            detectors: vec![
+                Box::new(KNOWN_FORMATS.clone()),
+                Box::new(EntropyDetector::default()),
+            ],
+        }
+    }
+
+    /// Add a detector that runs after the others.
+    pub(crate) fn with_detector(mut self, detector: impl SecretDetector + 'static) -> Self {
+        self.detectors.push(Box::new(detector));
+        self
+    }
+
+    /// `text` with its secrets redacted, or `None` when none were found.
+    pub(crate) fn redact(&self, text: &str) -> Option<String> {
+        let mut redacted = Cow::Borrowed(text);
+        for detector in &self.detectors {
+            let replaced = match detector.redact(&redacted) {
+                Cow::Owned(replaced) => replaced,
+                Cow::Borrowed(_) => continue,
+            };
+            redacted = Cow::Owned(replaced);
+        }
+        match redacted {
+            Cow::Owned(redacted) if redacted != text => Some(redacted),
+            _ => None,
+        }
+    }
+}
+
+/// `text` with the known credential formats redacted. Unlike
+/// [`SecretScanner`], skips the entropy check, which would also hit hashes
+/// and IDs in request bodies.
+pub(crate) fn redact_secrets(text: &str) -> String {
+    KNOWN_FORMATS.redact(text).into_owned()
+}
+
+fn shannon_entropy(token: &str) -> f64 {
+    let mut counts: HashMap<u8, usize> = HashMap::new();
+    for byte in token.bytes() {
+        *counts.entry(byte).or_default() += 1;
+    }
+    let len = token.len() as f64;
+    counts
+        .values()
+        .map(|&count| {
+            let p = count as f64 / len;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn scanner_redacts_known_formats_and_random_tokens() {
+        let scanner = SecretScanner::new();
+
+        assert_eq!(
+            scanner.redact("Deploys use the key sk-proj-abcdefghijklmnopqrstuv"),
+            Some("Deploys use the key [REDACTED]".to_string())
+        );
+        assert_eq!(
+            scanner.redact("The staging token is Zq8vN3pL0xR7tYw2Kf9Hs4Mb6Jc1"),
+            Some("The staging token is [REDACTED]".to_string())
+        );
+        assert_eq!(
+            scanner.redact("Fixed in 9fceb02d0ae598e95dc970b74767f19372d61af8"),
+            None
+        );
+        assert_eq!(
+            scanner.redact("Run integration tests with `cargo nextest run --workspace`"),
+            None
+        );
+    }
+
+    #[test]
+    fn added_detectors_run_after_the_built_in_ones() {
+        struct Hostnames;
+        impl SecretDetector for Hostnames {
+            fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+                if text.contains("db.internal") {
+                    Cow::Owned(text.replace("db.internal", REDACTED))
+                } else {
+                    Cow::Borrowed(text)
+                }
+            }
+        }
+        let scanner = SecretScanner::new()
+            .with_detector(PatternDetector::new([(r"\bacme_[a-z]{8}\b", REDACTED)]))
+            .with_detector(Hostnames);
+
+        assert_eq!(
+            scanner.redact("Connect to db.internal with acme_qwertyui"),
+            Some("Connect to [REDACTED] with [REDACTED]".to_string())
+        );
+    }
+}
@@ -0,0 +1,522 @@
+//! Local embedding index for semantic code search.
+//!
+//! The index lives under `.codex/index` at the repository root and stores one
+//! embedding per chunk of each tracked or untracked-but-not-ignored file.
+//! Files are keyed by their git blob hash, so an update only re-embeds files
+//! whose contents changed since the last run.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::client::ModelClient;
+use crate::context_ignore::ContextIgnore;
+use crate::git_info::get_git_repo_root;
+
+/// Directory, relative to the repository root, that holds local indexes.
+pub(crate) const INDEX_DIR: &str = ".codex/index";
+const SEMANTIC_INDEX_FILENAME: &str = "semantic.json";
+const INDEX_VERSION: u32 = 1;
+
+const CHUNK_LINES: usize = 60;
+const MAX_CHUNK_BYTES: usize = 8 * 1024;
+const MAX_FILE_BYTES: u64 = 512 * 1024;
+const EMBED_BATCH_SIZE: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct SemanticIndex {
+    version: u32,
+    model: String,
+    /// Indexed files keyed by path relative to the repository root.
+    files: BTreeMap<String, IndexedFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct IndexedFile {
+    /// Git blob hash of the contents that were embedded.
+    blob: String,
+    chunks: Vec<IndexedChunk>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct IndexedChunk {
+    start_line: usize,
+    end_line: usize,
+    embedding: Vec<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SearchHit {
+    pub path: String,
+    pub start_line: usize,
+    pub end_line: usize,
+    pub score: f32,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct IndexUpdateStats {
+    pub files_indexed: usize,
+    pub files_unchanged: usize,
+    pub files_removed: usize,
+    pub total_chunks: usize,
+}
+
+/// The repository root an index for `cwd` is stored under, if any.
+pub(crate) fn index_root(cwd: &Path) -> Option<PathBuf> {
+    get_git_repo_root(cwd)
+}
+
+fn index_file(root: &Path) -> PathBuf {
+    root.join(INDEX_DIR).join(SEMANTIC_INDEX_FILENAME)
+}
+
+impl SemanticIndex {
+    fn new(model: &str) -> Self {
+        Self {
+            version: INDEX_VERSION,
+            model: model.to_string(),
+            files: BTreeMap::new(),
+        }
+    }
+
+    pub(crate) fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Load the index stored under `root`. Returns `None` when no index has
+    /// been built or it was written by an incompatible version.
+    pub(crate) async fn load(root: &Path) -> Result<Option<Self>> {
+        let path = index_file(root);
+        let bytes = match tokio::fs::read(&path).await {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to read {}", path.display()));
+            }
+        };
+        let index: Self = serde_json::from_slice(&bytes)
+            .with_context(|| format!("failed to parse {}", path.display()))?;
+        Ok((index.version == INDEX_VERSION).then_some(index))
+    }
+
+    async fn save(&self, root: &Path) -> Result<()> {
+        let dir = root.join(INDEX_DIR);
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .with_context(|| format!("failed to create {}", dir.display()))?;
+        // Keep the index out of `git status` without touching the user's
+        // own ignore files.
+        let gitignore = dir.join(".gitignore");
+        if !tokio::fs::try_exists(&gitignore).await.unwrap_or(false) {
+            tokio::fs::write(&gitignore, "*\n").await?;
+        }
+
+        let path = index_file(root);
+        let tmp = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp, serde_json::to_vec(self)?)
+            .await
+            .with_context(|| format!("failed to write {}", tmp.display()))?;
+        tokio::fs::rename(&tmp, &path)
+            .await
+            .with_context(|| format!("failed to replace {}", path.display()))?;
+        Ok(())
+    }
+
+    pub(crate) fn total_chunks(&self) -> usize {
+        self.files.values().map(|file| file.chunks.len()).sum()
+    }
+
+    /// Return the `limit` chunks most similar to `query`, best first.
+    pub(crate) fn search(&self, query: &[f32], limit: usize) -> Vec<SearchHit> {
+        let mut hits: Vec<SearchHit> = self
+            .files
+            .iter()
+            .flat_map(|(path, file)| {
+                file.chunks.iter().map(move |chunk| SearchHit {
+                    path: path.clone(),
+                    start_line: chunk.start_line,
+                    end_line: chunk.end_line,
+                    score: cosine_similarity(query, &chunk.embedding),
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| b.score.total_cmp(&a.score));
+        hits.truncate(limit);
+        hits
+    }
+}
+
+/// Bring the index under `root` up to date, embedding only files whose git
+/// blob hash changed. With `rebuild`, or when the provider's embedding model
+/// differs from the one the index was built with, every file is embedded
+/// again.
+pub(crate) async fn update_index(
+    client: &ModelClient,
+    root: &Path,
+    context_ignore: &ContextIgnore,
+    rebuild: bool,
+) -> Result<IndexUpdateStats> {
+    let provider = client.get_provider();
+    let Some(model) = provider.embedding_model() else {
+        bail!(
+            "provider `{}` has no `embedding_model`; set one to build a semantic index",
+            provider.name
+        );
+    };
+    let existing = if rebuild {
+        None
+    } else {
+        SemanticIndex::load(root).await?
+    };
+    let mut index = existing
+        .filter(|index| index.model == model)
+        .unwrap_or_else(|| SemanticIndex::new(model));
+
+    let blobs = visible_blobs(root, context_ignore, &[]).await?;
+    let stats = index.reconcile(client, root, blobs, |_| true).await?;
+    index.save(root).await?;
+    Ok(stats)
+}
+
+impl SemanticIndex {
+    /// Re-embed only `paths` (files or directories relative to `root`), e.g.
+    /// the paths a file watcher reported as changed since the last update.
+    pub(crate) async fn refresh_paths(
+        &mut self,
+        client: &ModelClient,
+        root: &Path,
+        context_ignore: &ContextIgnore,
+        paths: &[String],
+    ) -> Result<IndexUpdateStats> {
+        if paths.is_empty() {
+            return Ok(IndexUpdateStats {
+                total_chunks: self.total_chunks(),
+                ..Default::default()
+            });
+        }
+        let blobs = visible_blobs(root, context_ignore, paths).await?;
+        let stats = self
+            .reconcile(client, root, blobs, |path| {
+                paths.iter().any(|scope| path_in_scope(path, scope))
+            })
+            .await?;
+        self.save(root).await?;
+        Ok(stats)
+    }
+
+    /// Make the entries selected by `in_scope` match `blobs`: drop files that
+    /// disappeared and embed files that are new or whose blob changed.
+    async fn reconcile(
+        &mut self,
+        client: &ModelClient,
+        root: &Path,
+        blobs: BTreeMap<String, String>,
+        in_scope: impl Fn(&str) -> bool,
+    ) -> Result<IndexUpdateStats> {
+        let mut stats = IndexUpdateStats::default();
+        let before = self.files.len();
+        self.files
+            .retain(|path, _| !in_scope(path) || blobs.contains_key(path));
+        stats.files_removed = before - self.files.len();
+
+        let mut pending: Vec<(String, String, Vec<TextChunk>)> = Vec::new();
+        for (path, blob) in blobs {
+            if self.files.get(&path).is_some_and(|file| file.blob == blob) {
+                stats.files_unchanged += 1;
+                continue;
+            }
+            self.files.remove(&path);
+            let chunks = read_chunks(&root.join(&path)).await;
+            if !chunks.is_empty() {
+                pending.push((path, blob, chunks));
+            }
+        }
+
+        let inputs: Vec<String> = pending
+            .iter()
+            .flat_map(|(path, _, chunks)| {
+                chunks.iter().map(move |chunk| {
+                    format!(
+                        "{path}:{}-{}\n{}",
+                        chunk.start_line, chunk.end_line, chunk.text
+                    )
+                })
+            })
+            .collect();
+        let mut embeddings = Vec::with_capacity(inputs.len());
+        for batch in inputs.chunks(EMBED_BATCH_SIZE) {
+            let vectors = client
+                .embed(&self.model, batch)
+                .await
+                .context("embedding request failed")?;
+            embeddings.extend(vectors);
+        }
+
+        let mut embeddings = embeddings.into_iter();
+        for (path, blob, chunks) in pending {
+            let chunks = chunks
+                .into_iter()
+                .zip(embeddings.by_ref())
+                .map(|(chunk, embedding)| IndexedChunk {
+                    start_line: chunk.start_line,
+                    end_line: chunk.end_line,
+                    embedding,
+                })
+                .collect();
+            self.files.insert(path, IndexedFile { blob, chunks });
+            stats.files_indexed += 1;
+        }
+
+        stats.total_chunks = self.total_chunks();
+        Ok(stats)
+    }
+}
+
+/// Whether `path` is `scope` itself or lies beneath it.
+fn path_in_scope(path: &str, scope: &str) -> bool {
+    path.strip_prefix(scope)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+async fn visible_blobs(
+    root: &Path,
+    context_ignore: &ContextIgnore,
+    pathspecs: &[String],
+) -> Result<BTreeMap<String, String>> {
+    Ok(list_file_blobs(root, pathspecs)
+        .await?
+        .into_iter()
+        .filter(|(path, _)| !context_ignore.is_ignored(&root.join(path), false))
+        .collect())
+}
+
+/// List tracked and untracked-but-not-ignored files under `root` with the
+/// git blob hash of their current working-tree contents. A non-empty
+/// `pathspecs` limits the listing to those literal paths.
+async fn list_file_blobs(root: &Path, pathspecs: &[String]) -> Result<Vec<(String, String)>> {
+    let output = Command::new("git")
+        .args([
+            "--literal-pathspecs",
+            "ls-files",
+            "-z",
+            "--cached",
+            "--others",
+            "--exclude-standard",
+            "--",
+        ])
+        .args(pathspecs)
+        .current_dir(root)
+        .output()
+        .await
+        .context("failed to run git ls-files")?;
+    if !output.status.success() {
+        bail!(
+            "git ls-files failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let mut paths: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty() && !path.contains('\n'))
+        .filter(|path| !path.starts_with(INDEX_DIR))
+        .filter(|path| root.join(path).is_file())
+        .map(str::to_string)
+        .collect();
+    paths.sort();
+    paths.dedup();
+    if paths.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut child = Command::new("git")
+        .args(["hash-object", "--stdin-paths"])
+        .current_dir(root)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to run git hash-object")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(paths.join("\n").as_bytes()).await?;
+        stdin.write_all(b"\n").await?;
+    }
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        bail!(
+            "git hash-object failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    let hashes: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect();
+    if hashes.len() != paths.len() {
+        bail!(
+            "git hash-object returned {} hashes for {} files",
+            hashes.len(),
+            paths.len()
+        );
+    }
+    Ok(paths.into_iter().zip(hashes).collect())
+}
+
+#[derive(Debug, PartialEq)]
+struct TextChunk {
+    start_line: usize,
+    end_line: usize,
+    text: String,
+}
+
+async fn read_chunks(path: &Path) -> Vec<TextChunk> {
+    let Ok(metadata) = tokio::fs::metadata(path).await else {
+        return Vec::new();
+    };
+    if metadata.len() > MAX_FILE_BYTES {
+        return Vec::new();
+    }
+    match tokio::fs::read(path).await.map(String::from_utf8) {
+        Ok(Ok(contents)) => chunk_text(&contents),
+        // Binary or unreadable files are not indexed.
+        _ => Vec::new(),
+    }
+}
+
+fn chunk_text(contents: &str) -> Vec<TextChunk> {
+    let lines: Vec<&str> = contents.lines().collect();
+    lines
+        .chunks(CHUNK_LINES)
+        .enumerate()
+        .filter(|(_, chunk)| chunk.iter().any(|line| !line.trim().is_empty()))
+        .map(|(index, chunk)| {
+            let start_line = index * CHUNK_LINES + 1;
+            let mut text = chunk.join("\n");
+            if text.len() > MAX_CHUNK_BYTES {
+                let mut end = MAX_CHUNK_BYTES;
+                while !text.is_char_boundary(end) {
+                    end -= 1;
+                }
+                text.truncate(end);
+            }
+            TextChunk {
+                start_line,
+                end_line: start_line + chunk.len() - 1,
+                text,
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn file(blob: &str, embeddings: &[&[f32]]) -> IndexedFile {
+        IndexedFile {
+            blob: blob.to_string(),
+            chunks: embeddings
+                .iter()
+                .enumerate()
+                .map(|(index, embedding)| IndexedChunk {
+                    start_line: index * CHUNK_LINES + 1,
+                    end_line: (index + 1) * CHUNK_LINES,
+                    embedding: embedding.to_vec(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn chunks_skip_blank_windows() {
+        let mut contents = vec!["fn main() {}"];
+        contents.extend(std::iter::repeat_n("", CHUNK_LINES * 2));
+        contents.push("fn tail() {}");
+        let chunks = chunk_text(&contents.join("\n"));
+        assert_eq!(
+            chunks
+                .iter()
+                .map(|chunk| (chunk.start_line, chunk.end_line))
+                .collect::<Vec<_>>(),
+            vec![(1, CHUNK_LINES), (2 * CHUNK_LINES + 1, 2 * CHUNK_LINES + 2)]
+        );
+    }
+
+    #[test]
+    fn search_ranks_by_cosine_similarity() {
+        let mut index = SemanticIndex::new("text-embedding-3-small");
+        index
+            .files
+            .insert("a.rs".to_string(), file("1", &[&[1.0, 0.0], &[3.0, 4.0]]));
+        index
+            .files
+            .insert("b.rs".to_string(), file("2", &[&[0.0, 1.0]]));
+
+        let hits = index.search(&[0.0, 2.0], 2);
+        assert_eq!(
+            hits,
+            vec![
+                SearchHit {
+                    path: "b.rs".to_string(),
+                    start_line: 1,
+                    end_line: CHUNK_LINES,
+                    score: 1.0,
+                },
+                SearchHit {
+                    path: "a.rs".to_string(),
+                    start_line: CHUNK_LINES + 1,
+                    end_line: 2 * CHUNK_LINES,
+                    score: 0.8,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn scopes_match_files_and_directories() {
+        assert!(path_in_scope("src/lib.rs", "src/lib.rs"));
+        assert!(path_in_scope("src/lib.rs", "src"));
+        assert!(!path_in_scope("src2/lib.rs", "src"));
+        assert!(!path_in_scope("src", "src/lib.rs"));
+    }
+
+    #[tokio::test]
+    async fn save_and_load_round_trip() {
+        let dir = tempdir().expect("tempdir");
+        let mut index = SemanticIndex::new("text-embedding-3-small");
+        index
+            .files
+            .insert("src/lib.rs".to_string(), file("abc", &[&[0.5, 0.5]]));
+
+        index.save(dir.path()).await.expect("save");
+        let loaded = SemanticIndex::load(dir.path()).await.expect("load");
+        assert_eq!(loaded, Some(index));
+        assert!(dir.path().join(INDEX_DIR).join(".gitignore").is_file());
+    }
+}
@@ -0,0 +1,172 @@
+//! Snapshot of the machine a session runs on.
+//!
+//! Collected once at startup and recorded in the rollout's session metadata.
+//! With the `environment_snapshot` feature it is also given to the model as a
+//! compact `<system_info>` block, so it does not have to probe for the OS or
+//! tool versions itself.
+
+use std::collections::BTreeMap;
+use std::process::Stdio;
+use std::time::Duration;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::SessionEnvironment;
+use futures::future::join_all;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+use crate::environment_context::sandbox_mode_for_policy;
+use crate::protocol::SandboxPolicy;
+use crate::shell::default_user_shell;
+
+const SYSTEM_INFO_OPEN_TAG: &str = "<system_info>";
+const SYSTEM_INFO_CLOSE_TAG: &str = "</system_info>";
+
+/// Tools whose versions are worth knowing up front.
+const PROBED_TOOLS: &[&str] = &["git", "cargo", "node", "python3"];
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+pub(crate) async fn collect_session_environment(
+    sandbox_policy: &SandboxPolicy,
+) -> SessionEnvironment {
+    let os_info = os_info::get();
+    let version = os_info.version().to_string();
+    let tool_versions = join_all(PROBED_TOOLS.iter().map(|tool| async move {
+        probe_version(tool)
+            .await
+            .map(|version| ((*tool).to_string(), version))
+    }))
+    .await
+    .into_iter()
+    .flatten()
+    .collect::<BTreeMap<_, _>>();
+
+    SessionEnvironment {
+        os: os_info.os_type().to_string(),
+        os_version: (version != "Unknown").then_some(version),
+        arch: std::env::consts::ARCH.to_string(),
+        shell: default_user_shell().name().to_string(),
+        sandbox_mode: Some(sandbox_mode_for_policy(sandbox_policy)),
+        tool_versions,
+    }
+}
+
+async fn probe_version(tool: &str) -> Option<String> {
+    let output = timeout(
+        PROBE_TIMEOUT,
+        Command::new(tool)
+            .arg("--version")
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .ok()?
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_version(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Pull the version number out of `--version` output such as
+/// `git version 2.43.0` or `v20.11.0`.
+fn parse_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .next()?
+        .split_whitespace()
+        .map(|word| word.trim_start_matches('v'))
+        .find(|word| word.starts_with(|c: char| c.is_ascii_digit()))
+        .map(str::to_string)
+}
+
+/// Model-facing rendering of a [`SessionEnvironment`]. The shell and sandbox
+/// mode are omitted because `<environment_context>` already carries them.
+pub(crate) struct SystemInfo<'a>(pub &'a SessionEnvironment);
+
+impl SystemInfo<'_> {
+    pub fn is_system_info(message: &[ContentItem]) -> bool {
+        if let [ContentItem::InputText { text }] = message {
+            text.starts_with(SYSTEM_INFO_OPEN_TAG)
+        } else {
+            false
+        }
+    }
+
+    fn serialize_to_xml(&self) -> String {
+        let environment = self.0;
+        let os = match &environment.os_version {
+            Some(version) => format!("{} {version}", environment.os),
+            None => environment.os.clone(),
+        };
+        let mut lines = vec![
+            SYSTEM_INFO_OPEN_TAG.to_string(),
+            format!("  <os>{os} ({})</os>", environment.arch),
+        ];
+        if !environment.tool_versions.is_empty() {
+            let tools = environment
+                .tool_versions
+                .iter()
+                .map(|(tool, version)| format!("{tool} {version}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            lines.push(format!("  <tools>{tools}</tools>"));
+        }
+        lines.push(SYSTEM_INFO_CLOSE_TAG.to_string());
+        lines.join("\n")
+    }
+}
+
+impl From<SystemInfo<'_>> for ResponseItem {
+    fn from(info: SystemInfo<'_>) -> Self {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: info.serialize_to_xml(),
+            }],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_common_version_outputs() {
+        assert_eq!(
+            parse_version("git version 2.43.0\n"),
+            Some("2.43.0".to_string())
+        );
+        assert_eq!(
+            parse_version("cargo 1.90.0 (840b83a10 2025-07-30)\n"),
+            Some("1.90.0".to_string())
+        );
+        assert_eq!(parse_version("v20.11.0\n"), Some("20.11.0".to_string()));
+        assert_eq!(parse_version("Python 3.12.1"), Some("3.12.1".to_string()));
+        assert_eq!(parse_version("no version here"), None);
+    }
+
+    #[test]
+    fn renders_compact_system_info() {
+        let environment = SessionEnvironment {
+            os: "Ubuntu".to_string(),
+            os_version: Some("24.04".to_string()),
+            arch: "x86_64".to_string(),
+            shell: "bash".to_string(),
+            sandbox_mode: None,
+            tool_versions: BTreeMap::from([
+                ("cargo".to_string(), "1.90.0".to_string()),
+                ("git".to_string(), "2.43.0".to_string()),
+            ]),
+        };
+        assert_eq!(
+            SystemInfo(&environment).serialize_to_xml(),
+            "<system_info>\n  <os>Ubuntu 24.04 (x86_64)</os>\n  <tools>cargo 1.90.0, git 2.43.0</tools>\n</system_info>"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! How long each step of session startup took, logged once the session is
+//! configured so slow starts can be traced to a step.
+
+use std::future::Future;
+use std::time::Duration;
+use std::time::Instant;
+
+#[derive(Debug)]
+pub(crate) struct StartupTimings {
+    started: Instant,
+    phases: Vec<(&'static str, Duration)>,
+}
+
+impl StartupTimings {
+    pub(crate) fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            phases: Vec::new(),
+        }
+    }
+
+    pub(crate) fn record(&mut self, phase: &'static str, elapsed: Duration) {
+        self.phases.push((phase, elapsed));
+    }
+
+    /// Run `fut` and record how long it took under `phase`.
+    pub(crate) async fn time<T>(&mut self, phase: &'static str, fut: impl Future<Output = T>) -> T {
+        let (output, elapsed) = timed(fut).await;
+        self.record(phase, elapsed);
+        output
+    }
+
+    /// One line with the total and every step, slowest first. Steps that ran
+    /// concurrently overlap, so they can add up to more than the total.
+    pub(crate) fn report(&self) -> String {
+        let mut phases = self.phases.clone();
+        phases.sort_by(|a, b| b.1.cmp(&a.1));
+        let steps = phases
+            .iter()
+            .map(|(phase, elapsed)| format!("{phase} {}ms", elapsed.as_millis()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!(
+            "session startup took {}ms ({steps})",
+            self.started.elapsed().as_millis()
+        )
+    }
+}
+
+/// Run `fut` and return its output with how long it took, for steps that run
+/// concurrently and are recorded afterwards.
+pub(crate) async fn timed<T>(fut: impl Future<Output = T>) -> (T, Duration) {
+    let started = Instant::now();
+    let output = fut.await;
+    (output, started.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn report_lists_slowest_steps_first() {
+        let mut timings = StartupTimings::new();
+        timings.record("rollout", Duration::from_millis(40));
+        timings.record("project_docs", Duration::from_millis(250));
+        timings.record("exec_policy", Duration::from_millis(3));
+
+        let report = timings.report();
+        let steps = report
+            .split_once(" (")
+            .map(|(_, steps)| steps.trim_end_matches(')'))
+            .expect("steps in parentheses");
+        assert_eq!(steps, "project_docs 250ms, rollout 40ms, exec_policy 3ms");
+        assert!(report.starts_with("session startup took "));
+    }
+}
@@ -2,13 +2,23 @@ use std::sync::Arc;
 
 use crate::AuthManager;
 use crate::RolloutRecorder;
+use crate::agent_budget::AgentBudget;
+use crate::annotations::Annotations;
+use crate::index_watcher::IndexWatcher;
 use crate::mcp_connection_manager::McpConnectionManager;
+use crate::memories::MemoryRanker;
 use crate::openai_models::models_manager::ModelsManager;
+use crate::output_spill::OutputSpill;
+use crate::repo_map::RepoMapCache;
+use crate::scratch_dir::ScratchDir;
 use crate::skills::SkillsManager;
+use crate::tool_hints::ToolUsageTracker;
 use crate::tools::sandboxing::ApprovalStore;
 use crate::unified_exec::UnifiedExecSessionManager;
+use crate::usage_stats::UsageStatsRecorder;
 use crate::user_notification::UserNotifier;
 use codex_otel::otel_manager::OtelManager;
+use codex_protocol::protocol::SessionEnvironment;
 use tokio::sync::Mutex;
 use tokio::sync::RwLock;
 use tokio_util::sync::CancellationToken;
@@ -20,10 +30,28 @@ pub(crate) struct SessionServices {
     pub(crate) notifier: UserNotifier,
     pub(crate) rollout: Mutex<Option<RolloutRecorder>>,
     pub(crate) user_shell: Arc<crate::shell::Shell>,
-    pub(crate) show_raw_agent_reasoning: bool,
     pub(crate) auth_manager: Arc<AuthManager>,
     pub(crate) models_manager: Arc<ModelsManager>,
     pub(crate) otel_manager: OtelManager,
     pub(crate) tool_approvals: Mutex<ApprovalStore>,
     pub(crate) skills_manager: Arc<SkillsManager>,
+    /// Watches the repository for changes that invalidate local indexes.
+    pub(crate) index_watcher: Option<Arc<IndexWatcher>>,
+    pub(crate) repo_map_cache: RepoMapCache,
+    /// OS and tool versions captured at startup.
+    pub(crate) session_environment: SessionEnvironment,
+    /// Per-session temporary directory, when enabled.
+    pub(crate) scratch_dir: Option<ScratchDir>,
+    /// Full output of over-budget commands, when enabled.
+    pub(crate) output_spill: Option<OutputSpill>,
+    /// Successful tool calls counted for the project's tool hints, when enabled.
+    pub(crate) tool_usage: Option<ToolUsageTracker>,
+    /// Memories sent per turn because they exceed the `[memories]` limits.
+    pub(crate) memory_ranker: Option<MemoryRanker>,
+    /// Aggregate counts for `codex stats` and any shared telemetry.
+    pub(crate) usage_stats: Option<UsageStatsRecorder>,
+    /// Limits this session and its sub-agents draw on.
+    pub(crate) agent_budget: Arc<AgentBudget>,
+    /// Message count and annotations, kept in step with the rollout.
+    pub(crate) annotations: std::sync::Mutex<Annotations>,
 }
@@ -1,19 +1,42 @@
 //! Session-wide mutable state.
 
+use std::collections::HashMap;
+
+use codex_protocol::config_types::ReasoningDisplay;
 use codex_protocol::models::ResponseItem;
 
 use crate::codex::SessionConfiguration;
 use crate::context_manager::ContextManager;
+use crate::context_manager::HistoryFilterReport;
+use crate::external_edits::WatchedFiles;
+use crate::file_mentions::AttachedFiles;
 use crate::protocol::RateLimitSnapshot;
 use crate::protocol::TokenUsage;
 use crate::protocol::TokenUsageInfo;
 use crate::truncate::TruncationPolicy;
+use crate::turn_ledger::TurnLedger;
+use tokio::sync::oneshot;
 
 /// Persistent, session-scoped state previously stored directly on `Session`.
 pub(crate) struct SessionState {
     pub(crate) session_configuration: SessionConfiguration,
     pub(crate) history: ContextManager,
     pub(crate) latest_rate_limits: Option<RateLimitSnapshot>,
+    pub(crate) reasoning_display: ReasoningDisplay,
+    /// Files attached via `@path` mentions that are still present in history.
+    pub(crate) attached_files: AttachedFiles,
+    /// Files the model read or edited, as Codex last saw them.
+    pub(crate) watched_files: WatchedFiles,
+    /// What patched files held before each finished turn.
+    pub(crate) turn_ledger: TurnLedger,
+    /// Provider that produced the encrypted reasoning in history, if known.
+    pub(crate) reasoning_provider: Option<String>,
+    /// Memories extracted from finished turns that wait for the user, by
+    /// turn id. They outlive the turn, so they are not kept in `TurnState`.
+    pub(crate) pending_memory_reviews: HashMap<String, oneshot::Sender<Vec<usize>>>,
+    /// What `[history_filter]` left out of the last request, so it is only
+    /// reported when that changes.
+    pub(crate) history_filter_report: HistoryFilterReport,
 }
 
 impl SessionState {
@@ -24,16 +47,24 @@ impl SessionState {
             session_configuration,
             history,
             latest_rate_limits: None,
+            reasoning_display: ReasoningDisplay::default(),
+            attached_files: AttachedFiles::new(),
+            watched_files: WatchedFiles::new(),
+            turn_ledger: TurnLedger::default(),
+            reasoning_provider: None,
+            pending_memory_reviews: HashMap::new(),
+            history_filter_report: HistoryFilterReport::default(),
         }
     }
 
     // History helpers
-    pub(crate) fn record_items<I>(&mut self, items: I, policy: TruncationPolicy)
+    /// Returns how many bytes of tool output truncation dropped.
+    pub(crate) fn record_items<I>(&mut self, items: I, policy: TruncationPolicy) -> usize
     where
         I: IntoIterator,
         I::Item: std::ops::Deref<Target = ResponseItem>,
     {
-        self.history.record_items(items, policy);
+        self.history.record_items(items, policy)
     }
 
     pub(crate) fn clone_history(&self) -> ContextManager {
@@ -42,6 +73,8 @@ impl SessionState {
 
     pub(crate) fn replace_history(&mut self, items: Vec<ResponseItem>) {
         self.history.replace(items);
+        // Attachments may no longer be in history, so allow them to be re-sent.
+        self.attached_files.clear();
     }
 
     pub(crate) fn set_token_info(&mut self, info: Option<TokenUsageInfo>) {
@@ -57,6 +90,15 @@ impl SessionState {
         self.history.update_token_info(usage, model_context_window);
     }
 
+    pub(crate) fn add_side_token_usage(
+        &mut self,
+        usage: &TokenUsage,
+        model_context_window: Option<i64>,
+    ) {
+        self.history
+            .add_side_token_usage(usage, model_context_window);
+    }
+
     pub(crate) fn token_info(&self) -> Option<TokenUsageInfo> {
         self.history.token_info()
     }
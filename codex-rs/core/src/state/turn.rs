@@ -3,16 +3,22 @@
 use indexmap::IndexMap;
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 use tokio::sync::Mutex;
 use tokio::sync::Notify;
 use tokio_util::sync::CancellationToken;
 use tokio_util::task::AbortOnDropHandle;
 
 use codex_protocol::models::ResponseInputItem;
+use codex_protocol::plan_tool::PlanItemArg;
+use codex_protocol::plan_tool::StepStatus;
 use tokio::sync::oneshot;
 
 use crate::codex::TurnContext;
+use crate::protocol::PatchConflictResolution;
 use crate::protocol::ReviewDecision;
+use crate::protocol::TurnCompletedMetricsEvent;
+use crate::protocol::TurnProgressEvent;
 use crate::tasks::SessionTask;
 
 /// Metadata about the currently running turn.
@@ -67,7 +73,61 @@ impl ActiveTurn {
 #[derive(Default)]
 pub(crate) struct TurnState {
     pending_approvals: HashMap<String, oneshot::Sender<ReviewDecision>>,
+    pending_conflicts: HashMap<String, oneshot::Sender<PatchConflictResolution>>,
+    pending_questions: HashMap<String, oneshot::Sender<Option<String>>>,
+    pending_candidate_selections: HashMap<String, oneshot::Sender<Option<usize>>>,
     pending_input: Vec<ResponseInputItem>,
+    /// Set once the turn has made its last model request; input injected
+    /// after that would never reach the model.
+    input_closed: bool,
+    progress: TurnProgress,
+    /// Totals for `EventMsg::TurnCompletedMetrics`; the duration is filled
+    /// in when the turn ends.
+    metrics: TurnCompletedMetricsEvent,
+    stall_retried: bool,
+}
+
+/// What `EventMsg::TurnProgress` reports for the turn.
+struct TurnProgress {
+    started_at: Instant,
+    plan: Vec<PlanItemArg>,
+    tool_calls: u64,
+}
+
+impl Default for TurnProgress {
+    fn default() -> Self {
+        Self {
+            started_at: Instant::now(),
+            plan: Vec::new(),
+            tool_calls: 0,
+        }
+    }
+}
+
+impl TurnProgress {
+    fn event(&self) -> TurnProgressEvent {
+        let current_step = self
+            .plan
+            .iter()
+            .find(|item| matches!(item.status, StepStatus::InProgress))
+            .or_else(|| {
+                self.plan
+                    .iter()
+                    .find(|item| matches!(item.status, StepStatus::Pending))
+            })
+            .map(|item| item.step.clone());
+        TurnProgressEvent {
+            current_step,
+            steps_completed: self
+                .plan
+                .iter()
+                .filter(|item| matches!(item.status, StepStatus::Completed))
+                .count(),
+            steps_total: self.plan.len(),
+            tool_calls: self.tool_calls,
+            elapsed_ms: u64::try_from(self.started_at.elapsed().as_millis()).unwrap_or(u64::MAX),
+        }
+    }
 }
 
 impl TurnState {
@@ -86,8 +146,56 @@ impl TurnState {
         self.pending_approvals.remove(key)
     }
 
+    pub(crate) fn insert_pending_conflict(
+        &mut self,
+        key: String,
+        tx: oneshot::Sender<PatchConflictResolution>,
+    ) -> Option<oneshot::Sender<PatchConflictResolution>> {
+        self.pending_conflicts.insert(key, tx)
+    }
+
+    pub(crate) fn remove_pending_conflict(
+        &mut self,
+        key: &str,
+    ) -> Option<oneshot::Sender<PatchConflictResolution>> {
+        self.pending_conflicts.remove(key)
+    }
+
+    pub(crate) fn insert_pending_question(
+        &mut self,
+        key: String,
+        tx: oneshot::Sender<Option<String>>,
+    ) -> Option<oneshot::Sender<Option<String>>> {
+        self.pending_questions.insert(key, tx)
+    }
+
+    pub(crate) fn remove_pending_question(
+        &mut self,
+        key: &str,
+    ) -> Option<oneshot::Sender<Option<String>>> {
+        self.pending_questions.remove(key)
+    }
+
+    pub(crate) fn insert_pending_candidate_selection(
+        &mut self,
+        key: String,
+        tx: oneshot::Sender<Option<usize>>,
+    ) -> Option<oneshot::Sender<Option<usize>>> {
+        self.pending_candidate_selections.insert(key, tx)
+    }
+
+    pub(crate) fn remove_pending_candidate_selection(
+        &mut self,
+        key: &str,
+    ) -> Option<oneshot::Sender<Option<usize>>> {
+        self.pending_candidate_selections.remove(key)
+    }
+
     pub(crate) fn clear_pending(&mut self) {
         self.pending_approvals.clear();
+        self.pending_conflicts.clear();
+        self.pending_questions.clear();
+        self.pending_candidate_selections.clear();
         self.pending_input.clear();
     }
 
@@ -104,6 +212,53 @@ impl TurnState {
             ret
         }
     }
+
+    pub(crate) fn accepts_input(&self) -> bool {
+        !self.input_closed
+    }
+
+    /// Stop accepting input unless some is already waiting. Returns whether
+    /// the turn may finish, i.e. no input is left for the model to answer.
+    pub(crate) fn close_input_if_idle(&mut self) -> bool {
+        if self.pending_input.is_empty() {
+            self.input_closed = true;
+        }
+        self.input_closed
+    }
+
+    /// Whether a stalled model request may be retried: true only the first
+    /// time it is asked in a turn.
+    pub(crate) fn take_stall_retry(&mut self) -> bool {
+        !std::mem::replace(&mut self.stall_retried, true)
+    }
+
+    pub(crate) fn set_plan(&mut self, plan: Vec<PlanItemArg>) {
+        self.progress.plan = plan;
+    }
+
+    /// Count a finished tool call and return the turn's progress.
+    pub(crate) fn record_tool_call(&mut self, tool_name: &str) -> TurnProgressEvent {
+        self.progress.tool_calls += 1;
+        *self
+            .metrics
+            .tool_calls
+            .entry(tool_name.to_string())
+            .or_default() += 1;
+        self.progress.event()
+    }
+
+    pub(crate) fn metrics_mut(&mut self) -> &mut TurnCompletedMetricsEvent {
+        &mut self.metrics
+    }
+
+    /// The turn's totals, with the time since it started.
+    pub(crate) fn completed_metrics(&self) -> TurnCompletedMetricsEvent {
+        TurnCompletedMetricsEvent {
+            duration_ms: u64::try_from(self.progress.started_at.elapsed().as_millis())
+                .unwrap_or(u64::MAX),
+            ..self.metrics.clone()
+        }
+    }
 }
 
 impl ActiveTurn {
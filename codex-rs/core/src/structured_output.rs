@@ -0,0 +1,258 @@
+//! Values of a response constrained by `output_schema`, reported as soon as
+//! they are complete so clients can show e.g. findings one by one instead of
+//! waiting for the final message.
+
+use serde_json::Value;
+
+/// A value that finished streaming, with its JSON Pointer in the response.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PartialValue {
+    pub(crate) pointer: String,
+    pub(crate) value: Value,
+}
+
+#[derive(Debug)]
+struct Container {
+    pointer: String,
+    is_array: bool,
+    /// Index of the array element being read.
+    index: usize,
+    /// Key of the object member being read.
+    key: Option<String>,
+    /// Offset in the buffer where the element or member value started.
+    value_start: Option<usize>,
+}
+
+/// Incremental scanner over the text deltas of one message. Reports every
+/// completed array element, at any depth, and every completed member of the
+/// top-level object. A document followed by another one, as in
+/// newline-delimited JSON, is read as a new document. Text that is not JSON
+/// stops the scan for the rest of the message.
+#[derive(Debug, Default)]
+pub(crate) struct StructuredOutputParser {
+    buf: String,
+    /// Offset of the first byte not scanned yet.
+    pos: usize,
+    stack: Vec<Container>,
+    in_string: bool,
+    escaped: bool,
+    string_start: usize,
+    string_is_key: bool,
+    scalar_start: Option<usize>,
+    /// Offset where the current top-level document started.
+    root_start: Option<usize>,
+    failed: bool,
+}
+
+impl StructuredOutputParser {
+    /// Add `delta` to the message and return the values it completed.
+    pub(crate) fn push(&mut self, delta: &str) -> Vec<PartialValue> {
+        let mut completed = Vec::new();
+        if self.failed {
+            return completed;
+        }
+        self.buf.push_str(delta);
+        while !self.failed && self.pos < self.buf.len() {
+            let offset = self.pos;
+            let byte = self.buf.as_bytes()[offset];
+            self.pos += 1;
+
+            if self.in_string {
+                if self.escaped {
+                    self.escaped = false;
+                } else if byte == b'\\' {
+                    self.escaped = true;
+                } else if byte == b'"' {
+                    self.in_string = false;
+                    self.end_string(offset + 1, &mut completed);
+                }
+                continue;
+            }
+            if self.scalar_start.is_some() {
+                if is_scalar_byte(byte) {
+                    continue;
+                }
+                self.scalar_start = None;
+                self.end_value(offset, &mut completed);
+            }
+
+            match byte {
+                b'"' => {
+                    self.in_string = true;
+                    self.string_start = offset;
+                    self.string_is_key = self
+                        .stack
+                        .last()
+                        .is_some_and(|container| !container.is_array && container.key.is_none());
+                    if !self.string_is_key {
+                        self.begin_value(offset);
+                    }
+                }
+                b'{' | b'[' => {
+                    self.begin_value(offset);
+                    let pointer = self.child_pointer();
+                    self.stack.push(Container {
+                        pointer,
+                        is_array: byte == b'[',
+                        index: 0,
+                        key: None,
+                        value_start: None,
+                    });
+                }
+                b'}' | b']' => {
+                    if self.stack.pop().is_none() {
+                        self.failed = true;
+                    } else {
+                        self.end_value(offset + 1, &mut completed);
+                    }
+                }
+                b',' | b':' | b' ' | b'\t' | b'\n' | b'\r' => {}
+                byte if is_scalar_byte(byte) => {
+                    self.begin_value(offset);
+                    self.scalar_start = Some(offset);
+                }
+                _ => self.failed = true,
+            }
+        }
+        completed
+    }
+
+    fn begin_value(&mut self, offset: usize) {
+        match self.stack.last_mut() {
+            Some(parent) => parent.value_start = Some(offset),
+            None => self.root_start = Some(offset),
+        }
+    }
+
+    fn end_string(&mut self, end: usize, completed: &mut Vec<PartialValue>) {
+        if !self.string_is_key {
+            self.end_value(end, completed);
+            return;
+        }
+        match serde_json::from_str::<String>(&self.buf[self.string_start..end]) {
+            Ok(key) => {
+                if let Some(parent) = self.stack.last_mut() {
+                    parent.key = Some(key);
+                }
+            }
+            Err(_) => self.failed = true,
+        }
+    }
+
+    /// The value of the innermost container's current element or member
+    /// ended before `end`.
+    fn end_value(&mut self, end: usize, completed: &mut Vec<PartialValue>) {
+        let depth = self.stack.len();
+        let Some(parent) = self.stack.last_mut() else {
+            // A whole document ended; anything but JSON before it means the
+            // message is not JSON at all.
+            if let Some(start) = self.root_start.take()
+                && serde_json::from_str::<Value>(&self.buf[start..end]).is_err()
+            {
+                self.failed = true;
+            }
+            return;
+        };
+        let Some(start) = parent.value_start.take() else {
+            return;
+        };
+        let token = if parent.is_array {
+            parent.index += 1;
+            Some((parent.index - 1).to_string())
+        } else {
+            parent.key.take()
+        };
+        if (parent.is_array || depth == 1)
+            && let Some(token) = token
+            && let Ok(value) = serde_json::from_str(&self.buf[start..end])
+        {
+            completed.push(PartialValue {
+                pointer: format!("{}/{}", parent.pointer, escape_pointer_token(&token)),
+                value,
+            });
+        }
+    }
+
+    /// Pointer of a container that starts as the innermost container's
+    /// current element or member.
+    fn child_pointer(&self) -> String {
+        match self.stack.last() {
+            Some(parent) if parent.is_array => format!("{}/{}", parent.pointer, parent.index),
+            Some(parent) => format!(
+                "{}/{}",
+                parent.pointer,
+                escape_pointer_token(parent.key.as_deref().unwrap_or_default())
+            ),
+            None => String::new(),
+        }
+    }
+}
+
+fn is_scalar_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'+' | b'.')
+}
+
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    fn push_in_chunks(parser: &mut StructuredOutputParser, text: &str) -> Vec<PartialValue> {
+        let chars: Vec<char> = text.chars().collect();
+        chars
+            .chunks(3)
+            .flat_map(|chunk| parser.push(&chunk.iter().collect::<String>()))
+            .collect()
+    }
+
+    fn partial(pointer: &str, value: Value) -> PartialValue {
+        PartialValue {
+            pointer: pointer.to_string(),
+            value,
+        }
+    }
+
+    #[test]
+    fn reports_array_elements_and_top_level_members_as_they_complete() {
+        let mut parser = StructuredOutputParser::default();
+        let text = r#"{"summary": "2 issues", "findings": [{"title": "Leak \"a\"", "line": 12}, {"title": "b/c", "tags": ["perf"]}], "ok": false}"#;
+
+        assert_eq!(
+            push_in_chunks(&mut parser, text),
+            vec![
+                partial("/summary", json!("2 issues")),
+                partial("/findings/0", json!({"title": "Leak \"a\"", "line": 12})),
+                partial("/findings/1/tags/0", json!("perf")),
+                partial("/findings/1", json!({"title": "b/c", "tags": ["perf"]})),
+                partial(
+                    "/findings",
+                    json!([
+                        {"title": "Leak \"a\"", "line": 12},
+                        {"title": "b/c", "tags": ["perf"]}
+                    ])
+                ),
+                partial("/ok", json!(false)),
+            ]
+        );
+    }
+
+    #[test]
+    fn reads_newline_delimited_documents_and_stops_at_prose() {
+        let mut parser = StructuredOutputParser::default();
+        assert_eq!(
+            push_in_chunks(&mut parser, "{\"a/b\": 1}\n{\"a/b\": 2.5}\n"),
+            vec![partial("/a~1b", json!(1)), partial("/a~1b", json!(2.5))]
+        );
+
+        let mut parser = StructuredOutputParser::default();
+        assert_eq!(
+            push_in_chunks(&mut parser, "Here you go: {\"a\": 1}"),
+            Vec::new()
+        );
+    }
+}
@@ -2,8 +2,11 @@ mod compact;
 mod ghost_snapshot;
 mod regular;
 mod review;
+mod semantic_index;
+mod session_summary;
 mod undo;
 mod user_shell;
+mod workflow;
 
 use std::sync::Arc;
 use std::time::Duration;
@@ -33,8 +36,11 @@ pub(crate) use compact::CompactTask;
 pub(crate) use ghost_snapshot::GhostSnapshotTask;
 pub(crate) use regular::RegularTask;
 pub(crate) use review::ReviewTask;
+pub(crate) use semantic_index::SemanticIndexTask;
+pub(crate) use session_summary::SessionSummaryTask;
 pub(crate) use undo::UndoTask;
 pub(crate) use user_shell::UserShellCommandTask;
+pub(crate) use workflow::WorkflowTask;
 
 const GRACEFULL_INTERRUPTION_TIMEOUT_MS: u64 = 100;
 
@@ -168,6 +174,10 @@ impl Session {
         last_agent_message: Option<String>,
     ) {
         let mut active = self.active_turn.lock().await;
+        let metrics = match active.as_ref() {
+            Some(at) => Some(at.turn_state.lock().await.completed_metrics()),
+            None => None,
+        };
         let should_close_sessions = if let Some(at) = active.as_mut()
             && at.remove_task(&turn_context.sub_id)
         {
@@ -180,6 +190,13 @@ impl Session {
         if should_close_sessions {
             self.close_unified_exec_sessions().await;
         }
+        if let Some(metrics) = metrics {
+            self.send_event(
+                turn_context.as_ref(),
+                EventMsg::TurnCompletedMetrics(metrics),
+            )
+            .await;
+        }
         let event = EventMsg::TaskComplete(TaskCompleteEvent { last_agent_message });
         self.send_event(turn_context.as_ref(), event).await;
     }
@@ -234,6 +251,8 @@ impl Session {
         session_task
             .abort(session_ctx, Arc::clone(&task.turn_context))
             .await;
+        // The task may have edited watched files before it was stopped.
+        self.refresh_watched_files().await;
 
         let event = EventMsg::TurnAborted(TurnAbortedEvent { reason });
         self.send_event(task.turn_context.as_ref(), event).await;
@@ -11,6 +11,7 @@ use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::ExitedReviewModeEvent;
 use codex_protocol::protocol::ItemCompletedEvent;
 use codex_protocol::protocol::ReviewOutputEvent;
+use codex_protocol::protocol::SubAgentSource;
 use tokio_util::sync::CancellationToken;
 
 use crate::codex::Session;
@@ -92,7 +93,7 @@ async fn start_review_conversation(
     sub_agent_config.base_instructions = Some(crate::REVIEW_PROMPT.to_string());
 
     sub_agent_config.model = Some(config.review_model.clone());
-    (run_codex_conversation_one_shot(
+    match run_codex_conversation_one_shot(
         sub_agent_config,
         session.auth_manager(),
         session.models_manager(),
@@ -101,10 +102,19 @@ async fn start_review_conversation(
         ctx.clone(),
         cancellation_token,
         None,
+        SubAgentSource::Review,
     )
-    .await)
-        .ok()
-        .map(|io| io.rx_event)
+    .await
+    {
+        Ok(io) => Some(io.rx_event),
+        Err(err) => {
+            session
+                .clone_session()
+                .send_event(ctx.as_ref(), EventMsg::Error(err.to_error_event(None)))
+                .await;
+            None
+        }
+    }
 }
 
 async fn process_review_events(
@@ -132,7 +142,8 @@ async fn process_review_events(
                 ..
             })
             | EventMsg::AgentMessageDelta(AgentMessageDeltaEvent { .. })
-            | EventMsg::AgentMessageContentDelta(AgentMessageContentDeltaEvent { .. }) => {}
+            | EventMsg::AgentMessageContentDelta(AgentMessageContentDeltaEvent { .. })
+            | EventMsg::StructuredOutputDelta(_) => {}
             EventMsg::TaskComplete(task_complete) => {
                 // Parse review output from the last agent message (if present).
                 let out = task_complete
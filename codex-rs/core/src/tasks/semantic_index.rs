@@ -0,0 +1,103 @@
+use std::sync::Arc;
+
+use crate::codex::TurnContext;
+use crate::features::Feature;
+use crate::index_watcher::IndexKind;
+use crate::protocol::EventMsg;
+use crate::protocol::SemanticIndexUpdatedEvent;
+use crate::semantic_index::index_root;
+use crate::semantic_index::update_index;
+use crate::state::TaskKind;
+use crate::tasks::SessionTask;
+use crate::tasks::SessionTaskContext;
+use async_trait::async_trait;
+use codex_protocol::user_input::UserInput;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+use tracing::warn;
+
+pub(crate) struct SemanticIndexTask {
+    rebuild: bool,
+}
+
+impl SemanticIndexTask {
+    pub(crate) fn new(rebuild: bool) -> Self {
+        Self { rebuild }
+    }
+}
+
+#[async_trait]
+impl SessionTask for SemanticIndexTask {
+    fn kind(&self) -> TaskKind {
+        TaskKind::Regular
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        session: Arc<SessionTaskContext>,
+        ctx: Arc<TurnContext>,
+        _input: Vec<UserInput>,
+        cancellation_token: CancellationToken,
+    ) -> Option<String> {
+        let sess = session.clone_session();
+        let event = if !sess.enabled(Feature::SemanticSearch) {
+            SemanticIndexUpdatedEvent {
+                success: false,
+                message: "Semantic search is disabled. Enable the `semantic_search` feature to build an index.".to_string(),
+            }
+        } else if let Some(root) = index_root(&ctx.cwd) {
+            // A full update covers every pending change; put them back if it
+            // does not complete.
+            let watcher = sess
+                .services
+                .index_watcher
+                .as_ref()
+                .filter(|watcher| watcher.root() == root);
+            let dirty = watcher
+                .map(|watcher| watcher.take_dirty(IndexKind::Semantic))
+                .unwrap_or_default();
+            let update = update_index(&ctx.client, &root, &ctx.context_ignore, self.rebuild);
+            let result = tokio::select! {
+                _ = cancellation_token.cancelled() => None,
+                result = update => Some(result),
+            };
+            if !matches!(result, Some(Ok(_)))
+                && let Some(watcher) = watcher
+            {
+                watcher.restore_dirty(IndexKind::Semantic, dirty);
+            }
+            match result {
+                None => return None,
+                Some(Ok(stats)) => {
+                    info!(?stats, "semantic index updated");
+                    SemanticIndexUpdatedEvent {
+                        success: true,
+                        message: format!(
+                            "Semantic index updated: {} files embedded, {} unchanged, {} removed ({} chunks total).",
+                            stats.files_indexed,
+                            stats.files_unchanged,
+                            stats.files_removed,
+                            stats.total_chunks
+                        ),
+                    }
+                }
+                Some(Err(err)) => {
+                    warn!("failed to update semantic index: {err:#}");
+                    SemanticIndexUpdatedEvent {
+                        success: false,
+                        message: format!("Failed to update semantic index: {err:#}"),
+                    }
+                }
+            }
+        } else {
+            SemanticIndexUpdatedEvent {
+                success: false,
+                message: "The semantic index requires a git repository.".to_string(),
+            }
+        };
+
+        sess.send_event(ctx.as_ref(), EventMsg::SemanticIndexUpdated(event))
+            .await;
+        None
+    }
+}
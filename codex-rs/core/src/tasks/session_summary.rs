@@ -0,0 +1,44 @@
+use std::sync::Arc;
+
+use super::SessionTask;
+use super::SessionTaskContext;
+use crate::codex::TurnContext;
+use crate::compact::summarize_session;
+use crate::protocol::EventMsg;
+use crate::protocol::SessionSummaryEvent;
+use crate::state::TaskKind;
+use async_trait::async_trait;
+use codex_protocol::user_input::UserInput;
+use tokio_util::sync::CancellationToken;
+
+#[derive(Clone, Copy, Default)]
+pub(crate) struct SessionSummaryTask;
+
+#[async_trait]
+impl SessionTask for SessionSummaryTask {
+    fn kind(&self) -> TaskKind {
+        TaskKind::Compact
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        session: Arc<SessionTaskContext>,
+        ctx: Arc<TurnContext>,
+        _input: Vec<UserInput>,
+        cancellation_token: CancellationToken,
+    ) -> Option<String> {
+        let sess = session.clone_session();
+        let result = tokio::select! {
+            _ = cancellation_token.cancelled() => return None,
+            result = summarize_session(sess.as_ref(), ctx.as_ref()) => result,
+        };
+        let msg = match result {
+            Ok(markdown) => EventMsg::SessionSummary(SessionSummaryEvent { markdown }),
+            Err(err) => EventMsg::Error(
+                err.to_error_event(Some("Failed to summarize the session".to_string())),
+            ),
+        };
+        sess.send_event(ctx.as_ref(), msg).await;
+        None
+    }
+}
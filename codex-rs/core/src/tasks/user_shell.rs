@@ -26,7 +26,7 @@ use crate::protocol::TaskStartedEvent;
 use crate::sandboxing::ExecEnv;
 use crate::sandboxing::SandboxPermissions;
 use crate::state::TaskKind;
-use crate::tools::format_exec_output_str;
+use crate::tools::format_exec_output_for_display;
 use crate::user_shell_command::user_shell_command_record_item;
 
 use super::SessionTask;
@@ -176,7 +176,7 @@ impl SessionTask for UserShellCommandTask {
                             aggregated_output: output.aggregated_output.text.clone(),
                             exit_code: output.exit_code,
                             duration: output.duration,
-                            formatted_output: format_exec_output_str(
+                            formatted_output: format_exec_output_for_display(
                                 &output,
                                 turn_context.truncation_policy,
                             ),
@@ -221,7 +221,7 @@ impl SessionTask for UserShellCommandTask {
                             aggregated_output: exec_output.aggregated_output.text.clone(),
                             exit_code: exec_output.exit_code,
                             duration: exec_output.duration,
-                            formatted_output: format_exec_output_str(
+                            formatted_output: format_exec_output_for_display(
                                 &exec_output,
                                 turn_context.truncation_policy,
                             ),
@@ -0,0 +1,126 @@
+use std::sync::Arc;
+
+use crate::codex::TurnContext;
+use crate::codex::run_task;
+use crate::config::types::Workflow;
+use crate::protocol::CodexErrorInfo;
+use crate::protocol::ErrorCategory;
+use crate::protocol::ErrorDetails;
+use crate::protocol::ErrorEvent;
+use crate::protocol::EventMsg;
+use crate::protocol::WorkflowStepStartedEvent;
+use crate::state::TaskKind;
+use crate::tasks::SessionTask;
+use crate::tasks::SessionTaskContext;
+use async_trait::async_trait;
+use codex_protocol::user_input::UserInput;
+use tokio_util::sync::CancellationToken;
+
+const INPUT_PLACEHOLDER: &str = "{input}";
+
+/// Runs the steps of a configured workflow one after another, each as a
+/// regular turn with the step's own approval policy, tools and output schema.
+/// A step that ends without a final message stops the workflow.
+pub(crate) struct WorkflowTask {
+    name: String,
+    workflow: Workflow,
+    input: Option<String>,
+}
+
+impl WorkflowTask {
+    pub(crate) fn new(name: String, workflow: Workflow, input: Option<String>) -> Self {
+        Self {
+            name,
+            workflow,
+            input,
+        }
+    }
+}
+
+#[async_trait]
+impl SessionTask for WorkflowTask {
+    fn kind(&self) -> TaskKind {
+        TaskKind::Regular
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        session: Arc<SessionTaskContext>,
+        ctx: Arc<TurnContext>,
+        _input: Vec<UserInput>,
+        cancellation_token: CancellationToken,
+    ) -> Option<String> {
+        let sess = session.clone_session();
+        let steps = self.workflow.steps.len();
+        let mut last_agent_message = None;
+        for (index, step) in self.workflow.steps.iter().enumerate() {
+            if cancellation_token.is_cancelled() {
+                return None;
+            }
+            let step_context = match sess.new_workflow_step_turn(ctx.sub_id.clone(), step).await {
+                Ok(step_context) => step_context,
+                Err(err) => {
+                    sess.send_event(
+                        &ctx,
+                        EventMsg::Error(ErrorEvent {
+                            message: format!(
+                                "Step {} of workflow `{}` cannot run: {err}",
+                                index + 1,
+                                self.name
+                            ),
+                            codex_error_info: Some(CodexErrorInfo::BadRequest),
+                            details: Some(ErrorDetails::new(
+                                ErrorCategory::Protocol,
+                                "invalid_settings",
+                            )),
+                        }),
+                    )
+                    .await;
+                    return last_agent_message;
+                }
+            };
+            let prompt = expand_prompt(&step.prompt, self.input.as_deref());
+            sess.send_event(
+                &step_context,
+                EventMsg::WorkflowStepStarted(WorkflowStepStartedEvent {
+                    workflow: self.name.clone(),
+                    step: index,
+                    steps,
+                    prompt: prompt.clone(),
+                }),
+            )
+            .await;
+
+            last_agent_message = run_task(
+                Arc::clone(&sess),
+                step_context,
+                vec![UserInput::Text { text: prompt }],
+                cancellation_token.child_token(),
+            )
+            .await;
+            if last_agent_message.is_none() {
+                break;
+            }
+        }
+        last_agent_message
+    }
+}
+
+fn expand_prompt(prompt: &str, input: Option<&str>) -> String {
+    prompt.replace(INPUT_PLACEHOLDER, input.unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn expand_prompt_replaces_every_input_placeholder() {
+        assert_eq!(
+            expand_prompt("Reproduce {input}, then fix {input}.", Some("#42")),
+            "Reproduce #42, then fix #42."
+        );
+        assert_eq!(expand_prompt("Triage {input}", None), "Triage ");
+    }
+}
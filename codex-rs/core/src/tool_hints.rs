@@ -0,0 +1,333 @@
+//! Per-project record of the tools the model used successfully, turned into a
+//! short hint at the start of later sessions in the same project.
+//!
+//! Counts are kept under `$CODEX_HOME/tool_usage/`, one file per project
+//! (the Git root of the working directory, or the directory itself). Shell
+//! calls are counted by the command they ran, e.g. `cargo test`, so the hint
+//! can say which commands tend to work here.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::models::ShellCommandToolCallParams;
+use codex_protocol::models::ShellToolCallParams;
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use tokio::fs;
+use tracing::warn;
+
+use crate::bash::parse_shell_lc_plain_commands;
+use crate::file_lock::lock_file_async;
+use crate::file_lock::write_atomic_async;
+use crate::git_info::get_git_repo_root;
+use crate::tools::context::ToolPayload;
+
+pub(crate) const TOOL_HINTS_OPEN_TAG: &str = "<tool_hints>";
+const TOOL_HINTS_CLOSE_TAG: &str = "</tool_hints>";
+const TOOL_USAGE_DIRNAME: &str = "tool_usage";
+const SHELL_KEY_PREFIX: &str = "shell:";
+
+/// Tools and commands need this many successful calls before they are hinted.
+const MIN_SUCCESSES: u64 = 3;
+const MAX_HINTS: usize = 6;
+/// Tools that say nothing about how work gets done in a project.
+const IGNORED_TOOLS: &[&str] = &["update_plan", "ask_user", "register_artifact"];
+/// Programs whose first argument names what they do, e.g. `cargo test`.
+const SUBCOMMAND_PROGRAMS: &[&str] = &[
+    "bazel", "cargo", "docker", "dotnet", "git", "go", "gradle", "just", "kubectl", "make", "mvn",
+    "npm", "pnpm", "poetry", "uv", "yarn",
+];
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UsageFile {
+    #[serde(default)]
+    tools: BTreeMap<String, ToolUsage>,
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize)]
+struct ToolUsage {
+    successes: u64,
+    /// Unix timestamp of the last successful call.
+    last_used: i64,
+}
+
+/// Counts successful tool calls for one project during a session.
+#[derive(Debug)]
+pub(crate) struct ToolUsageTracker {
+    path: PathBuf,
+    /// Hint rendered from the counts saved before this session started.
+    hints: Option<String>,
+    pending: Mutex<HashMap<String, u64>>,
+}
+
+impl ToolUsageTracker {
+    pub(crate) async fn load(codex_home: &Path, cwd: &Path) -> Self {
+        let project_root = get_git_repo_root(cwd).unwrap_or_else(|| cwd.to_path_buf());
+        let path = codex_home
+            .join(TOOL_USAGE_DIRNAME)
+            .join(format!("{}.json", project_key(&project_root)));
+        let hints = match read_usage(&path).await {
+            Ok(usage) => render_hints(&usage),
+            Err(err) => {
+                warn!("failed to read tool usage at {}: {err}", path.display());
+                None
+            }
+        };
+        Self {
+            path,
+            hints,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn hints(&self) -> Option<&str> {
+        self.hints.as_deref()
+    }
+
+    /// Count one successful call of `tool_name`.
+    pub(crate) fn record(&self, tool_name: &str, payload: &ToolPayload) {
+        let Some(key) = usage_key(tool_name, payload) else {
+            return;
+        };
+        if let Ok(mut pending) = self.pending.lock() {
+            *pending.entry(key).or_default() += 1;
+        }
+    }
+
+    /// Add the calls counted since the last flush to the project's file.
+    pub(crate) async fn flush(&self) {
+        let pending = match self.pending.lock() {
+            Ok(mut pending) => std::mem::take(&mut *pending),
+            Err(_) => return,
+        };
+        if pending.is_empty() {
+            return;
+        }
+        if let Err(err) = self.write(pending).await {
+            warn!(
+                "failed to save tool usage to {}: {err}",
+                self.path.display()
+            );
+        }
+    }
+
+    async fn write(&self, pending: HashMap<String, u64>) -> std::io::Result<()> {
+        let _lock = lock_file_async(&self.path).await?;
+        let mut usage = read_usage(&self.path).await?;
+        let now = chrono::Utc::now().timestamp();
+        for (key, count) in pending {
+            let entry = usage.tools.entry(key).or_default();
+            entry.successes += count;
+            entry.last_used = now;
+        }
+        let json = serde_json::to_vec_pretty(&usage).map_err(std::io::Error::other)?;
+        write_atomic_async(&self.path, json, None).await
+    }
+}
+
+/// Past tool usage, converted into a user-role context message.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ToolHints {
+    pub hints: String,
+}
+
+impl ToolHints {
+    pub fn is_tool_hints(message: &[ContentItem]) -> bool {
+        if let [ContentItem::InputText { text }] = message {
+            text.starts_with(TOOL_HINTS_OPEN_TAG)
+        } else {
+            false
+        }
+    }
+}
+
+impl From<ToolHints> for ResponseItem {
+    fn from(tool_hints: ToolHints) -> Self {
+        ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format!(
+                    "{TOOL_HINTS_OPEN_TAG}\n{}\n{TOOL_HINTS_CLOSE_TAG}",
+                    tool_hints.hints
+                ),
+            }],
+        }
+    }
+}
+
+fn project_key(project_root: &Path) -> String {
+    let canonical = dunce::canonicalize(project_root).unwrap_or_else(|_| project_root.into());
+    let mut hasher = Sha256::new();
+    hasher.update(canonical.to_string_lossy().as_bytes());
+    let hex = format!("{:x}", hasher.finalize());
+    hex.get(..16).unwrap_or(&hex).to_string()
+}
+
+async fn read_usage(path: &Path) -> std::io::Result<UsageFile> {
+    match fs::read(path).await {
+        Ok(data) => serde_json::from_slice(&data)
+            .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(UsageFile::default()),
+        Err(err) => Err(err),
+    }
+}
+
+fn usage_key(tool_name: &str, payload: &ToolPayload) -> Option<String> {
+    if IGNORED_TOOLS.contains(&tool_name) {
+        return None;
+    }
+    let command = match (tool_name, payload) {
+        (_, ToolPayload::LocalShell { params }) => Some(params.command.clone()),
+        ("shell", ToolPayload::Function { arguments }) => {
+            serde_json::from_str::<ShellToolCallParams>(arguments)
+                .ok()
+                .map(|params| params.command)
+        }
+        ("shell_command", ToolPayload::Function { arguments }) => {
+            serde_json::from_str::<ShellCommandToolCallParams>(arguments)
+                .ok()
+                .map(|params| script_command(params.command))
+        }
+        ("exec_command", ToolPayload::Function { arguments }) => {
+            serde_json::from_str::<serde_json::Value>(arguments)
+                .ok()
+                .and_then(|args| args.get("cmd")?.as_str().map(str::to_string))
+                .map(script_command)
+        }
+        _ => return Some(tool_name.to_string()),
+    };
+    let summary = command_summary(&command?)?;
+    Some(format!("{SHELL_KEY_PREFIX}{summary}"))
+}
+
+fn script_command(script: String) -> Vec<String> {
+    vec!["bash".to_string(), "-lc".to_string(), script]
+}
+
+/// The program, and its subcommand for tools like `cargo`, of the first
+/// command that is not a `cd`.
+fn command_summary(command: &[String]) -> Option<String> {
+    let commands = parse_shell_lc_plain_commands(command).unwrap_or_else(|| vec![command.to_vec()]);
+    let words = commands
+        .into_iter()
+        .find(|words| words.first().is_some_and(|program| program != "cd"))?;
+    let program = Path::new(words.first()?).file_name()?.to_string_lossy();
+    match words.get(1) {
+        Some(subcommand)
+            if SUBCOMMAND_PROGRAMS.contains(&program.as_ref()) && !subcommand.starts_with('-') =>
+        {
+            Some(format!("{program} {subcommand}"))
+        }
+        _ => Some(program.into_owned()),
+    }
+}
+
+fn render_hints(usage: &UsageFile) -> Option<String> {
+    let mut entries: Vec<(&String, &ToolUsage)> = usage
+        .tools
+        .iter()
+        .filter(|(_, usage)| usage.successes >= MIN_SUCCESSES)
+        .collect();
+    if entries.is_empty() {
+        return None;
+    }
+    entries.sort_by(|(_, a), (_, b)| {
+        b.successes
+            .cmp(&a.successes)
+            .then(b.last_used.cmp(&a.last_used))
+    });
+    let tools: Vec<String> = entries
+        .into_iter()
+        .take(MAX_HINTS)
+        .map(|(key, _)| match key.strip_prefix(SHELL_KEY_PREFIX) {
+            Some(command) => format!("`{command}` via shell"),
+            None => key.clone(),
+        })
+        .collect();
+    Some(format!(
+        "Commonly useful tools in this project, from past sessions: {}.",
+        tools.join(", ")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn function(arguments: serde_json::Value) -> ToolPayload {
+        ToolPayload::Function {
+            arguments: arguments.to_string(),
+        }
+    }
+
+    #[test]
+    fn usage_key_names_shell_commands_by_program_and_subcommand() {
+        let shell = function(serde_json::json!({
+            "command": ["bash", "-lc", "cd core && cargo test -p codex-core"],
+        }));
+        assert_eq!(
+            usage_key("shell", &shell),
+            Some("shell:cargo test".to_string())
+        );
+
+        let shell_command = function(serde_json::json!({ "command": "rg --files src" }));
+        assert_eq!(
+            usage_key("shell_command", &shell_command),
+            Some("shell:rg".to_string())
+        );
+
+        let search = function(serde_json::json!({ "query": "config loading" }));
+        assert_eq!(
+            usage_key("semantic_search", &search),
+            Some("semantic_search".to_string())
+        );
+        assert_eq!(usage_key("update_plan", &search), None);
+    }
+
+    #[test]
+    fn render_hints_lists_frequent_tools_first() {
+        let usage = UsageFile {
+            tools: BTreeMap::from([
+                (
+                    "semantic_search".to_string(),
+                    ToolUsage {
+                        successes: 4,
+                        last_used: 10,
+                    },
+                ),
+                (
+                    "shell:cargo test".to_string(),
+                    ToolUsage {
+                        successes: 12,
+                        last_used: 5,
+                    },
+                ),
+                (
+                    "shell:ls".to_string(),
+                    ToolUsage {
+                        successes: 1,
+                        last_used: 20,
+                    },
+                ),
+            ]),
+        };
+
+        assert_eq!(
+            render_hints(&usage).as_deref(),
+            Some(
+                "Commonly useful tools in this project, from past sessions: `cargo test` via shell, semantic_search."
+            )
+        );
+        assert_eq!(render_hints(&UsageFile::default()), None);
+    }
+}
@@ -0,0 +1,367 @@
+//! Repair of tool-call arguments that do not match the tool's schema.
+//!
+//! Weaker models sometimes send arguments that are not valid JSON, leave out
+//! a required field, or use the wrong type (`"5000"` for a number, a string
+//! for a command array). With `[features] tool_call_repair`, such a call is
+//! not failed right away: a separate model request gets the schema, the
+//! arguments and the validation error, and is asked for corrected arguments.
+//! Each reply that still does not validate is sent back with its error, up to
+//! [`MAX_REPAIR_ATTEMPTS`] times. A repaired call runs with the corrected
+//! arguments, and the recorded call is updated to match so the model sees
+//! what actually ran. If no reply validates, the call runs with the original
+//! arguments and fails as it would have without repair. Arguments too large
+//! to send whole are not repaired, since a repair of a truncated copy would
+//! run without the part that was cut.
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::protocol::BackgroundEventEvent;
+use codex_protocol::protocol::EventMsg;
+use futures::StreamExt;
+use serde_json::Value;
+use tracing::warn;
+
+use crate::client_common::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::client_common::tools::ResponsesApiTool;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::compact::content_items_to_text;
+use crate::error::CodexErr;
+use crate::error::Result as CodexResult;
+use crate::tools::spec::JsonSchema;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+pub(crate) const TOOL_CALL_REPAIR_PROMPT: &str =
+    include_str!("../../templates/tool_call_repair/prompt.md");
+/// Repair requests made for one call before giving up.
+const MAX_REPAIR_ATTEMPTS: usize = 2;
+const MAX_REPORTED_ERRORS: usize = 5;
+const DESCRIPTION_MAX_BYTES: usize = 2 * 1024;
+const ARGUMENTS_MAX_BYTES: usize = 16 * 1024;
+
+/// Return `arguments` unchanged when they match `tool`'s schema, otherwise
+/// corrected arguments from the model, or the original ones when repair
+/// fails. Corrected arguments replace those of the recorded call `call_id`.
+pub(crate) async fn repair_invalid_arguments(
+    session: &Session,
+    turn: &TurnContext,
+    tool: &ResponsesApiTool,
+    call_id: &str,
+    arguments: String,
+) -> String {
+    let Err(error) = validate_arguments(&arguments, &tool.parameters) else {
+        return arguments;
+    };
+    if arguments.len() > ARGUMENTS_MAX_BYTES {
+        return arguments;
+    }
+    match repair_arguments(session, turn, tool, &arguments, error.clone()).await {
+        Some(repaired) => {
+            session
+                .replace_call_arguments(call_id, repaired.clone())
+                .await;
+            session
+                .send_event(
+                    turn,
+                    EventMsg::BackgroundEvent(BackgroundEventEvent {
+                        message: format!(
+                            "Repaired the arguments of a `{}` call ({error}).",
+                            tool.name
+                        ),
+                    }),
+                )
+                .await;
+            repaired
+        }
+        None => arguments,
+    }
+}
+
+async fn repair_arguments(
+    session: &Session,
+    turn: &TurnContext,
+    tool: &ResponsesApiTool,
+    arguments: &str,
+    error: String,
+) -> Option<String> {
+    let schema = match serde_json::to_string_pretty(&tool.parameters) {
+        Ok(schema) => schema,
+        Err(err) => {
+            warn!("failed to serialize schema of {}: {err}", tool.name);
+            return None;
+        }
+    };
+    let text = format!(
+        "<tool>\nname: {}\ndescription: {}\n</tool>\n\n<schema>\n{schema}\n</schema>\n\n<arguments>\n{}\n</arguments>\n\n<error>\n{error}\n</error>\n",
+        tool.name,
+        truncate_text(
+            &tool.description,
+            TruncationPolicy::Bytes(DESCRIPTION_MAX_BYTES)
+        ),
+        arguments,
+    );
+    let mut input = vec![message("user", text)];
+
+    for _ in 0..MAX_REPAIR_ATTEMPTS {
+        let prompt = Prompt {
+            input: input.clone(),
+            tools: Vec::new(),
+            parallel_tool_calls: false,
+            base_instructions_override: Some(TOOL_CALL_REPAIR_PROMPT.to_string()),
+            locale_instructions: None,
+            model_instructions: None,
+            composed_instructions: None,
+            output_schema: None,
+        };
+        let reply = match run_repair(session, turn, &prompt).await {
+            Ok(reply) => reply,
+            Err(err) => {
+                warn!("tool call repair failed: {err:#}");
+                return None;
+            }
+        };
+        let candidate = extract_json_object(&reply);
+        match validate_arguments(candidate, &tool.parameters) {
+            Ok(()) => return Some(candidate.to_string()),
+            Err(error) => {
+                input.push(message("assistant", reply));
+                input.push(message(
+                    "user",
+                    format!("These arguments are still invalid: {error}"),
+                ));
+            }
+        }
+    }
+    None
+}
+
+fn message(role: &str, text: String) -> ResponseItem {
+    let content = if role == "assistant" {
+        ContentItem::OutputText { text }
+    } else {
+        ContentItem::InputText { text }
+    };
+    ResponseItem::Message {
+        id: None,
+        role: role.to_string(),
+        content: vec![content],
+    }
+}
+
+async fn run_repair(session: &Session, turn: &TurnContext, prompt: &Prompt) -> CodexResult<String> {
+    let mut stream = turn.client.clone().stream(prompt).await?;
+    let mut reply = String::new();
+    while let Some(event) = stream.next().await {
+        match event? {
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. }) => {
+                if let Some(text) = content_items_to_text(&content) {
+                    reply.push_str(&text);
+                }
+            }
+            ResponseEvent::RateLimits(snapshot) => {
+                session.update_rate_limits(turn, snapshot).await;
+            }
+            ResponseEvent::Completed { token_usage, .. } => {
+                session
+                    .update_side_token_usage(turn, token_usage.as_ref())
+                    .await;
+                return Ok(reply);
+            }
+            _ => {}
+        }
+    }
+    Err(CodexErr::Stream(
+        "stream closed before response.completed".into(),
+        None,
+    ))
+}
+
+/// The JSON object in `reply`, which may be wrapped in a code fence.
+pub(crate) fn extract_json_object(reply: &str) -> &str {
+    let reply = reply.trim();
+    match (reply.find('{'), reply.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &reply[start..=end],
+        _ => reply,
+    }
+}
+
+/// Check `arguments` against the subset of JSON Schema that tool
+/// definitions use. Properties the schema does not declare are allowed, as
+/// the handlers ignore them.
+fn validate_arguments(arguments: &str, schema: &JsonSchema) -> Result<(), String> {
+    // MCP calls send empty arguments for tools that take none.
+    let arguments = if arguments.trim().is_empty() {
+        "{}"
+    } else {
+        arguments
+    };
+    let value: Value = serde_json::from_str(arguments)
+        .map_err(|err| format!("arguments are not valid JSON: {err}"))?;
+    let mut errors = Vec::new();
+    validate_value(&value, schema, "", &mut errors);
+    if errors.is_empty() {
+        return Ok(());
+    }
+    let more = errors.len().saturating_sub(MAX_REPORTED_ERRORS);
+    errors.truncate(MAX_REPORTED_ERRORS);
+    let mut message = errors.join("; ");
+    if more > 0 {
+        message.push_str(&format!(" (and {more} more)"));
+    }
+    Err(message)
+}
+
+fn validate_value(value: &Value, schema: &JsonSchema, path: &str, errors: &mut Vec<String>) {
+    let expected = match schema {
+        JsonSchema::Boolean { .. } if !value.is_boolean() => "a boolean",
+        JsonSchema::String { .. } if !value.is_string() => "a string",
+        JsonSchema::Number { .. } if !value.is_number() => "a number",
+        JsonSchema::Array { items, .. } => match value.as_array() {
+            Some(values) => {
+                for (index, item) in values.iter().enumerate() {
+                    validate_value(item, items, &format!("{path}[{index}]"), errors);
+                }
+                return;
+            }
+            None => "an array",
+        },
+        JsonSchema::Object {
+            properties,
+            required,
+            ..
+        } => match value.as_object() {
+            Some(object) => {
+                for name in required.iter().flatten() {
+                    if object.get(name).is_none_or(Value::is_null) {
+                        errors.push(format!("`{}` is required", join_path(path, name)));
+                    }
+                }
+                for (name, property) in properties {
+                    match object.get(name) {
+                        // Optional fields may be sent as null.
+                        None | Some(Value::Null) => {}
+                        Some(value) => {
+                            validate_value(value, property, &join_path(path, name), errors)
+                        }
+                    }
+                }
+                return;
+            }
+            None => "an object",
+        },
+        _ => return,
+    };
+    let name = if path.is_empty() { "arguments" } else { path };
+    errors.push(format!(
+        "`{name}` must be {expected}, got {}",
+        json_type(value)
+    ));
+}
+
+fn join_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{path}.{name}")
+    }
+}
+
+fn json_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::collections::BTreeMap;
+
+    fn shell_schema() -> JsonSchema {
+        JsonSchema::Object {
+            properties: BTreeMap::from([
+                (
+                    "command".to_string(),
+                    JsonSchema::Array {
+                        items: Box::new(JsonSchema::String {
+                            description: None,
+                            enum_values: None,
+                        }),
+                        description: None,
+                    },
+                ),
+                (
+                    "timeout_ms".to_string(),
+                    JsonSchema::Number { description: None },
+                ),
+                (
+                    "workdir".to_string(),
+                    JsonSchema::String {
+                        description: None,
+                        enum_values: None,
+                    },
+                ),
+            ]),
+            required: Some(vec!["command".to_string()]),
+            additional_properties: Some(false.into()),
+        }
+    }
+
+    #[test]
+    fn valid_arguments_pass() {
+        assert_eq!(
+            validate_arguments(
+                r#"{"command": ["ls", "-la"], "timeout_ms": 5000, "workdir": null, "extra": 1}"#,
+                &shell_schema()
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn invalid_arguments_name_every_problem() {
+        assert_eq!(
+            validate_arguments(
+                r#"{"command": "ls -la", "timeout_ms": "5000"}"#,
+                &shell_schema()
+            ),
+            Err(
+                "`command` must be an array, got a string; `timeout_ms` must be a number, got a string"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            validate_arguments(r#"{"command": ["ls", 1]}"#, &shell_schema()),
+            Err("`command[1]` must be a string, got a number".to_string())
+        );
+        assert_eq!(
+            validate_arguments(r#"{"timeout_ms": 1}"#, &shell_schema()),
+            Err("`command` is required".to_string())
+        );
+        assert_eq!(
+            validate_arguments("[]", &shell_schema()),
+            Err("`arguments` must be an object, got an array".to_string())
+        );
+        assert!(
+            validate_arguments(r#"{"command": ["ls"]"#, &shell_schema())
+                .is_err_and(|err| err.starts_with("arguments are not valid JSON"))
+        );
+    }
+
+    #[test]
+    fn repaired_arguments_are_read_from_fenced_replies() {
+        assert_eq!(
+            extract_json_object("```json\n{\"command\": [\"ls\"]}\n```"),
+            "{\"command\": [\"ls\"]}"
+        );
+        assert_eq!(extract_json_object("not json"), "not json");
+    }
+}
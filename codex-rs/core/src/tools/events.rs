@@ -12,7 +12,6 @@ use crate::protocol::ExecCommandSource;
 use crate::protocol::FileChange;
 use crate::protocol::PatchApplyBeginEvent;
 use crate::protocol::PatchApplyEndEvent;
-use crate::protocol::TurnDiffEvent;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::sandboxing::ToolError;
 use codex_protocol::parse_command::ParsedCommand;
@@ -21,7 +20,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
 
-use super::format_exec_output_str;
+use super::format_exec_output_for_display;
 
 #[derive(Clone, Copy)]
 pub(crate) struct ToolEventCtx<'a> {
@@ -258,11 +257,22 @@ impl ToolEmitter {
         self.emit(ctx, ToolEventStage::Begin).await;
     }
 
-    fn format_exec_output_for_model(
+    async fn format_exec_output_for_model(
         &self,
         output: &ExecToolCallOutput,
         ctx: ToolEventCtx<'_>,
     ) -> String {
+        let with_notice;
+        let output = match spill_full_output(output, ctx).await {
+            Some(notice) => {
+                let mut annotated = output.clone();
+                annotated.aggregated_output.text =
+                    format!("{notice}\n{}", annotated.aggregated_output.text);
+                with_notice = annotated;
+                &with_notice
+            }
+            None => output,
+        };
         match self {
             Self::Shell { freeform: true, .. } => {
                 super::format_exec_output_for_model_freeform(output, ctx.turn.truncation_policy)
@@ -278,7 +288,7 @@ impl ToolEmitter {
     ) -> Result<String, FunctionCallError> {
         let (event, result) = match out {
             Ok(output) => {
-                let content = self.format_exec_output_for_model(&output, ctx);
+                let content = self.format_exec_output_for_model(&output, ctx).await;
                 let exit_code = output.exit_code;
                 let event = ToolEventStage::Success(output);
                 let result = if exit_code == 0 {
@@ -290,7 +300,7 @@ impl ToolEmitter {
             }
             Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Timeout { output })))
             | Err(ToolError::Codex(CodexErr::Sandbox(SandboxErr::Denied { output }))) => {
-                let response = self.format_exec_output_for_model(&output, ctx);
+                let response = self.format_exec_output_for_model(&output, ctx).await;
                 let event = ToolEventStage::Failure(ToolEventFailure::Output(*output));
                 let result = Err(FunctionCallError::RespondToModel(response));
                 (event, result)
@@ -314,9 +324,46 @@ impl ToolEmitter {
                 (event, result)
             }
         };
+        if let ToolEventStage::Success(output)
+        | ToolEventStage::Failure(ToolEventFailure::Output(output)) = &event
+        {
+            let content_len = super::build_content_with_timeout(output).len();
+            let truncated = content_len.saturating_sub(ctx.turn.truncation_policy.byte_budget());
+            if truncated > 0 {
+                ctx.session.record_truncated_output(truncated).await;
+            }
+        }
+        if let ToolEventStage::Success(output) = &event
+            && output.exit_code == 0
+        {
+            let read_paths = self.read_paths();
+            if !read_paths.is_empty() {
+                ctx.session.watch_files(read_paths).await;
+            }
+        }
         self.emit(ctx, event).await;
         result
     }
+
+    /// Files a shell command read, resolved against its working directory.
+    fn read_paths(&self) -> Vec<PathBuf> {
+        let (Self::Shell {
+            cwd, parsed_cmd, ..
+        }
+        | Self::UnifiedExec {
+            cwd, parsed_cmd, ..
+        }) = self
+        else {
+            return Vec::new();
+        };
+        parsed_cmd
+            .iter()
+            .filter_map(|parsed| match parsed {
+                ParsedCommand::Read { path, .. } => Some(cwd.join(path)),
+                _ => None,
+            })
+            .collect()
+    }
 }
 
 struct ExecCommandInput<'a> {
@@ -383,7 +430,10 @@ async fn emit_exec_stage(
                 aggregated_output: output.aggregated_output.text.clone(),
                 exit_code: output.exit_code,
                 duration: output.duration,
-                formatted_output: format_exec_output_str(&output, ctx.turn.truncation_policy),
+                formatted_output: format_exec_output_for_display(
+                    &output,
+                    ctx.turn.truncation_policy,
+                ),
             };
             emit_exec_end(ctx, exec_input, exec_result).await;
         }
@@ -402,6 +452,18 @@ async fn emit_exec_stage(
     }
 }
 
+/// Save the full output of a command that overflows the truncation budget so
+/// the model can read the dropped middle with `read_output_range`. Returns the
+/// notice to put ahead of the truncated output.
+async fn spill_full_output(output: &ExecToolCallOutput, ctx: ToolEventCtx<'_>) -> Option<String> {
+    let spill = ctx.session.services.output_spill.as_ref()?;
+    let content = super::build_content_with_timeout(output);
+    if content.len() <= ctx.turn.truncation_policy.byte_budget() {
+        return None;
+    }
+    spill.save_full_output(ctx.call_id, &content).await
+}
+
 async fn emit_exec_end(
     ctx: ToolEventCtx<'_>,
     exec_input: ExecCommandInput<'_>,
@@ -452,13 +514,13 @@ async fn emit_patch_end(
         .await;
 
     if let Some(tracker) = ctx.turn_diff_tracker {
-        let unified_diff = {
+        let turn_diff = {
             let mut guard = tracker.lock().await;
-            guard.get_unified_diff()
+            guard.get_turn_diff()
         };
-        if let Ok(Some(unified_diff)) = unified_diff {
+        if let Ok(Some(turn_diff)) = turn_diff {
             ctx.session
-                .send_event(ctx.turn, EventMsg::TurnDiff(TurnDiffEvent { unified_diff }))
+                .send_event(ctx.turn, EventMsg::TurnDiff(turn_diff))
                 .await;
         }
     }
@@ -3,6 +3,7 @@ use std::path::Path;
 
 use crate::apply_patch;
 use crate::apply_patch::InternalApplyPatchInvocation;
+use crate::apply_patch::affected_paths;
 use crate::apply_patch::convert_apply_patch_to_protocol;
 use crate::client_common::tools::FreeformTool;
 use crate::client_common::tools::FreeformToolFormat;
@@ -29,7 +30,7 @@ use async_trait::async_trait;
 
 pub struct ApplyPatchHandler;
 
-const APPLY_PATCH_LARK_GRAMMAR: &str = include_str!("tool_apply_patch.lark");
+pub(crate) const APPLY_PATCH_LARK_GRAMMAR: &str = include_str!("tool_apply_patch.lark");
 
 #[async_trait]
 impl ToolHandler for ApplyPatchHandler {
@@ -75,6 +76,11 @@ impl ToolHandler for ApplyPatchHandler {
             }
         };
 
+        let patch_input = match &turn.wsl_paths {
+            Some(wsl_paths) => wsl_paths.to_host_patch(&patch_input),
+            None => patch_input,
+        };
+
         // Re-parse and verify the patch so we can compute changes and approval.
         // Avoid building temporary ExecParams/command vectors; derive directly from inputs.
         let cwd = turn.cwd.clone();
@@ -131,6 +137,7 @@ impl ToolHandler for ApplyPatchHandler {
                             Some(&tracker),
                         );
                         let content = emitter.finish(event_ctx, out).await?;
+                        session.watch_files(affected_paths(&apply.action)).await;
                         Ok(ToolOutput::Function {
                             content,
                             content_items: None,
@@ -170,7 +177,14 @@ pub(crate) async fn intercept_apply_patch(
     call_id: &str,
     tool_name: &str,
 ) -> Result<Option<ToolOutput>, FunctionCallError> {
-    match codex_apply_patch::maybe_parse_apply_patch_verified(command, cwd) {
+    let command: Vec<String> = match &turn.wsl_paths {
+        Some(wsl_paths) => command
+            .iter()
+            .map(|arg| wsl_paths.to_host_patch(arg))
+            .collect(),
+        None => command.to_vec(),
+    };
+    match codex_apply_patch::maybe_parse_apply_patch_verified(&command, cwd) {
         codex_apply_patch::MaybeApplyPatchVerified::Body(changes) => {
             session
                 .record_model_warning(
@@ -218,6 +232,7 @@ pub(crate) async fn intercept_apply_patch(
                     let event_ctx =
                         ToolEventCtx::new(session, turn, call_id, tracker.as_ref().copied());
                     let content = emitter.finish(event_ctx, out).await?;
+                    session.watch_files(affected_paths(&apply.action)).await;
                     Ok(Some(ToolOutput::Function {
                         content,
                         content_items: None,
@@ -260,6 +275,7 @@ pub(crate) fn create_apply_patch_json_tool() -> ToolSpec {
         "input".to_string(),
         JsonSchema::String {
             description: Some(r#"The entire contents of the apply_patch command"#.to_string()),
+            enum_values: None,
         },
     );
 
@@ -0,0 +1,86 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::protocol::AskForApproval;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct AskUserHandler;
+
+const UNAVAILABLE_MESSAGE: &str = "The user cannot answer questions in this session. Proceed with your best judgment and state the assumptions you made.";
+const DECLINED_MESSAGE: &str = "The user declined to answer. Proceed with your best judgment and state the assumptions you made.";
+
+#[derive(Deserialize)]
+struct AskUserArgs {
+    question: String,
+    #[serde(default)]
+    options: Vec<String>,
+}
+
+#[async_trait]
+impl ToolHandler for AskUserHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "ask_user handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: AskUserArgs = serde_json::from_str(&arguments).map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "failed to parse function arguments: {err:?}"
+            ))
+        })?;
+
+        let question = args.question.trim().to_string();
+        if question.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "question must not be empty".to_string(),
+            ));
+        }
+        let options = args
+            .options
+            .into_iter()
+            .map(|option| option.trim().to_string())
+            .filter(|option| !option.is_empty())
+            .collect();
+
+        // Without approvals nobody is watching the session, so do not wait.
+        let content = if turn.approval_policy == AskForApproval::Never {
+            UNAVAILABLE_MESSAGE.to_string()
+        } else {
+            match session
+                .request_user_answer(turn.as_ref(), call_id, question, options)
+                .await
+            {
+                Some(answer) => answer,
+                None => DECLINED_MESSAGE.to_string(),
+            }
+        };
+
+        Ok(ToolOutput::Function {
+            content,
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
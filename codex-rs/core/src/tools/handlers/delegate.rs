@@ -0,0 +1,318 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use codex_protocol::protocol::ArtifactRegisteredEvent;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::SubAgentSource;
+use codex_protocol::protocol::TurnDiffEvent;
+use codex_protocol::protocol::TurnDiffFile;
+use codex_protocol::user_input::UserInput;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
+
+use crate::codex_delegate::run_codex_conversation_one_shot;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+pub struct DelegateHandler;
+
+pub(crate) const HANDOFF_INSTRUCTIONS: &str =
+    include_str!("../../../templates/delegate/handoff.md");
+/// The full diff is cut to this size; the per-file list is always complete.
+const MAX_DIFF_BYTES: usize = 16 * 1024;
+
+#[derive(Deserialize)]
+struct DelegateArgs {
+    task: String,
+}
+
+/// What a sub-agent hands back to the agent that delegated to it.
+#[derive(Debug, PartialEq, Serialize)]
+struct Handoff {
+    status: HandoffStatus,
+    summary: String,
+    artifacts: Vec<HandoffArtifact>,
+    /// Files the sub-agent changed, from its own diff tracking.
+    diffs: Vec<TurnDiffFile>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    unified_diff: String,
+    open_questions: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum HandoffStatus {
+    Completed,
+    Failed,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+struct HandoffArtifact {
+    path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<String>,
+}
+
+/// The JSON object the handoff instructions ask the sub-agent to end with.
+#[derive(Deserialize)]
+struct ReportedHandoff {
+    summary: String,
+    #[serde(default)]
+    artifacts: Vec<PathBuf>,
+    #[serde(default)]
+    open_questions: Vec<String>,
+}
+
+/// Everything the parent learns from the sub-agent's events.
+#[derive(Default)]
+struct SubAgentOutcome {
+    completed: bool,
+    final_message: Option<String>,
+    error: Option<String>,
+    diff: Option<TurnDiffEvent>,
+    registered: Vec<ArtifactRegisteredEvent>,
+}
+
+#[async_trait]
+impl ToolHandler for DelegateHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "delegate handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: DelegateArgs = serde_json::from_str(&arguments).map_err(|e| {
+            FunctionCallError::RespondToModel(format!("failed to parse function arguments: {e:?}"))
+        })?;
+        let task = args.task.trim().to_string();
+        if task.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "task must not be empty".to_string(),
+            ));
+        }
+
+        let mut sub_agent_config = turn.client.config().as_ref().clone();
+        sub_agent_config.developer_instructions =
+            Some(match sub_agent_config.developer_instructions.take() {
+                Some(existing) => format!("{existing}\n\n{HANDOFF_INSTRUCTIONS}"),
+                None => HANDOFF_INSTRUCTIONS.to_string(),
+            });
+
+        // Stop the sub-agent if this call is cancelled with the turn.
+        let cancellation_token = CancellationToken::new();
+        let _stop_sub_agent = cancellation_token.clone().drop_guard();
+        let io = run_codex_conversation_one_shot(
+            sub_agent_config,
+            Arc::clone(&session.services.auth_manager),
+            Arc::clone(&session.services.models_manager),
+            vec![UserInput::Text { text: task }],
+            Arc::clone(&session),
+            Arc::clone(&turn),
+            cancellation_token,
+            None,
+            SubAgentSource::Other("delegate".to_string()),
+        )
+        .await
+        .map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to start sub-agent: {err}"))
+        })?;
+
+        let mut outcome = SubAgentOutcome::default();
+        while let Ok(event) = io.next_event().await {
+            match event.msg {
+                EventMsg::TaskComplete(done) => {
+                    outcome.completed = true;
+                    outcome.final_message = done.last_agent_message;
+                    break;
+                }
+                EventMsg::TurnAborted(_) => break,
+                EventMsg::Error(error) => outcome.error = Some(error.message),
+                EventMsg::TurnDiff(diff) => outcome.diff = Some(diff),
+                EventMsg::ArtifactRegistered(registered) => {
+                    // List the sub-agent's artifacts alongside the parent's.
+                    session
+                        .send_event(
+                            turn.as_ref(),
+                            EventMsg::ArtifactRegistered(ArtifactRegisteredEvent {
+                                turn_id: turn.sub_id.clone(),
+                                artifact: registered.artifact.clone(),
+                            }),
+                        )
+                        .await;
+                    outcome.registered.push(registered);
+                }
+                _ => {}
+            }
+        }
+
+        let handoff = build_handoff(outcome);
+        let content = serde_json::to_string(&handoff).map_err(|err| {
+            FunctionCallError::Fatal(format!("failed to serialize handoff: {err}"))
+        })?;
+        Ok(ToolOutput::Function {
+            content,
+            content_items: None,
+            success: Some(handoff.status == HandoffStatus::Completed),
+        })
+    }
+}
+
+fn build_handoff(outcome: SubAgentOutcome) -> Handoff {
+    let SubAgentOutcome {
+        completed,
+        final_message,
+        error,
+        diff,
+        registered,
+    } = outcome;
+    let (diffs, unified_diff) = match diff {
+        Some(diff) => (
+            diff.files,
+            truncate_text(&diff.unified_diff, TruncationPolicy::Bytes(MAX_DIFF_BYTES)),
+        ),
+        None => (Vec::new(), String::new()),
+    };
+    let mut artifacts: Vec<HandoffArtifact> = registered
+        .into_iter()
+        .map(|registered| HandoffArtifact {
+            path: registered.artifact.path,
+            title: Some(registered.artifact.title),
+        })
+        .collect();
+
+    if !completed {
+        return Handoff {
+            status: HandoffStatus::Failed,
+            summary: error.unwrap_or_else(|| "The sub-agent stopped before finishing.".to_string()),
+            artifacts,
+            diffs,
+            unified_diff,
+            open_questions: Vec::new(),
+        };
+    }
+
+    let text = final_message.unwrap_or_default();
+    let (summary, open_questions) = match parse_reported_handoff(&text) {
+        Some(report) => {
+            for path in report.artifacts {
+                if !artifacts.iter().any(|artifact| artifact.path == path) {
+                    artifacts.push(HandoffArtifact { path, title: None });
+                }
+            }
+            (report.summary, report.open_questions)
+        }
+        // Models sometimes answer in prose; keep it as the summary.
+        None => (text.trim().to_string(), Vec::new()),
+    };
+    Handoff {
+        status: HandoffStatus::Completed,
+        summary,
+        artifacts,
+        diffs,
+        unified_diff,
+        open_questions,
+    }
+}
+
+/// Parse the handoff object from the final message, which may wrap it in a
+/// code fence or surrounding text.
+fn parse_reported_handoff(text: &str) -> Option<ReportedHandoff> {
+    if let Ok(report) = serde_json::from_str(text) {
+        return Some(report);
+    }
+    let start = text.find('{')?;
+    let end = text.rfind('}')?;
+    serde_json::from_str(text.get(start..=end)?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::Artifact;
+    use pretty_assertions::assert_eq;
+
+    fn artifact_event(path: &str) -> ArtifactRegisteredEvent {
+        ArtifactRegisteredEvent {
+            turn_id: "sub".to_string(),
+            artifact: Artifact {
+                id: "call".to_string(),
+                path: PathBuf::from(path),
+                title: "Report".to_string(),
+                description: None,
+                size_bytes: 10,
+            },
+        }
+    }
+
+    #[test]
+    fn completed_handoff_merges_reported_and_registered_artifacts() {
+        let outcome = SubAgentOutcome {
+            completed: true,
+            final_message: Some(
+                "Done.\n```json\n{\"summary\": \"Fixed the parser.\", \"artifacts\": [\"/repo/report.md\", \"/repo/bench.txt\"], \"open_questions\": [\"Keep the old flag?\"]}\n```"
+                    .to_string(),
+            ),
+            registered: vec![artifact_event("/repo/report.md")],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            build_handoff(outcome),
+            Handoff {
+                status: HandoffStatus::Completed,
+                summary: "Fixed the parser.".to_string(),
+                artifacts: vec![
+                    HandoffArtifact {
+                        path: PathBuf::from("/repo/report.md"),
+                        title: Some("Report".to_string()),
+                    },
+                    HandoffArtifact {
+                        path: PathBuf::from("/repo/bench.txt"),
+                        title: None,
+                    },
+                ],
+                diffs: Vec::new(),
+                unified_diff: String::new(),
+                open_questions: vec!["Keep the old flag?".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn unfinished_sub_agent_reports_its_error() {
+        let outcome = SubAgentOutcome {
+            error: Some("sub-agent budget exceeded: sub-agent ran longer than 60s".to_string()),
+            ..Default::default()
+        };
+
+        let handoff = build_handoff(outcome);
+        assert_eq!(handoff.status, HandoffStatus::Failed);
+        assert_eq!(
+            handoff.summary,
+            "sub-agent budget exceeded: sub-agent ran longer than 60s"
+        );
+    }
+}
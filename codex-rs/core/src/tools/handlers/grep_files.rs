@@ -84,8 +84,12 @@ impl ToolHandler for GrepFilesHandler {
             }
         });
 
-        let search_results =
-            run_rg_search(pattern, include.as_deref(), &search_path, limit, &turn.cwd).await?;
+        let search_results: Vec<String> =
+            run_rg_search(pattern, include.as_deref(), &search_path, limit, &turn.cwd)
+                .await?
+                .into_iter()
+                .filter(|path| !turn.context_ignore.is_ignored(&turn.cwd.join(path), false))
+                .collect();
 
         if search_results.is_empty() {
             Ok(ToolOutput::Function {
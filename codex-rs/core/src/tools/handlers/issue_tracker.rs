@@ -0,0 +1,272 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::function_tool::FunctionCallError;
+use crate::git_info::collect_git_info;
+use crate::issue_tracker::IssueTracker;
+use crate::issue_tracker::current_branch;
+use crate::protocol::AskForApproval;
+use crate::protocol::ReviewDecision;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct IssueTrackerHandler;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum IssueTrackerAction {
+    GetIssue,
+    GetPullRequest,
+    CiStatus,
+    CiLogs,
+    PostComment,
+}
+
+#[derive(Deserialize)]
+struct IssueTrackerArgs {
+    action: IssueTrackerAction,
+    /// Declared as a JSON number in the tool schema, so `12.0` is accepted.
+    #[serde(default)]
+    number: Option<f64>,
+    #[serde(default)]
+    repo: Option<String>,
+    #[serde(default)]
+    branch: Option<String>,
+    #[serde(default)]
+    body: Option<String>,
+    #[serde(default)]
+    pull_request: bool,
+}
+
+#[async_trait]
+impl ToolHandler for IssueTrackerHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            call_id,
+            payload,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "issue_tracker handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: IssueTrackerArgs = serde_json::from_str(&arguments).map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "failed to parse function arguments: {err:?}"
+            ))
+        })?;
+        let number = args.number.map(item_number).transpose()?;
+        let require_number = || {
+            number.ok_or_else(|| {
+                FunctionCallError::RespondToModel("`number` is required".to_string())
+            })
+        };
+        // Comments are visible to others, so always ask first.
+        if matches!(args.action, IssueTrackerAction::PostComment)
+            && turn.approval_policy == AskForApproval::Never
+        {
+            return Err(FunctionCallError::RespondToModel(
+                "Posting comments needs the user's approval, which is not available in this session. Include the comment in your final message instead."
+                    .to_string(),
+            ));
+        }
+
+        let config = turn.client.config();
+        let tracker =
+            IssueTracker::for_repo(&config.issue_tracker, &turn.cwd, args.repo.as_deref())
+                .await
+                .map_err(|err| FunctionCallError::RespondToModel(format!("{err:#}")))?;
+        let result = match args.action {
+            IssueTrackerAction::GetIssue => tracker.issue(require_number()?).await,
+            IssueTrackerAction::GetPullRequest => tracker.pull_request(require_number()?).await,
+            IssueTrackerAction::CiStatus => tracker.ci_status(number, &turn.cwd).await,
+            IssueTrackerAction::CiLogs => {
+                // Only the current branch has a local commit to pin the runs
+                // to; any other branch uses the newest commit that has runs.
+                let (branch, head_sha) = match args.branch.as_deref().map(str::trim) {
+                    Some(branch) if !branch.is_empty() => (branch.to_string(), None),
+                    _ => {
+                        let branch = current_branch(&turn.cwd)
+                            .await
+                            .map_err(|err| FunctionCallError::RespondToModel(format!("{err:#}")))?;
+                        let head_sha = collect_git_info(&turn.cwd)
+                            .await
+                            .and_then(|info| info.commit_hash);
+                        (branch, head_sha)
+                    }
+                };
+                tracker
+                    .failing_ci_logs(&branch, head_sha.as_deref())
+                    .await
+                    .map(|logs| {
+                        logs.unwrap_or_else(|| {
+                            format!("The latest workflow runs on `{branch}` did not fail.")
+                        })
+                    })
+            }
+            IssueTrackerAction::PostComment => {
+                let number = require_number()?;
+                let body = args
+                    .body
+                    .as_deref()
+                    .map(str::trim)
+                    .filter(|body| !body.is_empty())
+                    .ok_or_else(|| {
+                        FunctionCallError::RespondToModel("`body` must not be empty".to_string())
+                    })?;
+                let reference = tracker.reference(number, args.pull_request);
+                let decision = session
+                    .request_command_approval(
+                        turn.as_ref(),
+                        call_id,
+                        vec![
+                            "issue_tracker".to_string(),
+                            "comment".to_string(),
+                            reference,
+                        ],
+                        turn.cwd.clone(),
+                        HashMap::new(),
+                        Some(format!("Post this comment:\n\n{body}")),
+                        None,
+                    )
+                    .await;
+                match decision {
+                    ReviewDecision::Approved
+                    | ReviewDecision::ApprovedForSession
+                    | ReviewDecision::ApprovedExecpolicyAmendment { .. } => {
+                        tracker.post_comment(number, args.pull_request, body).await
+                    }
+                    ReviewDecision::Denied | ReviewDecision::Abort => {
+                        return Err(FunctionCallError::RespondToModel(
+                            "The user declined to post the comment.".to_string(),
+                        ));
+                    }
+                }
+            }
+        };
+
+        let content =
+            result.map_err(|err| FunctionCallError::RespondToModel(format!("{err:#}")))?;
+        Ok(ToolOutput::Function {
+            content,
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+/// `number` as an issue number: a positive whole number.
+fn item_number(number: f64) -> Result<u64, FunctionCallError> {
+    if number.fract() == 0.0 && number >= 1.0 && number <= u32::MAX as f64 {
+        Ok(number as u64)
+    } else {
+        Err(FunctionCallError::RespondToModel(format!(
+            "`number` must be a positive whole number, got {number}"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::codex::make_session_and_context_with_rx;
+    use crate::turn_diff_tracker::TurnDiffTracker;
+    use pretty_assertions::assert_eq;
+
+    async fn call(arguments: serde_json::Value) -> Result<ToolOutput, FunctionCallError> {
+        let (session, turn, _rx) = make_session_and_context_with_rx().await;
+        IssueTrackerHandler
+            .handle(ToolInvocation {
+                session,
+                turn,
+                tracker: Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new())),
+                call_id: "call-1".to_string(),
+                tool_name: "issue_tracker".to_string(),
+                payload: ToolPayload::Function {
+                    arguments: arguments.to_string(),
+                },
+            })
+            .await
+    }
+
+    fn model_error(result: Result<ToolOutput, FunctionCallError>) -> String {
+        match result {
+            Err(FunctionCallError::RespondToModel(message)) => message,
+            Err(err) => panic!("unexpected error: {err:?}"),
+            Ok(_) => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn item_number_accepts_whole_json_numbers_only() {
+        assert_eq!(item_number(12.0).ok(), Some(12));
+        assert!(item_number(1.5).is_err());
+        assert!(item_number(0.0).is_err());
+        assert!(item_number(-3.0).is_err());
+    }
+
+    #[tokio::test]
+    async fn rejects_fractional_numbers_and_foreign_repo_paths() {
+        let message =
+            model_error(call(serde_json::json!({"action": "get_issue", "number": 4.5})).await);
+        assert_eq!(message, "`number` must be a positive whole number, got 4.5");
+
+        let message = model_error(
+            call(serde_json::json!({
+                "action": "get_issue",
+                "number": 4,
+                "repo": "openai/../../user",
+            }))
+            .await,
+        );
+        assert_eq!(
+            message,
+            "`openai/../../user` is not a repository of the form `owner/name`"
+        );
+    }
+
+    #[tokio::test]
+    async fn post_comment_refuses_without_approval() {
+        let (session, mut turn, _rx) = make_session_and_context_with_rx().await;
+        Arc::get_mut(&mut turn)
+            .expect("unique turn context Arc")
+            .approval_policy = AskForApproval::Never;
+        let result = IssueTrackerHandler
+            .handle(ToolInvocation {
+                session,
+                turn,
+                tracker: Arc::new(tokio::sync::Mutex::new(TurnDiffTracker::new())),
+                call_id: "call-1".to_string(),
+                tool_name: "issue_tracker".to_string(),
+                payload: ToolPayload::Function {
+                    arguments: serde_json::json!({
+                        "action": "post_comment",
+                        "number": 7,
+                        "repo": "openai/codex",
+                        "body": "Fixed in #8.",
+                    })
+                    .to_string(),
+                },
+            })
+            .await;
+        assert!(model_error(result).starts_with("Posting comments needs the user's approval"));
+    }
+}
@@ -9,6 +9,7 @@ use codex_utils_string::take_bytes_at_char_boundary;
 use serde::Deserialize;
 use tokio::fs;
 
+use crate::context_ignore::ContextIgnore;
 use crate::function_tool::FunctionCallError;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
@@ -51,7 +52,7 @@ impl ToolHandler for ListDirHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, .. } = invocation;
+        let ToolInvocation { payload, turn, .. } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -93,14 +94,20 @@ impl ToolHandler for ListDirHandler {
             ));
         }
 
-        let path = PathBuf::from(&dir_path);
+        let path = turn.host_path(&dir_path);
         if !path.is_absolute() {
             return Err(FunctionCallError::RespondToModel(
                 "dir_path must be an absolute path".to_string(),
             ));
         }
 
-        let entries = list_dir_slice(&path, offset, limit, depth).await?;
+        if turn.context_ignore.is_ignored(&path, true) {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "{dir_path} is excluded from context by ignore rules"
+            )));
+        }
+
+        let entries = list_dir_slice(&path, offset, limit, depth, &turn.context_ignore).await?;
         let mut output = Vec::with_capacity(entries.len() + 1);
         output.push(format!("Absolute path: {}", path.display()));
         output.extend(entries);
@@ -117,9 +124,10 @@ async fn list_dir_slice(
     offset: usize,
     limit: usize,
     depth: usize,
+    context_ignore: &ContextIgnore,
 ) -> Result<Vec<String>, FunctionCallError> {
     let mut entries = Vec::new();
-    collect_entries(path, Path::new(""), depth, &mut entries).await?;
+    collect_entries(path, Path::new(""), depth, context_ignore, &mut entries).await?;
 
     if entries.is_empty() {
         return Ok(Vec::new());
@@ -154,6 +162,7 @@ async fn collect_entries(
     dir_path: &Path,
     relative_prefix: &Path,
     depth: usize,
+    context_ignore: &ContextIgnore,
     entries: &mut Vec<DirEntry>,
 ) -> Result<(), FunctionCallError> {
     let mut queue = VecDeque::new();
@@ -173,6 +182,12 @@ async fn collect_entries(
                 FunctionCallError::RespondToModel(format!("failed to inspect entry: {err}"))
             })?;
 
+            let kind = DirEntryKind::from(&file_type);
+            // Excluded entries are left out entirely, not just their contents.
+            if context_ignore.is_ignored(&entry.path(), kind == DirEntryKind::Directory) {
+                continue;
+            }
+
             let file_name = entry.file_name();
             let relative_path = if prefix.as_os_str().is_empty() {
                 PathBuf::from(&file_name)
@@ -183,7 +198,6 @@ async fn collect_entries(
             let display_name = format_entry_component(&file_name);
             let display_depth = prefix.components().count();
             let sort_key = format_entry_name(&relative_path);
-            let kind = DirEntryKind::from(&file_type);
             dir_entries.push((
                 entry.path(),
                 relative_path,
@@ -307,7 +321,7 @@ mod tests {
             symlink(dir_path.join("entry.txt"), &link_path).expect("create symlink");
         }
 
-        let entries = list_dir_slice(dir_path, 1, 20, 3)
+        let entries = list_dir_slice(dir_path, 1, 20, 3, &ContextIgnore::default())
             .await
             .expect("list directory");
 
@@ -341,7 +355,7 @@ mod tests {
             .await
             .expect("create sub dir");
 
-        let err = list_dir_slice(dir_path, 10, 1, 2)
+        let err = list_dir_slice(dir_path, 10, 1, 2, &ContextIgnore::default())
             .await
             .expect_err("offset exceeds entries");
         assert_eq!(
@@ -368,7 +382,7 @@ mod tests {
             .await
             .expect("write deeper");
 
-        let entries_depth_one = list_dir_slice(dir_path, 1, 10, 1)
+        let entries_depth_one = list_dir_slice(dir_path, 1, 10, 1, &ContextIgnore::default())
             .await
             .expect("list depth 1");
         assert_eq!(
@@ -376,7 +390,7 @@ mod tests {
             vec!["nested/".to_string(), "root.txt".to_string(),]
         );
 
-        let entries_depth_two = list_dir_slice(dir_path, 1, 20, 2)
+        let entries_depth_two = list_dir_slice(dir_path, 1, 20, 2, &ContextIgnore::default())
             .await
             .expect("list depth 2");
         assert_eq!(
@@ -389,7 +403,7 @@ mod tests {
             ]
         );
 
-        let entries_depth_three = list_dir_slice(dir_path, 1, 30, 3)
+        let entries_depth_three = list_dir_slice(dir_path, 1, 30, 3, &ContextIgnore::default())
             .await
             .expect("list depth 3");
         assert_eq!(
@@ -418,7 +432,7 @@ mod tests {
             .await
             .expect("write gamma");
 
-        let entries = list_dir_slice(dir_path, 2, usize::MAX, 1)
+        let entries = list_dir_slice(dir_path, 2, usize::MAX, 1, &ContextIgnore::default())
             .await
             .expect("list without overflow");
         assert_eq!(
@@ -439,7 +453,7 @@ mod tests {
                 .expect("write file");
         }
 
-        let entries = list_dir_slice(dir_path, 1, 25, 1)
+        let entries = list_dir_slice(dir_path, 1, 25, 1, &ContextIgnore::default())
             .await
             .expect("list directory");
         assert_eq!(entries.len(), 26);
@@ -461,7 +475,8 @@ mod tests {
         tokio::fs::write(nested.join("child.txt"), b"child").await?;
         tokio::fs::write(deeper.join("grandchild.txt"), b"deep").await?;
 
-        let entries_depth_three = list_dir_slice(dir_path, 1, 3, 3).await?;
+        let entries_depth_three =
+            list_dir_slice(dir_path, 1, 3, 3, &ContextIgnore::default()).await?;
         assert_eq!(
             entries_depth_three,
             vec![
@@ -474,4 +489,22 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn skips_ignored_entries() -> anyhow::Result<()> {
+        let temp = tempdir()?;
+        let dir_path = temp.path();
+        let secrets = dir_path.join("secrets");
+        tokio::fs::create_dir(&secrets).await?;
+        tokio::fs::write(secrets.join("key.pem"), b"key").await?;
+        tokio::fs::write(dir_path.join("main.rs"), b"fn main() {}").await?;
+        tokio::fs::write(dir_path.join("cert.pem"), b"cert").await?;
+
+        let context_ignore =
+            ContextIgnore::new(dir_path, &["secrets/".to_string(), "*.pem".to_string()]);
+        let entries = list_dir_slice(dir_path, 1, 20, 2, &context_ignore).await?;
+        assert_eq!(entries, vec!["main.rs".to_string()]);
+
+        Ok(())
+    }
 }
@@ -1,26 +1,45 @@
 pub mod apply_patch;
+mod ask_user;
+mod delegate;
 mod grep_files;
+mod issue_tracker;
 mod list_dir;
 mod mcp;
 mod mcp_resource;
 mod plan;
 mod read_file;
+mod read_output_range;
+mod register_artifact;
+mod repo_map;
+mod semantic_search;
 mod shell;
+mod summarize_file;
 mod test_sync;
 mod unified_exec;
 mod view_image;
 
 pub use plan::PLAN_TOOL;
 
+pub(crate) use delegate::HANDOFF_INSTRUCTIONS;
+pub(crate) use summarize_file::SUMMARIZE_FILE_PROMPT;
+
 pub use apply_patch::ApplyPatchHandler;
+pub use ask_user::AskUserHandler;
+pub use delegate::DelegateHandler;
 pub use grep_files::GrepFilesHandler;
+pub use issue_tracker::IssueTrackerHandler;
 pub use list_dir::ListDirHandler;
 pub use mcp::McpHandler;
 pub use mcp_resource::McpResourceHandler;
 pub use plan::PlanHandler;
 pub use read_file::ReadFileHandler;
+pub use read_output_range::ReadOutputRangeHandler;
+pub use register_artifact::RegisterArtifactHandler;
+pub use repo_map::RepoMapHandler;
+pub use semantic_search::SemanticSearchHandler;
 pub use shell::ShellCommandHandler;
 pub use shell::ShellHandler;
+pub use summarize_file::SummarizeFileHandler;
 pub use test_sync::TestSyncHandler;
 pub use unified_exec::UnifiedExecHandler;
 pub use view_image::ViewImageHandler;
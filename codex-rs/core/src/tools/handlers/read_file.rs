@@ -1,5 +1,4 @@
 use std::collections::VecDeque;
-use std::path::PathBuf;
 
 use async_trait::async_trait;
 use codex_utils_string::take_bytes_at_char_boundary;
@@ -96,7 +95,12 @@ impl ToolHandler for ReadFileHandler {
     }
 
     async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
-        let ToolInvocation { payload, .. } = invocation;
+        let ToolInvocation {
+            session,
+            payload,
+            turn,
+            ..
+        } = invocation;
 
         let arguments = match payload {
             ToolPayload::Function { arguments } => arguments,
@@ -133,13 +137,19 @@ impl ToolHandler for ReadFileHandler {
             ));
         }
 
-        let path = PathBuf::from(&file_path);
+        let path = turn.host_path(&file_path);
         if !path.is_absolute() {
             return Err(FunctionCallError::RespondToModel(
                 "file_path must be an absolute path".to_string(),
             ));
         }
 
+        if turn.context_ignore.is_ignored(&path, false) {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "{file_path} is excluded from context by ignore rules"
+            )));
+        }
+
         let collected = match mode {
             ReadMode::Slice => slice::read(&path, offset, limit).await?,
             ReadMode::Indentation => {
@@ -147,6 +157,7 @@ impl ToolHandler for ReadFileHandler {
                 indentation::read_block(&path, offset, limit, indentation).await?
             }
         };
+        session.watch_files([path]).await;
         Ok(ToolOutput::Function {
             content: collected.join("\n"),
             content_items: None,
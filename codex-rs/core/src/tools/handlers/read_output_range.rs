@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::output_spill::DEFAULT_RANGE_LIMIT;
+use crate::output_spill::render_range;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct ReadOutputRangeHandler;
+
+fn default_start_line() -> usize {
+    1
+}
+
+fn default_limit() -> usize {
+    DEFAULT_RANGE_LIMIT
+}
+
+#[derive(Deserialize)]
+struct ReadOutputRangeArgs {
+    handle: String,
+    #[serde(default = "default_start_line")]
+    start_line: usize,
+    #[serde(default = "default_limit")]
+    limit: usize,
+    #[serde(default)]
+    pattern: Option<String>,
+}
+
+#[async_trait]
+impl ToolHandler for ReadOutputRangeHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session, payload, ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "read_output_range handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: ReadOutputRangeArgs = serde_json::from_str(&arguments).map_err(|e| {
+            FunctionCallError::RespondToModel(format!("failed to parse function arguments: {e:?}"))
+        })?;
+
+        if args.start_line == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "start_line must be a 1-indexed line number".to_string(),
+            ));
+        }
+        if args.limit == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "limit must be greater than zero".to_string(),
+            ));
+        }
+        let pattern = args.pattern.filter(|pattern| !pattern.is_empty());
+
+        let Some(spill) = session.services.output_spill.as_ref() else {
+            return Err(FunctionCallError::RespondToModel(
+                "saved command output is not available in this session".to_string(),
+            ));
+        };
+        let content = spill.read(&args.handle).await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "no saved output for handle `{}`: {err}",
+                args.handle
+            ))
+        })?;
+
+        Ok(ToolOutput::Function {
+            content: render_range(&content, args.start_line, args.limit, pattern.as_deref()),
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
@@ -0,0 +1,101 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+use tokio::fs;
+
+use crate::function_tool::FunctionCallError;
+use crate::protocol::Artifact;
+use crate::protocol::ArtifactRegisteredEvent;
+use crate::protocol::EventMsg;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct RegisterArtifactHandler;
+
+#[derive(Deserialize)]
+struct RegisterArtifactArgs {
+    path: String,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[async_trait]
+impl ToolHandler for RegisterArtifactHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            turn,
+            payload,
+            call_id,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "register_artifact handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: RegisterArtifactArgs = serde_json::from_str(&arguments).map_err(|e| {
+            FunctionCallError::RespondToModel(format!("failed to parse function arguments: {e:?}"))
+        })?;
+
+        let title = args.title.trim().to_string();
+        if title.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "title must not be empty".to_string(),
+            ));
+        }
+
+        let abs_path = turn.resolve_path(Some(args.path));
+        let metadata = fs::metadata(&abs_path).await.map_err(|error| {
+            FunctionCallError::RespondToModel(format!(
+                "unable to locate artifact at `{}`: {error}",
+                abs_path.display()
+            ))
+        })?;
+        if !metadata.is_file() {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "artifact path `{}` is not a file",
+                abs_path.display()
+            )));
+        }
+
+        let content = format!("Registered `{title}` ({}).", abs_path.display());
+        let artifact = Artifact {
+            id: call_id,
+            path: abs_path,
+            title,
+            description: args
+                .description
+                .map(|description| description.trim().to_string())
+                .filter(|description| !description.is_empty()),
+            size_bytes: metadata.len(),
+        };
+        session
+            .send_event(
+                turn.as_ref(),
+                EventMsg::ArtifactRegistered(ArtifactRegisteredEvent {
+                    turn_id: turn.sub_id.clone(),
+                    artifact,
+                }),
+            )
+            .await;
+
+        Ok(ToolOutput::Function {
+            content,
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
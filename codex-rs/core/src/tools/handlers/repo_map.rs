@@ -0,0 +1,146 @@
+use std::path::Path;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::codex::TurnContext;
+use crate::function_tool::FunctionCallError;
+use crate::index_watcher::IndexKind;
+use crate::repo_map::TOOL_REPO_MAP_MAX_TOKENS;
+use crate::repo_map::generate_repo_map;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct RepoMapHandler;
+
+/// Hard cap on the budget the model may request.
+const MAX_TOKENS_LIMIT: usize = 16_384;
+
+#[derive(Deserialize)]
+struct RepoMapArgs {
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    max_tokens: Option<usize>,
+}
+
+#[async_trait]
+impl ToolHandler for RepoMapHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            payload,
+            turn,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "repo_map handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: RepoMapArgs = serde_json::from_str(&arguments).map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "failed to parse function arguments: {err:?}"
+            ))
+        })?;
+
+        let max_tokens = args
+            .max_tokens
+            .unwrap_or(TOOL_REPO_MAP_MAX_TOKENS)
+            .min(MAX_TOKENS_LIMIT);
+        if max_tokens == 0 {
+            return Err(FunctionCallError::RespondToModel(
+                "max_tokens must be greater than zero".to_string(),
+            ));
+        }
+
+        let root = turn.resolve_path(args.path);
+        if !root.is_dir() {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "`{}` is not a directory",
+                root.display()
+            )));
+        }
+        if turn.context_ignore.is_ignored(&root, true) {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "`{}` is excluded from context by ignore rules",
+                root.display()
+            )));
+        }
+
+        // With a watcher, reuse the previous map until a file changes.
+        // Without one, always regenerate so the map is never stale.
+        let watcher = session
+            .services
+            .index_watcher
+            .as_ref()
+            .filter(|watcher| watcher.watches(&root));
+        let cache = &session.services.repo_map_cache;
+        let (map, freshness) = match watcher {
+            Some(watcher) => {
+                let staleness = watcher.staleness(IndexKind::RepoMap);
+                if !watcher.take_dirty(IndexKind::RepoMap).is_empty() {
+                    cache.clear();
+                }
+                match cache.get(&root, max_tokens) {
+                    Some(map) => (map, "unchanged since the previous map".to_string()),
+                    None => {
+                        let map = generate(&turn, &root, max_tokens).await?;
+                        cache.store(root.clone(), max_tokens, map.clone());
+                        let freshness = match staleness {
+                            Some(staleness) => format!("regenerated after {staleness}"),
+                            None => "freshly generated".to_string(),
+                        };
+                        (map, freshness)
+                    }
+                }
+            }
+            None => (
+                generate(&turn, &root, max_tokens).await?,
+                "freshly generated".to_string(),
+            ),
+        };
+
+        match map {
+            Some(map) => Ok(ToolOutput::Function {
+                content: format!(
+                    "Repository map for {} ({freshness}):\n{map}",
+                    root.display()
+                ),
+                content_items: None,
+                success: Some(true),
+            }),
+            None => Ok(ToolOutput::Function {
+                content: format!("No files found under {}.", root.display()),
+                content_items: None,
+                success: Some(false),
+            }),
+        }
+    }
+}
+
+async fn generate(
+    turn: &TurnContext,
+    root: &Path,
+    max_tokens: usize,
+) -> Result<Option<String>, FunctionCallError> {
+    let context_ignore = turn.context_ignore.clone();
+    let root = root.to_path_buf();
+    tokio::task::spawn_blocking(move || generate_repo_map(&root, &context_ignore, max_tokens))
+        .await
+        .map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to generate repo map: {err}"))
+        })
+}
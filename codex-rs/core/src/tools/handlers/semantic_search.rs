@@ -0,0 +1,200 @@
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::function_tool::FunctionCallError;
+use crate::index_watcher::IndexKind;
+use crate::semantic_index::SearchHit;
+use crate::semantic_index::SemanticIndex;
+use crate::semantic_index::index_root;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+
+pub struct SemanticSearchHandler;
+
+const DEFAULT_LIMIT: usize = 8;
+const MAX_LIMIT: usize = 50;
+const SNIPPET_LINES: usize = 12;
+/// Above this many changed paths, searching does not wait for a refresh and
+/// reports the index as stale instead.
+const MAX_LAZY_REFRESH_PATHS: usize = 200;
+
+#[derive(Deserialize)]
+struct SemanticSearchArgs {
+    query: String,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+#[async_trait]
+impl ToolHandler for SemanticSearchHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            payload,
+            turn,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "semantic_search handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let args: SemanticSearchArgs = serde_json::from_str(&arguments).map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "failed to parse function arguments: {err:?}"
+            ))
+        })?;
+
+        let query = args.query.trim();
+        if query.is_empty() {
+            return Err(FunctionCallError::RespondToModel(
+                "query must not be empty".to_string(),
+            ));
+        }
+        let limit = args.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+
+        let Some(root) = index_root(&turn.cwd) else {
+            return Err(FunctionCallError::RespondToModel(
+                "semantic search requires a git repository".to_string(),
+            ));
+        };
+        let index = SemanticIndex::load(&root).await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to load semantic index: {err:#}"))
+        })?;
+        let Some(mut index) = index else {
+            return Err(FunctionCallError::RespondToModel(
+                "no semantic index exists for this repository; ask the user to run `/index` to build one, or use grep_files instead".to_string(),
+            ));
+        };
+        // Query and index embeddings are only comparable from the same model.
+        if turn.client.get_provider().embedding_model() != Some(index.model()) {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "the semantic index was built with `{}`, which is not the current provider's embedding model; ask the user to run `/index rebuild`, or use grep_files instead",
+                index.model()
+            )));
+        }
+
+        // Refresh paths the watcher saw change since the last update, and
+        // tell the model when results may not reflect the working tree.
+        let mut notes = Vec::new();
+        match session
+            .services
+            .index_watcher
+            .as_ref()
+            .filter(|watcher| watcher.root() == root)
+        {
+            Some(watcher) => {
+                let staleness = watcher.staleness(IndexKind::Semantic);
+                let dirty = watcher.take_dirty(IndexKind::Semantic);
+                if dirty.len() > MAX_LAZY_REFRESH_PATHS {
+                    watcher.restore_dirty(IndexKind::Semantic, dirty);
+                    if let Some(staleness) = staleness {
+                        notes.push(format!(
+                            "Note: the index is stale ({staleness}); results for those files may be outdated. Ask the user to run `/index` to refresh it."
+                        ));
+                    }
+                } else if !dirty.is_empty() {
+                    match index
+                        .refresh_paths(&turn.client, &root, &turn.context_ignore, &dirty)
+                        .await
+                    {
+                        Ok(stats) => notes.push(format!(
+                            "Index refreshed before searching: {} file(s) re-embedded, {} removed.",
+                            stats.files_indexed, stats.files_removed
+                        )),
+                        Err(err) => {
+                            let count = dirty.len();
+                            watcher.restore_dirty(IndexKind::Semantic, dirty);
+                            notes.push(format!(
+                                "Note: {count} changed path(s) could not be reindexed ({err:#}); results for them may be outdated."
+                            ));
+                        }
+                    }
+                }
+            }
+            None => notes.push(
+                "Note: file changes are not being tracked, so results may not reflect recent edits."
+                    .to_string(),
+            ),
+        }
+
+        let query_embedding = turn
+            .client
+            .embed(index.model(), &[query.to_string()])
+            .await
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!("failed to embed query: {err}"))
+            })?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                FunctionCallError::RespondToModel("embedding response was empty".to_string())
+            })?;
+
+        let hits: Vec<SearchHit> = index
+            .search(&query_embedding, limit)
+            .into_iter()
+            .filter(|hit| !turn.context_ignore.is_ignored(&root.join(&hit.path), false))
+            .collect();
+        if hits.is_empty() {
+            notes.insert(0, "No matches found.".to_string());
+            return Ok(ToolOutput::Function {
+                content: notes.join("\n"),
+                content_items: None,
+                success: Some(false),
+            });
+        }
+
+        let mut output = notes;
+        output.push(format!(
+            "Top {} matches for \"{query}\" in {}:",
+            hits.len(),
+            root.display()
+        ));
+        for (rank, hit) in hits.iter().enumerate() {
+            output.push(format!(
+                "{}. {}:{}-{} (score {:.2})",
+                rank + 1,
+                hit.path,
+                hit.start_line,
+                hit.end_line,
+                hit.score
+            ));
+            if let Some(snippet) = read_snippet(&root.join(&hit.path), hit).await {
+                output.push(snippet);
+            }
+        }
+
+        Ok(ToolOutput::Function {
+            content: output.join("\n"),
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+/// The first few non-blank lines of the hit, indented under its heading.
+async fn read_snippet(path: &std::path::Path, hit: &SearchHit) -> Option<String> {
+    let contents = tokio::fs::read_to_string(path).await.ok()?;
+    let lines: Vec<String> = contents
+        .lines()
+        .skip(hit.start_line.saturating_sub(1))
+        .take(hit.end_line + 1 - hit.start_line)
+        .skip_while(|line| line.trim().is_empty())
+        .take(SNIPPET_LINES)
+        .map(|line| format!("   {line}"))
+        .collect();
+    (!lines.is_empty()).then(|| lines.join("\n"))
+}
@@ -1,15 +1,25 @@
 use async_trait::async_trait;
+use codex_protocol::models::CommandRisk;
 use codex_protocol::models::ShellCommandToolCallParams;
 use codex_protocol::models::ShellToolCallParams;
+use std::collections::HashMap;
 use std::sync::Arc;
 
+use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::command_safety::declared_risk::CommandDeclaration;
+use crate::command_safety::declared_risk::MISSING_DECLARATION_MESSAGE;
 use crate::exec::ExecParams;
 use crate::exec_env::create_env;
 use crate::exec_policy::create_exec_approval_requirement_for_command;
+use crate::features::Feature;
 use crate::function_tool::FunctionCallError;
 use crate::is_safe_command::is_known_safe_command;
+use crate::powershell_translate::translate_powershell_command;
 use crate::protocol::ExecCommandSource;
+use crate::protocol::SandboxPolicy;
+use crate::resource_preflight::apply_resource_preflight;
+use crate::sandboxing::SandboxPermissions;
 use crate::shell::Shell;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolOutput;
@@ -23,6 +33,7 @@ use crate::tools::registry::ToolKind;
 use crate::tools::runtimes::shell::ShellRequest;
 use crate::tools::runtimes::shell::ShellRuntime;
 use crate::tools::sandboxing::ToolCtx;
+use crate::tools::simulated_exec::simulate_command;
 
 pub struct ShellHandler;
 
@@ -34,7 +45,7 @@ impl ShellHandler {
             command: params.command,
             cwd: turn_context.resolve_path(params.workdir.clone()),
             expiration: params.timeout_ms.into(),
-            env: create_env(&turn_context.shell_environment_policy),
+            env: merged_env(turn_context, params.env),
             sandbox_permissions: params.sandbox_permissions.unwrap_or_default(),
             justification: params.justification,
             arg0: None,
@@ -60,7 +71,7 @@ impl ShellCommandHandler {
             command,
             cwd: turn_context.resolve_path(params.workdir.clone()),
             expiration: params.timeout_ms.into(),
-            env: create_env(&turn_context.shell_environment_policy),
+            env: merged_env(turn_context, params.env),
             sandbox_permissions: params.sandbox_permissions.unwrap_or_default(),
             justification: params.justification,
             arg0: None,
@@ -85,7 +96,10 @@ impl ToolHandler for ShellHandler {
         match &invocation.payload {
             ToolPayload::Function { arguments } => {
                 serde_json::from_str::<ShellToolCallParams>(arguments)
-                    .map(|params| !is_known_safe_command(&params.command))
+                    .map(|params| {
+                        has_env_overrides(params.env.as_ref())
+                            || !is_known_safe_command(&params.command)
+                    })
                     .unwrap_or(true)
             }
             ToolPayload::LocalShell { params } => !is_known_safe_command(&params.command),
@@ -111,10 +125,22 @@ impl ToolHandler for ShellHandler {
                             "failed to parse function arguments: {e:?}"
                         ))
                     })?;
+                let declaration =
+                    Self::declaration(session.as_ref(), params.intent.clone(), params.risk)?;
+                check_overrides(
+                    turn.as_ref(),
+                    params.workdir.as_deref(),
+                    params.env.as_ref(),
+                    params.sandbox_permissions.unwrap_or_default(),
+                )
+                .map_err(FunctionCallError::RespondToModel)?;
+                let env_overrides = params.env.clone().unwrap_or_default();
                 let exec_params = Self::to_exec_params(params, turn.as_ref());
                 Self::run_exec_like(
                     tool_name.as_str(),
                     exec_params,
+                    env_overrides,
+                    declaration,
                     session,
                     turn,
                     tracker,
@@ -124,10 +150,20 @@ impl ToolHandler for ShellHandler {
                 .await
             }
             ToolPayload::LocalShell { params } => {
+                // Local shell calls have no room for a declaration.
+                check_overrides(
+                    turn.as_ref(),
+                    params.workdir.as_deref(),
+                    None,
+                    SandboxPermissions::UseDefault,
+                )
+                .map_err(FunctionCallError::RespondToModel)?;
                 let exec_params = Self::to_exec_params(params, turn.as_ref());
                 Self::run_exec_like(
                     tool_name.as_str(),
                     exec_params,
+                    HashMap::new(),
+                    None,
                     session,
                     turn,
                     tracker,
@@ -162,7 +198,7 @@ impl ToolHandler for ShellCommandHandler {
             .map(|params| {
                 let shell = invocation.session.user_shell();
                 let command = Self::base_command(shell.as_ref(), &params.command, params.login);
-                !is_known_safe_command(&command)
+                has_env_overrides(params.env.as_ref()) || !is_known_safe_command(&command)
             })
             .unwrap_or(true)
     }
@@ -186,10 +222,22 @@ impl ToolHandler for ShellCommandHandler {
         let params: ShellCommandToolCallParams = serde_json::from_str(&arguments).map_err(|e| {
             FunctionCallError::RespondToModel(format!("failed to parse function arguments: {e:?}"))
         })?;
+        let declaration =
+            ShellHandler::declaration(session.as_ref(), params.intent.clone(), params.risk)?;
+        check_overrides(
+            turn.as_ref(),
+            params.workdir.as_deref(),
+            params.env.as_ref(),
+            params.sandbox_permissions.unwrap_or_default(),
+        )
+        .map_err(FunctionCallError::RespondToModel)?;
+        let env_overrides = params.env.clone().unwrap_or_default();
         let exec_params = Self::to_exec_params(params, session.as_ref(), turn.as_ref());
         ShellHandler::run_exec_like(
             tool_name.as_str(),
             exec_params,
+            env_overrides,
+            declaration,
             session,
             turn,
             tracker,
@@ -201,9 +249,29 @@ impl ToolHandler for ShellCommandHandler {
 }
 
 impl ShellHandler {
+    /// The intent and risk declared for a command. Both are required while
+    /// `command_risk_declarations` is enabled and ignored otherwise.
+    pub(crate) fn declaration(
+        session: &Session,
+        intent: Option<String>,
+        risk: Option<CommandRisk>,
+    ) -> Result<Option<CommandDeclaration>, FunctionCallError> {
+        if !session.enabled(Feature::CommandRiskDeclarations) {
+            return Ok(None);
+        }
+        CommandDeclaration::from_args(intent, risk)
+            .map(Some)
+            .ok_or_else(|| {
+                FunctionCallError::RespondToModel(MISSING_DECLARATION_MESSAGE.to_string())
+            })
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn run_exec_like(
         tool_name: &str,
         exec_params: ExecParams,
+        env_overrides: HashMap<String, String>,
+        declaration: Option<CommandDeclaration>,
         session: Arc<crate::codex::Session>,
         turn: Arc<TurnContext>,
         tracker: crate::tools::context::SharedTurnDiffTracker,
@@ -241,6 +309,11 @@ impl ShellHandler {
             return Ok(output);
         }
 
+        let exec_params = ExecParams {
+            command: turn.exec_command(exec_params.command, &exec_params.cwd),
+            ..exec_params
+        };
+        let exec_params = translate_powershell(exec_params)?;
         let source = ExecCommandSource::Agent;
         let emitter = ToolEmitter::shell(
             exec_params.command.clone(),
@@ -251,6 +324,25 @@ impl ShellHandler {
         let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
         emitter.begin(event_ctx).await;
 
+        // Nothing runs, so there is nothing to approve.
+        if turn.tools_config.simulate_commands {
+            let out = simulate_command(
+                session.as_ref(),
+                turn.as_ref(),
+                &exec_params.command,
+                &exec_params.cwd,
+            )
+            .await;
+            let success = out.exit_code == 0;
+            let event_ctx = ToolEventCtx::new(session.as_ref(), turn.as_ref(), &call_id, None);
+            let content = emitter.finish(event_ctx, Ok(out)).await?;
+            return Ok(ToolOutput::Function {
+                content,
+                content_items: None,
+                success: Some(success),
+            });
+        }
+
         let features = session.features();
         let exec_approval_requirement = create_exec_approval_requirement_for_command(
             &turn.exec_policy,
@@ -259,6 +351,23 @@ impl ShellHandler {
             turn.approval_policy,
             &turn.sandbox_policy,
             exec_params.sandbox_permissions,
+            &env_overrides,
+        )
+        .await;
+        let exec_approval_requirement = match &declaration {
+            Some(declaration) => declaration.apply(
+                &exec_params.command,
+                turn.approval_policy,
+                exec_approval_requirement,
+            ),
+            None => exec_approval_requirement,
+        };
+        let exec_approval_requirement = apply_resource_preflight(
+            session.as_ref(),
+            turn.as_ref(),
+            &exec_params.command,
+            &exec_params.cwd,
+            exec_approval_requirement,
         )
         .await;
 
@@ -267,8 +376,12 @@ impl ShellHandler {
             cwd: exec_params.cwd.clone(),
             timeout_ms: exec_params.expiration.timeout_ms(),
             env: exec_params.env.clone(),
+            env_overrides,
             sandbox_permissions: exec_params.sandbox_permissions,
-            justification: exec_params.justification.clone(),
+            justification: exec_params
+                .justification
+                .clone()
+                .or_else(|| declaration.as_ref().map(CommandDeclaration::intent_reason)),
             exec_approval_requirement,
         };
         let mut orchestrator = ToolOrchestrator::new();
@@ -292,8 +405,82 @@ impl ShellHandler {
     }
 }
 
+/// Variables that change which code a process loads. A command that runs
+/// outside the sandbox without asking the user may not set them, since they
+/// would let it run code the user never saw.
+const LOADER_ENV_VARS: &[&str] = &["LD_PRELOAD", "LD_LIBRARY_PATH", "LD_AUDIT"];
+
+/// The session's shell environment with the call's `env` applied on top.
+fn merged_env(
+    turn_context: &TurnContext,
+    overrides: Option<HashMap<String, String>>,
+) -> HashMap<String, String> {
+    let mut env = create_env(&turn_context.shell_environment_policy);
+    env.extend(overrides.unwrap_or_default());
+    env
+}
+
+/// Check the `workdir` and `env` a call asked for before anything runs.
+fn check_overrides(
+    turn_context: &TurnContext,
+    workdir: Option<&str>,
+    env: Option<&HashMap<String, String>>,
+    sandbox_permissions: SandboxPermissions,
+) -> Result<(), String> {
+    if let Some(workdir) = workdir {
+        let cwd = turn_context.resolve_path(Some(workdir.to_string()));
+        if !cwd.is_dir() {
+            return Err(format!(
+                "workdir `{}` is not an existing directory",
+                cwd.display()
+            ));
+        }
+    }
+    let Some(env) = env else {
+        return Ok(());
+    };
+    let unsandboxed_without_approval = !sandbox_permissions.requires_escalated_permissions()
+        && matches!(
+            turn_context.sandbox_policy,
+            SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. }
+        );
+    let mut keys: Vec<&String> = env.keys().collect();
+    keys.sort();
+    for key in keys {
+        if key.is_empty() || key.contains(['=', '\0']) {
+            return Err(format!("`{key}` is not a valid environment variable name"));
+        }
+        if key.starts_with("CODEX_SANDBOX") {
+            return Err(format!("`{key}` is set by Codex and cannot be overridden"));
+        }
+        if unsandboxed_without_approval && is_loader_env_var(key) {
+            return Err(format!(
+                "`{key}` cannot be set for a command that runs outside the sandbox without approval; drop it, or set sandbox_permissions to \"require_escalated\" to ask the user"
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn has_env_overrides(env: Option<&HashMap<String, String>>) -> bool {
+    env.is_some_and(|env| !env.is_empty())
+}
+
+fn is_loader_env_var(key: &str) -> bool {
+    LOADER_ENV_VARS.contains(&key) || key.starts_with("DYLD_")
+}
+
+/// Rewrite the Unix-isms of a PowerShell script, or refuse the command with
+/// hints for the model when they cannot be rewritten.
+fn translate_powershell(mut exec_params: ExecParams) -> Result<ExecParams, FunctionCallError> {
+    translate_powershell_command(&mut exec_params.command)
+        .map_err(FunctionCallError::RespondToModel)?;
+    Ok(exec_params)
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::path::PathBuf;
     use std::sync::Arc;
 
@@ -305,12 +492,16 @@ mod tests {
     use crate::is_safe_command::is_known_safe_command;
     use crate::powershell::try_find_powershell_executable_blocking;
     use crate::powershell::try_find_pwsh_executable_blocking;
+    use crate::protocol::SandboxPolicy;
     use crate::sandboxing::SandboxPermissions;
     use crate::shell::Shell;
     use crate::shell::ShellType;
     use crate::shell_snapshot::ShellSnapshot;
     use crate::tools::handlers::ShellCommandHandler;
 
+    use super::check_overrides;
+    use super::merged_env;
+
     /// The logic for is_known_safe_command() has heuristics for known shells,
     /// so we must ensure the commands generated by [ShellCommandHandler] can be
     /// recognized as safe if the `command` is safe.
@@ -376,10 +567,13 @@ mod tests {
         let params = ShellCommandToolCallParams {
             command,
             workdir,
+            env: None,
             login,
             timeout_ms,
             sandbox_permissions: Some(sandbox_permissions),
             justification: justification.clone(),
+            intent: None,
+            risk: None,
         };
 
         let exec_params = ShellCommandHandler::to_exec_params(params, &session, &turn_context);
@@ -418,4 +612,82 @@ mod tests {
             shell.derive_exec_args("echo non login shell", false)
         );
     }
+
+    #[tokio::test]
+    async fn shell_overrides_are_checked_against_the_sandbox() {
+        let (_session, mut turn_context) = make_session_and_context().await;
+        turn_context.sandbox_policy = SandboxPolicy::new_workspace_write_policy();
+        let dir = tempfile::tempdir().expect("create temp dir");
+        let workdir = dir.path().to_string_lossy().to_string();
+        let env = |key: &str| HashMap::from([(key.to_string(), "1".to_string())]);
+
+        assert_eq!(
+            check_overrides(
+                &turn_context,
+                Some(&workdir),
+                Some(&env("RUST_LOG")),
+                SandboxPermissions::UseDefault,
+            ),
+            Ok(())
+        );
+        assert!(
+            check_overrides(
+                &turn_context,
+                Some(&format!("{workdir}/missing")),
+                None,
+                SandboxPermissions::UseDefault,
+            )
+            .is_err()
+        );
+        assert_eq!(
+            check_overrides(
+                &turn_context,
+                None,
+                Some(&env("LD_PRELOAD")),
+                SandboxPermissions::UseDefault,
+            ),
+            Ok(())
+        );
+        assert!(
+            check_overrides(
+                &turn_context,
+                None,
+                Some(&env("CODEX_SANDBOX_NETWORK_DISABLED")),
+                SandboxPermissions::RequireEscalated,
+            )
+            .is_err()
+        );
+        assert!(
+            check_overrides(
+                &turn_context,
+                None,
+                Some(&env("A=B")),
+                SandboxPermissions::UseDefault,
+            )
+            .is_err()
+        );
+
+        let merged = merged_env(&turn_context, Some(env("RUST_LOG")));
+        assert_eq!(merged.get("RUST_LOG"), Some(&"1".to_string()));
+
+        turn_context.sandbox_policy = SandboxPolicy::DangerFullAccess;
+        assert!(
+            check_overrides(
+                &turn_context,
+                None,
+                Some(&env("LD_PRELOAD")),
+                SandboxPermissions::UseDefault,
+            )
+            .is_err()
+        );
+        assert_eq!(
+            check_overrides(
+                &turn_context,
+                None,
+                Some(&env("LD_PRELOAD")),
+                SandboxPermissions::RequireEscalated,
+            ),
+            Ok(())
+        );
+    }
 }
@@ -0,0 +1,381 @@
+use async_trait::async_trait;
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use codex_protocol::openai_models::ReasoningEffort;
+use futures::StreamExt;
+use futures::stream;
+use serde::Deserialize;
+
+use crate::client::ModelClient;
+use crate::client_common::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::compact::content_items_to_text;
+use crate::function_tool::FunctionCallError;
+use crate::tools::context::ToolInvocation;
+use crate::tools::context::ToolOutput;
+use crate::tools::context::ToolPayload;
+use crate::tools::registry::ToolHandler;
+use crate::tools::registry::ToolKind;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+pub struct SummarizeFileHandler;
+
+pub(crate) const SUMMARIZE_FILE_PROMPT: &str =
+    include_str!("../../../templates/summarize_file/prompt.md");
+const DEFAULT_CHUNK_LINES: usize = 400;
+/// Upper bound on model passes per call; larger files get larger chunks.
+const MAX_CHUNKS: usize = 32;
+const MAX_CHUNK_BYTES: usize = 48 * 1024;
+/// Larger files would be truncated chunk by chunk anyway.
+const MAX_FILE_BYTES: u64 = (MAX_CHUNKS * MAX_CHUNK_BYTES) as u64;
+const CHUNK_CONCURRENCY: usize = 4;
+const MAX_SYMBOLS: usize = 500;
+const MAX_SYMBOL_LENGTH: usize = 120;
+
+/// Keywords that introduce a declaration worth indexing, across the languages
+/// we commonly see. Matched after stripping visibility and other modifiers.
+const DECLARATION_KEYWORDS: &[&str] = &[
+    "fn ",
+    "struct ",
+    "enum ",
+    "trait ",
+    "impl ",
+    "impl<",
+    "mod ",
+    "type ",
+    "macro_rules! ",
+    "def ",
+    "class ",
+    "function ",
+    "interface ",
+    "func ",
+];
+const DECLARATION_MODIFIERS: &[&str] = &[
+    "pub ",
+    "pub(crate) ",
+    "pub(super) ",
+    "export ",
+    "default ",
+    "async ",
+    "unsafe ",
+    "abstract ",
+    "public ",
+    "private ",
+    "protected ",
+    "static ",
+];
+
+#[derive(Deserialize)]
+struct SummarizeFileArgs {
+    file_path: String,
+    /// A JSON number, as the schema says, so `400.0` is accepted too.
+    #[serde(default)]
+    chunk_lines: Option<f64>,
+}
+
+#[async_trait]
+impl ToolHandler for SummarizeFileHandler {
+    fn kind(&self) -> ToolKind {
+        ToolKind::Function
+    }
+
+    async fn handle(&self, invocation: ToolInvocation) -> Result<ToolOutput, FunctionCallError> {
+        let ToolInvocation {
+            session,
+            payload,
+            turn,
+            ..
+        } = invocation;
+
+        let arguments = match payload {
+            ToolPayload::Function { arguments } => arguments,
+            _ => {
+                return Err(FunctionCallError::RespondToModel(
+                    "summarize_file handler received unsupported payload".to_string(),
+                ));
+            }
+        };
+
+        let SummarizeFileArgs {
+            file_path,
+            chunk_lines,
+        } = serde_json::from_str(&arguments).map_err(|err| {
+            FunctionCallError::RespondToModel(format!(
+                "failed to parse function arguments: {err:?}"
+            ))
+        })?;
+
+        let path = turn.host_path(&file_path);
+        if !path.is_absolute() {
+            return Err(FunctionCallError::RespondToModel(
+                "file_path must be an absolute path".to_string(),
+            ));
+        }
+        if turn.context_ignore.is_ignored(&path, false) {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "{file_path} is excluded from context by ignore rules"
+            )));
+        }
+        let chunk_lines = match chunk_lines {
+            None => DEFAULT_CHUNK_LINES,
+            Some(lines) if lines >= 1.0 && lines.fract() == 0.0 => lines as usize,
+            Some(_) => {
+                return Err(FunctionCallError::RespondToModel(
+                    "chunk_lines must be a whole number greater than zero".to_string(),
+                ));
+            }
+        };
+
+        let size = tokio::fs::metadata(&path)
+            .await
+            .map_err(|err| {
+                FunctionCallError::RespondToModel(format!("failed to read file: {err}"))
+            })?
+            .len();
+        if size > MAX_FILE_BYTES {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "{file_path} is {size} bytes; summarize_file reads at most {MAX_FILE_BYTES}. Read the parts you need instead."
+            )));
+        }
+
+        let bytes = tokio::fs::read(&path).await.map_err(|err| {
+            FunctionCallError::RespondToModel(format!("failed to read file: {err}"))
+        })?;
+        let contents = String::from_utf8(bytes).map_err(|_| {
+            FunctionCallError::RespondToModel(
+                "file is not valid UTF-8 text and cannot be summarized".to_string(),
+            )
+        })?;
+
+        let lines: Vec<&str> = contents.lines().collect();
+        let chunks = chunk_lines_evenly(&lines, chunk_lines);
+
+        // Chunk summaries do not need deep reasoning; keep the passes cheap,
+        // on `summarize_file_model` when one is configured.
+        let config = turn.client.config();
+        let client = match config.summarize_file_model.as_deref() {
+            Some(model) => {
+                let model_family = session
+                    .services
+                    .models_manager
+                    .construct_model_family(model, &config)
+                    .await;
+                turn.client.with_model_family(model, model_family)
+            }
+            None => turn.client.clone(),
+        }
+        .with_reasoning_effort(Some(ReasoningEffort::Low));
+        let summaries: Vec<String> = stream::iter(chunks.iter())
+            .map(|chunk| summarize_chunk(&client, &file_path, chunk))
+            .buffered(CHUNK_CONCURRENCY)
+            .collect()
+            .await;
+
+        let mut output = vec![format!("File: {file_path} ({} lines)", lines.len())];
+        output.push(String::new());
+        output.push("## Summary".to_string());
+        for (chunk, summary) in chunks.iter().zip(summaries) {
+            output.push(format!("### Lines {}-{}", chunk.start, chunk.end));
+            output.push(summary);
+        }
+        output.push(String::new());
+        output.push("## Symbols".to_string());
+        let symbols = index_symbols(&lines);
+        if symbols.is_empty() {
+            output.push("(no top-level declarations found)".to_string());
+        } else {
+            output.extend(symbols);
+        }
+
+        Ok(ToolOutput::Function {
+            content: output.join("\n"),
+            content_items: None,
+            success: Some(true),
+        })
+    }
+}
+
+/// A contiguous, 1-indexed inclusive range of lines.
+#[derive(Debug, PartialEq)]
+struct Chunk {
+    start: usize,
+    end: usize,
+    text: String,
+}
+
+fn chunk_lines_evenly(lines: &[&str], requested_lines: usize) -> Vec<Chunk> {
+    if lines.is_empty() {
+        return Vec::new();
+    }
+    let chunk_size = requested_lines.max(lines.len().div_ceil(MAX_CHUNKS));
+    lines
+        .chunks(chunk_size)
+        .enumerate()
+        .map(|(index, chunk)| {
+            let start = index * chunk_size + 1;
+            Chunk {
+                start,
+                end: start + chunk.len() - 1,
+                text: chunk.join("\n"),
+            }
+        })
+        .collect()
+}
+
+async fn summarize_chunk(client: &ModelClient, file_path: &str, chunk: &Chunk) -> String {
+    let text = truncate_text(&chunk.text, TruncationPolicy::Bytes(MAX_CHUNK_BYTES));
+    let prompt = Prompt {
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText {
+                text: format!(
+                    "File: {file_path}\nLines {}-{}:\n```\n{text}\n```",
+                    chunk.start, chunk.end
+                ),
+            }],
+        }],
+        tools: Vec::new(),
+        parallel_tool_calls: false,
+        base_instructions_override: Some(SUMMARIZE_FILE_PROMPT.to_string()),
+        locale_instructions: None,
+        model_instructions: None,
+        composed_instructions: None,
+        output_schema: None,
+    };
+
+    match collect_response_text(client, &prompt).await {
+        Ok(summary) if !summary.trim().is_empty() => summary.trim().to_string(),
+        Ok(_) => "(no summary returned)".to_string(),
+        Err(err) => format!("(summary unavailable: {err})"),
+    }
+}
+
+async fn collect_response_text(client: &ModelClient, prompt: &Prompt) -> Result<String, String> {
+    let mut stream = client.stream(prompt).await.map_err(|err| err.to_string())?;
+    let mut output_items: Vec<String> = Vec::new();
+    let mut streamed_text = String::new();
+
+    loop {
+        let Some(event) = stream.next().await else {
+            return Err("stream closed before completion".to_string());
+        };
+        match event {
+            Ok(ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. })) => {
+                if let Some(text) = content_items_to_text(&content) {
+                    output_items.push(text);
+                }
+            }
+            Ok(ResponseEvent::OutputTextDelta(delta)) => streamed_text.push_str(&delta),
+            Ok(ResponseEvent::Completed { .. }) => break,
+            Ok(_) => {}
+            Err(err) => return Err(err.to_string()),
+        }
+    }
+
+    if output_items.is_empty() {
+        Ok(streamed_text)
+    } else {
+        Ok(output_items.join("\n"))
+    }
+}
+
+/// Build a line-numbered index of declarations using lightweight keyword
+/// matching. Deeply nested declarations are skipped to keep the index
+/// focused on the file's structure.
+fn index_symbols(lines: &[&str]) -> Vec<String> {
+    let mut symbols = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if symbols.len() == MAX_SYMBOLS {
+            symbols.push(format!("(symbol index truncated at {MAX_SYMBOLS} entries)"));
+            break;
+        }
+        let indent = line.len() - line.trim_start().len();
+        if indent > 4 {
+            continue;
+        }
+        if let Some(signature) = declaration_signature(line.trim()) {
+            symbols.push(format!("L{}: {signature}", index + 1));
+        }
+    }
+    symbols
+}
+
+fn declaration_signature(line: &str) -> Option<String> {
+    let mut rest = line;
+    while let Some(stripped) = DECLARATION_MODIFIERS
+        .iter()
+        .find_map(|modifier| rest.strip_prefix(modifier))
+    {
+        rest = stripped;
+    }
+    if !DECLARATION_KEYWORDS
+        .iter()
+        .any(|keyword| rest.starts_with(keyword))
+    {
+        return None;
+    }
+
+    let signature = line
+        .split_once('{')
+        .map_or(line, |(head, _)| head)
+        .trim_end()
+        .trim_end_matches(':');
+    if signature.len() > MAX_SYMBOL_LENGTH {
+        let mut end = MAX_SYMBOL_LENGTH;
+        while !signature.is_char_boundary(end) {
+            end -= 1;
+        }
+        Some(format!("{}…", &signature[..end]))
+    } else {
+        Some(signature.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn chunks_grow_to_respect_chunk_cap() {
+        let lines: Vec<&str> = vec!["x"; MAX_CHUNKS * 10 + 1];
+        let chunks = chunk_lines_evenly(&lines, 5);
+        assert!(chunks.len() <= MAX_CHUNKS);
+        assert_eq!((chunks[0].start, chunks[0].end), (1, 11));
+        let last = chunks.last().expect("chunks");
+        assert_eq!(last.end, lines.len());
+    }
+
+    #[test]
+    fn indexes_top_level_declarations() {
+        let source = [
+            "use std::fmt;",
+            "",
+            "pub(crate) struct Widget {",
+            "    name: String,",
+            "}",
+            "",
+            "impl Widget {",
+            "    pub async fn render(&self) -> String {",
+            "        fn helper() {}",
+            "        String::new()",
+            "    }",
+            "}",
+            "class Legacy:",
+            "    def run(self):",
+        ];
+
+        assert_eq!(
+            index_symbols(&source),
+            vec![
+                "L3: pub(crate) struct Widget".to_string(),
+                "L7: impl Widget".to_string(),
+                "L8: pub async fn render(&self) -> String".to_string(),
+                "L13: class Legacy".to_string(),
+                "L14: def run(self)".to_string(),
+            ]
+        );
+    }
+}
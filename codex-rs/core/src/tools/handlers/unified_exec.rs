@@ -1,5 +1,7 @@
+use crate::command_safety::declared_risk::CommandDeclaration;
 use crate::function_tool::FunctionCallError;
 use crate::is_safe_command::is_known_safe_command;
+use crate::powershell_translate::translate_powershell_command;
 use crate::protocol::EventMsg;
 use crate::protocol::ExecCommandSource;
 use crate::protocol::TerminalInteractionEvent;
@@ -12,15 +14,18 @@ use crate::tools::context::ToolPayload;
 use crate::tools::events::ToolEmitter;
 use crate::tools::events::ToolEventCtx;
 use crate::tools::events::ToolEventStage;
+use crate::tools::handlers::ShellHandler;
 use crate::tools::handlers::apply_patch::intercept_apply_patch;
 use crate::tools::registry::ToolHandler;
 use crate::tools::registry::ToolKind;
+use crate::tools::terminal_output_for_model;
 use crate::unified_exec::ExecCommandRequest;
 use crate::unified_exec::UnifiedExecContext;
 use crate::unified_exec::UnifiedExecResponse;
 use crate::unified_exec::UnifiedExecSessionManager;
 use crate::unified_exec::WriteStdinRequest;
 use async_trait::async_trait;
+use codex_protocol::models::CommandRisk;
 use serde::Deserialize;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -44,6 +49,10 @@ struct ExecCommandArgs {
     sandbox_permissions: SandboxPermissions,
     #[serde(default)]
     justification: Option<String>,
+    #[serde(default)]
+    intent: Option<String>,
+    #[serde(default)]
+    risk: Option<CommandRisk>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -126,8 +135,12 @@ impl ToolHandler for UnifiedExecHandler {
                         "failed to parse exec_command arguments: {err:?}"
                     ))
                 })?;
+                let declaration =
+                    ShellHandler::declaration(session.as_ref(), args.intent.clone(), args.risk)?;
+                let mut command = get_command(&args, session.user_shell());
+                translate_powershell_command(&mut command)
+                    .map_err(FunctionCallError::RespondToModel)?;
                 let process_id = manager.allocate_process_id().await;
-                let command = get_command(&args, session.user_shell());
 
                 let ExecCommandArgs {
                     workdir,
@@ -172,6 +185,8 @@ impl ToolHandler for UnifiedExecHandler {
                     return Ok(output);
                 }
 
+                let command = context.turn.exec_command(command, &cwd);
+
                 let event_ctx = ToolEventCtx::new(
                     context.session.as_ref(),
                     context.turn.as_ref(),
@@ -195,7 +210,10 @@ impl ToolHandler for UnifiedExecHandler {
                             max_output_tokens,
                             workdir,
                             sandbox_permissions,
-                            justification,
+                            justification: justification.or_else(|| {
+                                declaration.as_ref().map(CommandDeclaration::intent_reason)
+                            }),
+                            declaration,
                         },
                         &context,
                     )
@@ -240,7 +258,20 @@ impl ToolHandler for UnifiedExecHandler {
             }
         };
 
-        let content = format_response(&response);
+        let spill_notice = match session.services.output_spill.as_ref() {
+            Some(spill) => {
+                let full_output = String::from_utf8_lossy(&response.raw_output);
+                if response.output != full_output
+                    || full_output.len() > turn.truncation_policy.byte_budget()
+                {
+                    spill.save_full_output(&call_id, &full_output).await
+                } else {
+                    None
+                }
+            }
+            None => None,
+        };
+        let content = format_response(&response, spill_notice.as_deref());
 
         Ok(ToolOutput::Function {
             content,
@@ -262,7 +293,7 @@ fn get_command(args: &ExecCommandArgs, session_shell: Arc<Shell>) -> Vec<String>
     shell.derive_exec_args(&args.cmd, args.login)
 }
 
-fn format_response(response: &UnifiedExecResponse) -> String {
+fn format_response(response: &UnifiedExecResponse, spill_notice: Option<&str>) -> String {
     let mut sections = Vec::new();
 
     if !response.chunk_id.is_empty() {
@@ -286,7 +317,10 @@ fn format_response(response: &UnifiedExecResponse) -> String {
     }
 
     sections.push("Output:".to_string());
-    sections.push(response.output.clone());
+    if let Some(notice) = spill_notice {
+        sections.push(notice.to_string());
+    }
+    sections.push(terminal_output_for_model(&response.output));
 
     sections.join("\n")
 }
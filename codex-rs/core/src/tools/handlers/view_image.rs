@@ -49,6 +49,13 @@ impl ToolHandler for ViewImageHandler {
 
         let abs_path = turn.resolve_path(Some(args.path));
 
+        if turn.context_ignore.is_ignored(&abs_path, false) {
+            return Err(FunctionCallError::RespondToModel(format!(
+                "`{}` is excluded from context by ignore rules",
+                abs_path.display()
+            )));
+        }
+
         let metadata = fs::metadata(&abs_path).await.map_err(|error| {
             FunctionCallError::RespondToModel(format!(
                 "unable to locate image at `{}`: {error}",
@@ -1,3 +1,4 @@
+pub(crate) mod argument_repair;
 pub mod context;
 pub mod events;
 pub(crate) mod handlers;
@@ -7,12 +8,16 @@ pub mod registry;
 pub mod router;
 pub mod runtimes;
 pub mod sandboxing;
+pub(crate) mod simulated_exec;
 pub mod spec;
 
 use crate::exec::ExecToolCallOutput;
 use crate::truncate::TruncationPolicy;
 use crate::truncate::formatted_truncate_text;
 use crate::truncate::truncate_text;
+use crate::truncate::truncate_text_keeping_tail;
+use codex_utils_string::collapse_carriage_returns;
+use codex_utils_string::strip_ansi_escapes;
 pub use router::ToolRouter;
 use serde::Serialize;
 
@@ -22,6 +27,10 @@ pub(crate) const TELEMETRY_PREVIEW_MAX_LINES: usize = 64; // lines
 pub(crate) const TELEMETRY_PREVIEW_TRUNCATION_NOTICE: &str =
     "[... telemetry preview truncated ...]";
 
+/// Share of the budget for the output of a failed command that goes to its
+/// end, where the error usually is, or to stderr when it was captured apart.
+const FAILED_OUTPUT_TAIL_PERCENT: usize = 75;
+
 /// Format the combined exec output for sending back to the model.
 /// Includes exit code and duration metadata; truncates large bodies safely.
 pub fn format_exec_output_for_model_structured(
@@ -74,7 +83,7 @@ pub fn format_exec_output_for_model_freeform(
 
     let total_lines = content.lines().count();
 
-    let formatted_output = truncate_text(&content, truncation_policy);
+    let formatted_output = truncate_exec_content(exec_output, &content, truncation_policy);
 
     let mut sections = Vec::new();
 
@@ -97,18 +106,161 @@ pub fn format_exec_output_str(
     let content = build_content_with_timeout(exec_output);
 
     // Truncate for model consumption before serialization.
+    if exec_output.exit_code == 0 {
+        return formatted_truncate_text(&content, truncation_policy);
+    }
+    if content.len() <= truncation_policy.byte_budget() {
+        return content;
+    }
+    let total_lines = content.lines().count();
+    let result = truncate_exec_content(exec_output, &content, truncation_policy);
+    format!("Total output lines: {total_lines}\n\n{result}")
+}
+
+/// Truncate `content`, the output of `exec_output` as built for the model.
+/// A failed command keeps more of its end than of its beginning; when its
+/// stderr was captured apart from stdout, the output becomes stderr with as
+/// many of the last lines of stdout as fit, so the error is not cut out of
+/// the middle.
+fn truncate_exec_content(
+    exec_output: &ExecToolCallOutput,
+    content: &str,
+    truncation_policy: TruncationPolicy,
+) -> String {
+    let budget = truncation_policy.byte_budget();
+    if exec_output.exit_code == 0 || budget == 0 || content.len() <= budget {
+        return truncate_text(content, truncation_policy);
+    }
+    let stderr = terminal_output_for_model(&exec_output.stderr.text);
+    if stderr.trim().is_empty() {
+        return truncate_text_keeping_tail(content, truncation_policy, FAILED_OUTPUT_TAIL_PERCENT);
+    }
+
+    let stdout = terminal_output_for_model(&exec_output.stdout.text);
+    let stdout_budget = stdout
+        .len()
+        .min(budget * (100 - FAILED_OUTPUT_TAIL_PERCENT) / 100);
+    let stderr = truncate_text_keeping_tail(
+        stderr.trim_end(),
+        truncation_policy.mul((budget - stdout_budget) as f64 / budget as f64),
+        FAILED_OUTPUT_TAIL_PERCENT,
+    );
+    let mut sections = Vec::new();
+    if !stdout.trim().is_empty() {
+        let (tail, omitted_lines) = tail_lines(&stdout, budget.saturating_sub(stderr.len()));
+        sections.push(if omitted_lines == 0 {
+            format!("stdout:\n{tail}")
+        } else {
+            format!("stdout ({omitted_lines} earlier lines omitted):\n{tail}")
+        });
+    }
+    sections.push(format!("stderr:\n{stderr}"));
+    with_timeout_notice(exec_output, sections.join("\n\n"))
+}
+
+/// The last whole lines of `text` that fit in `max_bytes`, and how many
+/// lines come before them.
+fn tail_lines(text: &str, max_bytes: usize) -> (&str, usize) {
+    let text = text.trim_end();
+    let mut start = text.len();
+    for (index, _) in text.rmatch_indices('\n') {
+        if text.len() - (index + 1) > max_bytes {
+            break;
+        }
+        start = index + 1;
+    }
+    if text.len() <= max_bytes {
+        start = 0;
+    }
+    (&text[start..], text[..start].lines().count())
+}
+
+/// Like [`format_exec_output_str`], but keeps ANSI styling so clients can
+/// render the output as the command printed it.
+pub fn format_exec_output_for_display(
+    exec_output: &ExecToolCallOutput,
+    truncation_policy: TruncationPolicy,
+) -> String {
+    let content = with_timeout_notice(
+        exec_output,
+        collapse_carriage_returns(&exec_output.aggregated_output.text),
+    );
     formatted_truncate_text(&content, truncation_policy)
 }
 
+/// Plain-text view of terminal output for the model: escape sequences are
+/// stripped and redrawn lines keep only their final contents.
+pub(crate) fn terminal_output_for_model(output: &str) -> String {
+    strip_ansi_escapes(&collapse_carriage_returns(output))
+}
+
 /// Extracts exec output content and prepends a timeout message if the command timed out.
 fn build_content_with_timeout(exec_output: &ExecToolCallOutput) -> String {
+    with_timeout_notice(
+        exec_output,
+        terminal_output_for_model(&exec_output.aggregated_output.text),
+    )
+}
+
+fn with_timeout_notice(exec_output: &ExecToolCallOutput, content: String) -> String {
     if exec_output.timed_out {
         format!(
-            "command timed out after {} milliseconds\n{}",
-            exec_output.duration.as_millis(),
-            exec_output.aggregated_output.text
+            "command timed out after {} milliseconds\n{content}",
+            exec_output.duration.as_millis()
         )
     } else {
-        exec_output.aggregated_output.text.clone()
+        content
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exec::StreamOutput;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn failed_commands_keep_stderr_and_the_end_of_stdout() {
+        let stdout: String = (1..=200).map(|line| format!("line {line}\n")).collect();
+        let output = ExecToolCallOutput {
+            exit_code: 1,
+            stdout: StreamOutput::new(stdout.clone()),
+            stderr: StreamOutput::new("error: boom\n".to_string()),
+            aggregated_output: StreamOutput::new(format!("{stdout}error: boom\n")),
+            ..Default::default()
+        };
+        let kept: Vec<String> = (191..=200).map(|line| format!("line {line}")).collect();
+
+        assert_eq!(
+            format_exec_output_str(&output, TruncationPolicy::Bytes(100)),
+            format!(
+                "Total output lines: 201\n\nstdout (190 earlier lines omitted):\n{}\n\nstderr:\nerror: boom",
+                kept.join("\n")
+            )
+        );
+    }
+
+    #[test]
+    fn failed_commands_without_separate_stderr_keep_more_of_the_end() {
+        let output = ExecToolCallOutput {
+            exit_code: 2,
+            aggregated_output: StreamOutput::new("abcdefghijklmnopqrstuvwxyz".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            format_exec_output_str(&output, TruncationPolicy::Bytes(8)),
+            "Total output lines: 1\n\nab…18 chars truncated…uvwxyz"
+        );
+        assert_eq!(
+            format_exec_output_str(
+                &ExecToolCallOutput {
+                    exit_code: 0,
+                    ..output
+                },
+                TruncationPolicy::Bytes(8)
+            ),
+            "Total output lines: 1\n\nabcd…18 chars truncated…wxyz"
+        );
     }
 }
@@ -66,6 +66,8 @@ impl ToolRegistry {
         let call_id_owned = invocation.call_id.clone();
         let otel = invocation.turn.client.get_otel_manager();
         let payload_for_response = invocation.payload.clone();
+        let session = invocation.session.clone();
+        let turn = invocation.turn.clone();
         let log_payload = payload_for_response.log_payload();
 
         let handler = match self.handler(tool_name.as_ref()) {
@@ -130,15 +132,32 @@ impl ToolRegistry {
             )
             .await;
 
+        session
+            .record_tool_call_progress(&turn, tool_name.as_ref())
+            .await;
+
         match result {
             Ok(_) => {
                 let mut guard = output_cell.lock().await;
                 let output = guard.take().ok_or_else(|| {
                     FunctionCallError::Fatal("tool produced no output".to_string())
                 })?;
+                if let Some(usage_stats) = session.services.usage_stats.as_ref() {
+                    usage_stats.record_tool_call(output.success_for_logging());
+                }
+                if output.success_for_logging()
+                    && let Some(tool_usage) = session.services.tool_usage.as_ref()
+                {
+                    tool_usage.record(tool_name.as_ref(), &payload_for_response);
+                }
                 Ok(output.into_response(&call_id_owned, &payload_for_response))
             }
-            Err(err) => Err(err),
+            Err(err) => {
+                if let Some(usage_stats) = session.services.usage_stats.as_ref() {
+                    usage_stats.record_tool_call(false);
+                }
+                Err(err)
+            }
         }
     }
 }
@@ -1,8 +1,10 @@
+use crate::client_common::tools::ResponsesApiTool;
 use crate::client_common::tools::ToolSpec;
 use crate::codex::Session;
 use crate::codex::TurnContext;
 use crate::function_tool::FunctionCallError;
 use crate::sandboxing::SandboxPermissions;
+use crate::tools::argument_repair::repair_invalid_arguments;
 use crate::tools::context::SharedTurnDiffTracker;
 use crate::tools::context::ToolInvocation;
 use crate::tools::context::ToolPayload;
@@ -10,6 +12,7 @@ use crate::tools::registry::ConfiguredToolSpec;
 use crate::tools::registry::ToolRegistry;
 use crate::tools::spec::ToolsConfig;
 use crate::tools::spec::build_specs;
+use crate::tools::spec::fit_tool_descriptions;
 use codex_protocol::models::LocalShellAction;
 use codex_protocol::models::ResponseInputItem;
 use codex_protocol::models::ResponseItem;
@@ -28,6 +31,8 @@ pub struct ToolCall {
 pub struct ToolRouter {
     registry: ToolRegistry,
     specs: Vec<ConfiguredToolSpec>,
+    allowed_tools: Option<Vec<String>>,
+    repair_tool_arguments: bool,
 }
 
 impl ToolRouter {
@@ -36,9 +41,20 @@ impl ToolRouter {
         mcp_tools: Option<HashMap<String, mcp_types::Tool>>,
     ) -> Self {
         let builder = build_specs(config, mcp_tools);
-        let (specs, registry) = builder.build();
+        let (mut specs, registry) = builder.build();
+        if let Some(allowed_tools) = &config.allowed_tools {
+            specs.retain(|spec| allowed_tools.iter().any(|name| name == spec.spec.name()));
+        }
+        if let Some(budget) = config.description_token_budget {
+            fit_tool_descriptions(&mut specs, budget);
+        }
 
-        Self { registry, specs }
+        Self {
+            registry,
+            specs,
+            allowed_tools: config.allowed_tools.clone(),
+            repair_tool_arguments: config.repair_tool_arguments,
+        }
     }
 
     pub fn specs(&self) -> Vec<ToolSpec> {
@@ -48,6 +64,13 @@ impl ToolRouter {
             .collect()
     }
 
+    fn function_spec(&self, tool_name: &str) -> Option<&ResponsesApiTool> {
+        self.specs.iter().find_map(|config| match &config.spec {
+            ToolSpec::Function(tool) if tool.name == tool_name => Some(tool),
+            _ => None,
+        })
+    }
+
     pub fn tool_supports_parallel(&self, tool_name: &str) -> bool {
         self.specs
             .iter()
@@ -110,9 +133,12 @@ impl ToolRouter {
                         let params = ShellToolCallParams {
                             command: exec.command,
                             workdir: exec.working_directory,
+                            env: None,
                             timeout_ms: exec.timeout_ms,
                             sandbox_permissions: Some(SandboxPermissions::UseDefault),
                             justification: None,
+                            intent: None,
+                            risk: None,
                         };
                         Ok(Some(ToolCall {
                             tool_name: "local_shell".to_string(),
@@ -142,6 +168,45 @@ impl ToolRouter {
         let payload_outputs_custom = matches!(payload, ToolPayload::Custom { .. });
         let failure_call_id = call_id.clone();
 
+        if let Some(allowed_tools) = &self.allowed_tools
+            && !allowed_tools.contains(&tool_name)
+        {
+            return Ok(Self::failure_response(
+                failure_call_id,
+                payload_outputs_custom,
+                FunctionCallError::RespondToModel(format!(
+                    "tool `{tool_name}` is not available in this step"
+                )),
+            ));
+        }
+
+        let payload = match self.function_spec(&tool_name) {
+            Some(tool) if self.repair_tool_arguments => match payload {
+                ToolPayload::Function { arguments } => ToolPayload::Function {
+                    arguments: repair_invalid_arguments(&session, &turn, tool, &call_id, arguments)
+                        .await,
+                },
+                ToolPayload::Mcp {
+                    server,
+                    tool: mcp_tool,
+                    raw_arguments,
+                } => ToolPayload::Mcp {
+                    server,
+                    tool: mcp_tool,
+                    raw_arguments: repair_invalid_arguments(
+                        &session,
+                        &turn,
+                        tool,
+                        &call_id,
+                        raw_arguments,
+                    )
+                    .await,
+                },
+                payload => payload,
+            },
+            _ => payload,
+        };
+
         let invocation = ToolInvocation {
             session,
             turn,
@@ -117,6 +117,7 @@ impl Approvable<ApplyPatchRequest> for ApplyPatchRuntime {
                             call_id,
                             vec!["apply_patch".to_string()],
                             cwd,
+                            HashMap::new(),
                             Some(reason),
                             None,
                         )
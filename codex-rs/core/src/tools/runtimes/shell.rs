@@ -5,8 +5,10 @@ Executes shell requests under the orchestrator: asks for approval when needed,
 builds a CommandSpec, and runs it under the current SandboxAttempt.
 */
 use crate::exec::ExecToolCallOutput;
+use crate::features::Feature;
 use crate::sandboxing::SandboxPermissions;
 use crate::sandboxing::execute_env;
+use crate::sandboxing::execute_env_in_pty;
 use crate::tools::runtimes::build_command_spec;
 use crate::tools::runtimes::maybe_wrap_shell_lc_with_snapshot;
 use crate::tools::sandboxing::Approvable;
@@ -22,6 +24,8 @@ use crate::tools::sandboxing::ToolRuntime;
 use crate::tools::sandboxing::with_cached_approval;
 use codex_protocol::protocol::ReviewDecision;
 use futures::future::BoxFuture;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 #[derive(Clone, Debug)]
@@ -29,7 +33,9 @@ pub struct ShellRequest {
     pub command: Vec<String>,
     pub cwd: PathBuf,
     pub timeout_ms: Option<u64>,
-    pub env: std::collections::HashMap<String, String>,
+    pub env: HashMap<String, String>,
+    /// The part of `env` the tool call set itself, shown in approval prompts.
+    pub env_overrides: HashMap<String, String>,
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
     pub exec_approval_requirement: ExecApprovalRequirement,
@@ -42,6 +48,7 @@ pub struct ShellRuntime;
 pub(crate) struct ApprovalKey {
     command: Vec<String>,
     cwd: PathBuf,
+    env_overrides: BTreeMap<String, String>,
     sandbox_permissions: SandboxPermissions,
 }
 
@@ -75,6 +82,7 @@ impl Approvable<ShellRequest> for ShellRuntime {
         ApprovalKey {
             command: req.command.clone(),
             cwd: req.cwd.clone(),
+            env_overrides: req.env_overrides.clone().into_iter().collect(),
             sandbox_permissions: req.sandbox_permissions,
         }
     }
@@ -87,6 +95,7 @@ impl Approvable<ShellRequest> for ShellRuntime {
         let key = self.approval_key(req);
         let command = req.command.clone();
         let cwd = req.cwd.clone();
+        let env_overrides = req.env_overrides.clone();
         let reason = ctx
             .retry_reason
             .clone()
@@ -102,6 +111,7 @@ impl Approvable<ShellRequest> for ShellRuntime {
                         call_id,
                         command,
                         cwd,
+                        env_overrides,
                         reason,
                         req.exec_approval_requirement
                             .proposed_execpolicy_amendment()
@@ -156,9 +166,12 @@ impl ToolRuntime<ShellRequest, ExecToolCallOutput> for ShellRuntime {
         let env = attempt
             .env_for(spec)
             .map_err(|err| ToolError::Codex(err.into()))?;
-        let out = execute_env(env, attempt.policy, Self::stdout_stream(ctx))
-            .await
-            .map_err(ToolError::Codex)?;
+        let out = if ctx.session.enabled(Feature::ShellPty) {
+            execute_env_in_pty(env, attempt.policy, Self::stdout_stream(ctx)).await
+        } else {
+            execute_env(env, attempt.policy, Self::stdout_stream(ctx)).await
+        }
+        .map_err(ToolError::Codex)?;
         Ok(out)
     }
 }
@@ -120,6 +120,7 @@ impl Approvable<UnifiedExecRequest> for UnifiedExecRuntime<'_> {
                         call_id,
                         command,
                         cwd,
+                        HashMap::new(),
                         reason,
                         req.exec_approval_requirement
                             .proposed_execpolicy_amendment()
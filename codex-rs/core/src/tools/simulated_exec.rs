@@ -0,0 +1,183 @@
+//! Simulated execution, enabled with `[features] simulated_exec`.
+//!
+//! Shell tool calls are not run. A separate model request predicts what the
+//! command would print and its exit code, and that prediction is returned to
+//! the model and shown in the UI behind a notice saying it was simulated.
+//! This makes it possible to rehearse risky operations such as migrations or
+//! deploys before running them for real. apply_patch calls are not affected.
+
+use std::path::Path;
+use std::time::Duration;
+
+use codex_protocol::models::ContentItem;
+use codex_protocol::models::ResponseItem;
+use futures::StreamExt;
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::client_common::Prompt;
+use crate::client_common::ResponseEvent;
+use crate::codex::Session;
+use crate::codex::TurnContext;
+use crate::compact::content_items_to_text;
+use crate::error::CodexErr;
+use crate::error::Result as CodexResult;
+use crate::exec::ExecToolCallOutput;
+use crate::exec::StreamOutput;
+use crate::parse_command::shlex_join;
+use crate::tools::argument_repair::extract_json_object;
+use crate::truncate::TruncationPolicy;
+use crate::truncate::truncate_text;
+
+pub(crate) const SIMULATED_EXEC_PROMPT: &str =
+    include_str!("../../templates/simulated_exec/prompt.md");
+pub(crate) const SIMULATED_NOTICE: &str =
+    "[simulated: this command was not executed; the output below is a predicted outcome]";
+const COMMAND_MAX_BYTES: usize = 16 * 1024;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Prediction {
+    exit_code: i32,
+    #[serde(default)]
+    output: String,
+}
+
+/// Predict the outcome of `command` instead of running it.
+pub(crate) async fn simulate_command(
+    session: &Session,
+    turn: &TurnContext,
+    command: &[String],
+    cwd: &Path,
+) -> ExecToolCallOutput {
+    let text = format!(
+        "<command>\n{}\n</command>\n\n<cwd>\n{}\n</cwd>\n\n<os>\n{}\n</os>\n\n<shell>\n{}\n</shell>\n",
+        truncate_text(
+            &shlex_join(command),
+            TruncationPolicy::Bytes(COMMAND_MAX_BYTES)
+        ),
+        cwd.display(),
+        std::env::consts::OS,
+        session.user_shell().name(),
+    );
+    let prompt = Prompt {
+        input: vec![ResponseItem::Message {
+            id: None,
+            role: "user".to_string(),
+            content: vec![ContentItem::InputText { text }],
+        }],
+        tools: Vec::new(),
+        parallel_tool_calls: false,
+        base_instructions_override: Some(SIMULATED_EXEC_PROMPT.to_string()),
+        locale_instructions: None,
+        model_instructions: None,
+        composed_instructions: None,
+        output_schema: None,
+    };
+    let prediction = match run_simulation(session, turn, &prompt).await {
+        Ok(reply) => parse_prediction(&reply),
+        Err(err) => {
+            warn!("command simulation failed: {err:#}");
+            Prediction {
+                exit_code: 1,
+                output: format!("The outcome could not be predicted: {err}"),
+            }
+        }
+    };
+    simulated_output(prediction)
+}
+
+fn simulated_output(prediction: Prediction) -> ExecToolCallOutput {
+    let text = if prediction.output.is_empty() {
+        SIMULATED_NOTICE.to_string()
+    } else {
+        format!("{SIMULATED_NOTICE}\n{}", prediction.output)
+    };
+    ExecToolCallOutput {
+        exit_code: prediction.exit_code,
+        stdout: StreamOutput::new(text.clone()),
+        stderr: StreamOutput::new(String::new()),
+        aggregated_output: StreamOutput::new(text),
+        duration: Duration::ZERO,
+        timed_out: false,
+    }
+}
+
+async fn run_simulation(
+    session: &Session,
+    turn: &TurnContext,
+    prompt: &Prompt,
+) -> CodexResult<String> {
+    let mut stream = turn.client.clone().stream(prompt).await?;
+    let mut reply = String::new();
+    while let Some(event) = stream.next().await {
+        match event? {
+            ResponseEvent::OutputItemDone(ResponseItem::Message { content, .. }) => {
+                if let Some(text) = content_items_to_text(&content) {
+                    reply.push_str(&text);
+                }
+            }
+            ResponseEvent::RateLimits(snapshot) => {
+                session.update_rate_limits(turn, snapshot).await;
+            }
+            ResponseEvent::Completed { token_usage, .. } => {
+                session
+                    .update_side_token_usage(turn, token_usage.as_ref())
+                    .await;
+                return Ok(reply);
+            }
+            _ => {}
+        }
+    }
+    Err(CodexErr::Stream(
+        "stream closed before response.completed".into(),
+        None,
+    ))
+}
+
+/// A reply that is not the requested JSON object is used as the output of a
+/// successful run.
+fn parse_prediction(reply: &str) -> Prediction {
+    serde_json::from_str(extract_json_object(reply)).unwrap_or_else(|_| Prediction {
+        exit_code: 0,
+        output: reply.trim().to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn predictions_are_read_from_json_or_plain_replies() {
+        assert_eq!(
+            parse_prediction(
+                "```json\n{\"exit_code\": 2, \"output\": \"error: relation \\\"users\\\" already exists\"}\n```"
+            ),
+            Prediction {
+                exit_code: 2,
+                output: "error: relation \"users\" already exists".to_string(),
+            }
+        );
+        assert_eq!(
+            parse_prediction("Applied 3 migrations.\n"),
+            Prediction {
+                exit_code: 0,
+                output: "Applied 3 migrations.".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn simulated_output_is_labeled() {
+        let output = simulated_output(Prediction {
+            exit_code: 1,
+            output: "deploy failed".to_string(),
+        });
+        assert_eq!(output.exit_code, 1);
+        assert_eq!(
+            output.aggregated_output.text,
+            format!("{SIMULATED_NOTICE}\ndeploy failed")
+        );
+    }
+}
@@ -6,9 +6,13 @@ use crate::openai_models::model_family::ModelFamily;
 use crate::tools::handlers::PLAN_TOOL;
 use crate::tools::handlers::apply_patch::create_apply_patch_freeform_tool;
 use crate::tools::handlers::apply_patch::create_apply_patch_json_tool;
+use crate::tools::registry::ConfiguredToolSpec;
 use crate::tools::registry::ToolRegistryBuilder;
+use crate::truncate::approx_token_count;
 use codex_protocol::openai_models::ApplyPatchToolType;
 use codex_protocol::openai_models::ConfigShellToolType;
+use codex_protocol::protocol::AskForApproval;
+use codex_protocol::protocol::SandboxPolicy;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_json::Value as JsonValue;
@@ -22,7 +26,27 @@ pub(crate) struct ToolsConfig {
     pub apply_patch_tool_type: Option<ApplyPatchToolType>,
     pub web_search_request: bool,
     pub include_view_image_tool: bool,
+    pub include_summarize_file_tool: bool,
+    pub include_repo_map_tool: bool,
+    pub include_semantic_search_tool: bool,
+    pub include_ask_user_tool: bool,
+    pub include_register_artifact_tool: bool,
+    pub include_issue_tracker_tool: bool,
+    pub include_delegate_tool: bool,
+    pub include_read_output_range_tool: bool,
+    pub require_command_risk: bool,
+    /// Ask the model to fix arguments that do not match a tool's schema
+    /// before running the call.
+    pub repair_tool_arguments: bool,
+    /// Answer shell calls with a predicted outcome instead of running them.
+    pub simulate_commands: bool,
     pub experimental_supported_tools: Vec<String>,
+    /// Approximate token budget for tool and parameter descriptions; tools
+    /// past it keep only the first sentence of their description.
+    pub description_token_budget: Option<usize>,
+    /// Names of the only tools offered to the model, e.g. for a workflow
+    /// step. `None` offers every tool.
+    pub allowed_tools: Option<Vec<String>>,
 }
 
 pub(crate) struct ToolsConfigParams<'a> {
@@ -39,9 +63,26 @@ impl ToolsConfig {
         let include_apply_patch_tool = features.enabled(Feature::ApplyPatchFreeform);
         let include_web_search_request = features.enabled(Feature::WebSearchRequest);
         let include_view_image_tool = features.enabled(Feature::ViewImageTool);
+        let include_summarize_file_tool = features.enabled(Feature::SummarizeFileTool);
+        let include_repo_map_tool = features.enabled(Feature::RepoMap);
+        let include_semantic_search_tool = features.enabled(Feature::SemanticSearch);
+        let include_ask_user_tool = features.enabled(Feature::AskUserTool);
+        let include_register_artifact_tool = features.enabled(Feature::Artifacts);
+        let include_issue_tracker_tool = features.enabled(Feature::IssueTracker);
+        let include_delegate_tool = features.enabled(Feature::DelegateTool);
+        let include_read_output_range_tool = features.enabled(Feature::OutputSpill);
+        let require_command_risk = features.enabled(Feature::CommandRiskDeclarations);
+        let repair_tool_arguments = features.enabled(Feature::ToolCallRepair);
+        let simulate_commands = features.enabled(Feature::SimulatedExec);
 
         let shell_type = if !features.enabled(Feature::ShellTool) {
             ConfigShellToolType::Disabled
+        } else if simulate_commands {
+            // Interactive sessions cannot be simulated; offer the one-shot tool.
+            match model_family.shell_type {
+                ConfigShellToolType::UnifiedExec => ConfigShellToolType::ShellCommand,
+                shell_type => shell_type,
+            }
         } else if features.enabled(Feature::UnifiedExec) {
             // If ConPTY not supported (for old Windows versions), fallback on ShellCommand.
             if codex_utils_pty::conpty_supported() {
@@ -70,7 +111,38 @@ impl ToolsConfig {
             apply_patch_tool_type,
             web_search_request: include_web_search_request,
             include_view_image_tool,
+            include_summarize_file_tool,
+            include_repo_map_tool,
+            include_semantic_search_tool,
+            include_ask_user_tool,
+            include_register_artifact_tool,
+            include_issue_tracker_tool,
+            include_delegate_tool,
+            include_read_output_range_tool,
+            require_command_risk,
+            repair_tool_arguments,
+            simulate_commands,
             experimental_supported_tools: model_family.experimental_supported_tools.clone(),
+            description_token_budget: None,
+            allowed_tools: None,
+        }
+    }
+
+    /// Drop tools that cannot do anything under the turn's policies: web
+    /// search without network access, and apply_patch when the sandbox is
+    /// read-only and the user is never asked to approve an edit.
+    pub fn prune_for_policies(
+        &mut self,
+        approval_policy: AskForApproval,
+        sandbox_policy: &SandboxPolicy,
+    ) {
+        if !sandbox_policy.has_full_network_access() {
+            self.web_search_request = false;
+        }
+        if matches!(sandbox_policy, SandboxPolicy::ReadOnly)
+            && approval_policy == AskForApproval::Never
+        {
+            self.apply_patch_tool_type = None;
         }
     }
 }
@@ -86,6 +158,9 @@ pub(crate) enum JsonSchema {
     String {
         #[serde(skip_serializing_if = "Option::is_none")]
         description: Option<String>,
+        /// The only values allowed, when the string is one of a fixed set.
+        #[serde(rename = "enum", default, skip_serializing_if = "Option::is_none")]
+        enum_values: Option<Vec<String>>,
     },
     /// MCP schema allows "number" | "integer" for Number
     #[serde(alias = "integer")]
@@ -131,12 +206,13 @@ impl From<JsonSchema> for AdditionalProperties {
     }
 }
 
-fn create_exec_command_tool() -> ToolSpec {
+fn create_exec_command_tool(require_command_risk: bool) -> ToolSpec {
     let mut properties = BTreeMap::new();
     properties.insert(
         "cmd".to_string(),
         JsonSchema::String {
             description: Some("Shell command to execute.".to_string()),
+            enum_values: None,
         },
     );
     properties.insert(
@@ -146,12 +222,14 @@ fn create_exec_command_tool() -> ToolSpec {
                 "Optional working directory to run the command in; defaults to the turn cwd."
                     .to_string(),
             ),
+            enum_values: None,
         },
     );
     properties.insert(
         "shell".to_string(),
         JsonSchema::String {
             description: Some("Shell binary to launch. Defaults to /bin/bash.".to_string()),
+            enum_values: None,
         },
     );
     properties.insert(
@@ -184,8 +262,7 @@ fn create_exec_command_tool() -> ToolSpec {
             description: Some(
                 "Sandbox permissions for the command. Set to \"require_escalated\" to request running without sandbox restrictions; defaults to \"use_default\"."
                     .to_string(),
-            ),
-        },
+            ), enum_values: None },
     );
     properties.insert(
         "justification".to_string(),
@@ -193,10 +270,14 @@ fn create_exec_command_tool() -> ToolSpec {
             description: Some(
                 "Only set if sandbox_permissions is \"require_escalated\". 1-sentence explanation of why we want to run this command."
                     .to_string(),
-            ),
-        },
+            ), enum_values: None },
     );
 
+    let mut required = vec!["cmd".to_string()];
+    if require_command_risk {
+        insert_command_risk_properties(&mut properties, &mut required);
+    }
+
     ToolSpec::Function(ResponsesApiTool {
         name: "exec_command".to_string(),
         description:
@@ -205,7 +286,7 @@ fn create_exec_command_tool() -> ToolSpec {
         strict: false,
         parameters: JsonSchema::Object {
             properties,
-            required: Some(vec!["cmd".to_string()]),
+            required: Some(required),
             additional_properties: Some(false.into()),
         },
     })
@@ -223,6 +304,7 @@ fn create_write_stdin_tool() -> ToolSpec {
         "chars".to_string(),
         JsonSchema::String {
             description: Some("Bytes to write to stdin (may be empty to poll).".to_string()),
+            enum_values: None,
         },
     );
     properties.insert(
@@ -256,12 +338,44 @@ fn create_write_stdin_tool() -> ToolSpec {
     })
 }
 
-fn create_shell_tool() -> ToolSpec {
+/// Add the required `intent` and `risk` parameters used by
+/// `command_risk_declarations`.
+fn insert_command_risk_properties(
+    properties: &mut BTreeMap<String, JsonSchema>,
+    required: &mut Vec<String>,
+) {
+    properties.insert(
+        "intent".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "1-sentence description of what this command is meant to accomplish.".to_string(),
+            ),
+            enum_values: None,
+        },
+    );
+    properties.insert(
+        "risk".to_string(),
+        JsonSchema::String {
+            description: Some("How much the command changes: \"read_only\" if it only inspects files or state, \"write\" if it changes workspace files in ways that are easy to undo, \"destructive\" if it deletes data, rewrites history, or changes anything outside the workspace.".to_string()),
+            enum_values: Some(vec![
+                "read_only".to_string(),
+                "write".to_string(),
+                "destructive".to_string(),
+            ]),
+        },
+    );
+    required.extend(["intent".to_string(), "risk".to_string()]);
+}
+
+fn create_shell_tool(require_command_risk: bool) -> ToolSpec {
     let mut properties = BTreeMap::new();
     properties.insert(
         "command".to_string(),
         JsonSchema::Array {
-            items: Box::new(JsonSchema::String { description: None }),
+            items: Box::new(JsonSchema::String {
+                description: None,
+                enum_values: None,
+            }),
             description: Some("The command to execute".to_string()),
         },
     );
@@ -269,6 +383,21 @@ fn create_shell_tool() -> ToolSpec {
         "workdir".to_string(),
         JsonSchema::String {
             description: Some("The working directory to execute the command in".to_string()),
+            enum_values: None,
+        },
+    );
+    properties.insert(
+        "env".to_string(),
+        JsonSchema::Object {
+            properties: BTreeMap::new(),
+            required: None,
+            additional_properties: Some(
+                JsonSchema::String {
+                    description: None,
+                    enum_values: None,
+                }
+                .into(),
+            ),
         },
     );
     properties.insert(
@@ -281,14 +410,12 @@ fn create_shell_tool() -> ToolSpec {
     properties.insert(
         "sandbox_permissions".to_string(),
         JsonSchema::String {
-            description: Some("Sandbox permissions for the command. Set to \"require_escalated\" to request running without sandbox restrictions; defaults to \"use_default\".".to_string()),
-        },
+            description: Some("Sandbox permissions for the command. Set to \"require_escalated\" to request running without sandbox restrictions; defaults to \"use_default\".".to_string()), enum_values: None },
     );
     properties.insert(
         "justification".to_string(),
         JsonSchema::String {
-            description: Some("Only set if sandbox_permissions is \"require_escalated\". 1-sentence explanation of why we want to run this command.".to_string()),
-        },
+            description: Some("Only set if sandbox_permissions is \"require_escalated\". 1-sentence explanation of why we want to run this command.".to_string()), enum_values: None },
     );
 
     let description  = if cfg!(windows) {
@@ -308,19 +435,24 @@ Examples of valid command strings:
 - Always set the `workdir` param when using the shell function. Do not use `cd` unless absolutely necessary."#
     }.to_string();
 
+    let mut required = vec!["command".to_string()];
+    if require_command_risk {
+        insert_command_risk_properties(&mut properties, &mut required);
+    }
+
     ToolSpec::Function(ResponsesApiTool {
         name: "shell".to_string(),
         description,
         strict: false,
         parameters: JsonSchema::Object {
             properties,
-            required: Some(vec!["command".to_string()]),
+            required: Some(required),
             additional_properties: Some(false.into()),
         },
     })
 }
 
-fn create_shell_command_tool() -> ToolSpec {
+fn create_shell_command_tool(require_command_risk: bool) -> ToolSpec {
     let mut properties = BTreeMap::new();
     properties.insert(
         "command".to_string(),
@@ -328,12 +460,28 @@ fn create_shell_command_tool() -> ToolSpec {
             description: Some(
                 "The shell script to execute in the user's default shell".to_string(),
             ),
+            enum_values: None,
         },
     );
     properties.insert(
         "workdir".to_string(),
         JsonSchema::String {
             description: Some("The working directory to execute the command in".to_string()),
+            enum_values: None,
+        },
+    );
+    properties.insert(
+        "env".to_string(),
+        JsonSchema::Object {
+            properties: BTreeMap::new(),
+            required: None,
+            additional_properties: Some(
+                JsonSchema::String {
+                    description: None,
+                    enum_values: None,
+                }
+                .into(),
+            ),
         },
     );
     properties.insert(
@@ -354,14 +502,12 @@ fn create_shell_command_tool() -> ToolSpec {
     properties.insert(
         "sandbox_permissions".to_string(),
         JsonSchema::String {
-            description: Some("Sandbox permissions for the command. Set to \"require_escalated\" to request running without sandbox restrictions; defaults to \"use_default\".".to_string()),
-        },
+            description: Some("Sandbox permissions for the command. Set to \"require_escalated\" to request running without sandbox restrictions; defaults to \"use_default\".".to_string()), enum_values: None },
     );
     properties.insert(
         "justification".to_string(),
         JsonSchema::String {
-            description: Some("Only set if sandbox_permissions is \"require_escalated\". 1-sentence explanation of why we want to run this command.".to_string()),
-        },
+            description: Some("Only set if sandbox_permissions is \"require_escalated\". 1-sentence explanation of why we want to run this command.".to_string()), enum_values: None },
     );
 
     let description = if cfg!(windows) {
@@ -380,13 +526,18 @@ Examples of valid command strings:
 - Always set the `workdir` param when using the shell_command function. Do not use `cd` unless absolutely necessary."#
     }.to_string();
 
+    let mut required = vec!["command".to_string()];
+    if require_command_risk {
+        insert_command_risk_properties(&mut properties, &mut required);
+    }
+
     ToolSpec::Function(ResponsesApiTool {
         name: "shell_command".to_string(),
         description,
         strict: false,
         parameters: JsonSchema::Object {
             properties,
-            required: Some(vec!["command".to_string()]),
+            required: Some(required),
             additional_properties: Some(false.into()),
         },
     })
@@ -399,6 +550,7 @@ fn create_view_image_tool() -> ToolSpec {
         "path".to_string(),
         JsonSchema::String {
             description: Some("Local filesystem path to an image file".to_string()),
+            enum_values: None,
         },
     );
 
@@ -440,6 +592,7 @@ fn create_test_sync_tool() -> ToolSpec {
             description: Some(
                 "Identifier shared by concurrent calls that should rendezvous".to_string(),
             ),
+            enum_values: None,
         },
     );
     barrier_properties.insert(
@@ -484,6 +637,7 @@ fn create_grep_files_tool() -> ToolSpec {
         "pattern".to_string(),
         JsonSchema::String {
             description: Some("Regular expression pattern to search for.".to_string()),
+            enum_values: None,
         },
     );
     properties.insert(
@@ -494,6 +648,7 @@ fn create_grep_files_tool() -> ToolSpec {
                  \"*.{ts,tsx}\")."
                     .to_string(),
             ),
+            enum_values: None,
         },
     );
     properties.insert(
@@ -503,6 +658,7 @@ fn create_grep_files_tool() -> ToolSpec {
                 "Directory or file path to search. Defaults to the session's working directory."
                     .to_string(),
             ),
+            enum_values: None,
         },
     );
     properties.insert(
@@ -534,6 +690,7 @@ fn create_read_file_tool() -> ToolSpec {
         "file_path".to_string(),
         JsonSchema::String {
             description: Some("Absolute path to the file".to_string()),
+            enum_values: None,
         },
     );
     properties.insert(
@@ -558,6 +715,7 @@ fn create_read_file_tool() -> ToolSpec {
                  to expand around an anchor line."
                     .to_string(),
             ),
+            enum_values: None,
         },
     );
 
@@ -632,6 +790,7 @@ fn create_list_dir_tool() -> ToolSpec {
         "dir_path".to_string(),
         JsonSchema::String {
             description: Some("Absolute path to the directory to list.".to_string()),
+            enum_values: None,
         },
     );
     properties.insert(
@@ -665,7 +824,311 @@ fn create_list_dir_tool() -> ToolSpec {
         strict: false,
         parameters: JsonSchema::Object {
             properties,
-            required: Some(vec!["dir_path".to_string()]),
+            required: Some(vec!["dir_path".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_ask_user_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "question".to_string(),
+        JsonSchema::String {
+            description: Some("The question to show the user.".to_string()),
+            enum_values: None,
+        },
+    );
+    properties.insert(
+        "options".to_string(),
+        JsonSchema::Array {
+            items: Box::new(JsonSchema::String { description: None, enum_values: None }),
+            description: Some(
+                "Suggested answers when the answer is one of a few choices; the user may still answer in their own words."
+                    .to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "ask_user".to_string(),
+        description: "Asks the user a clarifying question and waits for the answer. Use it only when you cannot proceed sensibly without the user's input, not to confirm routine steps. Ask one focused question per call. The user may decline to answer."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["question".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_register_artifact_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "path".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Path to the file, absolute or relative to the working directory.".to_string(),
+            ),
+            enum_values: None,
+        },
+    );
+    properties.insert(
+        "title".to_string(),
+        JsonSchema::String {
+            description: Some("Short name for the artifact, shown to the user.".to_string()),
+            enum_values: None,
+        },
+    );
+    properties.insert(
+        "description".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Optional one-line description of what the file contains.".to_string(),
+            ),
+            enum_values: None,
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "register_artifact".to_string(),
+        description: "Registers a file you produced as a deliverable (a report, generated image, built binary, and so on) so the user can find, open, and save it without searching the transcript. Register final outputs only, after the file is written, not intermediate files."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["path".to_string(), "title".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_delegate_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "task".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Self-contained description of the work: the goal, relevant files, and what to report back. The sub-agent does not see this conversation."
+                    .to_string(),
+            ), enum_values: None },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "delegate".to_string(),
+        description: "Hands a self-contained task to a sub-agent that works in the same workspace with the same tools, and waits for it to finish. Returns a JSON handoff with `status`, `summary`, `artifacts`, `diffs` (files it changed), `unified_diff`, and `open_questions`. Delegated tasks run one at a time."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["task".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_issue_tracker_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "action".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "One of `get_issue`, `get_pull_request`, `ci_status`, `ci_logs` or `post_comment`. `ci_logs` returns the failing steps' log excerpts of the latest GitHub Actions run on a branch, if it failed."
+                    .to_string(),
+            ), enum_values: None },
+    );
+    properties.insert(
+        "number".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "Issue or pull request number. Required except for `ci_status`, which checks the local HEAD commit without it, and `ci_logs`."
+                    .to_string(),
+            ),
+        },
+    );
+    properties.insert(
+        "repo".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Repository as `owner/name` (or the project path on GitLab). Defaults to the `origin` remote."
+                    .to_string(),
+            ), enum_values: None },
+    );
+    properties.insert(
+        "branch".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "For `ci_logs`: branch whose runs to look at. Defaults to the current branch."
+                    .to_string(),
+            ),
+            enum_values: None,
+        },
+    );
+    properties.insert(
+        "body".to_string(),
+        JsonSchema::String {
+            description: Some("Markdown text of the comment for `post_comment`.".to_string()),
+            enum_values: None,
+        },
+    );
+    properties.insert(
+        "pull_request".to_string(),
+        JsonSchema::Boolean {
+            description: Some(
+                "For `post_comment`: whether `number` is a pull (merge) request. Defaults to false."
+                    .to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "issue_tracker".to_string(),
+        description: "Reads an issue or pull request (description, labels, recent comments), the CI checks of a pull request or commit, or the logs of failing CI jobs, or posts a comment, on the GitHub or GitLab repository of the working directory. Posting asks the user for approval first; post only when the user asked you to."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["action".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_read_output_range_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "handle".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Handle of the saved output, as given in the truncated command output.".to_string(),
+            ),
+            enum_values: None,
+        },
+    );
+    properties.insert(
+        "start_line".to_string(),
+        JsonSchema::Number {
+            description: Some("1-indexed line to start from. Defaults to 1.".to_string()),
+        },
+    );
+    properties.insert(
+        "limit".to_string(),
+        JsonSchema::Number {
+            description: Some("Maximum number of lines to return. Defaults to 200.".to_string()),
+        },
+    );
+    properties.insert(
+        "pattern".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Only return lines containing this text, to locate errors or test failures before reading around them."
+                    .to_string(),
+            ), enum_values: None },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "read_output_range".to_string(),
+        description: "Reads lines from the full output of an earlier command whose output was truncated. Use it to inspect the part of a long log that was cut, such as a specific test failure."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["handle".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_summarize_file_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "file_path".to_string(),
+        JsonSchema::String {
+            description: Some("Absolute path to the file to summarize.".to_string()),
+            enum_values: None,
+        },
+    );
+    properties.insert(
+        "chunk_lines".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "Whole number of lines summarized per chunk; defaults to 400. Larger files use larger chunks automatically."
+                    .to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "summarize_file".to_string(),
+        description: "Summarizes a large file chunk by chunk and returns a structural overview plus an index of top-level symbols with line numbers. Use it to orient yourself in files that are too big to read in full, then read_file the relevant ranges."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["file_path".to_string()]),
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_repo_map_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "path".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Directory to map, relative to the working directory; defaults to the working directory."
+                    .to_string(),
+            ), enum_values: None },
+    );
+    properties.insert(
+        "max_tokens".to_string(),
+        JsonSchema::Number {
+            description: Some(
+                "Approximate token budget for the map; defaults to 4096.".to_string(),
+            ),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "repo_map".to_string(),
+        description: "Returns a directory tree where each source file lists its top-level declarations (functions, types, classes). Respects .gitignore and context ignore rules. Use it to orient yourself in an unfamiliar part of the codebase, or to refresh the map from the start of the session after files have changed."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: None,
+            additional_properties: Some(false.into()),
+        },
+    })
+}
+
+fn create_semantic_search_tool() -> ToolSpec {
+    let mut properties = BTreeMap::new();
+    properties.insert(
+        "query".to_string(),
+        JsonSchema::String {
+            description: Some(
+                "Natural-language description of the code to find, e.g. \"where retries are scheduled\"."
+                    .to_string(),
+            ), enum_values: None },
+    );
+    properties.insert(
+        "limit".to_string(),
+        JsonSchema::Number {
+            description: Some("Maximum number of matches to return (defaults to 8).".to_string()),
+        },
+    );
+
+    ToolSpec::Function(ResponsesApiTool {
+        name: "semantic_search".to_string(),
+        description: "Searches the repository's local embedding index for code chunks that are semantically related to the query. Returns file paths with line ranges and a short snippet. Prefer grep_files for exact identifiers."
+            .to_string(),
+        strict: false,
+        parameters: JsonSchema::Object {
+            properties,
+            required: Some(vec!["query".to_string()]),
             additional_properties: Some(false.into()),
         },
     })
@@ -679,8 +1142,7 @@ fn create_list_mcp_resources_tool() -> ToolSpec {
             description: Some(
                 "Optional MCP server name. When omitted, lists resources from every configured server."
                     .to_string(),
-            ),
-        },
+            ), enum_values: None },
     );
     properties.insert(
         "cursor".to_string(),
@@ -689,6 +1151,7 @@ fn create_list_mcp_resources_tool() -> ToolSpec {
                 "Opaque cursor returned by a previous list_mcp_resources call for the same server."
                     .to_string(),
             ),
+            enum_values: None,
         },
     );
 
@@ -712,8 +1175,7 @@ fn create_list_mcp_resource_templates_tool() -> ToolSpec {
             description: Some(
                 "Optional MCP server name. When omitted, lists resource templates from all configured servers."
                     .to_string(),
-            ),
-        },
+            ), enum_values: None },
     );
     properties.insert(
         "cursor".to_string(),
@@ -721,8 +1183,7 @@ fn create_list_mcp_resource_templates_tool() -> ToolSpec {
             description: Some(
                 "Opaque cursor returned by a previous list_mcp_resource_templates call for the same server."
                     .to_string(),
-            ),
-        },
+            ), enum_values: None },
     );
 
     ToolSpec::Function(ResponsesApiTool {
@@ -745,8 +1206,7 @@ fn create_read_mcp_resource_tool() -> ToolSpec {
             description: Some(
                 "MCP server name exactly as configured. Must match the 'server' field returned by list_mcp_resources."
                     .to_string(),
-            ),
-        },
+            ), enum_values: None },
     );
     properties.insert(
         "uri".to_string(),
@@ -755,6 +1215,7 @@ fn create_read_mcp_resource_tool() -> ToolSpec {
                 "Resource URI to read. Must be one of the URIs returned by list_mcp_resources."
                     .to_string(),
             ),
+            enum_values: None,
         },
     );
 
@@ -981,14 +1442,22 @@ pub(crate) fn build_specs(
     mcp_tools: Option<HashMap<String, mcp_types::Tool>>,
 ) -> ToolRegistryBuilder {
     use crate::tools::handlers::ApplyPatchHandler;
+    use crate::tools::handlers::AskUserHandler;
+    use crate::tools::handlers::DelegateHandler;
     use crate::tools::handlers::GrepFilesHandler;
+    use crate::tools::handlers::IssueTrackerHandler;
     use crate::tools::handlers::ListDirHandler;
     use crate::tools::handlers::McpHandler;
     use crate::tools::handlers::McpResourceHandler;
     use crate::tools::handlers::PlanHandler;
     use crate::tools::handlers::ReadFileHandler;
+    use crate::tools::handlers::ReadOutputRangeHandler;
+    use crate::tools::handlers::RegisterArtifactHandler;
+    use crate::tools::handlers::RepoMapHandler;
+    use crate::tools::handlers::SemanticSearchHandler;
     use crate::tools::handlers::ShellCommandHandler;
     use crate::tools::handlers::ShellHandler;
+    use crate::tools::handlers::SummarizeFileHandler;
     use crate::tools::handlers::TestSyncHandler;
     use crate::tools::handlers::UnifiedExecHandler;
     use crate::tools::handlers::ViewImageHandler;
@@ -1007,13 +1476,13 @@ pub(crate) fn build_specs(
 
     match &config.shell_type {
         ConfigShellToolType::Default => {
-            builder.push_spec(create_shell_tool());
+            builder.push_spec(create_shell_tool(config.require_command_risk));
         }
         ConfigShellToolType::Local => {
             builder.push_spec(ToolSpec::LocalShell {});
         }
         ConfigShellToolType::UnifiedExec => {
-            builder.push_spec(create_exec_command_tool());
+            builder.push_spec(create_exec_command_tool(config.require_command_risk));
             builder.push_spec(create_write_stdin_tool());
             builder.register_handler("exec_command", unified_exec_handler.clone());
             builder.register_handler("write_stdin", unified_exec_handler);
@@ -1022,7 +1491,7 @@ pub(crate) fn build_specs(
             // Do nothing.
         }
         ConfigShellToolType::ShellCommand => {
-            builder.push_spec(create_shell_command_tool());
+            builder.push_spec(create_shell_command_tool(config.require_command_risk));
         }
     }
 
@@ -1093,6 +1562,51 @@ pub(crate) fn build_specs(
         builder.register_handler("test_sync_tool", test_sync_handler);
     }
 
+    if config.include_summarize_file_tool {
+        let summarize_file_handler = Arc::new(SummarizeFileHandler);
+        builder.push_spec_with_parallel_support(create_summarize_file_tool(), true);
+        builder.register_handler("summarize_file", summarize_file_handler);
+    }
+
+    if config.include_repo_map_tool {
+        let repo_map_handler = Arc::new(RepoMapHandler);
+        builder.push_spec_with_parallel_support(create_repo_map_tool(), true);
+        builder.register_handler("repo_map", repo_map_handler);
+    }
+
+    if config.include_semantic_search_tool {
+        let semantic_search_handler = Arc::new(SemanticSearchHandler);
+        builder.push_spec_with_parallel_support(create_semantic_search_tool(), true);
+        builder.register_handler("semantic_search", semantic_search_handler);
+    }
+
+    if config.include_ask_user_tool {
+        builder.push_spec(create_ask_user_tool());
+        builder.register_handler("ask_user", Arc::new(AskUserHandler));
+    }
+
+    if config.include_register_artifact_tool {
+        builder.push_spec(create_register_artifact_tool());
+        builder.register_handler("register_artifact", Arc::new(RegisterArtifactHandler));
+    }
+
+    if config.include_issue_tracker_tool {
+        builder.push_spec(create_issue_tracker_tool());
+        builder.register_handler("issue_tracker", Arc::new(IssueTrackerHandler));
+    }
+
+    if config.include_delegate_tool {
+        // Sub-agents share the workspace, so concurrent ones would edit the
+        // same files and mix up each other's `diffs`.
+        builder.push_spec(create_delegate_tool());
+        builder.register_handler("delegate", Arc::new(DelegateHandler));
+    }
+
+    if config.include_read_output_range_tool {
+        builder.push_spec_with_parallel_support(create_read_output_range_tool(), true);
+        builder.register_handler("read_output_range", Arc::new(ReadOutputRangeHandler));
+    }
+
     if config.web_search_request {
         builder.push_spec(ToolSpec::WebSearch {});
     }
@@ -1122,6 +1636,71 @@ pub(crate) fn build_specs(
     builder
 }
 
+/// Keep tool descriptions within `budget_tokens`, spent in tool order. Each
+/// tool that no longer fits is cut down to the first sentence of its
+/// description, without parameter descriptions.
+pub(crate) fn fit_tool_descriptions(specs: &mut [ConfiguredToolSpec], budget_tokens: usize) {
+    let mut remaining = budget_tokens;
+    for configured in specs.iter_mut() {
+        let (description, parameters) = match &mut configured.spec {
+            ToolSpec::Function(tool) => (&mut tool.description, Some(&mut tool.parameters)),
+            ToolSpec::Freeform(tool) => (&mut tool.description, None),
+            ToolSpec::LocalShell {} | ToolSpec::WebSearch {} => continue,
+        };
+        let full_cost = approx_token_count(description)
+            + parameters.as_deref().map_or(0, schema_description_tokens);
+        if full_cost <= remaining {
+            remaining -= full_cost;
+            continue;
+        }
+        *description = first_sentence(description).to_string();
+        if let Some(parameters) = parameters {
+            strip_schema_descriptions(parameters);
+        }
+        remaining = remaining.saturating_sub(approx_token_count(description));
+    }
+}
+
+fn first_sentence(text: &str) -> &str {
+    let text = text.trim_start();
+    let line = text.lines().next().unwrap_or_default();
+    match line.find(". ") {
+        Some(end) => &line[..=end],
+        None => line,
+    }
+}
+
+fn schema_description_tokens(schema: &JsonSchema) -> usize {
+    match schema {
+        JsonSchema::Boolean { description }
+        | JsonSchema::String { description, .. }
+        | JsonSchema::Number { description } => {
+            description.as_deref().map_or(0, approx_token_count)
+        }
+        JsonSchema::Array { items, description } => {
+            description.as_deref().map_or(0, approx_token_count) + schema_description_tokens(items)
+        }
+        JsonSchema::Object { properties, .. } => {
+            properties.values().map(schema_description_tokens).sum()
+        }
+    }
+}
+
+fn strip_schema_descriptions(schema: &mut JsonSchema) {
+    match schema {
+        JsonSchema::Boolean { description }
+        | JsonSchema::String { description, .. }
+        | JsonSchema::Number { description } => *description = None,
+        JsonSchema::Array { items, description } => {
+            *description = None;
+            strip_schema_descriptions(items);
+        }
+        JsonSchema::Object { properties, .. } => {
+            properties.values_mut().for_each(strip_schema_descriptions);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::client_common::tools::FreeformTool;
@@ -1187,7 +1766,7 @@ mod tests {
     fn strip_descriptions_schema(schema: &mut JsonSchema) {
         match schema {
             JsonSchema::Boolean { description }
-            | JsonSchema::String { description }
+            | JsonSchema::String { description, .. }
             | JsonSchema::Number { description } => {
                 *description = None;
             }
@@ -1252,7 +1831,7 @@ mod tests {
         // Build expected from the same helpers used by the builder.
         let mut expected: BTreeMap<String, ToolSpec> = BTreeMap::new();
         for spec in [
-            create_exec_command_tool(),
+            create_exec_command_tool(false),
             create_write_stdin_tool(),
             create_list_mcp_resources_tool(),
             create_list_mcp_resource_templates_tool(),
@@ -1493,6 +2072,23 @@ mod tests {
         assert_contains_tool_names(&tools, &subset);
     }
 
+    #[test]
+    fn test_allowed_tools_limit_router_specs() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let features = Features::with_defaults();
+        let mut tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        tools_config.allowed_tools = Some(vec!["update_plan".to_string()]);
+
+        let specs = crate::tools::router::ToolRouter::from_config(&tools_config, None).specs();
+        let names: Vec<&str> = specs.iter().map(tool_name).collect();
+
+        assert_eq!(names, vec!["update_plan"]);
+    }
+
     #[test]
     #[ignore]
     fn test_parallel_support_flags() {
@@ -1545,6 +2141,312 @@ mod tests {
         assert!(tools.iter().any(|tool| tool_name(&tool.spec) == "list_dir"));
     }
 
+    #[test]
+    fn summarize_file_tool_requires_feature() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| tool_name(&tool.spec) == "summarize_file")
+        );
+
+        features.enable(Feature::SummarizeFileTool);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(find_tool(&tools, "summarize_file").supports_parallel_tool_calls);
+    }
+
+    #[test]
+    fn ask_user_tool_requires_feature() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(!tools.iter().any(|tool| tool_name(&tool.spec) == "ask_user"));
+
+        features.enable(Feature::AskUserTool);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        // Questions pause the turn, so they must not run alongside other tools.
+        assert!(!find_tool(&tools, "ask_user").supports_parallel_tool_calls);
+    }
+
+    #[test]
+    fn delegate_tool_requires_feature() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(!tools.iter().any(|tool| tool_name(&tool.spec) == "delegate"));
+
+        features.enable(Feature::DelegateTool);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        // Sub-agents share the workspace, so they must not run concurrently.
+        assert!(!find_tool(&tools, "delegate").supports_parallel_tool_calls);
+    }
+
+    #[test]
+    fn register_artifact_tool_requires_feature() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| tool_name(&tool.spec) == "register_artifact")
+        );
+
+        features.enable(Feature::Artifacts);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        find_tool(&tools, "register_artifact");
+    }
+
+    #[test]
+    fn read_output_range_tool_requires_feature() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| tool_name(&tool.spec) == "read_output_range")
+        );
+
+        features.enable(Feature::OutputSpill);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(find_tool(&tools, "read_output_range").supports_parallel_tool_calls);
+    }
+
+    #[test]
+    fn prune_for_policies_drops_tools_the_policies_rule_out() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        features.enable(Feature::WebSearchRequest);
+        let new_config = || {
+            ToolsConfig::new(&ToolsConfigParams {
+                model_family: &model_family,
+                features: &features,
+            })
+        };
+
+        let mut tools_config = new_config();
+        tools_config.prune_for_policies(AskForApproval::OnRequest, &SandboxPolicy::ReadOnly);
+        assert!(!tools_config.web_search_request);
+        assert!(tools_config.apply_patch_tool_type.is_some());
+
+        let mut tools_config = new_config();
+        tools_config.prune_for_policies(AskForApproval::Never, &SandboxPolicy::ReadOnly);
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| matches!(tool_name(&tool.spec), "web_search" | "apply_patch"))
+        );
+
+        let mut tools_config = new_config();
+        tools_config.prune_for_policies(AskForApproval::Never, &SandboxPolicy::DangerFullAccess);
+        assert!(tools_config.web_search_request);
+        assert!(tools_config.apply_patch_tool_type.is_some());
+    }
+
+    #[test]
+    fn fit_tool_descriptions_shortens_tools_past_the_budget() {
+        let tool = |name: &str| {
+            ConfiguredToolSpec::new(
+                ToolSpec::Function(ResponsesApiTool {
+                    name: name.to_string(),
+                    description: "Reads a file. Supports ranges and indentation modes.".to_string(),
+                    strict: false,
+                    parameters: JsonSchema::Object {
+                        properties: BTreeMap::from([(
+                            "path".to_string(),
+                            JsonSchema::String {
+                                description: Some("Absolute path to the file.".to_string()),
+                                enum_values: None,
+                            },
+                        )]),
+                        required: None,
+                        additional_properties: None,
+                    },
+                }),
+                false,
+            )
+        };
+        let mut specs = vec![tool("first"), tool("second")];
+
+        fit_tool_descriptions(&mut specs, 25);
+
+        assert_eq!(specs[0].spec, tool("first").spec);
+        assert_eq!(
+            specs[1].spec,
+            ToolSpec::Function(ResponsesApiTool {
+                name: "second".to_string(),
+                description: "Reads a file.".to_string(),
+                strict: false,
+                parameters: JsonSchema::Object {
+                    properties: BTreeMap::from([(
+                        "path".to_string(),
+                        JsonSchema::String {
+                            description: None,
+                            enum_values: None
+                        },
+                    )]),
+                    required: None,
+                    additional_properties: None,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn command_risk_declarations_are_required_when_enabled() {
+        let required = |tool: ToolSpec| match tool {
+            ToolSpec::Function(ResponsesApiTool {
+                parameters: JsonSchema::Object { required, .. },
+                ..
+            }) => required,
+            _ => panic!("expected function tool"),
+        };
+
+        assert_eq!(
+            required(create_shell_command_tool(false)),
+            Some(vec!["command".to_string()])
+        );
+        assert_eq!(
+            required(create_shell_command_tool(true)),
+            Some(vec![
+                "command".to_string(),
+                "intent".to_string(),
+                "risk".to_string()
+            ])
+        );
+        assert_eq!(
+            required(create_shell_tool(true)),
+            Some(vec![
+                "command".to_string(),
+                "intent".to_string(),
+                "risk".to_string()
+            ])
+        );
+        assert_eq!(
+            required(create_exec_command_tool(true)),
+            Some(vec![
+                "cmd".to_string(),
+                "intent".to_string(),
+                "risk".to_string()
+            ])
+        );
+
+        let ToolSpec::Function(ResponsesApiTool {
+            parameters: JsonSchema::Object { properties, .. },
+            ..
+        }) = create_exec_command_tool(true)
+        else {
+            panic!("expected function tool");
+        };
+        let Some(JsonSchema::String { enum_values, .. }) = properties.get("risk") else {
+            panic!("expected a string risk parameter");
+        };
+        assert_eq!(
+            enum_values,
+            &Some(vec![
+                "read_only".to_string(),
+                "write".to_string(),
+                "destructive".to_string()
+            ])
+        );
+    }
+
+    #[test]
+    fn repo_map_tool_requires_feature() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(!tools.iter().any(|tool| tool_name(&tool.spec) == "repo_map"));
+
+        features.enable(Feature::RepoMap);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(find_tool(&tools, "repo_map").supports_parallel_tool_calls);
+    }
+
+    #[test]
+    fn semantic_search_tool_requires_feature() {
+        let config = test_config();
+        let model_family = ModelsManager::construct_model_family_offline("gpt-5-codex", &config);
+        let mut features = Features::with_defaults();
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(
+            !tools
+                .iter()
+                .any(|tool| tool_name(&tool.spec) == "semantic_search")
+        );
+
+        features.enable(Feature::SemanticSearch);
+        let tools_config = ToolsConfig::new(&ToolsConfigParams {
+            model_family: &model_family,
+            features: &features,
+        });
+        let (tools, _) = build_specs(&tools_config, None).build();
+        assert!(find_tool(&tools, "semantic_search").supports_parallel_tool_calls);
+    }
+
     #[test]
     fn test_build_specs_mcp_tools_converted() {
         let config = test_config();
@@ -1604,7 +2506,10 @@ mod tests {
                     properties: BTreeMap::from([
                         (
                             "string_argument".to_string(),
-                            JsonSchema::String { description: None }
+                            JsonSchema::String {
+                                description: None,
+                                enum_values: None
+                            }
                         ),
                         (
                             "number_argument".to_string(),
@@ -1616,7 +2521,10 @@ mod tests {
                                 properties: BTreeMap::from([
                                     (
                                         "string_property".to_string(),
-                                        JsonSchema::String { description: None }
+                                        JsonSchema::String {
+                                            description: None,
+                                            enum_values: None
+                                        }
                                     ),
                                     (
                                         "number_property".to_string(),
@@ -1761,7 +2669,8 @@ mod tests {
                     properties: BTreeMap::from([(
                         "query".to_string(),
                         JsonSchema::String {
-                            description: Some("search query".to_string())
+                            description: Some("search query".to_string()),
+                            enum_values: None
                         }
                     )]),
                     required: None,
@@ -1870,7 +2779,10 @@ mod tests {
                     properties: BTreeMap::from([(
                         "tags".to_string(),
                         JsonSchema::Array {
-                            items: Box::new(JsonSchema::String { description: None }),
+                            items: Box::new(JsonSchema::String {
+                                description: None,
+                                enum_values: None
+                            }),
                             description: None
                         }
                     )]),
@@ -1925,7 +2837,10 @@ mod tests {
                 parameters: JsonSchema::Object {
                     properties: BTreeMap::from([(
                         "value".to_string(),
-                        JsonSchema::String { description: None }
+                        JsonSchema::String {
+                            description: None,
+                            enum_values: None
+                        }
                     )]),
                     required: None,
                     additional_properties: None,
@@ -1938,7 +2853,7 @@ mod tests {
 
     #[test]
     fn test_shell_tool() {
-        let tool = super::create_shell_tool();
+        let tool = super::create_shell_tool(false);
         let ToolSpec::Function(ResponsesApiTool {
             description, name, ..
         }) = &tool
@@ -1968,7 +2883,7 @@ Examples of valid command strings:
 
     #[test]
     fn test_shell_command_tool() {
-        let tool = super::create_shell_command_tool();
+        let tool = super::create_shell_command_tool(false);
         let ToolSpec::Function(ResponsesApiTool {
             description, name, ..
         }) = &tool
@@ -2063,7 +2978,10 @@ Examples of valid command strings:
                     properties: BTreeMap::from([
                         (
                             "string_argument".to_string(),
-                            JsonSchema::String { description: None }
+                            JsonSchema::String {
+                                description: None,
+                                enum_values: None
+                            }
                         ),
                         (
                             "number_argument".to_string(),
@@ -2075,7 +2993,10 @@ Examples of valid command strings:
                                 properties: BTreeMap::from([
                                     (
                                         "string_property".to_string(),
-                                        JsonSchema::String { description: None }
+                                        JsonSchema::String {
+                                            description: None,
+                                            enum_values: None
+                                        }
                                     ),
                                     (
                                         "number_property".to_string(),
@@ -2090,7 +3011,10 @@ Examples of valid command strings:
                                     JsonSchema::Object {
                                         properties: BTreeMap::from([(
                                             "addtl_prop".to_string(),
-                                            JsonSchema::String { description: None }
+                                            JsonSchema::String {
+                                                description: None,
+                                                enum_values: None
+                                            }
                                         ),]),
                                         required: Some(vec!["addtl_prop".to_string(),]),
                                         additional_properties: Some(false.into()),
@@ -2112,7 +3036,13 @@ Examples of valid command strings:
     #[test]
     fn chat_tools_include_top_level_name() {
         let mut properties = BTreeMap::new();
-        properties.insert("foo".to_string(), JsonSchema::String { description: None });
+        properties.insert(
+            "foo".to_string(),
+            JsonSchema::String {
+                description: None,
+                enum_values: None,
+            },
+        );
         let tools = vec![ToolSpec::Function(ResponsesApiTool {
             name: "demo".to_string(),
             description: "A demo tool".to_string(),
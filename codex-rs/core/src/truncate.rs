@@ -8,6 +8,8 @@ use codex_protocol::openai_models::TruncationMode;
 use codex_protocol::openai_models::TruncationPolicyConfig;
 
 const APPROX_BYTES_PER_TOKEN: usize = 4;
+/// Share of the budget [`truncate_text`] keeps for the end of the content.
+const DEFAULT_TAIL_PERCENT: usize = 50;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TruncationPolicy {
@@ -96,13 +98,25 @@ pub(crate) fn formatted_truncate_text(content: &str, policy: TruncationPolicy) -
 
 pub(crate) fn truncate_text(content: &str, policy: TruncationPolicy) -> String {
     match policy {
-        TruncationPolicy::Bytes(_) => truncate_with_byte_estimate(content, policy),
+        TruncationPolicy::Bytes(_) => {
+            truncate_with_byte_estimate(content, policy, DEFAULT_TAIL_PERCENT)
+        }
         TruncationPolicy::Tokens(_) => {
             let (truncated, _) = truncate_with_token_budget(content, policy);
             truncated
         }
     }
 }
+
+/// Like [`truncate_text`], but keeps `tail_percent` of the budget for the end
+/// of `content` instead of half of it.
+pub(crate) fn truncate_text_keeping_tail(
+    content: &str,
+    policy: TruncationPolicy,
+    tail_percent: usize,
+) -> String {
+    truncate_with_byte_estimate(content, policy, tail_percent)
+}
 /// Globally truncate function output items to fit within the given
 /// truncation policy's budget, preserving as many text/image items as
 /// possible and appending a summary for any omitted text items.
@@ -179,7 +193,7 @@ fn truncate_with_token_budget(s: &str, policy: TruncationPolicy) -> (String, Opt
         return (s.to_string(), None);
     }
 
-    let truncated = truncate_with_byte_estimate(s, policy);
+    let truncated = truncate_with_byte_estimate(s, policy, DEFAULT_TAIL_PERCENT);
     let approx_total_usize = approx_token_count(s);
     let approx_total = u64::try_from(approx_total_usize).unwrap_or(u64::MAX);
     if truncated == s {
@@ -192,7 +206,7 @@ fn truncate_with_token_budget(s: &str, policy: TruncationPolicy) -> (String, Opt
 /// Truncate a string using a byte budget derived from the token budget, without
 /// performing any real tokenization. This keeps the logic purely byte-based and
 /// uses a bytes placeholder in the truncated output.
-fn truncate_with_byte_estimate(s: &str, policy: TruncationPolicy) -> String {
+fn truncate_with_byte_estimate(s: &str, policy: TruncationPolicy, tail_percent: usize) -> String {
     if s.is_empty() {
         return String::new();
     }
@@ -215,7 +229,7 @@ fn truncate_with_byte_estimate(s: &str, policy: TruncationPolicy) -> String {
 
     let total_bytes = s.len();
 
-    let (left_budget, right_budget) = split_budget(max_bytes);
+    let (left_budget, right_budget) = split_budget(max_bytes, tail_percent);
 
     let (removed_chars, left, right) = split_string(s, left_budget, right_budget);
 
@@ -274,8 +288,8 @@ fn format_truncation_marker(policy: TruncationPolicy, removed_count: u64) -> Str
     }
 }
 
-fn split_budget(budget: usize) -> (usize, usize) {
-    let left = budget / 2;
+fn split_budget(budget: usize, tail_percent: usize) -> (usize, usize) {
+    let left = budget * (100 - tail_percent.min(100)) / 100;
     (left, budget - left)
 }
 
@@ -322,10 +336,25 @@ mod tests {
     use super::split_string;
     use super::truncate_function_output_items_with_policy;
     use super::truncate_text;
+    use super::truncate_text_keeping_tail;
     use super::truncate_with_token_budget;
     use codex_protocol::models::FunctionCallOutputContentItem;
     use pretty_assertions::assert_eq;
 
+    #[test]
+    fn truncate_text_keeping_tail_gives_the_end_more_of_the_budget() {
+        let content = "abcdefghijklmnopqrstuvwxyz";
+
+        assert_eq!(
+            truncate_text_keeping_tail(content, TruncationPolicy::Bytes(8), 75),
+            "ab…18 chars truncated…uvwxyz"
+        );
+        assert_eq!(
+            truncate_text_keeping_tail(content, TruncationPolicy::Bytes(8), 50),
+            truncate_text(content, TruncationPolicy::Bytes(8))
+        );
+    }
+
     #[test]
     fn split_string_works() {
         assert_eq!(split_string("hello world", 5, 5), (1, "hello", "world"));
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
@@ -11,6 +12,9 @@ use sha1::digest::Output;
 use uuid::Uuid;
 
 use crate::protocol::FileChange;
+use crate::protocol::TurnDiffEvent;
+use crate::protocol::TurnDiffFile;
+use crate::protocol::TurnDiffFileChange;
 
 const ZERO_OID: &str = "0000000000000000000000000000000000000000";
 const DEV_NULL: &str = "/dev/null";
@@ -127,6 +131,44 @@ impl TurnDiffTracker {
         }
     }
 
+    /// Current path and starting contents of every file patched so far, in
+    /// path order. Files created during the turn have empty baselines.
+    pub fn baselines(&self) -> Vec<(PathBuf, &[u8])> {
+        let mut baselines: Vec<(PathBuf, &[u8])> = self
+            .baseline_file_info
+            .iter()
+            .filter_map(|(internal, info)| {
+                Some((
+                    self.get_path_for_internal(internal)?,
+                    info.content.as_slice(),
+                ))
+            })
+            .collect();
+        baselines.sort_by(|a, b| a.0.cmp(&b.0));
+        baselines
+    }
+
+    /// What every path the turn patched held before the turn: the starting
+    /// contents for files that existed, `None` for files that were created or
+    /// moved into place.
+    pub fn contents_before_turn(&self) -> Vec<(PathBuf, Option<Vec<u8>>)> {
+        let mut contents: BTreeMap<PathBuf, Option<Vec<u8>>> = self
+            .baseline_file_info
+            .values()
+            .map(|info| {
+                let existed = info.oid != ZERO_OID;
+                (info.path.clone(), existed.then(|| info.content.clone()))
+            })
+            .collect();
+        // Destinations of moves that were not patched themselves.
+        for internal in self.baseline_file_info.keys() {
+            if let Some(current) = self.get_path_for_internal(internal) {
+                contents.entry(current).or_insert(None);
+            }
+        }
+        contents.into_iter().collect()
+    }
+
     fn get_path_for_internal(&self, internal: &str) -> Option<PathBuf> {
         self.temp_name_to_current_path
             .get(internal)
@@ -223,7 +265,14 @@ impl TurnDiffTracker {
     /// collected before the first time they were touched by apply_patch during this turn with
     /// the current repo state.
     pub fn get_unified_diff(&mut self) -> Result<Option<String>> {
+        Ok(self.get_turn_diff()?.map(|diff| diff.unified_diff))
+    }
+
+    /// Like [`Self::get_unified_diff`], with a per-file summary that names
+    /// created, deleted, and renamed files explicitly and counts changed lines.
+    pub fn get_turn_diff(&mut self) -> Result<Option<TurnDiffEvent>> {
         let mut aggregated = String::new();
+        let mut files = Vec::new();
 
         // Compute diffs per tracked internal file in a stable order by external path.
         let mut baseline_file_names: Vec<String> =
@@ -236,20 +285,27 @@ impl TurnDiffTracker {
         });
 
         for internal in baseline_file_names {
-            aggregated.push_str(self.get_file_diff(&internal).as_str());
+            let Some((diff, file)) = self.get_file_diff(&internal) else {
+                continue;
+            };
+            aggregated.push_str(diff.as_str());
             if !aggregated.ends_with('\n') {
                 aggregated.push('\n');
             }
+            files.push(file);
         }
 
-        if aggregated.trim().is_empty() {
+        if files.is_empty() {
             Ok(None)
         } else {
-            Ok(Some(aggregated))
+            Ok(Some(TurnDiffEvent {
+                unified_diff: aggregated,
+                files,
+            }))
         }
     }
 
-    fn get_file_diff(&mut self, internal_file_name: &str) -> String {
+    fn get_file_diff(&mut self, internal_file_name: &str) -> Option<(String, TurnDiffFile)> {
         let mut aggregated = String::new();
 
         // Snapshot lightweight fields only.
@@ -260,10 +316,7 @@ impl TurnDiffTracker {
                 (PathBuf::new(), FileMode::Regular, ZERO_OID.to_string())
             }
         };
-        let current_external_path = match self.get_path_for_internal(internal_file_name) {
-            Some(p) => p,
-            None => return aggregated,
-        };
+        let current_external_path = self.get_path_for_internal(internal_file_name)?;
 
         let current_mode = file_mode_for_path(&current_external_path).unwrap_or(FileMode::Regular);
         let right_bytes = blob_bytes(&current_external_path, current_mode);
@@ -294,15 +347,41 @@ impl TurnDiffTracker {
             None
         };
 
-        // Fast path: identical bytes or both missing.
-        if left_bytes == right_bytes.as_deref() {
-            return aggregated;
+        let is_add = !left_present && right_bytes.is_some();
+        let is_delete = left_present && right_bytes.is_none();
+        let is_rename = left_present
+            && right_bytes.is_some()
+            && baseline_external_path != current_external_path;
+
+        // Fast path: identical bytes or both missing, and not moved.
+        if left_bytes == right_bytes.as_deref() && !is_rename {
+            return None;
         }
 
-        aggregated.push_str(&format!("diff --git a/{left_display} b/{right_display}\n"));
+        let change = if is_add {
+            TurnDiffFileChange::Added
+        } else if is_delete {
+            TurnDiffFileChange::Deleted
+        } else if is_rename {
+            TurnDiffFileChange::Renamed {
+                from: baseline_external_path.clone(),
+            }
+        } else {
+            TurnDiffFileChange::Modified
+        };
+        let mut file = TurnDiffFile {
+            path: if is_delete {
+                baseline_external_path.clone()
+            } else {
+                current_external_path.clone()
+            },
+            change,
+            added_lines: 0,
+            removed_lines: 0,
+            binary: false,
+        };
 
-        let is_add = !left_present && right_bytes.is_some();
-        let is_delete = left_present && right_bytes.is_none();
+        aggregated.push_str(&format!("diff --git a/{left_display} b/{right_display}\n"));
 
         if is_add {
             aggregated.push_str(&format!("new file mode {current_mode}\n"));
@@ -322,25 +401,44 @@ impl TurnDiffTracker {
             (left_text, right_text, is_add, is_delete),
             (Some(_), Some(_), _, _) | (_, Some(_), true, _) | (Some(_), _, _, true)
         );
+        let text_diff = can_text_diff.then(|| {
+            similar::TextDiff::from_lines(left_text.unwrap_or(""), right_text.unwrap_or(""))
+        });
 
-        if can_text_diff {
-            let l = left_text.unwrap_or("");
-            let r = right_text.unwrap_or("");
-
-            aggregated.push_str(&format!("index {left_oid}..{right_oid}\n"));
-
-            let old_header = if left_present {
-                format!("a/{left_display}")
-            } else {
-                DEV_NULL.to_string()
-            };
-            let new_header = if right_bytes.is_some() {
-                format!("b/{right_display}")
-            } else {
-                DEV_NULL.to_string()
+        if is_rename {
+            let similarity = match &text_diff {
+                Some(diff) => (diff.ratio() * 100.0).round() as u32,
+                None if left_bytes == right_bytes.as_deref() => 100,
+                None => 0,
             };
+            aggregated.push_str(&format!("similarity index {similarity}%\n"));
+            aggregated.push_str(&format!("rename from {left_display}\n"));
+            aggregated.push_str(&format!("rename to {right_display}\n"));
+            if left_bytes == right_bytes.as_deref() {
+                return Some((aggregated, file));
+            }
+        }
 
-            let diff = similar::TextDiff::from_lines(l, r);
+        aggregated.push_str(&format!("index {left_oid}..{right_oid}\n"));
+        let old_header = if left_present {
+            format!("a/{left_display}")
+        } else {
+            DEV_NULL.to_string()
+        };
+        let new_header = if right_bytes.is_some() {
+            format!("b/{right_display}")
+        } else {
+            DEV_NULL.to_string()
+        };
+
+        if let Some(diff) = text_diff {
+            for change in diff.iter_all_changes() {
+                match change.tag() {
+                    similar::ChangeTag::Insert => file.added_lines += 1,
+                    similar::ChangeTag::Delete => file.removed_lines += 1,
+                    similar::ChangeTag::Equal => {}
+                }
+            }
             let unified = diff
                 .unified_diff()
                 .context_radius(3)
@@ -349,22 +447,12 @@ impl TurnDiffTracker {
 
             aggregated.push_str(&unified);
         } else {
-            aggregated.push_str(&format!("index {left_oid}..{right_oid}\n"));
-            let old_header = if left_present {
-                format!("a/{left_display}")
-            } else {
-                DEV_NULL.to_string()
-            };
-            let new_header = if right_bytes.is_some() {
-                format!("b/{right_display}")
-            } else {
-                DEV_NULL.to_string()
-            };
+            file.binary = true;
             aggregated.push_str(&format!("--- {old_header}\n"));
             aggregated.push_str(&format!("+++ {new_header}\n"));
             aggregated.push_str("Binary files differ\n");
         }
-        aggregated
+        Some((aggregated, file))
     }
 }
 
@@ -635,6 +723,9 @@ index {left_oid}..{ZERO_OID}
             let right_oid = git_blob_sha1_hex("line2\n");
             format!(
                 r#"diff --git a/<TMP>/src.txt b/<TMP>/dst.txt
+similarity index 0%
+rename from <TMP>/src.txt
+rename to <TMP>/dst.txt
 index {left_oid}..{right_oid}
 --- a/<TMP>/src.txt
 +++ b/<TMP>/dst.txt
@@ -648,7 +739,7 @@ index {left_oid}..{right_oid}
     }
 
     #[test]
-    fn move_without_1change_yields_no_diff() {
+    fn move_without_change_is_reported_as_rename() {
         let dir = tempdir().unwrap();
         let src = dir.path().join("moved.txt");
         let dest = dir.path().join("renamed.txt");
@@ -667,8 +758,90 @@ index {left_oid}..{right_oid}
         // Simulate apply: move only, no content change.
         fs::rename(&src, &dest).unwrap();
 
-        let diff = acc.get_unified_diff().unwrap();
-        assert_eq!(diff, None);
+        let diff = acc.get_turn_diff().unwrap().unwrap();
+        assert_eq!(
+            normalize_diff_for_test(&diff.unified_diff, dir.path()),
+            r#"diff --git a/<TMP>/moved.txt b/<TMP>/renamed.txt
+similarity index 100%
+rename from <TMP>/moved.txt
+rename to <TMP>/renamed.txt
+"#
+        );
+        assert_eq!(
+            diff.files,
+            vec![TurnDiffFile {
+                path: dest,
+                change: TurnDiffFileChange::Renamed { from: src },
+                added_lines: 0,
+                removed_lines: 0,
+                binary: false,
+            }]
+        );
+    }
+
+    #[test]
+    fn turn_diff_summarizes_each_file() {
+        let dir = tempdir().unwrap();
+        let added = dir.path().join("added.txt");
+        let deleted = dir.path().join("deleted.txt");
+        let modified = dir.path().join("modified.txt");
+        fs::write(&deleted, "a\nb\n").unwrap();
+        fs::write(&modified, "one\ntwo\nthree\n").unwrap();
+
+        let mut acc = TurnDiffTracker::new();
+        acc.on_patch_begin(&HashMap::from([
+            (
+                added.clone(),
+                FileChange::Add {
+                    content: String::new(),
+                },
+            ),
+            (
+                deleted.clone(),
+                FileChange::Delete {
+                    content: String::new(),
+                },
+            ),
+            (
+                modified.clone(),
+                FileChange::Update {
+                    unified_diff: String::new(),
+                    move_path: None,
+                },
+            ),
+        ]));
+
+        fs::write(&added, "x\ny\nz\n").unwrap();
+        fs::remove_file(&deleted).unwrap();
+        fs::write(&modified, "one\n2\nthree\nfour\n").unwrap();
+
+        let diff = acc.get_turn_diff().unwrap().unwrap();
+        assert_eq!(
+            diff.files,
+            vec![
+                TurnDiffFile {
+                    path: added,
+                    change: TurnDiffFileChange::Added,
+                    added_lines: 3,
+                    removed_lines: 0,
+                    binary: false,
+                },
+                TurnDiffFile {
+                    path: deleted,
+                    change: TurnDiffFileChange::Deleted,
+                    added_lines: 0,
+                    removed_lines: 2,
+                    binary: false,
+                },
+                TurnDiffFile {
+                    path: modified,
+                    change: TurnDiffFileChange::Modified,
+                    added_lines: 2,
+                    removed_lines: 1,
+                    binary: false,
+                },
+            ]
+        );
     }
 
     #[test]
@@ -0,0 +1,252 @@
+//! What files looked like after each turn of the session.
+//!
+//! When a turn ends, the ledger keeps the contents every patched file had
+//! before the turn, together with the ghost commit taken as the turn started
+//! when `ghost_commit` is enabled. The state after turn N is then the ghost
+//! commit of turn N + 1, or else the pre-turn contents recorded by the first
+//! later turn that patched the file, or else the file as it is now. Only the
+//! ghost commits see changes made by shell commands.
+//!
+//! A turn is one user submission: the steps of a workflow are recorded as a
+//! single turn. Once the recorded contents exceed [`MAX_LEDGER_BYTES`], the
+//! oldest turns are forgotten.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::Result;
+use anyhow::bail;
+use codex_git::GhostCommit;
+use tokio::process::Command;
+
+use crate::git_info::get_git_repo_root;
+
+/// Bytes of pre-turn file contents kept across all turns.
+const MAX_LEDGER_BYTES: usize = 32 * 1024 * 1024;
+
+#[derive(Debug, Default)]
+pub(crate) struct TurnLedger {
+    /// Number of oldest turns dropped to stay under [`MAX_LEDGER_BYTES`].
+    forgotten: usize,
+    turns: Vec<LedgerEntry>,
+    /// Bytes of contents held by `turns`.
+    bytes: usize,
+}
+
+#[derive(Debug)]
+struct LedgerEntry {
+    /// The submission that started the turn.
+    sub_id: String,
+    /// Snapshot of the workspace taken as the turn started.
+    checkpoint: Option<GhostCommit>,
+    /// Patched paths and what they held before the turn; `None` when the
+    /// path did not exist.
+    contents_before: Vec<(PathBuf, Option<Vec<u8>>)>,
+}
+
+/// Where the contents of a file after some turn can be found.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum FileSource {
+    Checkpoint(GhostCommit),
+    Recorded(Option<Vec<u8>>),
+    Workspace,
+}
+
+impl TurnLedger {
+    /// Number of turns recorded so far, forgotten ones included.
+    pub(crate) fn len(&self) -> usize {
+        self.forgotten + self.turns.len()
+    }
+
+    /// The checkpoint of the latest turn, so a turn that did not take its
+    /// own is not credited with an older one.
+    pub(crate) fn latest_checkpoint(&self) -> Option<&GhostCommit> {
+        self.turns
+            .iter()
+            .rev()
+            .find_map(|entry| entry.checkpoint.as_ref())
+    }
+
+    /// Record a finished task of submission `sub_id`. A later task of the
+    /// same submission, such as the next step of a workflow, only adds the
+    /// paths the turn had not patched yet.
+    pub(crate) fn record(
+        &mut self,
+        sub_id: &str,
+        checkpoint: Option<GhostCommit>,
+        contents_before: Vec<(PathBuf, Option<Vec<u8>>)>,
+    ) {
+        match self.turns.last_mut() {
+            Some(last) if last.sub_id == sub_id => {
+                for (path, contents) in contents_before {
+                    if last
+                        .contents_before
+                        .iter()
+                        .all(|(patched, _)| *patched != path)
+                    {
+                        self.bytes += contents.as_ref().map_or(0, Vec::len);
+                        last.contents_before.push((path, contents));
+                    }
+                }
+            }
+            _ => {
+                self.bytes += contents_bytes(&contents_before);
+                self.turns.push(LedgerEntry {
+                    sub_id: sub_id.to_string(),
+                    checkpoint,
+                    contents_before,
+                });
+            }
+        }
+        while self.bytes > MAX_LEDGER_BYTES && self.turns.len() > 1 {
+            let oldest = self.turns.remove(0);
+            self.bytes -= contents_bytes(&oldest.contents_before);
+            self.forgotten += 1;
+        }
+    }
+
+    /// Where to read `path` as it was after `turn` (1-based). Turn 0 is the
+    /// start of the session.
+    pub(crate) fn source(&self, path: &Path, turn: usize) -> Result<FileSource> {
+        if turn > self.len() {
+            bail!("only {} turn(s) have finished in this session", self.len());
+        }
+        if turn < self.forgotten {
+            bail!(
+                "turn {turn} is no longer kept; the earliest kept is turn {}",
+                self.forgotten
+            );
+        }
+        let turn = turn - self.forgotten;
+        if let Some(checkpoint) = self
+            .turns
+            .get(turn)
+            .and_then(|next| next.checkpoint.as_ref())
+        {
+            return Ok(FileSource::Checkpoint(checkpoint.clone()));
+        }
+        Ok(self.turns[turn..]
+            .iter()
+            .find_map(|entry| {
+                entry
+                    .contents_before
+                    .iter()
+                    .find(|(patched, _)| patched == path)
+                    .map(|(_, contents)| FileSource::Recorded(contents.clone()))
+            })
+            .unwrap_or(FileSource::Workspace))
+    }
+}
+
+fn contents_bytes(contents: &[(PathBuf, Option<Vec<u8>>)]) -> usize {
+    contents
+        .iter()
+        .map(|(_, contents)| contents.as_ref().map_or(0, Vec::len))
+        .sum()
+}
+
+/// Contents of `path` in a ghost commit, or `None` when the commit does not
+/// contain it.
+pub(crate) async fn read_from_checkpoint(
+    checkpoint: &GhostCommit,
+    path: &Path,
+) -> Result<Option<Vec<u8>>> {
+    let parent = path.parent().unwrap_or(path);
+    let root = get_git_repo_root(parent)
+        .with_context(|| format!("{} is not in a git repository", path.display()))?;
+    let relative = path
+        .strip_prefix(&root)
+        .with_context(|| format!("{} is outside {}", path.display(), root.display()))?;
+    let spec = format!(
+        "{}:{}",
+        checkpoint.id(),
+        relative.to_string_lossy().replace('\\', "/")
+    );
+    let output = Command::new("git")
+        .args(["cat-file", "blob", &spec])
+        .current_dir(&root)
+        .output()
+        .await
+        .context("failed to run git")?;
+    Ok(output.status.success().then_some(output.stdout))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn checkpoint(id: &str) -> GhostCommit {
+        GhostCommit::new(id.to_string(), None, Vec::new(), Vec::new())
+    }
+
+    #[test]
+    fn source_prefers_next_checkpoint_then_later_contents() {
+        let path = PathBuf::from("/repo/src/lib.rs");
+        let mut ledger = TurnLedger::default();
+        ledger.record("1", None, vec![(path.clone(), None)]);
+        ledger.record("2", None, Vec::new());
+        ledger.record("3", None, vec![(path.clone(), Some(b"two".to_vec()))]);
+        ledger.record("4", Some(checkpoint("abc")), Vec::new());
+
+        // Before turn 1 created it, the file did not exist.
+        assert_eq!(ledger.source(&path, 0).unwrap(), FileSource::Recorded(None));
+        // Turn 3 recorded what turns 1 and 2 left behind.
+        assert_eq!(
+            ledger.source(&path, 1).unwrap(),
+            FileSource::Recorded(Some(b"two".to_vec()))
+        );
+        assert_eq!(
+            ledger.source(&path, 3).unwrap(),
+            FileSource::Checkpoint(checkpoint("abc"))
+        );
+        assert_eq!(ledger.source(&path, 4).unwrap(), FileSource::Workspace);
+        assert!(ledger.source(&path, 5).is_err());
+    }
+    #[test]
+    fn workflow_steps_share_a_turn() {
+        let first = PathBuf::from("/repo/a.rs");
+        let second = PathBuf::from("/repo/b.rs");
+        let mut ledger = TurnLedger::default();
+        ledger.record("1", None, vec![(first.clone(), Some(b"a0".to_vec()))]);
+        ledger.record(
+            "1",
+            Some(checkpoint("step")),
+            vec![
+                (first.clone(), Some(b"a1".to_vec())),
+                (second.clone(), None),
+            ],
+        );
+
+        assert_eq!(ledger.len(), 1);
+        assert_eq!(
+            ledger.source(&first, 0).unwrap(),
+            FileSource::Recorded(Some(b"a0".to_vec()))
+        );
+        assert_eq!(
+            ledger.source(&second, 0).unwrap(),
+            FileSource::Recorded(None)
+        );
+    }
+
+    #[test]
+    fn oldest_turns_are_forgotten_past_the_byte_cap() {
+        let path = PathBuf::from("/repo/big.bin");
+        let mut ledger = TurnLedger::default();
+        for turn in 1..=3 {
+            ledger.record(
+                &turn.to_string(),
+                None,
+                vec![(path.clone(), Some(vec![0; MAX_LEDGER_BYTES / 2]))],
+            );
+        }
+
+        assert_eq!(ledger.len(), 3);
+        assert!(ledger.source(&path, 0).is_err());
+        assert_eq!(
+            ledger.source(&path, 1).unwrap(),
+            FileSource::Recorded(Some(vec![0; MAX_LEDGER_BYTES / 2]))
+        );
+    }
+}
@@ -33,6 +33,7 @@ use tokio::sync::Mutex;
 
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::command_safety::declared_risk::CommandDeclaration;
 use crate::sandboxing::SandboxPermissions;
 
 mod async_watcher;
@@ -96,6 +97,9 @@ pub(crate) struct ExecCommandRequest {
     pub workdir: Option<PathBuf>,
     pub sandbox_permissions: SandboxPermissions,
     pub justification: Option<String>,
+    /// Intent and risk the model declared, when `command_risk_declarations`
+    /// is enabled.
+    pub declaration: Option<CommandDeclaration>,
 }
 
 #[derive(Debug)]
@@ -220,6 +224,7 @@ mod tests {
                     workdir: None,
                     sandbox_permissions: SandboxPermissions::UseDefault,
                     justification: None,
+                    declaration: None,
                 },
                 &context,
             )
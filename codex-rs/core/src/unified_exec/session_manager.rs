@@ -13,10 +13,12 @@ use tokio_util::sync::CancellationToken;
 use crate::bash::extract_bash_command;
 use crate::codex::Session;
 use crate::codex::TurnContext;
+use crate::command_safety::declared_risk::CommandDeclaration;
 use crate::exec_env::create_env;
 use crate::exec_policy::create_exec_approval_requirement_for_command;
 use crate::protocol::BackgroundEventEvent;
 use crate::protocol::EventMsg;
+use crate::resource_preflight::apply_resource_preflight;
 use crate::sandboxing::ExecEnv;
 use crate::sandboxing::SandboxPermissions;
 use crate::tools::orchestrator::ToolOrchestrator;
@@ -128,6 +130,7 @@ impl UnifiedExecSessionManager {
                 cwd.clone(),
                 request.sandbox_permissions,
                 request.justification,
+                request.declaration.as_ref(),
                 context,
             )
             .await;
@@ -478,6 +481,7 @@ impl UnifiedExecSessionManager {
         cwd: PathBuf,
         sandbox_permissions: SandboxPermissions,
         justification: Option<String>,
+        declaration: Option<&CommandDeclaration>,
         context: &UnifiedExecContext,
     ) -> Result<UnifiedExecSession, UnifiedExecError> {
         let env = apply_unified_exec_env(create_env(&context.turn.shell_environment_policy));
@@ -491,6 +495,23 @@ impl UnifiedExecSessionManager {
             context.turn.approval_policy,
             &context.turn.sandbox_policy,
             sandbox_permissions,
+            &HashMap::new(),
+        )
+        .await;
+        let exec_approval_requirement = match declaration {
+            Some(declaration) => declaration.apply(
+                command,
+                context.turn.approval_policy,
+                exec_approval_requirement,
+            ),
+            None => exec_approval_requirement,
+        };
+        let exec_approval_requirement = apply_resource_preflight(
+            context.session.as_ref(),
+            context.turn.as_ref(),
+            command,
+            &cwd,
+            exec_approval_requirement,
         )
         .await;
         let req = UnifiedExecToolRequest::new(
@@ -0,0 +1,331 @@
+//! Aggregate usage counts kept on this machine, shown by `codex stats`.
+//!
+//! Sessions add what they did (turns, tool calls, failures by category) to
+//! `$CODEX_HOME/stats.json` at the end of each turn. Nothing here leaves the
+//! machine unless the matching `telemetry.share` category is granted, in
+//! which case the same counts are also emitted as an OpenTelemetry event for
+//! the configured `[otel]` exporter.
+
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use codex_otel::otel_manager::OtelManager;
+use codex_protocol::protocol::EventMsg;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::fs;
+use tracing::warn;
+
+use crate::config::types::Telemetry;
+use crate::config::types::TelemetryCategory;
+use crate::file_lock::lock_file_async;
+use crate::file_lock::write_atomic_async;
+
+const STATS_FILENAME: &str = "stats.json";
+
+/// Counts since `since` (Unix timestamp of the first recorded session).
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageStats {
+    #[serde(default)]
+    pub since: i64,
+    #[serde(default)]
+    pub sessions: u64,
+    #[serde(default)]
+    pub turns: u64,
+    #[serde(default)]
+    pub tool_calls: u64,
+    #[serde(default)]
+    pub failed_tool_calls: u64,
+    /// Errors reported to the user, by `ErrorCategory`.
+    #[serde(default)]
+    pub failures: BTreeMap<String, u64>,
+}
+
+impl UsageStats {
+    fn is_empty(&self) -> bool {
+        self.sessions == 0
+            && self.turns == 0
+            && self.tool_calls == 0
+            && self.failed_tool_calls == 0
+            && self.failures.is_empty()
+    }
+
+    fn add(&mut self, other: &UsageStats) {
+        self.sessions += other.sessions;
+        self.turns += other.turns;
+        self.tool_calls += other.tool_calls;
+        self.failed_tool_calls += other.failed_tool_calls;
+        for (category, count) in &other.failures {
+            *self.failures.entry(category.clone()).or_default() += count;
+        }
+    }
+}
+
+pub async fn load_usage_stats(codex_home: &Path) -> std::io::Result<UsageStats> {
+    read_stats(&codex_home.join(STATS_FILENAME)).await
+}
+
+/// Delete the local stats. Returns whether there were any.
+pub async fn reset_usage_stats(codex_home: &Path) -> std::io::Result<bool> {
+    let path = codex_home.join(STATS_FILENAME);
+    let _lock = lock_file_async(&path).await?;
+    match fs::remove_file(&path).await {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err),
+    }
+}
+
+/// Counts what one session does until the next flush.
+#[derive(Debug)]
+pub(crate) struct UsageStatsRecorder {
+    /// `None` when `telemetry.local_stats` is off.
+    path: Option<PathBuf>,
+    share: Vec<TelemetryCategory>,
+    pending: Mutex<UsageStats>,
+}
+
+impl UsageStatsRecorder {
+    /// `None` when neither local stats nor any shared category is enabled.
+    pub(crate) fn new(codex_home: &Path, telemetry: &Telemetry) -> Option<Self> {
+        if !telemetry.local_stats && telemetry.share.is_empty() {
+            return None;
+        }
+        let recorder = Self {
+            path: telemetry
+                .local_stats
+                .then(|| codex_home.join(STATS_FILENAME)),
+            share: telemetry.share.clone(),
+            pending: Mutex::new(UsageStats::default()),
+        };
+        recorder.update(|stats| stats.sessions += 1);
+        Some(recorder)
+    }
+
+    /// Count the turns and failures among the session's events.
+    pub(crate) fn observe(&self, msg: &EventMsg) {
+        match msg {
+            EventMsg::TaskStarted(_) => self.update(|stats| stats.turns += 1),
+            EventMsg::Error(error) => {
+                let category = error
+                    .details
+                    .as_ref()
+                    .and_then(|details| serde_json::to_value(details.category).ok())
+                    .and_then(|value| value.as_str().map(str::to_string))
+                    .unwrap_or_else(|| "unknown".to_string());
+                self.update(|stats| *stats.failures.entry(category).or_default() += 1);
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn record_tool_call(&self, success: bool) {
+        self.update(|stats| {
+            stats.tool_calls += 1;
+            if !success {
+                stats.failed_tool_calls += 1;
+            }
+        });
+    }
+
+    /// Save the counts since the last flush and share the granted categories.
+    pub(crate) async fn flush(&self, otel: &OtelManager) {
+        let pending = match self.pending.lock() {
+            Ok(mut pending) => std::mem::take(&mut *pending),
+            Err(_) => return,
+        };
+        if pending.is_empty() {
+            return;
+        }
+        if !self.share.is_empty() {
+            let usage = self.share.contains(&TelemetryCategory::Usage);
+            let failures = self.share.contains(&TelemetryCategory::Failures);
+            let categories = pending
+                .failures
+                .iter()
+                .map(|(category, count)| format!("{category}={count}"))
+                .collect::<Vec<_>>()
+                .join(",");
+            otel.usage_stats(
+                usage.then_some(pending.turns),
+                usage.then_some(pending.tool_calls),
+                failures.then_some(pending.failed_tool_calls),
+                failures.then_some(categories.as_str()),
+            );
+        }
+        if let Some(path) = &self.path
+            && let Err(err) = write_stats(path, &pending).await
+        {
+            warn!("failed to save usage stats to {}: {err}", path.display());
+        }
+    }
+
+    fn update(&self, apply: impl FnOnce(&mut UsageStats)) {
+        if let Ok(mut pending) = self.pending.lock() {
+            apply(&mut pending);
+        }
+    }
+}
+
+async fn read_stats(path: &Path) -> std::io::Result<UsageStats> {
+    match fs::read(path).await {
+        Ok(data) => serde_json::from_slice(&data)
+            .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err)),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(UsageStats::default()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Add `pending` to the file. Sessions running at the same time flush in
+/// turn, so none of their counts are lost.
+async fn write_stats(path: &Path, pending: &UsageStats) -> std::io::Result<()> {
+    let _lock = lock_file_async(path).await?;
+    let mut stats = read_stats(path).await?;
+    if stats.since == 0 {
+        stats.since = chrono::Utc::now().timestamp();
+    }
+    stats.add(pending);
+    let json = serde_json::to_vec_pretty(&stats).map_err(std::io::Error::other)?;
+    write_atomic_async(path, json, None).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::ErrorCategory;
+    use codex_protocol::protocol::ErrorDetails;
+    use codex_protocol::protocol::ErrorEvent;
+    use pretty_assertions::assert_eq;
+    use std::process::Command;
+    use std::process::Stdio;
+    use tempfile::tempdir;
+
+    /// Set for the child processes of `concurrent_processes_flush_every_count`
+    /// to the `CODEX_HOME` they record to.
+    const CHILD_CODEX_HOME_ENV: &str = "CODEX_USAGE_STATS_TEST_HOME";
+
+    /// A session that records five tool calls, flushing after each.
+    async fn run_session(codex_home: &Path) {
+        let recorder =
+            UsageStatsRecorder::new(codex_home, &Telemetry::default()).expect("recorder");
+        let otel = OtelManager::new(
+            codex_protocol::ConversationId::new(),
+            "model",
+            "slug",
+            None,
+            None,
+            None,
+            false,
+            "test".to_string(),
+            codex_protocol::protocol::SessionSource::Exec,
+        );
+        for _ in 0..5 {
+            recorder.record_tool_call(true);
+            recorder.flush(&otel).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn flush_adds_counts_to_the_stats_file() {
+        let codex_home = tempdir().expect("tempdir");
+        let telemetry = Telemetry::default();
+        let recorder = UsageStatsRecorder::new(codex_home.path(), &telemetry).expect("recorder");
+        recorder.record_tool_call(true);
+        recorder.record_tool_call(false);
+        recorder.observe(&EventMsg::Error(ErrorEvent {
+            message: "boom".to_string(),
+            codex_error_info: None,
+            details: Some(ErrorDetails::new(ErrorCategory::Sandbox, "denied")),
+        }));
+        let otel = OtelManager::new(
+            codex_protocol::ConversationId::new(),
+            "model",
+            "slug",
+            None,
+            None,
+            None,
+            false,
+            "test".to_string(),
+            codex_protocol::protocol::SessionSource::Exec,
+        );
+        recorder.flush(&otel).await;
+        recorder.record_tool_call(true);
+        recorder.flush(&otel).await;
+
+        let stats = load_usage_stats(codex_home.path()).await.expect("stats");
+        assert_eq!(
+            UsageStats {
+                since: stats.since,
+                sessions: 1,
+                turns: 0,
+                tool_calls: 3,
+                failed_tool_calls: 1,
+                failures: BTreeMap::from([("sandbox".to_string(), 1)]),
+            },
+            stats
+        );
+        assert!(reset_usage_stats(codex_home.path()).await.expect("reset"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_sessions_flush_every_count() {
+        let codex_home = tempdir().expect("tempdir");
+        let mut sessions = Vec::new();
+        for _ in 0..8 {
+            let codex_home = codex_home.path().to_path_buf();
+            sessions.push(tokio::spawn(async move {
+                run_session(&codex_home).await;
+            }));
+        }
+        for session in sessions {
+            session.await.expect("session");
+        }
+
+        let stats = load_usage_stats(codex_home.path()).await.expect("stats");
+        assert_eq!((stats.sessions, stats.tool_calls), (8, 40));
+    }
+
+    /// Does nothing unless run as a child process of
+    /// `concurrent_processes_flush_every_count`.
+    #[tokio::test]
+    async fn run_session_in_child_process() {
+        if let Some(codex_home) = std::env::var_os(CHILD_CODEX_HOME_ENV) {
+            run_session(Path::new(&codex_home)).await;
+        }
+    }
+
+    #[tokio::test]
+    async fn concurrent_processes_flush_every_count() {
+        let codex_home = tempdir().expect("tempdir");
+
+        // Re-run this test binary so each session flushes from its own
+        // process, as separate Codex processes do.
+        let exe = std::env::current_exe().expect("test binary");
+        let children = (0..4)
+            .map(|_| {
+                Command::new(&exe)
+                    .args([
+                        "--exact",
+                        "usage_stats::tests::run_session_in_child_process",
+                    ])
+                    .env(CHILD_CODEX_HOME_ENV, codex_home.path())
+                    .stdout(Stdio::null())
+                    .spawn()
+                    .expect("spawn child process")
+            })
+            .collect::<Vec<_>>();
+        for mut child in children {
+            let status = tokio::task::spawn_blocking(move || child.wait())
+                .await
+                .expect("join")
+                .expect("wait for child process");
+            assert!(status.success(), "child process failed");
+        }
+
+        let stats = load_usage_stats(codex_home.path()).await.expect("stats");
+        assert_eq!((stats.sessions, stats.tool_calls), (4, 20));
+    }
+}
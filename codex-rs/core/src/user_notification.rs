@@ -1,27 +1,43 @@
+use std::io::Write;
+use std::path::Path;
+use std::process::Stdio;
+
 use serde::Serialize;
 use tracing::error;
 use tracing::warn;
 
+use crate::config::types::NotificationKind;
+use crate::config::types::NotifySink;
+use crate::config::types::NotifySinkTarget;
+
 #[derive(Debug, Default)]
 pub(crate) struct UserNotifier {
     notify_command: Option<Vec<String>>,
+    sinks: Vec<NotifySink>,
 }
 
 impl UserNotifier {
     pub(crate) fn notify(&self, notification: &UserNotification) {
+        let Ok(json) = serde_json::to_string(&notification) else {
+            error!("failed to serialise notification payload");
+            return;
+        };
+        // `notify` predates the other notifications; keep its scripts
+        // seeing only completed turns.
         if let Some(notify_command) = &self.notify_command
             && !notify_command.is_empty()
+            && notification.kind() == NotificationKind::AgentTurnComplete
         {
-            self.invoke_notify(notify_command, notification)
+            self.invoke_notify(notify_command, &json)
+        }
+        for sink in &self.sinks {
+            if sink.events.is_empty() || sink.events.contains(&notification.kind()) {
+                deliver(sink.target.clone(), format!("{json}\n"));
+            }
         }
     }
 
-    fn invoke_notify(&self, notify_command: &[String], notification: &UserNotification) {
-        let Ok(json) = serde_json::to_string(&notification) else {
-            error!("failed to serialise notification payload");
-            return;
-        };
-
+    fn invoke_notify(&self, notify_command: &[String], json: &str) {
         let mut command = std::process::Command::new(&notify_command[0]);
         if notify_command.len() > 1 {
             command.args(&notify_command[1..]);
@@ -34,13 +50,80 @@ impl UserNotifier {
         }
     }
 
-    pub(crate) fn new(notify: Option<Vec<String>>) -> Self {
+    pub(crate) fn new(notify: Option<Vec<String>>, sinks: Vec<NotifySink>) -> Self {
         Self {
             notify_command: notify,
+            sinks,
         }
     }
 }
 
+/// Write one JSON line to `target` on a thread of its own, so a slow
+/// reader never holds up the session.
+fn deliver(target: NotifySinkTarget, line: String) {
+    std::thread::spawn(move || {
+        let result = match &target {
+            NotifySinkTarget::Socket { path } => write_to_socket(path, &line),
+            NotifySinkTarget::Fifo { path } => write_to_fifo(path, &line),
+            NotifySinkTarget::Command { command } => pipe_to_command(command, &line),
+        };
+        if let Err(err) = result {
+            warn!("failed to deliver notification to {target:?}: {err}");
+        }
+    });
+}
+
+#[cfg(unix)]
+fn write_to_socket(path: &Path, line: &str) -> std::io::Result<()> {
+    let mut stream = std::os::unix::net::UnixStream::connect(path)?;
+    stream.set_write_timeout(Some(std::time::Duration::from_secs(1)))?;
+    stream.write_all(line.as_bytes())
+}
+
+#[cfg(not(unix))]
+fn write_to_socket(_path: &Path, _line: &str) -> std::io::Result<()> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "Unix domain sockets are not supported on this platform",
+    ))
+}
+
+/// Notifications are dropped while nothing has the pipe open for reading,
+/// rather than waiting for a reader.
+fn write_to_fifo(path: &Path, line: &str) -> std::io::Result<()> {
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.custom_flags(libc::O_NONBLOCK);
+    }
+    let mut pipe = match options.open(path) {
+        Ok(pipe) => pipe,
+        #[cfg(unix)]
+        Err(err) if err.raw_os_error() == Some(libc::ENXIO) => {
+            tracing::debug!("no reader on {}; dropping notification", path.display());
+            return Ok(());
+        }
+        Err(err) => return Err(err),
+    };
+    pipe.write_all(line.as_bytes())
+}
+
+fn pipe_to_command(command: &[String], line: &str) -> std::io::Result<()> {
+    let Some((program, args)) = command.split_first() else {
+        return Ok(());
+    };
+    let mut child = std::process::Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(line.as_bytes())?;
+    }
+    child.wait().map(|_| ())
+}
+
 /// User can configure a program that will receive notifications. Each
 /// notification is serialized as JSON and passed as an argument to the
 /// program.
@@ -59,6 +142,32 @@ pub(crate) enum UserNotification {
         /// The last message sent by the assistant in the turn.
         last_assistant_message: Option<String>,
     },
+
+    /// A command or patch is waiting for the user to approve it.
+    #[serde(rename_all = "kebab-case")]
+    ApprovalRequested {
+        thread_id: String,
+        turn_id: String,
+        cwd: String,
+        call_id: String,
+
+        /// The command waiting for approval; `None` for a patch.
+        command: Option<String>,
+
+        /// Files the patch would change; empty for a command.
+        changes: Vec<String>,
+
+        reason: Option<String>,
+    },
+}
+
+impl UserNotification {
+    pub(crate) fn kind(&self) -> NotificationKind {
+        match self {
+            Self::AgentTurnComplete { .. } => NotificationKind::AgentTurnComplete,
+            Self::ApprovalRequested { .. } => NotificationKind::ApprovalRequested,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -84,4 +193,45 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn approval_requested_has_its_own_kind() -> Result<()> {
+        let notification = UserNotification::ApprovalRequested {
+            thread_id: "thread".to_string(),
+            turn_id: "1".to_string(),
+            cwd: "/repo".to_string(),
+            call_id: "call-1".to_string(),
+            command: Some("cargo test".to_string()),
+            changes: Vec::new(),
+            reason: None,
+        };
+
+        assert_eq!(notification.kind(), NotificationKind::ApprovalRequested);
+        assert_eq!(
+            serde_json::to_string(&notification)?,
+            r#"{"type":"approval-requested","thread-id":"thread","turn-id":"1","cwd":"/repo","call-id":"call-1","command":"cargo test","changes":[],"reason":null}"#
+        );
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn socket_and_fifo_sinks_receive_json_lines() -> Result<()> {
+        use std::io::Read;
+
+        let dir = tempfile::tempdir()?;
+        let socket = dir.path().join("codex.sock");
+        let listener = std::os::unix::net::UnixListener::bind(&socket)?;
+        write_to_socket(&socket, "{\"type\":\"agent-turn-complete\"}\n")?;
+        let mut received = String::new();
+        listener.accept()?.0.read_to_string(&mut received)?;
+        assert_eq!(received, "{\"type\":\"agent-turn-complete\"}\n");
+
+        // Without a reader the notification is dropped instead of blocking.
+        let fifo = dir.path().join("codex.fifo");
+        let fifo_path = std::ffi::CString::new(fifo.to_string_lossy().as_bytes())?;
+        assert_eq!(unsafe { libc::mkfifo(fifo_path.as_ptr(), 0o600) }, 0);
+        write_to_fifo(&fifo, "{}\n")?;
+        Ok(())
+    }
 }
@@ -0,0 +1,380 @@
+//! Translation between Windows and WSL path styles.
+//!
+//! When Codex runs on Windows against a workspace inside WSL (or inside WSL
+//! against files on a Windows drive), the model sees and writes a mix of
+//! `C:\...`, `\\wsl.localhost\<distro>\...`, and `/mnt/c/...` paths. This
+//! module rewrites them into the form the consumer understands:
+//!
+//! * file tools and patches are opened by Codex itself, so their paths use the
+//!   host's form;
+//! * command arguments that are paths use the form of the configured
+//!   execution side; shell scripts are left as written. When that side is
+//!   WSL but Codex runs on Windows, commands are wrapped in `wsl.exe`.
+//!
+//! Nothing is translated unless the `[wsl]` table is set.
+
+use std::path::Path;
+
+use crate::config::WslExecutionSide;
+use crate::config::WslToml;
+
+const WSL_UNC_PREFIXES: &[&str] = &[
+    r"\\wsl$\",
+    r"\\wsl.localhost\",
+    "//wsl$/",
+    "//wsl.localhost/",
+];
+const PATCH_PATH_HEADERS: &[&str] = &[
+    "*** Add File: ",
+    "*** Delete File: ",
+    "*** Update File: ",
+    "*** Move to: ",
+];
+const WINDOWS_SHELLS: &[&str] = &[
+    "cmd",
+    "cmd.exe",
+    "powershell",
+    "powershell.exe",
+    "pwsh",
+    "pwsh.exe",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WslPaths {
+    host_side: WslExecutionSide,
+    execution_side: WslExecutionSide,
+    distro: Option<String>,
+}
+
+/// Fill in the `[wsl]` settings from the environment. Returns `None` when
+/// the table is not set.
+pub(crate) fn resolve_config(toml: Option<WslToml>, cwd: &Path) -> Option<WslToml> {
+    let toml = toml?;
+    let distro = toml.distro.or_else(|| {
+        if cfg!(windows) {
+            distro_from_unc(&cwd.to_string_lossy())
+        } else {
+            std::env::var("WSL_DISTRO_NAME")
+                .ok()
+                .filter(|distro| !distro.is_empty())
+        }
+    });
+    Some(WslToml {
+        execution_side: Some(toml.execution_side.unwrap_or_else(host_side)),
+        distro,
+    })
+}
+
+fn host_side() -> WslExecutionSide {
+    if cfg!(windows) {
+        WslExecutionSide::Windows
+    } else {
+        WslExecutionSide::Wsl
+    }
+}
+
+impl From<WslToml> for WslPaths {
+    fn from(toml: WslToml) -> Self {
+        Self::new(
+            host_side(),
+            toml.execution_side.unwrap_or_else(host_side),
+            toml.distro,
+        )
+    }
+}
+
+impl WslPaths {
+    fn new(
+        host_side: WslExecutionSide,
+        execution_side: WslExecutionSide,
+        distro: Option<String>,
+    ) -> Self {
+        Self {
+            host_side,
+            execution_side,
+            distro,
+        }
+    }
+
+    /// Rewrite a path the model supplied into the form Codex can open.
+    pub(crate) fn to_host_path(&self, path: &str) -> String {
+        self.translate_path(path, self.host_side, true)
+            .unwrap_or_else(|| path.to_string())
+    }
+
+    /// Rewrite the file paths in an `apply_patch` body into the host's form.
+    pub(crate) fn to_host_patch(&self, patch: &str) -> String {
+        patch
+            .split_inclusive('\n')
+            .map(|line| {
+                PATCH_PATH_HEADERS
+                    .iter()
+                    .find_map(|header| {
+                        let rest = line.strip_prefix(header)?;
+                        let path = rest.trim_end_matches(['\r', '\n']);
+                        let line_end = &rest[path.len()..];
+                        Some(format!("{header}{}{line_end}", self.to_host_path(path)))
+                    })
+                    .unwrap_or_else(|| line.to_string())
+            })
+            .collect()
+    }
+
+    /// Rewrite the arguments of `command` that are paths for the execution
+    /// side, wrapping it in `wsl.exe` when commands must cross from Windows
+    /// into WSL. Scripts passed to a shell are not rewritten.
+    pub(crate) fn to_exec_command(&self, command: Vec<String>, cwd: &Path) -> Vec<String> {
+        let side = self.execution_side;
+        let mut command: Vec<String> = command
+            .into_iter()
+            .map(|arg| self.translate_path(&arg, side, false).unwrap_or(arg))
+            .collect();
+        if self.host_side == WslExecutionSide::Windows && side == WslExecutionSide::Wsl {
+            // Scripts the tools wrapped for the user's Windows shell run under
+            // bash on the WSL side instead.
+            let is_windows_shell = command.first().is_some_and(|program| {
+                let name = program.rsplit(['\\', '/']).next().unwrap_or(program);
+                WINDOWS_SHELLS.contains(&name.to_ascii_lowercase().as_str())
+            });
+            if is_windows_shell && let Some(script) = command.pop() {
+                command = vec!["bash".to_string(), "-lc".to_string(), script];
+            }
+            let mut wrapped = vec!["wsl.exe".to_string()];
+            if let Some(distro) = &self.distro {
+                wrapped.extend(["-d".to_string(), distro.clone()]);
+            }
+            let cwd = cwd.to_string_lossy();
+            let cwd = self
+                .translate_path(&cwd, WslExecutionSide::Wsl, false)
+                .unwrap_or_else(|| cwd.to_string());
+            wrapped.extend(["--cd".to_string(), cwd, "--".to_string()]);
+            wrapped.extend(command);
+            command = wrapped;
+        }
+        command
+    }
+
+    /// Translate `path` into `side`'s form. `allow_unc` permits mapping Linux
+    /// paths outside `/mnt` to `\\wsl.localhost\<distro>\...`, which is only
+    /// safe for values known to be paths (not command-line switches).
+    fn translate_path(
+        &self,
+        path: &str,
+        side: WslExecutionSide,
+        allow_unc: bool,
+    ) -> Option<String> {
+        match side {
+            WslExecutionSide::Wsl => drive_to_mnt(path).or_else(|| unc_to_linux(path)),
+            WslExecutionSide::Windows => mnt_to_drive(path).or_else(|| {
+                if !allow_unc {
+                    return None;
+                }
+                let distro = self.distro.as_deref()?;
+                linux_to_unc(path, distro)
+            }),
+        }
+    }
+}
+
+/// `C:\Users\me` or `C:/Users/me` -> `/mnt/c/Users/me`.
+fn drive_to_mnt(path: &str) -> Option<String> {
+    let mut chars = path.chars();
+    let drive = chars.next().filter(char::is_ascii_alphabetic)?;
+    if chars.next() != Some(':') {
+        return None;
+    }
+    let rest = chars.as_str();
+    if !rest.starts_with(['\\', '/']) {
+        return None;
+    }
+    let rest = rest.replace('\\', "/");
+    let rest = rest.trim_end_matches('/');
+    Some(format!("/mnt/{}{rest}", drive.to_ascii_lowercase()))
+}
+
+/// `/mnt/c/Users/me` -> `C:\Users\me`.
+fn mnt_to_drive(path: &str) -> Option<String> {
+    let rest = path.strip_prefix("/mnt/")?;
+    let mut chars = rest.chars();
+    let drive = chars.next().filter(char::is_ascii_alphabetic)?;
+    let rest = chars.as_str();
+    if !(rest.is_empty() || rest.starts_with('/')) {
+        return None;
+    }
+    let rest = rest.trim_start_matches('/').replace('/', "\\");
+    Some(format!("{}:\\{rest}", drive.to_ascii_uppercase()))
+}
+
+/// `\\wsl.localhost\Ubuntu\home\me` -> `/home/me`.
+fn unc_to_linux(path: &str) -> Option<String> {
+    let rest = strip_unc_prefix(path)?;
+    let rest = rest.replace('\\', "/");
+    let path = rest.split_once('/').map_or("", |(_, path)| path);
+    Some(format!("/{path}"))
+}
+
+/// `/home/me` -> `\\wsl.localhost\<distro>\home\me`.
+fn linux_to_unc(path: &str, distro: &str) -> Option<String> {
+    if !path.starts_with('/') || path.starts_with("//") {
+        return None;
+    }
+    let rest = path.trim_start_matches('/').replace('/', "\\");
+    Some(format!(r"\\wsl.localhost\{distro}\{rest}"))
+}
+
+fn distro_from_unc(path: &str) -> Option<String> {
+    let rest = strip_unc_prefix(path)?;
+    let distro = rest.split(['\\', '/']).next()?;
+    (!distro.is_empty()).then(|| distro.to_string())
+}
+
+fn strip_unc_prefix(path: &str) -> Option<&str> {
+    WSL_UNC_PREFIXES.iter().find_map(|prefix| {
+        path.get(..prefix.len())
+            .filter(|head| head.eq_ignore_ascii_case(prefix))
+            .map(|_| &path[prefix.len()..])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::PathBuf;
+
+    fn windows_host(execution_side: WslExecutionSide) -> WslPaths {
+        WslPaths::new(
+            WslExecutionSide::Windows,
+            execution_side,
+            Some("Ubuntu".to_string()),
+        )
+    }
+
+    #[test]
+    fn host_paths_use_the_host_form() {
+        let windows = windows_host(WslExecutionSide::Windows);
+        assert_eq!(windows.to_host_path("/mnt/c/src/app"), r"C:\src\app");
+        assert_eq!(
+            windows.to_host_path("/home/me/app/main.rs"),
+            r"\\wsl.localhost\Ubuntu\home\me\app\main.rs"
+        );
+        assert_eq!(windows.to_host_path(r"C:\src"), r"C:\src");
+
+        let wsl = WslPaths::new(WslExecutionSide::Wsl, WslExecutionSide::Wsl, None);
+        assert_eq!(wsl.to_host_path(r"D:\data\x.csv"), "/mnt/d/data/x.csv");
+        assert_eq!(
+            wsl.to_host_path(r"\\wsl$\Ubuntu\home\me\app"),
+            "/home/me/app"
+        );
+        assert_eq!(wsl.to_host_path("relative/path"), "relative/path");
+    }
+
+    #[test]
+    fn patch_headers_are_translated() {
+        let wsl = WslPaths::new(WslExecutionSide::Wsl, WslExecutionSide::Wsl, None);
+        let patch = "*** Begin Patch\r\n*** Update File: C:\\src\\a.txt\r\n*** Move to: C:/src/b.txt\r\n@@\r\n-C:\\keep\r\n+x\r\n*** End Patch";
+
+        assert_eq!(
+            wsl.to_host_patch(patch),
+            "*** Begin Patch\r\n*** Update File: /mnt/c/src/a.txt\r\n*** Move to: /mnt/c/src/b.txt\r\n@@\r\n-C:\\keep\r\n+x\r\n*** End Patch"
+        );
+    }
+
+    #[test]
+    fn commands_are_wrapped_for_wsl_execution_from_windows() {
+        let paths = windows_host(WslExecutionSide::Wsl);
+        let command = vec![
+            r"C:\Windows\System32\WindowsPowerShell\v1.0\powershell.exe".to_string(),
+            "-Command".to_string(),
+            r#"cat "C:\src\notes.txt" | head"#.to_string(),
+        ];
+
+        assert_eq!(
+            paths.to_exec_command(command, &PathBuf::from(r"\\wsl.localhost\Ubuntu\home\me")),
+            vec![
+                "wsl.exe",
+                "-d",
+                "Ubuntu",
+                "--cd",
+                "/home/me",
+                "--",
+                "bash",
+                "-lc",
+                r#"cat "C:\src\notes.txt" | head"#,
+            ]
+        );
+        assert_eq!(
+            paths.to_exec_command(
+                vec!["cat".to_string(), r"C:\src\notes.txt".to_string()],
+                &PathBuf::from(r"C:\src"),
+            ),
+            vec![
+                "wsl.exe",
+                "-d",
+                "Ubuntu",
+                "--cd",
+                "/mnt/c/src",
+                "--",
+                "cat",
+                "/mnt/c/src/notes.txt",
+            ]
+        );
+    }
+
+    #[test]
+    fn commands_for_the_windows_side_only_map_mounted_drive_arguments() {
+        let paths = WslPaths::new(
+            WslExecutionSide::Wsl,
+            WslExecutionSide::Windows,
+            Some("Ubuntu".to_string()),
+        );
+        let command = vec![
+            "findstr.exe".to_string(),
+            "TODO".to_string(),
+            "/mnt/c/src/notes.txt".to_string(),
+            "/home/me/notes.txt".to_string(),
+        ];
+
+        assert_eq!(
+            paths.to_exec_command(command, &PathBuf::from("/mnt/c/src")),
+            vec![
+                "findstr.exe",
+                "TODO",
+                r"C:\src\notes.txt",
+                "/home/me/notes.txt"
+            ]
+        );
+        assert_eq!(
+            paths.to_exec_command(
+                vec![
+                    "cmd.exe".to_string(),
+                    "/c".to_string(),
+                    "type /mnt/c/src/notes.txt".to_string(),
+                ],
+                &PathBuf::from("/mnt/c/src"),
+            ),
+            vec!["cmd.exe", "/c", "type /mnt/c/src/notes.txt"]
+        );
+    }
+
+    #[test]
+    fn nothing_is_translated_without_the_wsl_table() {
+        assert_eq!(
+            resolve_config(None, &PathBuf::from(r"\\wsl.localhost\Ubuntu\home\me")),
+            None
+        );
+        assert_eq!(
+            resolve_config(
+                Some(WslToml {
+                    execution_side: None,
+                    distro: Some("Debian".to_string()),
+                }),
+                &PathBuf::from("/home/me"),
+            ),
+            Some(WslToml {
+                execution_side: Some(host_side()),
+                distro: Some("Debian".to_string()),
+            })
+        );
+    }
+}
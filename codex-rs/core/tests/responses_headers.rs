@@ -54,6 +54,7 @@ async fn responses_stream_includes_subagent_header_on_review() {
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
+        embedding_model: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -148,6 +149,7 @@ async fn responses_stream_includes_subagent_header_on_other() {
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
+        embedding_model: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
@@ -238,6 +240,7 @@ async fn responses_respects_model_family_overrides_from_config() {
         stream_max_retries: Some(0),
         stream_idle_timeout_ms: Some(5_000),
         requires_openai_auth: false,
+        embedding_model: None,
     };
 
     let codex_home = TempDir::new().expect("failed to create TempDir");
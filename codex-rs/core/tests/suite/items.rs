@@ -1,10 +1,12 @@
 #![cfg(not(target_os = "windows"))]
 
 use anyhow::Ok;
+use codex_core::protocol::ContextComponentKind;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ItemCompletedEvent;
 use codex_core::protocol::ItemStartedEvent;
 use codex_core::protocol::Op;
+use codex_protocol::config_types::ReasoningDisplay;
 use codex_protocol::items::TurnItem;
 use codex_protocol::user_input::UserInput;
 use core_test_support::responses::ev_assistant_message;
@@ -24,6 +26,7 @@ use core_test_support::responses::start_mock_server;
 use core_test_support::skip_if_no_network;
 use core_test_support::test_codex::TestCodex;
 use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
 use core_test_support::wait_for_event_match;
 use pretty_assertions::assert_eq;
 
@@ -79,6 +82,64 @@ async fn user_message_item_is_emitted() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn context_manifest_lists_attached_files() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+
+    let test = test_codex().build(&server).await?;
+    std::fs::write(test.workspace_path("notes.txt"), "remember the milk")?;
+
+    mount_sse_once(
+        &server,
+        sse(vec![ev_response_created("resp-1"), ev_completed("resp-1")]),
+    )
+    .await;
+
+    test.codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "summarize @notes.txt".into(),
+            }],
+        })
+        .await?;
+
+    let manifest = wait_for_event_match(&test.codex, |ev| match ev {
+        EventMsg::ContextManifest(manifest) => Some(manifest.clone()),
+        _ => None,
+    })
+    .await;
+    wait_for_event(&test.codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let kinds: Vec<ContextComponentKind> = manifest
+        .components
+        .iter()
+        .map(|component| component.kind)
+        .collect();
+    assert_eq!(kinds.first(), Some(&ContextComponentKind::BaseInstructions));
+    assert!(kinds.contains(&ContextComponentKind::EnvironmentContext));
+    assert!(kinds.contains(&ContextComponentKind::ConversationHistory));
+    assert!(kinds.contains(&ContextComponentKind::Tools));
+    let attached: Vec<&str> = manifest
+        .components
+        .iter()
+        .filter(|component| component.kind == ContextComponentKind::AttachedFile)
+        .map(|component| component.label.as_str())
+        .collect();
+    assert_eq!(attached, vec!["notes.txt"]);
+    assert_eq!(
+        manifest.total_tokens,
+        manifest
+            .components
+            .iter()
+            .map(|component| component.tokens)
+            .sum::<i64>()
+    );
+
+    Ok(())
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn assistant_message_item_is_emitted() -> anyhow::Result<()> {
     skip_if_no_network!(Ok(()));
@@ -416,3 +477,50 @@ async fn reasoning_raw_content_delta_respects_flag() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn hidden_reasoning_display_suppresses_reasoning_events() -> anyhow::Result<()> {
+    skip_if_no_network!(Ok(()));
+
+    let server = start_mock_server().await;
+
+    let TestCodex { codex, .. } = test_codex().build(&server).await?;
+
+    let stream = sse(vec![
+        ev_response_created("resp-1"),
+        ev_reasoning_item_added("reasoning-1", &[""]),
+        ev_reasoning_summary_text_delta("step one"),
+        ev_reasoning_item("reasoning-1", &["step one"], &[]),
+        ev_completed("resp-1"),
+    ]);
+    mount_sse_once(&server, stream).await;
+
+    codex
+        .submit(Op::SetReasoningDisplay {
+            display: ReasoningDisplay::Hidden,
+        })
+        .await?;
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "reason quietly".into(),
+            }],
+        })
+        .await?;
+
+    let mut saw_reasoning_event = false;
+    wait_for_event(&codex, |ev| {
+        saw_reasoning_event |= matches!(
+            ev,
+            EventMsg::AgentReasoning(_)
+                | EventMsg::AgentReasoningDelta(_)
+                | EventMsg::ReasoningContentDelta(_)
+        );
+        matches!(ev, EventMsg::TaskComplete(_))
+    })
+    .await;
+
+    assert!(!saw_reasoning_event, "reasoning events should be hidden");
+
+    Ok(())
+}
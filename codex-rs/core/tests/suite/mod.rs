@@ -51,6 +51,7 @@ mod shell_command;
 mod shell_serialization;
 mod shell_snapshot;
 mod skills;
+mod steer;
 mod stream_error_allows_next_turn;
 mod stream_no_completed;
 mod text_encoding_fix;
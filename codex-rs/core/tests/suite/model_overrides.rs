@@ -1,10 +1,16 @@
 use codex_core::CodexAuth;
 use codex_core::ConversationManager;
+use codex_core::config::Constrained;
+use codex_core::protocol::ApprovalPresetId;
+use codex_core::protocol::AskForApproval;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::Op;
+use codex_core::protocol::SandboxPolicy;
 use codex_protocol::openai_models::ReasoningEffort;
+use codex_utils_absolute_path::AbsolutePathBuf;
 use core_test_support::load_default_config_for_test;
 use core_test_support::wait_for_event;
+use core_test_support::wait_for_event_match;
 use pretty_assertions::assert_eq;
 use tempfile::TempDir;
 
@@ -40,6 +46,7 @@ async fn override_turn_context_does_not_persist_when_config_exists() {
             model: Some("o3".to_string()),
             effort: Some(Some(ReasoningEffort::High)),
             summary: None,
+            verbosity: None,
         })
         .await
         .expect("submit override");
@@ -82,6 +89,7 @@ async fn override_turn_context_does_not_create_config_file() {
             model: Some("o3".to_string()),
             effort: Some(Some(ReasoningEffort::Medium)),
             summary: None,
+            verbosity: None,
         })
         .await
         .expect("submit override");
@@ -94,3 +102,64 @@ async fn override_turn_context_does_not_create_config_file() {
         "override should not create config.toml"
     );
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn set_approval_preset_keeps_workspace_write_roots() {
+    let codex_home = TempDir::new().unwrap();
+    let writable = TempDir::new().unwrap();
+    let sandbox = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![AbsolutePathBuf::try_from(writable.path()).unwrap()],
+        network_access: true,
+        exclude_tmpdir_env_var: false,
+        exclude_slash_tmp: false,
+    };
+    let mut config = load_default_config_for_test(&codex_home).await;
+    config.approval_policy = Constrained::allow_any(AskForApproval::UnlessTrusted);
+    config.sandbox_policy = sandbox.clone();
+
+    let conversation_manager = ConversationManager::with_models_provider(
+        CodexAuth::from_api_key("Test API Key"),
+        config.model_provider.clone(),
+    );
+    let codex = conversation_manager
+        .new_conversation(config)
+        .await
+        .expect("create conversation")
+        .conversation;
+
+    codex
+        .submit(Op::SetApprovalPreset {
+            preset: ApprovalPresetId::FullAuto,
+        })
+        .await
+        .expect("submit preset");
+    let changes = wait_for_event_match(&codex, |ev| match ev {
+        EventMsg::TurnContextChanged(changes) => Some(changes.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(
+        changes.approval_policy.map(|change| change.new),
+        Some(AskForApproval::OnRequest)
+    );
+    assert_eq!(changes.sandbox_policy, None);
+
+    codex
+        .submit(Op::SetApprovalPreset {
+            preset: ApprovalPresetId::ReadOnly,
+        })
+        .await
+        .expect("submit preset");
+    let changes = wait_for_event_match(&codex, |ev| match ev {
+        EventMsg::TurnContextChanged(changes) => Some(changes.clone()),
+        _ => None,
+    })
+    .await;
+    assert_eq!(changes.approval_policy, None);
+    assert_eq!(
+        changes
+            .sandbox_policy
+            .map(|change| (change.old, change.new)),
+        Some((sandbox, SandboxPolicy::ReadOnly))
+    );
+}
@@ -27,6 +27,8 @@ fn resume_history(
         approval_policy: config.approval_policy.value(),
         sandbox_policy: config.sandbox_policy.clone(),
         model: previous_model.to_string(),
+        model_provider: None,
+        approval_preset: None,
         effort: config.model_reasoning_effort,
         summary: config.model_reasoning_summary,
     };
@@ -0,0 +1,141 @@
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_protocol::user_input::UserInput;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_function_call;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use core_test_support::test_codex::test_codex;
+use core_test_support::wait_for_event;
+use serde_json::json;
+
+/// A steer sent while a tool runs reaches the model in the next request of the
+/// same turn, and the running tool call is not cancelled.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn steer_is_sent_with_next_request_of_running_turn() {
+    skip_if_no_network!();
+
+    let call_id = "call-steer";
+    let args = json!({
+        "command": "sleep 1 && echo slept",
+        "timeout_ms": 10_000
+    })
+    .to_string();
+    let first_body = sse(vec![
+        ev_response_created("resp-1"),
+        ev_function_call(call_id, "shell_command", &args),
+        ev_completed("resp-1"),
+    ]);
+    let second_body = sse(vec![
+        ev_response_created("resp-2"),
+        ev_assistant_message("msg-1", "switching to python"),
+        ev_completed("resp-2"),
+    ]);
+
+    let server = start_mock_server().await;
+    let response_mock = mount_sse_sequence(&server, vec![first_body, second_body]).await;
+
+    let codex = test_codex()
+        .with_model("gpt-5.1")
+        .build(&server)
+        .await
+        .unwrap()
+        .codex;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "write the script".into(),
+            }],
+        })
+        .await
+        .unwrap();
+
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::ExecCommandBegin(_))).await;
+    codex
+        .submit(Op::Steer {
+            text: "use python instead".into(),
+        })
+        .await
+        .unwrap();
+
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let requests = response_mock.requests();
+    assert_eq!(requests.len(), 2, "steer should not start a new turn");
+    let follow_up = &requests[1];
+    let output = follow_up
+        .function_call_output_text(call_id)
+        .expect("tool output in follow-up request");
+    assert!(
+        output.contains("slept"),
+        "tool call ran to completion: {output}"
+    );
+    assert!(
+        follow_up
+            .message_input_texts("user")
+            .iter()
+            .any(|text| text == "use python instead"),
+        "steer missing from follow-up request"
+    );
+}
+
+/// A steer that arrives once the turn is over is not dropped; it starts a turn
+/// of its own.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn steer_after_turn_ends_starts_new_turn() {
+    skip_if_no_network!();
+
+    let first_body = sse(vec![
+        ev_response_created("resp-1"),
+        ev_assistant_message("msg-1", "wrote it in bash"),
+        ev_completed("resp-1"),
+    ]);
+    let second_body = sse(vec![
+        ev_response_created("resp-2"),
+        ev_assistant_message("msg-2", "rewrote it in python"),
+        ev_completed("resp-2"),
+    ]);
+
+    let server = start_mock_server().await;
+    let response_mock = mount_sse_sequence(&server, vec![first_body, second_body]).await;
+
+    let codex = test_codex()
+        .with_model("gpt-5.1")
+        .build(&server)
+        .await
+        .unwrap()
+        .codex;
+
+    codex
+        .submit(Op::UserInput {
+            items: vec![UserInput::Text {
+                text: "write the script".into(),
+            }],
+        })
+        .await
+        .unwrap();
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    codex
+        .submit(Op::Steer {
+            text: "use python instead".into(),
+        })
+        .await
+        .unwrap();
+    wait_for_event(&codex, |ev| matches!(ev, EventMsg::TaskComplete(_))).await;
+
+    let requests = response_mock.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(
+        requests[1]
+            .message_input_texts("user")
+            .iter()
+            .any(|text| text == "use python instead"),
+        "steer missing from new turn"
+    );
+}
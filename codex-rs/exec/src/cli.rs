@@ -94,6 +94,9 @@ pub enum Command {
 
     /// Run a code review against the current repository.
     Review(ReviewArgs),
+
+    /// Run a workflow defined under `[workflows]` in config.toml.
+    Run(RunArgs),
 }
 
 #[derive(Parser, Debug)]
@@ -147,6 +150,18 @@ pub struct ReviewArgs {
     pub prompt: Option<String>,
 }
 
+#[derive(Parser, Debug)]
+pub struct RunArgs {
+    /// Name of the workflow.
+    #[arg(value_name = "WORKFLOW")]
+    pub workflow: String,
+
+    /// Replaces `{input}` in the workflow's prompts. If `-` is used, read
+    /// from stdin.
+    #[arg(value_name = "INPUT", value_hint = clap::ValueHint::Other)]
+    pub input: Option<String>,
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
 #[value(rename_all = "kebab-case")]
 pub enum Color {
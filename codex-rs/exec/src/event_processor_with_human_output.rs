@@ -3,7 +3,13 @@ use codex_common::elapsed::format_elapsed;
 use codex_core::config::Config;
 use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
+use codex_core::protocol::ArtifactRegisteredEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::CandidateSelectedEvent;
+use codex_core::protocol::CandidateSelector;
+use codex_core::protocol::ContextRepairedEvent;
+use codex_core::protocol::ContextTrimmedEvent;
+use codex_core::protocol::DependencyReportEvent;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
@@ -16,13 +22,18 @@ use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
 use codex_core::protocol::PatchApplyBeginEvent;
 use codex_core::protocol::PatchApplyEndEvent;
+use codex_core::protocol::ProtectedPathViolationEvent;
+use codex_core::protocol::ResponseRegeneratedEvent;
 use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::StalledEvent;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TurnAbortReason;
+use codex_core::protocol::TurnContextChangedEvent;
 use codex_core::protocol::TurnDiffEvent;
 use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchEndEvent;
+use codex_core::protocol::WorkflowStepStartedEvent;
 use codex_protocol::num_format::format_with_separators;
 use owo_colors::OwoColorize;
 use owo_colors::Style;
@@ -162,9 +173,14 @@ impl EventProcessor for EventProcessorWithHumanOutput {
     fn process_event(&mut self, event: Event) -> CodexStatus {
         let Event { id: _, msg } = event;
         match msg {
-            EventMsg::Error(ErrorEvent { message, .. }) => {
+            EventMsg::Error(ErrorEvent {
+                message, details, ..
+            }) => {
                 let prefix = "ERROR:".style(self.red);
                 ts_msg!(self, "{prefix} {message}");
+                if let Some(remediation) = details.and_then(|details| details.remediation) {
+                    eprintln!("{}", format!("hint: {remediation}").style(self.dimmed));
+                }
             }
             EventMsg::Warning(WarningEvent { message }) => {
                 ts_msg!(
@@ -473,7 +489,7 @@ impl EventProcessor for EventProcessorWithHumanOutput {
                     eprintln!("{}", line.style(self.dimmed));
                 }
             }
-            EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => {
+            EventMsg::TurnDiff(TurnDiffEvent { unified_diff, .. }) => {
                 ts_msg!(
                     self,
                     "{}",
@@ -563,13 +579,154 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             EventMsg::ContextCompacted(_) => {
                 ts_msg!(self, "context compacted");
             }
+            EventMsg::DependencyReport(DependencyReportEvent { dependencies, .. }) => {
+                ts_msg!(
+                    self,
+                    "{}",
+                    "added dependencies".style(self.magenta).style(self.italic)
+                );
+                for dependency in dependencies {
+                    let version = dependency
+                        .resolved_version
+                        .as_deref()
+                        .or(dependency.requirement.as_deref())
+                        .unwrap_or("");
+                    let details = match (&dependency.lookup_error, &dependency.license) {
+                        (Some(err), _) => format!("lookup failed: {err}"),
+                        (None, Some(license)) => license.clone(),
+                        (None, None) => "no license listed".to_string(),
+                    };
+                    eprintln!(
+                        "{} {version} ({}) {}",
+                        dependency.name.style(self.bold),
+                        dependency.ecosystem.registry(),
+                        details.style(self.dimmed)
+                    );
+                    for advisory in &dependency.advisories {
+                        eprintln!(
+                            "  {} {}",
+                            advisory.id.style(self.red),
+                            advisory.summary.as_deref().unwrap_or_default()
+                        );
+                    }
+                }
+            }
+            EventMsg::ArtifactRegistered(ArtifactRegisteredEvent { artifact, .. }) => {
+                ts_msg!(
+                    self,
+                    "{} {} ({})",
+                    "artifact".style(self.magenta).style(self.italic),
+                    artifact.title.style(self.bold),
+                    artifact.path.to_string_lossy()
+                );
+            }
+            EventMsg::TurnContextChanged(changes) => {
+                ts_msg!(
+                    self,
+                    "{} settings changed: {}",
+                    "codex".style(self.italic).style(self.magenta),
+                    describe_turn_context_changes(&changes)
+                );
+            }
+            EventMsg::ResponseRegenerated(ResponseRegeneratedEvent { model, .. }) => {
+                ts_msg!(
+                    self,
+                    "{} regenerating the last response with {model}",
+                    "codex".style(self.italic).style(self.magenta)
+                );
+            }
+            EventMsg::WorkflowStepStarted(WorkflowStepStartedEvent {
+                workflow,
+                step,
+                steps,
+                prompt,
+            }) => {
+                ts_msg!(
+                    self,
+                    "{} {workflow} step {}/{steps}",
+                    "workflow".style(self.magenta).style(self.italic),
+                    step + 1
+                );
+                ts_msg!(self, "{}", prompt.style(self.dimmed));
+            }
+            EventMsg::Stalled(StalledEvent { idle_ms, retrying }) => {
+                let action = if retrying {
+                    "retrying the request"
+                } else {
+                    "still waiting"
+                };
+                ts_msg!(
+                    self,
+                    "{} no response from the model for {}s, {action}",
+                    "warning:".style(self.yellow).style(self.bold),
+                    idle_ms / 1000
+                );
+            }
+            EventMsg::CandidateSelected(CandidateSelectedEvent {
+                index,
+                candidates,
+                selected_by,
+            }) => {
+                let selected_by = match selected_by {
+                    CandidateSelector::User => "user",
+                    CandidateSelector::Judge => "judge",
+                };
+                ts_msg!(
+                    self,
+                    "{} continuing with candidate {} of {candidates} (picked by {selected_by})",
+                    "codex".style(self.italic).style(self.magenta),
+                    index + 1
+                );
+            }
+            EventMsg::ContextRepaired(ContextRepairedEvent {
+                error,
+                mut dropped,
+                compacted,
+            }) => {
+                if compacted {
+                    dropped.push("compacted the history".to_string());
+                }
+                ts_msg!(
+                    self,
+                    "{} request rejected ({error}); retrying after: {}",
+                    "warning:".style(self.yellow).style(self.bold),
+                    dropped.join(", ")
+                );
+            }
+            EventMsg::ContextTrimmed(ContextTrimmedEvent { summary, .. }) => {
+                ts_msg!(
+                    self,
+                    "{} {summary}",
+                    "context".style(self.magenta).style(self.italic)
+                );
+            }
+            EventMsg::ProtectedPathViolation(ProtectedPathViolationEvent {
+                violations, ..
+            }) => {
+                for violation in violations {
+                    ts_msg!(
+                        self,
+                        "{} {} (`{}`)",
+                        "refused patch to protected path:"
+                            .style(self.yellow)
+                            .style(self.bold),
+                        violation.path.to_string_lossy(),
+                        violation.pattern
+                    );
+                }
+            }
             EventMsg::ShutdownComplete => return CodexStatus::Shutdown,
             EventMsg::WebSearchBegin(_)
             | EventMsg::ExecApprovalRequest(_)
             | EventMsg::ApplyPatchApprovalRequest(_)
+            | EventMsg::PatchConflict(_)
+            | EventMsg::Question(_)
+            | EventMsg::CandidatesProposed(_)
+            | EventMsg::MemoryCandidatesProposed(_)
             | EventMsg::TerminalInteraction(_)
             | EventMsg::ExecCommandOutputDelta(_)
             | EventMsg::GetHistoryEntryResponse(_)
+            | EventMsg::FileAtTurnResponse(_)
             | EventMsg::McpListToolsResponse(_)
             | EventMsg::ListCustomPromptsResponse(_)
             | EventMsg::ListSkillsResponse(_)
@@ -585,9 +742,18 @@ impl EventProcessor for EventProcessorWithHumanOutput {
             | EventMsg::AgentMessageContentDelta(_)
             | EventMsg::ReasoningContentDelta(_)
             | EventMsg::ReasoningRawContentDelta(_)
+            | EventMsg::StructuredOutputDelta(_)
             | EventMsg::SkillsUpdateAvailable
+            | EventMsg::ContextManifest(_)
+            | EventMsg::SemanticIndexUpdated(_)
+            | EventMsg::SessionSummary(_)
+            | EventMsg::WorkingDirectoryChanged(_)
             | EventMsg::UndoCompleted(_)
-            | EventMsg::UndoStarted(_) => {}
+            | EventMsg::UndoStarted(_)
+            | EventMsg::ItemAnnotated(_)
+            | EventMsg::AnnotationRemoved(_)
+            | EventMsg::TurnProgress(_)
+            | EventMsg::TurnCompletedMetrics(_) => {}
         }
         CodexStatus::Running
     }
@@ -616,6 +782,27 @@ impl EventProcessor for EventProcessorWithHumanOutput {
     }
 }
 
+fn describe_turn_context_changes(changes: &TurnContextChangedEvent) -> String {
+    let mut parts = Vec::new();
+    if let Some(change) = &changes.model {
+        parts.push(format!("model {} → {}", change.old, change.new));
+    }
+    if let Some(change) = &changes.approval_policy {
+        parts.push(format!("approvals {} → {}", change.old, change.new));
+    }
+    if let Some(change) = &changes.sandbox_policy {
+        parts.push(format!("sandbox {} → {}", change.old, change.new));
+    }
+    if let Some(change) = &changes.cwd {
+        parts.push(format!(
+            "cwd {} → {}",
+            change.old.display(),
+            change.new.display()
+        ));
+    }
+    parts.join(", ")
+}
+
 fn escape_command(command: &[String]) -> String {
     try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "))
 }
@@ -18,6 +18,7 @@ use crate::exec_events::McpToolCallItem;
 use crate::exec_events::McpToolCallItemError;
 use crate::exec_events::McpToolCallItemResult;
 use crate::exec_events::McpToolCallStatus;
+use crate::exec_events::OutputPartialEvent;
 use crate::exec_events::PatchApplyStatus;
 use crate::exec_events::PatchChangeKind;
 use crate::exec_events::ReasoningItem;
@@ -149,6 +150,12 @@ impl EventProcessorWithJsonOutput {
                 message: ev.message.clone(),
             })],
             EventMsg::PlanUpdate(ev) => self.handle_plan_update(ev),
+            EventMsg::StructuredOutputDelta(ev) => {
+                vec![ThreadEvent::OutputPartial(OutputPartialEvent {
+                    pointer: ev.pointer.clone(),
+                    value: ev.value.clone(),
+                })]
+            }
             _ => Vec::new(),
         }
     }
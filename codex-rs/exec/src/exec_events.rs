@@ -30,6 +30,10 @@ pub enum ThreadEvent {
     /// Signals that an item has reached a terminal state—either success or failure.
     #[serde(rename = "item.completed")]
     ItemCompleted(ItemCompletedEvent),
+    /// With `--output-schema`, a value of the JSON response that finished
+    /// streaming before the agent message is complete.
+    #[serde(rename = "output.partial")]
+    OutputPartial(OutputPartialEvent),
     /// Represents an unrecoverable error emitted directly by the event stream.
     #[serde(rename = "error")]
     Error(ThreadErrorEvent),
@@ -66,6 +70,13 @@ pub struct Usage {
     pub output_tokens: i64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
+pub struct OutputPartialEvent {
+    /// JSON Pointer of the value in the response, e.g. `/findings/0`.
+    pub pointer: String,
+    pub value: JsonValue,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, TS)]
 pub struct ItemStartedEvent {
     pub item: ThreadItem,
@@ -13,6 +13,7 @@ pub mod exec_events;
 pub use cli::Cli;
 pub use cli::Command;
 pub use cli::ReviewArgs;
+pub use cli::RunArgs;
 use codex_common::oss::ensure_oss_provider_ready;
 use codex_common::oss::get_default_model_for_oss_provider;
 use codex_core::AuthManager;
@@ -64,6 +65,10 @@ enum InitialOperation {
     Review {
         review_request: ReviewRequest,
     },
+    Workflow {
+        name: String,
+        input: Option<String>,
+    },
 }
 
 pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> anyhow::Result<()> {
@@ -307,6 +312,22 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             let summary = codex_core::review_prompts::user_facing_hint(&review_request.target);
             (InitialOperation::Review { review_request }, summary)
         }
+        (Some(ExecCommand::Run(run_args)), _, _) => {
+            if !config.workflows.contains_key(&run_args.workflow) {
+                anyhow::bail!(
+                    "Unknown workflow `{}`; define it under [workflows] in config.toml",
+                    run_args.workflow
+                );
+            }
+            let summary = format!("workflow {}", run_args.workflow);
+            (
+                InitialOperation::Workflow {
+                    name: run_args.workflow,
+                    input: run_args.input.map(|input| resolve_prompt(Some(input))),
+                },
+                summary,
+            )
+        }
         (Some(ExecCommand::Resume(args)), root_prompt, imgs) => {
             let prompt_arg = args
                 .prompt
@@ -426,6 +447,11 @@ pub async fn run_main(cli: Cli, codex_linux_sandbox_exe: Option<PathBuf>) -> any
             info!("Sent review request with event ID: {task_id}");
             task_id
         }
+        InitialOperation::Workflow { name, input } => {
+            let task_id = conversation.submit(Op::RunWorkflow { name, input }).await?;
+            info!("Sent workflow request with event ID: {task_id}");
+            task_id
+        }
     };
 
     // Run the loop until the task is complete.
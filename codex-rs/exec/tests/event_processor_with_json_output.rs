@@ -29,6 +29,7 @@ use codex_exec::exec_events::McpToolCallItem;
 use codex_exec::exec_events::McpToolCallItemError;
 use codex_exec::exec_events::McpToolCallItemResult;
 use codex_exec::exec_events::McpToolCallStatus;
+use codex_exec::exec_events::OutputPartialEvent;
 use codex_exec::exec_events::PatchApplyStatus;
 use codex_exec::exec_events::PatchChangeKind;
 use codex_exec::exec_events::ReasoningItem;
@@ -50,6 +51,7 @@ use codex_protocol::plan_tool::UpdatePlanArgs;
 use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::ExecCommandOutputDeltaEvent;
 use codex_protocol::protocol::ExecOutputStream;
+use codex_protocol::protocol::StructuredOutputDeltaEvent;
 use mcp_types::CallToolResult;
 use mcp_types::ContentBlock;
 use mcp_types::TextContent;
@@ -535,6 +537,28 @@ fn agent_message_produces_item_completed_agent_message() {
     );
 }
 
+#[test]
+fn structured_output_delta_produces_output_partial() {
+    let mut ep = EventProcessorWithJsonOutput::new(None);
+    let out = ep.collect_thread_events(&event(
+        "e1",
+        EventMsg::StructuredOutputDelta(StructuredOutputDeltaEvent {
+            thread_id: "thread".to_string(),
+            turn_id: "turn".to_string(),
+            item_id: "msg".to_string(),
+            pointer: "/findings/0".to_string(),
+            value: json!({"title": "Leak"}),
+        }),
+    ));
+    assert_eq!(
+        out,
+        vec![ThreadEvent::OutputPartial(OutputPartialEvent {
+            pointer: "/findings/0".to_string(),
+            value: json!({"title": "Leak"}),
+        })]
+    );
+}
+
 #[test]
 fn error_event_produces_error() {
     let mut ep = EventProcessorWithJsonOutput::new(None);
@@ -543,6 +567,7 @@ fn error_event_produces_error() {
         EventMsg::Error(codex_core::protocol::ErrorEvent {
             message: "boom".to_string(),
             codex_error_info: Some(CodexErrorInfo::Other),
+            details: None,
         }),
     ));
     assert_eq!(
@@ -602,6 +627,7 @@ fn error_followed_by_task_complete_produces_turn_failed() {
         EventMsg::Error(ErrorEvent {
             message: "boom".to_string(),
             codex_error_info: Some(CodexErrorInfo::Other),
+            details: None,
         }),
     );
     assert_eq!(
@@ -0,0 +1,150 @@
+#!/usr/bin/env python3
+"""Generate the Python and Node declarations of libcodex_ffi from
+include/codex.h, so the wrappers cannot drift from the C API.
+
+Run from anywhere: ./codex-rs/ffi/bindings/generate.py
+Pass --check to fail instead of writing when the files are out of date.
+"""
+
+import argparse
+import re
+import sys
+from pathlib import Path
+
+BINDINGS = Path(__file__).resolve().parent
+HEADER = BINDINGS.parent / "include" / "codex.h"
+PYTHON_OUT = BINDINGS / "python" / "codex_ffi" / "_native.py"
+NODE_OUT = BINDINGS / "node" / "native.js"
+
+PROTOTYPE = re.compile(r"^([\w ]+?\s*\*?)\s*(codex_\w+)\((.*?)\);", re.MULTILINE | re.DOTALL)
+OPAQUE = re.compile(r"^typedef struct (\w+) \1;", re.MULTILINE)
+
+# C type -> ctypes type. `char *` returned by the library is owned by the
+# caller, so it stays a pointer that can be passed to codex_string_free.
+CTYPES = {
+    "void": "None",
+    "const char *": "ctypes.c_char_p",
+    "char *": "ctypes.c_void_p",
+    "char **": "ctypes.POINTER(ctypes.c_void_p)",
+    "int": "ctypes.c_int",
+    "int64_t": "ctypes.c_int64",
+}
+
+GENERATED = "Generated by bindings/generate.py from include/codex.h. Do not edit."
+
+
+def normalize(c: str) -> str:
+    """`c` in `const char *` form."""
+    stars = c.count("*")
+    base = " ".join(c.replace("*", " ").split())
+    return f"{base} {'*' * stars}" if stars else base
+
+
+def param_type(param: str) -> str:
+    """The type of a named parameter such as `const char *text`."""
+    return normalize(re.sub(r"\w+\s*$", "", param))
+
+
+def parse(header: str):
+    opaque = OPAQUE.findall(header)
+    functions = []
+    for result, name, params in PROTOTYPE.findall(header):
+        params = " ".join(params.split())
+        args = [] if params == "void" else [param_type(param) for param in params.split(",")]
+        result = normalize(result)
+        functions.append((name, result, args))
+    return opaque, functions
+
+
+def ctype(c: str, opaque) -> str:
+    if c in CTYPES:
+        return CTYPES[c]
+    base = c.removeprefix("const ").rstrip(" *")
+    if base in opaque and c.endswith("*"):
+        return "ctypes.c_void_p"
+    raise SystemExit(f"no ctypes mapping for `{c}`")
+
+
+def python(opaque, functions) -> str:
+    lines = [
+        f'"""{GENERATED}"""',
+        "",
+        "import ctypes",
+        "",
+        "",
+        "def bind(lib: ctypes.CDLL) -> ctypes.CDLL:",
+        '    """Set the argument and result types of every function of `lib`."""',
+    ]
+    for name, result, args in functions:
+        argtypes = ", ".join(ctype(arg, opaque) for arg in args)
+        lines.append(f"    lib.{name}.argtypes = [{argtypes}]")
+        lines.append(f"    lib.{name}.restype = {ctype(result, opaque)}")
+    lines.append("    return lib")
+    return "\n".join(lines) + "\n"
+
+
+def koffi_prototype(name: str, result: str, args) -> str:
+    # Owned strings come back as pointers to decode and free; the error slot
+    # is an out-parameter filled in place.
+    result = "void *" if result == "char *" else result
+    params = []
+    for index, arg in enumerate(args):
+        if arg == "char **":
+            params.append(f"_Out_ void **arg{index}")
+        elif arg == "char *":
+            params.append(f"void *arg{index}")
+        else:
+            params.append(f"{arg}{'' if arg.endswith('*') else ' '}arg{index}")
+    return f"{result}{'' if result.endswith('*') else ' '}{name}({', '.join(params) or 'void'})"
+
+
+def node(opaque, functions) -> str:
+    lines = [
+        f"// {GENERATED}",
+        '"use strict";',
+        "",
+        "const PROTOTYPES = {",
+    ]
+    for name, result, args in functions:
+        lines.append(f'  {name}: "{koffi_prototype(name, result, args)}",')
+    lines += [
+        "};",
+        "",
+        "// Declare the functions of `lib`, a library loaded with koffi.load().",
+        "function bind(koffi, lib) {",
+    ]
+    for name in opaque:
+        lines.append(f'  koffi.opaque("{name}");')
+    lines += [
+        "  const functions = {};",
+        "  for (const [name, prototype] of Object.entries(PROTOTYPES)) {",
+        "    functions[name] = lib.func(prototype);",
+        "  }",
+        "  return functions;",
+        "}",
+        "",
+        "module.exports = { PROTOTYPES, bind };",
+    ]
+    return "\n".join(lines) + "\n"
+
+
+def main() -> int:
+    parser = argparse.ArgumentParser(description=__doc__)
+    parser.add_argument("--check", action="store_true")
+    options = parser.parse_args()
+
+    opaque, functions = parse(HEADER.read_text())
+    outputs = {PYTHON_OUT: python(opaque, functions), NODE_OUT: node(opaque, functions)}
+    stale = [path for path, text in outputs.items() if not path.exists() or path.read_text() != text]
+    if options.check:
+        for path in stale:
+            print(f"{path} is out of date; run {Path(__file__).name}", file=sys.stderr)
+        return 1 if stale else 0
+    for path in stale:
+        path.write_text(text := outputs[path])
+        print(f"wrote {path} ({len(text.splitlines())} lines)")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
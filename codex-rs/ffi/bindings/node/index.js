@@ -0,0 +1,208 @@
+// Embed the Codex agent in Node through libcodex_ffi.
+//
+//   const { Client } = require("@openai/codex-ffi");
+//   const client = await Client.create({ cwd: "/path/to/repo", approval_policy: "never" });
+//   const thread = await client.startThread();
+//   const { finalResponse } = await thread.run("Summarize the README");
+//   await thread.shutdown();
+//   thread.close();
+//   client.close();
+//
+// Ops and events are objects in the shape of codex-sdk's Op and Event. Calls
+// that wait on the agent run on koffi's worker threads and return promises.
+// The library is looked up in $CODEX_FFI_LIBRARY, then next to this file.
+"use strict";
+
+const path = require("node:path");
+const koffi = require("koffi");
+const { bind } = require("./native.js");
+
+class CodexError extends Error {}
+
+function libraryPath() {
+  if (process.env.CODEX_FFI_LIBRARY) {
+    return process.env.CODEX_FFI_LIBRARY;
+  }
+  const file = {
+    darwin: "libcodex_ffi.dylib",
+    win32: "codex_ffi.dll",
+  }[process.platform] ?? "libcodex_ffi.so";
+  return path.join(__dirname, file);
+}
+
+const native = bind(koffi, koffi.load(libraryPath()));
+
+// Decode a string returned by the library and free it.
+function takeString(pointer) {
+  if (!pointer) {
+    return null;
+  }
+  try {
+    return koffi.decode(pointer, "char", -1);
+  } finally {
+    native.codex_string_free(pointer);
+  }
+}
+
+function check(result, error) {
+  const message = takeString(error[0]);
+  if (message !== null) {
+    throw new CodexError(message);
+  }
+  return result;
+}
+
+// Call `name` with an error slot appended, on the calling thread.
+function call(name, ...args) {
+  const error = [null];
+  return check(native[name](...args, error), error);
+}
+
+// Call `name` with an error slot appended, on a worker thread.
+function callAsync(name, ...args) {
+  const error = [null];
+  return new Promise((resolve, reject) => {
+    native[name].async(...args, error, (err, result) => {
+      if (err) {
+        reject(err);
+        return;
+      }
+      try {
+        resolve(check(result, error));
+      } catch (e) {
+        reject(e);
+      }
+    });
+  });
+}
+
+function libraryVersion() {
+  return native.codex_version();
+}
+
+// A loaded configuration, from which threads are started.
+class Client {
+  // `options` takes the keys of codex_client_new(): codex_home, codex_exe,
+  // cwd, model, model_provider, profile, approval_policy, sandbox_mode,
+  // base_instructions, developer_instructions, writable_roots and config.
+  // Without codex_exe, `codex` must be on the PATH.
+  static async create(options = null) {
+    const json = options === null ? null : JSON.stringify(options);
+    return new Client(await callAsync("codex_client_new", json));
+  }
+
+  constructor(handle) {
+    this.handle = handle;
+  }
+
+  async startThread() {
+    return new Thread(await callAsync("codex_thread_start", this.require()));
+  }
+
+  // Continue the thread recorded in `rolloutPath`.
+  async resumeThread(rolloutPath) {
+    return new Thread(
+      await callAsync("codex_thread_resume", this.require(), rolloutPath),
+    );
+  }
+
+  // Release the client. Threads started from it keep running.
+  close() {
+    if (this.handle) {
+      native.codex_client_free(this.handle);
+      this.handle = null;
+    }
+  }
+
+  require() {
+    if (!this.handle) {
+      throw new CodexError("the client is closed");
+    }
+    return this.handle;
+  }
+}
+
+// One conversation with the agent.
+class Thread {
+  constructor(handle) {
+    this.handle = handle;
+  }
+
+  get id() {
+    return takeString(native.codex_thread_id(this.require()));
+  }
+
+  // Model, working directory, policies and rollout path of the thread.
+  get sessionConfigured() {
+    return JSON.parse(
+      takeString(call("codex_thread_session_configured", this.require())),
+    );
+  }
+
+  // Queue an op such as { type: "interrupt" }; resolves to its submission id.
+  async submit(op) {
+    return takeString(
+      await callAsync("codex_thread_submit", this.require(), JSON.stringify(op)),
+    );
+  }
+
+  // Start a turn with a text message; resolves to its submission id.
+  async send(text) {
+    return takeString(await callAsync("codex_thread_send", this.require(), text));
+  }
+
+  // The next event, or null if none arrives within `timeoutMs`.
+  async nextEvent(timeoutMs = -1) {
+    const event = takeString(
+      await callAsync("codex_thread_next_event", this.require(), timeoutMs),
+    );
+    return event === null ? null : JSON.parse(event);
+  }
+
+  // Send a text message and collect the `msg` of every event until the turn
+  // ends. Approval requests must be answered with submit() meanwhile, or
+  // avoided with an approval policy of "never".
+  async run(text) {
+    await this.send(text);
+    const events = [];
+    for (;;) {
+      const { msg } = await this.nextEvent();
+      events.push(msg);
+      switch (msg.type) {
+        case "task_complete":
+          return { finalResponse: msg.last_agent_message ?? null, events };
+        case "turn_aborted":
+          return { finalResponse: null, events };
+        case "shutdown_complete":
+          throw new CodexError("the thread shut down before the turn ended");
+      }
+    }
+  }
+
+  // Stop the running turn, if any.
+  async interrupt() {
+    await callAsync("codex_thread_interrupt", this.require());
+  }
+
+  // End the thread and wait until it has stopped.
+  async shutdown() {
+    await callAsync("codex_thread_shutdown", this.require());
+  }
+
+  // Release the thread. Shut it down first: freeing does not stop it.
+  close() {
+    if (this.handle) {
+      native.codex_thread_free(this.handle);
+      this.handle = null;
+    }
+  }
+
+  require() {
+    if (!this.handle) {
+      throw new CodexError("the thread is closed");
+    }
+    return this.handle;
+  }
+}
+
+module.exports = { Client, CodexError, Thread, libraryVersion };
@@ -0,0 +1,32 @@
+// Generated by bindings/generate.py from include/codex.h. Do not edit.
+"use strict";
+
+const PROTOTYPES = {
+  codex_version: "const char *codex_version(void)",
+  codex_client_new: "CodexClient *codex_client_new(const char *arg0, _Out_ void **arg1)",
+  codex_client_free: "void codex_client_free(CodexClient *arg0)",
+  codex_thread_start: "CodexThread *codex_thread_start(const CodexClient *arg0, _Out_ void **arg1)",
+  codex_thread_resume: "CodexThread *codex_thread_resume(const CodexClient *arg0, const char *arg1, _Out_ void **arg2)",
+  codex_thread_free: "void codex_thread_free(CodexThread *arg0)",
+  codex_thread_id: "void *codex_thread_id(const CodexThread *arg0)",
+  codex_thread_session_configured: "void *codex_thread_session_configured(const CodexThread *arg0, _Out_ void **arg1)",
+  codex_thread_submit: "void *codex_thread_submit(const CodexThread *arg0, const char *arg1, _Out_ void **arg2)",
+  codex_thread_send: "void *codex_thread_send(const CodexThread *arg0, const char *arg1, _Out_ void **arg2)",
+  codex_thread_next_event: "void *codex_thread_next_event(const CodexThread *arg0, int64_t arg1, _Out_ void **arg2)",
+  codex_thread_interrupt: "int codex_thread_interrupt(const CodexThread *arg0, _Out_ void **arg1)",
+  codex_thread_shutdown: "int codex_thread_shutdown(const CodexThread *arg0, _Out_ void **arg1)",
+  codex_string_free: "void codex_string_free(void *arg0)",
+};
+
+// Declare the functions of `lib`, a library loaded with koffi.load().
+function bind(koffi, lib) {
+  koffi.opaque("CodexClient");
+  koffi.opaque("CodexThread");
+  const functions = {};
+  for (const [name, prototype] of Object.entries(PROTOTYPES)) {
+    functions[name] = lib.func(prototype);
+  }
+  return functions;
+}
+
+module.exports = { PROTOTYPES, bind };
@@ -0,0 +1,189 @@
+"""Embed the Codex agent in Python through libcodex_ffi.
+
+    from codex_ffi import Client
+
+    with Client({"cwd": "/path/to/repo", "approval_policy": "never"}) as client:
+        with client.start_thread() as thread:
+            final_response, events = thread.run("Summarize the README")
+
+Ops and events are dicts in the shape of codex-sdk's Op and Event. The
+library is looked up in $CODEX_FFI_LIBRARY, then on the system library path.
+"""
+
+import ctypes
+import ctypes.util
+import json
+import os
+from typing import Any, Optional
+
+from ._native import bind
+
+__all__ = ["Client", "CodexError", "Thread", "library_version"]
+
+
+class CodexError(RuntimeError):
+    """A call into libcodex_ffi failed."""
+
+
+def _load() -> ctypes.CDLL:
+    path = os.environ.get("CODEX_FFI_LIBRARY") or ctypes.util.find_library("codex_ffi")
+    if path is None:
+        raise CodexError("libcodex_ffi not found; set CODEX_FFI_LIBRARY to its path")
+    return bind(ctypes.CDLL(path))
+
+
+_lib = _load()
+
+
+def _take_string(pointer: Optional[int]) -> Optional[str]:
+    """Decode a string returned by the library and free it."""
+    if not pointer:
+        return None
+    try:
+        return ctypes.string_at(pointer).decode("utf-8")
+    finally:
+        _lib.codex_string_free(pointer)
+
+
+def _call(function, *args):
+    """Call `function` with an error slot appended; raise what it reports."""
+    error = ctypes.c_void_p()
+    result = function(*args, ctypes.byref(error))
+    message = _take_string(error.value)
+    if message is not None:
+        raise CodexError(message)
+    return result
+
+
+def library_version() -> str:
+    return _lib.codex_version().decode("utf-8")
+
+
+class Client:
+    """A loaded configuration, from which threads are started.
+
+    `options` takes the keys of codex_client_new(): codex_home, codex_exe,
+    cwd, model, model_provider, profile, approval_policy, sandbox_mode,
+    base_instructions, developer_instructions, writable_roots and config.
+    Without codex_exe, `codex` must be on the PATH.
+    """
+
+    def __init__(self, options: Optional[dict] = None):
+        encoded = None if options is None else json.dumps(options).encode("utf-8")
+        self._handle = _call(_lib.codex_client_new, encoded)
+
+    def start_thread(self) -> "Thread":
+        return Thread(_call(_lib.codex_thread_start, self._require()))
+
+    def resume_thread(self, rollout_path: str) -> "Thread":
+        """Continue the thread recorded in `rollout_path`."""
+        return Thread(
+            _call(_lib.codex_thread_resume, self._require(), os.fsencode(rollout_path))
+        )
+
+    def close(self) -> None:
+        """Release the client. Threads started from it keep running."""
+        if self._handle:
+            _lib.codex_client_free(self._handle)
+            self._handle = None
+
+    def _require(self) -> int:
+        if not self._handle:
+            raise CodexError("the client is closed")
+        return self._handle
+
+    def __enter__(self) -> "Client":
+        return self
+
+    def __exit__(self, *exc) -> None:
+        self.close()
+
+    def __del__(self) -> None:
+        self.close()
+
+
+class Thread:
+    """One conversation with the agent."""
+
+    def __init__(self, handle: int):
+        self._handle = handle
+
+    @property
+    def id(self) -> str:
+        return _take_string(_lib.codex_thread_id(self._require()))
+
+    @property
+    def session_configured(self) -> dict:
+        """Model, working directory, policies and rollout path of the thread."""
+        return json.loads(
+            _take_string(_call(_lib.codex_thread_session_configured, self._require()))
+        )
+
+    def submit(self, op: dict) -> str:
+        """Queue an op such as {"type": "interrupt"}; returns its submission id."""
+        return _take_string(
+            _call(_lib.codex_thread_submit, self._require(), json.dumps(op).encode("utf-8"))
+        )
+
+    def send(self, text: str) -> str:
+        """Start a turn with a text message; returns its submission id."""
+        return _take_string(
+            _call(_lib.codex_thread_send, self._require(), text.encode("utf-8"))
+        )
+
+    def next_event(self, timeout: Optional[float] = None) -> Optional[dict]:
+        """The next event, or None if none arrives within `timeout` seconds."""
+        timeout_ms = -1 if timeout is None else int(timeout * 1000)
+        event = _take_string(
+            _call(_lib.codex_thread_next_event, self._require(), timeout_ms)
+        )
+        return None if event is None else json.loads(event)
+
+    def run(self, text: str) -> "tuple[Optional[str], list[dict[str, Any]]]":
+        """Send a text message and collect the events until the turn ends.
+
+        Returns the agent's last message, if any, and the `msg` of every
+        event. Approval requests must be answered from another thread with
+        submit(), or avoided with an approval policy of "never".
+        """
+        self.send(text)
+        events = []
+        while True:
+            msg = self.next_event()["msg"]
+            events.append(msg)
+            if msg["type"] == "task_complete":
+                return msg.get("last_agent_message"), events
+            if msg["type"] == "turn_aborted":
+                return None, events
+            if msg["type"] == "shutdown_complete":
+                raise CodexError("the thread shut down before the turn ended")
+
+    def interrupt(self) -> None:
+        """Stop the running turn, if any."""
+        _call(_lib.codex_thread_interrupt, self._require())
+
+    def shutdown(self) -> None:
+        """End the thread and wait until it has stopped."""
+        _call(_lib.codex_thread_shutdown, self._require())
+
+    def close(self) -> None:
+        """Release the thread. Shut it down first: freeing does not stop it."""
+        if self._handle:
+            _lib.codex_thread_free(self._handle)
+            self._handle = None
+
+    def _require(self) -> int:
+        if not self._handle:
+            raise CodexError("the thread is closed")
+        return self._handle
+
+    def __enter__(self) -> "Thread":
+        return self
+
+    def __exit__(self, *exc) -> None:
+        if self._handle:
+            self.shutdown()
+        self.close()
+
+    def __del__(self) -> None:
+        self.close()
@@ -0,0 +1,36 @@
+"""Generated by bindings/generate.py from include/codex.h. Do not edit."""
+
+import ctypes
+
+
+def bind(lib: ctypes.CDLL) -> ctypes.CDLL:
+    """Set the argument and result types of every function of `lib`."""
+    lib.codex_version.argtypes = []
+    lib.codex_version.restype = ctypes.c_char_p
+    lib.codex_client_new.argtypes = [ctypes.c_char_p, ctypes.POINTER(ctypes.c_void_p)]
+    lib.codex_client_new.restype = ctypes.c_void_p
+    lib.codex_client_free.argtypes = [ctypes.c_void_p]
+    lib.codex_client_free.restype = None
+    lib.codex_thread_start.argtypes = [ctypes.c_void_p, ctypes.POINTER(ctypes.c_void_p)]
+    lib.codex_thread_start.restype = ctypes.c_void_p
+    lib.codex_thread_resume.argtypes = [ctypes.c_void_p, ctypes.c_char_p, ctypes.POINTER(ctypes.c_void_p)]
+    lib.codex_thread_resume.restype = ctypes.c_void_p
+    lib.codex_thread_free.argtypes = [ctypes.c_void_p]
+    lib.codex_thread_free.restype = None
+    lib.codex_thread_id.argtypes = [ctypes.c_void_p]
+    lib.codex_thread_id.restype = ctypes.c_void_p
+    lib.codex_thread_session_configured.argtypes = [ctypes.c_void_p, ctypes.POINTER(ctypes.c_void_p)]
+    lib.codex_thread_session_configured.restype = ctypes.c_void_p
+    lib.codex_thread_submit.argtypes = [ctypes.c_void_p, ctypes.c_char_p, ctypes.POINTER(ctypes.c_void_p)]
+    lib.codex_thread_submit.restype = ctypes.c_void_p
+    lib.codex_thread_send.argtypes = [ctypes.c_void_p, ctypes.c_char_p, ctypes.POINTER(ctypes.c_void_p)]
+    lib.codex_thread_send.restype = ctypes.c_void_p
+    lib.codex_thread_next_event.argtypes = [ctypes.c_void_p, ctypes.c_int64, ctypes.POINTER(ctypes.c_void_p)]
+    lib.codex_thread_next_event.restype = ctypes.c_void_p
+    lib.codex_thread_interrupt.argtypes = [ctypes.c_void_p, ctypes.POINTER(ctypes.c_void_p)]
+    lib.codex_thread_interrupt.restype = ctypes.c_int
+    lib.codex_thread_shutdown.argtypes = [ctypes.c_void_p, ctypes.POINTER(ctypes.c_void_p)]
+    lib.codex_thread_shutdown.restype = ctypes.c_int
+    lib.codex_string_free.argtypes = [ctypes.c_void_p]
+    lib.codex_string_free.restype = None
+    return lib
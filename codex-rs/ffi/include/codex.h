@@ -0,0 +1,81 @@
+/*
+ * C API of libcodex_ffi: embed the Codex agent in another program.
+ *
+ * Options, ops and events are UTF-8 JSON in the shape of the codex-sdk crate's
+ * Op and Event types (see codex-rs/sdk). Functions that can fail take `char **error` last;
+ * on failure they return NULL or -1 and store a message there unless it is
+ * NULL. Every `char *` returned, errors included, belongs to the caller and
+ * is released with codex_string_free(). Calls block; handles may be shared
+ * between threads but not freed while a call on them is running.
+ *
+ * The Python and Node wrappers in ../bindings are generated from this file
+ * by ../bindings/generate.py; run it after changing a declaration.
+ */
+
+#ifndef CODEX_FFI_H
+#define CODEX_FFI_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef struct CodexClient CodexClient;
+typedef struct CodexThread CodexThread;
+
+/* Version of the library. Static; do not free. */
+const char *codex_version(void);
+
+/* Load the configuration and create a client. `options_json` is NULL or an
+ * object with any of codex_home, codex_exe, cwd, model, model_provider,
+ * profile, approval_policy, sandbox_mode, base_instructions,
+ * developer_instructions, writable_roots and config (config.toml keys as
+ * dotted paths). codex_exe is the `codex` executable the agent runs to apply
+ * patches and enter the sandbox; without it `codex` must be on the PATH. */
+CodexClient *codex_client_new(const char *options_json, char **error);
+
+/* Release a client. Threads started from it keep running. */
+void codex_client_free(CodexClient *client);
+
+/* Start a new thread. */
+CodexThread *codex_thread_start(const CodexClient *client, char **error);
+
+/* Continue the thread recorded in `rollout_path`. */
+CodexThread *codex_thread_resume(const CodexClient *client, const char *rollout_path, char **error);
+
+/* Release a thread. Call codex_thread_shutdown() first: freeing only
+ * releases the handle, and the agent keeps running until it is shut down. */
+void codex_thread_free(CodexThread *thread);
+
+/* Id of the thread. */
+char *codex_thread_id(const CodexThread *thread);
+
+/* The settings the thread started with, as JSON. */
+char *codex_thread_session_configured(const CodexThread *thread, char **error);
+
+/* Queue an op given as JSON, e.g. {"type":"interrupt"}; returns its
+ * submission id. */
+char *codex_thread_submit(const CodexThread *thread, const char *op_json, char **error);
+
+/* Start a turn with a text message; returns its submission id. */
+char *codex_thread_send(const CodexThread *thread, const char *text, char **error);
+
+/* Wait up to `timeout_ms` (forever when negative) for the next event, as
+ * JSON. Returns NULL without an error on timeout. */
+char *codex_thread_next_event(const CodexThread *thread, int64_t timeout_ms, char **error);
+
+/* Stop the running turn, if any. Returns 0, or -1 on failure. */
+int codex_thread_interrupt(const CodexThread *thread, char **error);
+
+/* End the thread and wait until it has stopped. Returns 0, or -1. */
+int codex_thread_shutdown(const CodexThread *thread, char **error);
+
+/* Release a string returned by this library. */
+void codex_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* CODEX_FFI_H */
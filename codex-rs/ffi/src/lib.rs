@@ -0,0 +1,461 @@
+//! C ABI over [`codex_sdk`], so that programs in other languages can embed
+//! the Codex agent instead of running the CLI. `include/codex.h` declares
+//! these functions; `bindings/` holds the Python and Node wrappers generated
+//! from it.
+//!
+//! Conventions shared by every function:
+//!
+//! - Structured values cross the boundary as UTF-8 JSON: client options,
+//!   [`codex_sdk::Op`]s going in and [`codex_sdk::Event`]s coming out, in
+//!   the shape their serde derives give them.
+//! - Functions that can fail take a `char **error` last. On failure they
+//!   return `NULL` or `-1` and, when `error` is not `NULL`, store a message
+//!   in it. Every `char *` returned, including errors, is owned by the caller
+//!   and must be released with [`codex_string_free`].
+//! - Calls block the calling thread; the agent runs on a runtime owned by
+//!   the library. Handles may be used from several threads at once, e.g. one
+//!   thread polling events while another submits approvals, but must not be
+//!   freed while a call on them is in progress.
+
+mod options;
+
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::ffi::c_char;
+use std::ffi::c_int;
+use std::panic::AssertUnwindSafe;
+use std::panic::catch_unwind;
+use std::ptr;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use codex_sdk::Codex;
+use codex_sdk::Op;
+use codex_sdk::Thread;
+use tokio::runtime::Runtime;
+
+use crate::options::ClientOptions;
+
+/// Runtime shared by every client; threads keep running on it between calls.
+static RUNTIME: LazyLock<std::io::Result<Runtime>> = LazyLock::new(|| {
+    tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .thread_name("codex-ffi")
+        .build()
+});
+
+/// A loaded configuration, from which threads are started.
+pub struct CodexClient {
+    codex: Codex,
+}
+
+/// One conversation with the agent.
+pub struct CodexThread {
+    thread: Thread,
+}
+
+/// Version of the library, as a static string that must not be freed.
+#[unsafe(no_mangle)]
+pub extern "C" fn codex_version() -> *const c_char {
+    concat!(env!("CARGO_PKG_VERSION"), "\0").as_ptr().cast()
+}
+
+/// Load the configuration and create a client. `options_json` is `NULL` or
+/// an object with any of `codex_home`, `codex_exe`, `cwd`, `model`,
+/// `model_provider`, `profile`, `approval_policy`, `sandbox_mode`,
+/// `base_instructions`, `developer_instructions`, `writable_roots` and
+/// `config` (`config.toml` keys as dotted paths). Without `codex_exe`,
+/// `codex` must be on the `PATH`. Release the client with
+/// [`codex_client_free`].
+///
+/// # Safety
+///
+/// `options_json` must be `NULL` or a NUL-terminated string, and `error`
+/// `NULL` or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_client_new(
+    options_json: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut CodexClient {
+    // SAFETY: the caller upholds the contract above.
+    unsafe {
+        guard(error, ptr::null_mut(), || {
+            let options = if options_json.is_null() {
+                ClientOptions::default()
+            } else {
+                ClientOptions::parse(str_arg(options_json, "options_json")?)
+                    .map_err(|err| format!("invalid options: {err}"))?
+            };
+            let codex = run(options.builder().build())?;
+            Ok(Box::into_raw(Box::new(CodexClient { codex })))
+        })
+    }
+}
+
+/// Release a client. Threads started from it keep running.
+///
+/// # Safety
+///
+/// `client` must be `NULL` or returned by [`codex_client_new`] and not yet
+/// freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_client_free(client: *mut CodexClient) {
+    if !client.is_null() {
+        // SAFETY: the caller hands back ownership of the box.
+        drop(unsafe { Box::from_raw(client) });
+    }
+}
+
+/// Start a new thread. Release it with [`codex_thread_free`].
+///
+/// # Safety
+///
+/// `client` must be a live client and `error` `NULL` or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_thread_start(
+    client: *const CodexClient,
+    error: *mut *mut c_char,
+) -> *mut CodexThread {
+    // SAFETY: the caller upholds the contract above.
+    unsafe {
+        guard(error, ptr::null_mut(), || {
+            let client = handle(client, "client")?;
+            let thread = run(client.codex.start_thread())?;
+            Ok(Box::into_raw(Box::new(CodexThread { thread })))
+        })
+    }
+}
+
+/// Continue the thread recorded in `rollout_path`, as found in the
+/// [`codex_thread_session_configured`] settings of the thread. Release it with
+/// [`codex_thread_free`].
+///
+/// # Safety
+///
+/// `client` must be a live client, `rollout_path` a NUL-terminated string
+/// and `error` `NULL` or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_thread_resume(
+    client: *const CodexClient,
+    rollout_path: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut CodexThread {
+    // SAFETY: the caller upholds the contract above.
+    unsafe {
+        guard(error, ptr::null_mut(), || {
+            let client = handle(client, "client")?;
+            let rollout_path = str_arg(rollout_path, "rollout_path")?;
+            let thread = run(client.codex.resume_thread(rollout_path))?;
+            Ok(Box::into_raw(Box::new(CodexThread { thread })))
+        })
+    }
+}
+
+/// Release a thread. Call [`codex_thread_shutdown`] first: the client still
+/// holds the conversation, so a thread that is only freed keeps running.
+///
+/// # Safety
+///
+/// `thread` must be `NULL` or returned by [`codex_thread_start`] or
+/// [`codex_thread_resume`] and not yet freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_thread_free(thread: *mut CodexThread) {
+    if !thread.is_null() {
+        // SAFETY: the caller hands back ownership of the box.
+        drop(unsafe { Box::from_raw(thread) });
+    }
+}
+
+/// Id of the thread, or `NULL` when `thread` is `NULL`.
+///
+/// # Safety
+///
+/// `thread` must be `NULL` or a live thread.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_thread_id(thread: *const CodexThread) -> *mut c_char {
+    // SAFETY: the caller upholds the contract above.
+    match unsafe { thread.as_ref() } {
+        Some(thread) => into_c_string(thread.thread.id().to_string()),
+        None => ptr::null_mut(),
+    }
+}
+
+/// The settings the thread started with, as JSON: its id, model, working
+/// directory, policies and rollout path ([`codex_sdk::SessionConfigured`]).
+///
+/// # Safety
+///
+/// `thread` must be a live thread and `error` `NULL` or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_thread_session_configured(
+    thread: *const CodexThread,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    // SAFETY: the caller upholds the contract above.
+    unsafe {
+        guard(error, ptr::null_mut(), || {
+            let thread = handle(thread, "thread")?;
+            let json = serde_json::to_string(thread.thread.session_configured())
+                .map_err(|err| err.to_string())?;
+            Ok(into_c_string(json))
+        })
+    }
+}
+
+/// Queue an op, given as JSON such as
+/// `{"type":"exec_approval","id":"...","decision":"approved"}`, and return
+/// its submission id, which the events answering it carry.
+///
+/// # Safety
+///
+/// `thread` must be a live thread, `op_json` a NUL-terminated string and
+/// `error` `NULL` or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_thread_submit(
+    thread: *const CodexThread,
+    op_json: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    // SAFETY: the caller upholds the contract above.
+    unsafe {
+        guard(error, ptr::null_mut(), || {
+            let thread = handle(thread, "thread")?;
+            let op: Op = serde_json::from_str(str_arg(op_json, "op_json")?)
+                .map_err(|err| format!("invalid op: {err}"))?;
+            let id = run(thread.thread.submit(op))?;
+            Ok(into_c_string(id))
+        })
+    }
+}
+
+/// Start a turn with a text message and return its submission id.
+///
+/// # Safety
+///
+/// `thread` must be a live thread, `text` a NUL-terminated string and
+/// `error` `NULL` or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_thread_send(
+    thread: *const CodexThread,
+    text: *const c_char,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    // SAFETY: the caller upholds the contract above.
+    unsafe {
+        guard(error, ptr::null_mut(), || {
+            let thread = handle(thread, "thread")?;
+            let text = str_arg(text, "text")?;
+            let id = run(thread.thread.send(text))?;
+            Ok(into_c_string(id))
+        })
+    }
+}
+
+/// Wait up to `timeout_ms` milliseconds, or indefinitely when it is
+/// negative, for the next event of the thread and return it as JSON
+/// (`{"id":...,"msg":{"type":...}}`). Returns `NULL` without an error when
+/// the wait times out; an error means the thread has stopped.
+///
+/// # Safety
+///
+/// `thread` must be a live thread and `error` `NULL` or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_thread_next_event(
+    thread: *const CodexThread,
+    timeout_ms: i64,
+    error: *mut *mut c_char,
+) -> *mut c_char {
+    // SAFETY: the caller upholds the contract above.
+    unsafe {
+        guard(error, ptr::null_mut(), || {
+            let thread = handle(thread, "thread")?;
+            let timeout = u64::try_from(timeout_ms).ok().map(Duration::from_millis);
+            let event = run(async {
+                let next = thread.thread.next_event();
+                match timeout {
+                    Some(timeout) => match tokio::time::timeout(timeout, next).await {
+                        Ok(event) => event.map(Some),
+                        Err(_) => Ok(None),
+                    },
+                    None => next.await.map(Some),
+                }
+            })?;
+            let Some(event) = event else {
+                return Ok(ptr::null_mut());
+            };
+            let json = serde_json::to_string(&event).map_err(|err| err.to_string())?;
+            Ok(into_c_string(json))
+        })
+    }
+}
+
+/// Stop the running turn, if any. Returns 0, or -1 on failure.
+///
+/// # Safety
+///
+/// `thread` must be a live thread and `error` `NULL` or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_thread_interrupt(
+    thread: *const CodexThread,
+    error: *mut *mut c_char,
+) -> c_int {
+    // SAFETY: the caller upholds the contract above.
+    unsafe {
+        guard(error, -1, || {
+            let thread = handle(thread, "thread")?;
+            run(thread.thread.interrupt())?;
+            Ok(0)
+        })
+    }
+}
+
+/// End the thread and wait until it has stopped, discarding the events it
+/// emits meanwhile. Returns 0, or -1 on failure.
+///
+/// # Safety
+///
+/// `thread` must be a live thread and `error` `NULL` or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_thread_shutdown(
+    thread: *const CodexThread,
+    error: *mut *mut c_char,
+) -> c_int {
+    // SAFETY: the caller upholds the contract above.
+    unsafe {
+        guard(error, -1, || {
+            let thread = handle(thread, "thread")?;
+            run(thread.thread.shutdown())?;
+            Ok(0)
+        })
+    }
+}
+
+/// Release a string returned by this library.
+///
+/// # Safety
+///
+/// `string` must be `NULL` or a string returned by this library and not yet
+/// freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn codex_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        // SAFETY: the caller hands back ownership of the string.
+        drop(unsafe { CString::from_raw(string) });
+    }
+}
+
+/// Run `call`, returning `failed` and storing the message in `error` when it
+/// fails or panics, since neither may cross the C ABI.
+///
+/// # Safety
+///
+/// `error` must be `NULL` or valid for writes.
+unsafe fn guard<T>(
+    error: *mut *mut c_char,
+    failed: T,
+    call: impl FnOnce() -> Result<T, String>,
+) -> T {
+    let message = match catch_unwind(AssertUnwindSafe(call)) {
+        Ok(Ok(value)) => return value,
+        Ok(Err(message)) => message,
+        Err(panic) => match panic.downcast_ref::<&str>() {
+            Some(message) => format!("codex_ffi panicked: {message}"),
+            None => match panic.downcast_ref::<String>() {
+                Some(message) => format!("codex_ffi panicked: {message}"),
+                None => "codex_ffi panicked".to_string(),
+            },
+        },
+    };
+    if !error.is_null() {
+        // SAFETY: the caller guarantees `error` is valid for writes.
+        unsafe { *error = into_c_string(message) };
+    }
+    failed
+}
+
+/// Block on `future` on the shared runtime.
+fn run<T>(future: impl Future<Output = codex_sdk::Result<T>>) -> Result<T, String> {
+    match RUNTIME.as_ref() {
+        Ok(runtime) => runtime.block_on(future).map_err(|err| err.to_string()),
+        Err(err) => Err(format!("failed to start the Codex runtime: {err}")),
+    }
+}
+
+/// # Safety
+///
+/// `pointer` must be `NULL` or point to a live `T`.
+unsafe fn handle<'a, T>(pointer: *const T, name: &str) -> Result<&'a T, String> {
+    // SAFETY: the caller guarantees `pointer` is `NULL` or live.
+    unsafe { pointer.as_ref() }.ok_or_else(|| format!("`{name}` is NULL"))
+}
+
+/// # Safety
+///
+/// `pointer` must be `NULL` or a NUL-terminated string that outlives `'a`.
+unsafe fn str_arg<'a>(pointer: *const c_char, name: &str) -> Result<&'a str, String> {
+    if pointer.is_null() {
+        return Err(format!("`{name}` is NULL"));
+    }
+    // SAFETY: the caller guarantees `pointer` is NUL-terminated.
+    unsafe { CStr::from_ptr(pointer) }
+        .to_str()
+        .map_err(|err| format!("`{name}` is not UTF-8: {err}"))
+}
+
+fn into_c_string(string: String) -> *mut c_char {
+    // Errors may quote input with NUL bytes in it; JSON escapes them.
+    match CString::new(string.replace('\0', "")) {
+        Ok(string) => string.into_raw(),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn header_declares_every_exported_function() {
+        let header = include_str!("../include/codex.h");
+        let exports: Vec<&str> = include_str!("lib.rs")
+            .lines()
+            .filter_map(|line| line.split("extern \"C\" fn ").nth(1))
+            .filter_map(|rest| rest.split('(').next())
+            .filter(|name| name.starts_with("codex_"))
+            .collect();
+
+        assert_eq!(exports.len(), 14);
+        for name in exports {
+            assert!(
+                header.contains(&format!(" {name}(")) || header.contains(&format!("*{name}(")),
+                "include/codex.h does not declare {name}"
+            );
+        }
+    }
+
+    #[test]
+    fn failures_are_reported_through_the_error_pointer() {
+        let mut error = ptr::null_mut();
+        let options = CString::new("{\"modle\": \"gpt-5.1\"}").expect("no NUL");
+
+        // SAFETY: valid arguments; NULL handles are rejected, not read.
+        unsafe {
+            assert!(codex_client_new(options.as_ptr(), &mut error).is_null());
+            let message = CStr::from_ptr(error).to_string_lossy().into_owned();
+            assert!(message.starts_with("invalid options:"), "{message}");
+            codex_string_free(error);
+
+            error = ptr::null_mut();
+            assert_eq!(codex_thread_interrupt(ptr::null(), &mut error), -1);
+            assert_eq!(CStr::from_ptr(error).to_str(), Ok("`thread` is NULL"));
+            codex_string_free(error);
+
+            assert!(codex_thread_id(ptr::null()).is_null());
+            assert_eq!(
+                CStr::from_ptr(codex_version()).to_str(),
+                Ok(env!("CARGO_PKG_VERSION"))
+            );
+        }
+    }
+}
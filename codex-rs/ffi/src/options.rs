@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use codex_sdk::AskForApproval;
+use codex_sdk::Codex;
+use codex_sdk::CodexBuilder;
+use codex_sdk::ConfigValue;
+use codex_sdk::SandboxMode;
+use serde::Deserialize;
+
+/// The JSON object `codex_client_new` takes. Every field is optional and
+/// maps onto the [`CodexBuilder`] setter of the same name.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(crate) struct ClientOptions {
+    codex_home: Option<PathBuf>,
+    codex_exe: Option<PathBuf>,
+    cwd: Option<PathBuf>,
+    model: Option<String>,
+    model_provider: Option<String>,
+    profile: Option<String>,
+    approval_policy: Option<AskForApproval>,
+    sandbox_mode: Option<SandboxMode>,
+    base_instructions: Option<String>,
+    developer_instructions: Option<String>,
+    writable_roots: Vec<PathBuf>,
+    /// `config.toml` keys, as dotted paths, with their values.
+    config: BTreeMap<String, ConfigValue>,
+}
+
+impl ClientOptions {
+    pub(crate) fn parse(json: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(json)
+    }
+
+    pub(crate) fn builder(self) -> CodexBuilder {
+        let mut builder = Codex::builder();
+        if let Some(codex_home) = self.codex_home {
+            builder = builder.codex_home(codex_home);
+        }
+        if let Some(codex_exe) = self.codex_exe {
+            builder = builder.codex_exe(codex_exe);
+        }
+        if let Some(cwd) = self.cwd {
+            builder = builder.cwd(cwd);
+        }
+        if let Some(model) = self.model {
+            builder = builder.model(model);
+        }
+        if let Some(provider) = self.model_provider {
+            builder = builder.model_provider(provider);
+        }
+        if let Some(profile) = self.profile {
+            builder = builder.profile(profile);
+        }
+        if let Some(approval_policy) = self.approval_policy {
+            builder = builder.approval_policy(approval_policy);
+        }
+        if let Some(sandbox_mode) = self.sandbox_mode {
+            builder = builder.sandbox_mode(sandbox_mode);
+        }
+        if let Some(instructions) = self.base_instructions {
+            builder = builder.base_instructions(instructions);
+        }
+        if let Some(instructions) = self.developer_instructions {
+            builder = builder.developer_instructions(instructions);
+        }
+        for root in self.writable_roots {
+            builder = builder.writable_root(root);
+        }
+        for (key, value) in self.config {
+            builder = builder.config(key, value);
+        }
+        builder
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_accepts_the_documented_fields_only() {
+        let options = ClientOptions::parse(
+            r#"{
+                "cwd": "/repo",
+                "codex_exe": "/usr/local/bin/codex",
+                "approval_policy": "never",
+                "sandbox_mode": "workspace-write",
+                "config": {"model_reasoning_effort": "high"}
+            }"#,
+        )
+        .expect("options");
+        assert_eq!(options.cwd, Some(PathBuf::from("/repo")));
+        assert_eq!(
+            options.codex_exe,
+            Some(PathBuf::from("/usr/local/bin/codex"))
+        );
+        assert_eq!(options.approval_policy, Some(AskForApproval::Never));
+        assert_eq!(options.sandbox_mode, Some(SandboxMode::WorkspaceWrite));
+        assert_eq!(
+            options.config.get("model_reasoning_effort"),
+            Some(&ConfigValue::from("high"))
+        );
+
+        assert!(ClientOptions::parse(r#"{"modle": "gpt-5.1"}"#).is_err());
+        assert!(ClientOptions::parse("{}").is_ok());
+    }
+}
@@ -0,0 +1,142 @@
+#![allow(clippy::expect_used)]
+
+use std::ffi::CStr;
+use std::ffi::CString;
+use std::ffi::c_char;
+use std::ptr;
+
+use codex_ffi::codex_client_free;
+use codex_ffi::codex_client_new;
+use codex_ffi::codex_string_free;
+use codex_ffi::codex_thread_free;
+use codex_ffi::codex_thread_id;
+use codex_ffi::codex_thread_next_event;
+use codex_ffi::codex_thread_send;
+use codex_ffi::codex_thread_session_configured;
+use codex_ffi::codex_thread_shutdown;
+use codex_ffi::codex_thread_start;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use pretty_assertions::assert_eq;
+use serde_json::Value;
+use serde_json::json;
+use tempfile::TempDir;
+
+/// Take a string returned by the library, panicking on `NULL`.
+unsafe fn take(string: *mut c_char) -> String {
+    assert!(!string.is_null(), "the library returned NULL");
+    // SAFETY: `string` came from the library and is freed right after.
+    unsafe {
+        let text = CStr::from_ptr(string).to_string_lossy().into_owned();
+        codex_string_free(string);
+        text
+    }
+}
+
+/// Panic with the message in `error`, if any.
+unsafe fn assert_no_error(error: *mut c_char) {
+    if !error.is_null() {
+        // SAFETY: `error` came from the library.
+        panic!("codex_ffi failed: {}", unsafe { take(error) });
+    }
+}
+
+fn c_string(text: &str) -> CString {
+    CString::new(text).expect("no NUL")
+}
+
+// The calls block on the library's own runtime, so the test drives them from
+// a plain thread and keeps a separate runtime for the mock server.
+#[test]
+fn a_thread_runs_a_turn_over_the_c_api() {
+    skip_if_no_network!();
+
+    let runtime = tokio::runtime::Runtime::new().expect("runtime");
+    let server = runtime.block_on(start_mock_server());
+    let response = runtime.block_on(mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "The README describes the CLI."),
+            ev_completed("resp-1"),
+        ]),
+    ));
+    let codex_home = TempDir::new().expect("codex home");
+    let cwd = TempDir::new().expect("cwd");
+    let options = json!({
+        "codex_home": codex_home.path(),
+        "codex_exe": std::env::current_exe().expect("test binary"),
+        "cwd": cwd.path(),
+        "model": "gpt-5.1",
+        "model_provider": "mock",
+        "approval_policy": "never",
+        "sandbox_mode": "read-only",
+        "config": {
+            "model_providers.mock.name": "mock",
+            "model_providers.mock.base_url": format!("{}/v1", server.uri()),
+            "model_providers.mock.wire_api": "responses",
+        },
+    })
+    .to_string();
+
+    // SAFETY: every pointer passed is live, and every string returned is
+    // freed once.
+    unsafe {
+        let mut error = ptr::null_mut();
+        let client = codex_client_new(c_string(&options).as_ptr(), &mut error);
+        assert_no_error(error);
+        let thread = codex_thread_start(client, &mut error);
+        assert_no_error(error);
+
+        let configured: Value =
+            serde_json::from_str(&take(codex_thread_session_configured(thread, &mut error)))
+                .expect("session_configured JSON");
+        assert_eq!(configured["model"], "gpt-5.1");
+        assert_eq!(
+            configured["thread_id"],
+            Value::String(take(codex_thread_id(thread)))
+        );
+
+        let submission = codex_thread_send(
+            thread,
+            c_string("Summarize the README").as_ptr(),
+            &mut error,
+        );
+        assert_no_error(error);
+        let submission = take(submission);
+        let mut types = Vec::new();
+        let complete = loop {
+            let event = codex_thread_next_event(thread, 10_000, &mut error);
+            assert_no_error(error);
+            let event: Value = serde_json::from_str(&take(event)).expect("event JSON");
+            let kind = event["msg"]["type"].as_str().expect("type").to_string();
+            types.push(kind.clone());
+            if kind == "task_complete" {
+                break event;
+            }
+        };
+        assert_eq!(complete["id"], Value::String(submission));
+        assert_eq!(
+            complete["msg"]["last_agent_message"],
+            "The README describes the CLI."
+        );
+        assert!(
+            types.iter().any(|kind| kind == "agent_message"),
+            "{types:?}"
+        );
+
+        assert_eq!(codex_thread_shutdown(thread, &mut error), 0);
+        assert_no_error(error);
+        codex_thread_free(thread);
+        codex_client_free(client);
+    }
+    assert_eq!(
+        response.single_request().message_input_texts("user").last(),
+        Some(&"Summarize the README".to_string())
+    );
+}
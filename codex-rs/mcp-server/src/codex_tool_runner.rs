@@ -9,6 +9,7 @@ use crate::exec_approval::handle_exec_approval_request;
 use crate::outgoing_message::OutgoingMessageSender;
 use crate::outgoing_message::OutgoingNotificationMeta;
 use crate::patch_approval::handle_patch_approval_request;
+use crate::question::handle_question_request;
 use codex_core::CodexConversation;
 use codex_core::ConversationManager;
 use codex_core::NewConversation;
@@ -19,6 +20,7 @@ use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
 use codex_core::protocol::ExecApprovalRequestEvent;
 use codex_core::protocol::Op;
+use codex_core::protocol::PatchConflictResolution;
 use codex_core::protocol::Submission;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_protocol::ConversationId;
@@ -177,6 +179,7 @@ async fn run_codex_tool_session_inner(
                         turn_id: _,
                         command,
                         cwd,
+                        env: _,
                         call_id,
                         reason: _,
                         proposed_execpolicy_amendment: _,
@@ -211,6 +214,48 @@ async fn run_codex_tool_session_inner(
                         // TODO: forward elicitation requests to the client?
                         continue;
                     }
+                    EventMsg::PatchConflict(conflict) => {
+                        // There is no MCP request for this yet, so keep the
+                        // concurrent edits and let the model re-read the files.
+                        if let Err(err) = codex
+                            .submit(Op::ResolvePatchConflict {
+                                id: conflict.turn_id,
+                                resolution: PatchConflictResolution::Reread,
+                            })
+                            .await
+                        {
+                            tracing::error!("failed to submit ResolvePatchConflict: {err}");
+                        }
+                        continue;
+                    }
+                    EventMsg::Question(question) => {
+                        handle_question_request(
+                            question.question,
+                            question.options,
+                            outgoing.clone(),
+                            codex.clone(),
+                            request_id.clone(),
+                            request_id_str.clone(),
+                            event.id.clone(),
+                            question.call_id,
+                        )
+                        .await;
+                        continue;
+                    }
+                    EventMsg::CandidatesProposed(proposal) => {
+                        // There is no MCP request for this either, so leave the
+                        // choice to the judge prompt.
+                        if let Err(err) = codex
+                            .submit(Op::SelectCandidate {
+                                id: proposal.turn_id,
+                                index: None,
+                            })
+                            .await
+                        {
+                            tracing::error!("failed to submit SelectCandidate: {err}");
+                        }
+                        continue;
+                    }
                     EventMsg::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
                         call_id,
                         turn_id: _,
@@ -292,7 +337,9 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::WebSearchBegin(_)
                     | EventMsg::WebSearchEnd(_)
                     | EventMsg::GetHistoryEntryResponse(_)
+                    | EventMsg::FileAtTurnResponse(_)
                     | EventMsg::PlanUpdate(_)
+                    | EventMsg::MemoryCandidatesProposed(_)
                     | EventMsg::TurnAborted(_)
                     | EventMsg::UserMessage(_)
                     | EventMsg::ShutdownComplete
@@ -304,11 +351,30 @@ async fn run_codex_tool_session_inner(
                     | EventMsg::AgentMessageContentDelta(_)
                     | EventMsg::ReasoningContentDelta(_)
                     | EventMsg::ReasoningRawContentDelta(_)
+                    | EventMsg::StructuredOutputDelta(_)
                     | EventMsg::SkillsUpdateAvailable
+                    | EventMsg::ContextManifest(_)
+                    | EventMsg::SemanticIndexUpdated(_)
+                    | EventMsg::SessionSummary(_)
+                    | EventMsg::WorkingDirectoryChanged(_)
+                    | EventMsg::TurnContextChanged(_)
                     | EventMsg::UndoStarted(_)
                     | EventMsg::UndoCompleted(_)
                     | EventMsg::ExitedReviewMode(_)
                     | EventMsg::ContextCompacted(_)
+                    | EventMsg::ProtectedPathViolation(_)
+                    | EventMsg::DependencyReport(_)
+                    | EventMsg::ArtifactRegistered(_)
+                    | EventMsg::ContextRepaired(_)
+                    | EventMsg::ContextTrimmed(_)
+                    | EventMsg::ItemAnnotated(_)
+                    | EventMsg::ResponseRegenerated(_)
+                    | EventMsg::WorkflowStepStarted(_)
+                    | EventMsg::TurnProgress(_)
+                    | EventMsg::TurnCompletedMetrics(_)
+                    | EventMsg::Stalled(_)
+                    | EventMsg::CandidateSelected(_)
+                    | EventMsg::AnnotationRemoved(_)
                     | EventMsg::DeprecationNotice(_) => {
                         // For now, we do not do anything extra for these
                         // events. Note that
@@ -26,6 +26,7 @@ mod exec_approval;
 pub(crate) mod message_processor;
 mod outgoing_message;
 mod patch_approval;
+mod question;
 
 use crate::message_processor::MessageProcessor;
 use crate::outgoing_message::OutgoingMessage;
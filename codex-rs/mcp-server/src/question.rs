@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use codex_core::CodexConversation;
+use codex_core::protocol::Op;
+use mcp_types::ElicitRequest;
+use mcp_types::ElicitRequestParamsRequestedSchema;
+use mcp_types::ElicitResult;
+use mcp_types::JSONRPCErrorError;
+use mcp_types::ModelContextProtocolRequest;
+use mcp_types::RequestId;
+use serde::Deserialize;
+use serde::Serialize;
+use serde_json::json;
+use tracing::error;
+
+use crate::codex_tool_runner::INVALID_PARAMS_ERROR_CODE;
+use crate::outgoing_message::OutgoingMessageSender;
+
+/// Conforms to [`mcp_types::ElicitRequestParams`] so that it can be used as the
+/// `params` field of an [`ElicitRequest`].
+#[derive(Debug, Deserialize, Serialize)]
+pub struct QuestionElicitRequestParams {
+    // These fields are required so that `params`
+    // conforms to ElicitRequestParams.
+    pub message: String,
+
+    #[serde(rename = "requestedSchema")]
+    pub requested_schema: ElicitRequestParamsRequestedSchema,
+
+    // These are additional fields the client can use to
+    // correlate the request with the codex tool call.
+    pub codex_elicitation: String,
+    pub codex_mcp_tool_call_id: String,
+    pub codex_event_id: String,
+    pub codex_call_id: String,
+    pub codex_options: Vec<String>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn handle_question_request(
+    question: String,
+    options: Vec<String>,
+    outgoing: Arc<OutgoingMessageSender>,
+    codex: Arc<CodexConversation>,
+    request_id: RequestId,
+    tool_call_id: String,
+    event_id: String,
+    call_id: String,
+) {
+    let description = if options.is_empty() {
+        "Your answer.".to_string()
+    } else {
+        format!("Your answer, e.g. one of: {}.", options.join(", "))
+    };
+    let params = QuestionElicitRequestParams {
+        message: question,
+        requested_schema: ElicitRequestParamsRequestedSchema {
+            r#type: "object".to_string(),
+            properties: json!({
+                "answer": {
+                    "type": "string",
+                    "description": description,
+                },
+            }),
+            required: Some(vec!["answer".to_string()]),
+        },
+        codex_elicitation: "question".to_string(),
+        codex_mcp_tool_call_id: tool_call_id,
+        codex_event_id: event_id.clone(),
+        codex_call_id: call_id,
+        codex_options: options,
+    };
+    let params_json = match serde_json::to_value(&params) {
+        Ok(value) => value,
+        Err(err) => {
+            let message = format!("Failed to serialize QuestionElicitRequestParams: {err}");
+            error!("{message}");
+
+            outgoing
+                .send_error(
+                    request_id.clone(),
+                    JSONRPCErrorError {
+                        code: INVALID_PARAMS_ERROR_CODE,
+                        message,
+                        data: None,
+                    },
+                )
+                .await;
+
+            return;
+        }
+    };
+
+    let on_response = outgoing
+        .send_request(ElicitRequest::METHOD, Some(params_json))
+        .await;
+
+    // Listen for the response on a separate task so we don't block the main agent loop.
+    tokio::spawn(async move {
+        on_question_response(event_id, on_response, codex).await;
+    });
+}
+
+async fn on_question_response(
+    event_id: String,
+    receiver: tokio::sync::oneshot::Receiver<mcp_types::Result>,
+    codex: Arc<CodexConversation>,
+) {
+    let answer = match receiver.await {
+        Ok(value) => match serde_json::from_value::<ElicitResult>(value) {
+            Ok(result) => answer_from_elicit_result(result),
+            Err(err) => {
+                error!("failed to deserialize ElicitResult: {err}");
+                None
+            }
+        },
+        Err(err) => {
+            error!("request failed: {err:?}");
+            None
+        }
+    };
+
+    if let Err(err) = codex
+        .submit(Op::AnswerQuestion {
+            id: event_id,
+            answer,
+        })
+        .await
+    {
+        error!("failed to submit AnswerQuestion: {err}");
+    }
+}
+
+/// Anything other than an accepted, non-empty answer counts as declining.
+fn answer_from_elicit_result(result: ElicitResult) -> Option<String> {
+    if result.action != "accept" {
+        return None;
+    }
+    let answer = result.content?.get("answer")?.as_str()?.trim().to_string();
+    (!answer.is_empty()).then_some(answer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn only_accepted_answers_are_forwarded() {
+        let accepted = ElicitResult {
+            action: "accept".to_string(),
+            content: Some(json!({ "answer": " staging " })),
+        };
+        assert_eq!(
+            answer_from_elicit_result(accepted),
+            Some("staging".to_string())
+        );
+
+        let declined = ElicitResult {
+            action: "decline".to_string(),
+            content: Some(json!({ "answer": "staging" })),
+        };
+        assert_eq!(answer_from_elicit_result(declined), None);
+
+        let empty = ElicitResult {
+            action: "accept".to_string(),
+            content: Some(json!({ "answer": "" })),
+        };
+        assert_eq!(answer_from_elicit_result(empty), None);
+    }
+}
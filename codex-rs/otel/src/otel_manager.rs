@@ -498,6 +498,31 @@ impl OtelManager {
         );
     }
 
+    /// Aggregate counts the user agreed to share; categories they did not
+    /// grant are left out.
+    pub fn usage_stats(
+        &self,
+        turns: Option<u64>,
+        tool_calls: Option<u64>,
+        failed_tool_calls: Option<u64>,
+        failures: Option<&str>,
+    ) {
+        tracing::event!(
+            tracing::Level::INFO,
+            event.name = "codex.usage_stats",
+            event.timestamp = %timestamp(),
+            conversation.id = %self.metadata.conversation_id,
+            app.version = %self.metadata.app_version,
+            terminal.type = %self.metadata.terminal_type,
+            model = %self.metadata.model,
+            slug = %self.metadata.slug,
+            turns = turns,
+            tool_calls = tool_calls,
+            failed_tool_calls = failed_tool_calls,
+            failures = failures,
+        );
+    }
+
     fn responses_type(event: &ResponseEvent) -> String {
         match event {
             ResponseEvent::Created => "created".into(),
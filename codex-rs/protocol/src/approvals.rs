@@ -49,6 +49,10 @@ pub struct ExecApprovalRequestEvent {
     pub command: Vec<String>,
     /// The command's working directory.
     pub cwd: PathBuf,
+    /// Environment variables the call sets on top of the session's shell
+    /// environment.
+    #[serde(default)]
+    pub env: HashMap<String, String>,
     /// Optional human-readable reason for the approval (e.g. retry without sandbox).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reason: Option<String>,
@@ -93,3 +97,52 @@ pub struct ApplyPatchApprovalRequestEvent {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub grant_root: Option<PathBuf>,
 }
+
+/// Sent before `apply_patch` writes to files that changed on disk since the
+/// model last read or edited them. The turn waits for
+/// `Op::ResolvePatchConflict`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct PatchConflictEvent {
+    /// Responses API call id for the associated patch apply call.
+    pub call_id: String,
+    /// Turn ID that this patch belongs to.
+    pub turn_id: String,
+    pub conflicts: Vec<PatchConflict>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct PatchConflict {
+    pub path: PathBuf,
+    /// Unified diff from the content the model last saw to the content on
+    /// disk, or `None` when the file was deleted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub diff: Option<String>,
+}
+
+/// How to proceed after a [`PatchConflictEvent`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum PatchConflictResolution {
+    /// Skip the patch and tell the model what changed so it re-reads the files.
+    #[default]
+    Reread,
+    /// Apply the patch over the concurrent edits.
+    Force,
+    /// Skip the patch and abort the turn.
+    Abort,
+}
+
+/// Sent when the model calls the `ask_user` tool. The turn waits for
+/// `Op::AnswerQuestion`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct QuestionEvent {
+    /// Responses API call id for the associated `ask_user` call.
+    pub call_id: String,
+    /// Turn ID that this question belongs to.
+    pub turn_id: String,
+    pub question: String,
+    /// Suggested answers. When empty the question expects free text; when
+    /// present the user may still answer in their own words.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+}
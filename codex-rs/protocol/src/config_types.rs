@@ -21,6 +21,53 @@ pub enum ReasoningSummary {
     None,
 }
 
+/// Controls which reasoning content is streamed to clients while a turn runs.
+#[derive(
+    Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq, Eq, Display, JsonSchema, TS,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ReasoningDisplay {
+    /// Stream reasoning summaries.
+    #[default]
+    Summary,
+    /// Stream raw reasoning content when the provider emits it.
+    Raw,
+    /// Do not stream any reasoning.
+    Hidden,
+}
+
+impl ReasoningDisplay {
+    /// Derive the display mode from the `hide_agent_reasoning` and
+    /// `show_raw_agent_reasoning` config flags.
+    pub fn from_flags(hide_agent_reasoning: bool, show_raw_agent_reasoning: bool) -> Self {
+        if hide_agent_reasoning {
+            ReasoningDisplay::Hidden
+        } else if show_raw_agent_reasoning {
+            ReasoningDisplay::Raw
+        } else {
+            ReasoningDisplay::Summary
+        }
+    }
+
+    /// Next mode when cycling through the available modes.
+    pub fn next(self) -> Self {
+        match self {
+            ReasoningDisplay::Summary => ReasoningDisplay::Raw,
+            ReasoningDisplay::Raw => ReasoningDisplay::Hidden,
+            ReasoningDisplay::Hidden => ReasoningDisplay::Summary,
+        }
+    }
+
+    pub fn hides_reasoning(self) -> bool {
+        matches!(self, ReasoningDisplay::Hidden)
+    }
+
+    pub fn shows_raw_reasoning(self) -> bool {
+        matches!(self, ReasoningDisplay::Raw)
+    }
+}
+
 /// Controls output length/detail on GPT-5 models via the Responses API.
 /// Serialized with lowercase values to match the OpenAI API.
 #[derive(
@@ -7,6 +7,7 @@ use serde::Deserialize;
 use serde::Deserializer;
 use serde::Serialize;
 use serde::ser::Serializer;
+use strum_macros::Display;
 use ts_rs::TS;
 
 use crate::user_input::UserInput;
@@ -337,12 +338,42 @@ impl From<Vec<UserInput>> for ResponseInputItem {
     }
 }
 
+/// Risk level the model declares for a shell command.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Serialize,
+    Deserialize,
+    Display,
+    JsonSchema,
+    TS,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum CommandRisk {
+    /// Only inspects files or system state.
+    ReadOnly,
+    /// Changes files in the workspace in ways that are easy to undo.
+    Write,
+    /// Deletes data, rewrites history, or changes state outside the workspace.
+    Destructive,
+}
+
 /// If the `name` of a `ResponseItem::FunctionCall` is either `container.exec`
 /// or `shell`, the `arguments` field should deserialize to this struct.
 #[derive(Deserialize, Debug, Clone, PartialEq, JsonSchema, TS)]
 pub struct ShellToolCallParams {
     pub command: Vec<String>,
     pub workdir: Option<String>,
+    /// Variables set on top of the session's shell environment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub env: Option<HashMap<String, String>>,
 
     /// This is the maximum time in milliseconds that the command is allowed to run.
     #[serde(alias = "timeout")]
@@ -352,6 +383,14 @@ pub struct ShellToolCallParams {
     pub sandbox_permissions: Option<SandboxPermissions>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub justification: Option<String>,
+    /// What the command is meant to accomplish, in the model's words.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub intent: Option<String>,
+    /// How much the model expects the command to change.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub risk: Option<CommandRisk>,
 }
 
 /// If the `name` of a `ResponseItem::FunctionCall` is `shell_command`, the
@@ -360,6 +399,10 @@ pub struct ShellToolCallParams {
 pub struct ShellCommandToolCallParams {
     pub command: String,
     pub workdir: Option<String>,
+    /// Variables set on top of the session's shell environment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub env: Option<HashMap<String, String>>,
 
     /// Whether to run the shell with login shell semantics
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -372,6 +415,14 @@ pub struct ShellCommandToolCallParams {
     pub sandbox_permissions: Option<SandboxPermissions>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub justification: Option<String>,
+    /// What the command is meant to accomplish, in the model's words.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub intent: Option<String>,
+    /// How much the model expects the command to change.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub risk: Option<CommandRisk>,
 }
 
 /// Responses API compatible content items that can be returned by a tool call.
@@ -763,9 +814,12 @@ mod tests {
             ShellToolCallParams {
                 command: vec!["ls".to_string(), "-l".to_string()],
                 workdir: Some("/tmp".to_string()),
+                env: None,
                 timeout_ms: Some(1000),
                 sandbox_permissions: None,
                 justification: None,
+                intent: None,
+                risk: None,
             },
             params
         );
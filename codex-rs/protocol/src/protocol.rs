@@ -3,6 +3,7 @@
 //! Uses a SQ (Submission Queue) / EQ (Event Queue) pattern to asynchronously communicate
 //! between user and agent.
 
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::fmt;
 use std::path::Path;
@@ -12,7 +13,10 @@ use std::time::Duration;
 
 use crate::ConversationId;
 use crate::approvals::ElicitationRequestEvent;
+use crate::config_types::ReasoningDisplay;
 use crate::config_types::ReasoningSummary as ReasoningSummaryConfig;
+use crate::config_types::SandboxMode;
+use crate::config_types::Verbosity as VerbosityConfig;
 use crate::custom_prompts::CustomPrompt;
 use crate::items::TurnItem;
 use crate::message_history::HistoryEntry;
@@ -42,6 +46,10 @@ pub use crate::approvals::ApplyPatchApprovalRequestEvent;
 pub use crate::approvals::ElicitationAction;
 pub use crate::approvals::ExecApprovalRequestEvent;
 pub use crate::approvals::ExecPolicyAmendment;
+pub use crate::approvals::PatchConflict;
+pub use crate::approvals::PatchConflictEvent;
+pub use crate::approvals::PatchConflictResolution;
+pub use crate::approvals::QuestionEvent;
 
 /// Open/close tags for special user-input blocks. Used across crates to avoid
 /// duplicated hardcoded strings.
@@ -106,6 +114,13 @@ pub enum Op {
         final_output_json_schema: Option<Value>,
     },
 
+    /// Steer the running turn with a short user message. The message is added
+    /// to the conversation before the next model request of the turn; tool
+    /// calls already in progress keep running. When no turn is running, or
+    /// the running one has already made its last request, the message starts
+    /// a new turn like `Op::UserInput`.
+    Steer { text: String },
+
     /// Override parts of the persistent turn context for subsequent turns.
     ///
     /// All fields are optional; when omitted, the existing value is preserved.
@@ -139,8 +154,42 @@ pub enum Op {
         /// Updated reasoning summary preference (honored only for reasoning-capable models).
         #[serde(skip_serializing_if = "Option::is_none")]
         summary: Option<ReasoningSummaryConfig>,
+
+        /// Updated output verbosity (honored only for models that support
+        /// `text.verbosity`).
+        ///
+        /// Use `Some(Some(_))` to set a specific verbosity, `Some(None)` to
+        /// fall back to the model default, or `None` to leave the existing
+        /// value unchanged.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        verbosity: Option<Option<VerbosityConfig>>,
     },
 
+    /// Change which reasoning content is streamed to clients for the rest of
+    /// the session. Takes effect immediately, including for an in-flight turn.
+    SetReasoningDisplay { display: ReasoningDisplay },
+
+    /// Replace the process-wide log filter with `RUST_LOG`-style directives,
+    /// e.g. `codex_core=info,codex_core::exec=warn`. Reply is delivered via
+    /// `EventMsg::BackgroundEvent`, or `EventMsg::Warning` if the filter could
+    /// not be applied.
+    SetLogFilter { filter: String },
+
+    /// Change the working directory used by subsequent turns. Relative paths
+    /// resolve against the current working directory. The directory must lie
+    /// within the writable roots the session started with unless the sandbox
+    /// allows writing anywhere. Reply is delivered via
+    /// `EventMsg::WorkingDirectoryChanged`, or `EventMsg::Error` if the
+    /// directory was rejected.
+    ChangeDirectory { path: PathBuf },
+
+    /// Switch to a named approval preset for subsequent turns. Sets both the
+    /// approval policy and the sandbox policy; a workspace-write sandbox that
+    /// is already in effect keeps its writable roots and network setting.
+    /// Reply is delivered via `EventMsg::TurnContextChanged`, or
+    /// `EventMsg::Error` if the approval policy is not allowed.
+    SetApprovalPreset { preset: ApprovalPresetId },
+
     /// Approve a command execution
     ExecApproval {
         /// The id of the submission we are approving
@@ -157,6 +206,38 @@ pub enum Op {
         decision: ReviewDecision,
     },
 
+    /// Answer a `EventMsg::PatchConflict` raised before applying a patch.
+    ResolvePatchConflict {
+        /// The id of the submission whose patch is waiting.
+        id: String,
+        resolution: PatchConflictResolution,
+    },
+
+    /// Answer a `EventMsg::Question` asked by the model.
+    AnswerQuestion {
+        /// The id of the submission whose question is being answered.
+        id: String,
+        /// The user's answer, or `None` when they declined to answer.
+        answer: Option<String>,
+    },
+
+    /// Pick one of the candidates from an `EventMsg::CandidatesProposed`.
+    SelectCandidate {
+        /// The id of the submission whose candidates are being chosen from.
+        id: String,
+        /// Zero-based index of the chosen candidate, or `None` to let the
+        /// judge prompt decide.
+        index: Option<usize>,
+    },
+
+    /// Answer an `EventMsg::MemoryCandidatesProposed`.
+    ReviewMemories {
+        /// The id of the turn the memories were extracted from.
+        id: String,
+        /// Zero-based indexes of the candidates to save; empty saves none.
+        accepted: Vec<usize>,
+    },
+
     /// Resolve an MCP elicitation request.
     ResolveElicitation {
         /// Name of the MCP server that issued the request.
@@ -179,6 +260,31 @@ pub enum Op {
     /// Request a single history entry identified by `log_id` + `offset`.
     GetHistoryEntryRequest { offset: usize, log_id: u64 },
 
+    /// Request the contents a file had after an earlier turn of this
+    /// session. Reply is delivered via `EventMsg::FileAtTurnResponse`.
+    GetFileAtTurn {
+        /// Absolute, or relative to the session's working directory.
+        path: PathBuf,
+        /// 1-based turn number; 0 is the start of the session.
+        turn: usize,
+    },
+
+    /// Attach an annotation to a message of this conversation. Reply is
+    /// delivered via `EventMsg::ItemAnnotated`.
+    AnnotateItem {
+        /// Zero-based position of the message among the conversation's
+        /// `UserMessage` and `AgentMessage` events, including those replayed
+        /// on resume.
+        message_index: usize,
+        kind: AnnotationKind,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        note: Option<String>,
+    },
+
+    /// Remove an annotation added with `Op::AnnotateItem`. Reply is
+    /// delivered via `EventMsg::AnnotationRemoved`.
+    RemoveAnnotation { annotation_id: String },
+
     /// Request the list of MCP tools available across all configured servers.
     /// Reply is delivered via `EventMsg::McpListToolsResponse`.
     ListMcpTools,
@@ -204,9 +310,49 @@ pub enum Op {
     /// to generate a summary which will be returned as an AgentMessage event.
     Compact,
 
+    /// Summarize the whole session as Markdown for a pull request
+    /// description: problem, approach, changes by file, testing, and
+    /// follow-ups. Reply is delivered via `EventMsg::SessionSummary`. Unlike
+    /// `Op::Compact`, the conversation history is left as it is.
+    SummarizeSession,
+
     /// Request Codex to undo a turn (turn are stacked so it is the same effect as CMD + Z).
     Undo,
 
+    /// Discard everything after the last user message and run that message
+    /// again. Replies with `EventMsg::ResponseRegenerated` before the new
+    /// turn starts. The discarded response stays in the rollout but is no
+    /// longer part of the model's history. Files it changed are not restored.
+    Regenerate {
+        /// Model slug to use from now on. When omitted, the current model is kept.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+
+        /// Reasoning effort to use from now on; same semantics as in
+        /// `Op::OverrideTurnContext`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        effort: Option<Option<ReasoningEffortConfig>>,
+    },
+
+    /// Run the steps of a workflow from the `[workflows]` config table as
+    /// one task. Each step starts with `EventMsg::WorkflowStepStarted`.
+    RunWorkflow {
+        /// Name of the workflow.
+        name: String,
+        /// Replaces `{input}` in the prompts of the workflow's steps.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        input: Option<String>,
+    },
+
+    /// Build or incrementally update the local semantic search index for the
+    /// session's repository. Reply is delivered via
+    /// `EventMsg::SemanticIndexUpdated`.
+    UpdateSemanticIndex {
+        /// When true, discard the existing index and embed every file again.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        rebuild: bool,
+    },
+
     /// Request a code review from the agent.
     Review { review_request: ReviewRequest },
 
@@ -335,6 +481,74 @@ pub enum SandboxPolicy {
     },
 }
 
+/// Named pairings of an approval policy and a sandbox policy, from the most
+/// to the least restrictive.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Display, JsonSchema, TS,
+)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum ApprovalPresetId {
+    /// Read anything; ask before editing files or running commands that
+    /// write.
+    ReadOnly,
+    /// Workspace-write sandbox; ask before edits and before commands that
+    /// are not known to be safe.
+    AutoEdit,
+    /// Workspace-write sandbox; the model asks only when it needs to leave
+    /// the sandbox. Same as `--full-auto`.
+    FullAuto,
+    /// No sandbox and no approvals. Same as `--yolo`.
+    Yolo,
+}
+
+impl ApprovalPresetId {
+    pub const ALL: [ApprovalPresetId; 4] = [
+        ApprovalPresetId::ReadOnly,
+        ApprovalPresetId::AutoEdit,
+        ApprovalPresetId::FullAuto,
+        ApprovalPresetId::Yolo,
+    ];
+
+    pub fn approval_policy(self) -> AskForApproval {
+        match self {
+            ApprovalPresetId::ReadOnly | ApprovalPresetId::FullAuto => AskForApproval::OnRequest,
+            ApprovalPresetId::AutoEdit => AskForApproval::UnlessTrusted,
+            ApprovalPresetId::Yolo => AskForApproval::Never,
+        }
+    }
+
+    pub fn sandbox_policy(self) -> SandboxPolicy {
+        match self {
+            ApprovalPresetId::ReadOnly => SandboxPolicy::new_read_only_policy(),
+            ApprovalPresetId::AutoEdit | ApprovalPresetId::FullAuto => {
+                SandboxPolicy::new_workspace_write_policy()
+            }
+            ApprovalPresetId::Yolo => SandboxPolicy::DangerFullAccess,
+        }
+    }
+
+    /// The preset that `approval` and `sandbox` amount to, if any. Any
+    /// workspace-write sandbox matches, whatever its writable roots.
+    pub fn matching(approval: AskForApproval, sandbox: &SandboxPolicy) -> Option<Self> {
+        Self::ALL.into_iter().find(|preset| {
+            preset.approval_policy() == approval
+                && matches!(
+                    (preset.sandbox_policy(), sandbox),
+                    (SandboxPolicy::ReadOnly, SandboxPolicy::ReadOnly)
+                        | (
+                            SandboxPolicy::DangerFullAccess,
+                            SandboxPolicy::DangerFullAccess
+                        )
+                        | (
+                            SandboxPolicy::WorkspaceWrite { .. },
+                            SandboxPolicy::WorkspaceWrite { .. }
+                        )
+                )
+        })
+    }
+}
+
 /// A writable root path accompanied by a list of subpaths that should remain
 /// read‑only even when the root is writable. This is primarily used to ensure
 /// that folders containing files that could be modified to escalate the
@@ -538,6 +752,10 @@ pub enum EventMsg {
     /// Conversation history was compacted (either automatically or manually).
     ContextCompacted(ContextCompactedEvent),
 
+    /// Breakdown of every context component included in the next model
+    /// request, with approximate token counts.
+    ContextManifest(ContextManifestEvent),
+
     /// Agent has started a task
     TaskStarted(TaskStartedEvent),
 
@@ -608,6 +826,66 @@ pub enum EventMsg {
 
     ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent),
 
+    /// A patch targets files that changed since the model last read them.
+    PatchConflict(PatchConflictEvent),
+
+    /// The model asked the user a question and waits for the answer.
+    Question(QuestionEvent),
+
+    /// Several responses were sampled for this turn and the user is asked to
+    /// pick one before any of their tool calls run.
+    CandidatesProposed(CandidatesProposedEvent),
+
+    /// One of the sampled responses was chosen and the turn continues with it.
+    CandidateSelected(CandidateSelectedEvent),
+
+    /// Memories were extracted from a finished turn and wait for the user
+    /// before they are saved.
+    MemoryCandidatesProposed(MemoryCandidatesProposedEvent),
+
+    /// A patch was refused because it touches protected paths.
+    ProtectedPathViolation(ProtectedPathViolationEvent),
+
+    /// Packages the model added to dependency manifests during a turn.
+    DependencyReport(DependencyReportEvent),
+
+    /// The model registered a file as an output of the session.
+    ArtifactRegistered(ArtifactRegisteredEvent),
+
+    /// The provider rejected a request, and the turn was retried after
+    /// repairing the request or compacting the history.
+    ContextRepaired(ContextRepairedEvent),
+
+    /// Content was removed from what the model sees: tool output was
+    /// truncated, the history was compacted, or `[history_filter]` left
+    /// items out of a request.
+    ContextTrimmed(ContextTrimmedEvent),
+
+    /// The last response was discarded and its user message is about to run
+    /// again.
+    ResponseRegenerated(ResponseRegeneratedEvent),
+
+    /// A step of a workflow started by `Op::RunWorkflow` is about to run.
+    WorkflowStepStarted(WorkflowStepStartedEvent),
+
+    /// How far the running turn has come, sent after each tool call.
+    TurnProgress(TurnProgressEvent),
+
+    /// Totals for the turn that just finished, sent right before
+    /// `TaskComplete`.
+    TurnCompletedMetrics(TurnCompletedMetricsEvent),
+
+    /// The model has sent nothing for a while in the running turn. Clients
+    /// can keep waiting, send `Op::Interrupt`, or interrupt and send
+    /// `Op::Regenerate` to run the turn again.
+    Stalled(StalledEvent),
+
+    /// A client attached an annotation to a message.
+    ItemAnnotated(ItemAnnotatedEvent),
+
+    /// A client removed an annotation.
+    AnnotationRemoved(AnnotationRemovedEvent),
+
     /// Notification advising the user that something they are using has been
     /// deprecated and should be phased out.
     DeprecationNotice(DeprecationNoticeEvent),
@@ -618,6 +896,19 @@ pub enum EventMsg {
 
     UndoCompleted(UndoCompletedEvent),
 
+    /// Result of an `Op::UpdateSemanticIndex` request.
+    SemanticIndexUpdated(SemanticIndexUpdatedEvent),
+
+    /// Result of an `Op::SummarizeSession` request.
+    SessionSummary(SessionSummaryEvent),
+
+    /// Result of an `Op::ChangeDirectory` request.
+    WorkingDirectoryChanged(WorkingDirectoryChangedEvent),
+
+    /// The model, approval policy, sandbox policy, or working directory
+    /// changed for later turns.
+    TurnContextChanged(TurnContextChangedEvent),
+
     /// Notification that a model stream experienced an error or disconnect
     /// and the system is handling it (e.g., retrying with backoff).
     StreamError(StreamErrorEvent),
@@ -634,6 +925,9 @@ pub enum EventMsg {
     /// Response to GetHistoryEntryRequest.
     GetHistoryEntryResponse(GetHistoryEntryResponseEvent),
 
+    /// Response to `Op::GetFileAtTurn`.
+    FileAtTurnResponse(FileAtTurnResponseEvent),
+
     /// List of MCP tools available to the agent.
     McpListToolsResponse(McpListToolsResponseEvent),
 
@@ -667,6 +961,10 @@ pub enum EventMsg {
     AgentMessageContentDelta(AgentMessageContentDeltaEvent),
     ReasoningContentDelta(ReasoningContentDeltaEvent),
     ReasoningRawContentDelta(ReasoningRawContentDeltaEvent),
+
+    /// A value of a response constrained by an output schema finished
+    /// streaming, before the message is complete.
+    StructuredOutputDelta(StructuredOutputDeltaEvent),
 }
 
 /// Codex errors that we expose to clients.
@@ -698,6 +996,48 @@ pub enum CodexErrorInfo {
     Other,
 }
 
+/// Subsystem an error originated in.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum ErrorCategory {
+    /// The model provider: HTTP failures, rate limits, context window.
+    Provider,
+    /// Sandbox setup or a command the sandbox refused.
+    Sandbox,
+    /// Missing or expired credentials.
+    Auth,
+    /// Local filesystem or process errors.
+    Io,
+    /// A request the session could not act on.
+    Protocol,
+    /// A bug or unexpected state inside Codex.
+    Internal,
+}
+
+/// Machine-readable description of an error so clients can render a
+/// targeted message instead of matching on `message`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, JsonSchema, TS)]
+pub struct ErrorDetails {
+    pub category: ErrorCategory,
+    /// Stable snake_case identifier, e.g. `context_window_exceeded`.
+    pub code: String,
+    /// What the user can do about it, when there is something to do.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub remediation: Option<String>,
+}
+
+impl ErrorDetails {
+    pub fn new(category: ErrorCategory, code: impl Into<String>) -> Self {
+        Self {
+            category,
+            code: code.into(),
+            remediation: None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, TS, JsonSchema)]
 pub struct RawResponseItemEvent {
     pub item: ResponseItem,
@@ -784,6 +1124,17 @@ pub struct ReasoningRawContentDeltaEvent {
     pub content_index: i64,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, TS, JsonSchema)]
+pub struct StructuredOutputDeltaEvent {
+    pub thread_id: String,
+    pub turn_id: String,
+    pub item_id: String,
+    /// JSON Pointer of the value in the response, e.g. `/findings/0`. Every
+    /// element of an array and every member of the top-level object is sent.
+    pub pointer: String,
+    pub value: serde_json::Value,
+}
+
 impl HasLegacyEvent for ReasoningRawContentDeltaEvent {
     fn as_legacy_events(&self, _: bool) -> Vec<EventMsg> {
         vec![EventMsg::AgentReasoningRawContentDelta(
@@ -824,6 +1175,9 @@ pub struct ErrorEvent {
     pub message: String,
     #[serde(default)]
     pub codex_error_info: Option<CodexErrorInfo>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub details: Option<ErrorDetails>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -834,6 +1188,50 @@ pub struct WarningEvent {
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct ContextCompactedEvent;
 
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct ContextManifestEvent {
+    /// Components in the order they are sent to the model.
+    pub components: Vec<ContextManifestComponent>,
+    /// Sum of the approximate token counts of all components.
+    pub total_tokens: i64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+pub struct ContextManifestComponent {
+    pub kind: ContextComponentKind,
+    /// Human-readable identifier, e.g. the path of an attached file.
+    pub label: String,
+    /// Approximate number of tokens this component contributes.
+    pub tokens: i64,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+#[ts(rename_all = "snake_case")]
+pub enum ContextComponentKind {
+    BaseInstructions,
+    /// The repository's `.codex/instructions.md`, sent with the base
+    /// instructions.
+    ProjectBaseInstructions,
+    DeveloperInstructions,
+    /// User instructions from config and `AGENTS.md` files.
+    ProjectInstructions,
+    Memories,
+    EnvironmentContext,
+    RepoMap,
+    /// Tools that worked well in past sessions of the project.
+    ToolHints,
+    Skill,
+    AttachedFile,
+    /// Notice that a file the model saw was edited outside Codex.
+    ExternalEdit,
+    /// Summary produced by compacting earlier conversation history.
+    HistorySummary,
+    /// Messages, tool calls, and tool outputs exchanged so far.
+    ConversationHistory,
+    Tools,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct TaskCompleteEvent {
     pub last_agent_message: Option<String>,
@@ -1261,6 +1659,8 @@ pub struct SessionMeta {
     #[serde(default)]
     pub source: SessionSource,
     pub model_provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub environment: Option<SessionEnvironment>,
 }
 
 impl Default for SessionMeta {
@@ -1274,16 +1674,45 @@ impl Default for SessionMeta {
             instructions: None,
             source: SessionSource::default(),
             model_provider: None,
+            environment: None,
         }
     }
 }
 
+/// Snapshot of the machine a session started on: operating system, shell,
+/// sandbox mode, and versions of common developer tools.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, JsonSchema, TS)]
+pub struct SessionEnvironment {
+    pub os: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub os_version: Option<String>,
+    pub arch: String,
+    pub shell: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_mode: Option<SandboxMode>,
+    /// Tool name to version, for tools found on `PATH`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tool_versions: BTreeMap<String, String>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, TS)]
 pub struct SessionMetaLine {
     #[serde(flatten)]
     pub meta: SessionMeta,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub git: Option<GitInfo>,
+    /// Workspace roots declared alongside `cwd`, in declaration order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workspace_roots: Vec<WorkspaceRoot>,
+}
+
+/// An additional directory the session treats as part of its workspace, such
+/// as a sibling repository checked out next to `cwd`.
+#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, TS)]
+pub struct WorkspaceRoot {
+    pub path: PathBuf,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub git: Option<GitInfo>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, JsonSchema, TS)]
@@ -1321,6 +1750,12 @@ pub struct TurnContextItem {
     pub approval_policy: AskForApproval,
     pub sandbox_policy: SandboxPolicy,
     pub model: String,
+    /// Provider the turn's requests went to. Absent in older rollouts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_provider: Option<String>,
+    /// Approval preset the approval and sandbox policies match, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_preset: Option<ApprovalPresetId>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub effort: Option<ReasoningEffortConfig>,
     pub summary: ReasoningSummaryConfig,
@@ -1578,6 +2013,54 @@ pub struct UndoCompletedEvent {
     pub message: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SemanticIndexUpdatedEvent {
+    pub success: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SessionSummaryEvent {
+    /// The summary as Markdown, ready to paste into a pull request.
+    pub markdown: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct WorkingDirectoryChangedEvent {
+    /// The new working directory, as an absolute path.
+    pub cwd: PathBuf,
+}
+
+/// Session settings that changed, each with its value before and after.
+/// Settings that stayed the same are omitted.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TurnContextChangedEvent {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub model: Option<SettingChange<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub approval_policy: Option<SettingChange<AskForApproval>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub sandbox_policy: Option<SettingChange<SandboxPolicy>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub cwd: Option<SettingChange<PathBuf>>,
+}
+
+impl TurnContextChangedEvent {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct SettingChange<T> {
+    pub old: T,
+    pub new: T,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct StreamErrorEvent {
     pub message: String,
@@ -1623,9 +2106,327 @@ pub struct PatchApplyEndEvent {
     pub changes: HashMap<PathBuf, FileChange>,
 }
 
+/// Sent when `apply_patch` refuses a patch because it touches paths listed
+/// under `protected_paths` in `config.toml`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ProtectedPathViolationEvent {
+    /// Identifier for the refused patch call.
+    pub call_id: String,
+    /// Turn ID that this patch belongs to.
+    pub turn_id: String,
+    pub violations: Vec<ProtectedPathViolation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ProtectedPathViolation {
+    pub path: PathBuf,
+    /// The configured pattern that protects `path`.
+    pub pattern: String,
+    pub rule: ProtectedPathRule,
+}
+
+/// How a protected path is guarded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtectedPathRule {
+    /// Edits need explicit approval, even when approvals are otherwise skipped.
+    RequireApproval,
+    /// Edits are always refused.
+    Block,
+}
+
+/// Sent after a turn that added packages to `Cargo.toml`, `package.json`, or
+/// `requirements*.txt`, with the license and known advisories of each.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct DependencyReportEvent {
+    /// Turn ID that added the dependencies.
+    pub turn_id: String,
+    pub dependencies: Vec<AddedDependency>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct AddedDependency {
+    /// Manifest the dependency was added to.
+    pub manifest: PathBuf,
+    pub ecosystem: DependencyEcosystem,
+    pub name: String,
+    /// Version requirement as written in the manifest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub requirement: Option<String>,
+    /// Version the license and advisories were looked up for: the pinned
+    /// version if the manifest pins one, otherwise the latest release.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub resolved_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub license: Option<String>,
+    #[serde(default)]
+    pub advisories: Vec<DependencyAdvisory>,
+    /// Why the package could not be looked up, if it could not.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub lookup_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyEcosystem {
+    Cargo,
+    Npm,
+    PyPi,
+}
+
+impl DependencyEcosystem {
+    /// Registry name shown to users.
+    pub fn registry(self) -> &'static str {
+        match self {
+            DependencyEcosystem::Cargo => "crates.io",
+            DependencyEcosystem::Npm => "npm",
+            DependencyEcosystem::PyPi => "PyPI",
+        }
+    }
+}
+
+/// A published security advisory affecting a dependency.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct DependencyAdvisory {
+    /// Advisory identifier, e.g. `RUSTSEC-2023-0001` or `GHSA-xxxx-xxxx-xxxx`.
+    pub id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub summary: Option<String>,
+}
+
+/// A file the model produced for the user, such as a report, an image, or a
+/// built binary, registered so clients can list it outside the transcript.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct Artifact {
+    /// Unique within the session; the call ID of the registering tool call.
+    pub id: String,
+    /// Absolute path of the file.
+    pub path: PathBuf,
+    /// Short human-readable name.
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub description: Option<String>,
+    /// File size when it was registered.
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ArtifactRegisteredEvent {
+    /// Turn ID that registered the artifact.
+    pub turn_id: String,
+    pub artifact: Artifact,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ContextRepairedEvent {
+    /// The provider error that triggered the repair.
+    pub error: String,
+    /// What was truncated or removed from the retried request, one entry per item.
+    pub dropped: Vec<String>,
+    /// True when the history was also compacted.
+    pub compacted: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ContextTrimmedEvent {
+    pub reason: ContextTrimReason,
+    /// What was removed and why, for display.
+    pub summary: String,
+    /// Approximate tokens of content the model no longer sees.
+    pub approx_tokens: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ContextTrimReason {
+    /// A tool output exceeded the model's output limit.
+    Truncation,
+    /// The history was replaced by a summary.
+    Compaction,
+    /// `[history_filter]` left items out of a request.
+    HistoryFilter,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ResponseRegeneratedEvent {
+    /// Model that will produce the new response.
+    pub model: String,
+    /// Number of history items discarded, including the user message that is
+    /// run again.
+    pub discarded_items: usize,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct WorkflowStepStartedEvent {
+    pub workflow: String,
+    /// Zero-based index of the step.
+    pub step: usize,
+    /// Number of steps in the workflow.
+    pub steps: usize,
+    /// The step's prompt, with `{input}` replaced.
+    pub prompt: String,
+}
+
+/// Progress of a running turn. The plan fields come from the model's latest
+/// `update_plan` call and are empty until it makes one.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TurnProgressEvent {
+    /// The plan step in progress, or else the first pending one.
+    pub current_step: Option<String>,
+    pub steps_completed: usize,
+    pub steps_total: usize,
+    /// Tool calls finished so far in this turn.
+    pub tool_calls: u64,
+    /// Milliseconds since the turn started.
+    pub elapsed_ms: u64,
+}
+
+/// What a finished turn cost, so clients need not add it up from the
+/// other events.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TurnCompletedMetricsEvent {
+    /// Milliseconds from the start of the turn to its end.
+    pub duration_ms: u64,
+    /// Requests sent to the model, including retried ones.
+    pub model_calls: u64,
+    /// Finished tool calls by tool name.
+    pub tool_calls: BTreeMap<String, u64>,
+    pub input_tokens: i64,
+    pub cached_input_tokens: i64,
+    pub output_tokens: i64,
+    /// Model requests retried after the stream failed.
+    pub retries: u64,
+    /// Bytes of tool output left out of what the model saw.
+    pub truncated_output_bytes: u64,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct StalledEvent {
+    /// Milliseconds since the model last sent anything.
+    pub idle_ms: u64,
+    /// Whether Codex is about to send the stalled model request again
+    /// (`stall_detection.auto_retry`).
+    pub retrying: bool,
+}
+
+/// Sent when `best_of` sampling asks the user to choose a response. The turn
+/// waits for `Op::SelectCandidate`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct CandidatesProposedEvent {
+    /// Turn ID that these candidates belong to.
+    pub turn_id: String,
+    pub candidates: Vec<ResponseCandidate>,
+}
+
+/// Sent when memories extracted from a turn wait for `Op::ReviewMemories`.
+/// Without an answer they are all saved after `auto_accept_secs`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct MemoryCandidatesProposedEvent {
+    /// Turn ID that the memories were extracted from.
+    pub turn_id: String,
+    pub candidates: Vec<String>,
+    /// The store the accepted memories are written to.
+    pub location: PathBuf,
+    pub auto_accept_secs: u64,
+}
+
+/// One sampled response, as shown to the user before it is chosen.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ResponseCandidate {
+    /// Assistant text of the response; empty when it only calls tools.
+    pub message: String,
+    /// Tool calls the response makes, e.g. `shell: cargo test`. None of
+    /// them run until the candidate is chosen.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tool_calls: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct CandidateSelectedEvent {
+    /// Zero-based index of the chosen candidate.
+    pub index: usize,
+    /// Number of candidates that were sampled.
+    pub candidates: usize,
+    pub selected_by: CandidateSelector,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum CandidateSelector {
+    User,
+    Judge,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(rename_all = "snake_case")]
+pub enum AnnotationKind {
+    Bookmark,
+    Comment,
+    /// The user marked the message as wrong.
+    Incorrect,
+}
+
+/// A client's note on one message of the conversation.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct HistoryAnnotation {
+    pub id: String,
+    /// Zero-based position of the message among the conversation's
+    /// `UserMessage` and `AgentMessage` events.
+    pub message_index: usize,
+    pub kind: AnnotationKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct ItemAnnotatedEvent {
+    pub annotation: HistoryAnnotation,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct AnnotationRemovedEvent {
+    pub annotation_id: String,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct TurnDiffEvent {
     pub unified_diff: String,
+    /// Per-file summary of `unified_diff`, in the same order.
+    #[serde(default)]
+    pub files: Vec<TurnDiffFile>,
+}
+
+/// How one file differs from its state before the turn.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+pub struct TurnDiffFile {
+    /// Current path of the file; for deletions, the path it was removed from.
+    pub path: PathBuf,
+    pub change: TurnDiffFileChange,
+    /// Lines added and removed. Both are zero for binary files.
+    pub added_lines: u64,
+    pub removed_lines: u64,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub binary: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, JsonSchema, TS)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[ts(tag = "type")]
+pub enum TurnDiffFileChange {
+    Added,
+    Deleted,
+    Modified,
+    /// Moved from `from`, possibly with edits.
+    Renamed {
+        from: PathBuf,
+    },
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
@@ -1637,6 +2438,24 @@ pub struct GetHistoryEntryResponseEvent {
     pub entry: Option<HistoryEntry>,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
+pub struct FileAtTurnResponseEvent {
+    pub path: PathBuf,
+    pub turn: usize,
+    /// Turns finished so far in this session.
+    pub turns: usize,
+    /// The file's contents after `turn`, lossily decoded as UTF-8. `None`
+    /// when the file did not exist then or could not be reconstructed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Whether the contents came from the git checkpoint taken before the
+    /// next turn rather than from the files Codex patched.
+    pub from_checkpoint: bool,
+    /// Why the contents could not be reconstructed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, JsonSchema, TS)]
 pub struct McpListToolsResponseEvent {
     /// Fully qualified tool name -> tool definition.
@@ -1876,6 +2695,31 @@ mod tests {
         assert!(enabled.has_full_network_access());
     }
 
+    #[test]
+    fn approval_presets_match_their_own_policies() {
+        for preset in ApprovalPresetId::ALL {
+            assert_eq!(
+                ApprovalPresetId::matching(preset.approval_policy(), &preset.sandbox_policy()),
+                Some(preset)
+            );
+        }
+        let custom_roots = SandboxPolicy::WorkspaceWrite {
+            writable_roots: Vec::new(),
+            network_access: true,
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+        };
+        assert_eq!(
+            ApprovalPresetId::matching(AskForApproval::OnRequest, &custom_roots),
+            Some(ApprovalPresetId::FullAuto)
+        );
+        assert_eq!(
+            ApprovalPresetId::matching(AskForApproval::OnFailure, &SandboxPolicy::ReadOnly),
+            None
+        );
+        assert_eq!(ApprovalPresetId::AutoEdit.to_string(), "auto-edit");
+    }
+
     #[test]
     fn item_started_event_from_web_search_emits_begin_event() {
         let event = ItemStartedEvent {
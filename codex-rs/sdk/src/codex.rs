@@ -0,0 +1,194 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_core::AuthManager;
+use codex_core::ConversationManager;
+use codex_core::NewConversation;
+use codex_core::config::Config;
+use codex_core::config::ConfigBuilder;
+use codex_core::config::ConfigOverrides;
+use codex_protocol::protocol::SessionSource;
+
+use crate::AskForApproval;
+use crate::ConfigValue;
+use crate::Error;
+use crate::Result;
+use crate::SandboxMode;
+use crate::Thread;
+
+/// Entry point of the SDK: the loaded configuration, from which threads are
+/// started. Share it behind an `Arc` to start threads from several tasks;
+/// threads keep running when it is dropped.
+pub struct Codex {
+    config: Config,
+    auth_manager: Arc<AuthManager>,
+    manager: ConversationManager,
+}
+
+impl Codex {
+    pub fn builder() -> CodexBuilder {
+        CodexBuilder::default()
+    }
+
+    /// Model set in the configuration. `None` leaves the choice to the
+    /// default for the provider; [`Thread::model`] tells which one a thread
+    /// got.
+    pub fn model(&self) -> Option<&str> {
+        self.config.model.as_deref()
+    }
+
+    /// Directory new threads work in.
+    pub fn cwd(&self) -> &Path {
+        &self.config.cwd
+    }
+
+    /// Directory holding the configuration, credentials and session logs.
+    pub fn codex_home(&self) -> &Path {
+        &self.config.codex_home
+    }
+
+    /// Start a new thread.
+    pub async fn start_thread(&self) -> Result<Thread> {
+        let conversation = self.manager.new_conversation(self.config.clone()).await?;
+        Ok(Thread::new(conversation))
+    }
+
+    /// Continue the thread recorded in `rollout_path`, as returned by
+    /// [`Thread::rollout_path`].
+    pub async fn resume_thread(&self, rollout_path: impl Into<PathBuf>) -> Result<Thread> {
+        let conversation: NewConversation = self
+            .manager
+            .resume_conversation_from_rollout(
+                self.config.clone(),
+                rollout_path.into(),
+                Arc::clone(&self.auth_manager),
+            )
+            .await?;
+        Ok(Thread::new(conversation))
+    }
+}
+
+/// Settings for a [`Codex`] client. Anything left unset comes from
+/// `config.toml` in the Codex home, as for the CLI.
+#[derive(Debug, Default, Clone)]
+pub struct CodexBuilder {
+    codex_home: Option<PathBuf>,
+    codex_exe: Option<PathBuf>,
+    overrides: ConfigOverrides,
+    config: Vec<(String, ConfigValue)>,
+}
+
+impl CodexBuilder {
+    /// Directory holding `config.toml`, credentials and session logs.
+    /// Defaults to `$CODEX_HOME`, or `~/.codex`.
+    pub fn codex_home(mut self, codex_home: impl Into<PathBuf>) -> Self {
+        self.codex_home = Some(codex_home.into());
+        self
+    }
+
+    /// The `codex` executable. The agent runs it to apply patches and, on
+    /// Linux, to enter the sandbox, because it cannot re-run the embedding
+    /// program for that. Defaults to `codex` on the `PATH`.
+    pub fn codex_exe(mut self, codex_exe: impl Into<PathBuf>) -> Self {
+        self.codex_exe = Some(codex_exe.into());
+        self
+    }
+
+    /// Directory the agent works in. Defaults to the process's current
+    /// directory.
+    pub fn cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+        self.overrides.cwd = Some(cwd.into());
+        self
+    }
+
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.overrides.model = Some(model.into());
+        self
+    }
+
+    /// Key of the provider in `model_providers`, e.g. `"openai"` or `"ollama"`.
+    pub fn model_provider(mut self, provider: impl Into<String>) -> Self {
+        self.overrides.model_provider = Some(provider.into());
+        self
+    }
+
+    /// Profile of `config.toml` to apply.
+    pub fn profile(mut self, profile: impl Into<String>) -> Self {
+        self.overrides.config_profile = Some(profile.into());
+        self
+    }
+
+    pub fn approval_policy(mut self, approval_policy: AskForApproval) -> Self {
+        self.overrides.approval_policy = Some(approval_policy.into_protocol());
+        self
+    }
+
+    pub fn sandbox_mode(mut self, sandbox_mode: SandboxMode) -> Self {
+        self.overrides.sandbox_mode = Some(sandbox_mode.into_protocol());
+        self
+    }
+
+    /// Replace the model's base instructions.
+    pub fn base_instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.overrides.base_instructions = Some(instructions.into());
+        self
+    }
+
+    /// Instructions sent as a developer message at the start of each thread.
+    pub fn developer_instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.overrides.developer_instructions = Some(instructions.into());
+        self
+    }
+
+    /// Another directory the agent may write to.
+    pub fn writable_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.overrides.additional_writable_roots.push(root.into());
+        self
+    }
+
+    /// Set any `config.toml` key, like `-c key=value` on the command line.
+    /// `key` is a dotted path such as `"model_reasoning_effort"` or
+    /// `"model_providers.local.base_url"`.
+    pub fn config(mut self, key: impl Into<String>, value: impl Into<ConfigValue>) -> Self {
+        self.config.push((key.into(), value.into()));
+        self
+    }
+
+    /// Load the configuration and set up the client.
+    pub async fn build(self) -> Result<Codex> {
+        let Self {
+            codex_home,
+            codex_exe,
+            mut overrides,
+            config,
+        } = self;
+        let codex_exe = match codex_exe {
+            Some(codex_exe) => codex_exe,
+            None => which::which("codex").map_err(|_| Error::CodexExeNotFound)?,
+        };
+        overrides.codex_linux_sandbox_exe = Some(codex_exe);
+        let config = config
+            .into_iter()
+            .map(|(key, value)| (key, value.into_toml()))
+            .collect();
+        let mut builder = ConfigBuilder::default()
+            .cli_overrides(config)
+            .harness_overrides(overrides);
+        if let Some(codex_home) = codex_home {
+            builder = builder.codex_home(codex_home);
+        }
+        let config = builder.build().await.map_err(Error::Config)?;
+        let auth_manager = AuthManager::shared(
+            config.codex_home.clone(),
+            true,
+            config.cli_auth_credentials_store_mode,
+        );
+        let manager = ConversationManager::new(Arc::clone(&auth_manager), SessionSource::Exec);
+        Ok(Codex {
+            config,
+            auth_manager,
+            manager,
+        })
+    }
+}
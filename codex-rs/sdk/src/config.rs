@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+
+use codex_protocol::config_types::SandboxMode as ProtocolSandboxMode;
+use codex_protocol::protocol::AskForApproval as ProtocolAskForApproval;
+use codex_protocol::protocol::SandboxPolicy;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// When the agent asks before running a command, as `approval_policy` in
+/// `config.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum AskForApproval {
+    /// Ask before anything but known-safe read-only commands.
+    #[serde(rename = "untrusted")]
+    UnlessTrusted,
+    /// Run commands in the sandbox and ask only when one fails there.
+    OnFailure,
+    /// Let the model decide when to ask.
+    OnRequest,
+    /// Never ask; failures are reported to the model.
+    Never,
+}
+
+impl AskForApproval {
+    pub(crate) fn into_protocol(self) -> ProtocolAskForApproval {
+        match self {
+            Self::UnlessTrusted => ProtocolAskForApproval::UnlessTrusted,
+            Self::OnFailure => ProtocolAskForApproval::OnFailure,
+            Self::OnRequest => ProtocolAskForApproval::OnRequest,
+            Self::Never => ProtocolAskForApproval::Never,
+        }
+    }
+
+    pub(crate) fn from_protocol(policy: ProtocolAskForApproval) -> Self {
+        match policy {
+            ProtocolAskForApproval::UnlessTrusted => Self::UnlessTrusted,
+            ProtocolAskForApproval::OnFailure => Self::OnFailure,
+            ProtocolAskForApproval::OnRequest => Self::OnRequest,
+            ProtocolAskForApproval::Never => Self::Never,
+        }
+    }
+}
+
+/// What commands may touch, as `sandbox_mode` in `config.toml`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[non_exhaustive]
+pub enum SandboxMode {
+    /// Read anything, write nothing, no network.
+    ReadOnly,
+    /// Write inside the working directory and the writable roots.
+    WorkspaceWrite,
+    /// No restrictions.
+    DangerFullAccess,
+}
+
+impl SandboxMode {
+    pub(crate) fn into_protocol(self) -> ProtocolSandboxMode {
+        match self {
+            Self::ReadOnly => ProtocolSandboxMode::ReadOnly,
+            Self::WorkspaceWrite => ProtocolSandboxMode::WorkspaceWrite,
+            Self::DangerFullAccess => ProtocolSandboxMode::DangerFullAccess,
+        }
+    }
+
+    /// The mode `policy` was made from. An external sandbox is one Codex
+    /// does not enforce, so commands run as with full access.
+    pub(crate) fn from_policy(policy: &SandboxPolicy) -> Self {
+        match policy {
+            SandboxPolicy::ReadOnly => Self::ReadOnly,
+            SandboxPolicy::WorkspaceWrite { .. } => Self::WorkspaceWrite,
+            SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
+                Self::DangerFullAccess
+            }
+        }
+    }
+}
+
+/// Value of a `config.toml` key set with [`crate::CodexBuilder::config`].
+/// In JSON it is written as the plain value.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+#[non_exhaustive]
+pub enum ConfigValue {
+    Boolean(bool),
+    Integer(i64),
+    Float(f64),
+    String(String),
+    Array(Vec<ConfigValue>),
+    Table(BTreeMap<String, ConfigValue>),
+}
+
+impl ConfigValue {
+    pub(crate) fn into_toml(self) -> toml::Value {
+        match self {
+            Self::Boolean(value) => toml::Value::Boolean(value),
+            Self::Integer(value) => toml::Value::Integer(value),
+            Self::Float(value) => toml::Value::Float(value),
+            Self::String(value) => toml::Value::String(value),
+            Self::Array(values) => {
+                toml::Value::Array(values.into_iter().map(Self::into_toml).collect())
+            }
+            Self::Table(entries) => toml::Value::Table(
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (key, value.into_toml()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl From<bool> for ConfigValue {
+    fn from(value: bool) -> Self {
+        Self::Boolean(value)
+    }
+}
+
+impl From<i64> for ConfigValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<i32> for ConfigValue {
+    fn from(value: i32) -> Self {
+        Self::Integer(value.into())
+    }
+}
+
+impl From<u32> for ConfigValue {
+    fn from(value: u32) -> Self {
+        Self::Integer(value.into())
+    }
+}
+
+impl From<f64> for ConfigValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<&str> for ConfigValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
+
+impl From<String> for ConfigValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl<T: Into<ConfigValue>> From<Vec<T>> for ConfigValue {
+    fn from(values: Vec<T>) -> Self {
+        Self::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Into<ConfigValue>> From<BTreeMap<String, T>> for ConfigValue {
+    fn from(entries: BTreeMap<String, T>) -> Self {
+        Self::Table(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key, value.into()))
+                .collect(),
+        )
+    }
+}
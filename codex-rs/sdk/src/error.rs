@@ -0,0 +1,30 @@
+/// Errors returned by this crate. Internal error types are kept behind
+/// [`std::error::Error::source`] so they can change without breaking callers.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// The configuration could not be read or is invalid.
+    #[error("failed to load the Codex configuration: {0}")]
+    Config(#[source] std::io::Error),
+
+    /// No `codex` executable was set with [`crate::CodexBuilder::codex_exe`]
+    /// or found on the `PATH`.
+    #[error("the `codex` executable was not found on PATH; set it with CodexBuilder::codex_exe")]
+    CodexExeNotFound,
+
+    /// The agent failed to start a thread or to run a submission.
+    #[error("{0}")]
+    Agent(#[source] Box<dyn std::error::Error + Send + Sync>),
+
+    /// The thread shut down before the turn ended.
+    #[error("the thread shut down before the turn ended")]
+    ThreadClosed,
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl From<codex_core::error::CodexErr> for Error {
+    fn from(err: codex_core::error::CodexErr) -> Self {
+        Self::Agent(Box::new(err))
+    }
+}
@@ -0,0 +1,357 @@
+//! The events a [`crate::Thread`] emits.
+//!
+//! These are the SDK's own copies of the agent's protocol events. Only the
+//! events an embedder acts on are carried over; new ones are added as new
+//! variants and new fields, which `#[non_exhaustive]` keeps from breaking
+//! existing matches.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use codex_protocol::protocol as proto;
+use serde::Serialize;
+
+/// An event and the id of the submission it answers.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct Event {
+    /// The id [`crate::Thread::submit`] returned. Approvals are answered
+    /// with the id of the event that asked.
+    pub id: String,
+    pub msg: EventMsg,
+}
+
+impl Event {
+    /// `None` for the protocol events the SDK does not carry over.
+    pub(crate) fn from_protocol(event: proto::Event) -> Option<Self> {
+        Some(Self {
+            id: event.id,
+            msg: EventMsg::from_protocol(event.msg)?,
+        })
+    }
+}
+
+/// What happened. In JSON the variant is the `type` field next to the
+/// payload's fields, e.g. `{"type":"agent_message","message":"..."}`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum EventMsg {
+    /// A turn started.
+    TaskStarted(TaskStartedEvent),
+    /// A complete message from the agent.
+    AgentMessage(AgentMessageEvent),
+    /// Part of an agent message that is still streaming.
+    AgentMessageDelta(AgentMessageDeltaEvent),
+    /// A summary of the agent's reasoning.
+    AgentReasoning(AgentReasoningEvent),
+    /// The agent wants to run a command; answer with [`crate::Op::ExecApproval`].
+    ExecApprovalRequest(ExecApprovalRequestEvent),
+    /// The agent wants to edit files; answer with [`crate::Op::PatchApproval`].
+    ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent),
+    ExecCommandBegin(ExecCommandBeginEvent),
+    ExecCommandEnd(ExecCommandEndEvent),
+    PatchApplyBegin(PatchApplyBeginEvent),
+    PatchApplyEnd(PatchApplyEndEvent),
+    /// Token usage after a model response.
+    TokenCount(TokenCountEvent),
+    /// Something went wrong but the turn goes on.
+    Warning(WarningEvent),
+    /// The submission failed.
+    Error(ErrorEvent),
+    /// The turn finished.
+    TaskComplete(TaskCompleteEvent),
+    /// The turn stopped early.
+    TurnAborted(TurnAbortedEvent),
+    /// The thread has ended.
+    ShutdownComplete,
+}
+
+impl EventMsg {
+    fn from_protocol(msg: proto::EventMsg) -> Option<Self> {
+        Some(match msg {
+            proto::EventMsg::TaskStarted(event) => Self::TaskStarted(TaskStartedEvent {
+                model_context_window: event.model_context_window,
+            }),
+            proto::EventMsg::AgentMessage(event) => Self::AgentMessage(AgentMessageEvent {
+                message: event.message,
+            }),
+            proto::EventMsg::AgentMessageDelta(event) => {
+                Self::AgentMessageDelta(AgentMessageDeltaEvent { delta: event.delta })
+            }
+            proto::EventMsg::AgentReasoning(event) => {
+                Self::AgentReasoning(AgentReasoningEvent { text: event.text })
+            }
+            proto::EventMsg::ExecApprovalRequest(event) => {
+                Self::ExecApprovalRequest(ExecApprovalRequestEvent {
+                    call_id: event.call_id,
+                    command: event.command,
+                    cwd: event.cwd,
+                    reason: event.reason,
+                })
+            }
+            proto::EventMsg::ApplyPatchApprovalRequest(event) => {
+                Self::ApplyPatchApprovalRequest(ApplyPatchApprovalRequestEvent {
+                    call_id: event.call_id,
+                    changes: FileChange::map_from_protocol(event.changes),
+                    reason: event.reason,
+                })
+            }
+            proto::EventMsg::ExecCommandBegin(event) => {
+                Self::ExecCommandBegin(ExecCommandBeginEvent {
+                    call_id: event.call_id,
+                    command: event.command,
+                    cwd: event.cwd,
+                })
+            }
+            proto::EventMsg::ExecCommandEnd(event) => Self::ExecCommandEnd(ExecCommandEndEvent {
+                call_id: event.call_id,
+                exit_code: event.exit_code,
+                stdout: event.stdout,
+                stderr: event.stderr,
+                aggregated_output: event.aggregated_output,
+            }),
+            proto::EventMsg::PatchApplyBegin(event) => {
+                Self::PatchApplyBegin(PatchApplyBeginEvent {
+                    call_id: event.call_id,
+                    auto_approved: event.auto_approved,
+                    changes: FileChange::map_from_protocol(event.changes),
+                })
+            }
+            proto::EventMsg::PatchApplyEnd(event) => Self::PatchApplyEnd(PatchApplyEndEvent {
+                call_id: event.call_id,
+                success: event.success,
+                stdout: event.stdout,
+                stderr: event.stderr,
+            }),
+            // Rate-limit-only updates carry no usage.
+            proto::EventMsg::TokenCount(event) => {
+                let info = event.info?;
+                Self::TokenCount(TokenCountEvent {
+                    total: TokenUsage::from_protocol(&info.total_token_usage),
+                    last: TokenUsage::from_protocol(&info.last_token_usage),
+                    model_context_window: info.model_context_window,
+                })
+            }
+            proto::EventMsg::Warning(event) => Self::Warning(WarningEvent {
+                message: event.message,
+            }),
+            proto::EventMsg::Error(event) => Self::Error(ErrorEvent {
+                message: event.message,
+            }),
+            proto::EventMsg::TaskComplete(event) => Self::TaskComplete(TaskCompleteEvent {
+                last_agent_message: event.last_agent_message,
+            }),
+            proto::EventMsg::TurnAborted(event) => Self::TurnAborted(TurnAbortedEvent {
+                reason: TurnAbortReason::from_protocol(event.reason),
+            }),
+            proto::EventMsg::ShutdownComplete => Self::ShutdownComplete,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct TaskStartedEvent {
+    /// Tokens the model can hold, if known.
+    pub model_context_window: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct AgentMessageEvent {
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct AgentMessageDeltaEvent {
+    pub delta: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct AgentReasoningEvent {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct ExecApprovalRequestEvent {
+    /// Matches the `call_id` of the command's begin and end events.
+    pub call_id: String,
+    pub command: Vec<String>,
+    pub cwd: PathBuf,
+    /// Why the agent is asking, e.g. to leave the sandbox.
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct ApplyPatchApprovalRequestEvent {
+    /// Matches the `call_id` of the patch's begin and end events.
+    pub call_id: String,
+    pub changes: BTreeMap<PathBuf, FileChange>,
+    /// Why the agent is asking, e.g. to write outside the workspace.
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct ExecCommandBeginEvent {
+    pub call_id: String,
+    pub command: Vec<String>,
+    pub cwd: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct ExecCommandEndEvent {
+    pub call_id: String,
+    pub exit_code: i32,
+    pub stdout: String,
+    pub stderr: String,
+    /// Stdout and stderr interleaved as they were written.
+    pub aggregated_output: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct PatchApplyBeginEvent {
+    pub call_id: String,
+    /// Whether the patch was applied without asking.
+    pub auto_approved: bool,
+    pub changes: BTreeMap<PathBuf, FileChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct PatchApplyEndEvent {
+    pub call_id: String,
+    pub success: bool,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct TokenCountEvent {
+    /// Usage of the whole thread so far.
+    pub total: TokenUsage,
+    /// Usage of the last model response.
+    pub last: TokenUsage,
+    pub model_context_window: Option<i64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct WarningEvent {
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct ErrorEvent {
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct TaskCompleteEvent {
+    pub last_agent_message: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct TurnAbortedEvent {
+    pub reason: TurnAbortReason,
+}
+
+/// A change to one file.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum FileChange {
+    Add {
+        content: String,
+    },
+    Delete {
+        content: String,
+    },
+    Update {
+        unified_diff: String,
+        /// Where the file is moved to, if it is.
+        move_path: Option<PathBuf>,
+    },
+}
+
+impl FileChange {
+    fn map_from_protocol(
+        changes: impl IntoIterator<Item = (PathBuf, proto::FileChange)>,
+    ) -> BTreeMap<PathBuf, Self> {
+        changes
+            .into_iter()
+            .map(|(path, change)| {
+                let change = match change {
+                    proto::FileChange::Add { content } => Self::Add { content },
+                    proto::FileChange::Delete { content } => Self::Delete { content },
+                    proto::FileChange::Update {
+                        unified_diff,
+                        move_path,
+                    } => Self::Update {
+                        unified_diff,
+                        move_path,
+                    },
+                };
+                (path, change)
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[non_exhaustive]
+pub struct TokenUsage {
+    pub input_tokens: i64,
+    /// The part of `input_tokens` read from the prompt cache.
+    pub cached_input_tokens: i64,
+    pub output_tokens: i64,
+    /// The part of `output_tokens` spent on reasoning.
+    pub reasoning_output_tokens: i64,
+    pub total_tokens: i64,
+}
+
+impl TokenUsage {
+    fn from_protocol(usage: &proto::TokenUsage) -> Self {
+        Self {
+            input_tokens: usage.input_tokens,
+            cached_input_tokens: usage.cached_input_tokens,
+            output_tokens: usage.output_tokens,
+            reasoning_output_tokens: usage.reasoning_output_tokens,
+            total_tokens: usage.total_tokens,
+        }
+    }
+}
+
+/// Why a turn stopped early.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum TurnAbortReason {
+    /// [`crate::Op::Interrupt`] was submitted.
+    Interrupted,
+    /// New input replaced the turn.
+    Replaced,
+    ReviewEnded,
+}
+
+impl TurnAbortReason {
+    fn from_protocol(reason: proto::TurnAbortReason) -> Self {
+        match reason {
+            proto::TurnAbortReason::Interrupted => Self::Interrupted,
+            proto::TurnAbortReason::Replaced => Self::Replaced,
+            proto::TurnAbortReason::ReviewEnded => Self::ReviewEnded,
+        }
+    }
+}
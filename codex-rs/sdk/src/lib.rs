@@ -0,0 +1,57 @@
+//! Embed the Codex agent in a Rust application.
+//!
+//! This crate is the supported way to drive Codex from Rust. It wraps the
+//! internals of `codex-core` behind a small API: a [`Codex`] client loads the
+//! configuration and starts [`Thread`]s, and each thread takes [`Op`]s and
+//! yields [`Event`]s.
+//!
+//! Every type in the API belongs to this crate, so it follows semver on its
+//! own: a minor release may add enum variants and struct fields, which
+//! `#[non_exhaustive]` keeps from breaking callers, and anything else that
+//! would break them waits for a major release. Changes to the agent's
+//! internal protocol are absorbed here.
+//!
+//! Patches are applied, and on Linux the sandbox is entered, by running the
+//! `codex` executable; see [`CodexBuilder::codex_exe`].
+//!
+//! ```no_run
+//! # async fn example() -> codex_sdk::Result<()> {
+//! use codex_sdk::Codex;
+//! use codex_sdk::SandboxMode;
+//!
+//! let codex = Codex::builder()
+//!     .cwd("/path/to/repo")
+//!     .sandbox_mode(SandboxMode::ReadOnly)
+//!     .build()
+//!     .await?;
+//! let thread = codex.start_thread().await?;
+//! let turn = thread.run("Summarize the README").await?;
+//! println!("{}", turn.final_response.unwrap_or_default());
+//! # Ok(())
+//! # }
+//! ```
+
+mod codex;
+mod config;
+mod error;
+pub mod events;
+mod op;
+mod thread;
+
+pub use codex::Codex;
+pub use codex::CodexBuilder;
+pub use error::Error;
+pub use error::Result;
+pub use thread::Thread;
+pub use thread::TurnOutput;
+
+pub use config::AskForApproval;
+pub use config::ConfigValue;
+pub use config::SandboxMode;
+pub use events::Event;
+pub use events::EventMsg;
+pub use op::Op;
+pub use op::ReviewDecision;
+pub use op::UserInput;
+pub use thread::SessionConfigured;
+pub use thread::ThreadId;
@@ -0,0 +1,112 @@
+use std::path::PathBuf;
+
+use codex_protocol::protocol::Op as ProtocolOp;
+use codex_protocol::protocol::ReviewDecision as ProtocolReviewDecision;
+use codex_protocol::user_input::UserInput as ProtocolUserInput;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A submission to a [`crate::Thread`]. In JSON the variant is the `type`
+/// field, e.g. `{"type":"exec_approval","id":"...","decision":"approved"}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Op {
+    /// Input from the user. It starts a turn, or is added to the running one.
+    UserInput { items: Vec<UserInput> },
+
+    /// Answer the [`crate::EventMsg::ExecApprovalRequest`] of the event `id`.
+    ExecApproval {
+        id: String,
+        decision: ReviewDecision,
+    },
+
+    /// Answer the [`crate::EventMsg::ApplyPatchApprovalRequest`] of the
+    /// event `id`.
+    PatchApproval {
+        id: String,
+        decision: ReviewDecision,
+    },
+
+    /// Stop the running turn.
+    Interrupt,
+
+    /// End the thread. It answers with [`crate::EventMsg::ShutdownComplete`].
+    Shutdown,
+}
+
+impl Op {
+    pub(crate) fn into_protocol(self) -> ProtocolOp {
+        match self {
+            Self::UserInput { items } => ProtocolOp::UserInput {
+                items: items.into_iter().map(UserInput::into_protocol).collect(),
+            },
+            Self::ExecApproval { id, decision } => ProtocolOp::ExecApproval {
+                id,
+                decision: decision.into_protocol(),
+            },
+            Self::PatchApproval { id, decision } => ProtocolOp::PatchApproval {
+                id,
+                decision: decision.into_protocol(),
+            },
+            Self::Interrupt => ProtocolOp::Interrupt,
+            Self::Shutdown => ProtocolOp::Shutdown,
+        }
+    }
+}
+
+/// One item of [`Op::UserInput`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum UserInput {
+    Text {
+        text: String,
+    },
+
+    /// An image given as a URL, such as a `data:` URL.
+    Image {
+        image_url: String,
+    },
+
+    /// An image file, read when the input is sent.
+    LocalImage {
+        path: PathBuf,
+    },
+}
+
+impl UserInput {
+    fn into_protocol(self) -> ProtocolUserInput {
+        match self {
+            Self::Text { text } => ProtocolUserInput::Text { text },
+            Self::Image { image_url } => ProtocolUserInput::Image { image_url },
+            Self::LocalImage { path } => ProtocolUserInput::LocalImage { path },
+        }
+    }
+}
+
+/// Answer to an approval request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum ReviewDecision {
+    /// Go ahead this once.
+    Approved,
+    /// Go ahead, and allow the same request for the rest of the thread.
+    ApprovedForSession,
+    /// Do not run it; the agent carries on without it.
+    Denied,
+    /// Do not run it, and stop the turn.
+    Abort,
+}
+
+impl ReviewDecision {
+    fn into_protocol(self) -> ProtocolReviewDecision {
+        match self {
+            Self::Approved => ProtocolReviewDecision::Approved,
+            Self::ApprovedForSession => ProtocolReviewDecision::ApprovedForSession,
+            Self::Denied => ProtocolReviewDecision::Denied,
+            Self::Abort => ProtocolReviewDecision::Abort,
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use codex_core::CodexConversation;
+use codex_core::NewConversation;
+use codex_protocol::protocol::SessionConfiguredEvent;
+use serde::Serialize;
+
+use crate::AskForApproval;
+use crate::Error;
+use crate::Event;
+use crate::EventMsg;
+use crate::Op;
+use crate::Result;
+use crate::SandboxMode;
+use crate::UserInput;
+
+/// Identifies a thread, here and in the session logs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+#[serde(transparent)]
+pub struct ThreadId(String);
+
+impl ThreadId {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ThreadId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The settings a thread started with, after `config.toml`, the profile and
+/// the builder's overrides were applied.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[non_exhaustive]
+pub struct SessionConfigured {
+    pub thread_id: ThreadId,
+    pub model: String,
+    /// Key of the provider in `model_providers`.
+    pub model_provider_id: String,
+    pub approval_policy: AskForApproval,
+    pub sandbox_mode: SandboxMode,
+    pub cwd: PathBuf,
+    /// File the thread is recorded to, for [`crate::Codex::resume_thread`].
+    pub rollout_path: PathBuf,
+}
+
+impl SessionConfigured {
+    fn from_protocol(event: SessionConfiguredEvent) -> Self {
+        Self {
+            thread_id: ThreadId(event.session_id.to_string()),
+            model: event.model,
+            model_provider_id: event.model_provider_id,
+            approval_policy: AskForApproval::from_protocol(event.approval_policy),
+            sandbox_mode: SandboxMode::from_policy(&event.sandbox_policy),
+            cwd: event.cwd,
+            rollout_path: event.rollout_path,
+        }
+    }
+}
+
+/// One conversation with the agent. Submissions are queued and run in
+/// order; every submission is answered with events from [`Thread::next_event`].
+/// Clones share the thread.
+#[derive(Clone)]
+pub struct Thread {
+    conversation: Arc<CodexConversation>,
+    session_configured: SessionConfigured,
+}
+
+/// What a turn started by [`Thread::run`] produced.
+#[derive(Debug, Clone)]
+pub struct TurnOutput {
+    /// The agent's last message, if it sent one.
+    pub final_response: Option<String>,
+    /// Every event of the turn, ending with `TaskComplete` or `TurnAborted`.
+    pub events: Vec<EventMsg>,
+}
+
+impl Thread {
+    pub(crate) fn new(conversation: NewConversation) -> Self {
+        Self {
+            conversation: conversation.conversation,
+            session_configured: SessionConfigured::from_protocol(conversation.session_configured),
+        }
+    }
+
+    pub fn id(&self) -> &ThreadId {
+        &self.session_configured.thread_id
+    }
+
+    /// Model the thread runs on.
+    pub fn model(&self) -> &str {
+        &self.session_configured.model
+    }
+
+    /// File the thread is recorded to, for [`crate::Codex::resume_thread`].
+    pub fn rollout_path(&self) -> PathBuf {
+        self.conversation.rollout_path()
+    }
+
+    /// The settings the thread started with.
+    pub fn session_configured(&self) -> &SessionConfigured {
+        &self.session_configured
+    }
+
+    /// Queue `op` and return its submission id, which the events answering
+    /// it carry.
+    pub async fn submit(&self, op: Op) -> Result<String> {
+        Ok(self.conversation.submit(op.into_protocol()).await?)
+    }
+
+    /// Wait for the next event of any submission. Agent events the SDK has
+    /// no type for are skipped.
+    pub async fn next_event(&self) -> Result<Event> {
+        loop {
+            if let Some(event) = Event::from_protocol(self.conversation.next_event().await?) {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Start a turn with a text message.
+    pub async fn send(&self, text: impl Into<String>) -> Result<String> {
+        self.submit(Op::UserInput {
+            items: vec![UserInput::Text { text: text.into() }],
+        })
+        .await
+    }
+
+    /// Send a text message and collect the events until the turn ends.
+    /// Approval requests are among the events and must be answered with
+    /// [`Op::ExecApproval`] or [`Op::PatchApproval`] from another task, or
+    /// avoided with an approval policy of `never`.
+    pub async fn run(&self, text: impl Into<String>) -> Result<TurnOutput> {
+        self.send(text).await?;
+        let mut events = Vec::new();
+        loop {
+            let event = self.next_event().await?;
+            match event.msg {
+                EventMsg::TaskComplete(complete) => {
+                    let final_response = complete.last_agent_message.clone();
+                    events.push(EventMsg::TaskComplete(complete));
+                    return Ok(TurnOutput {
+                        final_response,
+                        events,
+                    });
+                }
+                EventMsg::TurnAborted(aborted) => {
+                    events.push(EventMsg::TurnAborted(aborted));
+                    return Ok(TurnOutput {
+                        final_response: None,
+                        events,
+                    });
+                }
+                EventMsg::ShutdownComplete => return Err(Error::ThreadClosed),
+                msg => events.push(msg),
+            }
+        }
+    }
+
+    /// Stop the running turn, if any.
+    pub async fn interrupt(&self) -> Result<()> {
+        self.submit(Op::Interrupt).await.map(|_| ())
+    }
+
+    /// End the thread and wait until it has stopped.
+    pub async fn shutdown(&self) -> Result<()> {
+        self.submit(Op::Shutdown).await?;
+        loop {
+            match self.next_event().await {
+                Ok(Event {
+                    msg: EventMsg::ShutdownComplete,
+                    ..
+                }) => return Ok(()),
+                Ok(_) => {}
+                // The event channel closes once the thread is gone.
+                Err(_) => return Ok(()),
+            }
+        }
+    }
+}
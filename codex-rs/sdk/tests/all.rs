@@ -0,0 +1,15 @@
+// Single integration test binary that aggregates all test modules.
+// The submodules live in `tests/suite/`.
+use codex_arg0::arg0_dispatch;
+use ctor::ctor;
+use tempfile::TempDir;
+
+// Lets the test binary stand in for `codex` when the agent runs it to apply
+// a patch or enter the sandbox; the tests pass it as `codex_exe`.
+#[ctor]
+pub static CODEX_ALIASES_TEMP_DIR: TempDir = unsafe {
+    #[allow(clippy::unwrap_used)]
+    arg0_dispatch().unwrap()
+};
+
+mod suite;
@@ -0,0 +1,134 @@
+#![allow(clippy::expect_used)]
+
+use codex_sdk::AskForApproval;
+use codex_sdk::Codex;
+use codex_sdk::CodexBuilder;
+use codex_sdk::EventMsg;
+use codex_sdk::SandboxMode;
+use core_test_support::responses::ev_apply_patch_function_call;
+use core_test_support::responses::ev_assistant_message;
+use core_test_support::responses::ev_completed;
+use core_test_support::responses::ev_response_created;
+use core_test_support::responses::mount_sse_once;
+use core_test_support::responses::mount_sse_sequence;
+use core_test_support::responses::sse;
+use core_test_support::responses::start_mock_server;
+use core_test_support::skip_if_no_network;
+use pretty_assertions::assert_eq;
+use tempfile::TempDir;
+use wiremock::MockServer;
+
+/// A client for `server` that runs this test binary as `codex`.
+fn mock_builder(server: &MockServer, codex_home: &TempDir, cwd: &TempDir) -> CodexBuilder {
+    Codex::builder()
+        .codex_home(codex_home.path())
+        .codex_exe(std::env::current_exe().expect("test binary"))
+        .cwd(cwd.path())
+        .model("gpt-5.1")
+        .config("model_providers.mock.name", "mock")
+        .config(
+            "model_providers.mock.base_url",
+            format!("{}/v1", server.uri()),
+        )
+        .config("model_providers.mock.wire_api", "responses")
+        .model_provider("mock")
+        .approval_policy(AskForApproval::Never)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn run_returns_the_final_response_of_the_turn() {
+    skip_if_no_network!();
+
+    let server = start_mock_server().await;
+    let response = mount_sse_once(
+        &server,
+        sse(vec![
+            ev_response_created("resp-1"),
+            ev_assistant_message("msg-1", "The README describes the CLI."),
+            ev_completed("resp-1"),
+        ]),
+    )
+    .await;
+    let codex_home = TempDir::new().expect("codex home");
+    let cwd = TempDir::new().expect("cwd");
+
+    let codex = mock_builder(&server, &codex_home, &cwd)
+        .sandbox_mode(SandboxMode::ReadOnly)
+        .build()
+        .await
+        .expect("build client");
+    let thread = codex.start_thread().await.expect("start thread");
+    assert_eq!(thread.model(), "gpt-5.1");
+    assert_eq!(
+        thread.session_configured().sandbox_mode,
+        SandboxMode::ReadOnly
+    );
+
+    let turn = thread.run("Summarize the README").await.expect("run turn");
+
+    assert_eq!(
+        turn.final_response.as_deref(),
+        Some("The README describes the CLI.")
+    );
+    assert!(
+        turn.events
+            .iter()
+            .any(|msg| matches!(msg, EventMsg::AgentMessage(_)))
+    );
+    assert!(matches!(
+        turn.events.last(),
+        Some(EventMsg::TaskComplete(_))
+    ));
+    assert_eq!(
+        response.single_request().message_input_texts("user").last(),
+        Some(&"Summarize the README".to_string())
+    );
+    thread.shutdown().await.expect("shut down");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn patches_are_applied_by_the_codex_executable() {
+    skip_if_no_network!();
+
+    let server = start_mock_server().await;
+    let patch = "*** Begin Patch\n*** Add File: hello.txt\n+hello\n*** End Patch";
+    mount_sse_sequence(
+        &server,
+        vec![
+            sse(vec![
+                ev_response_created("resp-1"),
+                ev_apply_patch_function_call("call-1", patch),
+                ev_completed("resp-1"),
+            ]),
+            sse(vec![
+                ev_assistant_message("msg-1", "Added hello.txt."),
+                ev_completed("resp-2"),
+            ]),
+        ],
+    )
+    .await;
+    let codex_home = TempDir::new().expect("codex home");
+    let cwd = TempDir::new().expect("cwd");
+
+    let codex = mock_builder(&server, &codex_home, &cwd)
+        .sandbox_mode(SandboxMode::WorkspaceWrite)
+        .config("features.apply_patch_freeform", true)
+        .build()
+        .await
+        .expect("build client");
+    let thread = codex.start_thread().await.expect("start thread");
+
+    let turn = thread.run("Add hello.txt").await.expect("run turn");
+
+    assert_eq!(turn.final_response.as_deref(), Some("Added hello.txt."));
+    assert!(
+        turn.events
+            .iter()
+            .any(|msg| matches!(msg, EventMsg::PatchApplyEnd(end) if end.success))
+    );
+    assert_eq!(
+        std::fs::read_to_string(cwd.path().join("hello.txt")).expect("read hello.txt"),
+        "hello\n"
+    );
+    thread.shutdown().await.expect("shut down");
+}
@@ -0,0 +1,63 @@
+//! Screen-reader mode (`tui.accessibility = "screen-reader"`).
+//!
+//! Screen readers follow the terminal linearly and re-read regions that
+//! change, so this mode keeps the screen still (animations are disabled via
+//! `Config::animations`), writes state changes into the transcript as plain
+//! sentences, and pairs status colors with a distinct glyph.
+
+use std::sync::OnceLock;
+
+use codex_core::config::types::AccessibilityMode;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+
+use crate::history_cell::PlainHistoryCell;
+use crate::terminal_capabilities::glyph;
+
+static SCREEN_READER: OnceLock<bool> = OnceLock::new();
+
+/// Select the accessibility mode. Only the first call takes effect.
+pub(crate) fn init(mode: AccessibilityMode) {
+    let _ = SCREEN_READER.set(mode == AccessibilityMode::ScreenReader);
+}
+
+pub(crate) fn screen_reader_enabled() -> bool {
+    SCREEN_READER.get().copied().unwrap_or(false)
+}
+
+/// Bullet for a finished command or tool call.
+pub(crate) fn outcome_bullet(success: bool) -> Span<'static> {
+    match (success, screen_reader_enabled()) {
+        (true, false) => glyph("•", "*").green().bold(),
+        (false, false) => glyph("•", "*").red().bold(),
+        (true, true) => glyph("✓", "+").green().bold(),
+        (false, true) => glyph("✗", "x").red().bold(),
+    }
+}
+
+/// A transcript line announcing a state change, e.g. "Status: turn started".
+pub(crate) fn announcement(message: impl Into<String>) -> PlainHistoryCell {
+    PlainHistoryCell::new(vec![Line::from(vec![
+        "Status: ".bold(),
+        message.into().into(),
+    ])])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history_cell::HistoryCell;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn announcement_is_plain_text() {
+        let lines = announcement("approval needed").display_lines(80);
+        let text: String = lines[0]
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert_eq!(text, "Status: approval needed");
+    }
+}
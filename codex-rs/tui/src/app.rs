@@ -3,10 +3,13 @@ use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::bottom_pane::ApprovalRequest;
 use crate::chatwidget::ChatWidget;
+use crate::chatwidget::RemoteAppServer;
 use crate::diff_render::DiffSummary;
+use crate::diff_render::display_path_for;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::file_search::FileSearchManager;
 use crate::history_cell::HistoryCell;
+use crate::inline_image;
 use crate::model_migration::ModelMigrationOutcome;
 use crate::model_migration::migration_copy_for_models;
 use crate::model_migration::run_model_migration_prompt;
@@ -14,6 +17,8 @@ use crate::pager_overlay::Overlay;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::renderable::Renderable;
 use crate::resume_picker::ResumeSelection;
+use crate::terminal_capabilities::capabilities;
+use crate::transcript_file::TranscriptFile;
 use crate::tui;
 use crate::tui::TuiEvent;
 use crate::update_action::UpdateAction;
@@ -29,6 +34,7 @@ use codex_core::openai_models::model_presets::HIDE_GPT_5_1_CODEX_MAX_MIGRATION_P
 use codex_core::openai_models::model_presets::HIDE_GPT5_1_MIGRATION_PROMPT_CONFIG;
 use codex_core::openai_models::models_manager::ModelsManager;
 use codex_core::protocol::EventMsg;
+use codex_core::protocol::FileChange;
 use codex_core::protocol::FinalOutput;
 use codex_core::protocol::ListSkillsResponseEvent;
 use codex_core::protocol::Op;
@@ -36,6 +42,7 @@ use codex_core::protocol::SessionSource;
 use codex_core::protocol::SkillErrorInfo;
 use codex_core::protocol::TokenUsage;
 use codex_protocol::ConversationId;
+use codex_protocol::config_types::ReasoningDisplay;
 use codex_protocol::openai_models::ModelPreset;
 use codex_protocol::openai_models::ModelUpgrade;
 use codex_protocol::openai_models::ReasoningEffort as ReasoningEffortConfig;
@@ -293,6 +300,8 @@ pub(crate) struct App {
     pub(crate) file_search: FileSearchManager,
 
     pub(crate) transcript_cells: Vec<Arc<dyn HistoryCell>>,
+    /// Plain-text mirror of the transcript (`tui.transcript_file`).
+    transcript_file: Option<TranscriptFile>,
 
     // Pager overlay state (Transcript or Static like Diff)
     pub(crate) overlay: Option<Overlay>,
@@ -316,6 +325,9 @@ pub(crate) struct App {
 
     // One-shot suppression of the next world-writable scan after user confirmation.
     skip_world_writable_scan_once: bool,
+
+    /// Set when conversations run in a remote app server (`--remote`).
+    remote: Option<RemoteAppServer>,
 }
 
 impl App {
@@ -338,6 +350,7 @@ impl App {
         resume_selection: ResumeSelection,
         feedback: codex_feedback::CodexFeedback,
         is_first_run: bool,
+        remote: Option<RemoteAppServer>,
     ) -> Result<AppExitInfo> {
         use tokio_stream::StreamExt;
         let (app_event_tx, mut app_event_rx) = unbounded_channel();
@@ -386,7 +399,10 @@ impl App {
                     is_first_run,
                     model_family: model_family.clone(),
                 };
-                ChatWidget::new(init, conversation_manager.clone())
+                match remote.clone() {
+                    Some(remote) => ChatWidget::new_remote(init, remote),
+                    None => ChatWidget::new(init, conversation_manager.clone()),
+                }
             }
             ResumeSelection::Resume(path) => {
                 let resumed = conversation_manager
@@ -426,6 +442,11 @@ impl App {
         #[cfg(not(debug_assertions))]
         let upgrade_version = crate::updates::get_upgrade_version(&config);
 
+        let transcript_file = config
+            .tui_transcript_file
+            .as_deref()
+            .and_then(TranscriptFile::open);
+
         let mut app = Self {
             server: conversation_manager.clone(),
             app_event_tx,
@@ -437,6 +458,7 @@ impl App {
             file_search,
             enhanced_keys_supported,
             transcript_cells: Vec::new(),
+            transcript_file,
             overlay: None,
             deferred_history_lines: Vec::new(),
             has_emitted_history_lines: false,
@@ -446,6 +468,7 @@ impl App {
             pending_update_action: None,
             suppress_shutdown_complete: false,
             skip_world_writable_scan_once: false,
+            remote,
         };
 
         // On startup, if Agent mode (workspace-write) or ReadOnly is active, warn about world-writable dirs on Windows.
@@ -574,7 +597,10 @@ impl App {
                     is_first_run: false,
                     model_family: model_family.clone(),
                 };
-                self.chat_widget = ChatWidget::new(init, self.server.clone());
+                self.chat_widget = match self.remote.clone() {
+                    Some(remote) => ChatWidget::new_remote(init, remote),
+                    None => ChatWidget::new(init, self.server.clone()),
+                };
                 self.current_model = model_family.get_model_slug().to_string();
                 if let Some(summary) = summary {
                     let mut lines: Vec<Line<'static>> = vec![summary.usage_line.clone().into()];
@@ -664,7 +690,11 @@ impl App {
                     tui.frame_requester().schedule_frame();
                 }
                 self.transcript_cells.push(cell.clone());
-                let mut display = cell.display_lines(tui.terminal.last_known_screen_size.width);
+                let width = tui.terminal.last_known_screen_size.width;
+                if let Some(transcript_file) = self.transcript_file.as_mut() {
+                    transcript_file.append_cell(cell.as_ref(), width);
+                }
+                let mut display = cell.display_lines(width);
                 if !display.is_empty() {
                     // Only insert a separating blank line for new cells that are not
                     // part of an ongoing stream. Streaming continuations should not
@@ -676,8 +706,18 @@ impl App {
                             self.has_emitted_history_lines = true;
                         }
                     }
+                    // Images are only drawn straight into the scrollback; while an
+                    // overlay is open the caption stands in for them.
                     if self.overlay.is_some() {
                         self.deferred_history_lines.extend(display);
+                    } else if let Some(image) = cell
+                        .inline_image()
+                        .zip(capabilities().images)
+                        .and_then(|(image, protocol)| inline_image::prepare(image, protocol, width))
+                    {
+                        // Reserve the rows the image is drawn over.
+                        display.extend((0..image.rows).map(|_| Line::from("")));
+                        tui.insert_history_image(display, image);
                     } else {
                         tui.insert_history_lines(display);
                     }
@@ -742,6 +782,37 @@ impl App {
                 ));
                 tui.frame_requester().schedule_frame();
             }
+            AppEvent::ShowFileAtTurn {
+                path,
+                turn,
+                content,
+            } => {
+                let _ = tui.enter_alt_screen();
+                let width = content.lines().count().max(1).to_string().len();
+                let lines: Vec<Line<'static>> = if content.is_empty() {
+                    vec!["The file was empty.".italic().into()]
+                } else {
+                    content
+                        .lines()
+                        .enumerate()
+                        .map(|(index, line)| {
+                            Line::from(vec![
+                                format!("{:>width$} ", index + 1).dim(),
+                                line.to_string().into(),
+                            ])
+                        })
+                        .collect()
+                };
+                let title = format!(
+                    "{} after turn {turn}",
+                    display_path_for(&path, &self.config.cwd)
+                );
+                self.overlay = Some(Overlay::new_static_with_lines(lines, title));
+                tui.frame_requester().schedule_frame();
+            }
+            AppEvent::CiLogsFetched(result) => {
+                self.chat_widget.on_ci_logs_fetched(result);
+            }
             AppEvent::StartFileSearch(query) => {
                 if !query.is_empty() {
                     self.file_search.on_user_query(query);
@@ -756,6 +827,10 @@ impl App {
             AppEvent::UpdateReasoningEffort(effort) => {
                 self.on_update_reasoning_effort(effort);
             }
+            AppEvent::UpdateVerbosity(verbosity) => {
+                self.chat_widget.set_verbosity(verbosity);
+                self.config.model_verbosity = verbosity;
+            }
             AppEvent::UpdateModel(model) => {
                 let model_family = self
                     .server
@@ -768,6 +843,15 @@ impl App {
             AppEvent::OpenReasoningPopup { model } => {
                 self.chat_widget.open_reasoning_popup(model);
             }
+            AppEvent::OpenArtifactActions { artifact } => {
+                self.chat_widget.open_artifact_actions_popup(artifact);
+            }
+            AppEvent::OpenArtifact { path } => {
+                self.chat_widget.open_artifact(&path);
+            }
+            AppEvent::SaveArtifact { path } => {
+                self.chat_widget.save_artifact(&path);
+            }
             AppEvent::OpenAllModelsPopup { models } => {
                 self.chat_widget.open_all_models_popup(models);
             }
@@ -833,6 +917,7 @@ impl App {
                                         model: None,
                                         effort: None,
                                         summary: None,
+                                        verbosity: None,
                                     },
                                 ));
                                 self.app_event_tx
@@ -1106,6 +1191,29 @@ impl App {
                         "E L I C I T A T I O N".to_string(),
                     ));
                 }
+                ApprovalRequest::PatchConflict { cwd, conflicts, .. } => {
+                    let _ = tui.enter_alt_screen();
+                    // Show what changed on disk since Codex last read each file.
+                    let changes = conflicts
+                        .into_iter()
+                        .map(|conflict| {
+                            let change = match conflict.diff {
+                                Some(unified_diff) => FileChange::Update {
+                                    unified_diff,
+                                    move_path: None,
+                                },
+                                None => FileChange::Delete {
+                                    content: String::new(),
+                                },
+                            };
+                            (conflict.path, change)
+                        })
+                        .collect();
+                    self.overlay = Some(Overlay::new_static_with_renderables(
+                        vec![DiffSummary::new(changes, cwd).into()],
+                        "C O N F L I C T".to_string(),
+                    ));
+                }
             },
         }
         Ok(true)
@@ -1138,6 +1246,44 @@ impl App {
         self.config.model_reasoning_effort = effort;
     }
 
+    /// Advance to the next reasoning display mode, apply it to the running
+    /// session, and persist it for the active profile.
+    async fn cycle_reasoning_display(&mut self) {
+        let display = ReasoningDisplay::from_flags(
+            self.config.hide_agent_reasoning,
+            self.config.show_raw_agent_reasoning,
+        )
+        .next();
+        self.config.hide_agent_reasoning = display.hides_reasoning();
+        self.config.show_raw_agent_reasoning = display.shows_raw_reasoning();
+        self.chat_widget
+            .submit_op(Op::SetReasoningDisplay { display });
+
+        let profile = self.active_profile.as_deref();
+        if let Err(err) = ConfigEditsBuilder::new(&self.config.codex_home)
+            .with_profile(profile)
+            .set_reasoning_display(display)
+            .apply()
+            .await
+        {
+            tracing::error!(error = %err, "failed to persist reasoning display");
+            self.chat_widget.add_error_message(format!(
+                "Failed to save reasoning display preference: {err}"
+            ));
+            return;
+        }
+
+        let label = match display {
+            ReasoningDisplay::Summary => "reasoning summaries",
+            ReasoningDisplay::Raw => "raw reasoning",
+            ReasoningDisplay::Hidden => "no reasoning",
+        };
+        self.chat_widget.add_info_message(
+            format!("Showing {label}"),
+            Some("Press ctrl + r to switch".to_string()),
+        );
+    }
+
     async fn handle_key_event(&mut self, tui: &mut tui::Tui, key_event: KeyEvent) {
         match key_event {
             KeyEvent {
@@ -1151,6 +1297,14 @@ impl App {
                 self.overlay = Some(Overlay::new_transcript(self.transcript_cells.clone()));
                 tui.frame_requester().schedule_frame();
             }
+            KeyEvent {
+                code: KeyCode::Char('r'),
+                modifiers: crossterm::event::KeyModifiers::CONTROL,
+                kind: KeyEventKind::Press,
+                ..
+            } => {
+                self.cycle_reasoning_display().await;
+            }
             // Esc primes/advances backtracking only in normal (not working) mode
             // with the composer focused and empty. In any other state, forward
             // Esc so the active UI (e.g. status indicator, modals, popups)
@@ -1274,6 +1428,7 @@ mod tests {
             active_profile: None,
             file_search,
             transcript_cells: Vec::new(),
+            transcript_file: None,
             overlay: None,
             deferred_history_lines: Vec::new(),
             has_emitted_history_lines: false,
@@ -1284,6 +1439,7 @@ mod tests {
             pending_update_action: None,
             suppress_shutdown_complete: false,
             skip_world_writable_scan_once: false,
+            remote: None,
         }
     }
 
@@ -1314,6 +1470,7 @@ mod tests {
                 active_profile: None,
                 file_search,
                 transcript_cells: Vec::new(),
+                transcript_file: None,
                 overlay: None,
                 deferred_history_lines: Vec::new(),
                 has_emitted_history_lines: false,
@@ -1324,6 +1481,7 @@ mod tests {
                 pending_update_action: None,
                 suppress_shutdown_complete: false,
                 skip_world_writable_scan_once: false,
+                remote: None,
             },
             rx,
             op_rx,
@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use codex_common::approval_presets::ApprovalPreset;
+use codex_core::protocol::Artifact;
 use codex_core::protocol::ConversationPathResponseEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::RateLimitSnapshot;
@@ -13,6 +14,7 @@ use crate::history_cell::HistoryCell;
 use codex_core::features::Feature;
 use codex_core::protocol::AskForApproval;
 use codex_core::protocol::SandboxPolicy;
+use codex_protocol::config_types::Verbosity;
 use codex_protocol::openai_models::ReasoningEffort;
 
 #[allow(clippy::large_enum_variant)]
@@ -52,6 +54,16 @@ pub(crate) enum AppEvent {
     /// Result of computing a `/diff` command.
     DiffResult(String),
 
+    /// Contents of a file after an earlier turn, for `/file-at`.
+    ShowFileAtTurn {
+        path: PathBuf,
+        turn: usize,
+        content: String,
+    },
+
+    /// Log excerpts fetched for a `/ci` command; `None` when no run failed.
+    CiLogsFetched(Result<Option<String>, String>),
+
     InsertHistoryCell(Box<dyn HistoryCell>),
 
     StartCommitAnimation,
@@ -61,6 +73,9 @@ pub(crate) enum AppEvent {
     /// Update the current reasoning effort in the running app and widget.
     UpdateReasoningEffort(Option<ReasoningEffort>),
 
+    /// Update the current output verbosity in the running app and widget.
+    UpdateVerbosity(Option<Verbosity>),
+
     /// Update the current model slug in the running app and widget.
     UpdateModel(String),
 
@@ -75,6 +90,21 @@ pub(crate) enum AppEvent {
         model: ModelPreset,
     },
 
+    /// Show the open/save actions for an artifact picked in `/artifacts`.
+    OpenArtifactActions {
+        artifact: Artifact,
+    },
+
+    /// Open an artifact with the default application.
+    OpenArtifact {
+        path: PathBuf,
+    },
+
+    /// Copy an artifact into the working directory.
+    SaveArtifact {
+        path: PathBuf,
+    },
+
     /// Open the full model picker (non-auto models).
     OpenAllModelsPopup {
         models: Vec<ModelPreset>,
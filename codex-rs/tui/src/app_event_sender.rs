@@ -1,6 +1,7 @@
 use tokio::sync::mpsc::UnboundedSender;
 
 use crate::app_event::AppEvent;
+use crate::crash_report;
 use crate::session_log;
 
 #[derive(Clone, Debug)]
@@ -21,6 +22,9 @@ impl AppEventSender {
         if !matches!(event, AppEvent::CodexOp(_)) {
             session_log::log_inbound_app_event(&event);
         }
+        if let AppEvent::CodexEvent(ev) = &event {
+            crash_report::record_event(ev);
+        }
         if let Err(e) = self.app_event_tx.send(event) {
             tracing::error!("failed to send event: {e}");
         }
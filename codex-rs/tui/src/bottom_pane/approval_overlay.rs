@@ -11,6 +11,8 @@ use crate::bottom_pane::list_selection_view::SelectionViewParams;
 use crate::diff_render::DiffSummary;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::history_cell;
+use crate::i18n;
+use crate::i18n::Message;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::render::highlight::highlight_bash_to_lines;
@@ -22,6 +24,8 @@ use codex_core::protocol::ElicitationAction;
 use codex_core::protocol::ExecPolicyAmendment;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::Op;
+use codex_core::protocol::PatchConflict;
+use codex_core::protocol::PatchConflictResolution;
 use codex_core::protocol::ReviewDecision;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
@@ -42,6 +46,8 @@ pub(crate) enum ApprovalRequest {
     Exec {
         id: String,
         command: Vec<String>,
+        /// Environment variables the command sets for itself.
+        env: HashMap<String, String>,
         reason: Option<String>,
         proposed_execpolicy_amendment: Option<ExecPolicyAmendment>,
     },
@@ -56,6 +62,11 @@ pub(crate) enum ApprovalRequest {
         request_id: RequestId,
         message: String,
     },
+    PatchConflict {
+        id: String,
+        cwd: PathBuf,
+        conflicts: Vec<PatchConflict>,
+    },
 }
 
 /// Modal overlay asking the user to approve or deny one or more requests.
@@ -113,16 +124,20 @@ impl ApprovalOverlay {
                 ..
             } => (
                 exec_options(proposed_execpolicy_amendment.clone(), features),
-                "Would you like to run the following command?".to_string(),
+                i18n::tr(Message::RunCommandTitle).to_string(),
             ),
             ApprovalVariant::ApplyPatch { .. } => (
                 patch_options(),
-                "Would you like to make the following edits?".to_string(),
+                i18n::tr(Message::ApplyEditsTitle).to_string(),
             ),
             ApprovalVariant::McpElicitation { server_name, .. } => (
                 elicitation_options(),
                 format!("{server_name} needs your approval."),
             ),
+            ApprovalVariant::PatchConflict { .. } => (
+                conflict_options(),
+                "Files changed since Codex last read them".to_string(),
+            ),
         };
 
         let header = Box::new(ColumnRenderable::with([
@@ -183,6 +198,12 @@ impl ApprovalOverlay {
                 ) => {
                     self.handle_elicitation_decision(server_name, request_id, *decision);
                 }
+                (
+                    ApprovalVariant::PatchConflict { id },
+                    ApprovalDecision::PatchConflict(resolution),
+                ) => {
+                    self.handle_conflict_decision(id, *resolution);
+                }
                 _ => {}
             }
         }
@@ -221,6 +242,14 @@ impl ApprovalOverlay {
             }));
     }
 
+    fn handle_conflict_decision(&self, id: &str, resolution: PatchConflictResolution) {
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::ResolvePatchConflict {
+                id: id.to_string(),
+                resolution,
+            }));
+    }
+
     fn advance_queue(&mut self) {
         if let Some(next) = self.queue.pop() {
             self.set_current(next);
@@ -296,6 +325,9 @@ impl BottomPaneView for ApprovalOverlay {
                         ElicitationAction::Cancel,
                     );
                 }
+                ApprovalVariant::PatchConflict { id } => {
+                    self.handle_conflict_decision(id, PatchConflictResolution::Abort);
+                }
             }
         }
         self.queue.clear();
@@ -341,6 +373,7 @@ impl From<ApprovalRequest> for ApprovalRequestState {
             ApprovalRequest::Exec {
                 id,
                 command,
+                env,
                 reason,
                 proposed_execpolicy_amendment,
             } => {
@@ -355,6 +388,14 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     first.spans.insert(0, Span::from("$ "));
                 }
                 header.extend(full_cmd_lines);
+                let mut env: Vec<(String, String)> = env.into_iter().collect();
+                env.sort();
+                for (key, value) in env {
+                    header.push(Line::from(vec![
+                        "  env ".dim(),
+                        format!("{key}={value}").into(),
+                    ]));
+                }
                 Self {
                     variant: ApprovalVariant::Exec {
                         id,
@@ -405,6 +446,28 @@ impl From<ApprovalRequest> for ApprovalRequestState {
                     header: Box::new(header),
                 }
             }
+            ApprovalRequest::PatchConflict { id, cwd, conflicts } => {
+                let mut header = vec![Line::from(
+                    "Codex wants to patch files that were edited after it last read them:",
+                )];
+                for conflict in conflicts {
+                    let path = conflict.path.strip_prefix(&cwd).unwrap_or(&conflict.path);
+                    let status = if conflict.diff.is_some() {
+                        "modified"
+                    } else {
+                        "deleted"
+                    };
+                    header.push(Line::from(vec![
+                        "  ".into(),
+                        path.display().to_string().bold(),
+                        format!(" ({status})").dim(),
+                    ]));
+                }
+                Self {
+                    variant: ApprovalVariant::PatchConflict { id },
+                    header: Box::new(Paragraph::new(header).wrap(Wrap { trim: false })),
+                }
+            }
         }
     }
 }
@@ -423,12 +486,16 @@ enum ApprovalVariant {
         server_name: String,
         request_id: RequestId,
     },
+    PatchConflict {
+        id: String,
+    },
 }
 
 #[derive(Clone)]
 enum ApprovalDecision {
     Review(ReviewDecision),
     McpElicitation(ElicitationAction),
+    PatchConflict(PatchConflictResolution),
 }
 
 #[derive(Clone)]
@@ -452,7 +519,7 @@ fn exec_options(
     features: &Features,
 ) -> Vec<ApprovalOption> {
     vec![ApprovalOption {
-        label: "Yes, proceed".to_string(),
+        label: i18n::tr(Message::YesProceed).to_string(),
         decision: ApprovalDecision::Review(ReviewDecision::Approved),
         display_shortcut: None,
         additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
@@ -478,7 +545,7 @@ fn exec_options(
             }),
     )
     .chain([ApprovalOption {
-        label: "No, and tell Codex what to do differently".to_string(),
+        label: i18n::tr(Message::NoTellCodex).to_string(),
         decision: ApprovalDecision::Review(ReviewDecision::Abort),
         display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
         additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
@@ -489,13 +556,13 @@ fn exec_options(
 fn patch_options() -> Vec<ApprovalOption> {
     vec![
         ApprovalOption {
-            label: "Yes, proceed".to_string(),
+            label: i18n::tr(Message::YesProceed).to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::Approved),
             display_shortcut: None,
             additional_shortcuts: vec![key_hint::plain(KeyCode::Char('y'))],
         },
         ApprovalOption {
-            label: "No, and tell Codex what to do differently".to_string(),
+            label: i18n::tr(Message::NoTellCodex).to_string(),
             decision: ApprovalDecision::Review(ReviewDecision::Abort),
             display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
             additional_shortcuts: vec![key_hint::plain(KeyCode::Char('n'))],
@@ -526,6 +593,29 @@ fn elicitation_options() -> Vec<ApprovalOption> {
     ]
 }
 
+fn conflict_options() -> Vec<ApprovalOption> {
+    vec![
+        ApprovalOption {
+            label: "Re-read the files and rebuild the patch".to_string(),
+            decision: ApprovalDecision::PatchConflict(PatchConflictResolution::Reread),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('r'))],
+        },
+        ApprovalOption {
+            label: "Apply the patch anyway".to_string(),
+            decision: ApprovalDecision::PatchConflict(PatchConflictResolution::Force),
+            display_shortcut: None,
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('f'))],
+        },
+        ApprovalOption {
+            label: "Abort the turn".to_string(),
+            decision: ApprovalDecision::PatchConflict(PatchConflictResolution::Abort),
+            display_shortcut: Some(key_hint::plain(KeyCode::Esc)),
+            additional_shortcuts: vec![key_hint::plain(KeyCode::Char('a'))],
+        },
+    ]
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -537,6 +627,7 @@ mod tests {
         ApprovalRequest::Exec {
             id: "test".to_string(),
             command: vec!["echo".to_string(), "hi".to_string()],
+            env: HashMap::new(),
             reason: Some("reason".to_string()),
             proposed_execpolicy_amendment: None,
         }
@@ -579,6 +670,7 @@ mod tests {
             ApprovalRequest::Exec {
                 id: "test".to_string(),
                 command: vec!["echo".to_string()],
+                env: HashMap::new(),
                 reason: None,
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "echo".to_string(),
@@ -617,6 +709,7 @@ mod tests {
             ApprovalRequest::Exec {
                 id: "test".to_string(),
                 command: vec!["echo".to_string()],
+                env: HashMap::new(),
                 reason: None,
                 proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
                     "echo".to_string(),
@@ -635,6 +728,36 @@ mod tests {
         assert!(rx.try_recv().is_err());
     }
 
+    #[test]
+    fn conflict_shortcut_emits_resolution() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let tx = AppEventSender::new(tx);
+        let mut view = ApprovalOverlay::new(
+            ApprovalRequest::PatchConflict {
+                id: "turn-1".to_string(),
+                cwd: PathBuf::from("/repo"),
+                conflicts: vec![PatchConflict {
+                    path: PathBuf::from("/repo/src/lib.rs"),
+                    diff: Some("@@ -1 +1 @@\n-old\n+new\n".to_string()),
+                }],
+            },
+            tx,
+            Features::with_defaults(),
+        );
+        view.handle_key_event(KeyEvent::new(KeyCode::Char('f'), KeyModifiers::NONE));
+        assert!(view.is_complete());
+        let Ok(AppEvent::CodexOp(op)) = rx.try_recv() else {
+            panic!("expected conflict resolution op");
+        };
+        assert_eq!(
+            op,
+            Op::ResolvePatchConflict {
+                id: "turn-1".to_string(),
+                resolution: PatchConflictResolution::Force,
+            }
+        );
+    }
+
     #[test]
     fn header_includes_command_snippet() {
         let (tx, _rx) = unbounded_channel::<AppEvent>();
@@ -643,6 +766,7 @@ mod tests {
         let exec_request = ApprovalRequest::Exec {
             id: "test".into(),
             command,
+            env: HashMap::from([("RUST_LOG".to_string(), "debug".to_string())]),
             reason: None,
             proposed_execpolicy_amendment: None,
         };
@@ -664,6 +788,12 @@ mod tests {
                 .any(|line| line.contains("echo hello world")),
             "expected header to include command snippet, got {rendered:?}"
         );
+        assert!(
+            rendered
+                .iter()
+                .any(|line| line.contains("env RUST_LOG=debug")),
+            "expected header to include the env override, got {rendered:?}"
+        );
     }
 
     #[test]
@@ -1,10 +1,13 @@
 #[cfg(target_os = "linux")]
 use crate::clipboard_paste::is_probably_wsl;
+use crate::i18n;
+use crate::i18n::Message;
 use crate::key_hint;
 use crate::key_hint::KeyBinding;
 use crate::render::line_utils::prefix_lines;
 use crate::status::format_tokens_compact;
 use crate::ui_consts::FOOTER_INDENT_COLS;
+use codex_core::protocol::ApprovalPresetId;
 use crossterm::event::KeyCode;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
@@ -22,6 +25,8 @@ pub(crate) struct FooterProps {
     pub(crate) is_task_running: bool,
     pub(crate) context_window_percent: Option<i64>,
     pub(crate) context_window_used_tokens: Option<i64>,
+    /// Approval preset in effect, shown ahead of the context indicator.
+    pub(crate) approval_preset: Option<ApprovalPresetId>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -85,14 +90,22 @@ fn footer_lines(props: FooterProps) -> Vec<Line<'static>> {
             is_task_running: props.is_task_running,
         })],
         FooterMode::ShortcutSummary => {
-            let mut line = context_window_line(
-                props.context_window_percent,
-                props.context_window_used_tokens,
+            let mut line = Line::default();
+            if let Some(preset) = props.approval_preset {
+                line.push_span(Span::from(preset.to_string()).dim());
+                line.push_span(" · ".dim());
+            }
+            line.extend(
+                context_window_line(
+                    props.context_window_percent,
+                    props.context_window_used_tokens,
+                )
+                .spans,
             );
             line.push_span(" · ".dim());
             line.extend(vec![
                 key_hint::plain(KeyCode::Char('?')).into(),
-                " for shortcuts".dim(),
+                i18n::tr(Message::ForShortcuts).dim(),
             ]);
             vec![line]
         }
@@ -440,6 +453,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                approval_preset: None,
             },
         );
 
@@ -452,6 +466,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                approval_preset: None,
             },
         );
 
@@ -464,6 +479,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                approval_preset: None,
             },
         );
 
@@ -476,6 +492,7 @@ mod tests {
                 is_task_running: true,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                approval_preset: None,
             },
         );
 
@@ -488,6 +505,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                approval_preset: None,
             },
         );
 
@@ -500,6 +518,7 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: None,
+                approval_preset: None,
             },
         );
 
@@ -512,6 +531,7 @@ mod tests {
                 is_task_running: true,
                 context_window_percent: Some(72),
                 context_window_used_tokens: None,
+                approval_preset: None,
             },
         );
 
@@ -524,6 +544,20 @@ mod tests {
                 is_task_running: false,
                 context_window_percent: None,
                 context_window_used_tokens: Some(123_456),
+                approval_preset: None,
+            },
+        );
+
+        snapshot_footer(
+            "footer_shortcuts_approval_preset",
+            FooterProps {
+                mode: FooterMode::ShortcutSummary,
+                esc_backtrack_hint: false,
+                use_shift_enter_hint: false,
+                is_task_running: false,
+                context_window_percent: None,
+                context_window_used_tokens: None,
+                approval_preset: Some(ApprovalPresetId::FullAuto),
             },
         );
     }
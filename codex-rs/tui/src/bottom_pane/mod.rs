@@ -10,6 +10,7 @@ use crate::render::renderable::RenderableItem;
 use crate::tui::FrameRequester;
 use bottom_pane_view::BottomPaneView;
 use codex_core::features::Features;
+use codex_core::protocol::ApprovalPresetId;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
 use crossterm::event::KeyCode;
@@ -31,6 +32,8 @@ mod file_search_popup;
 mod footer;
 mod list_selection_view;
 mod prompt_args;
+mod question_view;
+pub(crate) use question_view::QuestionView;
 mod skill_popup;
 pub(crate) use list_selection_view::SelectionViewParams;
 mod feedback_view;
@@ -284,6 +287,15 @@ impl BottomPane {
         }
     }
 
+    /// Update the turn progress shown in the status indicator's brackets.
+    /// No-ops if the status indicator is not active.
+    pub(crate) fn update_status_progress(&mut self, progress: Option<String>) {
+        if let Some(status) = self.status.as_mut() {
+            status.update_progress(progress);
+            self.request_redraw();
+        }
+    }
+
     pub(crate) fn show_ctrl_c_quit_hint(&mut self) {
         self.ctrl_c_quit_hint = true;
         self.composer
@@ -376,6 +388,11 @@ impl BottomPane {
         }
     }
 
+    pub(crate) fn set_approval_preset(&mut self, preset: Option<ApprovalPresetId>) {
+        self.composer.set_approval_preset(preset);
+        self.request_redraw();
+    }
+
     pub(crate) fn set_context_window(&mut self, percent: Option<i64>, used_tokens: Option<i64>) {
         if self.context_window_percent == percent && self.context_window_used_tokens == used_tokens
         {
@@ -598,6 +615,7 @@ mod tests {
         ApprovalRequest::Exec {
             id: "1".to_string(),
             command: vec!["echo".into(), "ok".into()],
+            env: Default::default(),
             reason: None,
             proposed_execpolicy_amendment: None,
         }
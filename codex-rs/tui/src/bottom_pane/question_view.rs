@@ -0,0 +1,295 @@
+use codex_core::protocol::Op;
+use codex_core::protocol::QuestionEvent;
+use crossterm::event::KeyEvent;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Widget;
+use ratatui::widgets::Wrap;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+use crate::render::renderable::Renderable;
+
+use super::CancellationEvent;
+use super::bottom_pane_view::BottomPaneView;
+use super::custom_prompt_view::CustomPromptView;
+use super::list_selection_view::ListSelectionView;
+use super::list_selection_view::SelectionItem;
+use super::list_selection_view::SelectionViewParams;
+use super::popup_consts::standard_popup_hint_line;
+
+const OWN_WORDS_LABEL: &str = "Answer in your own words";
+
+/// Shows a question the model asked and sends the user's answer back.
+/// Dismissing the view declines to answer, so the turn never waits forever.
+pub(crate) struct QuestionView {
+    id: String,
+    question: String,
+    options: Vec<String>,
+    mode: Mode,
+    app_event_tx: AppEventSender,
+    answered: Arc<AtomicBool>,
+    complete: bool,
+}
+
+enum Mode {
+    Options(ListSelectionView),
+    Text(CustomPromptView),
+}
+
+impl QuestionView {
+    pub(crate) fn new(event: QuestionEvent, app_event_tx: AppEventSender) -> Self {
+        let answered = Arc::new(AtomicBool::new(false));
+        let mode = if event.options.is_empty() {
+            Mode::Text(Self::text_input(
+                &event.turn_id,
+                &app_event_tx,
+                Arc::clone(&answered),
+            ))
+        } else {
+            Mode::Options(Self::option_list(&event.options, &app_event_tx))
+        };
+        Self {
+            id: event.turn_id,
+            question: event.question,
+            options: event.options,
+            mode,
+            app_event_tx,
+            answered,
+            complete: false,
+        }
+    }
+
+    fn option_list(options: &[String], app_event_tx: &AppEventSender) -> ListSelectionView {
+        let items = options
+            .iter()
+            .cloned()
+            .chain(std::iter::once(OWN_WORDS_LABEL.to_string()))
+            .map(|name| SelectionItem {
+                name,
+                dismiss_on_select: false,
+                ..Default::default()
+            })
+            .collect();
+        ListSelectionView::new(
+            SelectionViewParams {
+                footer_hint: Some(standard_popup_hint_line()),
+                items,
+                ..Default::default()
+            },
+            app_event_tx.clone(),
+        )
+    }
+
+    fn text_input(
+        id: &str,
+        app_event_tx: &AppEventSender,
+        answered: Arc<AtomicBool>,
+    ) -> CustomPromptView {
+        let id = id.to_string();
+        let tx = app_event_tx.clone();
+        CustomPromptView::new(
+            "Your answer".to_string(),
+            "Type your answer and press Enter".to_string(),
+            None,
+            Box::new(move |answer: String| {
+                answered.store(true, Ordering::Relaxed);
+                tx.send(AppEvent::CodexOp(Op::AnswerQuestion {
+                    id: id.clone(),
+                    answer: Some(answer),
+                }));
+            }),
+        )
+    }
+
+    fn send_answer(&self, answer: Option<String>) {
+        self.answered.store(true, Ordering::Relaxed);
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::AnswerQuestion {
+                id: self.id.clone(),
+                answer,
+            }));
+    }
+
+    fn finish(&mut self) {
+        if !self.answered.load(Ordering::Relaxed) {
+            self.send_answer(None);
+        }
+        self.complete = true;
+    }
+
+    fn question_paragraph(&self) -> Paragraph<'static> {
+        Paragraph::new(Line::from(self.question.clone().bold())).wrap(Wrap { trim: false })
+    }
+
+    fn question_height(&self, width: u16) -> u16 {
+        u16::try_from(self.question_paragraph().line_count(width))
+            .unwrap_or(u16::MAX)
+            .saturating_add(1)
+    }
+
+    fn inner(&self) -> &dyn BottomPaneView {
+        match &self.mode {
+            Mode::Options(view) => view,
+            Mode::Text(view) => view,
+        }
+    }
+
+    /// Area left for the option list or text input below the question.
+    fn inner_area(&self, area: Rect) -> Rect {
+        let offset = self.question_height(area.width).min(area.height);
+        Rect {
+            y: area.y + offset,
+            height: area.height - offset,
+            ..area
+        }
+    }
+}
+
+impl BottomPaneView for QuestionView {
+    fn handle_key_event(&mut self, key_event: KeyEvent) {
+        let (selected, dismissed) = match &mut self.mode {
+            Mode::Options(view) => {
+                view.handle_key_event(key_event);
+                (view.take_last_selected_index(), view.is_complete())
+            }
+            Mode::Text(view) => {
+                view.handle_key_event(key_event);
+                (None, view.is_complete())
+            }
+        };
+        match selected.map(|idx| self.options.get(idx).cloned()) {
+            Some(Some(option)) => {
+                self.send_answer(Some(option));
+                self.complete = true;
+            }
+            // The last entry asks for an answer in the user's own words.
+            Some(None) => {
+                self.mode = Mode::Text(Self::text_input(
+                    &self.id,
+                    &self.app_event_tx,
+                    Arc::clone(&self.answered),
+                ));
+            }
+            None if dismissed => self.finish(),
+            None => {}
+        }
+    }
+
+    fn on_ctrl_c(&mut self) -> CancellationEvent {
+        if !self.complete {
+            self.finish();
+        }
+        CancellationEvent::Handled
+    }
+
+    fn is_complete(&self) -> bool {
+        self.complete
+    }
+
+    fn handle_paste(&mut self, pasted: String) -> bool {
+        match &mut self.mode {
+            Mode::Options(_) => false,
+            Mode::Text(view) => view.handle_paste(pasted),
+        }
+    }
+}
+
+impl Renderable for QuestionView {
+    fn desired_height(&self, width: u16) -> u16 {
+        self.question_height(width)
+            .saturating_add(self.inner().desired_height(width))
+    }
+
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 || area.width == 0 {
+            return;
+        }
+        self.question_paragraph().render(area, buf);
+        self.inner().render(self.inner_area(area), buf);
+    }
+
+    fn cursor_pos(&self, area: Rect) -> Option<(u16, u16)> {
+        self.inner().cursor_pos(self.inner_area(area))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyCode;
+    use crossterm::event::KeyModifiers;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    fn question(options: &[&str]) -> QuestionEvent {
+        QuestionEvent {
+            call_id: "call-1".to_string(),
+            turn_id: "turn-1".to_string(),
+            question: "Which database should the tests use?".to_string(),
+            options: options.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    fn answer_op(answer: Option<&str>) -> Op {
+        Op::AnswerQuestion {
+            id: "turn-1".to_string(),
+            answer: answer.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn selecting_an_option_sends_it_as_the_answer() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view =
+            QuestionView::new(question(&["sqlite", "postgres"]), AppEventSender::new(tx));
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(view.is_complete());
+        let Ok(AppEvent::CodexOp(op)) = rx.try_recv() else {
+            panic!("expected an answer");
+        };
+        assert_eq!(op, answer_op(Some("postgres")));
+    }
+
+    #[test]
+    fn own_words_option_switches_to_text_input() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = QuestionView::new(question(&["sqlite"]), AppEventSender::new(tx));
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+        view.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(!view.is_complete());
+        view.handle_paste("both".to_string());
+        view.handle_key_event(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+        assert!(view.is_complete());
+        let Ok(AppEvent::CodexOp(op)) = rx.try_recv() else {
+            panic!("expected an answer");
+        };
+        assert_eq!(op, answer_op(Some("both")));
+        assert!(rx.try_recv().is_err(), "answer sent once");
+    }
+
+    #[test]
+    fn dismissing_declines_to_answer() {
+        let (tx, mut rx) = unbounded_channel::<AppEvent>();
+        let mut view = QuestionView::new(question(&[]), AppEventSender::new(tx));
+
+        view.handle_key_event(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+
+        assert!(view.is_complete());
+        let Ok(AppEvent::CodexOp(op)) = rx.try_recv() else {
+            panic!("expected a declined answer");
+        };
+        assert_eq!(op, answer_op(None));
+    }
+}
@@ -11,6 +11,7 @@ use codex_core::config::Config;
 use codex_core::config::types::Notifications;
 use codex_core::features::FEATURES;
 use codex_core::features::Feature;
+use codex_core::fetch_failing_ci_logs;
 use codex_core::git_info::current_branch_name;
 use codex_core::git_info::local_git_branches;
 use codex_core::openai_models::model_family::ModelFamily;
@@ -23,9 +24,19 @@ use codex_core::protocol::AgentReasoningEvent;
 use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
+use codex_core::protocol::ApprovalPresetId;
+use codex_core::protocol::Artifact;
+use codex_core::protocol::ArtifactRegisteredEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::CandidateSelectedEvent;
+use codex_core::protocol::CandidateSelector;
+use codex_core::protocol::CandidatesProposedEvent;
+use codex_core::protocol::ContextRepairedEvent;
+use codex_core::protocol::ContextTrimmedEvent;
 use codex_core::protocol::CreditsSnapshot;
+use codex_core::protocol::DependencyReportEvent;
 use codex_core::protocol::DeprecationNoticeEvent;
+use codex_core::protocol::ErrorDetails;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::EventMsg;
@@ -34,6 +45,7 @@ use codex_core::protocol::ExecCommandBeginEvent;
 use codex_core::protocol::ExecCommandEndEvent;
 use codex_core::protocol::ExecCommandSource;
 use codex_core::protocol::ExitedReviewModeEvent;
+use codex_core::protocol::FileAtTurnResponseEvent;
 use codex_core::protocol::ListCustomPromptsResponseEvent;
 use codex_core::protocol::ListSkillsResponseEvent;
 use codex_core::protocol::McpListToolsResponseEvent;
@@ -42,19 +54,29 @@ use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::McpStartupUpdateEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::MemoryCandidatesProposedEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
+use codex_core::protocol::PatchConflictEvent;
+use codex_core::protocol::ProtectedPathViolationEvent;
+use codex_core::protocol::QuestionEvent;
 use codex_core::protocol::RateLimitSnapshot;
+use codex_core::protocol::ResponseRegeneratedEvent;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
+use codex_core::protocol::SemanticIndexUpdatedEvent;
+use codex_core::protocol::SessionSummaryEvent;
 use codex_core::protocol::SkillsListEntry;
+use codex_core::protocol::StalledEvent;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
 use codex_core::protocol::TurnAbortReason;
+use codex_core::protocol::TurnContextChangedEvent;
 use codex_core::protocol::TurnDiffEvent;
+use codex_core::protocol::TurnProgressEvent;
 use codex_core::protocol::UndoCompletedEvent;
 use codex_core::protocol::UndoStartedEvent;
 use codex_core::protocol::UserMessageEvent;
@@ -62,16 +84,20 @@ use codex_core::protocol::ViewImageToolCallEvent;
 use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
+use codex_core::protocol::WorkflowStepStartedEvent;
+use codex_core::protocol::WorkingDirectoryChangedEvent;
 use codex_core::skills::model::SkillMetadata;
 use codex_protocol::ConversationId;
 use codex_protocol::account::PlanType;
 use codex_protocol::approvals::ElicitationRequestEvent;
+use codex_protocol::config_types::Verbosity;
 use codex_protocol::parse_command::ParsedCommand;
 use codex_protocol::user_input::UserInput;
 use crossterm::event::KeyCode;
 use crossterm::event::KeyEvent;
 use crossterm::event::KeyEventKind;
 use crossterm::event::KeyModifiers;
+use image::DynamicImage;
 use rand::Rng;
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
@@ -84,8 +110,10 @@ use tokio::sync::mpsc::UnboundedSender;
 use tokio::task::JoinHandle;
 use tracing::debug;
 
+use crate::accessibility;
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
+use crate::artifacts;
 use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::BetaFeatureItem;
 use crate::bottom_pane::BottomPane;
@@ -93,6 +121,7 @@ use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
 use crate::bottom_pane::ExperimentalFeaturesView;
 use crate::bottom_pane::InputResult;
+use crate::bottom_pane::QuestionView;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
@@ -108,8 +137,12 @@ use crate::get_git_diff::get_git_diff;
 use crate::history_cell;
 use crate::history_cell::AgentMessageCell;
 use crate::history_cell::HistoryCell;
+use crate::history_cell::ImageHistoryCell;
 use crate::history_cell::McpToolCallCell;
 use crate::history_cell::PlainHistoryCell;
+use crate::i18n;
+use crate::i18n::Message;
+use crate::inline_image;
 use crate::markdown::append_markdown;
 use crate::render::Insets;
 use crate::render::renderable::ColumnRenderable;
@@ -119,6 +152,8 @@ use crate::render::renderable::RenderableExt;
 use crate::render::renderable::RenderableItem;
 use crate::slash_command::SlashCommand;
 use crate::status::RateLimitSnapshotDisplay;
+use crate::status_indicator_widget::fmt_elapsed_compact;
+use crate::terminal_capabilities::capabilities;
 use crate::text_formatting::truncate_text;
 use crate::tui::FrameRequester;
 mod interrupts;
@@ -126,6 +161,9 @@ use self::interrupts::InterruptManager;
 mod agent;
 use self::agent::spawn_agent;
 use self::agent::spawn_agent_from_existing;
+mod remote_agent;
+pub(crate) use self::remote_agent::RemoteAppServer;
+use self::remote_agent::spawn_remote_agent;
 mod session_header;
 use self::session_header::SessionHeader;
 use crate::streaming::controller::StreamController;
@@ -359,6 +397,8 @@ pub(crate) struct ChatWidget {
     feedback: codex_feedback::CodexFeedback,
     // Current session rollout path (if known)
     current_rollout_path: Option<PathBuf>,
+    // Files the model registered as outputs, oldest first.
+    artifacts: Vec<Artifact>,
 }
 
 struct UserMessage {
@@ -549,9 +589,10 @@ impl ChatWidget {
         self.bottom_pane.set_task_running(true);
         self.retry_status_header = None;
         self.bottom_pane.set_interrupt_hint_visible(true);
-        self.set_status_header(String::from("Working"));
+        self.set_status_header(i18n::tr(Message::Working).to_string());
         self.full_reasoning_buffer.clear();
         self.reasoning_buffer.clear();
+        self.announce("turn started".to_string());
         self.request_redraw();
     }
 
@@ -564,6 +605,7 @@ impl ChatWidget {
         self.running_commands.clear();
         self.suppressed_exec_calls.clear();
         self.last_unified_wait = None;
+        self.announce("turn complete".to_string());
         self.request_redraw();
 
         // If there is a queued user message, send exactly one now to begin the next turn.
@@ -705,9 +747,13 @@ impl ChatWidget {
         self.model_family.clone()
     }
 
-    fn on_error(&mut self, message: String) {
+    fn on_error(&mut self, message: String, details: Option<ErrorDetails>) {
         self.finalize_turn();
-        self.add_to_history(history_cell::new_error_event(message));
+        let remediation = details.and_then(|details| details.remediation);
+        self.add_to_history(history_cell::new_error_event_with_hint(
+            message,
+            remediation,
+        ));
         self.request_redraw();
 
         // After an error ends the turn, try sending the next queued input.
@@ -829,6 +875,19 @@ impl ChatWidget {
         self.add_to_history(history_cell::new_plan_update(update));
     }
 
+    fn on_turn_progress(&mut self, ev: TurnProgressEvent) {
+        let progress = if ev.steps_total > 0 {
+            // Count the step in progress, not just the finished ones.
+            let step = (ev.steps_completed + 1).min(ev.steps_total);
+            format!("step {step}/{}", ev.steps_total)
+        } else if ev.tool_calls == 1 {
+            "1 tool call".to_string()
+        } else {
+            format!("{} tool calls", ev.tool_calls)
+        };
+        self.bottom_pane.update_status_progress(Some(progress));
+    }
+
     fn on_exec_approval_request(&mut self, id: String, ev: ExecApprovalRequestEvent) {
         let id2 = id.clone();
         let ev2 = ev.clone();
@@ -847,6 +906,276 @@ impl ChatWidget {
         );
     }
 
+    fn on_patch_conflict(&mut self, ev: PatchConflictEvent) {
+        let ev2 = ev.clone();
+        self.defer_or_handle(
+            |q| q.push_patch_conflict(ev),
+            |s| s.handle_patch_conflict_now(ev2),
+        );
+    }
+
+    fn on_protected_path_violation(&mut self, ev: ProtectedPathViolationEvent) {
+        let paths = ev
+            .violations
+            .iter()
+            .map(|violation| {
+                format!(
+                    "{} (`{}`)",
+                    display_path_for(&violation.path, &self.config.cwd),
+                    violation.pattern
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.on_warning(format!("Refused a patch to protected paths: {paths}"));
+    }
+
+    fn on_dependency_report(&mut self, ev: DependencyReportEvent) {
+        self.add_to_history(history_cell::new_dependency_report(
+            &ev.dependencies,
+            &self.config.cwd,
+        ));
+        self.request_redraw();
+    }
+
+    fn on_context_repaired(&mut self, ev: ContextRepairedEvent) {
+        let mut changes = ev.dropped;
+        if ev.compacted {
+            changes.push("compacted the history".to_string());
+        }
+        self.on_warning(format!(
+            "The provider rejected the request; retrying after repairing the context: {}.",
+            changes.join(", ")
+        ));
+    }
+
+    fn on_context_trimmed(&mut self, ev: ContextTrimmedEvent) {
+        self.add_info_message(ev.summary, None);
+    }
+
+    fn on_candidates_proposed(&mut self, ev: CandidatesProposedEvent) {
+        self.flush_answer_stream_with_separator();
+        let mut items: Vec<SelectionItem> = Vec::new();
+        for (index, candidate) in ev.candidates.iter().enumerate() {
+            let name = format!("Candidate {}", index + 1);
+            let mut lines: Vec<Line<'static>> = vec![name.clone().bold().into()];
+            lines.extend(
+                candidate
+                    .message
+                    .lines()
+                    .map(|line| Line::from(line.to_string())),
+            );
+            lines.extend(
+                candidate
+                    .tool_calls
+                    .iter()
+                    .map(|call| Line::from(format!("→ {call}").dim())),
+            );
+            self.add_plain_history_lines(lines);
+
+            let description = candidate
+                .message
+                .lines()
+                .find(|line| !line.trim().is_empty())
+                .or(candidate.tool_calls.first().map(String::as_str))
+                .map(str::to_string);
+            let turn_id = ev.turn_id.clone();
+            items.push(SelectionItem {
+                name,
+                description,
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::CodexOp(Op::SelectCandidate {
+                        id: turn_id.clone(),
+                        index: Some(index),
+                    }));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+        let turn_id = ev.turn_id;
+        items.push(SelectionItem {
+            name: "Let Codex decide".to_string(),
+            description: Some("A judge prompt picks the best candidate".to_string()),
+            actions: vec![Box::new(move |tx: &AppEventSender| {
+                tx.send(AppEvent::CodexOp(Op::SelectCandidate {
+                    id: turn_id.clone(),
+                    index: None,
+                }));
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Continue with which response?".to_string()),
+            subtitle: Some("None of their tool calls have run yet.".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    fn on_memory_candidates_proposed(&mut self, ev: MemoryCandidatesProposedEvent) {
+        let location = display_path_for(&ev.location, &self.config.cwd);
+        let mut lines: Vec<Line<'static>> = vec![
+            format!("Codex would remember, in {location}:")
+                .bold()
+                .into(),
+        ];
+        lines.extend(
+            ev.candidates
+                .iter()
+                .map(|candidate| Line::from(format!("  • {candidate}"))),
+        );
+        self.add_plain_history_lines(lines);
+
+        let review = |turn_id: String, accepted: Vec<usize>| -> SelectionAction {
+            Box::new(move |tx: &AppEventSender| {
+                tx.send(AppEvent::CodexOp(Op::ReviewMemories {
+                    id: turn_id.clone(),
+                    accepted: accepted.clone(),
+                }));
+            })
+        };
+        let count = ev.candidates.len();
+        let mut items = vec![SelectionItem {
+            name: if count == 1 {
+                "Save it".to_string()
+            } else {
+                format!("Save all {count}")
+            },
+            actions: vec![review(ev.turn_id.clone(), (0..count).collect())],
+            dismiss_on_select: true,
+            ..Default::default()
+        }];
+        if count > 1 {
+            items.extend(ev.candidates.iter().enumerate().map(|(index, candidate)| {
+                SelectionItem {
+                    name: format!("Save only {}", index + 1),
+                    description: Some(candidate.clone()),
+                    actions: vec![review(ev.turn_id.clone(), vec![index])],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            }));
+        }
+        items.push(SelectionItem {
+            name: "Don't save".to_string(),
+            actions: vec![review(ev.turn_id, Vec::new())],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Save these memories?".to_string()),
+            subtitle: Some(format!(
+                "They are saved anyway after {} seconds without an answer.",
+                ev.auto_accept_secs
+            )),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    fn on_candidate_selected(&mut self, ev: CandidateSelectedEvent) {
+        let hint = match ev.selected_by {
+            CandidateSelector::User => None,
+            CandidateSelector::Judge => Some("(picked by the judge prompt)".to_string()),
+        };
+        self.add_info_message(
+            format!(
+                "Continuing with candidate {} of {}",
+                ev.index + 1,
+                ev.candidates
+            ),
+            hint,
+        );
+    }
+
+    fn on_stalled(&mut self, ev: StalledEvent) {
+        let idle = fmt_elapsed_compact(ev.idle_ms / 1000);
+        if ev.retrying {
+            self.on_warning(format!(
+                "No response from the model for {idle}. Sending the request again."
+            ));
+            return;
+        }
+        self.on_warning(format!("No response from the model for {idle}."));
+        let items = vec![
+            SelectionItem {
+                name: "Keep waiting".to_string(),
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Interrupt".to_string(),
+                description: Some("Stop the turn".to_string()),
+                actions: vec![Box::new(|tx: &AppEventSender| {
+                    tx.send(AppEvent::CodexOp(Op::Interrupt));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Retry".to_string(),
+                description: Some("Stop the turn and run the last message again".to_string()),
+                actions: vec![Box::new(|tx: &AppEventSender| {
+                    tx.send(AppEvent::CodexOp(Op::Interrupt));
+                    tx.send(AppEvent::CodexOp(Op::Regenerate {
+                        model: None,
+                        effort: None,
+                    }));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("The turn seems stuck".to_string()),
+            subtitle: Some(format!("The model has sent nothing for {idle}.")),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    fn on_response_regenerated(&mut self, ev: ResponseRegeneratedEvent) {
+        self.add_info_message(
+            format!("Regenerating the last response with {}", ev.model),
+            None,
+        );
+    }
+
+    fn on_workflow_step_started(&mut self, ev: WorkflowStepStartedEvent) {
+        self.add_info_message(
+            format!(
+                "Workflow {}: step {}/{}",
+                ev.workflow,
+                ev.step + 1,
+                ev.steps
+            ),
+            Some(ev.prompt),
+        );
+    }
+
+    fn on_artifact_registered(&mut self, ev: ArtifactRegisteredEvent) {
+        self.add_to_history(history_cell::new_artifact_registered(
+            &ev.artifact,
+            &self.config.cwd,
+        ));
+        self.artifacts.push(ev.artifact);
+        self.request_redraw();
+    }
+
+    fn on_question(&mut self, ev: QuestionEvent) {
+        let ev2 = ev.clone();
+        self.defer_or_handle(|q| q.push_question(ev), |s| s.handle_question_now(ev2));
+    }
+
     fn on_elicitation_request(&mut self, ev: ElicitationRequestEvent) {
         let ev2 = ev.clone();
         self.defer_or_handle(
@@ -940,10 +1269,7 @@ impl ChatWidget {
 
     fn on_view_image_tool_call(&mut self, event: ViewImageToolCallEvent) {
         self.flush_answer_stream_with_separator();
-        self.add_to_history(history_cell::new_view_image_tool_call(
-            event.path,
-            &self.config.cwd,
-        ));
+        self.add_image_to_history(event.path, history_cell::new_view_image_tool_call);
         self.request_redraw();
     }
 
@@ -1085,6 +1411,42 @@ impl ChatWidget {
         }
     }
 
+    fn on_semantic_index_updated(&mut self, event: SemanticIndexUpdatedEvent) {
+        let SemanticIndexUpdatedEvent { success, message } = event;
+        if success {
+            self.add_info_message(message, None);
+        } else {
+            self.add_error_message(message);
+        }
+    }
+
+    fn on_session_summary(&mut self, event: SessionSummaryEvent) {
+        let markdown = event.markdown.trim();
+        if markdown.is_empty() {
+            self.add_error_message("The model returned an empty summary.".to_string());
+            return;
+        }
+        self.flush_answer_stream_with_separator();
+        self.flush_active_cell();
+        // Shown verbatim, without markdown rendering or indentation, so it
+        // can be copied straight into a pull request.
+        let mut lines: Vec<Line<'static>> =
+            vec![vec!["• ".dim(), "Session summary".bold()].into(), "".into()];
+        lines.extend(markdown.lines().map(|line| Line::from(line.to_string())));
+        self.add_to_history(PlainHistoryCell::new(lines));
+    }
+
+    /// The TUI already announces the setting changes it requests, so this
+    /// only keeps its copy of the sandbox policy in step with the session's.
+    fn on_turn_context_changed(&mut self, event: TurnContextChangedEvent) {
+        if let Some(change) = event.sandbox_policy
+            && change.new != self.config.sandbox_policy
+        {
+            self.app_event_tx
+                .send(AppEvent::UpdateSandboxPolicy(change.new));
+        }
+    }
+
     fn on_stream_error(&mut self, message: String) {
         if self.retry_status_header.is_none() {
             self.retry_status_header = Some(self.current_status_header.clone());
@@ -1189,6 +1551,7 @@ impl ChatWidget {
                 parsed,
                 source,
                 ev.interaction_input.clone(),
+                self.exec_cwd(&ev.cwd),
                 self.config.animations,
             )));
         }
@@ -1233,11 +1596,13 @@ impl ChatWidget {
         self.flush_answer_stream_with_separator();
         let command = shlex::try_join(ev.command.iter().map(String::as_str))
             .unwrap_or_else(|_| ev.command.join(" "));
+        self.announce(format!("approval needed to run `{command}`"));
         self.notify(Notification::ExecApprovalRequested { command });
 
         let request = ApprovalRequest::Exec {
             id,
             command: ev.command,
+            env: ev.env,
             reason: ev.reason,
             proposed_execpolicy_amendment: ev.proposed_execpolicy_amendment,
         };
@@ -1252,6 +1617,10 @@ impl ChatWidget {
         ev: ApplyPatchApprovalRequestEvent,
     ) {
         self.flush_answer_stream_with_separator();
+        self.announce(format!(
+            "approval needed to edit {} file(s)",
+            ev.changes.len()
+        ));
 
         let request = ApprovalRequest::ApplyPatch {
             id,
@@ -1268,8 +1637,36 @@ impl ChatWidget {
         });
     }
 
+    pub(crate) fn handle_patch_conflict_now(&mut self, ev: PatchConflictEvent) {
+        self.flush_answer_stream_with_separator();
+        self.announce(format!(
+            "{} file(s) changed since Codex last read them",
+            ev.conflicts.len()
+        ));
+
+        let request = ApprovalRequest::PatchConflict {
+            id: ev.turn_id,
+            cwd: self.config.cwd.clone(),
+            conflicts: ev.conflicts,
+        };
+        self.bottom_pane
+            .push_approval_request(request, &self.config.features);
+        self.request_redraw();
+    }
+
+    pub(crate) fn handle_question_now(&mut self, ev: QuestionEvent) {
+        self.flush_answer_stream_with_separator();
+        // Keep the question in the transcript once the prompt is gone.
+        self.add_info_message(format!("Codex asked: {}", ev.question), None);
+
+        let view = QuestionView::new(ev, self.app_event_tx.clone());
+        self.bottom_pane.show_view(Box::new(view));
+        self.request_redraw();
+    }
+
     pub(crate) fn handle_elicitation_request_now(&mut self, ev: ElicitationRequestEvent) {
         self.flush_answer_stream_with_separator();
+        self.announce(format!("{} needs your approval", ev.server_name));
 
         self.notify(Notification::ElicitationRequested {
             server_name: ev.server_name.clone(),
@@ -1327,6 +1724,7 @@ impl ChatWidget {
                 ev.parsed_cmd.clone(),
                 ev.source,
                 interaction_input.clone(),
+                self.exec_cwd(&ev.cwd),
             )
         {
             *cell = new_exec;
@@ -1339,6 +1737,7 @@ impl ChatWidget {
                 ev.parsed_cmd,
                 ev.source,
                 interaction_input,
+                self.exec_cwd(&ev.cwd),
                 self.config.animations,
             )));
         }
@@ -1346,6 +1745,12 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    /// The label of a command's working directory, when it is not the
+    /// session cwd.
+    fn exec_cwd(&self, cwd: &Path) -> Option<String> {
+        (cwd != self.config.cwd).then(|| display_path_for(cwd, &self.config.cwd))
+    }
+
     pub(crate) fn handle_mcp_begin_now(&mut self, ev: McpToolCallBeginEvent) {
         self.flush_answer_stream_with_separator();
         self.flush_active_cell();
@@ -1394,6 +1799,23 @@ impl ChatWidget {
     pub(crate) fn new(
         common: ChatWidgetInit,
         conversation_manager: Arc<ConversationManager>,
+    ) -> Self {
+        Self::with_agent(common, |config, app_event_tx| {
+            spawn_agent(config, app_event_tx, conversation_manager)
+        })
+    }
+
+    /// Create a ChatWidget for a conversation that runs in a remote app
+    /// server.
+    pub(crate) fn new_remote(common: ChatWidgetInit, remote: RemoteAppServer) -> Self {
+        Self::with_agent(common, |config, app_event_tx| {
+            spawn_remote_agent(config, remote, app_event_tx)
+        })
+    }
+
+    fn with_agent(
+        common: ChatWidgetInit,
+        spawn: impl FnOnce(Config, AppEventSender) -> UnboundedSender<Op>,
     ) -> Self {
         let ChatWidgetInit {
             config,
@@ -1413,7 +1835,7 @@ impl ChatWidget {
         config.model = Some(model_slug.clone());
         let mut rng = rand::rng();
         let placeholder = EXAMPLE_PROMPTS[rng.random_range(0..EXAMPLE_PROMPTS.len())].to_string();
-        let codex_op_tx = spawn_agent(config.clone(), app_event_tx.clone(), conversation_manager);
+        let codex_op_tx = spawn(config.clone(), app_event_tx.clone());
 
         let mut widget = Self {
             app_event_tx: app_event_tx.clone(),
@@ -1455,7 +1877,7 @@ impl ChatWidget {
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
-            current_status_header: String::from("Working"),
+            current_status_header: i18n::tr(Message::Working).to_string(),
             retry_status_header: None,
             conversation_id: None,
             queued_user_messages: VecDeque::new(),
@@ -1468,9 +1890,11 @@ impl ChatWidget {
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            artifacts: Vec::new(),
         };
 
         widget.prefetch_rate_limits();
+        widget.refresh_approval_preset();
 
         widget
     }
@@ -1541,7 +1965,7 @@ impl ChatWidget {
             interrupts: InterruptManager::new(),
             reasoning_buffer: String::new(),
             full_reasoning_buffer: String::new(),
-            current_status_header: String::from("Working"),
+            current_status_header: i18n::tr(Message::Working).to_string(),
             retry_status_header: None,
             conversation_id: None,
             queued_user_messages: VecDeque::new(),
@@ -1554,9 +1978,11 @@ impl ChatWidget {
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            artifacts: Vec::new(),
         };
 
         widget.prefetch_rate_limits();
+        widget.refresh_approval_preset();
 
         widget
     }
@@ -1631,6 +2057,9 @@ impl ChatWidget {
                     InputResult::Command(cmd) => {
                         self.dispatch_command(cmd);
                     }
+                    InputResult::CommandWithArgs(cmd, args) => {
+                        self.dispatch_command_with_args(cmd, args);
+                    }
                     InputResult::None => {}
                 }
             }
@@ -1652,7 +2081,9 @@ impl ChatWidget {
         self.request_redraw();
     }
 
-    fn dispatch_command(&mut self, cmd: SlashCommand) {
+    /// Returns true (and reports an error) when `cmd` cannot run while a task
+    /// is in progress.
+    fn reject_command_during_task(&mut self, cmd: SlashCommand) -> bool {
         if !cmd.available_during_task() && self.bottom_pane.is_task_running() {
             let message = format!(
                 "'/{}' is disabled while a task is in progress.",
@@ -1660,6 +2091,29 @@ impl ChatWidget {
             );
             self.add_to_history(history_cell::new_error_event(message));
             self.request_redraw();
+            return true;
+        }
+        false
+    }
+
+    fn dispatch_command_with_args(&mut self, cmd: SlashCommand, args: String) {
+        if self.reject_command_during_task(cmd) {
+            return;
+        }
+        match cmd {
+            SlashCommand::Effort => self.set_reasoning_effort_from_args(args.trim()),
+            SlashCommand::Verbosity => self.set_verbosity_from_args(args.trim()),
+            SlashCommand::Index => self.update_semantic_index_from_args(args.trim()),
+            SlashCommand::LogFilter => self.set_log_filter_from_args(args.trim()),
+            SlashCommand::Steer => self.steer_from_args(args.trim()),
+            SlashCommand::FileAt => self.show_file_at_turn_from_args(args.trim()),
+            SlashCommand::Cd => self.change_directory_from_args(args.trim()),
+            _ => self.dispatch_command(cmd),
+        }
+    }
+
+    fn dispatch_command(&mut self, cmd: SlashCommand) {
+        if self.reject_command_during_task(cmd) {
             return;
         }
         match cmd {
@@ -1676,6 +2130,9 @@ impl ChatWidget {
             SlashCommand::Resume => {
                 self.app_event_tx.send(AppEvent::OpenResumePicker);
             }
+            SlashCommand::Cd => {
+                self.change_directory_from_args("");
+            }
             SlashCommand::Init => {
                 let init_target = self.config.cwd.join(DEFAULT_PROJECT_DOC_FILENAME);
                 if init_target.exists() {
@@ -1692,12 +2149,52 @@ impl ChatWidget {
                 self.clear_token_usage();
                 self.app_event_tx.send(AppEvent::CodexOp(Op::Compact));
             }
+            SlashCommand::Summary => {
+                self.app_event_tx
+                    .send(AppEvent::CodexOp(Op::SummarizeSession));
+            }
             SlashCommand::Review => {
                 self.open_review_popup();
             }
             SlashCommand::Model => {
                 self.open_model_popup();
             }
+            SlashCommand::Effort => {
+                let current = self
+                    .config
+                    .model_reasoning_effort
+                    .map_or_else(|| "default".to_string(), ToString::to_string);
+                let levels = ReasoningEffortConfig::iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("|");
+                self.add_info_message(
+                    format!("Reasoning effort: {current}"),
+                    Some(format!("Usage: /effort <{levels}>")),
+                );
+            }
+            SlashCommand::Verbosity => {
+                let current = self
+                    .config
+                    .model_verbosity
+                    .map_or_else(|| "default".to_string(), ToString::to_string);
+                self.add_info_message(
+                    format!("Output verbosity: {current}"),
+                    Some("Usage: /verbosity <low|medium|high|default>".to_string()),
+                );
+            }
+            SlashCommand::Index => {
+                self.update_semantic_index_from_args("");
+            }
+            SlashCommand::LogFilter => {
+                self.set_log_filter_from_args("");
+            }
+            SlashCommand::Steer => {
+                self.steer_from_args("");
+            }
+            SlashCommand::FileAt => {
+                self.show_file_at_turn_from_args("");
+            }
             SlashCommand::Approvals => {
                 self.open_approvals_popup();
             }
@@ -1736,6 +2233,18 @@ impl ChatWidget {
                     tx.send(AppEvent::DiffResult(text));
                 });
             }
+            SlashCommand::Ci => {
+                self.add_info_message("Fetching the logs of the latest CI run…".to_string(), None);
+                let tx = self.app_event_tx.clone();
+                let issue_tracker = self.config.issue_tracker.clone();
+                let cwd = self.config.cwd.clone();
+                tokio::spawn(async move {
+                    let result = fetch_failing_ci_logs(&issue_tracker, &cwd)
+                        .await
+                        .map_err(|err| format!("{err:#}"));
+                    tx.send(AppEvent::CiLogsFetched(result));
+                });
+            }
             SlashCommand::Mention => {
                 self.insert_str("@");
             }
@@ -1748,6 +2257,9 @@ impl ChatWidget {
             SlashCommand::Ps => {
                 self.add_ps_output();
             }
+            SlashCommand::Artifacts => {
+                self.open_artifacts_popup();
+            }
             SlashCommand::Mcp => {
                 self.add_mcp_output();
             }
@@ -1802,6 +2314,183 @@ impl ChatWidget {
         }
     }
 
+    fn set_reasoning_effort_from_args(&mut self, args: &str) {
+        let Some(effort) = ReasoningEffortConfig::iter()
+            .find(|effort| effort.to_string().eq_ignore_ascii_case(args))
+        else {
+            self.add_error_message(format!("Unknown reasoning effort '{args}'."));
+            return;
+        };
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::OverrideTurnContext {
+                cwd: None,
+                approval_policy: None,
+                sandbox_policy: None,
+                model: None,
+                effort: Some(Some(effort)),
+                summary: None,
+                verbosity: None,
+            }));
+        self.app_event_tx
+            .send(AppEvent::UpdateReasoningEffort(Some(effort)));
+        self.add_info_message(
+            format!("Reasoning effort set to {effort} for this session."),
+            None,
+        );
+    }
+
+    fn set_verbosity_from_args(&mut self, args: &str) {
+        let verbosity = match args.to_ascii_lowercase().as_str() {
+            "low" => Some(Verbosity::Low),
+            "medium" => Some(Verbosity::Medium),
+            "high" => Some(Verbosity::High),
+            "default" => None,
+            _ => {
+                self.add_error_message(format!("Unknown verbosity '{args}'."));
+                return;
+            }
+        };
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::OverrideTurnContext {
+                cwd: None,
+                approval_policy: None,
+                sandbox_policy: None,
+                model: None,
+                effort: None,
+                summary: None,
+                verbosity: Some(verbosity),
+            }));
+        self.app_event_tx.send(AppEvent::UpdateVerbosity(verbosity));
+        let label = verbosity.map_or_else(|| "the model default".to_string(), ToString::to_string);
+        self.add_info_message(
+            format!("Output verbosity set to {label} for this session."),
+            None,
+        );
+    }
+
+    fn change_directory_from_args(&mut self, args: &str) {
+        if args.is_empty() {
+            self.add_info_message(
+                format!(
+                    "Working directory: {}",
+                    crate::status::format_directory_display(&self.config.cwd, None)
+                ),
+                Some("Usage: /cd <path>".to_string()),
+            );
+            return;
+        }
+        let path = match args.strip_prefix('~') {
+            Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+                match dirs::home_dir() {
+                    Some(home) => home.join(rest.trim_start_matches(['/', '\\'])),
+                    None => PathBuf::from(args),
+                }
+            }
+            _ => PathBuf::from(args),
+        };
+        self.submit_op(Op::ChangeDirectory { path });
+    }
+
+    fn on_working_directory_changed(&mut self, event: WorkingDirectoryChangedEvent) {
+        self.config.cwd = event.cwd;
+        self.add_info_message(
+            format!(
+                "Working directory changed to {}",
+                crate::status::format_directory_display(&self.config.cwd, None)
+            ),
+            None,
+        );
+    }
+
+    fn set_log_filter_from_args(&mut self, args: &str) {
+        if args.is_empty() {
+            self.add_info_message(
+                "Change which log lines are written to codex-tui.log for this run.".to_string(),
+                Some("Usage: /log-filter <directives> (e.g. codex_core=debug,info)".to_string()),
+            );
+            return;
+        }
+        self.submit_op(Op::SetLogFilter {
+            filter: args.to_string(),
+        });
+    }
+
+    fn steer_from_args(&mut self, args: &str) {
+        if args.is_empty() {
+            self.add_info_message(
+                "Guide the running turn without interrupting it.".to_string(),
+                Some("Usage: /steer <message> (e.g. /steer use pytest instead)".to_string()),
+            );
+            return;
+        }
+        if !self.bottom_pane.is_task_running() {
+            // Nothing to steer; send the message as a regular prompt.
+            self.submit_user_message(args.to_string().into());
+            return;
+        }
+        self.add_to_history(history_cell::new_user_prompt(args.to_string()));
+        self.submit_op(Op::Steer {
+            text: args.to_string(),
+        });
+    }
+
+    fn show_file_at_turn_from_args(&mut self, args: &str) {
+        let parsed = args
+            .split_once(char::is_whitespace)
+            .and_then(|(turn, path)| Some((turn.parse::<usize>().ok()?, path.trim())))
+            .filter(|(_, path)| !path.is_empty());
+        let Some((turn, path)) = parsed else {
+            self.add_info_message(
+                "Show a file as it was after an earlier turn of this session.".to_string(),
+                Some(
+                    "Usage: /file-at <turn> <path> (e.g. /file-at 3 src/main.rs; turn 0 is the start)"
+                        .to_string(),
+                ),
+            );
+            return;
+        };
+        self.submit_op(Op::GetFileAtTurn {
+            path: PathBuf::from(path),
+            turn,
+        });
+    }
+
+    fn on_file_at_turn_response(&mut self, ev: FileAtTurnResponseEvent) {
+        let display_path = display_path_for(&ev.path, &self.config.cwd);
+        if let Some(error) = ev.error {
+            self.add_error_message(format!(
+                "Could not show {display_path} after turn {}: {error}",
+                ev.turn
+            ));
+            return;
+        }
+        match ev.content {
+            Some(content) => self.app_event_tx.send(AppEvent::ShowFileAtTurn {
+                path: ev.path,
+                turn: ev.turn,
+                content,
+            }),
+            None => self.add_info_message(
+                format!("{display_path} did not exist after turn {}.", ev.turn),
+                None,
+            ),
+        }
+    }
+
+    fn update_semantic_index_from_args(&mut self, args: &str) {
+        let rebuild = match args.to_ascii_lowercase().as_str() {
+            "" => false,
+            "rebuild" => true,
+            _ => {
+                self.add_error_message(format!(
+                    "Unknown /index argument '{args}'. Usage: /index [rebuild]"
+                ));
+                return;
+            }
+        };
+        self.submit_op(Op::UpdateSemanticIndex { rebuild });
+    }
+
     pub(crate) fn handle_paste(&mut self, text: String) {
         self.bottom_pane.handle_paste(text);
     }
@@ -1853,10 +2542,48 @@ impl ChatWidget {
             .send(AppEvent::InsertHistoryCell(Box::new(cell)));
     }
 
+    /// In screen-reader mode, record a state change in the transcript so it is
+    /// read out rather than only shown in the status line.
+    fn announce(&mut self, message: String) {
+        if accessibility::screen_reader_enabled() {
+            self.add_to_history(accessibility::announcement(message));
+        }
+    }
+
     fn add_to_history(&mut self, cell: impl HistoryCell + 'static) {
         self.add_boxed_history(Box::new(cell));
     }
 
+    /// Add the cell `new_cell` builds for the image at `path`. The image is
+    /// only decoded when the terminal can draw it, and then off the UI
+    /// thread, so the cell is inserted once decoding finishes.
+    fn add_image_to_history(
+        &mut self,
+        path: PathBuf,
+        new_cell: fn(&Path, &Path, Option<DynamicImage>) -> ImageHistoryCell,
+    ) {
+        if capabilities().images.is_none() {
+            self.add_to_history(new_cell(&path, &self.config.cwd, None));
+            return;
+        }
+        self.flush_active_cell();
+        self.needs_final_message_separator = true;
+        let tx = self.app_event_tx.clone();
+        let cwd = self.config.cwd.clone();
+        tokio::spawn(async move {
+            let image = tokio::task::spawn_blocking({
+                let path = path.clone();
+                move || inline_image::load(&path)
+            })
+            .await
+            .ok()
+            .flatten();
+            tx.send(AppEvent::InsertHistoryCell(Box::new(new_cell(
+                &path, &cwd, image,
+            ))));
+        });
+    }
+
     fn add_boxed_history(&mut self, cell: Box<dyn HistoryCell>) {
         if !cell.display_lines(u16::MAX).is_empty() {
             // Only break exec grouping if the cell renders visible lines.
@@ -1905,8 +2632,8 @@ impl ChatWidget {
             items.push(UserInput::Text { text: text.clone() });
         }
 
-        for path in image_paths {
-            items.push(UserInput::LocalImage { path });
+        for path in &image_paths {
+            items.push(UserInput::LocalImage { path: path.clone() });
         }
 
         if let Some(skills) = self.bottom_pane.skills() {
@@ -1934,10 +2661,13 @@ impl ChatWidget {
                 });
         }
 
-        // Only show the text portion in conversation history.
+        // Show the text, then each attached image.
         if !text.is_empty() {
             self.add_to_history(history_cell::new_user_prompt(text));
         }
+        for path in image_paths {
+            self.add_image_to_history(path, history_cell::new_attached_image);
+        }
         self.needs_final_message_separator = false;
     }
 
@@ -2007,7 +2737,9 @@ impl ChatWidget {
                 self.on_rate_limit_snapshot(ev.rate_limits);
             }
             EventMsg::Warning(WarningEvent { message }) => self.on_warning(message),
-            EventMsg::Error(ErrorEvent { message, .. }) => self.on_error(message),
+            EventMsg::Error(ErrorEvent {
+                message, details, ..
+            }) => self.on_error(message, details),
             EventMsg::McpStartupUpdate(ev) => self.on_mcp_startup_update(ev),
             EventMsg::McpStartupComplete(ev) => self.on_mcp_startup_complete(ev),
             EventMsg::TurnAborted(ev) => match ev.reason {
@@ -2015,7 +2747,7 @@ impl ChatWidget {
                     self.on_interrupted_turn(ev.reason);
                 }
                 TurnAbortReason::Replaced => {
-                    self.on_error("Turn aborted: replaced by a new task".to_owned())
+                    self.on_error("Turn aborted: replaced by a new task".to_owned(), None)
                 }
                 TurnAbortReason::ReviewEnded => {
                     self.on_interrupted_turn(ev.reason);
@@ -2029,6 +2761,20 @@ impl ChatWidget {
             EventMsg::ApplyPatchApprovalRequest(ev) => {
                 self.on_apply_patch_approval_request(id.unwrap_or_default(), ev)
             }
+            EventMsg::PatchConflict(ev) => self.on_patch_conflict(ev),
+            EventMsg::Question(ev) => self.on_question(ev),
+            EventMsg::ProtectedPathViolation(ev) => self.on_protected_path_violation(ev),
+            EventMsg::DependencyReport(ev) => self.on_dependency_report(ev),
+            EventMsg::ArtifactRegistered(ev) => self.on_artifact_registered(ev),
+            EventMsg::ContextRepaired(ev) => self.on_context_repaired(ev),
+            EventMsg::ContextTrimmed(ev) => self.on_context_trimmed(ev),
+            EventMsg::ResponseRegenerated(ev) => self.on_response_regenerated(ev),
+            EventMsg::WorkflowStepStarted(ev) => self.on_workflow_step_started(ev),
+            EventMsg::TurnProgress(ev) => self.on_turn_progress(ev),
+            EventMsg::Stalled(ev) => self.on_stalled(ev),
+            EventMsg::CandidatesProposed(ev) => self.on_candidates_proposed(ev),
+            EventMsg::CandidateSelected(ev) => self.on_candidate_selected(ev),
+            EventMsg::MemoryCandidatesProposed(ev) => self.on_memory_candidates_proposed(ev),
             EventMsg::ElicitationRequest(ev) => {
                 self.on_elicitation_request(ev);
             }
@@ -2044,6 +2790,7 @@ impl ChatWidget {
             EventMsg::WebSearchBegin(ev) => self.on_web_search_begin(ev),
             EventMsg::WebSearchEnd(ev) => self.on_web_search_end(ev),
             EventMsg::GetHistoryEntryResponse(ev) => self.on_get_history_entry_response(ev),
+            EventMsg::FileAtTurnResponse(ev) => self.on_file_at_turn_response(ev),
             EventMsg::McpListToolsResponse(ev) => self.on_list_mcp_tools(ev),
             EventMsg::ListCustomPromptsResponse(ev) => self.on_list_custom_prompts(ev),
             EventMsg::ListSkillsResponse(ev) => self.on_list_skills(ev),
@@ -2054,13 +2801,19 @@ impl ChatWidget {
                 });
             }
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
-            EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
+            EventMsg::TurnDiff(TurnDiffEvent { unified_diff, .. }) => {
+                self.on_turn_diff(unified_diff)
+            }
             EventMsg::DeprecationNotice(ev) => self.on_deprecation_notice(ev),
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 self.on_background_event(message)
             }
             EventMsg::UndoStarted(ev) => self.on_undo_started(ev),
             EventMsg::UndoCompleted(ev) => self.on_undo_completed(ev),
+            EventMsg::SemanticIndexUpdated(ev) => self.on_semantic_index_updated(ev),
+            EventMsg::SessionSummary(ev) => self.on_session_summary(ev),
+            EventMsg::TurnContextChanged(ev) => self.on_turn_context_changed(ev),
+            EventMsg::WorkingDirectoryChanged(ev) => self.on_working_directory_changed(ev),
             EventMsg::StreamError(StreamErrorEvent { message, .. }) => {
                 self.on_stream_error(message)
             }
@@ -2075,11 +2828,17 @@ impl ChatWidget {
             EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
             EventMsg::ContextCompacted(_) => self.on_agent_message("Context compacted".to_owned()),
             EventMsg::RawResponseItem(_)
+            | EventMsg::ContextManifest(_)
+            // The TUI already announces the setting changes it requests.
+            | EventMsg::TurnCompletedMetrics(_)
             | EventMsg::ItemStarted(_)
             | EventMsg::ItemCompleted(_)
+            | EventMsg::ItemAnnotated(_)
+            | EventMsg::AnnotationRemoved(_)
             | EventMsg::AgentMessageContentDelta(_)
             | EventMsg::ReasoningContentDelta(_)
-            | EventMsg::ReasoningRawContentDelta(_) => {}
+            | EventMsg::ReasoningRawContentDelta(_)
+            | EventMsg::StructuredOutputDelta(_) => {}
         }
     }
 
@@ -2204,6 +2963,28 @@ impl ChatWidget {
         self.request_redraw();
     }
 
+    pub(crate) fn on_ci_logs_fetched(&mut self, result: Result<Option<String>, String>) {
+        match result {
+            Ok(Some(logs)) => {
+                self.queue_user_message(
+                    format!(
+                        "CI is failing on this branch. Find out why from these logs, and fix it if the cause is in this repository.\n\n{logs}"
+                    )
+                    .into(),
+                );
+            }
+            Ok(None) => {
+                self.add_info_message(
+                    "The latest CI runs for this commit did not fail.".to_string(),
+                    None,
+                );
+            }
+            Err(err) => {
+                self.add_error_message(format!("Failed to fetch CI logs: {err}"));
+            }
+        }
+    }
+
     pub(crate) fn add_status_output(&mut self) {
         let default_usage = TokenUsage::default();
         let (total_usage, context_usage) = if let Some(ti) = &self.token_info {
@@ -2314,6 +3095,7 @@ impl ChatWidget {
                 model: Some(switch_model.clone()),
                 effort: Some(Some(default_effort)),
                 summary: None,
+                verbosity: None,
             }));
             tx.send(AppEvent::UpdateModel(switch_model.clone()));
             tx.send(AppEvent::UpdateReasoningEffort(Some(default_effort)));
@@ -2525,7 +3307,7 @@ impl ChatWidget {
     ) -> Vec<SelectionAction> {
         vec![Box::new(move |tx| {
             let effort_label = effort_for_action
-                .map(|effort| effort.to_string())
+                .map(ToString::to_string)
                 .unwrap_or_else(|| "default".to_string());
             tx.send(AppEvent::CodexOp(Op::OverrideTurnContext {
                 cwd: None,
@@ -2534,6 +3316,7 @@ impl ChatWidget {
                 model: Some(model_for_action.clone()),
                 effort: Some(effort_for_action),
                 summary: None,
+                verbosity: None,
             }));
             tx.send(AppEvent::UpdateModel(model_for_action.clone()));
             tx.send(AppEvent::UpdateReasoningEffort(effort_for_action));
@@ -2705,6 +3488,7 @@ impl ChatWidget {
                 model: Some(model.clone()),
                 effort: Some(effort),
                 summary: None,
+                verbosity: None,
             }));
         self.app_event_tx.send(AppEvent::UpdateModel(model.clone()));
         self.app_event_tx
@@ -2722,6 +3506,110 @@ impl ChatWidget {
         );
     }
 
+    /// Open a popup listing the artifacts registered in this session.
+    pub(crate) fn open_artifacts_popup(&mut self) {
+        if self.artifacts.is_empty() {
+            self.add_info_message(
+                "No artifacts have been registered in this session.".to_string(),
+                Some(
+                    "Enable the `artifacts` feature to let Codex register its outputs.".to_string(),
+                ),
+            );
+            return;
+        }
+        let items = self
+            .artifacts
+            .iter()
+            .rev()
+            .map(|artifact| {
+                let selected = artifact.clone();
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::OpenArtifactActions {
+                        artifact: selected.clone(),
+                    });
+                })];
+                SelectionItem {
+                    name: artifact.title.clone(),
+                    description: Some(display_path_for(&artifact.path, &self.config.cwd)),
+                    selected_description: artifact.description.clone(),
+                    actions,
+                    dismiss_on_select: true,
+                    search_value: Some(artifact.title.clone()),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Artifacts".to_string()),
+            subtitle: Some("Files Codex registered as outputs, newest first".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search artifacts".to_string()),
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn open_artifact_actions_popup(&mut self, artifact: Artifact) {
+        let open_path = artifact.path.clone();
+        let save_path = artifact.path.clone();
+        let items = vec![
+            SelectionItem {
+                name: "Open".to_string(),
+                description: Some("open with the default application".to_string()),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::OpenArtifact {
+                        path: open_path.clone(),
+                    });
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Save".to_string(),
+                description: Some(format!("copy into {}", self.config.cwd.display())),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::SaveArtifact {
+                        path: save_path.clone(),
+                    });
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(artifact.title),
+            subtitle: Some(display_path_for(&artifact.path, &self.config.cwd)),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn open_artifact(&mut self, path: &Path) {
+        let display_path = display_path_for(path, &self.config.cwd);
+        match artifacts::open_with_default_app(path) {
+            Ok(()) => self.add_info_message(format!("Opened {display_path}"), None),
+            Err(err) => self.add_error_message(format!("Failed to open {display_path}: {err}")),
+        }
+    }
+
+    pub(crate) fn save_artifact(&mut self, path: &Path) {
+        let display_path = display_path_for(path, &self.config.cwd);
+        match artifacts::save_to_dir(path, &self.config.cwd) {
+            Ok(saved) => self.add_info_message(
+                format!(
+                    "Saved {display_path} to {}",
+                    display_path_for(&saved, &self.config.cwd)
+                ),
+                None,
+            ),
+            Err(err) => self.add_error_message(format!("Failed to save {display_path}: {err}")),
+        }
+    }
+
     /// Open a popup to choose the approvals mode (ask for approval policy + sandbox policy).
     pub(crate) fn open_approvals_popup(&mut self) {
         let current_approval = self.config.approval_policy.value();
@@ -2737,7 +3625,7 @@ impl ChatWidget {
                 Ok(()) => None,
                 Err(err) => Some(err.to_string()),
             };
-            let requires_confirmation = preset.id == "full-access"
+            let requires_confirmation = preset.id == ApprovalPresetId::Yolo
                 && !self
                     .config
                     .notices
@@ -2750,7 +3638,7 @@ impl ChatWidget {
                         preset: preset_clone.clone(),
                     });
                 })]
-            } else if preset.id == "auto" {
+            } else if matches!(preset.sandbox, SandboxPolicy::WorkspaceWrite { .. }) {
                 #[cfg(target_os = "windows")]
                 {
                     if codex_core::get_platform_sandbox().is_none() {
@@ -2828,14 +3716,21 @@ impl ChatWidget {
     ) -> Vec<SelectionAction> {
         vec![Box::new(move |tx| {
             let sandbox_clone = sandbox.clone();
-            tx.send(AppEvent::CodexOp(Op::OverrideTurnContext {
-                cwd: None,
-                approval_policy: Some(approval),
-                sandbox_policy: Some(sandbox_clone.clone()),
-                model: None,
-                effort: None,
-                summary: None,
-            }));
+            // A preset keeps the writable roots of a workspace-write sandbox
+            // already in effect; `on_turn_context_changed` picks them up.
+            let op = match ApprovalPresetId::matching(approval, &sandbox_clone) {
+                Some(preset) => Op::SetApprovalPreset { preset },
+                None => Op::OverrideTurnContext {
+                    cwd: None,
+                    approval_policy: Some(approval),
+                    sandbox_policy: Some(sandbox_clone.clone()),
+                    model: None,
+                    effort: None,
+                    summary: None,
+                    verbosity: None,
+                },
+            };
+            tx.send(AppEvent::CodexOp(op));
             tx.send(AppEvent::UpdateAskForApprovalPolicy(approval));
             tx.send(AppEvent::UpdateSandboxPolicy(sandbox_clone));
         })]
@@ -2846,21 +3741,7 @@ impl ChatWidget {
         current_sandbox: &SandboxPolicy,
         preset: &ApprovalPreset,
     ) -> bool {
-        if current_approval != preset.approval {
-            return false;
-        }
-        matches!(
-            (&preset.sandbox, current_sandbox),
-            (SandboxPolicy::ReadOnly, SandboxPolicy::ReadOnly)
-                | (
-                    SandboxPolicy::DangerFullAccess,
-                    SandboxPolicy::DangerFullAccess
-                )
-                | (
-                    SandboxPolicy::WorkspaceWrite { .. },
-                    SandboxPolicy::WorkspaceWrite { .. }
-                )
-        )
+        ApprovalPresetId::matching(current_approval, current_sandbox) == Some(preset.id)
     }
 
     #[cfg(target_os = "windows")]
@@ -3125,7 +4006,7 @@ impl ChatWidget {
             && codex_core::get_platform_sandbox().is_none()
             && let Some(preset) = builtin_approval_presets()
                 .into_iter()
-                .find(|preset| preset.id == "auto")
+                .find(|preset| preset.id == ApprovalPresetId::FullAuto)
         {
             self.open_windows_sandbox_enable_prompt(preset);
         }
@@ -3143,11 +4024,22 @@ impl ChatWidget {
     #[allow(dead_code)]
     pub(crate) fn clear_forced_auto_mode_downgrade(&mut self) {}
 
+    /// Show the preset the current approval and sandbox policies match in the
+    /// footer.
+    fn refresh_approval_preset(&mut self) {
+        self.bottom_pane
+            .set_approval_preset(ApprovalPresetId::matching(
+                self.config.approval_policy.value(),
+                &self.config.sandbox_policy,
+            ));
+    }
+
     /// Set the approval policy in the widget's config copy.
     pub(crate) fn set_approval_policy(&mut self, policy: AskForApproval) {
         if let Err(err) = self.config.approval_policy.set(policy) {
             tracing::warn!(%err, "failed to set approval_policy on chat config");
         }
+        self.refresh_approval_preset();
     }
 
     /// Set the sandbox policy in the widget's config copy.
@@ -3157,6 +4049,7 @@ impl ChatWidget {
             || codex_core::get_platform_sandbox().is_some();
 
         self.config.sandbox_policy = policy;
+        self.refresh_approval_preset();
 
         #[cfg(target_os = "windows")]
         if should_clear_downgrade {
@@ -3200,6 +4093,10 @@ impl ChatWidget {
         self.config.model_reasoning_effort = effort;
     }
 
+    pub(crate) fn set_verbosity(&mut self, verbosity: Option<Verbosity>) {
+        self.config.model_verbosity = verbosity;
+    }
+
     /// Set the model in the widget's config copy.
     pub(crate) fn set_model(&mut self, model: &str, model_family: ModelFamily) {
         self.session_header.set_model(model);
@@ -3280,6 +4177,7 @@ impl ChatWidget {
     pub(crate) fn submit_op(&self, op: Op) {
         // Record outbound operation for session replay fidelity.
         crate::session_log::log_outbound_op(&op);
+        crate::crash_report::record_op(&op);
         if let Err(e) = self.codex_op_tx.send(op) {
             tracing::error!("failed to submit op: {e}");
         }
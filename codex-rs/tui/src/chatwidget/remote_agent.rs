@@ -0,0 +1,864 @@
+//! Agent loop for `--remote`: the conversation runs in an app server on
+//! another machine, started by a command such as
+//! `codex app-server ssh me@devbox`, and the TUI only renders it. Ops from
+//! the UI become app-server requests on the command's stdin, and the
+//! `codex/event/*` notifications on its stdout become the events the UI
+//! renders, so paths, commands and diffs all stay on the server.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicI64;
+use std::sync::atomic::Ordering;
+
+use codex_app_server_protocol::ApprovalDecision;
+use codex_app_server_protocol::AskForApproval as V2AskForApproval;
+use codex_app_server_protocol::ClientInfo;
+use codex_app_server_protocol::ClientRequest;
+use codex_app_server_protocol::CommandExecutionRequestApprovalResponse;
+use codex_app_server_protocol::FileChangeRequestApprovalResponse;
+use codex_app_server_protocol::InitializeParams;
+use codex_app_server_protocol::InitializeResponse;
+use codex_app_server_protocol::JSONRPCError;
+use codex_app_server_protocol::JSONRPCErrorError;
+use codex_app_server_protocol::JSONRPCMessage;
+use codex_app_server_protocol::JSONRPCNotification;
+use codex_app_server_protocol::JSONRPCResponse;
+use codex_app_server_protocol::MemoryCandidatesRequestReviewResponse;
+use codex_app_server_protocol::RequestId;
+use codex_app_server_protocol::SandboxMode as V2SandboxMode;
+use codex_app_server_protocol::SandboxPolicy as V2SandboxPolicy;
+use codex_app_server_protocol::ServerRequest;
+use codex_app_server_protocol::ThreadStartParams;
+use codex_app_server_protocol::ThreadStartResponse;
+use codex_app_server_protocol::TurnInterruptParams;
+use codex_app_server_protocol::TurnInterruptResponse;
+use codex_app_server_protocol::TurnStartParams;
+use codex_app_server_protocol::TurnStartResponse;
+use codex_app_server_protocol::UserInput as V2UserInput;
+use codex_core::config::Config;
+use codex_core::protocol::ErrorEvent;
+use codex_core::protocol::Event;
+use codex_core::protocol::EventMsg;
+use codex_core::protocol::Op;
+use codex_core::protocol::ReviewDecision;
+use codex_core::protocol::SandboxPolicy;
+use codex_core::protocol::SessionConfiguredEvent;
+use codex_core::protocol::WarningEvent;
+use codex_protocol::ConversationId;
+use codex_protocol::config_types::ReasoningSummary;
+use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::user_input::UserInput;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tokio::io::AsyncBufReadExt;
+use tokio::io::AsyncWriteExt;
+use tokio::io::BufReader;
+use tokio::process::ChildStdout;
+use tokio::process::Command;
+use tokio::sync::Mutex;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::mpsc::unbounded_channel;
+use tokio::sync::oneshot;
+
+use crate::app_event::AppEvent;
+use crate::app_event_sender::AppEventSender;
+
+/// An app server the TUI connects to instead of running the agent itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct RemoteAppServer {
+    /// Program and arguments that start the server speaking JSON-RPC on
+    /// stdin and stdout.
+    command: Vec<String>,
+    /// Working directory on the server's machine; the server's own when
+    /// unset.
+    cwd: Option<String>,
+}
+
+impl RemoteAppServer {
+    /// `command` is split like a shell command line.
+    pub(crate) fn parse(command: &str, cwd: Option<PathBuf>) -> Result<Self, String> {
+        let command = shlex::split(command)
+            .filter(|command| !command.is_empty())
+            .ok_or_else(|| format!("cannot parse the --remote command `{command}`"))?;
+        Ok(Self {
+            command,
+            cwd: cwd.map(|cwd| cwd.display().to_string()),
+        })
+    }
+}
+
+/// Start the remote app server and the loops that drive it, returning the
+/// `UnboundedSender<Op>` used by the UI to submit operations.
+pub(crate) fn spawn_remote_agent(
+    config: Config,
+    remote: RemoteAppServer,
+    app_event_tx: AppEventSender,
+) -> UnboundedSender<Op> {
+    let (codex_op_tx, codex_op_rx) = unbounded_channel::<Op>();
+
+    tokio::spawn(async move {
+        if let Err(message) =
+            run_remote_agent(config, remote, app_event_tx.clone(), codex_op_rx).await
+        {
+            tracing::error!("remote app server: {message}");
+            app_event_tx.send(AppEvent::CodexEvent(error_event(message)));
+        }
+    });
+
+    codex_op_tx
+}
+
+async fn run_remote_agent(
+    config: Config,
+    remote: RemoteAppServer,
+    app_event_tx: AppEventSender,
+    mut codex_op_rx: UnboundedReceiver<Op>,
+) -> Result<(), String> {
+    let Some((program, args)) = remote.command.split_first() else {
+        return Err("the --remote command is empty".to_string());
+    };
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|err| format!("failed to start `{}`: {err}", remote.command.join(" ")))?;
+    let (Some(mut stdin), Some(stdout), Some(stderr)) =
+        (child.stdin.take(), child.stdout.take(), child.stderr.take())
+    else {
+        return Err("the remote app server's stdio is unavailable".to_string());
+    };
+
+    // Writing to the terminal would draw over the UI, so the server's
+    // stderr goes to the log.
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(stderr).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            tracing::warn!("remote: {line}");
+        }
+    });
+
+    let (outgoing_tx, mut outgoing_rx) = unbounded_channel::<Value>();
+    tokio::spawn(async move {
+        while let Some(message) = outgoing_rx.recv().await {
+            let line = format!("{message}\n");
+            if stdin.write_all(line.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let connection = Arc::new(Connection {
+        outgoing: outgoing_tx,
+        pending: Mutex::new(HashMap::new()),
+        next_id: AtomicI64::new(0),
+    });
+    let approvals = Arc::new(Mutex::new(Approvals::default()));
+    let closing = Arc::new(AtomicBool::new(false));
+    // Events wait here until `SessionConfigured` has gone to the UI.
+    let (event_tx, mut event_rx) = unbounded_channel::<Event>();
+    tokio::spawn(read_messages(
+        stdout,
+        Arc::clone(&connection),
+        Arc::clone(&approvals),
+        event_tx.clone(),
+        Arc::clone(&closing),
+    ));
+
+    connection
+        .request::<InitializeResponse>(|request_id| ClientRequest::Initialize {
+            request_id,
+            params: InitializeParams {
+                client_info: ClientInfo {
+                    name: "codex_tui".to_string(),
+                    title: Some("Codex TUI".to_string()),
+                    version: env!("CARGO_PKG_VERSION").to_string(),
+                },
+                heartbeat_interval_secs: None,
+            },
+        })
+        .await?;
+    let started = connection
+        .request::<ThreadStartResponse>(|request_id| ClientRequest::ThreadStart {
+            request_id,
+            params: ThreadStartParams {
+                model: config.model.clone(),
+                cwd: remote.cwd.clone(),
+                approval_policy: Some(V2AskForApproval::from(config.approval_policy.value())),
+                sandbox: Some(sandbox_mode(&config.sandbox_policy)),
+                ..Default::default()
+            },
+        })
+        .await?;
+    let thread_id = started.thread.id.clone();
+    let session_configured = session_configured(started)?;
+    app_event_tx.send(AppEvent::CodexEvent(Event {
+        id: String::new(),
+        msg: EventMsg::SessionConfigured(session_configured),
+    }));
+
+    let app_event_tx_clone = app_event_tx.clone();
+    tokio::spawn(async move {
+        while let Some(event) = event_rx.recv().await {
+            app_event_tx_clone.send(AppEvent::CodexEvent(event));
+        }
+    });
+
+    let mut overrides = TurnOverrides::default();
+    let mut active_turn: Option<String> = None;
+    while let Some(op) = codex_op_rx.recv().await {
+        match op {
+            Op::UserInput { items } => {
+                let params = TurnStartParams {
+                    thread_id: thread_id.clone(),
+                    input: items.into_iter().filter_map(remote_user_input).collect(),
+                    cwd: overrides.cwd.take(),
+                    approval_policy: overrides.approval_policy.take(),
+                    sandbox_policy: overrides.sandbox_policy.take(),
+                    model: overrides.model.take(),
+                    effort: overrides.effort.take(),
+                    summary: overrides.summary.take(),
+                };
+                match connection
+                    .request::<TurnStartResponse>(|request_id| ClientRequest::TurnStart {
+                        request_id,
+                        params,
+                    })
+                    .await
+                {
+                    Ok(response) => active_turn = Some(response.turn.id),
+                    Err(message) => {
+                        let _ = event_tx.send(error_event(format!(
+                            "failed to start a turn on the remote app server: {message}"
+                        )));
+                    }
+                }
+            }
+            Op::OverrideTurnContext {
+                cwd,
+                approval_policy,
+                sandbox_policy,
+                model,
+                effort,
+                summary,
+                ..
+            } => overrides.merge(cwd, approval_policy, sandbox_policy, model, effort, summary),
+            Op::Interrupt => {
+                let Some(turn_id) = active_turn.clone() else {
+                    continue;
+                };
+                let result = connection
+                    .request::<TurnInterruptResponse>(|request_id| ClientRequest::TurnInterrupt {
+                        request_id,
+                        params: TurnInterruptParams {
+                            thread_id: thread_id.clone(),
+                            turn_id,
+                        },
+                    })
+                    .await;
+                if let Err(message) = result {
+                    tracing::warn!("failed to interrupt the remote turn: {message}");
+                }
+            }
+            Op::ExecApproval { id, decision } => {
+                let answer = approvals.lock().await.answer(&id, decision);
+                if let Some((request, decision)) = answer {
+                    request.respond(&connection, decision);
+                }
+            }
+            Op::PatchApproval { id, decision } => {
+                let answer = approvals.lock().await.answer(&id, decision);
+                if let Some((request, decision)) = answer {
+                    request.respond(&connection, decision);
+                }
+            }
+            Op::ReviewMemories { id, accepted } => {
+                let answer = approvals.lock().await.answer_memory_review(id, accepted);
+                if let Some((request_id, accepted)) = answer {
+                    connection.respond(
+                        request_id,
+                        &MemoryCandidatesRequestReviewResponse { accepted },
+                    );
+                }
+            }
+            Op::Shutdown => {
+                closing.store(true, Ordering::Relaxed);
+                let _ = child.kill().await;
+                let _ = event_tx.send(Event {
+                    id: String::new(),
+                    msg: EventMsg::ShutdownComplete,
+                });
+                return Ok(());
+            }
+            // Sent in the background for local features the app server does
+            // not offer; the UI goes without them.
+            Op::AddToHistory { .. }
+            | Op::GetHistoryEntryRequest { .. }
+            | Op::ListCustomPrompts
+            | Op::ListSkills { .. }
+            | Op::ListMcpTools
+            | Op::SetLogFilter { .. }
+            | Op::SetReasoningDisplay { .. }
+            | Op::UpdateSemanticIndex { .. } => {}
+            _ => {
+                let _ = event_tx.send(Event {
+                    id: String::new(),
+                    msg: EventMsg::Warning(WarningEvent {
+                        message:
+                            "This action is not available when connected to a remote app server."
+                                .to_string(),
+                    }),
+                });
+            }
+        }
+    }
+
+    closing.store(true, Ordering::Relaxed);
+    Ok(())
+}
+
+/// Route the server's messages: responses to the requests waiting for them,
+/// `codex/event/*` notifications to the UI, and approval requests to
+/// [`Approvals`].
+async fn read_messages(
+    stdout: ChildStdout,
+    connection: Arc<Connection>,
+    approvals: Arc<Mutex<Approvals>>,
+    event_tx: UnboundedSender<Event>,
+    closing: Arc<AtomicBool>,
+) {
+    let mut lines = BufReader::new(stdout).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let message = match serde_json::from_str::<JSONRPCMessage>(&line) {
+            Ok(message) => message,
+            Err(_) => {
+                tracing::warn!("remote: {line}");
+                continue;
+            }
+        };
+        match message {
+            JSONRPCMessage::Response(JSONRPCResponse { id, result }) => {
+                connection.resolve(id, Ok(result)).await;
+            }
+            JSONRPCMessage::Error(JSONRPCError { id, error }) => {
+                connection.resolve(id, Err(error)).await;
+            }
+            JSONRPCMessage::Notification(notification) => {
+                if let Some(event) = event_from_notification(notification) {
+                    approvals.lock().await.record_event(&event);
+                    let _ = event_tx.send(event);
+                }
+            }
+            JSONRPCMessage::Request(request) => {
+                let request_id = request.id.clone();
+                let (call_id, request) = match ServerRequest::try_from(request) {
+                    Ok(ServerRequest::CommandExecutionRequestApproval { request_id, params }) => {
+                        (params.item_id, ApprovalRequest::Exec(request_id))
+                    }
+                    Ok(ServerRequest::FileChangeRequestApproval { request_id, params }) => {
+                        (params.item_id, ApprovalRequest::Patch(request_id))
+                    }
+                    Ok(ServerRequest::MemoryCandidatesRequestReview { request_id, params }) => {
+                        let answer = approvals
+                            .lock()
+                            .await
+                            .record_memory_request(params.turn_id, request_id);
+                        if let Some((request_id, accepted)) = answer {
+                            connection.respond(
+                                request_id,
+                                &MemoryCandidatesRequestReviewResponse { accepted },
+                            );
+                        }
+                        continue;
+                    }
+                    _ => {
+                        connection.respond_error(request_id, "not supported by the Codex TUI");
+                        continue;
+                    }
+                };
+                let answer = approvals.lock().await.record_request(call_id, request);
+                if let Some((request, decision)) = answer {
+                    request.respond(&connection, decision);
+                }
+            }
+        }
+    }
+
+    if !closing.load(Ordering::Relaxed) {
+        let _ = event_tx.send(error_event("The remote app server has exited.".to_string()));
+    }
+}
+
+type PendingResponse = oneshot::Sender<Result<Value, JSONRPCErrorError>>;
+
+/// The client side of the JSON-RPC connection.
+struct Connection {
+    outgoing: UnboundedSender<Value>,
+    pending: Mutex<HashMap<RequestId, PendingResponse>>,
+    next_id: AtomicI64,
+}
+
+impl Connection {
+    async fn request<T: DeserializeOwned>(
+        &self,
+        request: impl FnOnce(RequestId) -> ClientRequest,
+    ) -> Result<T, String> {
+        let request_id = RequestId::Integer(self.next_id.fetch_add(1, Ordering::Relaxed));
+        let (tx, rx) = oneshot::channel();
+        self.pending.lock().await.insert(request_id.clone(), tx);
+        let request = serde_json::to_value(request(request_id)).map_err(|err| err.to_string())?;
+        self.outgoing
+            .send(request)
+            .map_err(|_| "the remote app server has exited".to_string())?;
+        let result = rx
+            .await
+            .map_err(|_| "the remote app server has exited".to_string())?
+            .map_err(|error| error.message)?;
+        serde_json::from_value(result).map_err(|err| err.to_string())
+    }
+
+    async fn resolve(&self, request_id: RequestId, result: Result<Value, JSONRPCErrorError>) {
+        if let Some(tx) = self.pending.lock().await.remove(&request_id) {
+            let _ = tx.send(result);
+        }
+    }
+
+    fn respond<T: Serialize>(&self, request_id: RequestId, response: &T) {
+        match serde_json::to_value(response) {
+            Ok(result) => self.send(JSONRPCMessage::Response(JSONRPCResponse {
+                id: request_id,
+                result,
+            })),
+            Err(err) => tracing::error!("failed to serialize a response: {err}"),
+        }
+    }
+
+    fn respond_error(&self, request_id: RequestId, message: &str) {
+        self.send(JSONRPCMessage::Error(JSONRPCError {
+            id: request_id,
+            error: JSONRPCErrorError {
+                code: -32601,
+                message: message.to_string(),
+                data: None,
+            },
+        }));
+    }
+
+    fn send(&self, message: JSONRPCMessage) {
+        match serde_json::to_value(message) {
+            Ok(message) => {
+                let _ = self.outgoing.send(message);
+            }
+            Err(err) => tracing::error!("failed to serialize a message: {err}"),
+        }
+    }
+}
+
+/// A server request waiting for the user's decision.
+#[derive(Debug, Clone, PartialEq)]
+enum ApprovalRequest {
+    Exec(RequestId),
+    Patch(RequestId),
+}
+
+impl ApprovalRequest {
+    fn respond(self, connection: &Connection, decision: ApprovalDecision) {
+        match self {
+            Self::Exec(request_id) => connection.respond(
+                request_id,
+                &CommandExecutionRequestApprovalResponse { decision },
+            ),
+            Self::Patch(request_id) => {
+                connection.respond(request_id, &FileChangeRequestApprovalResponse { decision })
+            }
+        }
+    }
+}
+
+/// Pairs the UI's approval ops with the server's approval requests. The UI
+/// answers by event id, the server asks by call id, and the approval event
+/// links the two.
+#[derive(Debug, Default)]
+struct Approvals {
+    /// Call ids of approval events, by event id, oldest first.
+    calls: HashMap<String, VecDeque<String>>,
+    /// Server requests not yet answered, by call id.
+    requests: HashMap<String, ApprovalRequest>,
+    /// Decisions made before the server's request arrived, by call id.
+    decisions: HashMap<String, ApprovalDecision>,
+    /// Memory reviews by turn id, whichever of the request and the answer
+    /// came first.
+    memory_reviews: HashMap<String, MemoryReview>,
+}
+
+#[derive(Debug)]
+enum MemoryReview {
+    Requested(RequestId),
+    Answered(Vec<usize>),
+}
+
+impl Approvals {
+    fn record_event(&mut self, event: &Event) {
+        let call_id = match &event.msg {
+            EventMsg::ExecApprovalRequest(request) => &request.call_id,
+            EventMsg::ApplyPatchApprovalRequest(request) => &request.call_id,
+            _ => return,
+        };
+        self.calls
+            .entry(event.id.clone())
+            .or_default()
+            .push_back(call_id.clone());
+    }
+
+    fn record_request(
+        &mut self,
+        call_id: String,
+        request: ApprovalRequest,
+    ) -> Option<(ApprovalRequest, ApprovalDecision)> {
+        match self.decisions.remove(&call_id) {
+            Some(decision) => Some((request, decision)),
+            None => {
+                self.requests.insert(call_id, request);
+                None
+            }
+        }
+    }
+
+    fn answer(
+        &mut self,
+        event_id: &str,
+        decision: ReviewDecision,
+    ) -> Option<(ApprovalRequest, ApprovalDecision)> {
+        let calls = self.calls.get_mut(event_id)?;
+        let call_id = calls.pop_front()?;
+        if calls.is_empty() {
+            self.calls.remove(event_id);
+        }
+        let decision = approval_decision(decision);
+        match self.requests.remove(&call_id) {
+            Some(request) => Some((request, decision)),
+            None => {
+                self.decisions.insert(call_id, decision);
+                None
+            }
+        }
+    }
+
+    fn record_memory_request(
+        &mut self,
+        turn_id: String,
+        request_id: RequestId,
+    ) -> Option<(RequestId, Vec<usize>)> {
+        match self.memory_reviews.remove(&turn_id) {
+            Some(MemoryReview::Answered(accepted)) => Some((request_id, accepted)),
+            _ => {
+                self.memory_reviews
+                    .insert(turn_id, MemoryReview::Requested(request_id));
+                None
+            }
+        }
+    }
+
+    fn answer_memory_review(
+        &mut self,
+        turn_id: String,
+        accepted: Vec<usize>,
+    ) -> Option<(RequestId, Vec<usize>)> {
+        match self.memory_reviews.remove(&turn_id) {
+            Some(MemoryReview::Requested(request_id)) => Some((request_id, accepted)),
+            _ => {
+                self.memory_reviews
+                    .insert(turn_id, MemoryReview::Answered(accepted));
+                None
+            }
+        }
+    }
+}
+
+/// Turn settings changed since the last turn, sent with the next one.
+#[derive(Debug, Default)]
+struct TurnOverrides {
+    cwd: Option<PathBuf>,
+    approval_policy: Option<V2AskForApproval>,
+    sandbox_policy: Option<V2SandboxPolicy>,
+    model: Option<String>,
+    effort: Option<ReasoningEffort>,
+    summary: Option<ReasoningSummary>,
+}
+
+impl TurnOverrides {
+    fn merge(
+        &mut self,
+        cwd: Option<PathBuf>,
+        approval_policy: Option<codex_core::protocol::AskForApproval>,
+        sandbox_policy: Option<SandboxPolicy>,
+        model: Option<String>,
+        effort: Option<Option<ReasoningEffort>>,
+        summary: Option<ReasoningSummary>,
+    ) {
+        self.cwd = cwd.or(self.cwd.take());
+        self.approval_policy = approval_policy
+            .map(V2AskForApproval::from)
+            .or(self.approval_policy.take());
+        self.sandbox_policy = sandbox_policy
+            .map(V2SandboxPolicy::from)
+            .or(self.sandbox_policy.take());
+        self.model = model.or(self.model.take());
+        // `turn/start` cannot clear the effort, only set it.
+        self.effort = effort.flatten().or(self.effort.take());
+        self.summary = summary.or(self.summary.take());
+    }
+}
+
+/// The agent event a `codex/event/*` notification carries.
+fn event_from_notification(notification: JSONRPCNotification) -> Option<Event> {
+    if !notification.method.starts_with("codex/event/") {
+        return None;
+    }
+    let Value::Object(mut params) = notification.params? else {
+        return None;
+    };
+    params.remove("conversationId");
+    match serde_json::from_value(Value::Object(params)) {
+        Ok(event) => Some(event),
+        Err(err) => {
+            tracing::warn!("failed to decode `{}`: {err}", notification.method);
+            None
+        }
+    }
+}
+
+fn session_configured(started: ThreadStartResponse) -> Result<SessionConfiguredEvent, String> {
+    let ThreadStartResponse {
+        thread,
+        model,
+        model_provider,
+        cwd,
+        approval_policy,
+        sandbox,
+        reasoning_effort,
+    } = started;
+    let session_id = ConversationId::from_string(&thread.id)
+        .map_err(|err| format!("invalid thread id `{}`: {err}", thread.id))?;
+    Ok(SessionConfiguredEvent {
+        session_id,
+        model,
+        model_provider_id: model_provider,
+        approval_policy: approval_policy.to_core(),
+        sandbox_policy: sandbox.to_core(),
+        cwd,
+        reasoning_effort,
+        history_log_id: 0,
+        history_entry_count: 0,
+        initial_messages: None,
+        rollout_path: thread.path,
+    })
+}
+
+fn sandbox_mode(sandbox_policy: &SandboxPolicy) -> V2SandboxMode {
+    match sandbox_policy {
+        SandboxPolicy::ReadOnly => V2SandboxMode::ReadOnly,
+        SandboxPolicy::WorkspaceWrite { .. } => V2SandboxMode::WorkspaceWrite,
+        SandboxPolicy::DangerFullAccess | SandboxPolicy::ExternalSandbox { .. } => {
+            V2SandboxMode::DangerFullAccess
+        }
+    }
+}
+
+/// User input as the server needs it. Local images are read here, since
+/// their paths mean nothing on the server's machine; skills are dropped, as
+/// they are not listed in remote mode.
+fn remote_user_input(item: UserInput) -> Option<V2UserInput> {
+    match item {
+        UserInput::Text { text } => Some(V2UserInput::Text { text }),
+        UserInput::Image { image_url } => Some(V2UserInput::Image { url: image_url }),
+        UserInput::LocalImage { path } => match codex_utils_image::load_and_resize_to_fit(&path) {
+            Ok(image) => Some(V2UserInput::Image {
+                url: image.into_data_url(),
+            }),
+            Err(err) => {
+                tracing::warn!("failed to attach {}: {err}", path.display());
+                None
+            }
+        },
+        _ => None,
+    }
+}
+
+fn approval_decision(decision: ReviewDecision) -> ApprovalDecision {
+    match decision {
+        ReviewDecision::Approved => ApprovalDecision::Accept,
+        ReviewDecision::ApprovedExecpolicyAmendment {
+            proposed_execpolicy_amendment,
+        } => ApprovalDecision::AcceptWithExecpolicyAmendment {
+            execpolicy_amendment: proposed_execpolicy_amendment.into(),
+        },
+        ReviewDecision::ApprovedForSession => ApprovalDecision::AcceptForSession,
+        ReviewDecision::Denied => ApprovalDecision::Decline,
+        ReviewDecision::Abort => ApprovalDecision::Cancel,
+    }
+}
+
+fn error_event(message: String) -> Event {
+    Event {
+        id: String::new(),
+        msg: EventMsg::Error(ErrorEvent {
+            message,
+            codex_error_info: None,
+            details: None,
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::protocol::ExecApprovalRequestEvent;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_splits_the_command_like_a_shell() {
+        let remote = RemoteAppServer::parse(
+            "codex app-server ssh me@devbox --remote-command '~/bin/codex app-server'",
+            Some(PathBuf::from("/srv/repo")),
+        )
+        .expect("command");
+        assert_eq!(
+            remote,
+            RemoteAppServer {
+                command: vec![
+                    "codex".to_string(),
+                    "app-server".to_string(),
+                    "ssh".to_string(),
+                    "me@devbox".to_string(),
+                    "--remote-command".to_string(),
+                    "~/bin/codex app-server".to_string(),
+                ],
+                cwd: Some("/srv/repo".to_string()),
+            }
+        );
+        assert!(RemoteAppServer::parse("  ", None).is_err());
+        assert!(RemoteAppServer::parse("codex 'app-server", None).is_err());
+    }
+
+    #[test]
+    fn event_notifications_become_events() {
+        let notification = JSONRPCNotification {
+            method: "codex/event/agent_message".to_string(),
+            params: Some(serde_json::json!({
+                "id": "turn-1",
+                "conversationId": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+                "msg": {"type": "agent_message", "message": "hi"},
+            })),
+        };
+        let event = event_from_notification(notification).expect("event");
+        assert_eq!(event.id, "turn-1");
+        assert!(matches!(event.msg, EventMsg::AgentMessage(message) if message.message == "hi"));
+
+        let other = JSONRPCNotification {
+            method: "turn/started".to_string(),
+            params: Some(serde_json::json!({})),
+        };
+        assert!(event_from_notification(other).is_none());
+    }
+
+    fn exec_approval_event(event_id: &str, call_id: &str) -> Event {
+        Event {
+            id: event_id.to_string(),
+            msg: EventMsg::ExecApprovalRequest(ExecApprovalRequestEvent {
+                call_id: call_id.to_string(),
+                turn_id: event_id.to_string(),
+                command: vec!["ls".to_string()],
+                cwd: PathBuf::from("/srv/repo"),
+                env: HashMap::new(),
+                reason: None,
+                proposed_execpolicy_amendment: None,
+                parsed_cmd: Vec::new(),
+            }),
+        }
+    }
+
+    #[test]
+    fn approvals_answer_the_request_of_the_matching_call() {
+        let mut approvals = Approvals::default();
+        approvals.record_event(&exec_approval_event("turn-1", "call-1"));
+        approvals.record_event(&exec_approval_event("turn-1", "call-2"));
+        assert_eq!(
+            approvals.record_request(
+                "call-2".to_string(),
+                ApprovalRequest::Exec(RequestId::Integer(8))
+            ),
+            None
+        );
+        assert_eq!(
+            approvals.record_request(
+                "call-1".to_string(),
+                ApprovalRequest::Exec(RequestId::Integer(7))
+            ),
+            None
+        );
+
+        assert_eq!(
+            approvals.answer("turn-1", ReviewDecision::Approved),
+            Some((
+                ApprovalRequest::Exec(RequestId::Integer(7)),
+                ApprovalDecision::Accept
+            ))
+        );
+        assert_eq!(
+            approvals.answer("turn-1", ReviewDecision::Abort),
+            Some((
+                ApprovalRequest::Exec(RequestId::Integer(8)),
+                ApprovalDecision::Cancel
+            ))
+        );
+        assert_eq!(approvals.answer("turn-1", ReviewDecision::Approved), None);
+    }
+
+    #[test]
+    fn memory_reviews_are_answered_in_either_order() {
+        let mut approvals = Approvals::default();
+        assert_eq!(
+            approvals.record_memory_request("turn-1".to_string(), RequestId::Integer(4)),
+            None
+        );
+        assert_eq!(
+            approvals.answer_memory_review("turn-1".to_string(), vec![1]),
+            Some((RequestId::Integer(4), vec![1]))
+        );
+
+        assert_eq!(
+            approvals.answer_memory_review("turn-2".to_string(), Vec::new()),
+            None
+        );
+        assert_eq!(
+            approvals.record_memory_request("turn-2".to_string(), RequestId::Integer(5)),
+            Some((RequestId::Integer(5), Vec::new()))
+        );
+    }
+
+    #[test]
+    fn a_decision_made_before_the_request_is_sent_when_it_arrives() {
+        let mut approvals = Approvals::default();
+        approvals.record_event(&exec_approval_event("turn-1", "call-1"));
+        assert_eq!(approvals.answer("turn-1", ReviewDecision::Denied), None);
+        assert_eq!(
+            approvals.record_request(
+                "call-1".to_string(),
+                ApprovalRequest::Exec(RequestId::Integer(3))
+            ),
+            Some((
+                ApprovalRequest::Exec(RequestId::Integer(3)),
+                ApprovalDecision::Decline
+            ))
+        );
+    }
+}
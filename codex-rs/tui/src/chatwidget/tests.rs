@@ -17,6 +17,7 @@ use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningDeltaEvent;
 use codex_core::protocol::AgentReasoningEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
+use codex_core::protocol::ApprovalPresetId;
 use codex_core::protocol::BackgroundEventEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::Event;
@@ -36,6 +37,7 @@ use codex_core::protocol::PatchApplyEndEvent;
 use codex_core::protocol::RateLimitWindow;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
+use codex_core::protocol::SettingChange;
 use codex_core::protocol::StreamErrorEvent;
 use codex_core::protocol::TaskCompleteEvent;
 use codex_core::protocol::TaskStartedEvent;
@@ -43,6 +45,7 @@ use codex_core::protocol::TerminalInteractionEvent;
 use codex_core::protocol::TokenCountEvent;
 use codex_core::protocol::TokenUsage;
 use codex_core::protocol::TokenUsageInfo;
+use codex_core::protocol::TurnContextChangedEvent;
 use codex_core::protocol::UndoCompletedEvent;
 use codex_core::protocol::UndoStartedEvent;
 use codex_core::protocol::ViewImageToolCallEvent;
@@ -407,6 +410,7 @@ async fn make_chatwidget_manual(
         last_rendered_width: std::cell::Cell::new(None),
         feedback: codex_feedback::CodexFeedback::new(),
         current_rollout_path: None,
+        artifacts: Vec::new(),
     };
     (widget, rx, op_rx)
 }
@@ -718,6 +722,7 @@ async fn exec_approval_emits_proposed_command_and_decision_history() {
         turn_id: "turn-short".into(),
         command: vec!["bash".into(), "-lc".into(), "echo hello world".into()],
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        env: HashMap::new(),
         reason: Some(
             "this is a test reason such as one that would be produced by the model".into(),
         ),
@@ -762,6 +767,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         turn_id: "turn-multi".into(),
         command: vec!["bash".into(), "-lc".into(), "echo line1\necho line2".into()],
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        env: HashMap::new(),
         reason: Some(
             "this is a test reason such as one that would be produced by the model".into(),
         ),
@@ -814,6 +820,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         turn_id: "turn-long".into(),
         command: vec!["bash".into(), "-lc".into(), long],
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        env: HashMap::new(),
         reason: None,
         proposed_execpolicy_amendment: None,
         parsed_cmd: vec![],
@@ -1993,8 +2000,8 @@ async fn approvals_selection_popup_snapshot() {
 async fn preset_matching_ignores_extra_writable_roots() {
     let preset = builtin_approval_presets()
         .into_iter()
-        .find(|p| p.id == "auto")
-        .expect("auto preset exists");
+        .find(|p| p.id == ApprovalPresetId::FullAuto)
+        .expect("full-auto preset exists");
     let current_sandbox = SandboxPolicy::WorkspaceWrite {
         writable_roots: vec![AbsolutePathBuf::try_from("C:\\extra").unwrap()],
         network_access: false,
@@ -2004,7 +2011,7 @@ async fn preset_matching_ignores_extra_writable_roots() {
 
     assert!(
         ChatWidget::preset_matches_current(AskForApproval::OnRequest, &current_sandbox, &preset),
-        "WorkspaceWrite with extra roots should still match the Agent preset"
+        "WorkspaceWrite with extra roots should still match the Full Auto preset"
     );
     assert!(
         !ChatWidget::preset_matches_current(AskForApproval::Never, &current_sandbox, &preset),
@@ -2018,8 +2025,8 @@ async fn full_access_confirmation_popup_snapshot() {
 
     let preset = builtin_approval_presets()
         .into_iter()
-        .find(|preset| preset.id == "full-access")
-        .expect("full access preset");
+        .find(|preset| preset.id == ApprovalPresetId::Yolo)
+        .expect("yolo preset");
     chat.open_full_access_confirmation(preset);
 
     let popup = render_bottom_popup(&chat, 80);
@@ -2033,8 +2040,8 @@ async fn windows_auto_mode_prompt_requests_enabling_sandbox_feature() {
 
     let preset = builtin_approval_presets()
         .into_iter()
-        .find(|preset| preset.id == "auto")
-        .expect("auto preset");
+        .find(|preset| preset.id == ApprovalPresetId::FullAuto)
+        .expect("full-auto preset");
     chat.open_windows_sandbox_enable_prompt(preset);
 
     let popup = render_bottom_popup(&chat, 120);
@@ -2156,6 +2163,56 @@ async fn single_reasoning_option_skips_selection() {
     );
 }
 
+#[tokio::test]
+async fn effort_command_with_args_overrides_turn_context() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command_with_args(SlashCommand::Effort, "high".to_string());
+
+    let mut events = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        events.push(ev);
+    }
+
+    assert!(
+        events.iter().any(|ev| matches!(
+            ev,
+            AppEvent::CodexOp(Op::OverrideTurnContext {
+                effort: Some(Some(ReasoningEffortConfig::High)),
+                verbosity: None,
+                ..
+            })
+        )),
+        "expected effort override op; events: {events:?}"
+    );
+    assert!(
+        events.iter().any(|ev| matches!(
+            ev,
+            AppEvent::UpdateReasoningEffort(Some(ReasoningEffortConfig::High))
+        )),
+        "expected reasoning effort update; events: {events:?}"
+    );
+}
+
+#[tokio::test]
+async fn verbosity_command_rejects_unknown_level() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+
+    chat.dispatch_command_with_args(SlashCommand::Verbosity, "loud".to_string());
+
+    let mut events = Vec::new();
+    while let Ok(ev) = rx.try_recv() {
+        events.push(ev);
+    }
+
+    assert!(
+        !events
+            .iter()
+            .any(|ev| matches!(ev, AppEvent::CodexOp(Op::OverrideTurnContext { .. }))),
+        "unknown verbosity should not override the turn context; events: {events:?}"
+    );
+}
+
 #[tokio::test]
 async fn feedback_selection_popup_snapshot() {
     let (mut chat, _rx, _op_rx) = make_chatwidget_manual(None).await;
@@ -2320,13 +2377,13 @@ async fn approvals_popup_navigation_skips_disabled() {
     chat.open_approvals_popup();
 
     // The approvals popup is the active bottom-pane view; drive navigation via chat handle_key_event.
-    // Start selected at idx 0 (enabled), move down twice; the disabled option should be skipped
-    // and selection should wrap back to idx 0 (also enabled).
+    // Start selected at idx 0 (enabled), move down twice; the disabled options at idx 1 and 3
+    // should be skipped and selection should wrap back to idx 0 (also enabled).
     chat.handle_key_event(KeyEvent::from(KeyCode::Down));
     chat.handle_key_event(KeyEvent::from(KeyCode::Down));
 
-    // Press numeric shortcut for the disabled row (3 => idx 2); should not close or accept.
-    chat.handle_key_event(KeyEvent::from(KeyCode::Char('3')));
+    // Press numeric shortcut for a disabled row (2 => idx 1); should not close or accept.
+    chat.handle_key_event(KeyEvent::from(KeyCode::Char('2')));
 
     // Ensure the popup remains open and no selection actions were sent.
     let width = 80;
@@ -2357,9 +2414,8 @@ async fn approvals_popup_navigation_skips_disabled() {
     assert!(
         app_events.iter().any(|ev| matches!(
             ev,
-            AppEvent::CodexOp(Op::OverrideTurnContext {
-                approval_policy: Some(AskForApproval::OnRequest),
-                ..
+            AppEvent::CodexOp(Op::SetApprovalPreset {
+                preset: ApprovalPresetId::ReadOnly,
             })
         )),
         "enter should select an enabled preset"
@@ -2367,15 +2423,44 @@ async fn approvals_popup_navigation_skips_disabled() {
     assert!(
         !app_events.iter().any(|ev| matches!(
             ev,
-            AppEvent::CodexOp(Op::OverrideTurnContext {
-                approval_policy: Some(AskForApproval::Never),
-                ..
+            AppEvent::CodexOp(Op::SetApprovalPreset {
+                preset: ApprovalPresetId::AutoEdit | ApprovalPresetId::Yolo,
             })
         )),
         "disabled preset should not be selected"
     );
 }
 
+#[tokio::test]
+async fn turn_context_change_updates_local_sandbox_policy() {
+    let (mut chat, mut rx, _op_rx) = make_chatwidget_manual(None).await;
+    let sandbox = SandboxPolicy::WorkspaceWrite {
+        writable_roots: vec![AbsolutePathBuf::try_from("/extra").unwrap()],
+        network_access: true,
+        exclude_tmpdir_env_var: false,
+        exclude_slash_tmp: false,
+    };
+
+    chat.handle_codex_event(Event {
+        id: "sub".to_string(),
+        msg: EventMsg::TurnContextChanged(TurnContextChangedEvent {
+            sandbox_policy: Some(SettingChange {
+                old: chat.config.sandbox_policy.clone(),
+                new: sandbox.clone(),
+            }),
+            ..Default::default()
+        }),
+    });
+
+    let events: Vec<AppEvent> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+    assert!(
+        events
+            .iter()
+            .any(|ev| matches!(ev, AppEvent::UpdateSandboxPolicy(policy) if *policy == sandbox)),
+        "expected the session's sandbox policy to be mirrored; events: {events:?}"
+    );
+}
+
 //
 // Snapshot test: command approval modal
 //
@@ -2393,6 +2478,7 @@ async fn approval_modal_exec_snapshot() -> anyhow::Result<()> {
         turn_id: "turn-approve-cmd".into(),
         command: vec!["bash".into(), "-lc".into(), "echo hello world".into()],
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        env: HashMap::new(),
         reason: Some(
             "this is a test reason such as one that would be produced by the model".into(),
         ),
@@ -2448,6 +2534,7 @@ async fn approval_modal_exec_without_reason_snapshot() -> anyhow::Result<()> {
         turn_id: "turn-approve-cmd-noreason".into(),
         command: vec!["bash".into(), "-lc".into(), "echo hello world".into()],
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        env: HashMap::new(),
         reason: None,
         proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
             "echo".into(),
@@ -2668,6 +2755,7 @@ async fn status_widget_and_approval_modal_snapshot() {
         turn_id: "turn-approve-exec".into(),
         command: vec!["echo".into(), "hello world".into()],
         cwd: PathBuf::from("/tmp"),
+        env: HashMap::new(),
         reason: Some(
             "this is a test reason such as one that would be produced by the model".into(),
         ),
@@ -77,6 +77,12 @@ pub struct Cli {
     #[clap(long = "cd", short = 'C', value_name = "DIR")]
     pub cwd: Option<PathBuf>,
 
+    /// Run the session in the app server this command starts instead of in
+    /// this process, e.g. `codex app-server ssh me@devbox`. `--cd` then names
+    /// a directory on the server's machine.
+    #[arg(long = "remote", value_name = "COMMAND")]
+    pub remote: Option<String>,
+
     /// Enable web search (off by default). When enabled, the native Responses `web_search` tool is available to the model (no per‑call approval).
     #[arg(long = "search", default_value_t = false)]
     pub web_search: bool,
@@ -0,0 +1,278 @@
+//! Crash reports written from the panic hook.
+//!
+//! When the TUI panics we write `codex_home/crashes/crash-<timestamp>-<pid>.json`
+//! with the panic message and location, a backtrace, the most recent protocol
+//! events and ops, and the user's `config.toml` with secrets redacted, then
+//! point the user at the file so it can be attached to a bug report.
+
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::panic::PanicHookInfo;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::OnceLock;
+
+use codex_core::config::CONFIG_TOML_FILE;
+use codex_core::config::Config;
+use codex_core::protocol::Event;
+use codex_core::protocol::Op;
+use serde_json::Value as JsonValue;
+use serde_json::json;
+
+use crate::version::CODEX_CLI_VERSION;
+
+const CRASHES_DIR: &str = "crashes";
+/// How many protocol events and ops are kept for the report.
+const MAX_RECENT_EVENTS: usize = 50;
+const REDACTED: &str = "<redacted>";
+
+static REPORTER: OnceLock<CrashReporter> = OnceLock::new();
+
+struct CrashReporter {
+    crashes_dir: PathBuf,
+    config: JsonValue,
+    recent_events: Mutex<VecDeque<JsonValue>>,
+}
+
+/// Start collecting context for crash reports. Only the first call takes
+/// effect.
+pub(crate) fn init(config: &Config) {
+    let _ = REPORTER.set(CrashReporter {
+        crashes_dir: config.codex_home.join(CRASHES_DIR),
+        config: config_digest(config),
+        recent_events: Mutex::new(VecDeque::with_capacity(MAX_RECENT_EVENTS)),
+    });
+}
+
+pub(crate) fn record_event(event: &Event) {
+    record(json!({
+        "ts": now_ts(),
+        "dir": "event",
+        "id": event.id,
+        "kind": event.msg.to_string(),
+    }));
+}
+
+pub(crate) fn record_op(op: &Op) {
+    let kind = serde_json::to_value(op)
+        .ok()
+        .and_then(|value| value.get("type").cloned())
+        .unwrap_or(JsonValue::Null);
+    record(json!({
+        "ts": now_ts(),
+        "dir": "op",
+        "kind": kind,
+    }));
+}
+
+fn record(entry: JsonValue) {
+    let Some(reporter) = REPORTER.get() else {
+        return;
+    };
+    let mut recent = match reporter.recent_events.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    };
+    if recent.len() == MAX_RECENT_EVENTS {
+        recent.pop_front();
+    }
+    recent.push_back(entry);
+}
+
+/// Write a crash report for `info`. Returns the report path on success.
+pub(crate) fn write_report(info: &PanicHookInfo<'_>) -> Option<PathBuf> {
+    let reporter = REPORTER.get()?;
+    // Never block inside the panic hook: if the panic happened while the
+    // buffer was locked, report without recent events.
+    let recent_events: Vec<JsonValue> = match reporter.recent_events.try_lock() {
+        Ok(recent) => recent.iter().cloned().collect(),
+        Err(std::sync::TryLockError::Poisoned(poisoned)) => {
+            poisoned.into_inner().iter().cloned().collect()
+        }
+        Err(std::sync::TryLockError::WouldBlock) => Vec::new(),
+    };
+    let report = build_report(
+        &panic_message(info),
+        info.location().map(ToString::to_string),
+        &Backtrace::force_capture().to_string(),
+        recent_events,
+        reporter.config.clone(),
+    );
+    match persist_report(&reporter.crashes_dir, &report) {
+        Ok(path) => Some(path),
+        Err(err) => {
+            tracing::error!("failed to write crash report: {err}");
+            None
+        }
+    }
+}
+
+fn build_report(
+    message: &str,
+    location: Option<String>,
+    backtrace: &str,
+    recent_events: Vec<JsonValue>,
+    config: JsonValue,
+) -> JsonValue {
+    json!({
+        "version": CODEX_CLI_VERSION,
+        "timestamp": now_ts(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "thread": std::thread::current().name().unwrap_or("<unnamed>"),
+        "message": message,
+        "location": location,
+        "backtrace": backtrace.lines().collect::<Vec<_>>(),
+        "recent_events": recent_events,
+        "config": config,
+    })
+}
+
+fn persist_report(crashes_dir: &Path, report: &JsonValue) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(crashes_dir)?;
+    let filename = format!(
+        "crash-{}-{}.json",
+        chrono::Utc::now().format("%Y%m%dT%H%M%SZ"),
+        std::process::id()
+    );
+    let path = crashes_dir.join(filename);
+    std::fs::write(&path, serde_json::to_string_pretty(report)?)?;
+    Ok(path)
+}
+
+fn panic_message(info: &PanicHookInfo<'_>) -> String {
+    if let Some(message) = info.payload().downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = info.payload().downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "<non-string panic payload>".to_string()
+    }
+}
+
+/// Effective settings that matter for triage plus the user's `config.toml`
+/// with secret-looking values replaced.
+fn config_digest(config: &Config) -> JsonValue {
+    let config_toml = std::fs::read_to_string(config.codex_home.join(CONFIG_TOML_FILE))
+        .ok()
+        .and_then(|contents| toml::from_str::<toml::Value>(&contents).ok())
+        .map(|mut value| {
+            redact_secrets(&mut value);
+            value
+        });
+    json!({
+        "model": config.model,
+        "model_provider_id": config.model_provider_id,
+        "approval_policy": config.approval_policy.value().to_string(),
+        "sandbox_policy": config.sandbox_policy.to_string(),
+        "config_toml": config_toml,
+    })
+}
+
+fn redact_secrets(value: &mut toml::Value) {
+    match value {
+        toml::Value::Table(table) => {
+            for (key, value) in table.iter_mut() {
+                if is_secret_key(key) {
+                    *value = toml::Value::String(REDACTED.to_string());
+                } else {
+                    redact_secrets(value);
+                }
+            }
+        }
+        toml::Value::Array(values) => values.iter_mut().for_each(redact_secrets),
+        _ => {}
+    }
+}
+
+/// Keys whose values may hold credentials. `env` and header maps are
+/// redacted wholesale because MCP servers commonly receive tokens there.
+fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_lowercase();
+    matches!(key.as_str(), "env" | "http_headers" | "env_http_headers")
+        || [
+            "key",
+            "token",
+            "secret",
+            "password",
+            "auth",
+            "cookie",
+            "credential",
+        ]
+        .iter()
+        .any(|needle| key.contains(needle))
+}
+
+fn now_ts() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn redacts_secret_looking_values() {
+        let mut value: toml::Value = toml::from_str(
+            r#"
+model = "gpt-5.1-codex"
+
+[model_providers.azure]
+base_url = "https://example.openai.azure.com/openai"
+env_key = "AZURE_OPENAI_API_KEY"
+query_params = { api-version = "2025-04-01-preview" }
+http_headers = { "X-Api-Key" = "abc123" }
+
+[mcp_servers.github]
+command = "github-mcp"
+env = { GITHUB_TOKEN = "ghp_secret" }
+bearer_token_env_var = "GITHUB_TOKEN"
+"#,
+        )
+        .expect("parse");
+        redact_secrets(&mut value);
+
+        let expected: toml::Value = toml::from_str(
+            r#"
+model = "gpt-5.1-codex"
+
+[model_providers.azure]
+base_url = "https://example.openai.azure.com/openai"
+env_key = "<redacted>"
+query_params = { api-version = "2025-04-01-preview" }
+http_headers = "<redacted>"
+
+[mcp_servers.github]
+command = "github-mcp"
+env = "<redacted>"
+bearer_token_env_var = "<redacted>"
+"#,
+        )
+        .expect("parse");
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn report_is_written_under_crashes_dir() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let crashes_dir = dir.path().join(CRASHES_DIR);
+        let report = build_report(
+            "boom",
+            Some("src/app.rs:1:1".to_string()),
+            "0: frame\n1: frame",
+            vec![json!({"dir": "event", "kind": "task_started"})],
+            json!({"model": "gpt-5.1-codex"}),
+        );
+
+        let path = persist_report(&crashes_dir, &report).expect("persist");
+
+        assert_eq!(path.parent(), Some(crashes_dir.as_path()));
+        let written: JsonValue =
+            serde_json::from_str(&std::fs::read_to_string(&path).expect("read")).expect("json");
+        assert_eq!(written["message"], "boom");
+        assert_eq!(written["backtrace"], json!(["0: frame", "1: frame"]));
+        assert_eq!(written["recent_events"][0]["kind"], "task_started");
+    }
+}
@@ -23,6 +23,9 @@ pub(crate) struct ExecCall {
     pub(crate) start_time: Option<Instant>,
     pub(crate) duration: Option<Duration>,
     pub(crate) interaction_input: Option<String>,
+    /// Where the command ran, shown relative to the session cwd; `None` when
+    /// it ran in the session cwd.
+    pub(crate) cwd: Option<String>,
 }
 
 #[derive(Debug)]
@@ -46,6 +49,7 @@ impl ExecCell {
         parsed: Vec<ParsedCommand>,
         source: ExecCommandSource,
         interaction_input: Option<String>,
+        cwd: Option<String>,
     ) -> Option<Self> {
         let call = ExecCall {
             call_id,
@@ -56,6 +60,7 @@ impl ExecCell {
             start_time: Some(Instant::now()),
             duration: None,
             interaction_input,
+            cwd,
         };
         if self.is_exploring_cell() && Self::is_exploring_call(&call) {
             Some(Self {
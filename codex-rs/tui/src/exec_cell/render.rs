@@ -3,12 +3,15 @@ use std::time::Instant;
 use super::model::CommandOutput;
 use super::model::ExecCall;
 use super::model::ExecCell;
+use crate::accessibility::outcome_bullet;
 use crate::exec_command::strip_bash_lc_and_escape;
 use crate::history_cell::HistoryCell;
 use crate::render::highlight::highlight_bash_to_lines;
 use crate::render::line_utils::prefix_lines;
 use crate::render::line_utils::push_owned_lines;
 use crate::shimmer::shimmer_spans;
+use crate::terminal_capabilities::glyph;
+use crate::terminal_capabilities::has_true_color;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_line;
 use crate::wrapping::word_wrap_lines;
@@ -41,6 +44,7 @@ pub(crate) fn new_active_exec_command(
     parsed: Vec<ParsedCommand>,
     source: ExecCommandSource,
     interaction_input: Option<String>,
+    cwd: Option<String>,
     animations_enabled: bool,
 ) -> ExecCell {
     ExecCell::new(
@@ -53,6 +57,7 @@ pub(crate) fn new_active_exec_command(
             start_time: Some(Instant::now()),
             duration: None,
             interaction_input,
+            cwd,
         },
         animations_enabled,
     )
@@ -178,18 +183,20 @@ pub(crate) fn output_lines(
 }
 
 pub(crate) fn spinner(start_time: Option<Instant>, animations_enabled: bool) -> Span<'static> {
+    let dot = glyph("•", "*");
     if !animations_enabled {
-        return "•".dim();
+        return dot.dim();
     }
     let elapsed = start_time.map(|st| st.elapsed()).unwrap_or_default();
-    if supports_color::on_cached(supports_color::Stream::Stdout)
-        .map(|level| level.has_16m)
-        .unwrap_or(false)
-    {
-        shimmer_spans("•")[0].clone()
+    if has_true_color() {
+        shimmer_spans(dot)[0].clone()
     } else {
         let blink_on = (elapsed.as_millis() / 600).is_multiple_of(2);
-        if blink_on { "•".into() } else { "◦".dim() }
+        if blink_on {
+            dot.into()
+        } else {
+            glyph("◦", "o").dim()
+        }
     }
 }
 
@@ -362,8 +369,7 @@ impl ExecCell {
         let layout = EXEC_DISPLAY_LAYOUT;
         let success = call.output.as_ref().map(|o| o.exit_code == 0);
         let bullet = match success {
-            Some(true) => "•".green().bold(),
-            Some(false) => "•".red().bold(),
+            Some(success) => outcome_bullet(success),
             None => spinner(call.start_time, self.animations_enabled()),
         };
         let is_interaction = call.is_unified_exec_interaction();
@@ -380,7 +386,11 @@ impl ExecCell {
         let mut header_line = if is_interaction {
             Line::from(vec![bullet.clone(), " ".into()])
         } else {
-            Line::from(vec![bullet.clone(), " ".into(), title.bold(), " ".into()])
+            let mut spans = vec![bullet.clone(), " ".into(), title.bold(), " ".into()];
+            if let Some(cwd) = &call.cwd {
+                spans.push(format!("in {cwd}: ").dim());
+            }
+            Line::from(spans)
         };
         let header_prefix_width = header_line.width();
 
@@ -679,6 +689,7 @@ mod tests {
             start_time: None,
             duration: None,
             interaction_input: None,
+            cwd: None,
         };
 
         let cell = ExecCell::new(call, false);
@@ -702,4 +713,27 @@ mod tests {
             "expected at most {USER_SHELL_TOOL_CALL_MAX_LINES} screen lines of user shell output, got {output_screen_lines}",
         );
     }
+
+    #[test]
+    fn command_header_names_a_non_default_cwd() {
+        let cell = new_active_exec_command(
+            "call-id".to_string(),
+            vec!["bash".into(), "-lc".into(), "cargo test".into()],
+            Vec::new(),
+            ExecCommandSource::Agent,
+            None,
+            Some("crates/foo".to_string()),
+            false,
+        );
+
+        let header: String = cell.command_display_lines(80)[0]
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+        assert!(
+            header.ends_with("Running in crates/foo: cargo test"),
+            "unexpected header: {header}"
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use crate::accessibility::outcome_bullet;
 use crate::diff_render::create_diff_summary;
 use crate::diff_render::display_path_for;
 use crate::exec_cell::CommandOutput;
@@ -7,6 +8,7 @@ use crate::exec_cell::output_lines;
 use crate::exec_cell::spinner;
 use crate::exec_command::relativize_to_home;
 use crate::exec_command::strip_bash_lc_and_escape;
+use crate::inline_image;
 use crate::live_wrap::take_prefix_by_width;
 use crate::markdown::append_markdown;
 use crate::render::line_utils::line_to_static;
@@ -28,6 +30,8 @@ use base64::Engine;
 use codex_common::format_env_display::format_env_display;
 use codex_core::config::Config;
 use codex_core::config::types::McpServerTransportConfig;
+use codex_core::protocol::AddedDependency;
+use codex_core::protocol::Artifact;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
@@ -101,6 +105,12 @@ pub(crate) trait HistoryCell: std::fmt::Debug + Send + Sync + Any {
     fn is_stream_continuation(&self) -> bool {
         false
     }
+
+    /// Image to draw inline below the cell's lines when the terminal has a
+    /// graphics protocol. The lines must still make sense without it.
+    fn inline_image(&self) -> Option<&DynamicImage> {
+        None
+    }
 }
 
 impl Renderable for Box<dyn HistoryCell> {
@@ -731,11 +741,39 @@ impl HistoryCell for PatchHistoryCell {
 
 #[derive(Debug)]
 struct CompletedMcpToolCallWithImageOutput {
-    _image: DynamicImage,
+    image: DynamicImage,
 }
 impl HistoryCell for CompletedMcpToolCallWithImageOutput {
     fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
-        vec!["tool result (image output)".into()]
+        vec![
+            format!(
+                "tool result (image output, {})",
+                inline_image::dimensions_label(&self.image)
+            )
+            .into(),
+        ]
+    }
+
+    fn inline_image(&self) -> Option<&DynamicImage> {
+        Some(&self.image)
+    }
+}
+
+/// An image the user attached or a tool looked at. Shows a caption, and the
+/// image itself where the terminal can draw it.
+#[derive(Debug)]
+pub(crate) struct ImageHistoryCell {
+    lines: Vec<Line<'static>>,
+    image: Option<DynamicImage>,
+}
+
+impl HistoryCell for ImageHistoryCell {
+    fn display_lines(&self, _width: u16) -> Vec<Line<'static>> {
+        self.lines.clone()
+    }
+
+    fn inline_image(&self) -> Option<&DynamicImage> {
+        self.image.as_ref()
     }
 }
 
@@ -1168,8 +1206,7 @@ impl HistoryCell for McpToolCallCell {
         let mut lines: Vec<Line<'static>> = Vec::new();
         let status = self.success();
         let bullet = match status {
-            Some(true) => "•".green().bold(),
-            Some(false) => "•".red().bold(),
+            Some(success) => outcome_bullet(success),
             None => spinner(Some(self.start_time), self.animations_enabled),
         };
         let header_text = if status.is_some() {
@@ -1299,7 +1336,7 @@ fn try_new_completed_mcp_tool_call_with_image_output(
                     }
                 };
 
-                Some(CompletedMcpToolCallWithImageOutput { _image: image })
+                Some(CompletedMcpToolCallWithImageOutput { image })
             } else {
                 None
             }
@@ -1545,6 +1582,15 @@ pub(crate) fn new_error_event(message: String) -> PlainHistoryCell {
     PlainHistoryCell { lines }
 }
 
+/// An error followed by a dimmed line suggesting how to fix it.
+pub(crate) fn new_error_event_with_hint(message: String, hint: Option<String>) -> PlainHistoryCell {
+    let mut cell = new_error_event(message);
+    if let Some(hint) = hint {
+        cell.lines.push(vec!["  ".into(), hint.dim()].into());
+    }
+    cell
+}
+
 /// Render a user‑friendly plan update styled like a checkbox todo list.
 pub(crate) fn new_plan_update(update: UpdatePlanArgs) -> PlanUpdateCell {
     let UpdatePlanArgs { explanation, plan } = update;
@@ -1650,14 +1696,101 @@ pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
     PlainHistoryCell { lines }
 }
 
-pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> PlainHistoryCell {
-    let display_path = display_path_for(&path, cwd);
-
-    let lines: Vec<Line<'static>> = vec![
-        vec!["• ".dim(), "Viewed Image".bold()].into(),
-        vec!["  └ ".dim(), display_path.dim()].into(),
+pub(crate) fn new_artifact_registered(artifact: &Artifact, cwd: &Path) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec![
+        vec![
+            "• ".dim(),
+            "Artifact ".bold(),
+            artifact.title.clone().into(),
+        ]
+        .into(),
+        vec!["  └ ".dim(), display_path_for(&artifact.path, cwd).dim()].into(),
     ];
+    if let Some(description) = &artifact.description {
+        lines.push(vec!["    ".into(), description.clone().dim()].into());
+    }
+    PlainHistoryCell { lines }
+}
+
+/// `image` is the decoded image when the terminal can draw it; otherwise
+/// only the caption is shown.
+pub(crate) fn new_view_image_tool_call(
+    path: &Path,
+    cwd: &Path,
+    image: Option<DynamicImage>,
+) -> ImageHistoryCell {
+    new_image_cell("Viewed Image", path, cwd, image)
+}
+
+pub(crate) fn new_attached_image(
+    path: &Path,
+    cwd: &Path,
+    image: Option<DynamicImage>,
+) -> ImageHistoryCell {
+    new_image_cell("Attached Image", path, cwd, image)
+}
+
+fn new_image_cell(
+    title: &'static str,
+    path: &Path,
+    cwd: &Path,
+    image: Option<DynamicImage>,
+) -> ImageHistoryCell {
+    let mut detail = display_path_for(path, cwd);
+    let dimensions = match &image {
+        Some(image) => Some((image.width(), image.height())),
+        None => inline_image::dimensions(path),
+    };
+    if let Some((width, height)) = dimensions {
+        detail.push_str(&format!(" ({width}×{height})"));
+    }
+    ImageHistoryCell {
+        lines: vec![
+            vec!["• ".dim(), title.bold()].into(),
+            vec!["  └ ".dim(), detail.dim()].into(),
+        ],
+        image,
+    }
+}
 
+/// Dependencies the model added this turn, with their licenses and any
+/// known advisories.
+pub(crate) fn new_dependency_report(
+    dependencies: &[AddedDependency],
+    cwd: &Path,
+) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec![vec!["• ".dim(), "Added dependencies".bold()].into()];
+    for (idx, dependency) in dependencies.iter().enumerate() {
+        let prefix = if idx == 0 { "  └ " } else { "    " };
+        let version = dependency
+            .resolved_version
+            .as_deref()
+            .or(dependency.requirement.as_deref())
+            .map(|version| format!(" {version}"))
+            .unwrap_or_default();
+        let details = match (&dependency.lookup_error, &dependency.license) {
+            (Some(err), _) => format!("lookup failed: {err}"),
+            (None, Some(license)) => license.clone(),
+            (None, None) => "no license listed".to_string(),
+        };
+        lines.push(
+            vec![
+                prefix.dim(),
+                dependency.name.clone().bold(),
+                version.into(),
+                format!(" ({}) · {details} · ", dependency.ecosystem.registry()).dim(),
+                display_path_for(&dependency.manifest, cwd).dim(),
+            ]
+            .into(),
+        );
+        for advisory in &dependency.advisories {
+            let text = match &advisory.summary {
+                Some(summary) => format!("{}: {summary}", advisory.id),
+                None => advisory.id.clone(),
+            };
+            lines.push(vec!["      ".into(), format!("■ {text}").red()].into());
+        }
+    }
     PlainHistoryCell { lines }
 }
 
@@ -2282,6 +2415,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -2309,6 +2443,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -2326,6 +2461,7 @@ mod tests {
                 }],
                 ExecCommandSource::Agent,
                 None,
+                None,
             )
             .unwrap();
         cell.complete_call("c2", CommandOutput::default(), Duration::from_millis(1));
@@ -2341,6 +2477,7 @@ mod tests {
                 }],
                 ExecCommandSource::Agent,
                 None,
+                None,
             )
             .unwrap();
         cell.complete_call("c3", CommandOutput::default(), Duration::from_millis(1));
@@ -2378,6 +2515,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -2402,6 +2540,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -2428,6 +2567,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -2452,6 +2592,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -2475,6 +2616,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -2499,6 +2641,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -2523,6 +2666,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
@@ -2573,6 +2717,7 @@ mod tests {
                 start_time: Some(Instant::now()),
                 duration: None,
                 interaction_input: None,
+                cwd: None,
             },
             true,
         );
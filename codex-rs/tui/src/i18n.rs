@@ -0,0 +1,140 @@
+//! Minimal localization for the TUI's most visible strings.
+//!
+//! The interface language follows the `language` setting in `config.toml`.
+//! Anything without a translation falls back to English.
+
+use std::sync::OnceLock;
+
+static UI_LANGUAGE: OnceLock<UiLanguage> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) enum UiLanguage {
+    #[default]
+    English,
+    Japanese,
+    Chinese,
+    Spanish,
+    French,
+    German,
+}
+
+impl UiLanguage {
+    /// Match on the primary subtag, so `ja-JP` and `ja` both select Japanese.
+    fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.split(['-', '_']).next()?.to_ascii_lowercase();
+        match primary.as_str() {
+            "en" => Some(Self::English),
+            "ja" => Some(Self::Japanese),
+            "zh" => Some(Self::Chinese),
+            "es" => Some(Self::Spanish),
+            "fr" => Some(Self::French),
+            "de" => Some(Self::German),
+            _ => None,
+        }
+    }
+}
+
+/// Select the interface language. Only the first call takes effect.
+pub(crate) fn init(language: Option<&str>) {
+    let language = language.and_then(UiLanguage::from_tag).unwrap_or_default();
+    let _ = UI_LANGUAGE.set(language);
+}
+
+fn current_language() -> UiLanguage {
+    UI_LANGUAGE.get().copied().unwrap_or_default()
+}
+
+/// Strings shown in the TUI that have translations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Message {
+    /// Default status header while a turn is running.
+    Working,
+    /// Follows the Esc key hint in the status indicator.
+    ToInterrupt,
+    /// Follows the `?` key hint in the footer.
+    ForShortcuts,
+    RunCommandTitle,
+    ApplyEditsTitle,
+    YesProceed,
+    NoTellCodex,
+}
+
+pub(crate) fn tr(message: Message) -> &'static str {
+    translate(current_language(), message)
+}
+
+fn translate(language: UiLanguage, message: Message) -> &'static str {
+    use Message::*;
+    use UiLanguage::*;
+    match (language, message) {
+        (English, Working) => "Working",
+        (English, ToInterrupt) => " to interrupt",
+        (English, ForShortcuts) => " for shortcuts",
+        (English, RunCommandTitle) => "Would you like to run the following command?",
+        (English, ApplyEditsTitle) => "Would you like to make the following edits?",
+        (English, YesProceed) => "Yes, proceed",
+        (English, NoTellCodex) => "No, and tell Codex what to do differently",
+
+        (Japanese, Working) => "作業中",
+        (Japanese, ToInterrupt) => " で中断",
+        (Japanese, ForShortcuts) => " でショートカット一覧",
+        (Japanese, RunCommandTitle) => "次のコマンドを実行しますか?",
+        (Japanese, ApplyEditsTitle) => "次の編集を適用しますか?",
+        (Japanese, YesProceed) => "はい、続行します",
+        (Japanese, NoTellCodex) => "いいえ、Codex に別の方法を伝えます",
+
+        (Chinese, Working) => "工作中",
+        (Chinese, ToInterrupt) => " 中断",
+        (Chinese, ForShortcuts) => " 查看快捷键",
+        (Chinese, RunCommandTitle) => "是否运行以下命令?",
+        (Chinese, ApplyEditsTitle) => "是否进行以下修改?",
+        (Chinese, YesProceed) => "是，继续",
+        (Chinese, NoTellCodex) => "否，并告诉 Codex 应该怎么做",
+
+        (Spanish, Working) => "Trabajando",
+        (Spanish, ToInterrupt) => " para interrumpir",
+        (Spanish, ForShortcuts) => " para ver atajos",
+        (Spanish, RunCommandTitle) => "¿Quieres ejecutar el siguiente comando?",
+        (Spanish, ApplyEditsTitle) => "¿Quieres aplicar los siguientes cambios?",
+        (Spanish, YesProceed) => "Sí, continuar",
+        (Spanish, NoTellCodex) => "No, y decirle a Codex qué hacer diferente",
+
+        (French, Working) => "En cours",
+        (French, ToInterrupt) => " pour interrompre",
+        (French, ForShortcuts) => " pour les raccourcis",
+        (French, RunCommandTitle) => "Voulez-vous exécuter la commande suivante ?",
+        (French, ApplyEditsTitle) => "Voulez-vous appliquer les modifications suivantes ?",
+        (French, YesProceed) => "Oui, continuer",
+        (French, NoTellCodex) => "Non, et indiquer à Codex quoi faire autrement",
+
+        (German, Working) => "Arbeitet",
+        (German, ToInterrupt) => " zum Unterbrechen",
+        (German, ForShortcuts) => " für Tastenkürzel",
+        (German, RunCommandTitle) => "Möchtest du den folgenden Befehl ausführen?",
+        (German, ApplyEditsTitle) => "Möchtest du die folgenden Änderungen vornehmen?",
+        (German, YesProceed) => "Ja, fortfahren",
+        (German, NoTellCodex) => "Nein, und Codex sagen, was es anders machen soll",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parses_language_tags() {
+        assert_eq!(UiLanguage::from_tag("ja"), Some(UiLanguage::Japanese));
+        assert_eq!(UiLanguage::from_tag("zh-Hans"), Some(UiLanguage::Chinese));
+        assert_eq!(UiLanguage::from_tag("pt_BR"), None);
+    }
+
+    #[test]
+    fn defaults_to_english() {
+        assert_eq!(
+            translate(UiLanguage::default(), Message::Working),
+            "Working"
+        );
+        assert_eq!(translate(UiLanguage::German, Message::Working), "Arbeitet");
+    }
+}
@@ -0,0 +1,287 @@
+//! Drawing images inline in the transcript with terminal graphics protocols.
+//!
+//! History is written into the terminal's scrollback, so an image cell
+//! reserves blank rows below its caption and the image is drawn over them
+//! once they have been inserted. Terminals without a supported protocol only
+//! see the caption.
+
+use std::fmt::Write as _;
+use std::io::Cursor;
+use std::path::Path;
+
+use base64::Engine;
+use image::DynamicImage;
+use image::ImageFormat;
+use image::ImageReader;
+use image::imageops::FilterType;
+
+use crate::terminal_capabilities::ImageProtocol;
+
+/// Images never take more than this many columns of the transcript.
+const MAX_COLUMNS: u16 = 60;
+/// ...or this many rows.
+const MAX_ROWS: u16 = 20;
+/// Column the image starts at, lined up with the text after `  └ `.
+const IMAGE_INDENT: u16 = 4;
+/// Kitty limits each escape sequence's payload to 4096 bytes.
+const KITTY_CHUNK: usize = 4096;
+/// Cell size used when the terminal does not report its pixel size.
+const FALLBACK_CELL_PIXELS: (u32, u32) = (8, 16);
+
+/// An image ready to be drawn over the last `rows` inserted history rows.
+#[derive(Debug, Clone)]
+pub(crate) struct InlineImage {
+    pub rows: u16,
+    pub column: u16,
+    pub escape: String,
+}
+
+/// Width and height of the image at `path`, read from its header without
+/// decoding it.
+pub(crate) fn dimensions(path: &Path) -> Option<(u32, u32)> {
+    image::image_dimensions(path).ok()
+}
+
+pub(crate) fn load(path: &Path) -> Option<DynamicImage> {
+    let bytes = std::fs::read(path).ok()?;
+    decode(&bytes)
+}
+
+pub(crate) fn decode(bytes: &[u8]) -> Option<DynamicImage> {
+    let reader = match ImageReader::new(Cursor::new(bytes)).with_guessed_format() {
+        Ok(reader) => reader,
+        Err(err) => {
+            tracing::warn!("failed to guess image format: {err}");
+            return None;
+        }
+    };
+    match reader.decode() {
+        Ok(image) => Some(image),
+        Err(err) => {
+            tracing::warn!("image decoding failed: {err}");
+            None
+        }
+    }
+}
+
+/// Size, e.g. `1280×800`, for captions.
+pub(crate) fn dimensions_label(image: &DynamicImage) -> String {
+    format!("{}×{}", image.width(), image.height())
+}
+
+/// Encode `image` for `protocol`, sized to fit a transcript `width` columns
+/// wide.
+pub(crate) fn prepare(
+    image: &DynamicImage,
+    protocol: ImageProtocol,
+    width: u16,
+) -> Option<InlineImage> {
+    let max_columns = MAX_COLUMNS.min(width.saturating_sub(IMAGE_INDENT + 1));
+    if max_columns == 0 || image.width() == 0 || image.height() == 0 {
+        return None;
+    }
+    let cell = cell_pixels();
+    let (columns, rows) = fit(image.width(), image.height(), max_columns, cell);
+    // Never send more pixels than the cells can show.
+    let scaled = image.resize(
+        u32::from(columns) * cell.0,
+        u32::from(rows) * cell.1,
+        FilterType::Triangle,
+    );
+    let escape = match protocol {
+        ImageProtocol::Kitty => kitty(&png(&scaled)?, columns, rows),
+        ImageProtocol::Iterm2 => iterm2(&png(&scaled)?, columns, rows),
+        ImageProtocol::Sixel => sixel(&scaled),
+    };
+    Some(InlineImage {
+        rows,
+        column: IMAGE_INDENT,
+        escape,
+    })
+}
+
+fn cell_pixels() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0 => (
+            u32::from(size.width / size.columns).max(1),
+            u32::from(size.height / size.rows).max(1),
+        ),
+        _ => FALLBACK_CELL_PIXELS,
+    }
+}
+
+/// Columns and rows for an image of `width`×`height` pixels, keeping its
+/// aspect ratio and never scaling it up.
+fn fit(width: u32, height: u32, max_columns: u16, cell: (u32, u32)) -> (u16, u16) {
+    let (cell_width, cell_height) = (u64::from(cell.0), u64::from(cell.1));
+    let (width, height) = (u64::from(width), u64::from(height));
+    let mut columns = width.div_ceil(cell_width).clamp(1, u64::from(max_columns));
+    let mut rows = (columns * cell_width * height).div_ceil(width * cell_height);
+    if rows > u64::from(MAX_ROWS) {
+        rows = u64::from(MAX_ROWS);
+        columns = (rows * cell_height * width)
+            .div_ceil(height * cell_width)
+            .clamp(1, u64::from(max_columns));
+    }
+    (columns as u16, rows.max(1) as u16)
+}
+
+fn png(image: &DynamicImage) -> Option<Vec<u8>> {
+    let mut bytes = Vec::new();
+    match image.write_to(&mut Cursor::new(&mut bytes), ImageFormat::Png) {
+        Ok(()) => Some(bytes),
+        Err(err) => {
+            tracing::warn!("failed to encode image as PNG: {err}");
+            None
+        }
+    }
+}
+
+/// Kitty graphics protocol: transmit and display a PNG scaled to the cell
+/// area, leaving the cursor where it was (`C=1`).
+fn kitty(png: &[u8], columns: u16, rows: u16) -> String {
+    let data = base64::engine::general_purpose::STANDARD.encode(png);
+    let chunks: Vec<&str> = data
+        .as_bytes()
+        .chunks(KITTY_CHUNK)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect();
+    let mut escape = String::new();
+    for (index, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(index + 1 < chunks.len());
+        if index == 0 {
+            let _ = write!(
+                escape,
+                "\x1b_Gf=100,a=T,q=2,C=1,c={columns},r={rows},m={more};{chunk}\x1b\\"
+            );
+        } else {
+            let _ = write!(escape, "\x1b_Gm={more};{chunk}\x1b\\");
+        }
+    }
+    escape
+}
+
+/// iTerm2 inline image protocol (also understood by WezTerm).
+fn iterm2(png: &[u8], columns: u16, rows: u16) -> String {
+    let data = base64::engine::general_purpose::STANDARD.encode(png);
+    format!(
+        "\x1b]1337;File=inline=1;size={};width={columns};height={rows};preserveAspectRatio=1:{data}\x07",
+        png.len()
+    )
+}
+
+/// Sixel graphics with a fixed 6×6×6 color cube. Transparent pixels are
+/// left untouched.
+fn sixel(image: &DynamicImage) -> String {
+    let rgba = image.to_rgba8();
+    let (width, height) = rgba.dimensions();
+    let level = |value: u8| (u16::from(value) * 5 + 127) / 255;
+    let colors: Vec<Option<u16>> = rgba
+        .pixels()
+        .map(|pixel| {
+            (pixel[3] >= 128).then(|| level(pixel[0]) * 36 + level(pixel[1]) * 6 + level(pixel[2]))
+        })
+        .collect();
+
+    // `P2 = 1`: pixels without a sixel bit keep their current color.
+    let mut escape = format!("\x1bP0;1q\"1;1;{width};{height}");
+    for index in 0..216u16 {
+        let _ = write!(
+            escape,
+            "#{index};2;{};{};{}",
+            index / 36 * 20,
+            index / 6 % 6 * 20,
+            index % 6 * 20
+        );
+    }
+    for band in (0..height).step_by(6) {
+        let band_rows = (band..(band + 6).min(height)).collect::<Vec<_>>();
+        let mut used = [false; 216];
+        for &y in &band_rows {
+            for x in 0..width {
+                if let Some(color) = colors[(y * width + x) as usize] {
+                    used[usize::from(color)] = true;
+                }
+            }
+        }
+        for color in (0..216u16).filter(|color| used[usize::from(*color)]) {
+            let _ = write!(escape, "#{color}");
+            let mut run: Option<(char, usize)> = None;
+            for x in 0..width {
+                let bits = band_rows
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, y)| colors[(**y * width + x) as usize] == Some(color))
+                    .fold(0u8, |bits, (bit, _)| bits | (1 << bit));
+                let sixel = char::from(63 + bits);
+                run = match run {
+                    Some((current, count)) if current == sixel => Some((current, count + 1)),
+                    Some((current, count)) => {
+                        push_run(&mut escape, current, count);
+                        Some((sixel, 1))
+                    }
+                    None => Some((sixel, 1)),
+                };
+            }
+            if let Some((current, count)) = run {
+                push_run(&mut escape, current, count);
+            }
+            // Carriage return: the next color overprints the same band.
+            escape.push('$');
+        }
+        escape.push('-');
+    }
+    escape.push_str("\x1b\\");
+    escape
+}
+
+fn push_run(escape: &mut String, sixel: char, count: usize) {
+    if count > 3 {
+        let _ = write!(escape, "!{count}{sixel}");
+    } else {
+        escape.extend(std::iter::repeat_n(sixel, count));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::Rgba;
+    use image::RgbaImage;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn fit_keeps_aspect_ratio_within_limits() {
+        // Small images keep their size.
+        assert_eq!(fit(80, 32, 60, (8, 16)), (10, 2));
+        // Wide images are limited by the columns.
+        assert_eq!(fit(1920, 1080, 60, (8, 16)), (60, 17));
+        // Tall images are limited by the rows.
+        assert_eq!(fit(400, 1600, 60, (8, 16)), (10, 20));
+    }
+
+    #[test]
+    fn sixel_encodes_runs_per_color() {
+        let mut image = RgbaImage::from_pixel(5, 2, Rgba([255, 255, 255, 255]));
+        image.put_pixel(0, 1, Rgba([0, 0, 0, 0]));
+        let escape = sixel(&DynamicImage::ImageRgba8(image));
+        let body = escape
+            .rsplit_once("#215;2;100;100;100")
+            .map(|(_, body)| body)
+            .unwrap_or_default();
+        // Column 0 only has its top pixel set (bit 0), the others both rows.
+        assert_eq!(body, "#215@!4B$-\x1b\\");
+    }
+
+    #[test]
+    fn kitty_splits_payload_into_chunks() {
+        let escape = kitty(&[0u8; 4000], 10, 3);
+        let parts: Vec<&str> = escape
+            .split("\x1b\\")
+            .filter(|part| !part.is_empty())
+            .collect();
+        assert_eq!(parts.len(), 2);
+        assert!(parts[0].starts_with("\x1b_Gf=100,a=T,q=2,C=1,c=10,r=3,m=1;"));
+        assert!(parts[1].starts_with("\x1b_Gm=0;"));
+    }
+}
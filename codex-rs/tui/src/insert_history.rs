@@ -2,6 +2,7 @@ use std::fmt;
 use std::io;
 use std::io::Write;
 
+use crate::inline_image::InlineImage;
 use crate::wrapping::word_wrap_lines_borrowed;
 use crossterm::Command;
 use crossterm::cursor::MoveTo;
@@ -134,6 +135,29 @@ where
     Ok(())
 }
 
+/// Draw `image` over the last `image.rows` history rows, which
+/// [`insert_history_lines`] has just written above the viewport. Images
+/// taller than the space above the viewport are skipped, leaving the rows
+/// blank below their caption.
+pub(crate) fn draw_inline_image<B>(
+    terminal: &mut crate::custom_terminal::Terminal<B>,
+    image: &InlineImage,
+) -> io::Result<()>
+where
+    B: Backend + Write,
+{
+    let top = terminal.viewport_area.top();
+    if image.rows > top {
+        return Ok(());
+    }
+    let last_cursor_pos = terminal.last_known_cursor_pos;
+    let writer = terminal.backend_mut();
+    queue!(writer, MoveTo(image.column, top - image.rows))?;
+    queue!(writer, Print(&image.escape))?;
+    queue!(writer, MoveTo(last_cursor_pos.x, last_cursor_pos.y))?;
+    Ok(())
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SetScrollRegion(pub std::ops::Range<u16>);
 
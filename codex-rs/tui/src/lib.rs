@@ -23,7 +23,6 @@ use codex_core::find_conversation_path_by_id_str;
 use codex_core::get_platform_sandbox;
 use codex_core::protocol::AskForApproval;
 use codex_protocol::config_types::SandboxMode;
-use std::fs::OpenOptions;
 use std::path::PathBuf;
 use tracing::error;
 use tracing_appender::non_blocking;
@@ -31,17 +30,20 @@ use tracing_subscriber::EnvFilter;
 use tracing_subscriber::filter::Targets;
 use tracing_subscriber::prelude::*;
 
+mod accessibility;
 mod additional_dirs;
 mod app;
 mod app_backtrack;
 mod app_event;
 mod app_event_sender;
+mod artifacts;
 mod ascii_animation;
 mod bottom_pane;
 mod chatwidget;
 mod cli;
 mod clipboard_paste;
 mod color;
+mod crash_report;
 pub mod custom_terminal;
 mod diff_render;
 mod exec_cell;
@@ -50,9 +52,12 @@ mod file_search;
 mod frames;
 mod get_git_diff;
 mod history_cell;
+mod i18n;
+mod inline_image;
 pub mod insert_history;
 mod key_hint;
 pub mod live_wrap;
+mod log_file;
 mod markdown;
 mod markdown_render;
 mod markdown_stream;
@@ -62,6 +67,7 @@ pub mod onboarding;
 mod oss_selection;
 mod pager_overlay;
 pub mod public_widgets;
+pub mod releases;
 mod render;
 mod resume_picker;
 mod selection_list;
@@ -72,9 +78,11 @@ mod status;
 mod status_indicator_widget;
 mod streaming;
 mod style;
+mod terminal_capabilities;
 mod terminal_palette;
 mod text_formatting;
 mod tooltips;
+mod transcript_file;
 mod tui;
 mod ui_consts;
 pub mod update_action;
@@ -87,6 +95,8 @@ mod wrapping;
 #[cfg(test)]
 pub mod test_backend;
 
+use crate::chatwidget::RemoteAppServer;
+use crate::log_file::RotatingLogFile;
 use crate::onboarding::TrustDirectorySelection;
 use crate::onboarding::onboarding_screen::OnboardingScreenArgs;
 use crate::onboarding::onboarding_screen::run_onboarding_app;
@@ -198,6 +208,24 @@ pub async fn run_main(
         None // No model specified, will use the default.
     };
 
+    // In remote mode `--cd` names a directory on the server's machine, so
+    // it is passed along as given instead of shaping the local config.
+    #[allow(clippy::print_stderr)]
+    let remote = match cli.remote.as_deref() {
+        Some(_) if cli.resume_picker || cli.resume_last || cli.resume_session_id.is_some() => {
+            eprintln!("Error: `codex resume` cannot be combined with --remote");
+            std::process::exit(1);
+        }
+        Some(command) => match RemoteAppServer::parse(command, cli.cwd.take()) {
+            Ok(remote) => Some(remote),
+            Err(err) => {
+                eprintln!("Error: {err}");
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
     // canonicalize the cwd
     let cwd = cli.cwd.clone().map(|p| p.canonicalize().unwrap_or(p));
     let additional_dirs = cli.add_dir.clone();
@@ -234,38 +262,35 @@ pub async fn run_main(
     let active_profile = config.active_profile.clone();
     let log_dir = codex_core::config::log_dir(&config)?;
     std::fs::create_dir_all(&log_dir)?;
-    // Open (or create) your log file, appending to it.
-    let mut log_file_opts = OpenOptions::new();
-    log_file_opts.create(true).append(true);
-
-    // Ensure the file is only readable and writable by the current user.
-    // Doing the equivalent to `chmod 600` on Windows is quite a bit more code
-    // and requires the Windows API crates, so we can reconsider that when
-    // Codex CLI is officially supported on Windows.
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::OpenOptionsExt;
-        log_file_opts.mode(0o600);
-    }
-
-    let log_file = log_file_opts.open(log_dir.join("codex-tui.log"))?;
+    // Open (or create) your log file, appending to it and rotating it per
+    // the `[log]` settings.
+    let log_file = RotatingLogFile::open(log_dir.join("codex-tui.log"), &config.log)?;
 
     // Wrap file in non‑blocking writer.
     let (non_blocking, _guard) = non_blocking(log_file);
 
-    // use RUST_LOG env var, default to info for codex crates.
-    let env_filter = || {
-        EnvFilter::try_from_default_env().unwrap_or_else(|_| {
-            EnvFilter::new("codex_core=info,codex_tui=info,codex_rmcp_client=info")
-        })
-    };
+    // RUST_LOG wins over `log.filter`; default to info for codex crates.
+    const DEFAULT_LOG_FILTER: &str = "codex_core=info,codex_tui=info,codex_rmcp_client=info";
+    let env_filter = EnvFilter::try_new(codex_core::log_filter::initial_filter(
+        config.log.filter.as_deref(),
+        DEFAULT_LOG_FILTER,
+    ))
+    .unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER));
+    // Let `Op::SetLogFilter` (and `/log-filter`) swap the filter at runtime.
+    let (env_filter, env_filter_handle) = tracing_subscriber::reload::Layer::new(env_filter);
+    codex_core::log_filter::register_reloader(move |directives| {
+        let filter = EnvFilter::try_new(directives).map_err(|err| err.to_string())?;
+        env_filter_handle
+            .reload(filter)
+            .map_err(|err| err.to_string())
+    });
 
     let file_layer = tracing_subscriber::fmt::layer()
         .with_writer(non_blocking)
         .with_target(false)
         .with_ansi(false)
         .with_span_events(tracing_subscriber::fmt::format::FmtSpan::FULL)
-        .with_filter(env_filter());
+        .with_filter(env_filter);
 
     let feedback = codex_feedback::CodexFeedback::new();
     let targets = Targets::new().with_default(tracing::Level::TRACE);
@@ -320,6 +345,7 @@ pub async fn run_main(
         cli_kv_overrides,
         active_profile,
         feedback,
+        remote,
     )
     .await
     .map_err(|err| std::io::Error::other(err.to_string()))
@@ -332,22 +358,33 @@ async fn run_ratatui_app(
     cli_kv_overrides: Vec<(String, toml::Value)>,
     active_profile: Option<String>,
     feedback: codex_feedback::CodexFeedback,
+    remote: Option<RemoteAppServer>,
 ) -> color_eyre::Result<AppExitInfo> {
     color_eyre::install()?;
 
     // Forward panic reports through tracing so they appear in the UI status
     // line, but do not swallow the default/color-eyre panic handler.
     // Chain to the previous hook so users still get a rich panic report
-    // (including backtraces) after we restore the terminal.
+    // (including backtraces) after we restore the terminal, then point them
+    // at the crash report written under codex_home/crashes.
+    crash_report::init(&initial_config);
     let prev_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
         tracing::error!("panic: {info}");
+        let report_path = crash_report::write_report(info);
         prev_hook(info);
+        if let Some(path) = report_path {
+            eprintln!(
+                "A crash report was saved to {}. Please attach it when filing an issue at https://github.com/openai/codex/issues.",
+                path.display()
+            );
+        }
     }));
     let mut terminal = tui::init()?;
     terminal.clear()?;
 
     let mut tui = Tui::new(terminal);
+    terminal_capabilities::init(initial_config.tui_image_protocol);
 
     #[cfg(not(debug_assertions))]
     {
@@ -371,6 +408,8 @@ async fn run_ratatui_app(
 
     // Initialize high-fidelity session event logging if enabled.
     session_log::maybe_init(&initial_config);
+    i18n::init(initial_config.language.as_deref());
+    accessibility::init(initial_config.tui_accessibility);
 
     let auth_manager = AuthManager::shared(
         initial_config.codex_home.clone(),
@@ -379,8 +418,9 @@ async fn run_ratatui_app(
     );
     let login_status = get_login_status(&initial_config);
     let should_show_trust_screen = should_show_trust_screen(&initial_config);
-    let should_show_onboarding =
-        should_show_onboarding(login_status, &initial_config, should_show_trust_screen);
+    // A remote session signs in and trusts directories on the server.
+    let should_show_onboarding = remote.is_none()
+        && should_show_onboarding(login_status, &initial_config, should_show_trust_screen);
 
     let config = if should_show_onboarding {
         let onboarding_result = run_onboarding_app(
@@ -495,6 +535,7 @@ async fn run_ratatui_app(
         resume_selection,
         feedback,
         should_show_trust_screen, // Proxy to: is it a first run in this directory?
+        remote,
     )
     .await;
 
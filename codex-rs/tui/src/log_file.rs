@@ -0,0 +1,196 @@
+//! The TUI log file, rotated by size and optionally by hour or day.
+//!
+//! The active file keeps its name (`codex-tui.log`) so `tail -F` keeps
+//! following it; rotated files are renamed to `codex-tui.log.1`, `.2`, ...
+//! with `.1` the most recent.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::Local;
+use codex_core::config::types::LogRotation;
+use codex_core::config::types::Logging;
+
+const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+const DEFAULT_MAX_FILES: usize = 5;
+
+pub(crate) struct RotatingLogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    /// Rotation period (hour or day) the active file was written in.
+    period: Option<String>,
+    max_bytes: u64,
+    rotation: LogRotation,
+    max_files: usize,
+}
+
+impl RotatingLogFile {
+    pub(crate) fn open(path: PathBuf, settings: &Logging) -> io::Result<Self> {
+        let file = open_append(&path)?;
+        let metadata = file.metadata()?;
+        let last_written = metadata
+            .modified()
+            .map(DateTime::<Local>::from)
+            .unwrap_or_else(|_| Local::now());
+        Ok(Self {
+            size: metadata.len(),
+            period: period_key(settings.rotation, last_written),
+            file,
+            path,
+            max_bytes: settings.max_bytes.unwrap_or(DEFAULT_MAX_BYTES),
+            rotation: settings.rotation,
+            max_files: settings.max_files.unwrap_or(DEFAULT_MAX_FILES),
+        })
+    }
+
+    fn write_at(&mut self, buf: &[u8], now: DateTime<Local>) -> io::Result<usize> {
+        let period = period_key(self.rotation, now);
+        let period_changed = period != self.period;
+        let too_large =
+            self.max_bytes > 0 && self.size > 0 && self.size + buf.len() as u64 > self.max_bytes;
+        if period_changed || too_large {
+            // Keep logging to the current file if rotation fails; losing
+            // the rotation is better than losing the log line.
+            if let Err(err) = self.rotate() {
+                let _ = writeln!(self.file, "failed to rotate log file: {err}");
+            }
+            self.period = period;
+        }
+        let written = self.file.write(buf)?;
+        self.size += written as u64;
+        Ok(written)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        if self.max_files == 0 {
+            remove_if_exists(&self.path)?;
+        } else {
+            remove_if_exists(&rotated_path(&self.path, self.max_files))?;
+            for index in (1..self.max_files).rev() {
+                rename_if_exists(
+                    &rotated_path(&self.path, index),
+                    &rotated_path(&self.path, index + 1),
+                )?;
+            }
+            rename_if_exists(&self.path, &rotated_path(&self.path, 1))?;
+        }
+        self.file = open_append(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingLogFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_at(buf, Local::now())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+fn open_append(path: &Path) -> io::Result<File> {
+    let mut opts = OpenOptions::new();
+    opts.create(true).append(true);
+
+    // Ensure the file is only readable and writable by the current user.
+    // Doing the equivalent to `chmod 600` on Windows is quite a bit more code
+    // and requires the Windows API crates, so we can reconsider that when
+    // Codex CLI is officially supported on Windows.
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        opts.mode(0o600);
+    }
+
+    opts.open(path)
+}
+
+fn period_key(rotation: LogRotation, at: DateTime<Local>) -> Option<String> {
+    match rotation {
+        LogRotation::Never => None,
+        LogRotation::Hourly => Some(at.format("%Y-%m-%dT%H").to_string()),
+        LogRotation::Daily => Some(at.format("%Y-%m-%d").to_string()),
+    }
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{index}"));
+    path.with_file_name(name)
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match std::fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+fn rename_if_exists(from: &Path, to: &Path) -> io::Result<()> {
+    match std::fs::rename(from, to) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    fn read(path: &Path) -> String {
+        std::fs::read_to_string(path).unwrap_or_default()
+    }
+
+    #[test]
+    fn rotates_by_size_and_keeps_max_files() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("codex-tui.log");
+        let settings = Logging {
+            max_bytes: Some(6),
+            max_files: Some(2),
+            ..Default::default()
+        };
+        let mut log = RotatingLogFile::open(path.clone(), &settings).expect("open");
+        let now = Local::now();
+        for line in ["one\n", "two\n", "three\n", "four\n"] {
+            log.write_at(line.as_bytes(), now).expect("write");
+        }
+
+        assert_eq!(read(&path), "four\n");
+        assert_eq!(read(&rotated_path(&path, 1)), "three\n");
+        assert_eq!(read(&rotated_path(&path, 2)), "two\n");
+        assert!(!rotated_path(&path, 3).exists());
+    }
+
+    #[test]
+    fn rotates_when_the_day_changes() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("codex-tui.log");
+        let settings = Logging {
+            max_bytes: Some(0),
+            rotation: LogRotation::Daily,
+            ..Default::default()
+        };
+        let mut log = RotatingLogFile::open(path.clone(), &settings).expect("open");
+        let today = Local::now();
+        log.write_at(b"today\n", today).expect("write");
+        log.write_at(b"still today\n", today).expect("write");
+        log.write_at(b"tomorrow\n", today + Duration::days(1))
+            .expect("write");
+
+        assert_eq!(read(&path), "tomorrow\n");
+        assert_eq!(read(&rotated_path(&path, 1)), "today\nstill today\n");
+    }
+}
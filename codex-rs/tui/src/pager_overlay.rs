@@ -752,6 +752,7 @@ mod tests {
             vec![ParsedCommand::Unknown { cmd: "ls".into() }],
             ExecCommandSource::Agent,
             None,
+            None,
             true,
         );
         exec_cell.complete_call(
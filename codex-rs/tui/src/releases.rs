@@ -0,0 +1,149 @@
+//! Looks up Codex releases on GitHub for a given [`UpdateChannel`].
+//!
+//! Shared by the startup update notice and `codex self-update`.
+
+use codex_core::config::types::UpdateChannel;
+use codex_core::default_client::create_client;
+use serde::Deserialize;
+
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/openai/codex/releases/latest";
+const RECENT_RELEASES_URL: &str = "https://api.github.com/repos/openai/codex/releases?per_page=30";
+
+/// A published release as reported by the GitHub API.
+#[derive(Deserialize, Debug, Clone)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    /// Version without the `rust-v` tag prefix, e.g. `0.60.0-beta.1`.
+    pub fn version(&self) -> anyhow::Result<String> {
+        extract_version_from_latest_tag(&self.tag_name)
+    }
+
+    pub fn asset(&self, name: &str) -> Option<&ReleaseAsset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
+
+/// Fetch the newest release published on `channel`.
+pub async fn fetch_latest_release(channel: UpdateChannel) -> anyhow::Result<Release> {
+    match channel {
+        // `releases/latest` never returns pre-releases.
+        UpdateChannel::Stable => Ok(create_client()
+            .get(LATEST_RELEASE_URL)
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<Release>()
+            .await?),
+        UpdateChannel::Beta => {
+            let releases = create_client()
+                .get(RECENT_RELEASES_URL)
+                .send()
+                .await?
+                .error_for_status()?
+                .json::<Vec<Release>>()
+                .await?;
+            newest_on_channel(releases, channel)
+                .ok_or_else(|| anyhow::anyhow!("No {channel} release found"))
+        }
+    }
+}
+
+/// Whether `latest` is a newer version than `current`, including pre-release
+/// ordering (`0.60.0-beta.1` < `0.60.0`).
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    match (
+        semver::Version::parse(latest.trim()),
+        semver::Version::parse(current.trim()),
+    ) {
+        (Ok(latest), Ok(current)) => latest > current,
+        _ => false,
+    }
+}
+
+fn newest_on_channel(releases: Vec<Release>, channel: UpdateChannel) -> Option<Release> {
+    releases
+        .into_iter()
+        .filter(|release| !release.draft)
+        .filter_map(|release| {
+            let version = semver::Version::parse(&release.version().ok()?).ok()?;
+            on_channel(&version, channel).then_some((version, release))
+        })
+        .max_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(_, release)| release)
+}
+
+fn on_channel(version: &semver::Version, channel: UpdateChannel) -> bool {
+    match channel {
+        UpdateChannel::Stable => version.pre.is_empty(),
+        UpdateChannel::Beta => version.pre.is_empty() || version.pre.as_str().starts_with("beta"),
+    }
+}
+
+fn extract_version_from_latest_tag(latest_tag_name: &str) -> anyhow::Result<String> {
+    latest_tag_name
+        .strip_prefix("rust-v")
+        .map(str::to_owned)
+        .ok_or_else(|| anyhow::anyhow!("Failed to parse latest tag name '{latest_tag_name}'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn release(tag_name: &str) -> Release {
+        Release {
+            tag_name: tag_name.to_string(),
+            draft: false,
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn extracts_version_from_latest_tag() {
+        assert_eq!(
+            extract_version_from_latest_tag("rust-v1.5.0").expect("failed to parse version"),
+            "1.5.0"
+        );
+    }
+
+    #[test]
+    fn latest_tag_without_prefix_is_invalid() {
+        assert!(extract_version_from_latest_tag("v1.5.0").is_err());
+    }
+
+    #[test]
+    fn beta_channel_skips_alpha_releases() {
+        let releases = vec![
+            release("rust-v0.61.0-alpha.2"),
+            release("rust-v0.60.0"),
+            release("rust-v0.61.0-beta.1"),
+            release("rust-v0.59.0"),
+        ];
+        let newest = newest_on_channel(releases.clone(), UpdateChannel::Beta).expect("release");
+        assert_eq!(newest.tag_name, "rust-v0.61.0-beta.1");
+        let newest = newest_on_channel(releases, UpdateChannel::Stable).expect("release");
+        assert_eq!(newest.tag_name, "rust-v0.60.0");
+    }
+
+    #[test]
+    fn prerelease_orders_before_its_release() {
+        assert!(is_newer("0.61.0-beta.1", "0.60.0"));
+        assert!(is_newer("0.61.0", "0.61.0-beta.1"));
+        assert!(!is_newer("0.61.0-beta.1", "0.61.0"));
+        assert!(!is_newer("not-a-version", "0.61.0"));
+    }
+}
@@ -13,16 +13,25 @@ pub enum SlashCommand {
     // DO NOT ALPHA-SORT! Enum order is presentation order in the popup, so
     // more frequently used commands should be listed first.
     Model,
+    Effort,
+    Verbosity,
     Approvals,
     Experimental,
     Skills,
     Review,
+    Steer,
     New,
     Resume,
+    Cd,
     Init,
     Compact,
+    Summary,
+    Index,
     Undo,
     Diff,
+    FileAt,
+    Ci,
+    Artifacts,
     Mention,
     Status,
     Mcp,
@@ -30,6 +39,7 @@ pub enum SlashCommand {
     Quit,
     Exit,
     Feedback,
+    LogFilter,
     Rollout,
     Ps,
     TestApproval,
@@ -40,19 +50,41 @@ impl SlashCommand {
     pub fn description(self) -> &'static str {
         match self {
             SlashCommand::Feedback => "send logs to maintainers",
+            SlashCommand::LogFilter => {
+                "change the log filter for this run (e.g. /log-filter codex_core=debug)"
+            }
             SlashCommand::New => "start a new chat during a conversation",
             SlashCommand::Init => "create an AGENTS.md file with instructions for Codex",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
+            SlashCommand::Summary => "summarize this session as a pull request description",
             SlashCommand::Review => "review my current changes and find issues",
+            SlashCommand::Steer => {
+                "guide the running turn without interrupting it (e.g. /steer use pytest)"
+            }
             SlashCommand::Resume => "resume a saved chat",
+            SlashCommand::Cd => {
+                "change the working directory for later turns (e.g. /cd crates/core)"
+            }
+            SlashCommand::Index => {
+                "build or update the semantic search index (e.g. /index rebuild)"
+            }
             SlashCommand::Undo => "ask Codex to undo a turn",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::FileAt => {
+                "show a file as it was after an earlier turn (e.g. /file-at 3 src/main.rs)"
+            }
+            SlashCommand::Ci => "ask Codex why CI is failing, with the logs of the last failed run",
+            SlashCommand::Artifacts => "open or save files Codex registered as outputs",
             SlashCommand::Mention => "mention a file",
             SlashCommand::Skills => "use skills to improve how Codex performs specific tasks",
             SlashCommand::Status => "show current session configuration and token usage",
             SlashCommand::Ps => "list background terminals",
             SlashCommand::Model => "choose what model and reasoning effort to use",
+            SlashCommand::Effort => "set reasoning effort for this session (e.g. /effort high)",
+            SlashCommand::Verbosity => {
+                "set output verbosity for this session (e.g. /verbosity low)"
+            }
             SlashCommand::Approvals => "choose what Codex can do without approval",
             SlashCommand::Experimental => "toggle beta features",
             SlashCommand::Mcp => "list configured MCP tools",
@@ -73,21 +105,31 @@ impl SlashCommand {
         match self {
             SlashCommand::New
             | SlashCommand::Resume
+            | SlashCommand::Cd
             | SlashCommand::Init
             | SlashCommand::Compact
+            | SlashCommand::Summary
+            | SlashCommand::Index
             | SlashCommand::Undo
             | SlashCommand::Model
+            | SlashCommand::Effort
+            | SlashCommand::Verbosity
             | SlashCommand::Approvals
             | SlashCommand::Experimental
             | SlashCommand::Review
+            | SlashCommand::Ci
             | SlashCommand::Logout => false,
             SlashCommand::Diff
+            | SlashCommand::FileAt
+            | SlashCommand::Artifacts
             | SlashCommand::Mention
             | SlashCommand::Skills
             | SlashCommand::Status
             | SlashCommand::Ps
             | SlashCommand::Mcp
             | SlashCommand::Feedback
+            | SlashCommand::LogFilter
+            | SlashCommand::Steer
             | SlashCommand::Quit
             | SlashCommand::Exit => true,
             SlashCommand::Rollout => true,
@@ -95,6 +137,20 @@ impl SlashCommand {
         }
     }
 
+    /// Whether this command accepts inline arguments after the command name.
+    pub fn accepts_args(self) -> bool {
+        matches!(
+            self,
+            SlashCommand::Effort
+                | SlashCommand::Verbosity
+                | SlashCommand::Index
+                | SlashCommand::LogFilter
+                | SlashCommand::Cd
+                | SlashCommand::Steer
+                | SlashCommand::FileAt
+        )
+    }
+
     fn is_visible(self) -> bool {
         match self {
             SlashCommand::Rollout | SlashCommand::TestApproval => cfg!(debug_assertions),
@@ -12,9 +12,12 @@ use ratatui::style::Stylize;
 use ratatui::text::Line;
 use ratatui::widgets::WidgetRef;
 
+use crate::accessibility;
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
 use crate::exec_cell::spinner;
+use crate::i18n;
+use crate::i18n::Message;
 use crate::key_hint;
 use crate::render::renderable::Renderable;
 use crate::shimmer::shimmer_spans;
@@ -23,6 +26,8 @@ use crate::tui::FrameRequester;
 pub(crate) struct StatusIndicatorWidget {
     /// Animated header text (defaults to "Working").
     header: String,
+    /// Turn progress shown after the timer, e.g. "step 2/5".
+    progress: Option<String>,
     show_interrupt_hint: bool,
 
     elapsed_running: Duration,
@@ -57,7 +62,8 @@ impl StatusIndicatorWidget {
         animations_enabled: bool,
     ) -> Self {
         Self {
-            header: String::from("Working"),
+            header: i18n::tr(Message::Working).to_string(),
+            progress: None,
             show_interrupt_hint: true,
             elapsed_running: Duration::ZERO,
             last_resume_at: Instant::now(),
@@ -78,6 +84,11 @@ impl StatusIndicatorWidget {
         self.header = header;
     }
 
+    /// Update the progress label shown inside the brackets.
+    pub(crate) fn update_progress(&mut self, progress: Option<String>) {
+        self.progress = progress;
+    }
+
     #[cfg(test)]
     pub(crate) fn header(&self) -> &str {
         &self.header
@@ -144,12 +155,20 @@ impl Renderable for StatusIndicatorWidget {
             return;
         }
 
-        // Schedule next animation frame.
-        self.frame_requester
-            .schedule_frame_in(Duration::from_millis(32));
+        // A ticking timer makes screen readers re-read the line every second,
+        // so screen-reader mode shows a static line instead.
+        let screen_reader = accessibility::screen_reader_enabled();
+        if !screen_reader {
+            // Schedule next animation frame.
+            self.frame_requester
+                .schedule_frame_in(Duration::from_millis(32));
+        }
         let now = Instant::now();
         let elapsed_duration = self.elapsed_duration_at(now);
-        let pretty_elapsed = fmt_elapsed_compact(elapsed_duration.as_secs());
+        let mut pretty_elapsed = fmt_elapsed_compact(elapsed_duration.as_secs());
+        if let Some(progress) = &self.progress {
+            pretty_elapsed = format!("{pretty_elapsed} • {progress}");
+        }
 
         let mut spans = Vec::with_capacity(5);
         spans.push(spinner(Some(self.last_resume_at), self.animations_enabled));
@@ -160,11 +179,24 @@ impl Renderable for StatusIndicatorWidget {
             spans.push(self.header.clone().into());
         }
         spans.push(" ".into());
-        if self.show_interrupt_hint {
+        if screen_reader {
+            let progress = self
+                .progress
+                .as_ref()
+                .map(|progress| format!("{progress} • "))
+                .unwrap_or_default();
+            if self.show_interrupt_hint {
+                spans.extend(vec![
+                    format!("({progress}").dim(),
+                    key_hint::plain(KeyCode::Esc).into(),
+                    format!("{})", i18n::tr(Message::ToInterrupt)).dim(),
+                ]);
+            }
+        } else if self.show_interrupt_hint {
             spans.extend(vec![
                 format!("({pretty_elapsed} • ").dim(),
                 key_hint::plain(KeyCode::Esc).into(),
-                " to interrupt)".dim(),
+                format!("{})", i18n::tr(Message::ToInterrupt)).dim(),
             ]);
         } else {
             spans.push(format!("({pretty_elapsed})").dim());
@@ -0,0 +1,237 @@
+//! What the terminal can render, detected once at startup.
+//!
+//! `supports_color` only looks at `TERM`/`COLORTERM`, which many terminals
+//! leave unset or get wrong, so the detected terminal program refines its
+//! answer. The same detection decides whether non-ASCII glyphs are safe to
+//! draw and which graphics protocol, if any, can show images inline.
+
+use std::sync::OnceLock;
+
+use codex_core::config::types::ImageProtocolPreference;
+use codex_core::terminal::TerminalInfo;
+use codex_core::terminal::TerminalName;
+use codex_core::terminal::terminal_info;
+
+static CAPABILITIES: OnceLock<TerminalCapabilities> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct TerminalCapabilities {
+    pub color: ColorLevel,
+    /// Whether glyphs outside ASCII (bullets, box drawing, ✓/✗) render with
+    /// the width we expect.
+    pub unicode: bool,
+    /// Graphics protocol for inline images, if the terminal has one.
+    pub images: Option<ImageProtocol>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub(crate) enum ColorLevel {
+    None,
+    Ansi16,
+    Ansi256,
+    TrueColor,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ImageProtocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+}
+
+/// Detect the terminal's capabilities. Only the first call takes effect.
+pub(crate) fn init(image_protocol: ImageProtocolPreference) {
+    let capabilities = CAPABILITIES.get_or_init(|| {
+        detect(
+            &terminal_info(),
+            supports_color::on_cached(supports_color::Stream::Stdout),
+            &|name| std::env::var(name).ok(),
+            image_protocol,
+        )
+    });
+    tracing::info!("terminal capabilities: {capabilities:?}");
+}
+
+pub(crate) fn capabilities() -> TerminalCapabilities {
+    // Tests and code paths that run before `init` get the conservative
+    // defaults `supports_color` alone would give.
+    *CAPABILITIES.get_or_init(|| {
+        let unknown = TerminalInfo {
+            name: TerminalName::Unknown,
+            term_program: None,
+            version: None,
+            term: None,
+            multiplexer: None,
+        };
+        detect(
+            &unknown,
+            supports_color::on_cached(supports_color::Stream::Stdout),
+            &|_| None,
+            ImageProtocolPreference::None,
+        )
+    })
+}
+
+pub(crate) fn has_true_color() -> bool {
+    capabilities().color == ColorLevel::TrueColor
+}
+
+/// `unicode` when the terminal draws it reliably, `ascii` otherwise.
+pub(crate) fn glyph(unicode: &'static str, ascii: &'static str) -> &'static str {
+    if capabilities().unicode {
+        unicode
+    } else {
+        ascii
+    }
+}
+
+fn detect(
+    info: &TerminalInfo,
+    color: Option<supports_color::ColorLevel>,
+    env: &dyn Fn(&str) -> Option<String>,
+    image_protocol: ImageProtocolPreference,
+) -> TerminalCapabilities {
+    let reported = match color {
+        Some(level) if level.has_16m => ColorLevel::TrueColor,
+        Some(level) if level.has_256 => ColorLevel::Ansi256,
+        Some(_) => ColorLevel::Ansi16,
+        None => ColorLevel::None,
+    };
+    let color = match info.name {
+        // Terminal.app advertises 256 colors but mangles 24-bit sequences.
+        TerminalName::AppleTerminal => reported.min(ColorLevel::Ansi256),
+        // These render 24-bit color even when `COLORTERM` is not forwarded,
+        // for example through `sudo` or an SSH session that keeps
+        // `TERM_PROGRAM`.
+        TerminalName::Iterm2
+        | TerminalName::Ghostty
+        | TerminalName::WezTerm
+        | TerminalName::Kitty
+        | TerminalName::Alacritty
+        | TerminalName::VsCode
+        | TerminalName::WindowsTerminal
+            if reported != ColorLevel::None =>
+        {
+            ColorLevel::TrueColor
+        }
+        _ => reported,
+    };
+
+    let unicode = if cfg!(windows) {
+        true
+    } else if info.term.as_deref() == Some("linux") || env("TERM").as_deref() == Some("linux") {
+        // The Linux console font lacks most symbols we use.
+        false
+    } else {
+        ["LC_ALL", "LC_CTYPE", "LANG"]
+            .into_iter()
+            .find_map(|name| env(name).filter(|value| !value.is_empty()))
+            .map(|locale| {
+                let locale = locale.to_ascii_lowercase();
+                locale.contains("utf-8") || locale.contains("utf8")
+            })
+            // Without a locale most terminals still default to UTF-8.
+            .unwrap_or(true)
+    };
+
+    let images = match image_protocol {
+        ImageProtocolPreference::Kitty => Some(ImageProtocol::Kitty),
+        ImageProtocolPreference::Iterm2 => Some(ImageProtocol::Iterm2),
+        ImageProtocolPreference::Sixel => Some(ImageProtocol::Sixel),
+        ImageProtocolPreference::None => None,
+        // Multiplexers swallow graphics escapes unless passthrough is set up,
+        // so only draw images when talking to the terminal directly.
+        ImageProtocolPreference::Auto if info.multiplexer.is_some() => None,
+        ImageProtocolPreference::Auto => match info.name {
+            TerminalName::Kitty | TerminalName::Ghostty => Some(ImageProtocol::Kitty),
+            TerminalName::Iterm2 | TerminalName::WezTerm => Some(ImageProtocol::Iterm2),
+            TerminalName::Konsole => Some(ImageProtocol::Sixel),
+            _ if env("TERM")
+                .is_some_and(|term| term.starts_with("foot") || term.starts_with("mlterm")) =>
+            {
+                Some(ImageProtocol::Sixel)
+            }
+            _ => None,
+        },
+    };
+
+    TerminalCapabilities {
+        color,
+        unicode,
+        images,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_core::terminal::Multiplexer;
+    use pretty_assertions::assert_eq;
+
+    fn terminal(name: TerminalName, multiplexer: Option<Multiplexer>) -> TerminalInfo {
+        TerminalInfo {
+            name,
+            term_program: None,
+            version: None,
+            term: None,
+            multiplexer,
+        }
+    }
+
+    fn ansi256() -> Option<supports_color::ColorLevel> {
+        Some(supports_color::ColorLevel {
+            level: 2,
+            has_basic: true,
+            has_256: true,
+            has_16m: false,
+        })
+    }
+
+    #[test]
+    fn known_terminals_get_true_color_and_images() {
+        let utf8 = |name: &str| (name == "LANG").then(|| "en_US.UTF-8".to_string());
+        assert_eq!(
+            detect(
+                &terminal(TerminalName::Kitty, None),
+                ansi256(),
+                &utf8,
+                ImageProtocolPreference::Auto,
+            ),
+            TerminalCapabilities {
+                color: ColorLevel::TrueColor,
+                unicode: true,
+                images: Some(ImageProtocol::Kitty),
+            }
+        );
+        assert_eq!(
+            detect(
+                &terminal(
+                    TerminalName::Iterm2,
+                    Some(Multiplexer::Tmux { version: None })
+                ),
+                ansi256(),
+                &utf8,
+                ImageProtocolPreference::Auto,
+            )
+            .images,
+            None
+        );
+    }
+
+    #[test]
+    fn linux_console_and_non_utf8_locales_fall_back_to_ascii() {
+        let linux = |name: &str| (name == "TERM").then(|| "linux".to_string());
+        let latin1 = |name: &str| (name == "LC_ALL").then(|| "de_DE.ISO-8859-1".to_string());
+        for env in [&linux as &dyn Fn(&str) -> Option<String>, &latin1] {
+            let capabilities = detect(
+                &terminal(TerminalName::Unknown, None),
+                ansi256(),
+                env,
+                ImageProtocolPreference::Auto,
+            );
+            assert_eq!(capabilities.unicode, cfg!(windows));
+            assert_eq!(capabilities.color, ColorLevel::Ansi256);
+            assert_eq!(capabilities.images, None);
+        }
+    }
+}
@@ -1,16 +1,16 @@
 use crate::color::perceptual_distance;
+use crate::terminal_capabilities::ColorLevel;
+use crate::terminal_capabilities::capabilities;
 use ratatui::style::Color;
 
 /// Returns the closest color to the target color that the terminal can display.
 pub fn best_color(target: (u8, u8, u8)) -> Color {
-    let Some(color_level) = supports_color::on_cached(supports_color::Stream::Stdout) else {
-        return Color::default();
-    };
-    if color_level.has_16m {
+    let color_level = capabilities().color;
+    if color_level == ColorLevel::TrueColor {
         let (r, g, b) = target;
         #[allow(clippy::disallowed_methods)]
         Color::Rgb(r, g, b)
-    } else if color_level.has_256
+    } else if color_level == ColorLevel::Ansi256
         && let Some((i, _)) = xterm_fixed_colors().min_by(|(_, a), (_, b)| {
             perceptual_distance(*a, target)
                 .partial_cmp(&perceptual_distance(*b, target))
@@ -0,0 +1,120 @@
+//! Mirrors the transcript into a plain-text file (`tui.transcript_file`).
+//!
+//! Each history cell is appended as soon as it is inserted, so the file can be
+//! followed with `tail -f` from another terminal and survives lost scrollback.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use chrono::Local;
+use ratatui::text::Line;
+
+use crate::history_cell::HistoryCell;
+
+pub(crate) struct TranscriptFile {
+    file: File,
+}
+
+impl TranscriptFile {
+    /// Open `path` for appending and write a session header. Returns `None`
+    /// (after logging) when the file cannot be opened.
+    pub(crate) fn open(path: &Path) -> Option<Self> {
+        match Self::try_open(path) {
+            Ok(transcript) => Some(transcript),
+            Err(err) => {
+                tracing::warn!("failed to open transcript file {}: {err}", path.display());
+                None
+            }
+        }
+    }
+
+    fn try_open(path: &Path) -> std::io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut opts = OpenOptions::new();
+        opts.create(true).append(true);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            opts.mode(0o600);
+        }
+
+        let mut transcript = Self {
+            file: opts.open(path)?,
+        };
+        let started = Local::now().format("%Y-%m-%d %H:%M:%S");
+        transcript.write(&format!("=== Codex session started {started} ===\n"));
+        Ok(transcript)
+    }
+
+    /// Append `cell` as it renders at `width`, separated from the previous
+    /// cell by a blank line unless it continues a stream.
+    pub(crate) fn append_cell(&mut self, cell: &dyn HistoryCell, width: u16) {
+        let lines = cell.transcript_lines(width);
+        if lines.is_empty() {
+            return;
+        }
+        let mut text = String::new();
+        if !cell.is_stream_continuation() {
+            text.push('\n');
+        }
+        for line in &lines {
+            text.push_str(&plain_text(line));
+            text.push('\n');
+        }
+        self.write(&text);
+    }
+
+    fn write(&mut self, text: &str) {
+        if let Err(err) = self
+            .file
+            .write_all(text.as_bytes())
+            .and_then(|()| self.file.flush())
+        {
+            tracing::warn!("transcript file write error: {err}");
+        }
+    }
+}
+
+fn plain_text(line: &Line<'_>) -> String {
+    let text: String = line
+        .spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect();
+    text.trim_end().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history_cell::PlainHistoryCell;
+    use pretty_assertions::assert_eq;
+    use ratatui::style::Stylize;
+    use tempfile::tempdir;
+
+    #[test]
+    fn appends_cells_as_plain_text() {
+        let dir = tempdir().expect("tempdir");
+        let path = dir.path().join("logs").join("transcript.txt");
+        let mut transcript = TranscriptFile::open(&path).expect("open");
+
+        transcript.append_cell(
+            &PlainHistoryCell::new(vec![
+                Line::from(vec!["• ".dim(), "Ran ".bold(), "ls".into()]),
+                Line::from("  └ Cargo.toml   "),
+            ]),
+            80,
+        );
+        transcript.append_cell(&PlainHistoryCell::new(Vec::new()), 80);
+
+        let contents = std::fs::read_to_string(&path).expect("read");
+        let (header, body) = contents.split_once('\n').expect("header line");
+        assert!(header.starts_with("=== Codex session started "));
+        assert_eq!(body, "\n• Ran ls\n  └ Cargo.toml\n");
+    }
+}
@@ -37,6 +37,7 @@ use tokio_stream::Stream;
 pub use self::frame_requester::FrameRequester;
 use crate::custom_terminal;
 use crate::custom_terminal::Terminal as CustomTerminal;
+use crate::inline_image::InlineImage;
 use crate::notifications::DesktopNotificationBackend;
 use crate::notifications::NotificationBackendKind;
 use crate::notifications::detect_backend;
@@ -168,6 +169,9 @@ pub struct Tui {
     event_broker: Arc<EventBroker>,
     pub(crate) terminal: Terminal,
     pending_history_lines: Vec<Line<'static>>,
+    /// Images to draw over the rows reserved at the end of
+    /// `pending_history_lines[..end]`, keyed by that `end`.
+    pending_history_images: Vec<(usize, InlineImage)>,
     alt_saved_viewport: Option<ratatui::layout::Rect>,
     #[cfg(unix)]
     suspend_context: SuspendContext,
@@ -197,6 +201,7 @@ impl Tui {
             event_broker: Arc::new(EventBroker::new()),
             terminal,
             pending_history_lines: vec![],
+            pending_history_images: vec![],
             alt_saved_viewport: None,
             #[cfg(unix)]
             suspend_context: SuspendContext::new(),
@@ -329,6 +334,15 @@ impl Tui {
         self.frame_requester().schedule_frame();
     }
 
+    /// Like [`Tui::insert_history_lines`], then draw `image` over the last
+    /// `image.rows` of `lines`, which the caller leaves blank.
+    pub(crate) fn insert_history_image(&mut self, lines: Vec<Line<'static>>, image: InlineImage) {
+        self.pending_history_lines.extend(lines);
+        self.pending_history_images
+            .push((self.pending_history_lines.len(), image));
+        self.frame_requester().schedule_frame();
+    }
+
     pub fn draw(
         &mut self,
         height: u16,
@@ -376,11 +390,19 @@ impl Tui {
             }
 
             if !self.pending_history_lines.is_empty() {
-                crate::insert_history::insert_history_lines(
-                    terminal,
-                    self.pending_history_lines.clone(),
-                )?;
-                self.pending_history_lines.clear();
+                let mut lines = std::mem::take(&mut self.pending_history_lines);
+                let mut start = 0;
+                for (end, image) in std::mem::take(&mut self.pending_history_images) {
+                    crate::insert_history::insert_history_lines(
+                        terminal,
+                        lines[start..end].to_vec(),
+                    )?;
+                    crate::insert_history::draw_inline_image(terminal, &image)?;
+                    start = end;
+                }
+                if start < lines.len() {
+                    crate::insert_history::insert_history_lines(terminal, lines.split_off(start))?;
+                }
             }
 
             // Update the y position for suspending so Ctrl-Z can place the cursor correctly.
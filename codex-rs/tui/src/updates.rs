@@ -1,11 +1,13 @@
 #![cfg(not(debug_assertions))]
 
+use crate::releases;
 use crate::update_action;
 use crate::update_action::UpdateAction;
 use chrono::DateTime;
 use chrono::Duration;
 use chrono::Utc;
 use codex_core::config::Config;
+use codex_core::config::types::UpdateChannel;
 use codex_core::default_client::create_client;
 use serde::Deserialize;
 use serde::Serialize;
@@ -22,26 +24,31 @@ pub fn get_upgrade_version(config: &Config) -> Option<String> {
     let version_file = version_filepath(config);
     let info = read_version_info(&version_file).ok();
 
+    let channel = config.update_channel;
     if match &info {
         None => true,
-        Some(info) => info.last_checked_at < Utc::now() - Duration::hours(20),
+        Some(info) => {
+            info.channel != channel || info.last_checked_at < Utc::now() - Duration::hours(20)
+        }
     } {
         // Refresh the cached latest version in the background so TUI startup
         // isn’t blocked by a network call. The UI reads the previously cached
         // value (if any) for this run; the next run shows the banner if needed.
         tokio::spawn(async move {
-            check_for_update(&version_file)
+            check_for_update(&version_file, channel)
                 .await
                 .inspect_err(|e| tracing::error!("Failed to update version: {e}"))
         });
     }
 
     info.and_then(|info| {
-        if is_newer(&info.latest_version, CODEX_CLI_VERSION).unwrap_or(false) {
-            Some(info.latest_version)
-        } else {
-            None
-        }
+        let newer = match channel {
+            UpdateChannel::Stable => {
+                is_newer(&info.latest_version, CODEX_CLI_VERSION).unwrap_or(false)
+            }
+            UpdateChannel::Beta => releases::is_newer(&info.latest_version, CODEX_CLI_VERSION),
+        };
+        (info.channel == channel && newer).then_some(info.latest_version)
     })
 }
 
@@ -52,18 +59,15 @@ struct VersionInfo {
     last_checked_at: DateTime<Utc>,
     #[serde(default)]
     dismissed_version: Option<String>,
+    /// Channel `latest_version` was looked up on.
+    #[serde(default)]
+    channel: UpdateChannel,
 }
 
 const VERSION_FILENAME: &str = "version.json";
 // We use the latest version from the cask if installation is via homebrew - homebrew does not immediately pick up the latest release and can lag behind.
 const HOMEBREW_CASK_URL: &str =
     "https://raw.githubusercontent.com/Homebrew/homebrew-cask/HEAD/Casks/c/codex.rb";
-const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/openai/codex/releases/latest";
-
-#[derive(Deserialize, Debug, Clone)]
-struct ReleaseInfo {
-    tag_name: String,
-}
 
 fn version_filepath(config: &Config) -> PathBuf {
     config.codex_home.join(VERSION_FILENAME)
@@ -74,8 +78,9 @@ fn read_version_info(version_file: &Path) -> anyhow::Result<VersionInfo> {
     Ok(serde_json::from_str(&contents)?)
 }
 
-async fn check_for_update(version_file: &Path) -> anyhow::Result<()> {
+async fn check_for_update(version_file: &Path, channel: UpdateChannel) -> anyhow::Result<()> {
     let latest_version = match update_action::get_update_action() {
+        // Homebrew only publishes stable releases.
         Some(UpdateAction::BrewUpgrade) => {
             let cask_contents = create_client()
                 .get(HOMEBREW_CASK_URL)
@@ -86,18 +91,7 @@ async fn check_for_update(version_file: &Path) -> anyhow::Result<()> {
                 .await?;
             extract_version_from_cask(&cask_contents)?
         }
-        _ => {
-            let ReleaseInfo {
-                tag_name: latest_tag_name,
-            } = create_client()
-                .get(LATEST_RELEASE_URL)
-                .send()
-                .await?
-                .error_for_status()?
-                .json::<ReleaseInfo>()
-                .await?;
-            extract_version_from_latest_tag(&latest_tag_name)?
-        }
+        _ => releases::fetch_latest_release(channel).await?.version()?,
     };
 
     // Preserve any previously dismissed version if present.
@@ -106,6 +100,7 @@ async fn check_for_update(version_file: &Path) -> anyhow::Result<()> {
         latest_version,
         last_checked_at: Utc::now(),
         dismissed_version: prev_info.and_then(|p| p.dismissed_version),
+        channel,
     };
 
     let json_line = format!("{}\n", serde_json::to_string(&info)?);
@@ -135,13 +130,6 @@ fn extract_version_from_cask(cask_contents: &str) -> anyhow::Result<String> {
         .ok_or_else(|| anyhow::anyhow!("Failed to find version in Homebrew cask file"))
 }
 
-fn extract_version_from_latest_tag(latest_tag_name: &str) -> anyhow::Result<String> {
-    latest_tag_name
-        .strip_prefix("rust-v")
-        .map(str::to_owned)
-        .ok_or_else(|| anyhow::anyhow!("Failed to parse latest tag name '{latest_tag_name}'"))
-}
-
 /// Returns the latest version to show in a popup, if it should be shown.
 /// This respects the user's dismissal choice for the current latest version.
 pub fn get_upgrade_version_for_popup(config: &Config) -> Option<String> {
@@ -202,19 +190,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn extracts_version_from_latest_tag() {
-        assert_eq!(
-            extract_version_from_latest_tag("rust-v1.5.0").expect("failed to parse version"),
-            "1.5.0"
-        );
-    }
-
-    #[test]
-    fn latest_tag_without_prefix_is_invalid() {
-        assert!(extract_version_from_latest_tag("v1.5.0").is_err());
-    }
-
     #[test]
     fn prerelease_version_is_not_considered_newer() {
         assert_eq!(is_newer("0.11.0-beta.1", "0.11.0"), None);
@@ -36,6 +36,7 @@ use codex_core::openai_models::model_presets::HIDE_GPT_5_1_CODEX_MAX_MIGRATION_P
 use codex_core::openai_models::model_presets::HIDE_GPT5_1_MIGRATION_PROMPT_CONFIG;
 use codex_core::openai_models::models_manager::ModelsManager;
 use codex_core::protocol::EventMsg;
+use codex_core::protocol::FileChange;
 use codex_core::protocol::FinalOutput;
 use codex_core::protocol::ListSkillsResponseEvent;
 use codex_core::protocol::Op;
@@ -1608,6 +1609,15 @@ impl App {
             AppEvent::OpenReasoningPopup { model } => {
                 self.chat_widget.open_reasoning_popup(model);
             }
+            AppEvent::OpenArtifactActions { artifact } => {
+                self.chat_widget.open_artifact_actions_popup(artifact);
+            }
+            AppEvent::OpenArtifact { path } => {
+                self.chat_widget.open_artifact(&path);
+            }
+            AppEvent::SaveArtifact { path } => {
+                self.chat_widget.save_artifact(&path);
+            }
             AppEvent::OpenAllModelsPopup { models } => {
                 self.chat_widget.open_all_models_popup(models);
             }
@@ -1673,6 +1683,7 @@ impl App {
                                         model: None,
                                         effort: None,
                                         summary: None,
+                                        verbosity: None,
                                     },
                                 ));
                                 self.app_event_tx
@@ -1910,6 +1921,29 @@ impl App {
                         "E L I C I T A T I O N".to_string(),
                     ));
                 }
+                ApprovalRequest::PatchConflict { cwd, conflicts, .. } => {
+                    let _ = tui.enter_alt_screen();
+                    // Show what changed on disk since Codex last read each file.
+                    let changes = conflicts
+                        .into_iter()
+                        .map(|conflict| {
+                            let change = match conflict.diff {
+                                Some(unified_diff) => FileChange::Update {
+                                    unified_diff,
+                                    move_path: None,
+                                },
+                                None => FileChange::Delete {
+                                    content: String::new(),
+                                },
+                            };
+                            (conflict.path, change)
+                        })
+                        .collect();
+                    self.overlay = Some(Overlay::new_static_with_renderables(
+                        vec![DiffSummary::new(changes, cwd).into()],
+                        "C O N F L I C T".to_string(),
+                    ));
+                }
             },
         }
         Ok(true)
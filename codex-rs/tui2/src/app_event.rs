@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 use codex_common::approval_presets::ApprovalPreset;
+use codex_core::protocol::Artifact;
 use codex_core::protocol::ConversationPathResponseEvent;
 use codex_core::protocol::Event;
 use codex_core::protocol::RateLimitSnapshot;
@@ -74,6 +75,21 @@ pub(crate) enum AppEvent {
         model: ModelPreset,
     },
 
+    /// Show the open/save actions for an artifact picked in `/artifacts`.
+    OpenArtifactActions {
+        artifact: Artifact,
+    },
+
+    /// Open an artifact with the default application.
+    OpenArtifact {
+        path: PathBuf,
+    },
+
+    /// Copy an artifact into the working directory.
+    SaveArtifact {
+        path: PathBuf,
+    },
+
     /// Open the full model picker (non-auto models).
     OpenAllModelsPopup {
         models: Vec<ModelPreset>,
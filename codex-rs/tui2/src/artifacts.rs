@@ -0,0 +1,147 @@
+//! Open and save actions for files the model registered as artifacts.
+
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+/// Extensions the platform openers run rather than display.
+const EXECUTABLE_EXTENSIONS: &[&str] = &[
+    "app", "bat", "cmd", "com", "command", "cpl", "desktop", "exe", "hta", "jar", "js", "jse",
+    "lnk", "msc", "msi", "pif", "ps1", "reg", "scr", "sh", "vbe", "vbs", "wsf", "wsh",
+];
+
+/// Open `path` with the platform's default application. Returns once the
+/// opener has been launched; it is not waited on. Refuses anything the
+/// opener would run instead of open, see [`check_openable`].
+pub(crate) fn open_with_default_app(path: &Path) -> io::Result<()> {
+    check_openable(path)?;
+    // The path is passed as a single argument, never through a shell.
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        Command::new("explorer.exe")
+    } else {
+        Command::new("xdg-open")
+    };
+    command
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map(|_| ())
+}
+
+/// Fail unless `path` is a regular file that is neither executable nor of a
+/// type the platform openers launch, such as `.exe` or `.app`.
+fn check_openable(path: &Path) -> io::Result<()> {
+    let metadata = std::fs::metadata(path)?;
+    if !metadata.is_file() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "only regular files can be opened",
+        ));
+    }
+    #[cfg(unix)]
+    let executable = {
+        use std::os::unix::fs::PermissionsExt;
+        metadata.permissions().mode() & 0o111 != 0
+    };
+    #[cfg(not(unix))]
+    let executable = false;
+    let launchable = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            EXECUTABLE_EXTENSIONS
+                .iter()
+                .any(|candidate| candidate.eq_ignore_ascii_case(extension))
+        });
+    if executable || launchable {
+        return Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            "refusing to open a program; save it and inspect it instead",
+        ));
+    }
+    Ok(())
+}
+
+/// Copy `path` into `dir`, keeping its file name. Refuses to overwrite an
+/// existing file and returns the path written.
+pub(crate) fn save_to_dir(path: &Path, dir: &Path) -> io::Result<PathBuf> {
+    let file_name = path.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "artifact path has no file name",
+        )
+    })?;
+    let destination = dir.join(file_name);
+    if destination.exists() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", destination.display()),
+        ));
+    }
+    std::fs::copy(path, &destination)?;
+    Ok(destination)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use tempfile::tempdir;
+
+    #[test]
+    fn save_to_dir_copies_without_overwriting() {
+        let source_dir = tempdir().expect("tempdir");
+        let target_dir = tempdir().expect("tempdir");
+        let artifact = source_dir.path().join("report.md");
+        std::fs::write(&artifact, "# Report").expect("write artifact");
+
+        let saved = save_to_dir(&artifact, target_dir.path()).expect("save artifact");
+        assert_eq!(saved, target_dir.path().join("report.md"));
+        assert_eq!(
+            std::fs::read_to_string(&saved).expect("read saved"),
+            "# Report"
+        );
+
+        let err = save_to_dir(&artifact, target_dir.path()).expect_err("second save fails");
+        assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+    }
+
+    #[test]
+    fn check_openable_refuses_programs_and_directories() {
+        let dir = tempdir().expect("tempdir");
+        let report = dir.path().join("report.md");
+        std::fs::write(&report, "# Report").expect("write report");
+        let installer = dir.path().join("setup.EXE");
+        std::fs::write(&installer, "MZ").expect("write installer");
+
+        assert!(check_openable(&report).is_ok());
+        assert_eq!(
+            check_openable(&installer).expect_err("exe refused").kind(),
+            io::ErrorKind::PermissionDenied
+        );
+        assert_eq!(
+            check_openable(dir.path())
+                .expect_err("directory refused")
+                .kind(),
+            io::ErrorKind::InvalidInput
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let script = dir.path().join("report");
+            std::fs::write(&script, "#!/bin/sh\n").expect("write script");
+            std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))
+                .expect("chmod script");
+            assert_eq!(
+                check_openable(&script).expect_err("script refused").kind(),
+                io::ErrorKind::PermissionDenied
+            );
+        }
+    }
+}
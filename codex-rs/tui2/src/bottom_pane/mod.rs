@@ -9,6 +9,7 @@ use crate::render::renderable::RenderableItem;
 use crate::tui::FrameRequester;
 use bottom_pane_view::BottomPaneView;
 use codex_core::features::Features;
+use codex_core::protocol::ApprovalPresetId;
 use codex_core::skills::model::SkillMetadata;
 use codex_file_search::FileMatch;
 use crossterm::event::KeyCode;
@@ -29,6 +30,8 @@ mod file_search_popup;
 mod footer;
 mod list_selection_view;
 mod prompt_args;
+mod question_view;
+pub(crate) use question_view::QuestionView;
 mod skill_popup;
 pub(crate) use list_selection_view::SelectionViewParams;
 mod feedback_view;
@@ -368,6 +371,11 @@ impl BottomPane {
         }
     }
 
+    pub(crate) fn set_approval_preset(&mut self, preset: Option<ApprovalPresetId>) {
+        self.composer.set_approval_preset(preset);
+        self.request_redraw();
+    }
+
     pub(crate) fn set_context_window(&mut self, percent: Option<i64>, used_tokens: Option<i64>) {
         if self.context_window_percent == percent && self.context_window_used_tokens == used_tokens
         {
@@ -589,6 +597,7 @@ mod tests {
         ApprovalRequest::Exec {
             id: "1".to_string(),
             command: vec!["echo".into(), "ok".into()],
+            env: Default::default(),
             reason: None,
             proposed_execpolicy_amendment: None,
         }
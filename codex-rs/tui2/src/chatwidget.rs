@@ -21,8 +21,17 @@ use codex_core::protocol::AgentReasoningEvent;
 use codex_core::protocol::AgentReasoningRawContentDeltaEvent;
 use codex_core::protocol::AgentReasoningRawContentEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
+use codex_core::protocol::ApprovalPresetId;
+use codex_core::protocol::Artifact;
+use codex_core::protocol::ArtifactRegisteredEvent;
 use codex_core::protocol::BackgroundEventEvent;
+use codex_core::protocol::CandidateSelectedEvent;
+use codex_core::protocol::CandidateSelector;
+use codex_core::protocol::CandidatesProposedEvent;
+use codex_core::protocol::ContextRepairedEvent;
+use codex_core::protocol::ContextTrimmedEvent;
 use codex_core::protocol::CreditsSnapshot;
+use codex_core::protocol::DependencyReportEvent;
 use codex_core::protocol::DeprecationNoticeEvent;
 use codex_core::protocol::ErrorEvent;
 use codex_core::protocol::Event;
@@ -40,9 +49,14 @@ use codex_core::protocol::McpStartupStatus;
 use codex_core::protocol::McpStartupUpdateEvent;
 use codex_core::protocol::McpToolCallBeginEvent;
 use codex_core::protocol::McpToolCallEndEvent;
+use codex_core::protocol::MemoryCandidatesProposedEvent;
 use codex_core::protocol::Op;
 use codex_core::protocol::PatchApplyBeginEvent;
+use codex_core::protocol::PatchConflictEvent;
+use codex_core::protocol::ProtectedPathViolationEvent;
+use codex_core::protocol::QuestionEvent;
 use codex_core::protocol::RateLimitSnapshot;
+use codex_core::protocol::ResponseRegeneratedEvent;
 use codex_core::protocol::ReviewRequest;
 use codex_core::protocol::ReviewTarget;
 use codex_core::protocol::SkillsListEntry;
@@ -60,6 +74,7 @@ use codex_core::protocol::ViewImageToolCallEvent;
 use codex_core::protocol::WarningEvent;
 use codex_core::protocol::WebSearchBeginEvent;
 use codex_core::protocol::WebSearchEndEvent;
+use codex_core::protocol::WorkflowStepStartedEvent;
 use codex_core::skills::model::SkillMetadata;
 use codex_protocol::ConversationId;
 use codex_protocol::account::PlanType;
@@ -84,11 +99,13 @@ use tracing::debug;
 
 use crate::app_event::AppEvent;
 use crate::app_event_sender::AppEventSender;
+use crate::artifacts;
 use crate::bottom_pane::ApprovalRequest;
 use crate::bottom_pane::BottomPane;
 use crate::bottom_pane::BottomPaneParams;
 use crate::bottom_pane::CancellationEvent;
 use crate::bottom_pane::InputResult;
+use crate::bottom_pane::QuestionView;
 use crate::bottom_pane::SelectionAction;
 use crate::bottom_pane::SelectionItem;
 use crate::bottom_pane::SelectionViewParams;
@@ -334,6 +351,8 @@ pub(crate) struct ChatWidget {
     feedback: codex_feedback::CodexFeedback,
     // Current session rollout path (if known)
     current_rollout_path: Option<PathBuf>,
+    // Files the model registered as outputs, oldest first.
+    artifacts: Vec<Artifact>,
 }
 
 struct UserMessage {
@@ -821,6 +840,229 @@ impl ChatWidget {
         );
     }
 
+    fn on_patch_conflict(&mut self, ev: PatchConflictEvent) {
+        let ev2 = ev.clone();
+        self.defer_or_handle(
+            |q| q.push_patch_conflict(ev),
+            |s| s.handle_patch_conflict_now(ev2),
+        );
+    }
+
+    fn on_protected_path_violation(&mut self, ev: ProtectedPathViolationEvent) {
+        let paths = ev
+            .violations
+            .iter()
+            .map(|violation| {
+                format!(
+                    "{} (`{}`)",
+                    display_path_for(&violation.path, &self.config.cwd),
+                    violation.pattern
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.on_warning(format!("Refused a patch to protected paths: {paths}"));
+    }
+
+    fn on_dependency_report(&mut self, ev: DependencyReportEvent) {
+        self.add_to_history(history_cell::new_dependency_report(
+            &ev.dependencies,
+            &self.config.cwd,
+        ));
+        self.request_redraw();
+    }
+
+    fn on_context_repaired(&mut self, ev: ContextRepairedEvent) {
+        let mut changes = ev.dropped;
+        if ev.compacted {
+            changes.push("compacted the history".to_string());
+        }
+        self.on_warning(format!(
+            "The provider rejected the request; retrying after repairing the context: {}.",
+            changes.join(", ")
+        ));
+    }
+
+    fn on_context_trimmed(&mut self, ev: ContextTrimmedEvent) {
+        self.add_info_message(ev.summary, None);
+    }
+
+    fn on_candidates_proposed(&mut self, ev: CandidatesProposedEvent) {
+        self.flush_answer_stream_with_separator();
+        let mut items: Vec<SelectionItem> = Vec::new();
+        for (index, candidate) in ev.candidates.iter().enumerate() {
+            let name = format!("Candidate {}", index + 1);
+            let mut lines: Vec<Line<'static>> = vec![name.clone().bold().into()];
+            lines.extend(
+                candidate
+                    .message
+                    .lines()
+                    .map(|line| Line::from(line.to_string())),
+            );
+            lines.extend(
+                candidate
+                    .tool_calls
+                    .iter()
+                    .map(|call| Line::from(format!("→ {call}").dim())),
+            );
+            self.add_plain_history_lines(lines);
+
+            let description = candidate
+                .message
+                .lines()
+                .find(|line| !line.trim().is_empty())
+                .or(candidate.tool_calls.first().map(String::as_str))
+                .map(str::to_string);
+            let turn_id = ev.turn_id.clone();
+            items.push(SelectionItem {
+                name,
+                description,
+                actions: vec![Box::new(move |tx: &AppEventSender| {
+                    tx.send(AppEvent::CodexOp(Op::SelectCandidate {
+                        id: turn_id.clone(),
+                        index: Some(index),
+                    }));
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            });
+        }
+        let turn_id = ev.turn_id;
+        items.push(SelectionItem {
+            name: "Let Codex decide".to_string(),
+            description: Some("A judge prompt picks the best candidate".to_string()),
+            actions: vec![Box::new(move |tx: &AppEventSender| {
+                tx.send(AppEvent::CodexOp(Op::SelectCandidate {
+                    id: turn_id.clone(),
+                    index: None,
+                }));
+            })],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Continue with which response?".to_string()),
+            subtitle: Some("None of their tool calls have run yet.".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    fn on_memory_candidates_proposed(&mut self, ev: MemoryCandidatesProposedEvent) {
+        let location = display_path_for(&ev.location, &self.config.cwd);
+        let mut lines: Vec<Line<'static>> = vec![
+            format!("Codex would remember, in {location}:")
+                .bold()
+                .into(),
+        ];
+        lines.extend(
+            ev.candidates
+                .iter()
+                .map(|candidate| Line::from(format!("  • {candidate}"))),
+        );
+        self.add_plain_history_lines(lines);
+
+        let review = |turn_id: String, accepted: Vec<usize>| -> SelectionAction {
+            Box::new(move |tx: &AppEventSender| {
+                tx.send(AppEvent::CodexOp(Op::ReviewMemories {
+                    id: turn_id.clone(),
+                    accepted: accepted.clone(),
+                }));
+            })
+        };
+        let count = ev.candidates.len();
+        let mut items = vec![SelectionItem {
+            name: if count == 1 {
+                "Save it".to_string()
+            } else {
+                format!("Save all {count}")
+            },
+            actions: vec![review(ev.turn_id.clone(), (0..count).collect())],
+            dismiss_on_select: true,
+            ..Default::default()
+        }];
+        if count > 1 {
+            items.extend(ev.candidates.iter().enumerate().map(|(index, candidate)| {
+                SelectionItem {
+                    name: format!("Save only {}", index + 1),
+                    description: Some(candidate.clone()),
+                    actions: vec![review(ev.turn_id.clone(), vec![index])],
+                    dismiss_on_select: true,
+                    ..Default::default()
+                }
+            }));
+        }
+        items.push(SelectionItem {
+            name: "Don't save".to_string(),
+            actions: vec![review(ev.turn_id, Vec::new())],
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Save these memories?".to_string()),
+            subtitle: Some(format!(
+                "They are saved anyway after {} seconds without an answer.",
+                ev.auto_accept_secs
+            )),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+        self.request_redraw();
+    }
+
+    fn on_candidate_selected(&mut self, ev: CandidateSelectedEvent) {
+        let hint = match ev.selected_by {
+            CandidateSelector::User => None,
+            CandidateSelector::Judge => Some("(picked by the judge prompt)".to_string()),
+        };
+        self.add_info_message(
+            format!(
+                "Continuing with candidate {} of {}",
+                ev.index + 1,
+                ev.candidates
+            ),
+            hint,
+        );
+    }
+
+    fn on_response_regenerated(&mut self, ev: ResponseRegeneratedEvent) {
+        self.add_info_message(
+            format!("Regenerating the last response with {}", ev.model),
+            None,
+        );
+    }
+
+    fn on_workflow_step_started(&mut self, ev: WorkflowStepStartedEvent) {
+        self.add_info_message(
+            format!(
+                "Workflow {}: step {}/{}",
+                ev.workflow,
+                ev.step + 1,
+                ev.steps
+            ),
+            Some(ev.prompt),
+        );
+    }
+
+    fn on_artifact_registered(&mut self, ev: ArtifactRegisteredEvent) {
+        self.add_to_history(history_cell::new_artifact_registered(
+            &ev.artifact,
+            &self.config.cwd,
+        ));
+        self.artifacts.push(ev.artifact);
+        self.request_redraw();
+    }
+
+    fn on_question(&mut self, ev: QuestionEvent) {
+        let ev2 = ev.clone();
+        self.defer_or_handle(|q| q.push_question(ev), |s| s.handle_question_now(ev2));
+    }
+
     fn on_elicitation_request(&mut self, ev: ElicitationRequestEvent) {
         let ev2 = ev.clone();
         self.defer_or_handle(
@@ -1107,6 +1349,7 @@ impl ChatWidget {
         let request = ApprovalRequest::Exec {
             id,
             command: ev.command,
+            env: ev.env,
             reason: ev.reason,
             proposed_execpolicy_amendment: ev.proposed_execpolicy_amendment,
         };
@@ -1137,6 +1380,29 @@ impl ChatWidget {
         });
     }
 
+    pub(crate) fn handle_patch_conflict_now(&mut self, ev: PatchConflictEvent) {
+        self.flush_answer_stream_with_separator();
+
+        let request = ApprovalRequest::PatchConflict {
+            id: ev.turn_id,
+            cwd: self.config.cwd.clone(),
+            conflicts: ev.conflicts,
+        };
+        self.bottom_pane
+            .push_approval_request(request, &self.config.features);
+        self.request_redraw();
+    }
+
+    pub(crate) fn handle_question_now(&mut self, ev: QuestionEvent) {
+        self.flush_answer_stream_with_separator();
+        // Keep the question in the transcript once the prompt is gone.
+        self.add_info_message(format!("Codex asked: {}", ev.question), None);
+
+        let view = QuestionView::new(ev, self.app_event_tx.clone());
+        self.bottom_pane.show_view(Box::new(view));
+        self.request_redraw();
+    }
+
     pub(crate) fn handle_elicitation_request_now(&mut self, ev: ElicitationRequestEvent) {
         self.flush_answer_stream_with_separator();
 
@@ -1336,9 +1602,11 @@ impl ChatWidget {
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            artifacts: Vec::new(),
         };
 
         widget.prefetch_rate_limits();
+        widget.refresh_approval_preset();
 
         widget
     }
@@ -1421,9 +1689,11 @@ impl ChatWidget {
             last_rendered_width: std::cell::Cell::new(None),
             feedback,
             current_rollout_path: None,
+            artifacts: Vec::new(),
         };
 
         widget.prefetch_rate_limits();
+        widget.refresh_approval_preset();
 
         widget
     }
@@ -1498,6 +1768,9 @@ impl ChatWidget {
                     InputResult::Command(cmd) => {
                         self.dispatch_command(cmd);
                     }
+                    InputResult::CommandWithArgs(cmd, args) => {
+                        self.dispatch_command_with_args(cmd, args);
+                    }
                     InputResult::None => {}
                 }
             }
@@ -1519,7 +1792,9 @@ impl ChatWidget {
         self.request_redraw();
     }
 
-    fn dispatch_command(&mut self, cmd: SlashCommand) {
+    /// Returns true (and reports an error) when `cmd` cannot run while a task
+    /// is in progress.
+    fn reject_command_during_task(&mut self, cmd: SlashCommand) -> bool {
         if !cmd.available_during_task() && self.bottom_pane.is_task_running() {
             let message = format!(
                 "'/{}' is disabled while a task is in progress.",
@@ -1527,6 +1802,23 @@ impl ChatWidget {
             );
             self.add_to_history(history_cell::new_error_event(message));
             self.request_redraw();
+            return true;
+        }
+        false
+    }
+
+    fn dispatch_command_with_args(&mut self, cmd: SlashCommand, args: String) {
+        if self.reject_command_during_task(cmd) {
+            return;
+        }
+        match cmd {
+            SlashCommand::Steer => self.steer_from_args(args.trim()),
+            _ => self.dispatch_command(cmd),
+        }
+    }
+
+    fn dispatch_command(&mut self, cmd: SlashCommand) {
+        if self.reject_command_during_task(cmd) {
             return;
         }
         match cmd {
@@ -1562,6 +1854,9 @@ impl ChatWidget {
             SlashCommand::Review => {
                 self.open_review_popup();
             }
+            SlashCommand::Steer => {
+                self.steer_from_args("");
+            }
             SlashCommand::Model => {
                 self.open_model_popup();
             }
@@ -1583,6 +1878,9 @@ impl ChatWidget {
             SlashCommand::Undo => {
                 self.app_event_tx.send(AppEvent::CodexOp(Op::Undo));
             }
+            SlashCommand::Artifacts => {
+                self.open_artifacts_popup();
+            }
             SlashCommand::Diff => {
                 self.add_diff_in_progress();
                 let tx = self.app_event_tx.clone();
@@ -1714,6 +2012,25 @@ impl ChatWidget {
         }
     }
 
+    fn steer_from_args(&mut self, args: &str) {
+        if args.is_empty() {
+            self.add_info_message(
+                "Guide the running turn without interrupting it.".to_string(),
+                Some("Usage: /steer <message> (e.g. /steer use pytest instead)".to_string()),
+            );
+            return;
+        }
+        if !self.bottom_pane.is_task_running() {
+            // Nothing to steer; send the message as a regular prompt.
+            self.submit_user_message(args.to_string().into());
+            return;
+        }
+        self.add_to_history(history_cell::new_user_prompt(args.to_string()));
+        self.submit_op(Op::Steer {
+            text: args.to_string(),
+        });
+    }
+
     fn submit_user_message(&mut self, user_message: UserMessage) {
         let UserMessage { text, image_paths } = user_message;
         if text.is_empty() && image_paths.is_empty() {
@@ -1868,6 +2185,18 @@ impl ChatWidget {
             EventMsg::ApplyPatchApprovalRequest(ev) => {
                 self.on_apply_patch_approval_request(id.unwrap_or_default(), ev)
             }
+            EventMsg::PatchConflict(ev) => self.on_patch_conflict(ev),
+            EventMsg::Question(ev) => self.on_question(ev),
+            EventMsg::ProtectedPathViolation(ev) => self.on_protected_path_violation(ev),
+            EventMsg::DependencyReport(ev) => self.on_dependency_report(ev),
+            EventMsg::ArtifactRegistered(ev) => self.on_artifact_registered(ev),
+            EventMsg::ContextRepaired(ev) => self.on_context_repaired(ev),
+            EventMsg::ContextTrimmed(ev) => self.on_context_trimmed(ev),
+            EventMsg::ResponseRegenerated(ev) => self.on_response_regenerated(ev),
+            EventMsg::WorkflowStepStarted(ev) => self.on_workflow_step_started(ev),
+            EventMsg::CandidatesProposed(ev) => self.on_candidates_proposed(ev),
+            EventMsg::CandidateSelected(ev) => self.on_candidate_selected(ev),
+            EventMsg::MemoryCandidatesProposed(ev) => self.on_memory_candidates_proposed(ev),
             EventMsg::ElicitationRequest(ev) => {
                 self.on_elicitation_request(ev);
             }
@@ -1893,7 +2222,9 @@ impl ChatWidget {
                 });
             }
             EventMsg::ShutdownComplete => self.on_shutdown_complete(),
-            EventMsg::TurnDiff(TurnDiffEvent { unified_diff }) => self.on_turn_diff(unified_diff),
+            EventMsg::TurnDiff(TurnDiffEvent { unified_diff, .. }) => {
+                self.on_turn_diff(unified_diff)
+            }
             EventMsg::DeprecationNotice(ev) => self.on_deprecation_notice(ev),
             EventMsg::BackgroundEvent(BackgroundEventEvent { message }) => {
                 self.on_background_event(message)
@@ -1914,11 +2245,23 @@ impl ChatWidget {
             EventMsg::ExitedReviewMode(review) => self.on_exited_review_mode(review),
             EventMsg::ContextCompacted(_) => self.on_agent_message("Context compacted".to_owned()),
             EventMsg::RawResponseItem(_)
+            | EventMsg::ContextManifest(_)
+            | EventMsg::SemanticIndexUpdated(_)
+            | EventMsg::SessionSummary(_)
+            | EventMsg::WorkingDirectoryChanged(_)
+            | EventMsg::TurnContextChanged(_)
             | EventMsg::ItemStarted(_)
             | EventMsg::ItemCompleted(_)
+            | EventMsg::ItemAnnotated(_)
+            | EventMsg::AnnotationRemoved(_)
+            | EventMsg::TurnProgress(_)
+            | EventMsg::TurnCompletedMetrics(_)
+            | EventMsg::FileAtTurnResponse(_)
+            | EventMsg::Stalled(_)
             | EventMsg::AgentMessageContentDelta(_)
             | EventMsg::ReasoningContentDelta(_)
-            | EventMsg::ReasoningRawContentDelta(_) => {}
+            | EventMsg::ReasoningRawContentDelta(_)
+            | EventMsg::StructuredOutputDelta(_) => {}
         }
     }
 
@@ -2143,6 +2486,7 @@ impl ChatWidget {
                 model: Some(switch_model.clone()),
                 effort: Some(Some(default_effort)),
                 summary: None,
+                verbosity: None,
             }));
             tx.send(AppEvent::UpdateModel(switch_model.clone()));
             tx.send(AppEvent::UpdateReasoningEffort(Some(default_effort)));
@@ -2363,6 +2707,7 @@ impl ChatWidget {
                 model: Some(model_for_action.clone()),
                 effort: Some(effort_for_action),
                 summary: None,
+                verbosity: None,
             }));
             tx.send(AppEvent::UpdateModel(model_for_action.clone()));
             tx.send(AppEvent::UpdateReasoningEffort(effort_for_action));
@@ -2534,6 +2879,7 @@ impl ChatWidget {
                 model: Some(model.clone()),
                 effort: Some(effort),
                 summary: None,
+                verbosity: None,
             }));
         self.app_event_tx.send(AppEvent::UpdateModel(model.clone()));
         self.app_event_tx
@@ -2551,6 +2897,110 @@ impl ChatWidget {
         );
     }
 
+    /// Open a popup listing the artifacts registered in this session.
+    pub(crate) fn open_artifacts_popup(&mut self) {
+        if self.artifacts.is_empty() {
+            self.add_info_message(
+                "No artifacts have been registered in this session.".to_string(),
+                Some(
+                    "Enable the `artifacts` feature to let Codex register its outputs.".to_string(),
+                ),
+            );
+            return;
+        }
+        let items = self
+            .artifacts
+            .iter()
+            .rev()
+            .map(|artifact| {
+                let selected = artifact.clone();
+                let actions: Vec<SelectionAction> = vec![Box::new(move |tx| {
+                    tx.send(AppEvent::OpenArtifactActions {
+                        artifact: selected.clone(),
+                    });
+                })];
+                SelectionItem {
+                    name: artifact.title.clone(),
+                    description: Some(display_path_for(&artifact.path, &self.config.cwd)),
+                    selected_description: artifact.description.clone(),
+                    actions,
+                    dismiss_on_select: true,
+                    search_value: Some(artifact.title.clone()),
+                    ..Default::default()
+                }
+            })
+            .collect();
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some("Artifacts".to_string()),
+            subtitle: Some("Files Codex registered as outputs, newest first".to_string()),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            is_searchable: true,
+            search_placeholder: Some("Type to search artifacts".to_string()),
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn open_artifact_actions_popup(&mut self, artifact: Artifact) {
+        let open_path = artifact.path.clone();
+        let save_path = artifact.path.clone();
+        let items = vec![
+            SelectionItem {
+                name: "Open".to_string(),
+                description: Some("open with the default application".to_string()),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::OpenArtifact {
+                        path: open_path.clone(),
+                    });
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+            SelectionItem {
+                name: "Save".to_string(),
+                description: Some(format!("copy into {}", self.config.cwd.display())),
+                actions: vec![Box::new(move |tx| {
+                    tx.send(AppEvent::SaveArtifact {
+                        path: save_path.clone(),
+                    });
+                })],
+                dismiss_on_select: true,
+                ..Default::default()
+            },
+        ];
+
+        self.bottom_pane.show_selection_view(SelectionViewParams {
+            title: Some(artifact.title),
+            subtitle: Some(display_path_for(&artifact.path, &self.config.cwd)),
+            footer_hint: Some(standard_popup_hint_line()),
+            items,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn open_artifact(&mut self, path: &Path) {
+        let display_path = display_path_for(path, &self.config.cwd);
+        match artifacts::open_with_default_app(path) {
+            Ok(()) => self.add_info_message(format!("Opened {display_path}"), None),
+            Err(err) => self.add_error_message(format!("Failed to open {display_path}: {err}")),
+        }
+    }
+
+    pub(crate) fn save_artifact(&mut self, path: &Path) {
+        let display_path = display_path_for(path, &self.config.cwd);
+        match artifacts::save_to_dir(path, &self.config.cwd) {
+            Ok(saved) => self.add_info_message(
+                format!(
+                    "Saved {display_path} to {}",
+                    display_path_for(&saved, &self.config.cwd)
+                ),
+                None,
+            ),
+            Err(err) => self.add_error_message(format!("Failed to save {display_path}: {err}")),
+        }
+    }
+
     /// Open a popup to choose the approvals mode (ask for approval policy + sandbox policy).
     pub(crate) fn open_approvals_popup(&mut self) {
         let current_approval = self.config.approval_policy.value();
@@ -2563,7 +3013,7 @@ impl ChatWidget {
             let name = preset.label.to_string();
             let description_text = preset.description;
             let description = Some(description_text.to_string());
-            let requires_confirmation = preset.id == "full-access"
+            let requires_confirmation = preset.id == ApprovalPresetId::Yolo
                 && !self
                     .config
                     .notices
@@ -2576,7 +3026,7 @@ impl ChatWidget {
                         preset: preset_clone.clone(),
                     });
                 })]
-            } else if preset.id == "auto" {
+            } else if matches!(preset.sandbox, SandboxPolicy::WorkspaceWrite { .. }) {
                 #[cfg(target_os = "windows")]
                 {
                     if codex_core::get_platform_sandbox().is_none() {
@@ -2641,6 +3091,7 @@ impl ChatWidget {
                 model: None,
                 effort: None,
                 summary: None,
+                verbosity: None,
             }));
             tx.send(AppEvent::UpdateAskForApprovalPolicy(approval));
             tx.send(AppEvent::UpdateSandboxPolicy(sandbox_clone));
@@ -2652,21 +3103,7 @@ impl ChatWidget {
         current_sandbox: &SandboxPolicy,
         preset: &ApprovalPreset,
     ) -> bool {
-        if current_approval != preset.approval {
-            return false;
-        }
-        matches!(
-            (&preset.sandbox, current_sandbox),
-            (SandboxPolicy::ReadOnly, SandboxPolicy::ReadOnly)
-                | (
-                    SandboxPolicy::DangerFullAccess,
-                    SandboxPolicy::DangerFullAccess
-                )
-                | (
-                    SandboxPolicy::WorkspaceWrite { .. },
-                    SandboxPolicy::WorkspaceWrite { .. }
-                )
-        )
+        ApprovalPresetId::matching(current_approval, current_sandbox) == Some(preset.id)
     }
 
     #[cfg(target_os = "windows")]
@@ -2931,7 +3368,7 @@ impl ChatWidget {
             && codex_core::get_platform_sandbox().is_none()
             && let Some(preset) = builtin_approval_presets()
                 .into_iter()
-                .find(|preset| preset.id == "auto")
+                .find(|preset| preset.id == ApprovalPresetId::FullAuto)
         {
             self.open_windows_sandbox_enable_prompt(preset);
         }
@@ -2949,11 +3386,22 @@ impl ChatWidget {
     #[allow(dead_code)]
     pub(crate) fn clear_forced_auto_mode_downgrade(&mut self) {}
 
+    /// Show the preset the current approval and sandbox policies match in the
+    /// footer.
+    fn refresh_approval_preset(&mut self) {
+        self.bottom_pane
+            .set_approval_preset(ApprovalPresetId::matching(
+                self.config.approval_policy.value(),
+                &self.config.sandbox_policy,
+            ));
+    }
+
     /// Set the approval policy in the widget's config copy.
     pub(crate) fn set_approval_policy(&mut self, policy: AskForApproval) {
         if let Err(err) = self.config.approval_policy.set(policy) {
             tracing::warn!(%err, "failed to set approval_policy on chat config");
         }
+        self.refresh_approval_preset();
     }
 
     /// Set the sandbox policy in the widget's config copy.
@@ -2963,6 +3411,7 @@ impl ChatWidget {
             || codex_core::get_platform_sandbox().is_some();
 
         self.config.sandbox_policy = policy;
+        self.refresh_approval_preset();
 
         #[cfg(target_os = "windows")]
         if should_clear_downgrade {
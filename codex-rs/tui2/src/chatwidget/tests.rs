@@ -16,6 +16,7 @@ use codex_core::protocol::AgentMessageEvent;
 use codex_core::protocol::AgentReasoningDeltaEvent;
 use codex_core::protocol::AgentReasoningEvent;
 use codex_core::protocol::ApplyPatchApprovalRequestEvent;
+use codex_core::protocol::ApprovalPresetId;
 use codex_core::protocol::BackgroundEventEvent;
 use codex_core::protocol::CreditsSnapshot;
 use codex_core::protocol::Event;
@@ -404,6 +405,7 @@ async fn make_chatwidget_manual(
         last_rendered_width: std::cell::Cell::new(None),
         feedback: codex_feedback::CodexFeedback::new(),
         current_rollout_path: None,
+        artifacts: Vec::new(),
     };
     (widget, rx, op_rx)
 }
@@ -715,6 +717,7 @@ async fn exec_approval_emits_proposed_command_and_decision_history() {
         turn_id: "turn-short".into(),
         command: vec!["bash".into(), "-lc".into(), "echo hello world".into()],
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        env: HashMap::new(),
         reason: Some(
             "this is a test reason such as one that would be produced by the model".into(),
         ),
@@ -759,6 +762,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         turn_id: "turn-multi".into(),
         command: vec!["bash".into(), "-lc".into(), "echo line1\necho line2".into()],
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        env: HashMap::new(),
         reason: Some(
             "this is a test reason such as one that would be produced by the model".into(),
         ),
@@ -811,6 +815,7 @@ async fn exec_approval_decision_truncates_multiline_and_long_commands() {
         turn_id: "turn-long".into(),
         command: vec!["bash".into(), "-lc".into(), long],
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        env: HashMap::new(),
         reason: None,
         proposed_execpolicy_amendment: None,
         parsed_cmd: vec![],
@@ -1764,8 +1769,8 @@ async fn approvals_selection_popup_snapshot() {
 async fn preset_matching_ignores_extra_writable_roots() {
     let preset = builtin_approval_presets()
         .into_iter()
-        .find(|p| p.id == "auto")
-        .expect("auto preset exists");
+        .find(|p| p.id == ApprovalPresetId::FullAuto)
+        .expect("full-auto preset exists");
     let current_sandbox = SandboxPolicy::WorkspaceWrite {
         writable_roots: vec![AbsolutePathBuf::try_from("C:\\extra").unwrap()],
         network_access: false,
@@ -1775,7 +1780,7 @@ async fn preset_matching_ignores_extra_writable_roots() {
 
     assert!(
         ChatWidget::preset_matches_current(AskForApproval::OnRequest, &current_sandbox, &preset),
-        "WorkspaceWrite with extra roots should still match the Agent preset"
+        "WorkspaceWrite with extra roots should still match the Full Auto preset"
     );
     assert!(
         !ChatWidget::preset_matches_current(AskForApproval::Never, &current_sandbox, &preset),
@@ -1789,8 +1794,8 @@ async fn full_access_confirmation_popup_snapshot() {
 
     let preset = builtin_approval_presets()
         .into_iter()
-        .find(|preset| preset.id == "full-access")
-        .expect("full access preset");
+        .find(|preset| preset.id == ApprovalPresetId::Yolo)
+        .expect("yolo preset");
     chat.open_full_access_confirmation(preset);
 
     let popup = render_bottom_popup(&chat, 80);
@@ -1804,8 +1809,8 @@ async fn windows_auto_mode_prompt_requests_enabling_sandbox_feature() {
 
     let preset = builtin_approval_presets()
         .into_iter()
-        .find(|preset| preset.id == "auto")
-        .expect("auto preset");
+        .find(|preset| preset.id == ApprovalPresetId::FullAuto)
+        .expect("full-auto preset");
     chat.open_windows_sandbox_enable_prompt(preset);
 
     let popup = render_bottom_popup(&chat, 120);
@@ -2056,6 +2061,7 @@ async fn approval_modal_exec_snapshot() {
         turn_id: "turn-approve-cmd".into(),
         command: vec!["bash".into(), "-lc".into(), "echo hello world".into()],
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        env: HashMap::new(),
         reason: Some(
             "this is a test reason such as one that would be produced by the model".into(),
         ),
@@ -2109,6 +2115,7 @@ async fn approval_modal_exec_without_reason_snapshot() {
         turn_id: "turn-approve-cmd-noreason".into(),
         command: vec!["bash".into(), "-lc".into(), "echo hello world".into()],
         cwd: std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")),
+        env: HashMap::new(),
         reason: None,
         proposed_execpolicy_amendment: Some(ExecPolicyAmendment::new(vec![
             "echo".into(),
@@ -2325,6 +2332,7 @@ async fn status_widget_and_approval_modal_snapshot() {
         turn_id: "turn-approve-exec".into(),
         command: vec!["echo".into(), "hello world".into()],
         cwd: PathBuf::from("/tmp"),
+        env: HashMap::new(),
         reason: Some(
             "this is a test reason such as one that would be produced by the model".into(),
         ),
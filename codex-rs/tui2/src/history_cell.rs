@@ -26,6 +26,8 @@ use base64::Engine;
 use codex_common::format_env_display::format_env_display;
 use codex_core::config::Config;
 use codex_core::config::types::McpServerTransportConfig;
+use codex_core::protocol::AddedDependency;
+use codex_core::protocol::Artifact;
 use codex_core::protocol::FileChange;
 use codex_core::protocol::McpAuthStatus;
 use codex_core::protocol::McpInvocation;
@@ -1408,6 +1410,22 @@ pub(crate) fn new_patch_apply_failure(stderr: String) -> PlainHistoryCell {
     PlainHistoryCell { lines }
 }
 
+pub(crate) fn new_artifact_registered(artifact: &Artifact, cwd: &Path) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec![
+        vec![
+            "• ".dim(),
+            "Artifact ".bold(),
+            artifact.title.clone().into(),
+        ]
+        .into(),
+        vec!["  └ ".dim(), display_path_for(&artifact.path, cwd).dim()].into(),
+    ];
+    if let Some(description) = &artifact.description {
+        lines.push(vec!["    ".into(), description.clone().dim()].into());
+    }
+    PlainHistoryCell { lines }
+}
+
 pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> PlainHistoryCell {
     let display_path = display_path_for(&path, cwd);
 
@@ -1419,6 +1437,47 @@ pub(crate) fn new_view_image_tool_call(path: PathBuf, cwd: &Path) -> PlainHistor
     PlainHistoryCell { lines }
 }
 
+/// Dependencies the model added this turn, with their licenses and any
+/// known advisories.
+pub(crate) fn new_dependency_report(
+    dependencies: &[AddedDependency],
+    cwd: &Path,
+) -> PlainHistoryCell {
+    let mut lines: Vec<Line<'static>> = vec![vec!["• ".dim(), "Added dependencies".bold()].into()];
+    for (idx, dependency) in dependencies.iter().enumerate() {
+        let prefix = if idx == 0 { "  └ " } else { "    " };
+        let version = dependency
+            .resolved_version
+            .as_deref()
+            .or(dependency.requirement.as_deref())
+            .map(|version| format!(" {version}"))
+            .unwrap_or_default();
+        let details = match (&dependency.lookup_error, &dependency.license) {
+            (Some(err), _) => format!("lookup failed: {err}"),
+            (None, Some(license)) => license.clone(),
+            (None, None) => "no license listed".to_string(),
+        };
+        lines.push(
+            vec![
+                prefix.dim(),
+                dependency.name.clone().bold(),
+                version.into(),
+                format!(" ({}) · {details} · ", dependency.ecosystem.registry()).dim(),
+                display_path_for(&dependency.manifest, cwd).dim(),
+            ]
+            .into(),
+        );
+        for advisory in &dependency.advisories {
+            let text = match &advisory.summary {
+                Some(summary) => format!("{}: {summary}", advisory.id),
+                None => advisory.id.clone(),
+            };
+            lines.push(vec!["      ".into(), format!("■ {text}").red()].into());
+        }
+    }
+    PlainHistoryCell { lines }
+}
+
 pub(crate) fn new_reasoning_summary_block(
     full_reasoning_buffer: String,
     reasoning_summary_format: ReasoningSummaryFormat,
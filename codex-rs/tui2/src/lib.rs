@@ -36,6 +36,7 @@ mod app;
 mod app_backtrack;
 mod app_event;
 mod app_event_sender;
+mod artifacts;
 mod ascii_animation;
 mod bottom_pane;
 mod chatwidget;
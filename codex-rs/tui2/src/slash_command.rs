@@ -16,12 +16,14 @@ pub enum SlashCommand {
     Approvals,
     Skills,
     Review,
+    Steer,
     New,
     Resume,
     Init,
     Compact,
     Undo,
     Diff,
+    Artifacts,
     Mention,
     Status,
     Mcp,
@@ -42,10 +44,14 @@ impl SlashCommand {
             SlashCommand::Init => "create an AGENTS.md file with instructions for Codex",
             SlashCommand::Compact => "summarize conversation to prevent hitting the context limit",
             SlashCommand::Review => "review my current changes and find issues",
+            SlashCommand::Steer => {
+                "guide the running turn without interrupting it (e.g. /steer use pytest)"
+            }
             SlashCommand::Resume => "resume a saved chat",
             SlashCommand::Undo => "ask Codex to undo a turn",
             SlashCommand::Quit | SlashCommand::Exit => "exit Codex",
             SlashCommand::Diff => "show git diff (including untracked files)",
+            SlashCommand::Artifacts => "open or save files Codex registered as outputs",
             SlashCommand::Mention => "mention a file",
             SlashCommand::Skills => "use skills to improve how Codex performs specific tasks",
             SlashCommand::Status => "show current session configuration and token usage",
@@ -77,11 +83,13 @@ impl SlashCommand {
             | SlashCommand::Review
             | SlashCommand::Logout => false,
             SlashCommand::Diff
+            | SlashCommand::Artifacts
             | SlashCommand::Mention
             | SlashCommand::Skills
             | SlashCommand::Status
             | SlashCommand::Mcp
             | SlashCommand::Feedback
+            | SlashCommand::Steer
             | SlashCommand::Quit
             | SlashCommand::Exit => true,
             SlashCommand::Rollout => true,
@@ -89,6 +97,11 @@ impl SlashCommand {
         }
     }
 
+    /// Whether this command takes inline arguments (e.g. `/steer use pytest`).
+    pub fn accepts_args(self) -> bool {
+        matches!(self, SlashCommand::Steer)
+    }
+
     fn is_visible(self) -> bool {
         match self {
             SlashCommand::Rollout | SlashCommand::TestApproval => cfg!(debug_assertions),
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::path::Path;
 use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
 use std::sync::Arc;
 use std::sync::Mutex as StdMutex;
 use std::time::Duration;
@@ -38,6 +39,7 @@ pub struct ExecCommandSession {
     wait_handle: StdMutex<Option<JoinHandle<()>>>,
     exit_status: Arc<AtomicBool>,
     exit_code: Arc<StdMutex<Option<i32>>>,
+    output_bytes: Arc<AtomicU64>,
     // PtyPair must be preserved because the process will receive Control+C if the
     // slave is closed
     _pair: StdMutex<PtyPairWrapper>,
@@ -61,6 +63,7 @@ impl ExecCommandSession {
         wait_handle: JoinHandle<()>,
         exit_status: Arc<AtomicBool>,
         exit_code: Arc<StdMutex<Option<i32>>>,
+        output_bytes: Arc<AtomicU64>,
         pair: PtyPairWrapper,
     ) -> (Self, broadcast::Receiver<Vec<u8>>) {
         (
@@ -73,6 +76,7 @@ impl ExecCommandSession {
                 wait_handle: StdMutex::new(Some(wait_handle)),
                 exit_status,
                 exit_code,
+                output_bytes,
                 _pair: StdMutex::new(pair),
             },
             initial_output_rx,
@@ -95,6 +99,12 @@ impl ExecCommandSession {
         self.exit_code.lock().ok().and_then(|guard| *guard)
     }
 
+    /// Bytes the process has written so far, including those a lagging
+    /// receiver missed.
+    pub fn output_bytes(&self) -> u64 {
+        self.output_bytes.load(std::sync::atomic::Ordering::SeqCst)
+    }
+
     pub fn terminate(&self) {
         if let Ok(mut killer_opt) = self.killer.lock() {
             if let Some(mut killer) = killer_opt.take() {
@@ -191,12 +201,15 @@ pub async fn spawn_pty_process(
 
     let mut reader = pair.master.try_clone_reader()?;
     let output_tx_clone = output_tx.clone();
+    let output_bytes = Arc::new(AtomicU64::new(0));
+    let reader_output_bytes = Arc::clone(&output_bytes);
     let reader_handle: JoinHandle<()> = tokio::task::spawn_blocking(move || {
         let mut buf = [0u8; 8_192];
         loop {
             match reader.read(&mut buf) {
                 Ok(0) => break,
                 Ok(n) => {
+                    reader_output_bytes.fetch_add(n as u64, std::sync::atomic::Ordering::SeqCst);
                     let _ = output_tx_clone.send(buf[..n].to_vec());
                 }
                 Err(ref e) if e.kind() == ErrorKind::Interrupted => continue,
@@ -260,6 +273,7 @@ pub async fn spawn_pty_process(
         wait_handle,
         exit_status,
         exit_code,
+        output_bytes,
         pair,
     );
 
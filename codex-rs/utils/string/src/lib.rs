@@ -36,3 +36,104 @@ pub fn take_last_bytes_at_char_boundary(s: &str, maxb: usize) -> &str {
     }
     &s[start..]
 }
+
+// Keep only what a terminal would finally show on each line when output is
+// redrawn with carriage returns (progress bars, spinners). `\r\n` line endings
+// become `\n`.
+pub fn collapse_carriage_returns(s: &str) -> String {
+    if !s.contains('\r') {
+        return s.to_string();
+    }
+    s.split('\n')
+        .map(|line| {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            line.rsplit('\r').next().unwrap_or(line)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// Remove ANSI escape sequences (CSI such as colors and cursor movement, OSC
+// such as hyperlinks and window titles, and two-byte escapes) along with
+// other non-printing control characters except newlines and tabs.
+pub fn strip_ansi_escapes(s: &str) -> String {
+    enum State {
+        Text,
+        Escape,
+        Csi,
+        Charset,
+        Osc,
+        OscEscape,
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut state = State::Text;
+    for ch in s.chars() {
+        state = match state {
+            State::Text => match ch {
+                '\u{1b}' => State::Escape,
+                '\u{9b}' => State::Csi,
+                '\n' | '\t' => {
+                    out.push(ch);
+                    State::Text
+                }
+                c if c.is_control() => State::Text,
+                c => {
+                    out.push(c);
+                    State::Text
+                }
+            },
+            State::Escape => match ch {
+                '[' => State::Csi,
+                ']' => State::Osc,
+                // Charset selection is followed by one designator byte.
+                '(' | ')' | '*' | '+' => State::Charset,
+                _ => State::Text,
+            },
+            State::Charset => State::Text,
+            // Parameter and intermediate bytes continue the sequence; a final
+            // byte in `@`..=`~` ends it.
+            State::Csi => match ch {
+                '@'..='~' => State::Text,
+                _ => State::Csi,
+            },
+            // OSC ends with BEL or ST (`ESC \`).
+            State::Osc => match ch {
+                '\u{7}' => State::Text,
+                '\u{1b}' => State::OscEscape,
+                _ => State::Osc,
+            },
+            State::OscEscape => match ch {
+                '\\' => State::Text,
+                _ => State::Osc,
+            },
+        };
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn collapses_progress_lines() {
+        assert_eq!(
+            collapse_carriage_returns("Downloading 10%\rDownloading 100%\r\ndone\r\n"),
+            "Downloading 100%\ndone\n"
+        );
+        assert_eq!(collapse_carriage_returns("plain\n"), "plain\n");
+    }
+
+    #[test]
+    fn strips_color_and_hyperlinks() {
+        assert_eq!(
+            strip_ansi_escapes(
+                "\u{1b}[1;31merror\u{1b}[0m: see \u{1b}]8;;https://example.com\u{7}docs\u{1b}]8;;\u{1b}\\\n"
+            ),
+            "error: see docs\n"
+        );
+        assert_eq!(strip_ansi_escapes("\u{1b}[2K\u{1b}(Bok\tdone"), "ok\tdone");
+    }
+}
@@ -57,6 +57,17 @@ export type ItemCompletedEvent = {
   item: ThreadItem;
 };
 
+/**
+ * With an output schema, a value of the JSON response that finished streaming
+ * before the agent message is complete.
+ */
+export type OutputPartialEvent = {
+  type: "output.partial";
+  /** JSON Pointer of the value in the response, e.g. `/findings/0`. */
+  pointer: string;
+  value: unknown;
+};
+
 /** Fatal error emitted by the stream. */
 export type ThreadError = {
   message: string;
@@ -77,4 +88,5 @@ export type ThreadEvent =
   | ItemStartedEvent
   | ItemUpdatedEvent
   | ItemCompletedEvent
+  | OutputPartialEvent
   | ThreadErrorEvent;